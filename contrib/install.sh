@@ -172,13 +172,13 @@ check_source_deps() {
         echo "  curl --proto '=https' --tlsv1.2 -sSf https://sh.rustup.rs | sh"
         echo ""
         echo "To install dependencies on Debian/Ubuntu:"
-        echo "  sudo apt install git build-essential pkg-config libpipewire-0.3-dev libdbus-1-dev clang libclang-dev"
+        echo "  sudo apt install git build-essential pkg-config libpipewire-0.3-dev libdbus-1-dev libasound2-dev clang libclang-dev"
         echo ""
         echo "To install dependencies on Fedora:"
-        echo "  sudo dnf install git rust cargo pkgconf-pkg-config pipewire-devel dbus-devel clang clang-devel"
+        echo "  sudo dnf install git rust cargo pkgconf-pkg-config pipewire-devel dbus-devel alsa-lib-devel clang clang-devel"
         echo ""
         echo "To install dependencies on Arch Linux:"
-        echo "  sudo pacman -S git rust pipewire clang"
+        echo "  sudo pacman -S git rust pipewire alsa-lib clang"
         echo ""
         die "Please install missing dependencies and try again."
     fi
@@ -193,6 +193,11 @@ check_source_deps() {
         die "D-Bus development libraries not found. Install libdbus-1-dev (Debian/Ubuntu) or dbus-devel (Fedora)."
     fi
 
+    # Check for ALSA development libraries (MIDI controllers)
+    if ! pkg-config --exists alsa 2>/dev/null; then
+        die "ALSA development libraries not found. Install libasound2-dev (Debian/Ubuntu) or alsa-lib-devel (Fedora) or alsa-lib (Arch)."
+    fi
+
     # Check Rust version
     local rust_version
     rust_version=$(rustc --version | grep -oE '[0-9]+\.[0-9]+' | head -1)
@@ -215,6 +220,7 @@ try_install_deps() {
         check_command git || missing="$missing git"
         check_command clang || missing="$missing clang"
         pkg-config --exists libpipewire-0.3 2>/dev/null || missing="$missing pipewire"
+        pkg-config --exists alsa 2>/dev/null || missing="$missing alsa-lib"
 
         if [[ -n "$missing" ]]; then
             info "Installing dependencies:$missing"
@@ -231,6 +237,7 @@ try_install_deps() {
         check_command clang || missing="$missing clang libclang-dev"
         pkg-config --exists libpipewire-0.3 2>/dev/null || missing="$missing libpipewire-0.3-dev"
         pkg-config --exists dbus-1 2>/dev/null || missing="$missing libdbus-1-dev"
+        pkg-config --exists alsa 2>/dev/null || missing="$missing libasound2-dev"
 
         if [[ -n "$missing" ]]; then
             info "Installing dependencies:$missing"
@@ -247,6 +254,7 @@ try_install_deps() {
         check_command clang || missing="$missing clang clang-devel"
         pkg-config --exists libpipewire-0.3 2>/dev/null || missing="$missing pipewire-devel"
         pkg-config --exists dbus-1 2>/dev/null || missing="$missing dbus-devel"
+        pkg-config --exists alsa 2>/dev/null || missing="$missing alsa-lib-devel"
 
         if [[ -n "$missing" ]]; then
             info "Installing dependencies:$missing"
@@ -262,6 +270,7 @@ try_install_deps() {
         check_command clang || missing="$missing clang llvm-devel"
         pkg-config --exists libpipewire-0.3 2>/dev/null || missing="$missing pipewire-devel"
         pkg-config --exists dbus-1 2>/dev/null || missing="$missing dbus-1-devel"
+        pkg-config --exists alsa 2>/dev/null || missing="$missing alsa-devel"
 
         if [[ -n "$missing" ]]; then
             info "Installing dependencies:$missing"
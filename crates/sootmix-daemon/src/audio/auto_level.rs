@@ -0,0 +1,121 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Automatic loudness normalization ("auto level").
+//!
+//! Auto level is stepped on the [control-rate tick](super::ducking#control-rate).
+//! Each tick reads the short-term loudness of the channel's meter, which taps
+//! the signal before the fader, works out the make-up gain that would bring
+//! it to the target, and moves the applied gain toward it at a fixed rate in
+//! dB per second. The slow rate averages out the
+//! short-term window, so the gain follows the programme rather than each
+//! word or explosion. While the channel is quieter than [`GATE_LUFS`]
+//! (pauses, silence between tracks) the gain is held, so silence is not
+//! boosted.
+
+use crate::config::AutoLevelConfig;
+
+/// Short-term loudness below which the gain is held, in LUFS.
+pub const GATE_LUFS: f32 = -50.0;
+
+/// Make-up gain state for one channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AutoLevel {
+    /// Current gain in dB (positive = boost).
+    gain_db: f32,
+}
+
+impl AutoLevel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current gain in dB.
+    pub fn gain_db(&self) -> f32 {
+        self.gain_db
+    }
+
+    /// Advance by `dt` seconds given the channel's short-term loudness in
+    /// LUFS. A disabled config drops the gain to 0 dB at once.
+    ///
+    /// Returns the new gain in dB.
+    pub fn update(&mut self, config: &AutoLevelConfig, short_term_lufs: f32, dt: f32) -> f32 {
+        if !config.enabled {
+            self.gain_db = 0.0;
+            return 0.0;
+        }
+
+        let (min, max) = (-config.max_cut_db.max(0.0), config.max_boost_db.max(0.0));
+        let target = if short_term_lufs >= GATE_LUFS {
+            (config.target_lufs - short_term_lufs).clamp(min, max)
+        } else {
+            // Hold, but follow limits that were tightened meanwhile
+            self.gain_db.clamp(min, max)
+        };
+
+        let step = config.speed_db_per_sec.max(0.0) * dt;
+        self.gain_db = if self.gain_db < target {
+            (self.gain_db + step).min(target)
+        } else {
+            (self.gain_db - step).max(target)
+        };
+        self.gain_db
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AutoLevelConfig {
+        AutoLevelConfig {
+            enabled: true,
+            target_lufs: -18.0,
+            max_boost_db: 12.0,
+            max_cut_db: 6.0,
+            speed_db_per_sec: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_gain_moves_toward_target_at_speed() {
+        let config = config();
+        let mut level = AutoLevel::new();
+
+        // -24 LUFS source needs +6 dB, reached in 3 s
+        assert_eq!(level.update(&config, -24.0, 1.0), 2.0);
+        assert_eq!(level.update(&config, -24.0, 1.0), 4.0);
+        assert_eq!(level.update(&config, -24.0, 2.0), 6.0);
+        assert_eq!(level.update(&config, -24.0, 1.0), 6.0);
+    }
+
+    #[test]
+    fn test_gain_is_limited() {
+        let config = config();
+        let mut level = AutoLevel::new();
+
+        assert_eq!(level.update(&config, -40.0, 100.0), 12.0);
+        assert_eq!(level.update(&config, 0.0, 100.0), -6.0);
+    }
+
+    #[test]
+    fn test_silence_holds_gain() {
+        let config = config();
+        let mut level = AutoLevel::new();
+        level.update(&config, -24.0, 10.0);
+
+        assert_eq!(level.update(&config, -70.0, 10.0), 6.0);
+    }
+
+    #[test]
+    fn test_disabled_resets_gain() {
+        let mut config = config();
+        let mut level = AutoLevel::new();
+        level.update(&config, -24.0, 10.0);
+        config.enabled = false;
+
+        assert_eq!(level.update(&config, -24.0, 0.1), 0.0);
+        assert_eq!(level.gain_db(), 0.0);
+    }
+}
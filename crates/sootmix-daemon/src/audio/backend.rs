@@ -0,0 +1,56 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Audio graph backend abstraction.
+//!
+//! The service drives the graph only through [`PwCommand`]s and learns about
+//! it only through [`PwEvent`]s. A [`GraphBackend`] carries the commands:
+//! normally the PipeWire thread, in tests the in-memory graph of
+//! `fake_graph`. Backends are started by a [`BackendFactory`], which the
+//! service calls again to reconnect after a `Disconnected` event.
+
+use crate::audio::pipewire_thread::{PwCommand, PwError, PwEvent, PwThread};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Something that applies [`PwCommand`]s to an audio graph and reports the
+/// graph's changes as [`PwEvent`]s.
+pub trait GraphBackend: Send {
+    /// Send a command to the graph.
+    fn send(&self, cmd: PwCommand) -> Result<(), PwError>;
+
+    /// Give the graph `delay` to act on the commands sent so far. The
+    /// PipeWire thread applies them in its own time, so this sleeps; a
+    /// backend that applies commands as they are sent returns at once.
+    fn wait(&self, delay: Duration);
+
+    /// Stop the backend, waiting for it to finish.
+    fn shutdown(self: Box<Self>);
+}
+
+/// Starts a backend that reports to the given event sender.
+pub type BackendFactory =
+    Box<dyn FnMut(mpsc::Sender<PwEvent>) -> Result<Box<dyn GraphBackend>, PwError> + Send>;
+
+impl GraphBackend for PwThread {
+    fn send(&self, cmd: PwCommand) -> Result<(), PwError> {
+        PwThread::send(self, cmd)
+    }
+
+    fn wait(&self, delay: Duration) {
+        std::thread::sleep(delay);
+    }
+
+    fn shutdown(self: Box<Self>) {
+        PwThread::shutdown(*self)
+    }
+}
+
+/// Factory for the real PipeWire thread.
+pub fn pipewire_backend() -> BackendFactory {
+    Box::new(|event_tx| {
+        let backend: Box<dyn GraphBackend> = Box::new(PwThread::spawn(event_tx)?);
+        Ok(backend)
+    })
+}
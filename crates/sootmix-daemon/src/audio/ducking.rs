@@ -0,0 +1,141 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Sidechain ducking envelope.
+//!
+//! # Control rate
+//!
+//! Ducking is the first of the level controls that run outside the audio
+//! thread. The daemon's meter polling loop in `main.rs` ticks about 30
+//! times a second and steps each of them by the time since the last tick;
+//! every level they settle on becomes a node volume write, so steps too
+//! small to hear are skipped.
+//!
+//! Each ducking tick reads the trigger channel's `AtomicMeterLevels`,
+//! advances one [`DuckEnvelope`] per relationship, and folds the resulting
+//! gain reduction into the ducked channels' node volumes. Ramps are linear
+//! in dB, so `attack_ms`/`release_ms` are the times for a full-depth
+//! transition.
+
+use crate::config::DuckingConfig;
+
+/// Gain reduction state for one ducking relationship.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DuckEnvelope {
+    /// Current attenuation in dB (0.0 = none, up to `amount_db`).
+    reduction_db: f32,
+    /// Seconds left before release starts after the trigger went quiet.
+    hold_remaining: f32,
+}
+
+impl DuckEnvelope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current attenuation in dB.
+    pub fn reduction_db(&self) -> f32 {
+        self.reduction_db
+    }
+
+    /// Advance the envelope by `dt` seconds given the trigger level in dB.
+    ///
+    /// Returns the new attenuation in dB.
+    pub fn update(&mut self, config: &DuckingConfig, trigger_db: f32, dt: f32) -> f32 {
+        let amount = config.amount_db.max(0.0);
+
+        if trigger_db >= config.threshold_db {
+            self.hold_remaining = config.hold_ms.max(0.0) / 1000.0;
+            self.reduction_db = ramp(self.reduction_db, amount, amount, config.attack_ms, dt);
+        } else if self.hold_remaining > 0.0 {
+            self.hold_remaining = (self.hold_remaining - dt).max(0.0);
+            // Follow a shallower `amount` immediately while holding.
+            self.reduction_db = self.reduction_db.min(amount);
+        } else {
+            self.reduction_db = ramp(self.reduction_db, 0.0, amount, config.release_ms, dt);
+        }
+
+        self.reduction_db
+    }
+}
+
+/// Move `current` toward `target` at `depth` dB per `time_ms`.
+fn ramp(current: f32, target: f32, depth: f32, time_ms: f32, dt: f32) -> f32 {
+    if time_ms <= 0.0 || depth <= 0.0 {
+        return target;
+    }
+    let step = depth * dt * 1000.0 / time_ms;
+    if current < target {
+        (current + step).min(target)
+    } else {
+        (current - step).max(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn config() -> DuckingConfig {
+        DuckingConfig {
+            id: Uuid::new_v4(),
+            enabled: true,
+            trigger_channel: Uuid::new_v4(),
+            ducked_channels: vec![Uuid::new_v4()],
+            threshold_db: -40.0,
+            amount_db: 12.0,
+            attack_ms: 125.0,
+            hold_ms: 250.0,
+            release_ms: 500.0,
+        }
+    }
+
+    #[test]
+    fn test_attack_ramps_to_full_depth() {
+        let config = config();
+        let mut env = DuckEnvelope::new();
+
+        assert_eq!(env.update(&config, -20.0, 0.0625), 6.0);
+        assert_eq!(env.update(&config, -20.0, 0.0625), 12.0);
+        assert_eq!(env.update(&config, -20.0, 0.0625), 12.0);
+    }
+
+    #[test]
+    fn test_hold_then_release() {
+        let config = config();
+        let mut env = DuckEnvelope::new();
+        env.update(&config, 0.0, 1.0);
+
+        // Quiet trigger: held for 250 ms, then released over 500 ms.
+        assert_eq!(env.update(&config, -60.0, 0.125), 12.0);
+        assert_eq!(env.update(&config, -60.0, 0.125), 12.0);
+        assert_eq!(env.update(&config, -60.0, 0.25), 6.0);
+        assert_eq!(env.update(&config, -60.0, 0.25), 0.0);
+        assert_eq!(env.update(&config, -60.0, 0.25), 0.0);
+    }
+
+    #[test]
+    fn test_retrigger_restarts_hold() {
+        let config = config();
+        let mut env = DuckEnvelope::new();
+        env.update(&config, 0.0, 1.0);
+        env.update(&config, -60.0, 0.125);
+        env.update(&config, 0.0, 0.0625);
+
+        assert_eq!(env.update(&config, -60.0, 0.125), 12.0);
+    }
+
+    #[test]
+    fn test_zero_times_jump() {
+        let mut config = config();
+        config.attack_ms = 0.0;
+        config.hold_ms = 0.0;
+        config.release_ms = 0.0;
+        let mut env = DuckEnvelope::new();
+
+        assert_eq!(env.update(&config, 0.0, 0.03), 12.0);
+        assert_eq!(env.update(&config, -60.0, 0.03), 0.0);
+    }
+}
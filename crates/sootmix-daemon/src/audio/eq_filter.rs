@@ -0,0 +1,410 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Per-channel EQ using PipeWire filter-chain.
+//!
+//! Each EQ is a filter-chain process exposing two nodes:
+//! - `sootmix.eq.<name>` (Audio/Sink) fed by the channel's loopback output
+//! - `sootmix.eq.<name>.output` (Stream/Output/Audio) routed to the output device
+//!
+//! Routing between those nodes is done by the service, the same way it routes
+//! loopback outputs. Band parameters are updated live by the PipeWire thread
+//! setting the sink's `Props` (see [`eq_params`]), so changing presets does
+//! not interrupt audio, as long as the band types stay the same; a different
+//! layout needs a new filter.
+//!
+//! The filter-chain is stereo; surround channels can't enable the EQ (see
+//! `ChannelState::fits_filters`).
+//!
+//! Every band is one builtin biquad, except high- and low-pass bands steeper
+//! than 12 dB/octave, which are a cascade of them (see
+//! [`EqBandInfo::stage_qs`]).
+
+use crate::config::EqBand;
+use parking_lot::Mutex;
+use sootmix_ipc::{EqBandInfo, EqBandType};
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Child, Command};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum EqFilterError {
+    #[error("Failed to spawn filter-chain: {0}")]
+    SpawnFailed(#[from] std::io::Error),
+    #[error("Failed to create config file: {0}")]
+    ConfigFailed(String),
+}
+
+/// Node names of a running EQ filter.
+#[derive(Debug, Clone)]
+pub struct EqFilterNodes {
+    /// The Audio/Sink node that receives the channel's audio.
+    pub sink_node_name: String,
+    /// The output stream that carries the equalized audio.
+    pub output_node_name: String,
+}
+
+/// Info about a running EQ filter instance.
+struct EqFilterInstance {
+    child: Child,
+    #[allow(dead_code)]
+    config_file: NamedTempFile,
+}
+
+/// Track running filter processes for cleanup.
+static EQ_FILTER_PROCESSES: Mutex<Option<HashMap<Uuid, EqFilterInstance>>> = Mutex::new(None);
+
+fn get_processes() -> parking_lot::MutexGuard<'static, Option<HashMap<Uuid, EqFilterInstance>>> {
+    EQ_FILTER_PROCESSES.lock()
+}
+
+fn ensure_processes_map() {
+    let mut guard = get_processes();
+    if guard.is_none() {
+        *guard = Some(HashMap::new());
+    }
+}
+
+/// One biquad of the filter graph.
+#[derive(Debug, Clone, PartialEq)]
+struct Stage {
+    name: String,
+    label: &'static str,
+    freq: f32,
+    q: f64,
+    gain: f32,
+}
+
+/// filter-chain builtin implementing a band type.
+fn filter_label(kind: EqBandType) -> &'static str {
+    match kind {
+        EqBandType::Peaking => "bq_peaking",
+        EqBandType::LowShelf => "bq_lowshelf",
+        EqBandType::HighShelf => "bq_highshelf",
+        EqBandType::HighPass => "bq_highpass",
+        EqBandType::LowPass => "bq_lowpass",
+        EqBandType::Notch => "bq_notch",
+        EqBandType::BandPass => "bq_bandpass",
+    }
+}
+
+/// The biquads for `bands`, in series. An EQ without bands still gets a
+/// flat stage so the graph has an input and output.
+fn eq_stages(bands: &[EqBand]) -> Vec<Stage> {
+    if bands.is_empty() {
+        return vec![Stage {
+            name: "eq_band_0_0".to_string(),
+            label: filter_label(EqBandType::Peaking),
+            freq: 1000.0,
+            q: 1.0,
+            gain: 0.0,
+        }];
+    }
+
+    bands
+        .iter()
+        .enumerate()
+        .flat_map(|(i, band)| {
+            let gain = if band.kind.has_gain() { band.gain } else { 0.0 };
+            EqBandInfo::from(band)
+                .stage_qs()
+                .into_iter()
+                .enumerate()
+                .map(move |(s, q)| Stage {
+                    name: format!("eq_band_{i}_{s}"),
+                    label: filter_label(band.kind),
+                    freq: band.freq,
+                    q,
+                    gain,
+                })
+        })
+        .collect()
+}
+
+/// Whether a filter running `current` can be switched to `next` by setting
+/// its [`eq_params`], i.e. both have the same biquads.
+pub fn same_layout(current: &[EqBand], next: &[EqBand]) -> bool {
+    let labels = |bands: &[EqBand]| -> Vec<(String, &'static str)> {
+        eq_stages(bands)
+            .into_iter()
+            .map(|stage| (stage.name, stage.label))
+            .collect()
+    };
+    labels(current) == labels(next)
+}
+
+/// Generate a filter-chain config running `bands` in series.
+fn generate_eq_config(channel_name: &str, bands: &[EqBand]) -> String {
+    let sink_node_name = format!("sootmix.eq.{}", channel_name);
+    let output_node_name = format!("sootmix.eq.{}.output", channel_name);
+    let stages = eq_stages(bands);
+
+    let mut config = String::new();
+
+    config.push_str(&format!(
+        r#"context.properties = {{
+    core.daemon = false
+    core.name = "sootmix-eq-{channel_name}"
+}}
+
+context.spa-libs = {{
+    audio.convert.* = audioconvert/libspa-audioconvert
+    support.*       = support/libspa-support
+}}
+
+context.modules = [
+    {{ name = libpipewire-module-rt
+        args = {{
+            nice.level = -11
+        }}
+        flags = [ ifexists nofail ]
+    }}
+    {{ name = libpipewire-module-protocol-native }}
+    {{ name = libpipewire-module-client-node }}
+    {{ name = libpipewire-module-adapter }}
+    {{ name = libpipewire-module-filter-chain
+        args = {{
+            node.name = "{sink_node_name}"
+            node.description = "SootMix EQ - {channel_name}"
+            media.name = "SootMix EQ"
+            filter.graph = {{
+                nodes = [
+"#
+    ));
+
+    for stage in &stages {
+        config.push_str(&format!(
+            r#"                    {{
+                        type = builtin
+                        name = {name}
+                        label = {label}
+                        control = {{ "Freq" = {freq:.1} "Q" = {q:.3} "Gain" = {gain:.2} }}
+                    }}
+"#,
+            name = stage.name,
+            label = stage.label,
+            freq = stage.freq,
+            q = stage.q,
+            gain = stage.gain
+        ));
+    }
+
+    config.push_str(
+        r#"                ]
+                links = [
+"#,
+    );
+
+    // Stages run in series: first -> ... -> last
+    for pair in stages.windows(2) {
+        config.push_str(&format!(
+            r#"                    {{ output = "{from}:Out" input = "{to}:In" }}
+"#,
+            from = pair[0].name,
+            to = pair[1].name
+        ));
+    }
+
+    let first = &stages[0].name;
+    let last = &stages[stages.len() - 1].name;
+    config.push_str(&format!(
+        r#"                ]
+                inputs = [ "{first}:In" ]
+                outputs = [ "{last}:Out" ]
+            }}
+            capture.props = {{
+                media.class = Audio/Sink
+                node.name = "{sink_node_name}"
+                node.description = "SootMix EQ - {channel_name}"
+                audio.position = [ FL FR ]
+            }}
+            playback.props = {{
+                media.class = Stream/Output/Audio
+                node.name = "{output_node_name}"
+                node.autoconnect = false
+                node.dont-move = true
+                audio.position = [ FL FR ]
+            }}
+        }}
+    }}
+]
+"#
+    ));
+
+    config
+}
+
+/// Control values to set in a running filter's `Props` `params` to apply
+/// `bands`, as `(control, value)` pairs.
+pub fn eq_params(bands: &[EqBand]) -> Vec<(String, f32)> {
+    eq_stages(bands)
+        .iter()
+        .flat_map(|stage| {
+            [
+                (format!("{}:Freq", stage.name), stage.freq),
+                (format!("{}:Q", stage.name), stage.q as f32),
+                (format!("{}:Gain", stage.name), stage.gain),
+            ]
+        })
+        .collect()
+}
+
+/// Response of `bands` at `freq` Hz in dB, for audio at `sample_rate`.
+pub fn eq_response_db(bands: &[EqBand], freq: f64, sample_rate: f64) -> f64 {
+    let bands: Vec<EqBandInfo> = bands.iter().map(EqBandInfo::from).collect();
+    sootmix_ipc::eq_response_db(&bands, freq, sample_rate)
+}
+
+/// Create an EQ filter for an output channel.
+///
+/// Any EQ already tracked for the channel is destroyed first so the node
+/// names don't collide. Returns the names of the nodes it will register; the
+/// caller is responsible for routing the channel's loopback output into the
+/// sink and the output stream to a device once they show up.
+pub fn create_eq_filter(
+    channel_id: Uuid,
+    channel_name: &str,
+    bands: &[EqBand],
+) -> Result<EqFilterNodes, EqFilterError> {
+    ensure_processes_map();
+    destroy_eq_filter(channel_id)?;
+
+    // Sanitize channel name
+    let safe_name = channel_name
+        .chars()
+        .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+        .collect::<String>();
+
+    let config_content = generate_eq_config(&safe_name, bands);
+    debug!("Generated EQ filter config:\n{}", config_content);
+
+    // Write to temp file
+    let mut config_file = tempfile::Builder::new()
+        .prefix("sootmix-eq-")
+        .suffix(".conf")
+        .tempfile()
+        .map_err(|e| EqFilterError::ConfigFailed(e.to_string()))?;
+    config_file
+        .write_all(config_content.as_bytes())
+        .map_err(|e| EqFilterError::ConfigFailed(e.to_string()))?;
+
+    let config_path = config_file.path().to_string_lossy().to_string();
+    info!(
+        "Creating EQ filter for channel '{}' ({} bands) with config: {}",
+        channel_name,
+        bands.len(),
+        config_path
+    );
+
+    // Spawn pipewire with the filter-chain config
+    let child = Command::new("pipewire")
+        .arg("-c")
+        .arg(&config_path)
+        .spawn()?;
+    debug!("EQ filter spawned with PID: {}", child.id());
+
+    if let Some(ref mut map) = *get_processes() {
+        map.insert(channel_id, EqFilterInstance { child, config_file });
+    }
+
+    Ok(EqFilterNodes {
+        sink_node_name: format!("sootmix.eq.{}", safe_name),
+        output_node_name: format!("sootmix.eq.{}.output", safe_name),
+    })
+}
+
+/// Destroy a channel's EQ filter. Does nothing if the channel has none.
+pub fn destroy_eq_filter(channel_id: Uuid) -> Result<(), EqFilterError> {
+    if let Some(ref mut map) = *get_processes() {
+        if let Some(mut instance) = map.remove(&channel_id) {
+            info!("Destroying EQ filter for channel {}", channel_id);
+            let _ = instance.child.kill();
+            let _ = instance.child.wait();
+        }
+    }
+    Ok(())
+}
+
+/// Destroy all EQ filters (cleanup on exit).
+pub fn destroy_all_eq_filters() {
+    if let Some(ref mut map) = *get_processes() {
+        for (channel_id, mut instance) in map.drain() {
+            info!("Cleaning up EQ filter for channel {}", channel_id);
+            let _ = instance.child.kill();
+            let _ = instance.child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EqPreset;
+
+    #[test]
+    fn test_generate_eq_config() {
+        let preset = EqPreset::flat();
+        let config = generate_eq_config("Music", &preset.bands);
+        assert!(config.contains(r#"node.name = "sootmix.eq.Music""#));
+        assert!(config.contains(r#"node.name = "sootmix.eq.Music.output""#));
+        assert!(config.contains("node.autoconnect = false"));
+        assert!(config.contains(r#"outputs = [ "eq_band_4_0:Out" ]"#));
+        assert_eq!(config.matches("bq_peaking").count(), 5);
+
+        let mut high_pass = EqBand::new(80.0);
+        high_pass.kind = EqBandType::HighPass;
+        high_pass.slope = 36;
+        let config = generate_eq_config("Voice", &[high_pass]);
+        assert_eq!(config.matches("bq_highpass").count(), 3);
+        assert!(config.contains(r#"{ output = "eq_band_0_1:Out" input = "eq_band_0_2:In" }"#));
+
+        let config = generate_eq_config("Empty", &[]);
+        assert!(config.contains(r#"inputs = [ "eq_band_0_0:In" ]"#));
+    }
+
+    #[test]
+    fn test_eq_params() {
+        let mut preset = EqPreset::flat();
+        preset.bands[2] = EqBand::new(1000.0).with_gain(3.0).with_q(1.5);
+        let params = eq_params(&preset.bands);
+        assert_eq!(params.len(), 15);
+        assert_eq!(
+            params[6..9],
+            [
+                ("eq_band_2_0:Freq".to_string(), 1000.0),
+                ("eq_band_2_0:Q".to_string(), 1.5),
+                ("eq_band_2_0:Gain".to_string(), 3.0),
+            ]
+        );
+        assert_eq!(params[14], ("eq_band_4_0:Gain".to_string(), 0.0));
+    }
+
+    #[test]
+    fn test_same_layout() {
+        let flat = EqPreset::flat().bands;
+        let mut boosted = flat.clone();
+        boosted[0] = boosted[0].with_gain(6.0);
+        assert!(same_layout(&flat, &boosted));
+
+        boosted[0].kind = EqBandType::LowShelf;
+        assert!(!same_layout(&flat, &boosted));
+        assert!(!same_layout(&flat, &flat[..4]));
+        assert!(same_layout(&[], &[EqBand::new(200.0)]));
+    }
+
+    #[test]
+    fn test_eq_response() {
+        let mut preset = EqPreset::flat();
+        assert_eq!(eq_response_db(&preset.bands, 1000.0, 48000.0), 0.0);
+
+        preset.bands[2] = EqBand::new(1000.0).with_gain(6.0).with_q(2.0);
+        assert!((eq_response_db(&preset.bands, 1000.0, 48000.0) - 6.0).abs() < 1e-6);
+        assert!(eq_response_db(&preset.bands, 100.0, 48000.0).abs() < 0.1);
+        assert!(eq_response_db(&preset.bands, 10_000.0, 48000.0).abs() < 0.1);
+    }
+}
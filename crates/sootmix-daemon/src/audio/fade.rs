@@ -0,0 +1,85 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Timed volume fades, used when recalling scenes.
+//!
+//! Fades are stepped on the [control-rate tick](super::ducking#control-rate).
+
+/// Lowest level a fade starts from or ends at (the fader's bottom), in dB.
+pub const FADE_FLOOR_DB: f32 = -60.0;
+
+/// A linear-in-dB ramp from one fader level to another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeFade {
+    from_db: f32,
+    to_db: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl VolumeFade {
+    /// Fade from `from_db` to `to_db` over `duration_ms`. Levels below the
+    /// fader floor are ramped from/to the floor so the fade stays audible.
+    pub fn new(from_db: f32, to_db: f32, duration_ms: u32) -> Self {
+        Self {
+            from_db: from_db.max(FADE_FLOOR_DB),
+            to_db,
+            duration: duration_ms as f32 / 1000.0,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Target level in dB.
+    pub fn target_db(&self) -> f32 {
+        self.to_db
+    }
+
+    /// Advance by `dt` seconds and return the current level in dB.
+    pub fn advance(&mut self, dt: f32) -> f32 {
+        self.elapsed += dt;
+        if self.is_done() {
+            return self.to_db;
+        }
+        let t = self.elapsed / self.duration;
+        let to = self.to_db.max(FADE_FLOOR_DB);
+        self.from_db + (to - self.from_db) * t
+    }
+
+    /// Whether the fade has reached its target.
+    pub fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fade_interpolates_in_db() {
+        let mut fade = VolumeFade::new(-20.0, 0.0, 1000);
+        assert_eq!(fade.advance(0.25), -15.0);
+        assert_eq!(fade.advance(0.25), -10.0);
+        assert!(!fade.is_done());
+        assert_eq!(fade.advance(0.5), 0.0);
+        assert!(fade.is_done());
+    }
+
+    #[test]
+    fn test_fade_clamps_to_floor() {
+        let mut fade = VolumeFade::new(-96.0, -20.0, 1000);
+        assert_eq!(fade.advance(0.5), -40.0);
+
+        let mut fade = VolumeFade::new(0.0, -96.0, 1000);
+        assert_eq!(fade.advance(0.5), -30.0);
+        assert_eq!(fade.advance(0.5), -96.0);
+    }
+
+    #[test]
+    fn test_zero_duration_jumps() {
+        let mut fade = VolumeFade::new(-20.0, -6.0, 0);
+        assert_eq!(fade.advance(0.0), -6.0);
+        assert!(fade.is_done());
+    }
+}
@@ -0,0 +1,669 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! In-memory audio graph for testing the service without PipeWire.
+//!
+//! [`FakeGraph`] is a [`GraphBackend`] that applies the service's commands
+//! to a model of nodes, ports and links and reports the changes as the
+//! PipeWire thread would: virtual sinks and sources become node pairs with
+//! ports, links are created and destroyed, and channel routes follow the
+//! default sink. EQ and plugin filters (which also host noise suppression
+//! and the talk gate) become a sink and output stream pair without
+//! processing. Tests script the rest of the world through the same handle:
+//! devices and apps appearing and going away, WirePlumber linking streams
+//! and changing the default sink, and PipeWire restarting.
+//!
+//! Meters, recording and volume are not modeled; their commands are only
+//! logged.
+
+use crate::audio::backend::{BackendFactory, GraphBackend};
+use crate::audio::pipewire_thread::{PwCommand, PwError, PwEvent, RouteFinishReason};
+use crate::audio::plugin_stream::{plugin_output_node_name, plugin_sink_node_name};
+use crate::audio::routing;
+use crate::audio::types::{AudioChannel, MediaClass, PortDirection, PwLink, PwNode, PwPort};
+use crate::audio::virtual_sink::{sink_node_names, source_node_names};
+use parking_lot::Mutex;
+use sootmix_ipc::ChannelLayout;
+use std::collections::{HashMap, HashSet};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// First global ID handed out; lower ones belong to PipeWire's own objects.
+const FIRST_ID: u32 = 100;
+
+/// Shared handle to an in-memory graph. Clones refer to the same graph.
+#[derive(Clone, Default)]
+pub struct FakeGraph {
+    inner: Arc<Mutex<FakeGraphState>>,
+}
+
+#[derive(Default)]
+struct FakeGraphState {
+    /// Events go to the connected service (`None` while disconnected).
+    event_tx: Option<mpsc::Sender<PwEvent>>,
+    next_id: u32,
+    nodes: HashMap<u32, PwNode>,
+    ports: HashMap<u32, PwPort>,
+    links: HashMap<u32, PwLink>,
+    /// Nodes created by the service's commands; gone after a restart.
+    created: HashSet<u32>,
+    default_sink: Option<String>,
+    /// `target.node` of streams.
+    stream_targets: HashMap<u32, u32>,
+    /// Sink and output stream of each channel's EQ and plugin filter.
+    filters: HashMap<(Uuid, Filter), (u32, u32)>,
+    /// Every command received, in order.
+    commands: Vec<PwCommand>,
+    events_sent: usize,
+}
+
+/// Kind of a channel's filter.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Filter {
+    Eq,
+    Plugins,
+}
+
+/// The backend half handed to the service.
+struct FakeBackend {
+    graph: FakeGraph,
+}
+
+impl GraphBackend for FakeBackend {
+    fn send(&self, cmd: PwCommand) -> Result<(), PwError> {
+        let mut state = self.graph.inner.lock();
+        if state.event_tx.is_none() {
+            return Err(PwError::ThreadError("Channel closed".to_string()));
+        }
+        state.commands.push(cmd.clone());
+        state.apply(cmd);
+        Ok(())
+    }
+
+    // Commands are applied as they are sent
+    fn wait(&self, _delay: Duration) {}
+
+    fn shutdown(self: Box<Self>) {}
+}
+
+impl FakeGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Factory connecting the service to this graph. Each connection
+    /// announces the whole graph and the default sink, like the registry
+    /// of a fresh PipeWire connection.
+    pub fn backend_factory(&self) -> BackendFactory {
+        let graph = self.clone();
+        Box::new(move |event_tx| {
+            graph.inner.lock().connect(event_tx);
+            let backend: Box<dyn GraphBackend> = Box::new(FakeBackend {
+                graph: graph.clone(),
+            });
+            Ok(backend)
+        })
+    }
+
+    // ==================== Scripting ====================
+
+    /// Add a stereo hardware sink. Returns its node ID.
+    pub fn add_sink(&self, name: &str, description: &str) -> u32 {
+        let mut state = self.inner.lock();
+        let id = state.add_node(name, description, MediaClass::AudioSink);
+        state.add_ports(id, PortDirection::Input, ChannelLayout::Stereo);
+        id
+    }
+
+    /// Add a stereo playback stream of an app. Returns its node ID.
+    pub fn add_app(&self, name: &str, binary: &str) -> u32 {
+        let mut state = self.inner.lock();
+        let id = state.add_node(name, name, MediaClass::StreamOutputAudio);
+        if let Some(node) = state.nodes.get_mut(&id) {
+            node.app_name = Some(name.to_string());
+            node.binary_name = Some(binary.to_string());
+        }
+        state.add_ports(id, PortDirection::Output, ChannelLayout::Stereo);
+        id
+    }
+
+    /// Set a node property, as when binding a stream fills in properties
+    /// its registry global lacked.
+    pub fn set_node_property(&self, node_id: u32, key: &str, value: &str) {
+        let mut state = self.inner.lock();
+        let Some(node) = state.nodes.get_mut(&node_id) else {
+            return;
+        };
+        node.properties.insert(key.to_string(), value.to_string());
+        let node = node.clone();
+        state.send(PwEvent::NodeChanged(node));
+    }
+
+    /// Remove a node, as when a device is unplugged or an app exits.
+    pub fn remove_node(&self, node_id: u32) {
+        self.inner.lock().remove_node(node_id);
+    }
+
+    /// Link all matching ports of two nodes, as WirePlumber does when it
+    /// routes a stream. Returns the new link IDs.
+    pub fn link_nodes(&self, output_node: u32, input_node: u32) -> Vec<u32> {
+        let mut state = self.inner.lock();
+        state
+            .port_pairs(output_node, input_node)
+            .into_iter()
+            .filter_map(|(output_port, input_port)| state.add_link(output_port, input_port))
+            .collect()
+    }
+
+    /// Make a sink the default, as WirePlumber does when a preferred device
+    /// appears or the user picks one elsewhere.
+    pub fn set_default_sink(&self, name: &str) {
+        self.inner.lock().set_default_sink(name);
+    }
+
+    /// Drop the service's connection, as when PipeWire restarts. Nodes the
+    /// service created and all links are lost; devices and apps remain and
+    /// are announced again on the next connection.
+    pub fn disconnect(&self) {
+        let mut state = self.inner.lock();
+        state.send(PwEvent::Disconnected);
+        state.event_tx = None;
+        let created: Vec<u32> = state.created.drain().collect();
+        for node_id in created {
+            state.nodes.remove(&node_id);
+            state.ports.retain(|_, p| p.node_id != node_id);
+        }
+        state.links.clear();
+        state.stream_targets.clear();
+        state.filters.clear();
+    }
+
+    // ==================== Inspection ====================
+
+    /// ID of the node named `name`.
+    pub fn node_id(&self, name: &str) -> Option<u32> {
+        self.inner.lock().node_id(name)
+    }
+
+    /// Nodes `node_id` is linked to, sorted.
+    pub fn linked_to(&self, node_id: u32) -> Vec<u32> {
+        let state = self.inner.lock();
+        let mut targets: Vec<u32> = state
+            .links
+            .values()
+            .filter(|l| l.output_node == node_id)
+            .map(|l| l.input_node)
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+        targets
+    }
+
+    /// Number of links between two nodes.
+    pub fn link_count(&self, output_node: u32, input_node: u32) -> usize {
+        self.inner
+            .lock()
+            .links
+            .values()
+            .filter(|l| l.output_node == output_node && l.input_node == input_node)
+            .count()
+    }
+
+    /// `target.node` of a stream.
+    pub fn stream_target(&self, node_id: u32) -> Option<u32> {
+        self.inner.lock().stream_targets.get(&node_id).copied()
+    }
+
+    /// Commands received so far.
+    pub fn commands(&self) -> Vec<PwCommand> {
+        self.inner.lock().commands.clone()
+    }
+
+    /// Number of events sent so far; unchanged once the service has caught
+    /// up with the graph.
+    pub fn events_sent(&self) -> usize {
+        self.inner.lock().events_sent
+    }
+}
+
+impl FakeGraphState {
+    fn send(&mut self, event: PwEvent) {
+        if let Some(tx) = &self.event_tx {
+            if tx.send(event).is_ok() {
+                self.events_sent += 1;
+            }
+        }
+    }
+
+    fn allocate_id(&mut self) -> u32 {
+        self.next_id = self.next_id.max(FIRST_ID) + 1;
+        self.next_id
+    }
+
+    fn connect(&mut self, event_tx: mpsc::Sender<PwEvent>) {
+        self.event_tx = Some(event_tx);
+        self.send(PwEvent::Connected);
+
+        let mut nodes: Vec<PwNode> = self.nodes.values().cloned().collect();
+        nodes.sort_by_key(|n| n.id);
+        let mut ports: Vec<PwPort> = self.ports.values().cloned().collect();
+        ports.sort_by_key(|p| p.id);
+        let mut links: Vec<PwLink> = self.links.values().cloned().collect();
+        links.sort_by_key(|l| l.id);
+
+        for node in nodes {
+            self.send(PwEvent::NodeAdded(node));
+        }
+        for port in ports {
+            self.send(PwEvent::PortAdded(port));
+        }
+        for link in links {
+            self.send(PwEvent::LinkAdded(link));
+        }
+        let name = self.default_sink.clone();
+        self.send(PwEvent::DefaultSinkChanged { name });
+    }
+
+    fn node_id(&self, name: &str) -> Option<u32> {
+        self.nodes.values().find(|n| n.name == name).map(|n| n.id)
+    }
+
+    fn add_node(&mut self, name: &str, description: &str, media_class: MediaClass) -> u32 {
+        let id = self.allocate_id();
+        let mut node = PwNode::new(id);
+        node.name = name.to_string();
+        node.description = description.to_string();
+        node.media_class = media_class;
+        self.nodes.insert(id, node.clone());
+        self.send(PwEvent::NodeAdded(node));
+        id
+    }
+
+    fn add_ports(&mut self, node_id: u32, direction: PortDirection, layout: ChannelLayout) {
+        let prefix = match direction {
+            PortDirection::Input => "playback",
+            _ => "output",
+        };
+        for position in layout.positions() {
+            let id = self.allocate_id();
+            let mut port = PwPort::new(id, node_id);
+            port.name = format!("{}_{}", prefix, position);
+            port.direction = direction;
+            port.channel = AudioChannel::from_position(position);
+            self.ports.insert(id, port.clone());
+            self.send(PwEvent::PortAdded(port));
+        }
+    }
+
+    /// Add a pair of service-created nodes: the virtual device and its
+    /// loopback stream.
+    fn add_loopback(
+        &mut self,
+        (main, main_class, main_direction): (&str, MediaClass, PortDirection),
+        (stream, stream_class, stream_direction): (&str, MediaClass, PortDirection),
+        description: &str,
+        layout: ChannelLayout,
+    ) -> (u32, u32) {
+        let main_id = self.add_node(main, description, main_class);
+        self.add_ports(main_id, main_direction, layout);
+        let stream_id = self.add_node(stream, description, stream_class);
+        self.add_ports(stream_id, stream_direction, layout);
+        self.created.extend([main_id, stream_id]);
+        (main_id, stream_id)
+    }
+
+    fn remove_node(&mut self, node_id: u32) {
+        let links: Vec<u32> = self
+            .links
+            .values()
+            .filter(|l| l.output_node == node_id || l.input_node == node_id)
+            .map(|l| l.id)
+            .collect();
+        for link_id in links {
+            self.remove_link(link_id);
+        }
+
+        let mut ports: Vec<u32> = self
+            .ports
+            .values()
+            .filter(|p| p.node_id == node_id)
+            .map(|p| p.id)
+            .collect();
+        ports.sort_unstable();
+        for port_id in ports {
+            self.ports.remove(&port_id);
+            self.send(PwEvent::PortRemoved(port_id));
+        }
+
+        self.created.remove(&node_id);
+        self.stream_targets.remove(&node_id);
+        if self.nodes.remove(&node_id).is_some() {
+            self.send(PwEvent::NodeRemoved(node_id));
+        }
+    }
+
+    /// Add a channel's filter, replacing the one it had. Returns its sink
+    /// and output stream.
+    fn add_filter(
+        &mut self,
+        channel_id: Uuid,
+        kind: Filter,
+        (sink_name, output_name): (&str, &str),
+        description: &str,
+    ) -> (u32, u32) {
+        self.remove_filter(channel_id, kind);
+        let nodes = self.add_loopback(
+            (sink_name, MediaClass::AudioSink, PortDirection::Input),
+            (
+                output_name,
+                MediaClass::StreamOutputAudio,
+                PortDirection::Output,
+            ),
+            description,
+            ChannelLayout::Stereo,
+        );
+        self.filters.insert((channel_id, kind), nodes);
+        nodes
+    }
+
+    fn remove_filter(&mut self, channel_id: Uuid, kind: Filter) {
+        if let Some((sink, output)) = self.filters.remove(&(channel_id, kind)) {
+            self.remove_node(sink);
+            self.remove_node(output);
+        }
+    }
+
+    /// The node created together with `node_id`, if it is half of a
+    /// virtual sink or source.
+    fn loopback_partner(&self, node_id: u32) -> Option<u32> {
+        let name = &self.nodes.get(&node_id)?.name;
+        let base = name.strip_prefix("sootmix.")?;
+        [sink_node_names(base), source_node_names(base)]
+            .into_iter()
+            .find_map(|names| self.node_id(&names.stream))
+    }
+
+    fn port_pairs(&self, output_node: u32, input_node: u32) -> Vec<(u32, u32)> {
+        let outputs: Vec<&PwPort> = self
+            .ports
+            .values()
+            .filter(|p| p.node_id == output_node && p.direction == PortDirection::Output)
+            .collect();
+        let inputs: Vec<&PwPort> = self
+            .ports
+            .values()
+            .filter(|p| p.node_id == input_node && p.direction == PortDirection::Input)
+            .collect();
+        routing::pair_ports(&outputs, &inputs)
+    }
+
+    /// Link two ports. Fails, like PipeWire, if either port is missing or
+    /// they are already linked.
+    fn add_link(&mut self, output_port: u32, input_port: u32) -> Option<u32> {
+        let output_node = self.ports.get(&output_port)?.node_id;
+        let input_node = self.ports.get(&input_port)?.node_id;
+        if self
+            .links
+            .values()
+            .any(|l| l.output_port == output_port && l.input_port == input_port)
+        {
+            return None;
+        }
+
+        let id = self.allocate_id();
+        let link = PwLink {
+            id,
+            output_node,
+            output_port,
+            input_node,
+            input_port,
+            active: true,
+        };
+        self.links.insert(id, link.clone());
+        self.send(PwEvent::LinkAdded(link));
+        Some(id)
+    }
+
+    fn remove_link(&mut self, link_id: u32) {
+        if self.links.remove(&link_id).is_some() {
+            self.send(PwEvent::LinkRemoved(link_id));
+        }
+    }
+
+    fn set_default_sink(&mut self, name: &str) {
+        self.default_sink = Some(name.to_string());
+        self.send(PwEvent::DefaultSinkChanged {
+            name: Some(name.to_string()),
+        });
+    }
+
+    fn apply(&mut self, cmd: PwCommand) {
+        match cmd {
+            PwCommand::CreateVirtualSink {
+                channel_id,
+                name,
+                layout,
+                ..
+            } => {
+                let names = sink_node_names(&name);
+                let (sink, stream) = self.add_loopback(
+                    (&names.main, MediaClass::AudioSink, PortDirection::Input),
+                    (
+                        &names.stream,
+                        MediaClass::StreamOutputAudio,
+                        PortDirection::Output,
+                    ),
+                    &name,
+                    layout,
+                );
+                self.send(PwEvent::VirtualSinkCreated {
+                    channel_id,
+                    node_id: sink,
+                    loopback_output_node_id: Some(stream),
+                    meter_levels: None,
+                    loudness: None,
+                });
+            }
+            PwCommand::CreateVirtualSource {
+                channel_id, name, ..
+            } => {
+                let names = source_node_names(&name);
+                let (source, stream) = self.add_loopback(
+                    (&names.main, MediaClass::AudioSource, PortDirection::Output),
+                    (
+                        &names.stream,
+                        MediaClass::StreamInputAudio,
+                        PortDirection::Input,
+                    ),
+                    &name,
+                    ChannelLayout::Stereo,
+                );
+                self.send(PwEvent::VirtualSourceCreated {
+                    channel_id,
+                    source_node_id: source,
+                    loopback_capture_node_id: Some(stream),
+                    meter_levels: None,
+                    loudness: None,
+                });
+            }
+            PwCommand::DestroyVirtualSink { node_id } => {
+                self.send(PwEvent::VirtualSinkDestroyed { node_id });
+                if let Some(partner) = self.loopback_partner(node_id) {
+                    self.remove_node(partner);
+                }
+                self.remove_node(node_id);
+            }
+            PwCommand::DestroyNode { node_id } => self.remove_node(node_id),
+            PwCommand::CreateEqFilter {
+                channel_id, name, ..
+            } => {
+                let (sink, output) = self.add_filter(
+                    channel_id,
+                    Filter::Eq,
+                    (
+                        &format!("sootmix.eq.{}", name),
+                        &format!("sootmix.eq.{}.output", name),
+                    ),
+                    &name,
+                );
+                self.send(PwEvent::EqFilterCreated {
+                    channel_id,
+                    sink_node_id: sink,
+                    output_node_id: output,
+                });
+            }
+            PwCommand::DestroyEqFilter { channel_id } => {
+                self.remove_filter(channel_id, Filter::Eq);
+                self.send(PwEvent::EqFilterDestroyed { channel_id });
+            }
+            PwCommand::CreatePluginFilter {
+                channel_id, name, ..
+            } => {
+                let (sink, output) = self.add_filter(
+                    channel_id,
+                    Filter::Plugins,
+                    (
+                        &plugin_sink_node_name(&name),
+                        &plugin_output_node_name(&name),
+                    ),
+                    &name,
+                );
+                self.send(PwEvent::PluginFilterCreated {
+                    channel_id,
+                    sink_node_id: sink,
+                    output_node_id: output,
+                });
+            }
+            PwCommand::DestroyPluginFilter { channel_id } => {
+                self.remove_filter(channel_id, Filter::Plugins)
+            }
+            PwCommand::CreateLink {
+                output_port,
+                input_port,
+            } => {
+                let linked = self.add_link(output_port, input_port).is_some();
+                if !linked {
+                    self.send(PwEvent::Error(format!(
+                        "Failed to create link: {} -> {}",
+                        output_port, input_port
+                    )));
+                }
+            }
+            PwCommand::DestroyLink { link_id } => self.remove_link(link_id),
+            PwCommand::SetDefaultSink { node_id } => {
+                if let Some(name) = self.nodes.get(&node_id).map(|n| n.name.clone()) {
+                    self.set_default_sink(&name);
+                }
+            }
+            PwCommand::SetStreamTarget {
+                stream_node_id,
+                target_node_id,
+            } => match target_node_id {
+                Some(target) => {
+                    self.stream_targets.insert(stream_node_id, target);
+                }
+                None => {
+                    self.stream_targets.remove(&stream_node_id);
+                }
+            },
+            PwCommand::RouteChannelToDevice {
+                loopback_output_node,
+                target_device_id,
+                ..
+            } => self.route(loopback_output_node, target_device_id),
+            _ => {}
+        }
+    }
+
+    /// Relink a channel's output stage to a device, or to the default sink.
+    fn route(&mut self, loopback_output_node: u32, target_device_id: Option<u32>) {
+        let target = target_device_id.or_else(|| {
+            let name = self.default_sink.as_deref()?;
+            self.node_id(name)
+        });
+
+        let stale: Vec<u32> = self
+            .links
+            .values()
+            .filter(|l| l.output_node == loopback_output_node)
+            .map(|l| l.id)
+            .collect();
+        for link_id in stale {
+            self.remove_link(link_id);
+        }
+
+        let reason = match target {
+            None => RouteFinishReason::NoTargetDevice,
+            Some(target) => {
+                let pairs = self.port_pairs(loopback_output_node, target);
+                if pairs.is_empty() {
+                    RouteFinishReason::NoPortPairs
+                } else {
+                    let count = pairs.len();
+                    for (output_port, input_port) in pairs {
+                        self.add_link(output_port, input_port);
+                    }
+                    RouteFinishReason::LinksCreated { count }
+                }
+            }
+        };
+        self.send(PwEvent::RouteFinished {
+            loopback_output_node,
+            reason,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_virtual_sink_routes_to_default() {
+        let graph = FakeGraph::new();
+        let speakers = graph.add_sink("alsa_output.speakers", "Speakers");
+        graph.set_default_sink("alsa_output.speakers");
+
+        let (event_tx, event_rx) = mpsc::channel();
+        let mut factory = graph.backend_factory();
+        let backend = factory(event_tx).unwrap();
+        backend
+            .send(PwCommand::CreateVirtualSink {
+                channel_id: uuid::Uuid::new_v4(),
+                name: "Music".to_string(),
+                target_device: None,
+                layout: ChannelLayout::Stereo,
+            })
+            .unwrap();
+
+        let stream = event_rx
+            .try_iter()
+            .find_map(|event| match event {
+                PwEvent::VirtualSinkCreated {
+                    loopback_output_node_id,
+                    ..
+                } => loopback_output_node_id,
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(graph.node_id("output.sootmix.Music.output"), Some(stream));
+
+        backend
+            .send(PwCommand::RouteChannelToDevice {
+                loopback_output_node: stream,
+                target_device_id: None,
+                channel_id: None,
+            })
+            .unwrap();
+        assert_eq!(graph.link_count(stream, speakers), 2);
+
+        let sink = graph.node_id("sootmix.Music").unwrap();
+        backend
+            .send(PwCommand::DestroyVirtualSink { node_id: sink })
+            .unwrap();
+        assert_eq!(graph.node_id("output.sootmix.Music.output"), None);
+        assert!(graph.linked_to(stream).is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Latency compensation between output channels.
+//!
+//! Plugins such as lookahead limiters delay the channel they run on by the
+//! latency they report. Every output channel playing to the same device is
+//! delayed by the difference to the slowest one, so they stay in sync. The
+//! delay runs at the end of the channel's plugin filter, which is created
+//! for that alone on channels without plugins (see
+//! `DaemonService::sync_latency`). That filter is stereo, so surround
+//! channels aren't delayed; the service logs the delay they miss instead.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use uuid::Uuid;
+
+/// Longest compensation delay, in frames (one second at 48 kHz).
+pub const MAX_COMPENSATION_FRAMES: u32 = 48_000;
+
+/// Work out the extra delay of each channel.
+///
+/// `channels` yields `(id, output, latency)`, where `output` identifies the
+/// device the channel plays on and `latency` is what its plugin chain
+/// reports. Channels are delayed up to the slowest channel on the same
+/// output, by at most [`MAX_COMPENSATION_FRAMES`]; channels that need no
+/// delay are left out of the result.
+pub fn plan_compensation<K: Eq + Hash>(
+    channels: impl IntoIterator<Item = (Uuid, K, u32)>,
+) -> HashMap<Uuid, u32> {
+    let channels: Vec<_> = channels.into_iter().collect();
+
+    let mut slowest: HashMap<&K, u32> = HashMap::new();
+    for (_, output, latency) in &channels {
+        let max = slowest.entry(output).or_default();
+        *max = (*max).max(*latency);
+    }
+
+    channels
+        .iter()
+        .filter_map(|(id, output, latency)| {
+            let delay = (slowest[output] - latency).min(MAX_COMPENSATION_FRAMES);
+            (delay > 0).then_some((*id, delay))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channels_align_to_slowest_on_output() {
+        let (game, music, chat) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let plan = plan_compensation([
+            (game, "speakers", 0),
+            (music, "speakers", 256),
+            (chat, "speakers", 64),
+        ]);
+
+        assert_eq!(plan.get(&game), Some(&256));
+        assert_eq!(plan.get(&music), None);
+        assert_eq!(plan.get(&chat), Some(&192));
+    }
+
+    #[test]
+    fn test_outputs_are_aligned_separately() {
+        let (game, chat) = (Uuid::new_v4(), Uuid::new_v4());
+        let plan = plan_compensation([(game, Some(40), 512), (chat, Some(41), 0)]);
+
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_compensation_is_capped() {
+        let (game, music) = (Uuid::new_v4(), Uuid::new_v4());
+        let plan = plan_compensation([(game, 0, 0), (music, 0, 10 * MAX_COMPENSATION_FRAMES)]);
+
+        assert_eq!(plan.get(&game), Some(&MAX_COMPENSATION_FRAMES));
+    }
+}
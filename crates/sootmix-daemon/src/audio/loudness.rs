@@ -0,0 +1,598 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Loudness and true-peak metering (ITU-R BS.1770-4, EBU R128).
+//!
+//! A [`LoudnessMeter`] runs on the PulseAudio meter thread of each channel
+//! (and of the master output's monitor), fed with the full-rate stereo
+//! signal. Audio is K-weighted and summed into 100 ms blocks:
+//!
+//! - momentary loudness covers the last 400 ms, short-term the last 3 s;
+//! - integrated loudness gates 400 ms blocks (75% overlap) at -70 LUFS and
+//!   then 10 LU below their mean;
+//! - loudness range is the spread between the 10th and 95th percentile of
+//!   short-term loudness, gated at -70 LUFS and 20 LU below the mean
+//!   (EBU Tech 3342);
+//! - true peak is the highest absolute value of the signal oversampled to at
+//!   least 192 kHz.
+//!
+//! Integrated loudness, loudness range and true peak accumulate until reset.
+//! The meter thread publishes results through [`SharedLoudness`], which the
+//! daemon's meter polling loop reads.
+
+use std::f64::consts::PI;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Absolute gate, in LUFS.
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+/// Relative gate for integrated loudness, in LU below the ungated mean.
+const INTEGRATED_RELATIVE_GATE_LU: f64 = -10.0;
+/// Relative gate for loudness range, in LU below the ungated mean.
+const RANGE_RELATIVE_GATE_LU: f64 = -20.0;
+/// Sub-blocks in the momentary window (4 x 100 ms).
+const MOMENTARY_BLOCKS: usize = 4;
+/// Sub-blocks in the short-term window (30 x 100 ms).
+const SHORT_TERM_BLOCKS: usize = 30;
+/// Histogram resolution for gating, in LU.
+const HISTOGRAM_STEP_LU: f64 = 0.1;
+/// Loudest value the histograms track separately, in LUFS.
+const HISTOGRAM_MAX_LUFS: f64 = 10.0;
+/// Interpolation taps per oversampling phase.
+const TRUE_PEAK_TAPS: usize = 12;
+
+/// Loudness of a mean-square energy, in LUFS.
+fn energy_to_lufs(energy: f64) -> f64 {
+    if energy <= 0.0 {
+        f64::NEG_INFINITY
+    } else {
+        -0.691 + 10.0 * energy.log10()
+    }
+}
+
+// ============================================================================
+// K-WEIGHTING
+// ============================================================================
+
+/// Biquad filter (transposed direct form II).
+#[derive(Debug, Clone, Copy, Default)]
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+    z: [f64; 2],
+}
+
+impl Biquad {
+    #[inline]
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b[0] * x + self.z[0];
+        self.z[0] = self.b[1] * x - self.a[0] * y + self.z[1];
+        self.z[1] = self.b[2] * x - self.a[1] * y;
+        y
+    }
+}
+
+/// The two-stage K-weighting filter for one channel: a high shelf modelling
+/// the head, then the RLB high-pass.
+#[derive(Debug, Clone, Copy)]
+struct KWeighting {
+    shelf: Biquad,
+    high_pass: Biquad,
+}
+
+impl KWeighting {
+    /// Filter coefficients for `sample_rate`, matching the 48 kHz values in
+    /// BS.1770 and derived for other rates.
+    fn new(sample_rate: f64) -> Self {
+        let f0 = 1_681.974_450_955_533;
+        let gain_db = 3.999_843_853_973_347;
+        let q = 0.707_175_236_955_419_6;
+        let k = (PI * f0 / sample_rate).tan();
+        let vh = 10f64.powf(gain_db / 20.0);
+        let vb = vh.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = Biquad {
+            b: [
+                (vh + vb * k / q + k * k) / a0,
+                2.0 * (k * k - vh) / a0,
+                (vh - vb * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            z: [0.0; 2],
+        };
+
+        let f0 = 38.135_470_876_024_44;
+        let q = 0.500_327_037_323_877_3;
+        let k = (PI * f0 / sample_rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = Biquad {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+            z: [0.0; 2],
+        };
+
+        Self { shelf, high_pass }
+    }
+
+    #[inline]
+    fn process(&mut self, x: f64) -> f64 {
+        self.high_pass.process(self.shelf.process(x))
+    }
+}
+
+// ============================================================================
+// GATING HISTOGRAM
+// ============================================================================
+
+/// Block loudness values binned at 0.1 LU, so long programmes are gated in
+/// constant memory.
+#[derive(Debug, Clone)]
+struct GatingHistogram {
+    /// Blocks per bin.
+    counts: Vec<u64>,
+    /// Summed block energy per bin.
+    energy: Vec<f64>,
+}
+
+impl GatingHistogram {
+    fn new() -> Self {
+        let bins = ((HISTOGRAM_MAX_LUFS - ABSOLUTE_GATE_LUFS) / HISTOGRAM_STEP_LU) as usize + 1;
+        Self {
+            counts: vec![0; bins],
+            energy: vec![0.0; bins],
+        }
+    }
+
+    fn clear(&mut self) {
+        self.counts.fill(0);
+        self.energy.fill(0.0);
+    }
+
+    fn bin(&self, lufs: f64) -> usize {
+        let bin = ((lufs - ABSOLUTE_GATE_LUFS) / HISTOGRAM_STEP_LU).max(0.0) as usize;
+        bin.min(self.counts.len() - 1)
+    }
+
+    /// Loudness at the centre of a bin.
+    fn bin_lufs(bin: usize) -> f64 {
+        ABSOLUTE_GATE_LUFS + (bin as f64 + 0.5) * HISTOGRAM_STEP_LU
+    }
+
+    /// Add a block, dropping it if it's below the absolute gate.
+    fn add(&mut self, energy: f64) {
+        let lufs = energy_to_lufs(energy);
+        if lufs < ABSOLUTE_GATE_LUFS {
+            return;
+        }
+        let bin = self.bin(lufs);
+        self.counts[bin] += 1;
+        self.energy[bin] += energy;
+    }
+
+    /// First bin that passes a gate `relative_lu` below the mean of all
+    /// blocks, or `None` if there are none.
+    fn relative_gate_bin(&self, relative_lu: f64) -> Option<usize> {
+        let count: u64 = self.counts.iter().sum();
+        if count == 0 {
+            return None;
+        }
+        let mean = self.energy.iter().sum::<f64>() / count as f64;
+        Some(self.bin(energy_to_lufs(mean) + relative_lu))
+    }
+
+    /// Mean loudness of the blocks passing the relative gate.
+    fn gated_loudness(&self, relative_lu: f64) -> Option<f64> {
+        let gate = self.relative_gate_bin(relative_lu)?;
+        let count: u64 = self.counts[gate..].iter().sum();
+        let energy: f64 = self.energy[gate..].iter().sum();
+        (count > 0).then(|| energy_to_lufs(energy / count as f64))
+    }
+
+    /// Spread between the 10th and 95th percentile of the blocks passing the
+    /// relative gate, in LU.
+    fn range(&self, relative_lu: f64) -> Option<f64> {
+        let gate = self.relative_gate_bin(relative_lu)?;
+        let count: u64 = self.counts[gate..].iter().sum();
+        if count == 0 {
+            return None;
+        }
+
+        let percentile = |fraction: f64| {
+            let target = ((count - 1) as f64 * fraction).round() as u64;
+            let mut seen = 0;
+            for (bin, &n) in self.counts.iter().enumerate().skip(gate) {
+                seen += n;
+                if seen > target {
+                    return Self::bin_lufs(bin);
+                }
+            }
+            Self::bin_lufs(self.counts.len() - 1)
+        };
+        Some(percentile(0.95) - percentile(0.10))
+    }
+}
+
+// ============================================================================
+// TRUE PEAK
+// ============================================================================
+
+/// Oversampling peak detector for one channel.
+#[derive(Debug, Clone)]
+struct TruePeak {
+    /// Interpolation filter, `factor * TRUE_PEAK_TAPS` long.
+    filter: Vec<f64>,
+    /// Oversampling factor (1 = sample peak).
+    factor: usize,
+    /// Last `TRUE_PEAK_TAPS` input samples, newest first.
+    history: [f64; TRUE_PEAK_TAPS],
+    /// Highest absolute value seen.
+    peak: f64,
+}
+
+impl TruePeak {
+    /// Oversample to at least 192 kHz with a Hann-windowed sinc interpolator.
+    fn new(sample_rate: f64) -> Self {
+        let factor = if sample_rate < 96_000.0 {
+            4
+        } else if sample_rate < 192_000.0 {
+            2
+        } else {
+            1
+        };
+
+        let len = factor * TRUE_PEAK_TAPS;
+        let centre = (len - 1) as f64 / 2.0;
+        let filter = (0..len)
+            .map(|n| {
+                let x = (n as f64 - centre) / factor as f64;
+                let sinc = if x == 0.0 {
+                    1.0
+                } else {
+                    (PI * x).sin() / (PI * x)
+                };
+                let window = 0.5 - 0.5 * (2.0 * PI * (n as f64 + 0.5) / len as f64).cos();
+                sinc * window
+            })
+            .collect();
+
+        Self {
+            filter,
+            factor,
+            history: [0.0; TRUE_PEAK_TAPS],
+            peak: 0.0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, x: f64) {
+        self.history.copy_within(0..TRUE_PEAK_TAPS - 1, 1);
+        self.history[0] = x;
+        self.peak = self.peak.max(x.abs());
+        if self.factor == 1 {
+            return;
+        }
+        for phase in 0..self.factor {
+            let y: f64 = self
+                .history
+                .iter()
+                .enumerate()
+                .map(|(tap, &h)| h * self.filter[tap * self.factor + phase])
+                .sum();
+            self.peak = self.peak.max(y.abs());
+        }
+    }
+}
+
+// ============================================================================
+// LOUDNESS METER
+// ============================================================================
+
+/// Measured loudness of a signal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoudnessLevels {
+    /// Momentary loudness (400 ms), in LUFS.
+    pub momentary: f32,
+    /// Short-term loudness (3 s), in LUFS.
+    pub short_term: f32,
+    /// Integrated loudness since the last reset, in LUFS.
+    pub integrated: f32,
+    /// Loudness range since the last reset, in LU.
+    pub range: f32,
+    /// Highest true peak since the last reset, linear.
+    pub true_peak: f32,
+}
+
+impl Default for LoudnessLevels {
+    fn default() -> Self {
+        Self {
+            momentary: f32::NEG_INFINITY,
+            short_term: f32::NEG_INFINITY,
+            integrated: f32::NEG_INFINITY,
+            range: 0.0,
+            true_peak: 0.0,
+        }
+    }
+}
+
+/// BS.1770 loudness meter for interleaved stereo audio.
+#[derive(Debug, Clone)]
+pub struct LoudnessMeter {
+    weighting: [KWeighting; 2],
+    true_peak: [TruePeak; 2],
+    /// Frames per 100 ms sub-block.
+    block_frames: usize,
+    /// Frames in the current sub-block so far.
+    frames: usize,
+    /// Summed weighted square of the current sub-block.
+    sum: f64,
+    /// Energy of the last `SHORT_TERM_BLOCKS` sub-blocks, oldest first.
+    blocks: [f64; SHORT_TERM_BLOCKS],
+    /// Sub-blocks seen since the last reset (saturating).
+    block_count: usize,
+    /// 400 ms gating blocks for integrated loudness.
+    integrated: GatingHistogram,
+    /// Short-term values for loudness range.
+    short_term: GatingHistogram,
+    levels: LoudnessLevels,
+}
+
+impl LoudnessMeter {
+    pub fn new(sample_rate: u32) -> Self {
+        let rate = sample_rate.max(1) as f64;
+        Self {
+            weighting: [KWeighting::new(rate); 2],
+            true_peak: [TruePeak::new(rate), TruePeak::new(rate)],
+            block_frames: (sample_rate as usize / 10).max(1),
+            frames: 0,
+            sum: 0.0,
+            blocks: [0.0; SHORT_TERM_BLOCKS],
+            block_count: 0,
+            integrated: GatingHistogram::new(),
+            short_term: GatingHistogram::new(),
+            levels: LoudnessLevels::default(),
+        }
+    }
+
+    /// Current measurements.
+    pub fn levels(&self) -> LoudnessLevels {
+        self.levels
+    }
+
+    /// Feed interleaved stereo samples. A trailing partial frame is ignored.
+    pub fn process(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(2) {
+            for (channel, &sample) in frame.iter().enumerate() {
+                let x = sample as f64;
+                let weighted = self.weighting[channel].process(x);
+                self.sum += weighted * weighted;
+                self.true_peak[channel].process(x);
+            }
+
+            self.frames += 1;
+            if self.frames == self.block_frames {
+                self.finish_block();
+            }
+        }
+
+        let peak = self.true_peak[0].peak.max(self.true_peak[1].peak);
+        self.levels.true_peak = peak as f32;
+    }
+
+    /// Forget integrated loudness, loudness range and true peak.
+    pub fn reset(&mut self) {
+        self.integrated.clear();
+        self.short_term.clear();
+        for peak in &mut self.true_peak {
+            peak.peak = 0.0;
+        }
+        self.block_count = 0;
+        self.blocks = [0.0; SHORT_TERM_BLOCKS];
+        self.levels = LoudnessLevels::default();
+    }
+
+    /// Close a 100 ms sub-block and update every measurement.
+    fn finish_block(&mut self) {
+        let energy = self.sum / self.frames as f64;
+        self.sum = 0.0;
+        self.frames = 0;
+
+        self.blocks.rotate_left(1);
+        self.blocks[SHORT_TERM_BLOCKS - 1] = energy;
+        self.block_count = self.block_count.saturating_add(1);
+
+        let momentary = self.blocks[SHORT_TERM_BLOCKS - MOMENTARY_BLOCKS..]
+            .iter()
+            .sum::<f64>()
+            / MOMENTARY_BLOCKS as f64;
+        let short_term = self.blocks.iter().sum::<f64>() / SHORT_TERM_BLOCKS as f64;
+
+        if self.block_count >= MOMENTARY_BLOCKS {
+            self.integrated.add(momentary);
+        }
+        if self.block_count >= SHORT_TERM_BLOCKS {
+            self.short_term.add(short_term);
+        }
+
+        self.levels.momentary = energy_to_lufs(momentary) as f32;
+        self.levels.short_term = energy_to_lufs(short_term) as f32;
+        self.levels.integrated = self
+            .integrated
+            .gated_loudness(INTEGRATED_RELATIVE_GATE_LU)
+            .unwrap_or(f64::NEG_INFINITY) as f32;
+        self.levels.range = self.short_term.range(RANGE_RELATIVE_GATE_LU).unwrap_or(0.0) as f32;
+    }
+}
+
+// ============================================================================
+// SHARED LEVELS
+// ============================================================================
+
+/// Loudness readings shared between a meter thread and the polling loop.
+#[derive(Debug)]
+pub struct SharedLoudness {
+    momentary: AtomicU32,
+    short_term: AtomicU32,
+    integrated: AtomicU32,
+    range: AtomicU32,
+    true_peak: AtomicU32,
+    /// Set by the daemon to ask the meter thread to reset integration.
+    reset_requested: AtomicBool,
+}
+
+impl Default for SharedLoudness {
+    fn default() -> Self {
+        let shared = Self {
+            momentary: AtomicU32::new(0),
+            short_term: AtomicU32::new(0),
+            integrated: AtomicU32::new(0),
+            range: AtomicU32::new(0),
+            true_peak: AtomicU32::new(0),
+            reset_requested: AtomicBool::new(false),
+        };
+        shared.store(LoudnessLevels::default());
+        shared
+    }
+}
+
+impl SharedLoudness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn store(&self, levels: LoudnessLevels) {
+        self.momentary
+            .store(levels.momentary.to_bits(), Ordering::Relaxed);
+        self.short_term
+            .store(levels.short_term.to_bits(), Ordering::Relaxed);
+        self.integrated
+            .store(levels.integrated.to_bits(), Ordering::Relaxed);
+        self.range.store(levels.range.to_bits(), Ordering::Relaxed);
+        self.true_peak
+            .store(levels.true_peak.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn load(&self) -> LoudnessLevels {
+        let load = |value: &AtomicU32| f32::from_bits(value.load(Ordering::Relaxed));
+        LoudnessLevels {
+            momentary: load(&self.momentary),
+            short_term: load(&self.short_term),
+            integrated: load(&self.integrated),
+            range: load(&self.range),
+            true_peak: load(&self.true_peak),
+        }
+    }
+
+    /// Ask the meter thread to reset integrated loudness, loudness range and
+    /// true peak. The readings drop to silence straight away.
+    pub fn request_reset(&self) {
+        self.store(LoudnessLevels::default());
+        self.reset_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Take a pending reset request (meter thread side).
+    pub fn take_reset(&self) -> bool {
+        self.reset_requested.swap(false, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+
+    /// Stereo sine of `amplitude` at `freq` Hz, the same on both channels.
+    fn sine(freq: f64, amplitude: f32, seconds: f64, phase: f64) -> Vec<f32> {
+        let frames = (RATE as f64 * seconds) as usize;
+        (0..frames)
+            .flat_map(|n| {
+                let t = n as f64 / RATE as f64;
+                let x = amplitude * (2.0 * PI * freq * t + phase).sin() as f32;
+                [x, x]
+            })
+            .collect()
+    }
+
+    fn db(amplitude: f32) -> f32 {
+        10f32.powf(amplitude / 20.0)
+    }
+
+    #[test]
+    fn test_sine_reads_its_level() {
+        // EBU Tech 3341: a 1 kHz sine at -23 dBFS on both channels is -23 LUFS
+        let mut meter = LoudnessMeter::new(RATE);
+        meter.process(&sine(1000.0, db(-23.0), 5.0, 0.0));
+        let levels = meter.levels();
+
+        assert!((levels.momentary + 23.0).abs() < 0.1, "{:?}", levels);
+        assert!((levels.short_term + 23.0).abs() < 0.1, "{:?}", levels);
+        assert!((levels.integrated + 23.0).abs() < 0.1, "{:?}", levels);
+        assert!(levels.range < 0.5, "{:?}", levels);
+    }
+
+    #[test]
+    fn test_silence_is_gated_out() {
+        let mut meter = LoudnessMeter::new(RATE);
+        meter.process(&sine(1000.0, db(-20.0), 4.0, 0.0));
+        meter.process(&vec![0.0; RATE as usize * 2 * 10]);
+        let levels = meter.levels();
+
+        assert_eq!(levels.momentary, f32::NEG_INFINITY);
+        assert!((levels.integrated + 20.0).abs() < 0.2, "{:?}", levels);
+    }
+
+    #[test]
+    fn test_loudness_range_spans_levels() {
+        let mut meter = LoudnessMeter::new(RATE);
+        for _ in 0..2 {
+            meter.process(&sine(1000.0, db(-30.0), 6.0, 0.0));
+            meter.process(&sine(1000.0, db(-20.0), 6.0, 0.0));
+        }
+        let range = meter.levels().range;
+
+        assert!((range - 10.0).abs() < 1.0, "range {}", range);
+    }
+
+    #[test]
+    fn test_true_peak_finds_intersample_peak() {
+        // A quarter-rate sine sampled 45 degrees off its peaks reads 3 dB low
+        let mut meter = LoudnessMeter::new(RATE);
+        let samples = sine(RATE as f64 / 4.0, 0.5, 0.5, PI / 4.0);
+        let sample_peak = samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        meter.process(&samples);
+        let true_peak = meter.levels().true_peak;
+
+        assert!(sample_peak < 0.36);
+        assert!((true_peak - 0.5).abs() < 0.03, "true peak {}", true_peak);
+    }
+
+    #[test]
+    fn test_reset_clears_integration() {
+        let mut meter = LoudnessMeter::new(RATE);
+        meter.process(&sine(1000.0, db(-10.0), 2.0, 0.0));
+        meter.reset();
+        assert_eq!(meter.levels(), LoudnessLevels::default());
+
+        meter.process(&sine(1000.0, db(-30.0), 2.0, 0.0));
+        assert!((meter.levels().integrated + 30.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_shared_loudness_round_trip() {
+        let shared = SharedLoudness::new();
+        let levels = LoudnessLevels {
+            momentary: -14.0,
+            short_term: -15.0,
+            integrated: -16.0,
+            range: 6.0,
+            true_peak: 0.9,
+        };
+        shared.store(levels);
+        assert_eq!(shared.load(), levels);
+
+        shared.request_reset();
+        assert_eq!(shared.load(), LoudnessLevels::default());
+        assert!(shared.take_reset());
+        assert!(!shared.take_reset());
+    }
+}
@@ -4,13 +4,26 @@
 
 //! Audio subsystem for the daemon - PipeWire integration.
 
+pub mod auto_level;
+pub mod backend;
+pub mod ducking;
+pub mod eq_filter;
+pub mod fade;
+#[cfg(test)]
+pub mod fake_graph;
+pub mod latency;
+pub mod loudness;
+pub mod monitor;
 pub mod native_loopback;
-pub mod noise_filter;
+pub mod noise_suppression;
 pub mod pipewire_thread;
+pub mod plugin_stream;
 pub mod pulse_meter;
+pub mod record_stream;
 pub mod routing;
+pub mod spectrum;
+pub mod talk_gate;
 pub mod types;
 pub mod virtual_sink;
-pub mod volume;
 
 pub use native_loopback::AtomicMeterLevels;
@@ -0,0 +1,196 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Monitor bus for solo and sidetone.
+//!
+//! The bus is a stereo virtual sink (`sootmix._monitor`) whose loopback
+//! output the service links to the monitor device, so the monitor level and
+//! mute live on a node the daemon owns. Soloed channels are tapped into the
+//! bus sink: inputs from their virtual source, outputs from their sink's
+//! monitor ports, which sit before the channel fader. Sidetone gets one
+//! small virtual sink per input channel in front of the bus so each mic's
+//! sidetone level is independent of its channel volume.
+//!
+//! Stages are created when something needs them and destroyed when nothing
+//! does; the service reconciles the stages and their links against the
+//! graph after every relevant change (see `DaemonService::sync_monitor`).
+
+use sootmix_ipc::SoloMode;
+use uuid::Uuid;
+
+/// Channel name of the monitor bus sink (`sootmix._monitor`).
+pub const MONITOR_BUS_NAME: &str = "_monitor";
+
+/// A virtual sink owned by the monitor bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MonitorStage {
+    /// The bus itself, feeding the monitor device.
+    Bus,
+    /// Sidetone level stage of an input channel, feeding the bus.
+    Sidetone(Uuid),
+}
+
+impl MonitorStage {
+    /// Name passed to `spawn_virtual_sink` (the node becomes `sootmix.{name}`).
+    pub fn name(&self) -> String {
+        match self {
+            Self::Bus => MONITOR_BUS_NAME.to_string(),
+            Self::Sidetone(channel_id) => {
+                format!("_sidetone-{}", &channel_id.simple().to_string()[..8])
+            }
+        }
+    }
+
+    /// Human-readable node description.
+    pub fn description(&self) -> String {
+        match self {
+            Self::Bus => "SootMix Monitor".to_string(),
+            Self::Sidetone(_) => "SootMix Sidetone".to_string(),
+        }
+    }
+}
+
+/// Node IDs of a created monitor stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StageNodes {
+    pub sink: u32,
+    /// Loopback output.
+    pub output: u32,
+}
+
+/// What the solo state means for each channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SoloPlan {
+    /// Channels tapped into the monitor bus.
+    pub monitored: Vec<Uuid>,
+    /// Output channels silenced by solo-in-place.
+    pub cut: Vec<Uuid>,
+}
+
+/// Work out which channels feed the monitor bus and which are cut.
+///
+/// `channels` yields `(id, is_input, solo)`. In PFL mode every soloed channel
+/// is heard on the monitor bus and the main mix is untouched. In solo-in-place
+/// mode soloed output channels stay in the main mix while every other output
+/// channel is cut; soloed inputs, which have no place in the main mix, are
+/// still heard on the monitor bus.
+pub fn plan_solo(
+    mode: SoloMode,
+    channels: impl IntoIterator<Item = (Uuid, bool, bool)>,
+) -> SoloPlan {
+    let channels: Vec<_> = channels.into_iter().collect();
+    let mut plan = SoloPlan::default();
+
+    match mode {
+        SoloMode::Pfl => {
+            plan.monitored = channels
+                .iter()
+                .filter(|&&(_, _, solo)| solo)
+                .map(|&(id, _, _)| id)
+                .collect();
+        }
+        SoloMode::InPlace => {
+            plan.monitored = channels
+                .iter()
+                .filter(|&&(_, is_input, solo)| is_input && solo)
+                .map(|&(id, _, _)| id)
+                .collect();
+            let any_output_solo = channels
+                .iter()
+                .any(|&(_, is_input, solo)| !is_input && solo);
+            if any_output_solo {
+                plan.cut = channels
+                    .iter()
+                    .filter(|&&(_, is_input, solo)| !is_input && !solo)
+                    .map(|&(id, _, _)| id)
+                    .collect();
+            }
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids() -> [Uuid; 4] {
+        [
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        ]
+    }
+
+    #[test]
+    fn test_pfl_monitors_soloed_channels() {
+        let [game, music, chat, mic] = ids();
+        let plan = plan_solo(
+            SoloMode::Pfl,
+            [
+                (game, false, true),
+                (music, false, false),
+                (chat, false, false),
+                (mic, true, true),
+            ],
+        );
+
+        assert_eq!(plan.monitored, vec![game, mic]);
+        assert!(plan.cut.is_empty());
+    }
+
+    #[test]
+    fn test_in_place_cuts_other_outputs() {
+        let [game, music, chat, mic] = ids();
+        let plan = plan_solo(
+            SoloMode::InPlace,
+            [
+                (game, false, true),
+                (music, false, false),
+                (chat, false, false),
+                (mic, true, false),
+            ],
+        );
+
+        assert!(plan.monitored.is_empty());
+        assert_eq!(plan.cut, vec![music, chat]);
+    }
+
+    #[test]
+    fn test_in_place_input_solo_only_monitors() {
+        let [game, music, _, mic] = ids();
+        let plan = plan_solo(
+            SoloMode::InPlace,
+            [
+                (game, false, false),
+                (music, false, false),
+                (mic, true, true),
+            ],
+        );
+
+        assert_eq!(plan.monitored, vec![mic]);
+        assert!(plan.cut.is_empty());
+    }
+
+    #[test]
+    fn test_no_solo_is_a_no_op() {
+        let [game, _, _, mic] = ids();
+        for mode in SoloMode::ALL {
+            let plan = plan_solo(mode, [(game, false, false), (mic, true, false)]);
+            assert_eq!(plan, SoloPlan::default());
+        }
+    }
+
+    #[test]
+    fn test_stage_names_are_distinct() {
+        let [a, b, _, _] = ids();
+        assert_eq!(MonitorStage::Bus.name(), MONITOR_BUS_NAME);
+        assert_ne!(
+            MonitorStage::Sidetone(a).name(),
+            MonitorStage::Sidetone(b).name()
+        );
+    }
+}
@@ -28,18 +28,19 @@
 
 use pipewire::properties::properties;
 use pipewire::stream::{Stream, StreamFlags, StreamListener, StreamRc};
+use sootmix_ipc::ChannelLayout;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
-use tracing::{debug, info, trace, warn};
+use tracing::{debug, info, trace};
 use uuid::Uuid;
 
 /// Size of the audio buffer between capture and playback streams (in frames).
 const AUDIO_BUFFER_FRAMES: usize = 2048;
 
-/// Number of audio channels (stereo).
-const NUM_CHANNELS: usize = 2;
+/// Number of audio channels of virtual sources (stereo).
+const SOURCE_CHANNELS: usize = 2;
 
 // ============================================================================
 // AUDIO RING BUFFER
@@ -50,8 +51,10 @@ const NUM_CHANNELS: usize = 2;
 /// This is a fixed-size buffer optimized for the typical case where
 /// capture and playback are synchronized.
 struct AudioRingBuffer {
-    /// Interleaved stereo samples [L0, R0, L1, R1, ...].
+    /// Interleaved samples, one per channel per frame [L0, R0, L1, R1, ...].
     buffer: Vec<f32>,
+    /// Channels per frame.
+    channels: usize,
     /// Write position.
     write_pos: usize,
     /// Read position.
@@ -61,45 +64,42 @@ struct AudioRingBuffer {
 }
 
 impl AudioRingBuffer {
-    fn new(capacity_frames: usize) -> Self {
+    fn new(capacity_frames: usize, channels: usize) -> Self {
         Self {
-            buffer: vec![0.0; capacity_frames * NUM_CHANNELS],
+            buffer: vec![0.0; capacity_frames * channels],
+            channels,
             write_pos: 0,
             read_pos: 0,
             capacity_frames,
         }
     }
 
-    /// Write interleaved stereo samples to the buffer.
+    /// Write interleaved samples to the buffer.
     fn write(&mut self, samples: &[f32]) {
-        let frames = samples.len() / NUM_CHANNELS;
-        for i in 0..frames {
-            let idx = (self.write_pos % self.capacity_frames) * NUM_CHANNELS;
-            self.buffer[idx] = samples[i * NUM_CHANNELS];
-            self.buffer[idx + 1] = samples[i * NUM_CHANNELS + 1];
+        let channels = self.channels;
+        for frame in samples.chunks_exact(channels) {
+            let idx = (self.write_pos % self.capacity_frames) * channels;
+            self.buffer[idx..idx + channels].copy_from_slice(frame);
             self.write_pos = self.write_pos.wrapping_add(1);
         }
     }
 
-    /// Read interleaved stereo samples from the buffer.
+    /// Read interleaved samples from the buffer.
     /// Returns the number of frames actually read.
     fn read(&mut self, samples: &mut [f32]) -> usize {
-        let requested_frames = samples.len() / NUM_CHANNELS;
+        let channels = self.channels;
+        let requested_frames = samples.len() / channels;
         let available = self.write_pos.wrapping_sub(self.read_pos);
         let frames_to_read = requested_frames.min(available);
 
-        for i in 0..frames_to_read {
-            let idx = (self.read_pos % self.capacity_frames) * NUM_CHANNELS;
-            samples[i * NUM_CHANNELS] = self.buffer[idx];
-            samples[i * NUM_CHANNELS + 1] = self.buffer[idx + 1];
+        for frame in samples.chunks_exact_mut(channels).take(frames_to_read) {
+            let idx = (self.read_pos % self.capacity_frames) * channels;
+            frame.copy_from_slice(&self.buffer[idx..idx + channels]);
             self.read_pos = self.read_pos.wrapping_add(1);
         }
 
         // Zero-fill if not enough data
-        for i in frames_to_read..requested_frames {
-            samples[i * NUM_CHANNELS] = 0.0;
-            samples[i * NUM_CHANNELS + 1] = 0.0;
-        }
+        samples[frames_to_read * channels..requested_frames * channels].fill(0.0);
 
         frames_to_read
     }
@@ -150,6 +150,8 @@ struct LoopbackUserData {
     meter_levels: Arc<AtomicMeterLevels>,
     /// Whether this is the capture (true) or playback (false) stream.
     is_capture: bool,
+    /// Interleaved channels per frame.
+    channels: usize,
     /// Channel ID for logging.
     channel_id: Uuid,
 }
@@ -191,23 +193,31 @@ impl NativeLoopback {
     /// # Arguments
     /// * `target_device` - Optional target device name. If provided, the playback stream
     ///   will be routed to this device. If None, uses system default.
+    /// * `layout` - Speaker layout of both streams.
     pub fn new_sink(
         core: &pipewire::core::CoreRc,
         channel_id: Uuid,
         name: &str,
         description: &str,
         target_device: Option<&str>,
+        layout: ChannelLayout,
     ) -> Result<Self, pipewire::Error> {
         let sink_name = format!("sootmix.{}", name);
         let output_name = format!("sootmix.{}.output", name);
 
         info!(
-            "Creating native virtual sink: {} ({}) target={:?}",
-            sink_name, channel_id, target_device
+            "Creating native virtual sink: {} ({}) target={:?} layout={}",
+            sink_name, channel_id, target_device, layout
         );
 
+        let channels = layout.channels();
+        let positions = layout.positions().join(",");
+
         // Shared state
-        let audio_buffer = Rc::new(RefCell::new(AudioRingBuffer::new(AUDIO_BUFFER_FRAMES)));
+        let audio_buffer = Rc::new(RefCell::new(AudioRingBuffer::new(
+            AUDIO_BUFFER_FRAMES,
+            channels,
+        )));
         let meter_levels = Arc::new(AtomicMeterLevels::new());
 
         // Create capture stream (Audio/Sink - apps play to this)
@@ -219,8 +229,8 @@ impl NativeLoopback {
                 "media.class" => "Audio/Sink",
                 "node.name" => sink_name.clone(),
                 "node.description" => description,
-                "audio.channels" => "2",
-                "audio.position" => "FL,FR",
+                "audio.channels" => channels.to_string(),
+                "audio.position" => positions.clone(),
                 "priority.session" => "2000"
             },
         )?;
@@ -232,8 +242,8 @@ impl NativeLoopback {
             "media.class" => "Stream/Output/Audio",
             "node.name" => output_name.clone(),
             "node.description" => format!("{} Output", description),
-            "audio.channels" => "2",
-            "audio.position" => "FL,FR",
+            "audio.channels" => channels.to_string(),
+            "audio.position" => positions,
             "stream.autoconnect" => "true",
             "factory.mode" => "merge",
             "audio.adapt.follower" => ""
@@ -256,6 +266,7 @@ impl NativeLoopback {
             audio_buffer: Rc::clone(&audio_buffer),
             meter_levels: Arc::clone(&meter_levels),
             is_capture: true,
+            channels,
             channel_id,
         };
 
@@ -275,6 +286,7 @@ impl NativeLoopback {
             audio_buffer: Rc::clone(&audio_buffer),
             meter_levels: Arc::clone(&meter_levels),
             is_capture: false,
+            channels,
             channel_id,
         };
 
@@ -320,7 +332,10 @@ impl NativeLoopback {
         );
 
         // Shared state
-        let audio_buffer = Rc::new(RefCell::new(AudioRingBuffer::new(AUDIO_BUFFER_FRAMES)));
+        let audio_buffer = Rc::new(RefCell::new(AudioRingBuffer::new(
+            AUDIO_BUFFER_FRAMES,
+            SOURCE_CHANNELS,
+        )));
         let meter_levels = Arc::new(AtomicMeterLevels::new());
 
         // Build capture properties - target specific device if provided
@@ -363,6 +378,7 @@ impl NativeLoopback {
             audio_buffer: Rc::clone(&audio_buffer),
             meter_levels: Arc::clone(&meter_levels),
             is_capture: true,
+            channels: SOURCE_CHANNELS,
             channel_id,
         };
 
@@ -382,6 +398,7 @@ impl NativeLoopback {
             audio_buffer: Rc::clone(&audio_buffer),
             meter_levels: Arc::clone(&meter_levels),
             is_capture: false,
+            channels: SOURCE_CHANNELS,
             channel_id,
         };
 
@@ -529,83 +546,12 @@ impl NativeLoopback {
         self.connected.load(Ordering::Relaxed)
     }
 
-    /// Re-route the loopback output to a different target device.
-    ///
-    /// For output channels (sinks), this changes where the playback stream routes to.
-    /// Uses WirePlumber metadata to change the target without disconnecting.
-    ///
-    /// # Arguments
-    /// * `target_node_id` - The node ID of the target device (or None for system default)
-    pub fn reroute_to_device(&self, target_node_id: Option<u32>) -> Result<(), String> {
-        if self.is_source {
-            // For input channels, we'd need to change the capture target
-            // This is more complex - for now, just log
-            warn!(
-                "Re-routing input channel {} not yet implemented via native API",
-                self.channel_id
-            );
-            return Err("Input channel re-routing not implemented".to_string());
-        }
-
-        let playback_node_id = self.playback_stream.node_id();
-        if playback_node_id == u32::MAX {
-            return Err("Playback stream has no valid node ID".to_string());
-        }
-
-        info!(
-            "Re-routing loopback {} (node {}) to device {:?}",
-            self.channel_id, playback_node_id, target_node_id
-        );
-
-        set_playback_target_node(playback_node_id, target_node_id)
-    }
-
     /// Get the playback stream node ID (for routing operations).
     pub fn playback_node_id(&self) -> u32 {
         self.playback_stream.node_id()
     }
 }
 
-/// Set (or clear) the `target.node` WirePlumber metadata for a playback stream.
-///
-/// Runs `pw-metadata` and blocks on the subprocess; callers that must not
-/// stall the PipeWire main loop should invoke this from a background thread.
-pub fn set_playback_target_node(
-    playback_node_id: u32,
-    target_node_id: Option<u32>,
-) -> Result<(), String> {
-    let args: Vec<String> = if let Some(target_id) = target_node_id {
-        vec![
-            "-n".to_string(),
-            "default".to_string(),
-            playback_node_id.to_string(),
-            "target.node".to_string(),
-            format!("{{ \"name\": \"target.node\", \"value\": {} }}", target_id),
-        ]
-    } else {
-        vec![
-            "-n".to_string(),
-            "default".to_string(),
-            "-d".to_string(),
-            playback_node_id.to_string(),
-            "target.node".to_string(),
-        ]
-    };
-
-    let output = std::process::Command::new("pw-metadata")
-        .args(&args)
-        .output()
-        .map_err(|e| format!("Failed to run pw-metadata: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        // Not fatal - WirePlumber may still route correctly via AUTOCONNECT
-        debug!("pw-metadata returned non-zero: {}", stderr);
-    }
-
-    Ok(())
-}
-
 // ============================================================================
 // PROCESS CALLBACK
 // ============================================================================
@@ -625,9 +571,10 @@ fn process_callback(stream: &Stream, user_data: &mut LoopbackUserData) {
         return;
     }
 
+    let channels = user_data.channels;
     let data = &mut datas[0];
     let chunk = data.chunk();
-    let n_frames = chunk.size() as usize / (NUM_CHANNELS * std::mem::size_of::<f32>());
+    let n_frames = chunk.size() as usize / (channels * std::mem::size_of::<f32>());
 
     if n_frames == 0 {
         return;
@@ -639,12 +586,12 @@ fn process_callback(stream: &Stream, user_data: &mut LoopbackUserData) {
     };
 
     let samples: &mut [f32] = unsafe {
-        std::slice::from_raw_parts_mut(audio_data.as_mut_ptr() as *mut f32, n_frames * NUM_CHANNELS)
+        std::slice::from_raw_parts_mut(audio_data.as_mut_ptr() as *mut f32, n_frames * channels)
     };
 
     if user_data.is_capture {
         // Capture: calculate peaks and write to buffer
-        let (peak_left, peak_right) = calculate_stereo_peaks(samples);
+        let (peak_left, peak_right) = calculate_peaks(samples, channels);
         user_data.meter_levels.store(peak_left, peak_right);
         user_data.audio_buffer.borrow_mut().write(samples);
     } else {
@@ -661,21 +608,24 @@ fn process_callback(stream: &Stream, user_data: &mut LoopbackUserData) {
 
     // Update chunk for output
     let chunk_mut = data.chunk_mut();
-    *chunk_mut.size_mut() = (n_frames * NUM_CHANNELS * std::mem::size_of::<f32>()) as u32;
+    *chunk_mut.size_mut() = (n_frames * channels * std::mem::size_of::<f32>()) as u32;
     *chunk_mut.offset_mut() = 0;
-    *chunk_mut.stride_mut() = (NUM_CHANNELS * std::mem::size_of::<f32>()) as i32;
+    *chunk_mut.stride_mut() = (channels * std::mem::size_of::<f32>()) as i32;
 }
 
-/// Calculate stereo peak levels from interleaved samples.
+/// Calculate left/right peak levels from interleaved samples.
+///
+/// The meter follows the front pair (the first two channels of every
+/// layout); a mono stream reads the same level on both sides.
 #[inline]
-fn calculate_stereo_peaks(samples: &[f32]) -> (f32, f32) {
+fn calculate_peaks(samples: &[f32], channels: usize) -> (f32, f32) {
     let mut peak_left: f32 = 0.0;
     let mut peak_right: f32 = 0.0;
+    let right = if channels > 1 { 1 } else { 0 };
 
-    let mut i = 0;
-    while i + 1 < samples.len() {
-        let left_abs = samples[i].abs();
-        let right_abs = samples[i + 1].abs();
+    for frame in samples.chunks_exact(channels) {
+        let left_abs = frame[0].abs();
+        let right_abs = frame[right].abs();
 
         if left_abs > peak_left {
             peak_left = left_abs;
@@ -683,8 +633,6 @@ fn calculate_stereo_peaks(samples: &[f32]) -> (f32, f32) {
         if right_abs > peak_right {
             peak_right = right_abs;
         }
-
-        i += 2;
     }
 
     (peak_left, peak_right)
@@ -696,7 +644,7 @@ mod tests {
 
     #[test]
     fn test_audio_ring_buffer() {
-        let mut buffer = AudioRingBuffer::new(16);
+        let mut buffer = AudioRingBuffer::new(16, 2);
 
         // Write some samples
         let input = vec![1.0, 2.0, 3.0, 4.0]; // 2 frames
@@ -711,7 +659,7 @@ mod tests {
 
     #[test]
     fn test_audio_ring_buffer_underrun() {
-        let mut buffer = AudioRingBuffer::new(16);
+        let mut buffer = AudioRingBuffer::new(16, 2);
 
         // Write 1 frame
         buffer.write(&[1.0, 2.0]);
@@ -726,14 +674,41 @@ mod tests {
         assert_eq!(output[3], 0.0);
     }
 
+    #[test]
+    fn test_audio_ring_buffer_surround() {
+        let mut buffer = AudioRingBuffer::new(4, 6);
+
+        let input: Vec<f32> = (0..12).map(|i| i as f32).collect(); // 2 frames
+        buffer.write(&input);
+
+        let mut output = vec![-1.0; 18];
+        let frames = buffer.read(&mut output);
+        assert_eq!(frames, 2);
+        assert_eq!(output[..12], input[..]);
+        assert!(output[12..].iter().all(|&s| s == 0.0));
+    }
+
     #[test]
     fn test_stereo_peaks() {
         let samples = vec![0.1, 0.2, -0.5, 0.3, 0.4, -0.8];
-        let (left, right) = calculate_stereo_peaks(&samples);
+        let (left, right) = calculate_peaks(&samples, 2);
         assert!((left - 0.5).abs() < 0.001);
         assert!((right - 0.8).abs() < 0.001);
     }
 
+    #[test]
+    fn test_surround_and_mono_peaks() {
+        // Only the front pair is metered
+        let samples = vec![0.1, 0.2, 0.9, 0.9, 0.9, 0.9, -0.3, 0.1, 0.9, 0.9, 0.9, 0.9];
+        let (left, right) = calculate_peaks(&samples, 6);
+        assert!((left - 0.3).abs() < 0.001);
+        assert!((right - 0.2).abs() < 0.001);
+
+        let (left, right) = calculate_peaks(&[0.25, -0.5], 1);
+        assert_eq!(left, right);
+        assert!((left - 0.5).abs() < 0.001);
+    }
+
     #[test]
     fn test_atomic_meter_levels() {
         let levels = AtomicMeterLevels::new();
@@ -0,0 +1,195 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! In-process noise suppression for input channels.
+//!
+//! The suppressor of the `sootmix-rnnoise-ladspa` crate runs at the front of
+//! an input channel's plugin filter, so the mic is denoised before the plugin
+//! chain sees it:
+//!
+//! ```text
+//! [Mic] → [Plugin Sink] → [RNNoise → Plugin Chain] → [Plugin Output] → [Capture] → [Source]
+//! ```
+//!
+//! It delays the audio by one 10 ms frame plus the retroactive VAD grace.
+//! Settings are swapped on the running filter; the channel's source node is
+//! never recreated. Each block publishes the voice probability to the
+//! channel's [`VoiceActivity`], which the service turns into "talking"
+//! signals.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+pub use sootmix_rnnoise_ladspa::suppressor::{Model, Settings as NoiseSuppression, Suppressor};
+
+/// Number of audio channels (stereo, like the plugin filter).
+const NUM_CHANNELS: usize = 2;
+
+/// Voice activity of an input channel, written by the RT thread.
+#[derive(Debug, Default)]
+pub struct VoiceActivity {
+    /// Voice probability of the last frame (0.0-1.0), as `f32` bits.
+    probability: AtomicU32,
+    /// Whether the voice gate is open.
+    talking: AtomicBool,
+}
+
+impl VoiceActivity {
+    pub fn probability(&self) -> f32 {
+        f32::from_bits(self.probability.load(Ordering::Relaxed))
+    }
+
+    pub fn is_talking(&self) -> bool {
+        self.talking.load(Ordering::Relaxed)
+    }
+
+    fn store(&self, probability: f32, talking: bool) {
+        self.probability
+            .store(probability.to_bits(), Ordering::Relaxed);
+        self.talking.store(talking, Ordering::Relaxed);
+    }
+}
+
+/// Allocate a stereo denoiser for [`NoiseSuppressor::set_suppressor`].
+pub fn new_suppressor() -> Suppressor {
+    Suppressor::new(NUM_CHANNELS, NoiseSuppression::default())
+}
+
+/// Noise suppression over a stereo block, in place.
+pub struct NoiseSuppressor {
+    /// Current settings; `None` passes audio through untouched.
+    settings: Option<NoiseSuppression>,
+    /// Given by the owner the first time suppression is enabled.
+    suppressor: Option<Suppressor>,
+    /// Where the voice probability is published.
+    activity: Option<Arc<VoiceActivity>>,
+}
+
+impl NoiseSuppressor {
+    pub fn new() -> Self {
+        Self {
+            settings: None,
+            suppressor: None,
+            activity: None,
+        }
+    }
+
+    /// Publish voice activity to `activity` from now on.
+    pub fn set_voice_activity(&mut self, activity: Option<Arc<VoiceActivity>>) {
+        self.activity = activity;
+    }
+
+    /// Give the suppressor its denoiser, allocated with [`new_suppressor`]
+    /// off the RT thread. Returns the one it replaces.
+    pub fn set_suppressor(&mut self, mut suppressor: Suppressor) -> Option<Suppressor> {
+        if let Some(settings) = self.settings {
+            suppressor.set_settings(settings);
+        }
+        self.suppressor.replace(suppressor)
+    }
+
+    /// Change the settings (`None` to bypass).
+    ///
+    /// Never allocates: suppression is bypassed until a suppressor is set.
+    /// Turning suppression on starts from silence rather than replaying
+    /// audio buffered before it was last turned off.
+    pub fn set_settings(&mut self, settings: Option<NoiseSuppression>) {
+        match (settings, &mut self.suppressor) {
+            (Some(settings), Some(suppressor)) => {
+                if self.settings.is_none() {
+                    suppressor.reset();
+                }
+                suppressor.set_settings(settings);
+            }
+            (Some(_), None) => {}
+            (None, _) => {
+                if let Some(activity) = &self.activity {
+                    activity.store(0.0, false);
+                }
+            }
+        }
+        self.settings = settings;
+    }
+
+    /// Denoise a block in place.
+    pub fn process(&mut self, block: &mut [&mut [f32]; 2]) {
+        if self.settings.is_none() {
+            return;
+        }
+        let Some(suppressor) = &mut self.suppressor else {
+            return;
+        };
+        suppressor.process(block);
+        if let Some(activity) = &self.activity {
+            activity.store(suppressor.voice_probability(), suppressor.is_open());
+        }
+    }
+}
+
+impl Default for NoiseSuppressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(suppressor: &mut NoiseSuppressor, left: &mut [f32], right: &mut [f32]) {
+        suppressor.process(&mut [left, right]);
+    }
+
+    #[test]
+    fn test_disabled_passes_through() {
+        let mut suppressor = NoiseSuppressor::new();
+        let mut left = [0.5; 64];
+        let mut right = [-0.5; 64];
+        process(&mut suppressor, &mut left, &mut right);
+
+        assert_eq!(left, [0.5; 64]);
+        assert_eq!(right, [-0.5; 64]);
+    }
+
+    #[test]
+    fn test_enabled_without_suppressor_passes_through() {
+        let mut suppressor = NoiseSuppressor::new();
+        suppressor.set_settings(Some(NoiseSuppression::default()));
+
+        let mut left = [0.5; 64];
+        let mut right = [-0.5; 64];
+        process(&mut suppressor, &mut left, &mut right);
+        assert_eq!(left, [0.5; 64]);
+    }
+
+    #[test]
+    fn test_output_is_delayed() {
+        let mut suppressor = NoiseSuppressor::new();
+        suppressor.set_suppressor(new_suppressor());
+        suppressor.set_settings(Some(NoiseSuppression::default()));
+
+        // Nothing is out of the denoiser until the first frame is full
+        let mut left = vec![0.5; 256];
+        let mut right = vec![0.5; 256];
+        process(&mut suppressor, &mut left, &mut right);
+        assert!(left.iter().chain(&right).all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_silence_is_not_talking() {
+        let activity = Arc::new(VoiceActivity::default());
+        let mut suppressor = NoiseSuppressor::new();
+        suppressor.set_voice_activity(Some(activity.clone()));
+        suppressor.set_suppressor(new_suppressor());
+        suppressor.set_settings(Some(NoiseSuppression::default()));
+
+        for _ in 0..4 {
+            let mut left = vec![0.0; 256];
+            let mut right = vec![0.0; 256];
+            process(&mut suppressor, &mut left, &mut right);
+            assert!(left.iter().chain(&right).all(|&s| s == 0.0));
+        }
+        assert!(!activity.is_talking());
+    }
+}
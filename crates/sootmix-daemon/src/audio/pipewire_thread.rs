@@ -4,8 +4,17 @@
 
 //! PipeWire thread management and event handling.
 
+use crate::audio::loudness::SharedLoudness;
 use crate::audio::native_loopback::{AtomicMeterLevels, NativeLoopback};
+use crate::audio::noise_suppression::{NoiseSuppression, VoiceActivity};
+use crate::audio::plugin_stream::{
+    plugin_output_node_name, plugin_sink_node_name, PluginFilterStreams,
+};
 use crate::audio::pulse_meter::PulseAudioMeter;
+use crate::audio::record_stream::{record_node_name, replay_node_name, CaptureSink, RecordStream};
+use crate::audio::routing::{self, DOWNMIX_GAIN};
+use crate::audio::spectrum::SpectrumTapPoint;
+use crate::audio::talk_gate::TalkGateSettings;
 use crate::audio::types::{AudioChannel, MediaClass, PortDirection, PwLink, PwNode, PwPort};
 use pipewire::link::Link;
 use pipewire::metadata::{Metadata, MetadataListener};
@@ -13,10 +22,11 @@ use pipewire::node::{Node, NodeListener};
 use pipewire::properties::properties;
 use pipewire::spa::param::ParamType;
 use pipewire::spa::pod::Pod;
+use sootmix_ipc::{Balance, ChannelLayout, SpectrumTap};
 use std::cell::RefCell;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::rc::Rc;
-use std::sync::{mpsc, Arc, Mutex};
+use std::sync::{mpsc, Arc};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 use thiserror::Error;
@@ -31,6 +41,8 @@ pub enum PwCommand {
         name: String,
         /// Target device name for routing (uses system default if None)
         target_device: Option<String>,
+        /// Speaker layout of the sink and its loopback output.
+        layout: ChannelLayout,
     },
     CreateVirtualSource {
         channel_id: Uuid,
@@ -41,6 +53,11 @@ pub enum PwCommand {
     DestroyVirtualSink {
         node_id: u32,
     },
+    /// Destroy a node through the registry, e.g. one left over from a
+    /// previous run.
+    DestroyNode {
+        node_id: u32,
+    },
     UpdateSinkDescription {
         node_id: u32,
         description: String,
@@ -68,9 +85,22 @@ pub enum PwCommand {
         node_id: u32,
         muted: bool,
     },
+    /// Set a node's pan and width; later `SetVolume`s are spread over its
+    /// speakers accordingly.
+    SetBalance {
+        node_id: u32,
+        balance: Balance,
+    },
     SetDefaultSink {
         node_id: u32,
     },
+    /// Set or clear (`None`) a stream's `target.node` metadata, which tells
+    /// WirePlumber where to route it and stops it from relinking the stream
+    /// to the default sink.
+    SetStreamTarget {
+        stream_node_id: u32,
+        target_node_id: Option<u32>,
+    },
     RouteChannelToDevice {
         loopback_output_node: u32,
         target_device_id: Option<u32>,
@@ -83,16 +113,96 @@ pub enum PwCommand {
     DestroyRecordingSource {
         node_id: u32,
     },
-    /// Create a native noise filter for an input channel.
-    CreateNativeNoiseFilter {
+    /// Create an EQ filter for an output channel.
+    CreateEqFilter {
         channel_id: Uuid,
         name: String,
-        target_mic: Option<String>,
-        vad_threshold: f32,
+        bands: Vec<crate::config::EqBand>,
+    },
+    /// Apply new band settings to a running EQ filter of the same layout.
+    UpdateEqFilter {
+        sink_node_id: u32,
+        bands: Vec<crate::config::EqBand>,
+    },
+    /// Destroy a channel's EQ filter.
+    DestroyEqFilter {
+        channel_id: Uuid,
+    },
+    /// Create the plugin chain streams for a channel.
+    CreatePluginFilter {
+        channel_id: Uuid,
+        name: String,
+        plugin_instances: sootmix_plugin_host::SharedPluginInstances,
+        /// Active (non-bypassed) instance IDs in processing order.
+        plugin_chain: Vec<Uuid>,
+        /// Noise suppression ahead of the chain (input channels).
+        noise_suppression: Option<NoiseSuppression>,
+        /// Where noise suppression publishes voice activity (input channels).
+        voice_activity: Option<Arc<VoiceActivity>>,
+        /// Push-to-talk gate after the chain (input channels).
+        talk_gate: Option<TalkGateSettings>,
+    },
+    /// Replace the active plugin chain of a running plugin filter.
+    UpdatePluginChain {
+        channel_id: Uuid,
+        plugin_chain: Vec<Uuid>,
+    },
+    /// Change a running plugin filter's noise suppression (`None` to bypass).
+    SetNoiseSuppression {
+        channel_id: Uuid,
+        settings: Option<NoiseSuppression>,
     },
-    /// Destroy a native noise filter.
-    DestroyNativeNoiseFilter {
+    /// Key a running plugin filter's talk gate (`None` to let everything
+    /// through).
+    SetTalkGate {
         channel_id: Uuid,
+        settings: Option<TalkGateSettings>,
+    },
+    /// Set a plugin filter's compensation delay and reported latency.
+    SetPluginLatency {
+        channel_id: Uuid,
+        /// Delay added after the chain, in frames.
+        compensation: u32,
+        /// Chain latency plus `compensation`, reported to PipeWire.
+        reported: u32,
+    },
+    /// Destroy a channel's plugin chain streams.
+    DestroyPluginFilter {
+        channel_id: Uuid,
+    },
+    /// Create the capture stream for a recording.
+    CreateRecordStream {
+        recording_id: Uuid,
+        buffer: Arc<crate::recording::CaptureBuffer>,
+    },
+    /// Destroy a recording's capture stream.
+    DestroyRecordStream {
+        recording_id: Uuid,
+    },
+    /// Create the capture stream feeding a channel's replay buffer.
+    CreateReplayStream {
+        channel_id: Uuid,
+        feed: crate::recording::replay::ReplayFeed,
+    },
+    /// Destroy a channel's replay capture stream.
+    DestroyReplayStream {
+        channel_id: Uuid,
+    },
+    /// Create one of the monitor bus's virtual sinks.
+    CreateMonitorStage {
+        stage: crate::audio::monitor::MonitorStage,
+    },
+    /// Meter the loudness of the master output through its monitor source,
+    /// replacing any previous master meter. `None` stops master metering.
+    SetMasterMeter {
+        source_name: Option<String>,
+        loudness: Arc<SharedLoudness>,
+    },
+    /// Point the spectrum analyzer at a channel or the master output, or
+    /// stop it with `None`. The tap follows meters and plugin filters that
+    /// are created later.
+    SetSpectrumTap {
+        tap: Option<SpectrumTapPoint>,
     },
     /// Link an input channel's capture stream to a target microphone.
     /// This is needed because we use node.autoconnect=false to prevent WirePlumber
@@ -130,6 +240,8 @@ pub enum PwEvent {
         loopback_output_node_id: Option<u32>,
         /// Atomic meter levels for real-time level reading.
         meter_levels: Option<std::sync::Arc<crate::audio::native_loopback::AtomicMeterLevels>>,
+        /// Loudness readings of the same meter.
+        loudness: Option<Arc<SharedLoudness>>,
     },
     VirtualSourceCreated {
         channel_id: Uuid,
@@ -137,6 +249,8 @@ pub enum PwEvent {
         loopback_capture_node_id: Option<u32>,
         /// Atomic meter levels for real-time level reading.
         meter_levels: Option<std::sync::Arc<crate::audio::native_loopback::AtomicMeterLevels>>,
+        /// Loudness readings of the same meter.
+        loudness: Option<Arc<SharedLoudness>>,
     },
     VirtualSinkDestroyed {
         node_id: u32,
@@ -148,20 +262,63 @@ pub enum PwEvent {
     RecordingSourceDestroyed {
         node_id: u32,
     },
-    /// Native noise filter was created successfully.
-    NativeNoiseFilterCreated {
+    /// EQ filter was created successfully.
+    EqFilterCreated {
         channel_id: Uuid,
-        source_node_id: u32,
+        sink_node_id: u32,
+        output_node_id: u32,
     },
-    /// Native noise filter was destroyed.
-    NativeNoiseFilterDestroyed {
+    /// EQ filter was destroyed.
+    EqFilterDestroyed {
         channel_id: Uuid,
     },
-    /// Native noise filter creation failed.
-    NativeNoiseFilterFailed {
+    /// EQ filter creation failed.
+    EqFilterFailed {
         channel_id: Uuid,
         error: String,
     },
+    /// Plugin chain streams are up and both nodes are in the registry.
+    PluginFilterCreated {
+        channel_id: Uuid,
+        sink_node_id: u32,
+        output_node_id: u32,
+    },
+    /// Plugin chain stream creation failed.
+    PluginFilterFailed {
+        channel_id: Uuid,
+        error: String,
+    },
+    /// A recording's capture stream is up and its node is in the registry.
+    RecordStreamCreated {
+        recording_id: Uuid,
+        node_id: u32,
+    },
+    /// Recording capture stream creation failed.
+    RecordStreamFailed {
+        recording_id: Uuid,
+        error: String,
+    },
+    /// A channel's replay capture stream is up and its node is in the registry.
+    ReplayStreamCreated {
+        channel_id: Uuid,
+        node_id: u32,
+    },
+    /// Replay capture stream creation failed.
+    ReplayStreamFailed {
+        channel_id: Uuid,
+        error: String,
+    },
+    /// A monitor bus stage is up.
+    MonitorStageCreated {
+        stage: crate::audio::monitor::MonitorStage,
+        sink_node_id: u32,
+        output_node_id: u32,
+    },
+    /// Monitor bus stage creation failed.
+    MonitorStageFailed {
+        stage: crate::audio::monitor::MonitorStage,
+        error: String,
+    },
     /// A RouteChannelToDevice command has completed (success or failure).
     RouteFinished {
         loopback_output_node: u32,
@@ -176,8 +333,12 @@ pub enum PwEvent {
     },
     /// WirePlumber's `default.audio.sink` metadata key changed.
     /// Sent when a hardware sink is promoted/demoted as the system default
-    /// (e.g. Bluetooth headset reconnect, USB audio replug).
-    DefaultSinkChanged,
+    /// (e.g. Bluetooth headset reconnect, USB audio replug), and once with
+    /// the current default when the metadata is bound.
+    DefaultSinkChanged {
+        /// Node name of the new default sink.
+        name: Option<String>,
+    },
     Error(String),
 }
 
@@ -214,13 +375,129 @@ struct CreatedLink {
     proxy: Link,
 }
 
-const CLI_THROTTLE_MS: u64 = 50;
+/// How long a spawned helper process's nodes, or the ports of a requested
+/// link, may take to appear in the registry.
+const PENDING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often pending spawns and links are checked against `PENDING_TIMEOUT`.
+const PENDING_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A helper process (pw-loopback or filter-chain) whose nodes haven't all
+/// appeared in the registry yet.
+struct PendingSpawn {
+    /// Node names the process registers, with their IDs once seen.
+    nodes: Vec<(String, Option<u32>)>,
+    started: Instant,
+    ready: SpawnReady,
+}
+
+/// What a spawned helper process is for, and so what to report once its
+/// nodes are up.
+enum SpawnReady {
+    /// Nodes: the sink, then its loopback output.
+    VirtualSink {
+        channel_id: Uuid,
+        meter_levels: Arc<AtomicMeterLevels>,
+        loudness: Arc<SharedLoudness>,
+    },
+    /// Nodes: the source, then its capture stream.
+    VirtualSource {
+        channel_id: Uuid,
+        meter_levels: Arc<AtomicMeterLevels>,
+        loudness: Arc<SharedLoudness>,
+    },
+    /// Nodes: the stage's sink, then its loopback output.
+    MonitorStage(crate::audio::monitor::MonitorStage),
+    /// Nodes: the source.
+    RecordingSource { name: String },
+    /// Nodes: the EQ sink, then its output stream.
+    EqFilter { channel_id: Uuid },
+}
+
+impl PendingSpawn {
+    fn new(names: impl IntoIterator<Item = String>, ready: SpawnReady) -> Self {
+        Self {
+            nodes: names.into_iter().map(|name| (name, None)).collect(),
+            started: Instant::now(),
+            ready,
+        }
+    }
+
+    /// Kill the process after its nodes failed to appear.
+    fn stop_process(&self) {
+        match &self.ready {
+            SpawnReady::VirtualSink { .. }
+            | SpawnReady::VirtualSource { .. }
+            | SpawnReady::MonitorStage(_)
+            | SpawnReady::RecordingSource { .. } => {
+                crate::audio::virtual_sink::stop_loopback(&self.nodes[0].0);
+            }
+            SpawnReady::EqFilter { channel_id } => {
+                let _ = crate::audio::eq_filter::destroy_eq_filter(*channel_id);
+            }
+        }
+    }
+}
+
+impl SpawnReady {
+    /// Event reporting the process up, given the IDs of its nodes.
+    fn ready_event(self, ids: &[u32]) -> PwEvent {
+        match self {
+            SpawnReady::VirtualSink {
+                channel_id,
+                meter_levels,
+                loudness,
+            } => PwEvent::VirtualSinkCreated {
+                channel_id,
+                node_id: ids[0],
+                loopback_output_node_id: Some(ids[1]),
+                meter_levels: Some(meter_levels),
+                loudness: Some(loudness),
+            },
+            SpawnReady::VirtualSource {
+                channel_id,
+                meter_levels,
+                loudness,
+            } => PwEvent::VirtualSourceCreated {
+                channel_id,
+                source_node_id: ids[0],
+                loopback_capture_node_id: Some(ids[1]),
+                meter_levels: Some(meter_levels),
+                loudness: Some(loudness),
+            },
+            SpawnReady::MonitorStage(stage) => PwEvent::MonitorStageCreated {
+                stage,
+                sink_node_id: ids[0],
+                output_node_id: ids[1],
+            },
+            SpawnReady::RecordingSource { name } => PwEvent::RecordingSourceCreated {
+                name,
+                node_id: ids[0],
+            },
+            SpawnReady::EqFilter { channel_id } => PwEvent::EqFilterCreated {
+                channel_id,
+                sink_node_id: ids[0],
+                output_node_id: ids[1],
+            },
+        }
+    }
 
-/// Pending CLI command that was throttled
-#[derive(Clone)]
-enum PendingCliCmd {
-    Volume(f32),
-    Mute(bool),
+    /// Event reporting the process failed to start.
+    fn failed_event(self, error: String) -> PwEvent {
+        match self {
+            SpawnReady::VirtualSink { .. } => {
+                PwEvent::Error(format!("Failed to create virtual sink: {}", error))
+            }
+            SpawnReady::VirtualSource { .. } => {
+                PwEvent::Error(format!("Failed to create virtual source: {}", error))
+            }
+            SpawnReady::MonitorStage(stage) => PwEvent::MonitorStageFailed { stage, error },
+            SpawnReady::RecordingSource { .. } => {
+                PwEvent::Error(format!("Failed to create recording source: {}", error))
+            }
+            SpawnReady::EqFilter { channel_id } => PwEvent::EqFilterFailed { channel_id, error },
+        }
+    }
 }
 
 struct PwThreadState {
@@ -231,25 +508,53 @@ struct PwThreadState {
     bound_nodes: HashMap<u32, BoundNode>,
     created_links: HashMap<(u32, u32), CreatedLink>,
     event_tx: Rc<mpsc::Sender<PwEvent>>,
-    cli_last_cmd: HashMap<u32, Instant>,
-    /// Pending CLI commands that were throttled - stores the latest value to apply
-    pending_cli_cmds: HashMap<u32, PendingCliCmd>,
-    /// Node IDs currently being processed by CLI background threads.
-    /// Prevents concurrent CLI operations on the same node.
-    cli_in_flight: Arc<Mutex<HashSet<u32>>>,
+    /// Links requested before both their ports were in the registry, with
+    /// when they were requested. Created as soon as the ports appear.
+    pending_links: Vec<(u32, u32, Instant)>,
+    /// Helper processes waiting for their nodes to appear.
+    pending_spawns: Vec<PendingSpawn>,
     /// Native loopback instances (replacing pw-loopback CLI).
     native_loopbacks: HashMap<Uuid, NativeLoopback>,
     /// PulseAudio-based meters for input channels.
     pulse_meters: HashMap<Uuid, PulseAudioMeter>,
+    /// Loudness meter on the master output's monitor source.
+    master_meter: Option<PulseAudioMeter>,
+    /// Where the spectrum analyzer listens, if it's running.
+    spectrum_tap: Option<SpectrumTapPoint>,
     /// Pending loopback node discovery: node_name -> (channel_id, is_main_node)
     /// Used to match nodes created by native loopbacks once they appear in registry.
     pending_loopback_nodes: HashMap<String, (Uuid, bool)>,
     /// Discovered node IDs for pending loopbacks: channel_id -> (main_node_id, secondary_node_id)
     /// For sinks: (sink_id, output_id), for sources: (source_id, capture_id)
     discovered_loopback_nodes: HashMap<Uuid, (Option<u32>, Option<u32>)>,
+    /// Plugin chain streams per channel.
+    plugin_filters: HashMap<Uuid, PluginFilterStreams>,
+    /// Pending plugin filter node discovery: node_name -> (channel_id, is_sink_node)
+    pending_plugin_nodes: HashMap<String, (Uuid, bool)>,
+    /// Discovered node IDs for pending plugin filters: channel_id -> (sink_id, output_id)
+    discovered_plugin_nodes: HashMap<Uuid, (Option<u32>, Option<u32>)>,
+    /// Capture streams of running recordings.
+    record_streams: HashMap<Uuid, RecordStream>,
+    /// Pending record stream node discovery: node_name -> recording_id
+    pending_record_nodes: HashMap<String, Uuid>,
+    /// Replay capture streams per channel.
+    replay_streams: HashMap<Uuid, RecordStream>,
+    /// Pending replay stream node discovery: node_name -> channel_id
+    pending_replay_nodes: HashMap<String, Uuid>,
     /// Bound "default" metadata object + listener. Kept here so the listener
     /// stays alive for the lifetime of the PW thread. `(global_id, proxy, listener)`.
     default_metadata: Option<(u32, Metadata, MetadataListener)>,
+    /// Node name of the default sink, from the `default` metadata.
+    default_sink_name: Option<String>,
+    /// Node name of the default source, from the `default` metadata.
+    default_source_name: Option<String>,
+    /// Pan/width per node (absent = centred).
+    node_balance: HashMap<u32, Balance>,
+    /// Last volume set per node, reapplied when its balance changes.
+    node_volumes: HashMap<u32, f32>,
+    /// Speaker positions of a node that are folded onto other positions of
+    /// the device it's routed to, played at `DOWNMIX_GAIN`.
+    node_downmix: HashMap<u32, Vec<AudioChannel>>,
 }
 
 impl PwThreadState {
@@ -262,81 +567,161 @@ impl PwThreadState {
             bound_nodes: HashMap::new(),
             created_links: HashMap::new(),
             event_tx,
-            cli_last_cmd: HashMap::new(),
-            pending_cli_cmds: HashMap::new(),
-            cli_in_flight: Arc::new(Mutex::new(HashSet::new())),
+            pending_links: Vec::new(),
+            pending_spawns: Vec::new(),
             native_loopbacks: HashMap::new(),
             pulse_meters: HashMap::new(),
+            master_meter: None,
+            spectrum_tap: None,
             pending_loopback_nodes: HashMap::new(),
             discovered_loopback_nodes: HashMap::new(),
+            plugin_filters: HashMap::new(),
+            pending_plugin_nodes: HashMap::new(),
+            discovered_plugin_nodes: HashMap::new(),
+            record_streams: HashMap::new(),
+            pending_record_nodes: HashMap::new(),
+            replay_streams: HashMap::new(),
+            pending_replay_nodes: HashMap::new(),
             default_metadata: None,
+            default_sink_name: None,
+            default_source_name: None,
+            node_balance: HashMap::new(),
+            node_volumes: HashMap::new(),
+            node_downmix: HashMap::new(),
         }
     }
 
-    /// Check if CLI command should run now, or if it should be deferred.
-    /// Returns true if the command can run immediately.
-    /// If throttled, the pending command is stored and will be retrieved later.
-    fn should_run_cli(&mut self, node_id: u32, cmd: PendingCliCmd) -> bool {
-        let now = Instant::now();
-        let throttle = Duration::from_millis(CLI_THROTTLE_MS);
-
-        if let Some(last) = self.cli_last_cmd.get(&node_id) {
-            if now.duration_since(*last) < throttle {
-                // Throttled - store the latest pending command
-                self.pending_cli_cmds.insert(node_id, cmd);
-                return false;
+    /// Hand the spectrum feed to the meter or plugin filter the analyzer
+    /// taps, and take it from all others. A post-plugin tap on a channel
+    /// without a plugin filter listens on its meter instead.
+    fn apply_spectrum_tap(&mut self) {
+        let (meter_tap, filter_tap) = match &self.spectrum_tap {
+            None => (None, None),
+            Some(tap) => {
+                let on_filter = tap.tap == SpectrumTap::Post
+                    && tap
+                        .channel_id
+                        .is_some_and(|id| self.plugin_filters.contains_key(&id));
+                if on_filter {
+                    (None, Some(tap))
+                } else {
+                    (Some(tap), None)
+                }
             }
+        };
+        let feed_for = |tap: Option<&SpectrumTapPoint>, channel_id: Option<Uuid>| {
+            tap.filter(|tap| tap.channel_id == channel_id)
+                .map(|tap| Arc::clone(&tap.feed))
+        };
+
+        for (id, meter) in &self.pulse_meters {
+            meter.set_spectrum(feed_for(meter_tap, Some(*id)));
+        }
+        if let Some(meter) = &self.master_meter {
+            meter.set_spectrum(feed_for(meter_tap, None));
+        }
+        for (id, filter) in &mut self.plugin_filters {
+            filter.set_spectrum(feed_for(filter_tap, Some(*id)));
         }
-        self.cli_last_cmd.insert(node_id, now);
-        // Clear any pending command since we're executing now
-        self.pending_cli_cmds.remove(&node_id);
-        true
     }
 
-    /// Check if throttle period has passed for any pending commands
-    fn get_ready_pending_cmds(&mut self) -> Vec<(u32, PendingCliCmd)> {
-        let now = Instant::now();
-        let throttle = Duration::from_millis(CLI_THROTTLE_MS);
-
-        let ready: Vec<u32> = self
-            .pending_cli_cmds
-            .keys()
-            .filter(|&node_id| {
-                self.cli_last_cmd
-                    .get(node_id)
-                    .map(|last| now.duration_since(*last) >= throttle)
-                    .unwrap_or(true)
-            })
-            .copied()
-            .collect();
+    /// ID of the node named `name`, if it's in the registry.
+    fn node_id_by_name(&self, name: &str) -> Option<u32> {
+        self.nodes.values().find(|n| n.name == name).map(|n| n.id)
+    }
 
-        ready
+    /// Wait for a spawned process's nodes, replacing any earlier spawn
+    /// waiting on the same names (its process has been stopped).
+    fn expect_spawn(&mut self, spawn: PendingSpawn) {
+        self.pending_spawns.retain(|pending| {
+            !pending
+                .nodes
+                .iter()
+                .any(|(name, _)| spawn.nodes.iter().any(|(other, _)| other == name))
+        });
+        self.pending_spawns.push(spawn);
+    }
+
+    /// Record a newly added node against the spawned process waiting for
+    /// it. Returns the process's ready event if that was its last node.
+    fn spawned_node_added(&mut self, node: &PwNode) -> Option<PwEvent> {
+        let index = self.pending_spawns.iter().position(|spawn| {
+            spawn
+                .nodes
+                .iter()
+                .any(|(name, id)| id.is_none() && *name == node.name)
+        })?;
+        let spawn = &mut self.pending_spawns[index];
+        if let Some(entry) = spawn
+            .nodes
+            .iter_mut()
+            .find(|(name, id)| id.is_none() && *name == node.name)
+        {
+            entry.1 = Some(node.id);
+        }
+        let ids: Vec<u32> = spawn
+            .nodes
             .iter()
-            .filter_map(|&node_id| {
-                self.pending_cli_cmds.remove(&node_id).map(|cmd| {
-                    self.cli_last_cmd.insert(node_id, now);
-                    (node_id, cmd)
-                })
-            })
-            .collect()
+            .map(|(_, id)| *id)
+            .collect::<Option<_>>()?;
+
+        let spawn = self.pending_spawns.remove(index);
+        info!(
+            "Spawned nodes ready: {:?}",
+            spawn.nodes.iter().map(|(name, _)| name).collect::<Vec<_>>()
+        );
+        Some(spawn.ready.ready_event(&ids))
     }
 
     fn get_node_for_port(&self, port_id: u32) -> Option<u32> {
         self.ports.get(&port_id).map(|p| p.node_id)
     }
 
+    /// Speaker positions of a node in channel-map order: its playback
+    /// ports for sinks, otherwise its output ports. Ports are created in
+    /// channel order, so global ID order matches the node's channel map.
+    fn node_positions(&self, node_id: u32) -> Vec<AudioChannel> {
+        let is_sink = self.nodes.get(&node_id).is_some_and(PwNode::is_sink);
+        let direction = if is_sink {
+            PortDirection::Input
+        } else {
+            PortDirection::Output
+        };
+        let mut ports: Vec<&PwPort> = self
+            .ports
+            .values()
+            .filter(|p| p.node_id == node_id && p.direction == direction)
+            .collect();
+        ports.sort_by_key(|p| p.id);
+        ports.into_iter().map(|p| p.channel.clone()).collect()
+    }
+
     fn set_node_volume(&self, node_id: u32, volume: f32) -> Result<(), String> {
         let bound = self
             .bound_nodes
             .get(&node_id)
             .ok_or_else(|| format!("Node {} not bound", node_id))?;
 
-        let pod_data = build_channel_volumes_pod(&[volume, volume]).map_err(|e| e.to_string())?;
+        let positions = self.node_positions(node_id);
+        let mut volumes = self
+            .node_balance
+            .get(&node_id)
+            .copied()
+            .unwrap_or_default()
+            .speaker_volumes(volume, &positions);
+        if let Some(folded) = self.node_downmix.get(&node_id) {
+            for (volume, position) in volumes.iter_mut().zip(&positions) {
+                if folded.contains(position) {
+                    *volume *= DOWNMIX_GAIN;
+                }
+            }
+        }
+        let pod_data = build_channel_volumes_pod(&volumes).map_err(|e| e.to_string())?;
         let pod = Pod::from_bytes(&pod_data)
             .ok_or_else(|| "Failed to create Pod from bytes".to_string())?;
 
         bound.proxy.set_param(ParamType::Props, 0, pod);
-        trace!("Native volume set on node {}: {:.3}", node_id, volume);
+        trace!("Native volume set on node {}: {:?}", node_id, volumes);
         Ok(())
     }
 
@@ -354,6 +739,23 @@ impl PwThreadState {
         trace!("Native mute set on node {}: {}", node_id, muted);
         Ok(())
     }
+
+    /// Set `params` of a node's `Props`: alternating names and values, as
+    /// filter-chain controls and node properties are set.
+    fn set_node_params(&self, node_id: u32, params: Vec<libspa::pod::Value>) -> Result<(), String> {
+        let bound = self
+            .bound_nodes
+            .get(&node_id)
+            .ok_or_else(|| format!("Node {} not bound", node_id))?;
+
+        let pod_data = build_params_pod(params).map_err(|e| e.to_string())?;
+        let pod = Pod::from_bytes(&pod_data)
+            .ok_or_else(|| "Failed to create Pod from bytes".to_string())?;
+
+        bound.proxy.set_param(ParamType::Props, 0, pod);
+        trace!("Native params set on node {}", node_id);
+        Ok(())
+    }
 }
 
 /// Handle to the PipeWire thread.
@@ -443,23 +845,20 @@ fn run_pipewire_loop(
         handle_command(cmd, &state_cmd, &main_loop_weak, &core_cmd, &registry_cmd);
     });
 
-    let _registry_listener = setup_registry_listener(&registry, state.clone(), event_tx.clone());
+    let _registry_listener =
+        setup_registry_listener(&registry, &core, state.clone(), event_tx.clone());
 
-    // Set up a timer to process pending CLI commands that were throttled
+    // Give up on spawned processes and links whose nodes or ports never appear
     let state_timer = state.clone();
     let timer = main_loop.loop_().add_timer(move |_| {
-        process_pending_cli_commands(&state_timer);
+        expire_pending(&state_timer);
     });
-    // Fire every 60ms (slightly longer than CLI_THROTTLE_MS) to process any pending commands
     if timer
-        .update_timer(
-            Some(Duration::from_millis(CLI_THROTTLE_MS + 10)),
-            Some(Duration::from_millis(CLI_THROTTLE_MS + 10)),
-        )
+        .update_timer(Some(PENDING_CHECK_INTERVAL), Some(PENDING_CHECK_INTERVAL))
         .into_result()
         .is_err()
     {
-        warn!("Failed to set CLI throttle timer interval");
+        warn!("Failed to set pending check timer interval");
     }
 
     main_loop.run();
@@ -470,9 +869,10 @@ fn run_pipewire_loop(
     Ok(())
 }
 
-/// Spawn a background thread for blocking CLI operations that would stall the PW main loop.
-/// Clones the event sender so the background thread can report results/errors.
-fn spawn_cli_work<F>(event_tx: &Rc<mpsc::Sender<PwEvent>>, work: F)
+/// Spawn a background thread for blocking work, such as stopping a helper
+/// process, that would stall the PW main loop. Clones the event sender so the
+/// background thread can report results/errors.
+fn spawn_blocking_work<F>(event_tx: &Rc<mpsc::Sender<PwEvent>>, work: F)
 where
     F: FnOnce(mpsc::Sender<PwEvent>) + Send + 'static,
 {
@@ -480,119 +880,98 @@ where
     thread::spawn(move || work(tx));
 }
 
-/// Spawn a CLI background thread for a specific node, with in-flight tracking.
-/// If the node is already being processed by another CLI thread, the work is skipped.
-/// The pending CLI timer will retry later.
-fn spawn_cli_work_for_node<F>(
-    event_tx: &Rc<mpsc::Sender<PwEvent>>,
-    in_flight: &Arc<Mutex<HashSet<u32>>>,
-    node_id: u32,
-    work: F,
-) where
-    F: FnOnce(mpsc::Sender<PwEvent>) + Send + 'static,
-{
-    // Check if node is already in-flight
-    {
-        let mut set = in_flight.lock().unwrap_or_else(|e| e.into_inner());
-        if set.contains(&node_id) {
-            trace!(
-                "Node {} already has CLI operation in-flight, skipping",
-                node_id
-            );
-            return;
-        }
-        set.insert(node_id);
-    }
-
-    let tx = mpsc::Sender::clone(event_tx);
-    let in_flight_clone = Arc::clone(in_flight);
-    thread::spawn(move || {
-        work(tx);
-        // Remove node from in-flight set when done
-        let mut set = in_flight_clone.lock().unwrap_or_else(|e| e.into_inner());
-        set.remove(&node_id);
-    });
-}
-
-/// Process any pending CLI commands that were throttled but are now ready to execute.
-/// CLI commands are dispatched to background threads to avoid blocking the PW loop.
-/// Uses in-flight tracking to prevent concurrent CLI operations on the same node.
-fn process_pending_cli_commands(state: &Rc<RefCell<PwThreadState>>) {
-    let ready_cmds = state.borrow_mut().get_ready_pending_cmds();
-
-    if ready_cmds.is_empty() {
-        return;
-    }
-
-    let in_flight = Arc::clone(&state.borrow().cli_in_flight);
-    let _tx = mpsc::Sender::clone(&state.borrow().event_tx);
-
-    // Filter out commands for nodes already in-flight
-    let filtered_cmds: Vec<(u32, PendingCliCmd)> = {
-        let set = in_flight.lock().unwrap_or_else(|e| e.into_inner());
-        ready_cmds
+/// Fail spawned processes and links that have waited longer than
+/// `PENDING_TIMEOUT` for their nodes or ports.
+fn expire_pending(state: &Rc<RefCell<PwThreadState>>) {
+    let now = Instant::now();
+    let (expired, event_tx) = {
+        let mut st = state.borrow_mut();
+        let event_tx = st.event_tx.clone();
+        st.pending_links
+            .retain(|&(output_port, input_port, requested)| {
+                let waiting = now.duration_since(requested) < PENDING_TIMEOUT;
+                if !waiting {
+                    warn!(
+                        "Ports of link {} -> {} never appeared",
+                        output_port, input_port
+                    );
+                    let _ = event_tx.send(PwEvent::Error(format!(
+                        "Failed to create link: port {} or {} not found",
+                        output_port, input_port
+                    )));
+                }
+                waiting
+            });
+        let (expired, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut st.pending_spawns)
             .into_iter()
-            .filter(|(node_id, _)| !set.contains(node_id))
-            .collect()
+            .partition(|spawn| now.duration_since(spawn.started) >= PENDING_TIMEOUT);
+        st.pending_spawns = waiting;
+        (expired, event_tx)
     };
 
-    if filtered_cmds.is_empty() {
-        return;
+    for spawn in expired {
+        let missing: Vec<&str> = spawn
+            .nodes
+            .iter()
+            .filter(|(_, id)| id.is_none())
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let error = format!("node {} did not appear", missing.join(", "));
+        warn!("Spawned process failed: {}", error);
+        spawn.stop_process();
+        let _ = event_tx.send(spawn.ready.failed_event(error));
     }
+}
 
-    // Mark all as in-flight
-    {
-        let mut set = in_flight.lock().unwrap_or_else(|e| e.into_inner());
-        for (node_id, _) in &filtered_cmds {
-            set.insert(*node_id);
+/// Destroy nodes registered under the names a helper process is about to
+/// use, e.g. ones left behind by a crashed daemon, so the new nodes aren't
+/// confused with them.
+fn destroy_nodes_named(
+    state: &Rc<RefCell<PwThreadState>>,
+    registry: &pipewire::registry::RegistryRc,
+    names: &[&str],
+) {
+    let stale: Vec<(u32, String)> = state
+        .borrow()
+        .nodes
+        .values()
+        .filter(|n| names.contains(&n.name.as_str()))
+        .map(|n| (n.id, n.name.clone()))
+        .collect();
+    for (node_id, name) in stale {
+        warn!(
+            "Found existing node '{}' (id={}), destroying it first",
+            name, node_id
+        );
+        if let Err(e) = registry.destroy_global(node_id).into_result() {
+            debug!("Failed to destroy node {}: {:?}", node_id, e);
         }
     }
+}
 
-    let in_flight_clone = Arc::clone(&in_flight);
-    thread::spawn(move || {
-        for (node_id, cmd) in &filtered_cmds {
-            match cmd {
-                PendingCliCmd::Volume(volume) => {
-                    trace!(
-                        "Processing pending volume command: node={} volume={:.3}",
-                        node_id,
-                        volume
-                    );
-                    if let Err(e) = crate::audio::volume::set_volume(*node_id, *volume) {
-                        error!(
-                            "Pending CLI volume control failed for node {}: {}",
-                            node_id, e
-                        );
-                    }
-                }
-                PendingCliCmd::Mute(muted) => {
-                    trace!(
-                        "Processing pending mute command: node={} muted={}",
-                        node_id,
-                        muted
-                    );
-                    if let Err(e) = crate::audio::volume::set_mute(*node_id, *muted) {
-                        error!(
-                            "Pending CLI mute control failed for node {}: {}",
-                            node_id, e
-                        );
-                    }
-                }
-            }
-        }
-        // Remove all from in-flight set
-        let mut set = in_flight_clone.lock().unwrap_or_else(|e| e.into_inner());
-        for (node_id, _) in &filtered_cmds {
-            set.remove(node_id);
+/// Destroy a pw-loopback virtual sink or source: stop its process, or, if
+/// it isn't one of ours (e.g. left over from a previous run), destroy the
+/// node itself.
+fn destroy_loopback_node(
+    state: &Rc<RefCell<PwThreadState>>,
+    registry: &pipewire::registry::RegistryRc,
+    node_id: u32,
+) {
+    let name = state.borrow().nodes.get(&node_id).map(|n| n.name.clone());
+    let stopped = name.is_some_and(|name| crate::audio::virtual_sink::stop_loopback(&name));
+    if !stopped {
+        debug!("No tracked pw-loopback for node {}, destroying it", node_id);
+        if let Err(e) = registry.destroy_global(node_id).into_result() {
+            warn!("Failed to destroy node {}: {:?}", node_id, e);
         }
-    });
+    }
 }
 
 /// Create a PipeWire link between two ports using the native `link-factory`.
 ///
 /// Must be called on the PipeWire main-loop thread. Returns `Err` when:
-///   - either port is not yet in our registry-mirror state (fast path to let
-///     callers decide between CLI fallback and a retry), or
+///   - either port is not yet in our registry-mirror state (callers that can
+///     wait queue the link in `pending_links` instead), or
 ///   - `core.create_object` fails (format mismatch, permission, etc.).
 ///
 /// On success the returned Link proxy is parked in `created_links` keyed by
@@ -657,29 +1036,29 @@ fn handle_command(
             channel_id,
             name,
             target_device,
+            layout,
         } => {
             debug!(
-                "Creating virtual sink: '{}' for channel {} (target: {:?})",
-                name, channel_id, target_device
+                "Creating virtual sink: '{}' ({}) for channel {} (target: {:?})",
+                name, layout, channel_id, target_device
             );
 
             // Create a PulseAudio-based meter for this output channel.
             // We monitor the sink's monitor source (sootmix.{name}.monitor) which
             // captures all audio being played to the virtual sink.
             let meter_levels = Arc::new(AtomicMeterLevels::new());
+            let loudness = Arc::new(SharedLoudness::new());
 
-            // Compute the monitor source name - must match what create_virtual_sink_full creates
-            let safe_name: String = name
-                .chars()
-                .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
-                .collect();
-            let monitor_source_name = format!("sootmix.{}.monitor", safe_name);
+            let names = crate::audio::virtual_sink::sink_node_names(&name);
+            let monitor_source_name = format!("{}.monitor", names.main);
 
             let meter =
-                PulseAudioMeter::new(channel_id, &monitor_source_name, Arc::clone(&meter_levels));
+                PulseAudioMeter::new(channel_id, &monitor_source_name, Arc::clone(&meter_levels))
+                    .with_loudness(Arc::clone(&loudness));
             // Start meter - it will retry connection until the sink appears
             meter.start();
             state.borrow_mut().pulse_meters.insert(channel_id, meter);
+            state.borrow_mut().apply_spectrum_tap();
 
             info!(
                 "PulseAudio meter started for output channel {} targeting '{}'",
@@ -690,25 +1069,25 @@ fn handle_command(
             // Native pw_stream creates a single interleaved port instead of separate FL/FR ports,
             // which causes WirePlumber to fail linking to hardware devices after ~60 seconds.
             // CLI pw-loopback uses the adapter module for proper stereo port creation.
-            let meter_levels_clone = Arc::clone(&meter_levels);
-            spawn_cli_work(&state.borrow().event_tx, move |event_tx| {
-                match crate::audio::virtual_sink::create_virtual_sink_full(&name, &name) {
-                    Ok(result) => {
-                        let _ = event_tx.send(PwEvent::VirtualSinkCreated {
+            destroy_nodes_named(state, registry, &[&names.main, &names.stream]);
+            match crate::audio::virtual_sink::spawn_virtual_sink(&name, &name, layout) {
+                Ok(names) => {
+                    state.borrow_mut().expect_spawn(PendingSpawn::new(
+                        [names.main, names.stream],
+                        SpawnReady::VirtualSink {
                             channel_id,
-                            node_id: result.sink_node_id,
-                            loopback_output_node_id: result.loopback_output_node_id,
-                            meter_levels: Some(meter_levels_clone),
-                        });
-                    }
-                    Err(e) => {
-                        let _ = event_tx.send(PwEvent::Error(format!(
-                            "Failed to create virtual sink: {}",
-                            e
-                        )));
-                    }
+                            meter_levels,
+                            loudness,
+                        },
+                    ));
                 }
-            });
+                Err(e) => {
+                    let _ = state.borrow().event_tx.send(PwEvent::Error(format!(
+                        "Failed to create virtual sink: {}",
+                        e
+                    )));
+                }
+            }
         }
 
         PwCommand::CreateVirtualSource {
@@ -725,6 +1104,7 @@ fn handle_command(
             // PulseAudio's PEAK_DETECT handles format conversion and works reliably
             // with any audio source (mono mics, stereo, etc).
             let meter_levels = Arc::new(AtomicMeterLevels::new());
+            let loudness = Arc::new(SharedLoudness::new());
 
             // Resolve device description to PA source name (node.name)
             // The target_device contains the friendly description, but PA needs the actual source name
@@ -740,9 +1120,11 @@ fn handle_command(
             };
 
             let meter =
-                PulseAudioMeter::new(channel_id, &pa_source_name, Arc::clone(&meter_levels));
+                PulseAudioMeter::new(channel_id, &pa_source_name, Arc::clone(&meter_levels))
+                    .with_loudness(Arc::clone(&loudness));
             meter.start();
             state.borrow_mut().pulse_meters.insert(channel_id, meter);
+            state.borrow_mut().apply_spectrum_tap();
 
             info!(
                 "PulseAudio meter started for channel {} targeting '{}'",
@@ -752,28 +1134,86 @@ fn handle_command(
             // Use CLI pw-loopback for input channels - it properly handles
             // format conversion (mono mic → stereo) via libspa-audioconvert adapter.
             // Native pw_stream doesn't load the adapter, causing format mismatch.
-            let meter_levels_clone = Arc::clone(&meter_levels);
-            spawn_cli_work(&state.borrow().event_tx, move |event_tx| {
-                match crate::audio::virtual_sink::create_virtual_source(
-                    &name,
-                    target_device.as_deref(),
-                ) {
-                    Ok(result) => {
-                        let _ = event_tx.send(PwEvent::VirtualSourceCreated {
+            let names = crate::audio::virtual_sink::source_node_names(&name);
+            destroy_nodes_named(state, registry, &[&names.main, &names.stream]);
+            match crate::audio::virtual_sink::spawn_virtual_source(&name, target_device.as_deref())
+            {
+                Ok(names) => {
+                    state.borrow_mut().expect_spawn(PendingSpawn::new(
+                        [names.main, names.stream],
+                        SpawnReady::VirtualSource {
                             channel_id,
-                            source_node_id: result.source_node_id,
-                            loopback_capture_node_id: result.capture_stream_node_id,
-                            meter_levels: Some(meter_levels_clone),
-                        });
-                    }
-                    Err(e) => {
-                        let _ = event_tx.send(PwEvent::Error(format!(
-                            "Failed to create virtual source: {}",
-                            e
-                        )));
-                    }
+                            meter_levels,
+                            loudness,
+                        },
+                    ));
                 }
-            });
+                Err(e) => {
+                    let _ = state.borrow().event_tx.send(PwEvent::Error(format!(
+                        "Failed to create virtual source: {}",
+                        e
+                    )));
+                }
+            }
+        }
+
+        PwCommand::CreateMonitorStage { stage } => {
+            debug!("Creating monitor stage {:?}", stage);
+            let names = crate::audio::virtual_sink::sink_node_names(&stage.name());
+            destroy_nodes_named(state, registry, &[&names.main, &names.stream]);
+            match crate::audio::virtual_sink::spawn_virtual_sink(
+                &stage.name(),
+                &stage.description(),
+                ChannelLayout::Stereo,
+            ) {
+                Ok(names) => {
+                    state.borrow_mut().expect_spawn(PendingSpawn::new(
+                        [names.main, names.stream],
+                        SpawnReady::MonitorStage(stage),
+                    ));
+                }
+                Err(e) => {
+                    let _ = state.borrow().event_tx.send(PwEvent::MonitorStageFailed {
+                        stage,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        PwCommand::SetMasterMeter {
+            source_name,
+            loudness,
+        } => {
+            // Dropping the old meter stops its thread
+            state.borrow_mut().master_meter = None;
+            loudness.store(Default::default());
+
+            if let Some(source_name) = source_name {
+                info!("Metering master loudness on '{}'", source_name);
+                let meter = PulseAudioMeter::new(
+                    Uuid::nil(),
+                    &source_name,
+                    Arc::new(AtomicMeterLevels::new()),
+                )
+                .with_loudness(loudness);
+                meter.start();
+                state.borrow_mut().master_meter = Some(meter);
+                state.borrow_mut().apply_spectrum_tap();
+            }
+        }
+
+        PwCommand::SetSpectrumTap { tap } => {
+            match &tap {
+                Some(tap) => info!(
+                    "Spectrum analyzer tapping {:?} ({})",
+                    tap.channel_id, tap.tap
+                ),
+                None => info!("Spectrum analyzer stopped"),
+            }
+            let mut st = state.borrow_mut();
+            st.spectrum_tap = tap;
+            st.apply_spectrum_tap();
         }
 
         PwCommand::UpdateSinkDescription {
@@ -781,13 +1221,13 @@ fn handle_command(
             description,
         } => {
             debug!("Updating sink {} description to '{}'", node_id, description);
-            spawn_cli_work(&state.borrow().event_tx, move |_event_tx| {
-                if let Err(e) =
-                    crate::audio::virtual_sink::update_node_description(node_id, &description)
-                {
-                    warn!("Failed to update sink description: {}", e);
-                }
-            });
+            let params = vec![
+                libspa::pod::Value::String("node.description".to_string()),
+                libspa::pod::Value::String(description),
+            ];
+            if let Err(e) = state.borrow().set_node_params(node_id, params) {
+                warn!("Failed to update sink description: {}", e);
+            }
         }
 
         PwCommand::DestroyVirtualSink { node_id } => {
@@ -817,16 +1257,7 @@ fn handle_command(
                     info!("Destroyed native loopback for channel {}", channel_id);
                 }
             } else {
-                // Fallback to CLI destroy for legacy loopbacks
-                warn!(
-                    "No native loopback found for node {}, trying CLI destroy",
-                    node_id
-                );
-                spawn_cli_work(&state.borrow().event_tx, move |_event_tx| {
-                    if let Err(e) = crate::audio::virtual_sink::destroy_virtual_sink(node_id) {
-                        warn!("Failed to destroy virtual sink {}: {}", node_id, e);
-                    }
-                });
+                destroy_loopback_node(state, registry, node_id);
             }
 
             let _ = state
@@ -844,22 +1275,26 @@ fn handle_command(
                 output_port, input_port
             );
 
-            if let Err(e) = try_create_link_native(core, state, output_port, input_port) {
-                // CLI fallback covers two narrow cases the native path can't
-                // handle yet: (1) the port isn't in our registry-mirror state
-                // (registry listener hasn't dispatched the global yet), and
-                // (2) core.create_object failed. Requeuing-on-PortAdded would
-                // eliminate this path but is out of scope here.
-                warn!(
-                    "Native create_link {} -> {} failed ({}), using CLI fallback",
-                    output_port, input_port, e
+            let ports_known = {
+                let st = state.borrow();
+                st.ports.contains_key(&output_port) && st.ports.contains_key(&input_port)
+            };
+            if !ports_known {
+                // The registry listener hasn't dispatched the port yet; it
+                // creates the link once both are in
+                debug!(
+                    "Ports of link {} -> {} not in registry yet, waiting",
+                    output_port, input_port
                 );
-                spawn_cli_work(&state.borrow().event_tx, move |event_tx| {
-                    if let Err(e2) = crate::audio::routing::create_link(output_port, input_port) {
-                        let _ = event_tx
-                            .send(PwEvent::Error(format!("Failed to create link: {}", e2)));
-                    }
-                });
+                state
+                    .borrow_mut()
+                    .pending_links
+                    .push((output_port, input_port, Instant::now()));
+            } else if let Err(e) = try_create_link_native(core, state, output_port, input_port) {
+                let _ = state
+                    .borrow()
+                    .event_tx
+                    .send(PwEvent::Error(format!("Failed to create link: {}", e)));
             }
         }
 
@@ -894,66 +1329,85 @@ fn handle_command(
 
         PwCommand::SetVolume { node_id, volume } => {
             trace!("PW cmd: SetVolume node={} volume={:.3}", node_id, volume);
-            let result = state.borrow().set_node_volume(node_id, volume);
-            if let Err(e) = result {
-                if state
-                    .borrow_mut()
-                    .should_run_cli(node_id, PendingCliCmd::Volume(volume))
-                {
-                    debug!("Native volume failed ({}), using CLI fallback", e);
-                    let in_flight = Arc::clone(&state.borrow().cli_in_flight);
-                    spawn_cli_work_for_node(
-                        &state.borrow().event_tx,
-                        &in_flight,
-                        node_id,
-                        move |_event_tx| {
-                            if let Err(e2) = crate::audio::volume::set_volume(node_id, volume) {
-                                error!("CLI volume control also failed: {}", e2);
-                            }
-                        },
-                    );
+            state.borrow_mut().node_volumes.insert(node_id, volume);
+            if let Err(e) = state.borrow().set_node_volume(node_id, volume) {
+                warn!("Failed to set volume of node {}: {}", node_id, e);
+            }
+        }
+
+        PwCommand::SetBalance { node_id, balance } => {
+            trace!("PW cmd: SetBalance node={} {:?}", node_id, balance);
+            let volume = {
+                let mut st = state.borrow_mut();
+                if balance.is_centered() {
+                    st.node_balance.remove(&node_id);
+                } else {
+                    st.node_balance.insert(node_id, balance);
+                }
+                st.node_volumes.get(&node_id).copied()
+            };
+            // Without a volume yet, the node's first SetVolume applies it
+            if let Some(volume) = volume {
+                if let Err(e) = state.borrow().set_node_volume(node_id, volume) {
+                    debug!("Could not apply balance to node {}: {}", node_id, e);
                 }
             }
         }
 
         PwCommand::SetMute { node_id, muted } => {
             trace!("PW cmd: SetMute node={} muted={}", node_id, muted);
-            let result = state.borrow().set_node_mute(node_id, muted);
-            if let Err(e) = result {
-                if state
-                    .borrow_mut()
-                    .should_run_cli(node_id, PendingCliCmd::Mute(muted))
-                {
-                    debug!("Native mute failed ({}), using CLI fallback", e);
-                    let in_flight = Arc::clone(&state.borrow().cli_in_flight);
-                    spawn_cli_work_for_node(
-                        &state.borrow().event_tx,
-                        &in_flight,
-                        node_id,
-                        move |_event_tx| {
-                            if let Err(e2) = crate::audio::volume::set_mute(node_id, muted) {
-                                error!("CLI mute control also failed: {}", e2);
-                            }
-                        },
-                    );
-                }
+            if let Err(e) = state.borrow().set_node_mute(node_id, muted) {
+                warn!("Failed to set mute of node {}: {}", node_id, e);
             }
         }
 
         PwCommand::SetDefaultSink { node_id } => {
             info!("Setting default sink to node {}", node_id);
-            spawn_cli_work(&state.borrow().event_tx, move |_event_tx| {
-                let output = std::process::Command::new("wpctl")
-                    .args(["set-default", &node_id.to_string()])
-                    .output();
-
-                if let Ok(result) = output {
-                    if !result.status.success() {
-                        let stderr = String::from_utf8_lossy(&result.stderr);
-                        warn!("wpctl set-default failed: {}", stderr);
-                    }
+            let st = state.borrow();
+            let Some(name) = st.nodes.get(&node_id).map(|n| n.name.clone()) else {
+                warn!("Cannot set default sink: node {} not found", node_id);
+                return;
+            };
+            match &st.default_metadata {
+                Some((_, metadata, _)) => {
+                    // What WirePlumber remembers as the user's choice; it
+                    // updates `default.audio.sink` from it
+                    metadata.set_property(
+                        0,
+                        "default.configured.audio.sink",
+                        Some("Spa:String:JSON"),
+                        Some(&routing::metadata_node_name_value(&name)),
+                    );
                 }
-            });
+                None => warn!("Cannot set default sink: 'default' metadata not bound"),
+            }
+        }
+
+        PwCommand::SetStreamTarget {
+            stream_node_id,
+            target_node_id,
+        } => {
+            info!(
+                "Setting stream {} target to {:?}",
+                stream_node_id, target_node_id
+            );
+            match &state.borrow().default_metadata {
+                Some((_, metadata, _)) => {
+                    let value = target_node_id.map(|id| id.to_string());
+                    metadata.set_property(stream_node_id, "target.node", None, value.as_deref());
+                }
+                None => warn!(
+                    "Cannot set target of stream {}: 'default' metadata not bound",
+                    stream_node_id
+                ),
+            }
+        }
+
+        PwCommand::DestroyNode { node_id } => {
+            debug!("Destroying node {}", node_id);
+            if let Err(e) = registry.destroy_global(node_id).into_result() {
+                warn!("Failed to destroy node {}: {:?}", node_id, e);
+            }
         }
 
         PwCommand::RouteChannelToDevice {
@@ -969,16 +1423,16 @@ fn handle_command(
             // Resolve the target device ID
             let target_node_id = target_device_id.or_else(|| {
                 // Use the WirePlumber default sink as fallback
-                crate::audio::routing::get_default_sink_id().and_then(|id| {
-                    let s = state.borrow();
-                    if s.nodes.get(&id).map_or(false, |n| {
-                        n.media_class == MediaClass::AudioSink && !n.name.starts_with("sootmix.")
-                    }) {
-                        Some(id)
-                    } else {
-                        None
-                    }
-                })
+                let s = state.borrow();
+                let name = s.default_sink_name.as_deref()?;
+                s.nodes
+                    .values()
+                    .find(|n| {
+                        n.name == name
+                            && n.media_class == MediaClass::AudioSink
+                            && !n.name.starts_with("sootmix.")
+                    })
+                    .map(|n| n.id)
             });
 
             // Check if this is a native loopback - if so, use native re-routing
@@ -993,10 +1447,10 @@ fn handle_command(
                 // guarantees the daemon applies set-before-destroy and fans
                 // events to WirePlumber in that order, so WP never observes an
                 // orphan stream with a stale `target.node` pointing at the old
-                // device. That state was the source of the double-audio bug:
-                // WP autoconnect would re-link to OLD after our destroy and
-                // before the subprocess pw-metadata write landed on a
-                // separate client socket.
+                // device. That state was the source of the double-audio bug
+                // when the metadata was written from a separate client: WP
+                // autoconnect would re-link to OLD after our destroy and
+                // before the write landed.
                 let links_to_destroy: Vec<u32> = {
                     let s = state.borrow();
                     s.links
@@ -1013,8 +1467,6 @@ fn handle_command(
                     })
                 });
 
-                let mut used_fallback = false;
-
                 match playback_info {
                     Some((_, true)) => {
                         warn!(
@@ -1066,24 +1518,9 @@ fn handle_command(
                             );
                         } else {
                             warn!(
-                                "default metadata not bound; falling back to subprocess for channel {:?}",
+                                "default metadata not bound; cannot reroute channel {:?}",
                                 channel_id
                             );
-                            used_fallback = true;
-                            spawn_cli_work(&state.borrow().event_tx, move |event_tx| {
-                                if let Err(e) =
-                                    crate::audio::native_loopback::set_playback_target_node(
-                                        pnid,
-                                        target_node_id,
-                                    )
-                                {
-                                    warn!("Native re-route failed: {}", e);
-                                }
-                                let _ = event_tx.send(PwEvent::RouteFinished {
-                                    loopback_output_node,
-                                    reason: RouteFinishReason::NativeRerouted,
-                                });
-                            });
                         }
                     }
                     Some((_, false)) => {
@@ -1097,12 +1534,10 @@ fn handle_command(
                     }
                 }
 
-                if !used_fallback {
-                    let _ = state.borrow().event_tx.send(PwEvent::RouteFinished {
-                        loopback_output_node,
-                        reason: RouteFinishReason::NativeRerouted,
-                    });
-                }
+                let _ = state.borrow().event_tx.send(PwEvent::RouteFinished {
+                    loopback_output_node,
+                    reason: RouteFinishReason::NativeRerouted,
+                });
                 return;
             }
 
@@ -1117,65 +1552,45 @@ fn handle_command(
             };
 
             let port_pairs: Vec<(u32, u32)> = if let Some(target_id) = target_node_id {
-                let s = state.borrow();
-                let mut out_ports: Vec<_> = s
-                    .ports
-                    .values()
-                    .filter(|p| {
-                        p.node_id == loopback_output_node && p.direction == PortDirection::Output
-                    })
-                    .collect();
-                let mut in_ports: Vec<_> = s
-                    .ports
-                    .values()
-                    .filter(|p| p.node_id == target_id && p.direction == PortDirection::Input)
-                    .collect();
-
-                out_ports.sort_by(|a, b| (&a.channel, a.id).cmp(&(&b.channel, b.id)));
-                in_ports.sort_by(|a, b| (&a.channel, a.id).cmp(&(&b.channel, b.id)));
-
-                let mut pairs = Vec::new();
-                let mut used_inputs: std::collections::HashSet<u32> =
-                    std::collections::HashSet::new();
-
-                for out_port in &out_ports {
-                    for in_port in &in_ports {
-                        if used_inputs.contains(&in_port.id) {
-                            continue;
-                        }
-                        if out_port.channel.is_compatible(&in_port.channel) {
-                            pairs.push((out_port.id, in_port.id));
-                            used_inputs.insert(in_port.id);
-                            break;
-                        }
-                    }
-                }
+                let mut st = state.borrow_mut();
+                let (pairs, folded) = {
+                    let out_ports: Vec<_> = st
+                        .ports
+                        .values()
+                        .filter(|p| {
+                            p.node_id == loopback_output_node
+                                && p.direction == PortDirection::Output
+                        })
+                        .collect();
+                    let in_ports: Vec<_> = st
+                        .ports
+                        .values()
+                        .filter(|p| p.node_id == target_id && p.direction == PortDirection::Input)
+                        .collect();
+                    (
+                        routing::pair_ports(&out_ports, &in_ports),
+                        routing::folded_positions(&out_ports, &in_ports),
+                    )
+                };
 
-                if pairs.is_empty() {
-                    for out_port in &out_ports {
-                        for in_port in &in_ports {
-                            if used_inputs.contains(&in_port.id) {
-                                continue;
-                            }
-                            let out_name = out_port.name.to_lowercase();
-                            let in_name = in_port.name.to_lowercase();
-                            let is_match = (out_name.contains("_0") && in_name.contains("_0"))
-                                || (out_name.contains("_1") && in_name.contains("_1"));
-                            if is_match {
-                                pairs.push((out_port.id, in_port.id));
-                                used_inputs.insert(in_port.id);
-                                break;
-                            }
+                // Turn down speakers folded onto others (e.g. 5.1 into a stereo
+                // headset) so the downmix doesn't clip
+                let changed = if folded.is_empty() {
+                    st.node_downmix.remove(&loopback_output_node).is_some()
+                } else {
+                    st.node_downmix.insert(loopback_output_node, folded.clone()) != Some(folded)
+                };
+                if changed {
+                    if let Some(&volume) = st.node_volumes.get(&loopback_output_node) {
+                        if let Err(e) = st.set_node_volume(loopback_output_node, volume) {
+                            debug!(
+                                "Could not apply downmix to node {}: {}",
+                                loopback_output_node, e
+                            );
                         }
                     }
                 }
 
-                if pairs.is_empty() && !out_ports.is_empty() && !in_ports.is_empty() {
-                    for (out_port, in_port) in out_ports.iter().zip(in_ports.iter()) {
-                        pairs.push((out_port.id, in_port.id));
-                    }
-                }
-
                 pairs
             } else {
                 Vec::new()
@@ -1238,84 +1653,308 @@ fn handle_command(
 
         PwCommand::CreateRecordingSource { name } => {
             info!("Creating recording source: {}", name);
-            spawn_cli_work(&state.borrow().event_tx, move |event_tx| {
-                match crate::audio::virtual_sink::create_virtual_source(&name, None) {
-                    Ok(result) => {
-                        let _ = event_tx.send(PwEvent::RecordingSourceCreated {
-                            name,
-                            node_id: result.source_node_id,
-                        });
-                    }
-                    Err(e) => {
-                        let _ = event_tx.send(PwEvent::Error(format!(
-                            "Failed to create recording source: {}",
-                            e
-                        )));
-                    }
+            let names = crate::audio::virtual_sink::source_node_names(&name);
+            destroy_nodes_named(state, registry, &[&names.main, &names.stream]);
+            match crate::audio::virtual_sink::spawn_virtual_source(&name, None) {
+                Ok(names) => {
+                    state.borrow_mut().expect_spawn(PendingSpawn::new(
+                        [names.main],
+                        SpawnReady::RecordingSource { name },
+                    ));
                 }
-            });
+                Err(e) => {
+                    let _ = state.borrow().event_tx.send(PwEvent::Error(format!(
+                        "Failed to create recording source: {}",
+                        e
+                    )));
+                }
+            }
         }
 
         PwCommand::DestroyRecordingSource { node_id } => {
             info!("Destroying recording source: {}", node_id);
-            spawn_cli_work(&state.borrow().event_tx, move |event_tx| {
-                if let Err(e) = crate::audio::virtual_sink::destroy_virtual_source(node_id) {
-                    warn!("Failed to destroy recording source {}: {}", node_id, e);
-                }
-                let _ = event_tx.send(PwEvent::RecordingSourceDestroyed { node_id });
-            });
+            destroy_loopback_node(state, registry, node_id);
+            let _ = state
+                .borrow()
+                .event_tx
+                .send(PwEvent::RecordingSourceDestroyed { node_id });
         }
 
-        PwCommand::CreateNativeNoiseFilter {
+        PwCommand::CreateEqFilter {
             channel_id,
             name,
-            target_mic,
-            vad_threshold,
+            bands,
         } => {
             info!(
-                "Creating noise filter (CLI) for channel {} (name={}, target={:?}, vad={}%)",
-                channel_id, name, target_mic, vad_threshold
+                "Creating EQ filter for channel {} (name={}, bands={})",
+                channel_id,
+                name,
+                bands.len()
             );
+            match crate::audio::eq_filter::create_eq_filter(channel_id, &name, &bands) {
+                Ok(nodes) => {
+                    state.borrow_mut().expect_spawn(PendingSpawn::new(
+                        [nodes.sink_node_name, nodes.output_node_name],
+                        SpawnReady::EqFilter { channel_id },
+                    ));
+                }
+                Err(e) => {
+                    error!("Failed to create EQ filter: {}", e);
+                    let _ = state.borrow().event_tx.send(PwEvent::EqFilterFailed {
+                        channel_id,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
 
-            // Use CLI-based filter-chain with RNNoise LADSPA plugin
-            spawn_cli_work(&state.borrow().event_tx, move |event_tx| {
-                match crate::audio::noise_filter::create_noise_filter(
-                    channel_id,
-                    &name,
-                    target_mic.as_deref(),
-                    false, // mono
-                    vad_threshold,
-                ) {
-                    Ok(source_node_id) => {
-                        info!(
-                            "Noise filter created for {}: source_node={}",
-                            channel_id, source_node_id
-                        );
-                        let _ = event_tx.send(PwEvent::NativeNoiseFilterCreated {
-                            channel_id,
-                            source_node_id,
-                        });
-                    }
-                    Err(e) => {
-                        error!("Failed to create noise filter: {}", e);
-                        let _ = event_tx.send(PwEvent::NativeNoiseFilterFailed {
-                            channel_id,
-                            error: e.to_string(),
-                        });
-                    }
+        PwCommand::UpdateEqFilter {
+            sink_node_id,
+            bands,
+        } => {
+            let params = crate::audio::eq_filter::eq_params(&bands)
+                .into_iter()
+                .flat_map(|(control, value)| {
+                    [
+                        libspa::pod::Value::String(control),
+                        libspa::pod::Value::Float(value),
+                    ]
+                })
+                .collect();
+            match state.borrow().set_node_params(sink_node_id, params) {
+                Ok(()) => info!("Applied EQ bands to filter node {}", sink_node_id),
+                Err(e) => {
+                    warn!("Failed to update EQ filter {}: {}", sink_node_id, e);
+                    let _ = state
+                        .borrow()
+                        .event_tx
+                        .send(PwEvent::Error(format!("Failed to update EQ filter: {}", e)));
                 }
+            }
+        }
+
+        PwCommand::DestroyEqFilter { channel_id } => {
+            info!("Destroying EQ filter for channel {}", channel_id);
+            state.borrow_mut().pending_spawns.retain(|spawn| {
+                !matches!(spawn.ready, SpawnReady::EqFilter { channel_id: id } if id == channel_id)
+            });
+            spawn_blocking_work(&state.borrow().event_tx, move |event_tx| {
+                if let Err(e) = crate::audio::eq_filter::destroy_eq_filter(channel_id) {
+                    warn!("Error destroying EQ filter: {}", e);
+                }
+                let _ = event_tx.send(PwEvent::EqFilterDestroyed { channel_id });
             });
         }
 
-        PwCommand::DestroyNativeNoiseFilter { channel_id } => {
-            info!("Destroying noise filter (CLI) for channel {}", channel_id);
-            // Use CLI-based destroy - kills the pipewire filter-chain process
-            spawn_cli_work(&state.borrow().event_tx, move |event_tx| {
-                if let Err(e) = crate::audio::noise_filter::destroy_noise_filter(channel_id) {
-                    warn!("Error destroying noise filter: {}", e);
+        PwCommand::CreatePluginFilter {
+            channel_id,
+            name,
+            plugin_instances,
+            plugin_chain,
+            noise_suppression,
+            voice_activity,
+            talk_gate,
+        } => {
+            let safe_name: String = name
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+                .collect();
+
+            // Streams for the same channel share node names, so drop the old pair first
+            if let Some(old) = state.borrow_mut().plugin_filters.remove(&channel_id) {
+                if let Err(e) = old.disconnect() {
+                    warn!("Failed to disconnect old plugin filter: {}", e);
                 }
-                let _ = event_tx.send(PwEvent::NativeNoiseFilterDestroyed { channel_id });
+            }
+
+            let result = PluginFilterStreams::new(
+                core,
+                channel_id,
+                &safe_name,
+                plugin_instances,
+                plugin_chain,
+                noise_suppression,
+                voice_activity,
+            )
+            .and_then(|mut filter| {
+                filter.reset_talk_gate(talk_gate);
+                filter.connect()?;
+                Ok(filter)
             });
+
+            match result {
+                Ok(filter) => {
+                    // Node IDs are reported once both nodes show up in the registry
+                    let mut st = state.borrow_mut();
+                    st.discovered_plugin_nodes.remove(&channel_id);
+                    st.pending_plugin_nodes
+                        .insert(plugin_sink_node_name(&safe_name), (channel_id, true));
+                    st.pending_plugin_nodes
+                        .insert(plugin_output_node_name(&safe_name), (channel_id, false));
+                    st.plugin_filters.insert(channel_id, filter);
+                    st.apply_spectrum_tap();
+                }
+                Err(e) => {
+                    error!("Failed to create plugin filter for channel {}: {}", channel_id, e);
+                    let _ = state.borrow().event_tx.send(PwEvent::PluginFilterFailed {
+                        channel_id,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        PwCommand::UpdatePluginChain {
+            channel_id,
+            plugin_chain,
+        } => {
+            debug!(
+                "Updating plugin chain for channel {} ({} active)",
+                channel_id,
+                plugin_chain.len()
+            );
+            if let Some(filter) = state.borrow_mut().plugin_filters.get_mut(&channel_id) {
+                filter.update_plugin_chain(plugin_chain);
+            }
+        }
+
+        PwCommand::SetNoiseSuppression {
+            channel_id,
+            settings,
+        } => {
+            debug!(
+                "Noise suppression for channel {}: {:?}",
+                channel_id, settings
+            );
+            if let Some(filter) = state.borrow_mut().plugin_filters.get_mut(&channel_id) {
+                filter.set_noise_suppression(settings);
+            }
+        }
+
+        PwCommand::SetTalkGate {
+            channel_id,
+            settings,
+        } => {
+            debug!("Talk gate for channel {}: {:?}", channel_id, settings);
+            if let Some(filter) = state.borrow_mut().plugin_filters.get_mut(&channel_id) {
+                filter.set_talk_gate(settings);
+            }
+        }
+
+        PwCommand::SetPluginLatency {
+            channel_id,
+            compensation,
+            reported,
+        } => {
+            debug!(
+                "Plugin filter latency for channel {}: {} frames ({} compensation)",
+                channel_id, reported, compensation
+            );
+            if let Some(filter) = state.borrow_mut().plugin_filters.get_mut(&channel_id) {
+                if let Err(e) = filter.set_latency(compensation, reported) {
+                    warn!("Failed to report plugin filter latency: {}", e);
+                }
+            }
+        }
+
+        PwCommand::DestroyPluginFilter { channel_id } => {
+            info!("Destroying plugin filter for channel {}", channel_id);
+            let mut st = state.borrow_mut();
+            st.pending_plugin_nodes.retain(|_, (id, _)| *id != channel_id);
+            st.discovered_plugin_nodes.remove(&channel_id);
+            if let Some(filter) = st.plugin_filters.remove(&channel_id) {
+                if let Err(e) = filter.disconnect() {
+                    warn!("Failed to disconnect plugin filter: {}", e);
+                }
+            }
+            st.apply_spectrum_tap();
+        }
+
+        PwCommand::CreateRecordStream {
+            recording_id,
+            buffer,
+        } => {
+            let result = RecordStream::new(
+                core,
+                record_node_name(recording_id),
+                CaptureSink::Recording(buffer),
+            )
+            .and_then(|stream| {
+                stream.connect()?;
+                Ok(stream)
+            });
+
+            match result {
+                Ok(stream) => {
+                    // The node ID is reported once the node shows up in the registry
+                    let mut st = state.borrow_mut();
+                    st.pending_record_nodes
+                        .insert(record_node_name(recording_id), recording_id);
+                    st.record_streams.insert(recording_id, stream);
+                }
+                Err(e) => {
+                    error!("Failed to create record stream {}: {}", recording_id, e);
+                    let _ = state.borrow().event_tx.send(PwEvent::RecordStreamFailed {
+                        recording_id,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        PwCommand::DestroyRecordStream { recording_id } => {
+            info!("Destroying record stream {}", recording_id);
+            let mut st = state.borrow_mut();
+            st.pending_record_nodes.retain(|_, id| *id != recording_id);
+            if let Some(stream) = st.record_streams.remove(&recording_id) {
+                if let Err(e) = stream.disconnect() {
+                    warn!("Failed to disconnect record stream: {}", e);
+                }
+            }
+        }
+
+        PwCommand::CreateReplayStream { channel_id, feed } => {
+            let result = feed
+                .take()
+                .ok_or(pipewire::Error::CreationFailed)
+                .and_then(|writer| {
+                    RecordStream::new(
+                        core,
+                        replay_node_name(channel_id),
+                        CaptureSink::Replay(writer),
+                    )
+                })
+                .and_then(|stream| {
+                    stream.connect()?;
+                    Ok(stream)
+                });
+
+            match result {
+                Ok(stream) => {
+                    let mut st = state.borrow_mut();
+                    st.pending_replay_nodes
+                        .insert(replay_node_name(channel_id), channel_id);
+                    st.replay_streams.insert(channel_id, stream);
+                }
+                Err(e) => {
+                    error!("Failed to create replay stream for {}: {}", channel_id, e);
+                    let _ = state.borrow().event_tx.send(PwEvent::ReplayStreamFailed {
+                        channel_id,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        PwCommand::DestroyReplayStream { channel_id } => {
+            info!("Destroying replay stream for {}", channel_id);
+            let mut st = state.borrow_mut();
+            st.pending_replay_nodes.retain(|_, id| *id != channel_id);
+            if let Some(stream) = st.replay_streams.remove(&channel_id) {
+                if let Err(e) = stream.disconnect() {
+                    warn!("Failed to disconnect replay stream: {}", e);
+                }
+            }
         }
 
         PwCommand::LinkInputChannelToMic {
@@ -1402,26 +2041,31 @@ fn handle_command(
             );
 
             // Get the actual system default source from WirePlumber
-            let default_mic =
-                if let Some(default_id) = crate::audio::routing::get_default_source_id() {
-                    let st = state.borrow();
-                    st.nodes
-                        .get(&default_id)
-                        .filter(|n| n.is_audio_input())
-                        .map(|n| (n.id, n.name.clone()))
-                } else {
-                    // Fallback: find the first hardware audio input that isn't a sootmix node
-                    let st = state.borrow();
-                    st.nodes
-                        .values()
-                        .filter(|n| {
-                            n.is_audio_input()
-                                && !n.name.starts_with("sootmix.")
-                                && !n.name.contains("loopback")
-                        })
-                        .next()
-                        .map(|n| (n.id, n.name.clone()))
-                };
+            let default_source_id = {
+                let st = state.borrow();
+                st.default_source_name
+                    .as_deref()
+                    .and_then(|name| st.node_id_by_name(name))
+            };
+            let default_mic = if let Some(default_id) = default_source_id {
+                let st = state.borrow();
+                st.nodes
+                    .get(&default_id)
+                    .filter(|n| n.is_audio_input())
+                    .map(|n| (n.id, n.name.clone()))
+            } else {
+                // Fallback: find the first hardware audio input that isn't a sootmix node
+                let st = state.borrow();
+                st.nodes
+                    .values()
+                    .filter(|n| {
+                        n.is_audio_input()
+                            && !n.name.starts_with("sootmix.")
+                            && !n.name.contains("loopback")
+                    })
+                    .next()
+                    .map(|n| (n.id, n.name.clone()))
+            };
 
             if let Some((mic_id, mic_name)) = default_mic {
                 info!("Using default mic: {} (node {})", mic_name, mic_id);
@@ -1642,6 +2286,7 @@ fn bind_stream_for_info(
 
 fn setup_registry_listener(
     registry: &pipewire::registry::RegistryRc,
+    core: &pipewire::core::CoreRc,
     state: Rc<RefCell<PwThreadState>>,
     event_tx: Rc<mpsc::Sender<PwEvent>>,
 ) -> pipewire::registry::Listener {
@@ -1650,6 +2295,7 @@ fn setup_registry_listener(
     let event_tx_add = event_tx.clone();
     let event_tx_remove = event_tx;
     let registry_clone = registry.clone();
+    let core = core.clone();
 
     registry
         .add_listener_local()
@@ -1752,11 +2398,13 @@ fn setup_registry_listener(
                             if is_source {
                                 // For input channels, use the PulseAudio meter's levels
                                 // (PulseAudio PEAK_DETECT handles format conversion reliably)
-                                let meter_levels = {
+                                let (meter_levels, loudness) = {
                                     let state_ref = state_add.borrow();
-                                    state_ref.pulse_meters
-                                        .get(&channel_id)
-                                        .map(|pm| Arc::clone(pm.levels()))
+                                    let meter = state_ref.pulse_meters.get(&channel_id);
+                                    (
+                                        meter.map(|pm| Arc::clone(pm.levels())),
+                                        meter.and_then(|pm| pm.loudness().cloned()),
+                                    )
                                 };
 
                                 info!(
@@ -1768,14 +2416,20 @@ fn setup_registry_listener(
                                     source_node_id: main_id,
                                     loopback_capture_node_id: Some(secondary_id),
                                     meter_levels,
+                                    loudness,
                                 });
                             } else {
                                 // For output channels, use native loopback's meter levels
-                                let meter_levels = {
+                                let (meter_levels, loudness) = {
                                     let state_ref = state_add.borrow();
-                                    state_ref.native_loopbacks
-                                        .get(&channel_id)
-                                        .map(|lb| Arc::clone(lb.meter_levels()))
+                                    (
+                                        state_ref.native_loopbacks
+                                            .get(&channel_id)
+                                            .map(|lb| Arc::clone(lb.meter_levels())),
+                                        state_ref.pulse_meters
+                                            .get(&channel_id)
+                                            .and_then(|pm| pm.loudness().cloned()),
+                                    )
                                 };
 
                                 info!(
@@ -1787,6 +2441,7 @@ fn setup_registry_listener(
                                     node_id: main_id,
                                     loopback_output_node_id: Some(secondary_id),
                                     meter_levels,
+                                    loudness,
                                 });
                             }
 
@@ -1795,6 +2450,61 @@ fn setup_registry_listener(
                         }
                     }
 
+                    // Check if this node belongs to a helper process we spawned
+                    let spawn_ready = state_add.borrow_mut().spawned_node_added(&node);
+                    if let Some(event) = spawn_ready {
+                        let _ = event_tx_add.send(event);
+                    }
+
+                    // Check if this node belongs to a plugin filter being brought up
+                    let plugin_match = state_add.borrow().pending_plugin_nodes.get(&node.name).cloned();
+                    if let Some((channel_id, is_sink)) = plugin_match {
+                        let discovered = {
+                            let mut st = state_add.borrow_mut();
+                            st.pending_plugin_nodes.remove(&node.name);
+                            let entry = st.discovered_plugin_nodes.entry(channel_id).or_insert((None, None));
+                            if is_sink {
+                                entry.0 = Some(node.id);
+                            } else {
+                                entry.1 = Some(node.id);
+                            }
+                            *entry
+                        };
+
+                        if let (Some(sink_node_id), Some(output_node_id)) = discovered {
+                            info!(
+                                "Plugin filter ready: channel={}, sink={}, output={}",
+                                channel_id, sink_node_id, output_node_id
+                            );
+                            state_add.borrow_mut().discovered_plugin_nodes.remove(&channel_id);
+                            let _ = event_tx_add.send(PwEvent::PluginFilterCreated {
+                                channel_id,
+                                sink_node_id,
+                                output_node_id,
+                            });
+                        }
+                    }
+
+                    // Check if this node is a recording's capture stream
+                    let record_match = state_add.borrow_mut().pending_record_nodes.remove(&node.name);
+                    if let Some(recording_id) = record_match {
+                        info!("Record stream ready: recording={}, node={}", recording_id, node.id);
+                        let _ = event_tx_add.send(PwEvent::RecordStreamCreated {
+                            recording_id,
+                            node_id: node.id,
+                        });
+                    }
+
+                    // Check if this node is a channel's replay capture stream
+                    let replay_match = state_add.borrow_mut().pending_replay_nodes.remove(&node.name);
+                    if let Some(channel_id) = replay_match {
+                        info!("Replay stream ready: channel={}, node={}", channel_id, node.id);
+                        let _ = event_tx_add.send(PwEvent::ReplayStreamCreated {
+                            channel_id,
+                            node_id: node.id,
+                        });
+                    }
+
                     let _ = event_tx_add.send(PwEvent::NodeAdded(node));
                 }
                 ObjectType::Port => {
@@ -1802,7 +2512,14 @@ fn setup_registry_listener(
 
                     if let Some(name) = props.get("port.name") {
                         port.name = name.to_string();
-                        port.channel = AudioChannel::from_str(name);
+                        port.channel = AudioChannel::from_port_name(name);
+                    }
+                    // The position itself beats guessing from the port name
+                    if let Some(position) = props.get("audio.channel") {
+                        let channel = AudioChannel::from_position(position);
+                        if channel != AudioChannel::Unknown {
+                            port.channel = channel;
+                        }
                     }
                     if let Some(node_id) = props.get("node.id") {
                         port.node_id = node_id.parse().unwrap_or(0);
@@ -1817,6 +2534,24 @@ fn setup_registry_listener(
                     );
 
                     state_add.borrow_mut().ports.insert(global.id, port.clone());
+
+                    // Create links that were waiting for this port
+                    let ready_links: Vec<(u32, u32)> = {
+                        let mut st = state_add.borrow_mut();
+                        let (ready, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut st.pending_links)
+                            .into_iter()
+                            .partition(|(output_port, input_port, _)| {
+                                st.ports.contains_key(output_port) && st.ports.contains_key(input_port)
+                            });
+                        st.pending_links = waiting;
+                        ready.into_iter().map(|(output_port, input_port, _)| (output_port, input_port)).collect()
+                    };
+                    for (output_port, input_port) in ready_links {
+                        if let Err(e) = try_create_link_native(&core, &state_add, output_port, input_port) {
+                            let _ = event_tx_add.send(PwEvent::Error(format!("Failed to create link: {}", e)));
+                        }
+                    }
+
                     let _ = event_tx_add.send(PwEvent::PortAdded(port));
                 }
                 ObjectType::Metadata => {
@@ -1833,13 +2568,30 @@ fn setup_registry_listener(
                     }
                     match registry_clone.bind::<Metadata, _>(global) {
                         Ok(metadata) => {
+                            // The current values arrive as property events
+                            // right after binding, so the service reconciles
+                            // with the default at startup too
                             let event_tx_meta = event_tx_add.clone();
+                            let state_meta = state_add.clone();
                             let listener = metadata
                                 .add_listener_local()
-                                .property(move |_subject, key, _type_, _value| {
-                                    if key == Some("default.audio.sink") {
-                                        let _ =
-                                            event_tx_meta.send(PwEvent::DefaultSinkChanged);
+                                .property(move |subject, key, _type_, value| {
+                                    // Defaults are properties of the core object
+                                    if subject != 0 {
+                                        return 0;
+                                    }
+                                    let name = value.and_then(routing::parse_metadata_node_name);
+                                    match key {
+                                        Some("default.audio.sink") => {
+                                            debug!("Default sink: {:?}", name);
+                                            state_meta.borrow_mut().default_sink_name = name.clone();
+                                            let _ = event_tx_meta.send(PwEvent::DefaultSinkChanged { name });
+                                        }
+                                        Some("default.audio.source") => {
+                                            debug!("Default source: {:?}", name);
+                                            state_meta.borrow_mut().default_source_name = name;
+                                        }
+                                        _ => {}
                                     }
                                     0
                                 })
@@ -1848,12 +2600,9 @@ fn setup_registry_listener(
                                 .borrow_mut()
                                 .default_metadata = Some((global.id, metadata, listener));
                             info!(
-                                "Bound 'default' metadata (id={}) for default-sink tracking",
+                                "Bound 'default' metadata (id={}) for default-device tracking",
                                 global.id
                             );
-                            // Emit once so the service can reconcile with the
-                            // current default at startup.
-                            let _ = event_tx_add.send(PwEvent::DefaultSinkChanged);
                         }
                         Err(e) => {
                             warn!("Failed to bind 'default' metadata {}: {:?}", global.id, e);
@@ -1913,9 +2662,9 @@ fn setup_registry_listener(
                 debug!("Node removed: {}", id);
                 // Clean up virtual_sinks map if this was a virtual sink node
                 state.virtual_sinks.retain(|_, &mut sink_id| sink_id != id);
-                // Clean up any pending CLI commands for this node
-                state.pending_cli_cmds.remove(&id);
-                state.cli_last_cmd.remove(&id);
+                state.node_balance.remove(&id);
+                state.node_volumes.remove(&id);
+                state.node_downmix.remove(&id);
                 let _ = event_tx_remove.send(PwEvent::NodeRemoved(id));
             } else if state.ports.remove(&id).is_some() {
                 debug!("Port removed: {}", id);
@@ -1975,3 +2724,27 @@ fn build_mute_pod(muted: bool) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
 
     Ok(buffer)
 }
+
+fn build_params_pod(
+    params: Vec<libspa::pod::Value>,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    use libspa::pod::serialize::PodSerializer;
+    use libspa::pod::Value;
+    use std::io::Cursor;
+
+    let props = Value::Object(libspa::pod::Object {
+        type_: libspa::sys::SPA_TYPE_OBJECT_Props,
+        id: libspa::sys::SPA_PARAM_Props,
+        properties: vec![libspa::pod::Property {
+            key: libspa::sys::SPA_PROP_params,
+            flags: libspa::pod::PropertyFlags::empty(),
+            value: Value::Struct(params),
+        }],
+    });
+
+    let mut buffer = Vec::new();
+    let cursor = Cursor::new(&mut buffer);
+    PodSerializer::serialize(cursor, &props)?;
+
+    Ok(buffer)
+}
@@ -0,0 +1,1000 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Per-channel plugin chain processing on the daemon's PipeWire thread.
+//!
+//! Each channel with plugins gets a pair of native streams: an Audio/Sink that
+//! the channel's audio is linked into, and an output stream that the service
+//! routes onwards (to the EQ, the output device, or an input channel's capture
+//! stream). Audio passes through the plugin chain between the two.
+//!
+//! The streams are stereo, so the service doesn't give surround channels a
+//! plugin filter, and refuses to load plugins on them (see
+//! `ChannelState::fits_filters`).
+//!
+//! # Architecture
+//!
+//! ```text
+//! [Loopback/Mic] → [Audio/Sink] → [Plugin Chain] → [Stream/Output/Audio] → [Next stage]
+//!                        ↓               ↑
+//!                  process_cb     PluginChainContext
+//! ```
+//!
+//! # Thread Safety
+//!
+//! - Streams are created and controlled on PipeWire's main loop thread; their
+//!   process callbacks run on the data loop (RT_PROCESS)
+//! - The chain context is owned by the capture stream's process callback.
+//!   Changes reach it through a lock-free [`Mailbox`] and are applied before
+//!   the next block; anything that allocates is allocated before it's sent,
+//!   and the values it replaces are sent back to be freed on the main loop
+//! - Main loop callbacks (format, state) have listeners of their own and
+//!   share only atomics with the process callbacks
+//! - Plugin instances are owned by the service's `PluginManager` and accessed
+//!   here via try_lock() for RT safety
+//! - If the lock is contended the block is passed through unprocessed
+//!
+//! # Latency
+//!
+//! The output of the chain can be delayed further to line up with other
+//! channels on the same device (see `audio::latency`). The service reports
+//! the chain latency plus that delay on the output stream as its
+//! ProcessLatency, so PipeWire accounts for it downstream.
+//!
+//! # Spectrum
+//!
+//! When the spectrum analyzer taps a channel after its plugins, the chain's
+//! output is copied to the analyzer's feed before the compensation delay.
+//!
+//! # Noise Suppression
+//!
+//! On input channels with noise suppression the mic is denoised ahead of the
+//! chain (see `audio::noise_suppression`); the filter is created for that
+//! alone when the channel has no plugins. The suppressor's voice activity is
+//! published for the service's "talking" signal.
+//!
+//! # Push-to-talk
+//!
+//! Input channels with a talk mode end the chain with a talk gate (see
+//! `audio::talk_gate`), which fades the mic in and out as the service keys
+//! it. Like noise suppression, it alone is reason enough for the filter.
+
+use super::latency::MAX_COMPENSATION_FRAMES;
+use super::noise_suppression::{
+    new_suppressor, NoiseSuppression, NoiseSuppressor, Suppressor, VoiceActivity,
+};
+use super::spectrum::SpectrumFeed;
+use super::talk_gate::{TalkGate, TalkGateSettings};
+use crate::realtime::{Mailbox, RingBuffer, RingBufferReader, RingBufferWriter};
+use libspa::pod::Pod;
+use pipewire::properties::properties;
+use pipewire::spa::param::format::{MediaSubtype, MediaType};
+use pipewire::spa::param::format_utils;
+use pipewire::spa::param::ParamType;
+use pipewire::stream::{Stream, StreamFlags, StreamListener, StreamRc};
+use sootmix_plugin_host::SharedPluginInstances;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tracing::{debug, info, trace};
+use uuid::Uuid;
+
+/// Size of the audio buffer between capture and playback streams (in frames).
+const AUDIO_BUFFER_FRAMES: usize = 2048;
+
+/// Number of audio channels (stereo).
+const NUM_CHANNELS: usize = 2;
+
+/// Default block size used to pre-allocate processing buffers.
+const DEFAULT_BLOCK_SIZE: usize = 1024;
+
+/// Graph rate assumed until the capture stream's format is known.
+const DEFAULT_SAMPLE_RATE: u32 = 48_000;
+
+/// Applied updates that can wait to be freed. At most two are ever
+/// outstanding: one sent back before the main loop last looked, one after.
+const SPENT_QUEUE_CAPACITY: usize = 4;
+
+/// Node name of a channel's plugin chain sink.
+pub fn plugin_sink_node_name(name: &str) -> String {
+    format!("sootmix.plugins.{}", name)
+}
+
+/// Node name of a channel's plugin chain output stream.
+pub fn plugin_output_node_name(name: &str) -> String {
+    format!("sootmix.plugins.{}.output", name)
+}
+
+// ============================================================================
+// PROCESSING CONTEXT
+// ============================================================================
+
+/// Context for plugin chain processing, owned by the RT callback.
+pub struct PluginChainContext {
+    /// Shared plugin instances (use try_lock for RT safety).
+    plugin_instances: SharedPluginInstances,
+    /// Active (non-bypassed) instance IDs in processing order.
+    plugin_chain: Vec<Uuid>,
+    /// Noise suppression ahead of the chain (input channels).
+    noise: NoiseSuppressor,
+    /// Push-to-talk gate after the chain (input channels).
+    talk: TalkGate,
+    /// Pre-allocated ping-pong buffer A for RT-safe plugin chain processing.
+    temp_a: Vec<Vec<f32>>,
+    /// Pre-allocated ping-pong buffer B for RT-safe plugin chain processing.
+    temp_b: Vec<Vec<f32>>,
+    /// Delay applied after the chain to align with other channels.
+    compensation: DelayLine,
+    /// Spectrum analyzer feed while the analyzer taps the chain's output.
+    spectrum: Option<Arc<SpectrumFeed>>,
+    /// Sample rate of the audio, from the capture stream's format.
+    sample_rate: u32,
+}
+
+impl PluginChainContext {
+    /// Create a new processing context.
+    pub fn new(plugin_instances: SharedPluginInstances, plugin_chain: Vec<Uuid>) -> Self {
+        Self {
+            plugin_instances,
+            plugin_chain,
+            noise: NoiseSuppressor::new(),
+            talk: TalkGate::new(),
+            temp_a: vec![vec![0.0f32; DEFAULT_BLOCK_SIZE]; NUM_CHANNELS],
+            temp_b: vec![vec![0.0f32; DEFAULT_BLOCK_SIZE]; NUM_CHANNELS],
+            compensation: DelayLine::new(),
+            spectrum: None,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+        }
+    }
+
+    /// Swap in the changes of `update`, leaving the values they replace in
+    /// it. Never allocates or frees.
+    fn apply(&mut self, update: &mut ContextUpdate) {
+        if let Some(plugin_chain) = &mut update.plugin_chain {
+            std::mem::swap(&mut self.plugin_chain, plugin_chain);
+        }
+        if let Some(suppressor) = update.suppressor.take() {
+            update.suppressor = self.noise.set_suppressor(suppressor);
+        }
+        if let Some(settings) = update.noise_suppression {
+            self.noise.set_settings(settings);
+        }
+        match update.talk_gate {
+            Some(settings) if update.talk_gate_reset => self.talk.reset(settings),
+            Some(settings) => self.talk.set(settings),
+            None => {}
+        }
+        if let Some(feed) = &mut update.spectrum {
+            std::mem::swap(&mut self.spectrum, feed);
+        }
+        if let Some(buffers) = &mut update.delay_buffers {
+            self.compensation.swap_buffers(buffers);
+        }
+        if let Some(frames) = update.compensation {
+            self.compensation.set_delay(frames);
+        }
+    }
+
+    /// Process deinterleaved stereo audio through the plugin chain and the
+    /// talk gate, then the compensation delay.
+    ///
+    /// Returns false (and copies input to output) if the chain is empty or
+    /// the instances lock is contended.
+    pub fn process_audio(&mut self, inputs: &[&[f32]; 2], outputs: &mut [&mut [f32]; 2]) -> bool {
+        let processed = self.process_chain(inputs, outputs);
+        self.talk.process(outputs, self.sample_rate);
+        if let Some(feed) = &self.spectrum {
+            feed.try_push_planar(outputs[0], outputs[1], self.sample_rate);
+        }
+        self.compensation.process(outputs);
+        processed
+    }
+
+    fn process_chain(&mut self, inputs: &[&[f32]; 2], outputs: &mut [&mut [f32]; 2]) -> bool {
+        if self.plugin_chain.is_empty() {
+            Self::copy_passthrough(inputs, outputs);
+            return false;
+        }
+
+        let mut instances = match self.plugin_instances.try_lock() {
+            Some(guard) => guard,
+            None => {
+                Self::copy_passthrough(inputs, outputs);
+                return false;
+            }
+        };
+
+        let num_samples = inputs[0].len();
+
+        // Ensure pre-allocated buffers are large enough (rare resize for unexpected block sizes)
+        for buf in self.temp_a.iter_mut().chain(self.temp_b.iter_mut()) {
+            if buf.len() < num_samples {
+                buf.resize(num_samples, 0.0);
+            }
+        }
+
+        for (src, dst) in inputs.iter().zip(self.temp_a.iter_mut()) {
+            dst[..num_samples].copy_from_slice(src);
+        }
+
+        // Ping-pong: read from one buffer, write to the other, then swap
+        let mut read_a = true;
+        for &instance_id in &self.plugin_chain {
+            let Some(instance) = instances.get_mut(&instance_id) else {
+                continue;
+            };
+
+            let (read_bufs, write_bufs) = if read_a {
+                (&self.temp_a, &mut self.temp_b)
+            } else {
+                (&self.temp_b, &mut self.temp_a)
+            };
+
+            let input_slices: [&[f32]; 2] =
+                [&read_bufs[0][..num_samples], &read_bufs[1][..num_samples]];
+            let (wb0, wb1) = write_bufs.split_at_mut(1);
+            let mut output_slices: [&mut [f32]; 2] =
+                [&mut wb0[0][..num_samples], &mut wb1[0][..num_samples]];
+
+            instance.process(&input_slices, &mut output_slices);
+            read_a = !read_a;
+        }
+
+        let final_bufs = if read_a { &self.temp_a } else { &self.temp_b };
+        for (temp, output) in final_bufs.iter().zip(outputs.iter_mut()) {
+            output.copy_from_slice(&temp[..num_samples]);
+        }
+
+        true
+    }
+
+    fn copy_passthrough(inputs: &[&[f32]; 2], outputs: &mut [&mut [f32]; 2]) {
+        for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+            output.copy_from_slice(input);
+        }
+    }
+}
+
+// ============================================================================
+// UPDATES
+// ============================================================================
+
+/// Changes to a running filter's chain context, made on the main loop.
+///
+/// Every field is `None` when unchanged. The capture callback swaps the new
+/// values in before its next block and sends the update back holding the
+/// ones they replaced, so nothing is allocated or freed on the RT thread.
+#[derive(Default)]
+struct ContextUpdate {
+    /// Active plugin instance IDs in processing order.
+    plugin_chain: Option<Vec<Uuid>>,
+    /// Denoiser, sent the first time noise suppression is turned on.
+    suppressor: Option<Suppressor>,
+    /// Noise suppression settings (`Some(None)` to bypass).
+    noise_suppression: Option<Option<NoiseSuppression>>,
+    /// Talk gate settings (`Some(None)` to let everything through).
+    talk_gate: Option<Option<TalkGateSettings>>,
+    /// Whether the talk gate jumps to its settings rather than fading.
+    talk_gate_reset: bool,
+    /// Spectrum analyzer feed (`Some(None)` to stop).
+    spectrum: Option<Option<Arc<SpectrumFeed>>>,
+    /// Zeroed delay buffers, sent the first time a delay is set.
+    delay_buffers: Option<Vec<Vec<f32>>>,
+    /// Compensation delay in frames.
+    compensation: Option<u32>,
+}
+
+// ============================================================================
+// COMPENSATION DELAY
+// ============================================================================
+
+/// Stereo delay line for latency compensation.
+struct DelayLine {
+    /// One circular buffer per channel (empty until a delay is set).
+    buffers: Vec<Vec<f32>>,
+    /// Next write position.
+    pos: usize,
+    /// Delay in frames.
+    delay: usize,
+}
+
+impl DelayLine {
+    fn new() -> Self {
+        Self {
+            buffers: Vec::new(),
+            pos: 0,
+            delay: 0,
+        }
+    }
+
+    /// Buffers for the longest delay, to allocate off the RT thread.
+    fn new_buffers() -> Vec<Vec<f32>> {
+        vec![vec![0.0; MAX_COMPENSATION_FRAMES as usize]; NUM_CHANNELS]
+    }
+
+    /// Swap in zeroed buffers from [`DelayLine::new_buffers`].
+    fn swap_buffers(&mut self, buffers: &mut Vec<Vec<f32>>) {
+        std::mem::swap(&mut self.buffers, buffers);
+        self.pos = 0;
+    }
+
+    /// Change the delay, clearing the buffered audio. There's no delay
+    /// until the line has buffers.
+    fn set_delay(&mut self, frames: u32) {
+        let frames = if self.buffers.is_empty() {
+            0
+        } else {
+            frames.min(MAX_COMPENSATION_FRAMES) as usize
+        };
+        if frames == self.delay {
+            return;
+        }
+        for buffer in &mut self.buffers {
+            buffer.fill(0.0);
+        }
+        self.pos = 0;
+        self.delay = frames;
+    }
+
+    /// Delay the block in place.
+    fn process(&mut self, channels: &mut [&mut [f32]; 2]) {
+        if self.delay == 0 {
+            return;
+        }
+
+        let capacity = self.buffers[0].len();
+        let frames = channels[0].len();
+        for (channel, buffer) in channels.iter_mut().zip(self.buffers.iter_mut()) {
+            let mut pos = self.pos;
+            for sample in channel.iter_mut() {
+                let read = (pos + capacity - self.delay) % capacity;
+                let delayed = buffer[read];
+                buffer[pos] = *sample;
+                *sample = delayed;
+                pos = (pos + 1) % capacity;
+            }
+        }
+        self.pos = (self.pos + frames) % capacity;
+    }
+}
+
+// ============================================================================
+// AUDIO RING BUFFER
+// ============================================================================
+
+/// Simple audio ring buffer for passing samples between streams.
+struct AudioRingBuffer {
+    /// Interleaved stereo samples [L0, R0, L1, R1, ...].
+    buffer: Vec<f32>,
+    /// Write position.
+    write_pos: usize,
+    /// Read position.
+    read_pos: usize,
+    /// Capacity in frames.
+    capacity_frames: usize,
+}
+
+impl AudioRingBuffer {
+    fn new(capacity_frames: usize) -> Self {
+        Self {
+            buffer: vec![0.0; capacity_frames * NUM_CHANNELS],
+            write_pos: 0,
+            read_pos: 0,
+            capacity_frames,
+        }
+    }
+
+    /// Write interleaved stereo samples to the buffer.
+    fn write(&mut self, samples: &[f32]) {
+        let frames = samples.len() / NUM_CHANNELS;
+        for i in 0..frames {
+            let idx = (self.write_pos % self.capacity_frames) * NUM_CHANNELS;
+            self.buffer[idx] = samples[i * NUM_CHANNELS];
+            self.buffer[idx + 1] = samples[i * NUM_CHANNELS + 1];
+            self.write_pos = self.write_pos.wrapping_add(1);
+        }
+    }
+
+    /// Read interleaved stereo samples from the buffer.
+    /// Returns the number of frames actually read.
+    fn read(&mut self, samples: &mut [f32]) -> usize {
+        let requested_frames = samples.len() / NUM_CHANNELS;
+        let available = self.write_pos.wrapping_sub(self.read_pos);
+        let frames_to_read = requested_frames.min(available);
+
+        for i in 0..frames_to_read {
+            let idx = (self.read_pos % self.capacity_frames) * NUM_CHANNELS;
+            samples[i * NUM_CHANNELS] = self.buffer[idx];
+            samples[i * NUM_CHANNELS + 1] = self.buffer[idx + 1];
+            self.read_pos = self.read_pos.wrapping_add(1);
+        }
+
+        // Zero-fill if not enough data
+        for i in frames_to_read..requested_frames {
+            samples[i * NUM_CHANNELS] = 0.0;
+            samples[i * NUM_CHANNELS + 1] = 0.0;
+        }
+
+        frames_to_read
+    }
+}
+
+// ============================================================================
+// STREAM USER DATA
+// ============================================================================
+
+/// User data of the capture stream's process callback, which owns the chain.
+struct CaptureData {
+    /// Processing context with the plugin chain.
+    context: PluginChainContext,
+    /// Changes from the main loop, applied before the next block.
+    updates: Arc<Mailbox<ContextUpdate>>,
+    /// Applied updates, sent back to be freed on the main loop.
+    spent: RingBufferWriter<Box<ContextUpdate>>,
+    /// Sample rate from the stream's format, set on the main loop.
+    sample_rate: Arc<AtomicU32>,
+    /// Audio buffer to the playback stream.
+    audio_buffer: Rc<RefCell<AudioRingBuffer>>,
+    /// Pre-allocated deinterleave buffers for RT-safe capture processing.
+    left_in: Vec<f32>,
+    right_in: Vec<f32>,
+    left_out: Vec<f32>,
+    right_out: Vec<f32>,
+}
+
+/// User data of the playback stream's process callback.
+///
+/// Both process callbacks run on the data loop, one at a time, so the audio
+/// buffer between them is never borrowed twice.
+struct PlaybackData {
+    /// Audio buffer from the capture stream.
+    audio_buffer: Rc<RefCell<AudioRingBuffer>>,
+}
+
+// ============================================================================
+// PLUGIN FILTER STREAMS
+// ============================================================================
+
+/// A pair of PipeWire streams running a channel's plugin chain.
+pub struct PluginFilterStreams {
+    /// Channel ID this filter belongs to.
+    pub channel_id: Uuid,
+    /// Capture stream (Audio/Sink fed by the previous stage).
+    capture_stream: StreamRc,
+    /// Playback stream (Stream/Output/Audio routed by the service).
+    playback_stream: StreamRc,
+    /// Capture stream state and format listener (main loop).
+    _capture_events: StreamListener<Arc<AtomicU32>>,
+    /// Capture stream process listener (data loop).
+    _capture_listener: StreamListener<CaptureData>,
+    /// Playback stream state listener (main loop).
+    _playback_events: StreamListener<()>,
+    /// Playback stream process listener (data loop).
+    _playback_listener: StreamListener<PlaybackData>,
+    /// Changes waiting for the capture callback.
+    updates: Arc<Mailbox<ContextUpdate>>,
+    /// Updates the capture callback is done with, freed here.
+    spent: RingBufferReader<Box<ContextUpdate>>,
+    /// Whether the capture callback has a denoiser.
+    has_suppressor: bool,
+    /// Whether the capture callback has delay buffers.
+    has_delay_buffers: bool,
+}
+
+impl PluginFilterStreams {
+    /// Create a new plugin filter stream pair.
+    ///
+    /// # Arguments
+    /// * `core` - PipeWire core connection
+    /// * `channel_id` - Channel UUID
+    /// * `name` - Sanitized channel name used for node naming
+    /// * `plugin_instances` - Shared plugin instances for processing
+    /// * `plugin_chain` - Active plugin instance IDs in processing order
+    /// * `noise_suppression` - Noise suppression ahead of the chain, if any
+    /// * `voice_activity` - Where noise suppression publishes voice activity
+    pub fn new(
+        core: &pipewire::core::CoreRc,
+        channel_id: Uuid,
+        name: &str,
+        plugin_instances: SharedPluginInstances,
+        plugin_chain: Vec<Uuid>,
+        noise_suppression: Option<NoiseSuppression>,
+        voice_activity: Option<Arc<VoiceActivity>>,
+    ) -> Result<Self, pipewire::Error> {
+        let sink_name = plugin_sink_node_name(name);
+        let output_name = plugin_output_node_name(name);
+
+        info!(
+            "Creating plugin filter streams for channel '{}' ({})",
+            name, channel_id
+        );
+
+        let mut context = PluginChainContext::new(plugin_instances, plugin_chain);
+        context.noise.set_voice_activity(voice_activity);
+        if noise_suppression.is_some() {
+            context.noise.set_suppressor(new_suppressor());
+        }
+        context.noise.set_settings(noise_suppression);
+
+        let updates = Arc::new(Mailbox::new());
+        let (spent_writer, spent) = RingBuffer::new(SPENT_QUEUE_CAPACITY).split();
+        let sample_rate = Arc::new(AtomicU32::new(DEFAULT_SAMPLE_RATE));
+        let audio_buffer = Rc::new(RefCell::new(AudioRingBuffer::new(AUDIO_BUFFER_FRAMES)));
+
+        // Capture side: a sink the service links the previous stage into
+        let capture_stream = StreamRc::new(
+            core.clone(),
+            &sink_name,
+            properties! {
+                "media.type" => "Audio",
+                "media.class" => "Audio/Sink",
+                "media.category" => "Filter",
+                "media.role" => "DSP",
+                "node.name" => sink_name.clone(),
+                "node.description" => format!("SootMix Plugins - {}", name),
+                "audio.channels" => "2",
+                "audio.position" => "FL,FR",
+                "node.autoconnect" => "false",
+                "node.dont-move" => "true"
+            },
+        )?;
+
+        // Playback side: routed explicitly by the service, never by WirePlumber
+        let playback_stream = StreamRc::new(
+            core.clone(),
+            &output_name,
+            properties! {
+                "media.type" => "Audio",
+                "media.class" => "Stream/Output/Audio",
+                "media.category" => "Filter",
+                "media.role" => "DSP",
+                "node.name" => output_name.clone(),
+                "node.description" => format!("SootMix Plugins - {} Output", name),
+                "audio.channels" => "2",
+                "audio.position" => "FL,FR",
+                "node.autoconnect" => "false",
+                "node.dont-move" => "true"
+            },
+        )?;
+
+        let capture_events = capture_stream
+            .add_local_listener_with_user_data(Arc::clone(&sample_rate))
+            .state_changed(move |_stream, _rate, old, new| {
+                debug!(
+                    "Plugin capture stream state: {:?} -> {:?} ({})",
+                    old, new, channel_id
+                );
+            })
+            .param_changed(|_stream, rate, id, param| {
+                let Some(param) = param else {
+                    return;
+                };
+                if id != ParamType::Format.as_raw() {
+                    return;
+                }
+                if !matches!(
+                    format_utils::parse_format(param),
+                    Ok((MediaType::Audio, MediaSubtype::Raw))
+                ) {
+                    return;
+                }
+                let mut info = pipewire::spa::param::audio::AudioInfoRaw::new();
+                if info.parse(param).is_ok() {
+                    rate.store(info.rate(), Ordering::Relaxed);
+                }
+            })
+            .register()?;
+
+        let capture_data = CaptureData {
+            context,
+            updates: Arc::clone(&updates),
+            spent: spent_writer,
+            sample_rate,
+            audio_buffer: Rc::clone(&audio_buffer),
+            left_in: vec![0.0f32; DEFAULT_BLOCK_SIZE],
+            right_in: vec![0.0f32; DEFAULT_BLOCK_SIZE],
+            left_out: vec![0.0f32; DEFAULT_BLOCK_SIZE],
+            right_out: vec![0.0f32; DEFAULT_BLOCK_SIZE],
+        };
+
+        let capture_listener = capture_stream
+            .add_local_listener_with_user_data(capture_data)
+            .process(capture_callback)
+            .register()?;
+
+        let playback_events = playback_stream
+            .add_local_listener_with_user_data(())
+            .state_changed(move |_stream, _, old, new| {
+                debug!(
+                    "Plugin playback stream state: {:?} -> {:?} ({})",
+                    old, new, channel_id
+                );
+            })
+            .register()?;
+
+        let playback_listener = playback_stream
+            .add_local_listener_with_user_data(PlaybackData { audio_buffer })
+            .process(playback_callback)
+            .register()?;
+
+        Ok(Self {
+            channel_id,
+            capture_stream,
+            playback_stream,
+            _capture_events: capture_events,
+            _capture_listener: capture_listener,
+            _playback_events: playback_events,
+            _playback_listener: playback_listener,
+            updates,
+            spent,
+            has_suppressor: noise_suppression.is_some(),
+            has_delay_buffers: false,
+        })
+    }
+
+    /// Connect the streams and start processing.
+    ///
+    /// Neither side autoconnects; the service links them once both nodes
+    /// have appeared in the registry.
+    pub fn connect(&self) -> Result<(), pipewire::Error> {
+        info!(
+            "Connecting plugin filter streams for channel {}",
+            self.channel_id
+        );
+
+        let base_flags = StreamFlags::MAP_BUFFERS | StreamFlags::RT_PROCESS;
+
+        self.capture_stream.connect(
+            libspa::utils::Direction::Input,
+            None,
+            base_flags | StreamFlags::DRIVER,
+            &mut [],
+        )?;
+
+        self.playback_stream.connect(
+            libspa::utils::Direction::Output,
+            None,
+            base_flags,
+            &mut [],
+        )?;
+
+        self.capture_stream.set_active(true)?;
+        self.playback_stream.set_active(true)?;
+
+        Ok(())
+    }
+
+    /// Disconnect the streams.
+    pub fn disconnect(&self) -> Result<(), pipewire::Error> {
+        info!(
+            "Disconnecting plugin filter streams for channel {}",
+            self.channel_id
+        );
+
+        self.capture_stream.disconnect()?;
+        self.playback_stream.disconnect()?;
+        Ok(())
+    }
+
+    /// Send a change to the capture callback, merged into any it hasn't
+    /// picked up yet, and free the updates it's done with.
+    fn send(&mut self, change: impl FnOnce(&mut ContextUpdate)) {
+        while self.spent.pop().is_some() {}
+        let mut update = self.updates.take().unwrap_or_default();
+        change(&mut update);
+        self.updates.replace(update);
+    }
+
+    /// Replace the active plugin chain.
+    pub fn update_plugin_chain(&mut self, plugin_chain: Vec<Uuid>) {
+        self.send(|update| update.plugin_chain = Some(plugin_chain));
+    }
+
+    /// Change the noise suppression ahead of the chain (`None` to bypass).
+    pub fn set_noise_suppression(&mut self, settings: Option<NoiseSuppression>) {
+        let suppressor = (settings.is_some() && !self.has_suppressor).then(new_suppressor);
+        self.has_suppressor |= suppressor.is_some();
+        self.send(|update| {
+            if suppressor.is_some() {
+                update.suppressor = suppressor;
+            }
+            update.noise_suppression = Some(settings);
+        });
+    }
+
+    /// Change the talk gate after the chain (`None` to let everything through).
+    pub fn set_talk_gate(&mut self, settings: Option<TalkGateSettings>) {
+        self.send(|update| update.talk_gate = Some(settings));
+    }
+
+    /// Set the talk gate without fading, before the streams are connected.
+    pub fn reset_talk_gate(&mut self, settings: Option<TalkGateSettings>) {
+        self.send(|update| {
+            update.talk_gate = Some(settings);
+            update.talk_gate_reset = true;
+        });
+    }
+
+    /// Copy the chain's output to `feed` (`None` to stop).
+    pub fn set_spectrum(&mut self, feed: Option<Arc<SpectrumFeed>>) {
+        self.send(|update| update.spectrum = Some(feed));
+    }
+
+    /// Set the compensation delay and the latency reported to PipeWire.
+    ///
+    /// `reported` is the whole latency of the filter in frames: the plugin
+    /// chain's plus `compensation`.
+    pub fn set_latency(&mut self, compensation: u32, reported: u32) -> Result<(), pipewire::Error> {
+        let buffers = (compensation > 0 && !self.has_delay_buffers).then(DelayLine::new_buffers);
+        self.has_delay_buffers |= buffers.is_some();
+        self.send(|update| {
+            if buffers.is_some() {
+                update.delay_buffers = buffers;
+            }
+            update.compensation = Some(compensation);
+        });
+
+        let values = build_process_latency_pod(reported)?;
+        let pod = Pod::from_bytes(&values).ok_or(pipewire::Error::CreationFailed)?;
+        self.playback_stream.update_params(&mut [pod])
+    }
+}
+
+// ============================================================================
+// PROCESS CALLBACK
+// ============================================================================
+
+/// Build a ProcessLatency param of `frames` frames.
+fn build_process_latency_pod(frames: u32) -> Result<Vec<u8>, pipewire::Error> {
+    use libspa::pod::serialize::PodSerializer;
+    use libspa::pod::Value;
+    use std::io::Cursor;
+
+    let latency = Value::Object(libspa::pod::Object {
+        type_: libspa::sys::SPA_TYPE_OBJECT_ParamProcessLatency,
+        id: libspa::sys::SPA_PARAM_ProcessLatency,
+        properties: vec![libspa::pod::Property {
+            key: libspa::sys::SPA_PARAM_PROCESS_LATENCY_rate,
+            flags: libspa::pod::PropertyFlags::empty(),
+            value: Value::Int(frames.min(i32::MAX as u32) as i32),
+        }],
+    });
+
+    let (cursor, _) = PodSerializer::serialize(Cursor::new(Vec::new()), &latency)
+        .map_err(|_| pipewire::Error::CreationFailed)?;
+    Ok(cursor.into_inner())
+}
+
+/// Process callback of the capture stream.
+fn capture_callback(stream: &Stream, data: &mut CaptureData) {
+    apply_updates(data);
+    data.context.sample_rate = data.sample_rate.load(Ordering::Relaxed);
+    with_buffer(stream, |samples, n_frames| {
+        process_capture(data, samples, n_frames)
+    });
+}
+
+/// Process callback of the playback stream.
+fn playback_callback(stream: &Stream, data: &mut PlaybackData) {
+    with_buffer(stream, |samples, n_frames| {
+        let frames_read = data.audio_buffer.borrow_mut().read(samples);
+        if frames_read < n_frames {
+            trace!(
+                "Plugin playback underrun: got {} frames, needed {}",
+                frames_read,
+                n_frames
+            );
+        }
+    });
+}
+
+/// Apply the changes waiting in the mailbox and send the update back.
+///
+/// Waits while the spent queue is full, so the callback never frees one.
+fn apply_updates(data: &mut CaptureData) {
+    if data.spent.is_full() {
+        return;
+    }
+    if let Some(mut update) = data.updates.take() {
+        data.context.apply(&mut update);
+        data.spent.push(update);
+    }
+}
+
+/// Run `process` over the interleaved samples of the stream's next buffer.
+fn with_buffer(stream: &Stream, process: impl FnOnce(&mut [f32], usize)) {
+    let mut buffer = match stream.dequeue_buffer() {
+        Some(b) => b,
+        None => {
+            trace!("No buffer available");
+            return;
+        }
+    };
+
+    let datas = buffer.datas_mut();
+    if datas.is_empty() {
+        return;
+    }
+
+    let data = &mut datas[0];
+    let chunk = data.chunk();
+    let n_frames = chunk.size() as usize / (NUM_CHANNELS * std::mem::size_of::<f32>());
+
+    if n_frames == 0 {
+        return;
+    }
+
+    let audio_data = match data.data() {
+        Some(d) => d,
+        None => return,
+    };
+
+    let samples: &mut [f32] = unsafe {
+        std::slice::from_raw_parts_mut(audio_data.as_mut_ptr() as *mut f32, n_frames * NUM_CHANNELS)
+    };
+
+    process(samples, n_frames);
+
+    let chunk_mut = data.chunk_mut();
+    *chunk_mut.size_mut() = (n_frames * NUM_CHANNELS * std::mem::size_of::<f32>()) as u32;
+    *chunk_mut.offset_mut() = 0;
+    *chunk_mut.stride_mut() = (NUM_CHANNELS * std::mem::size_of::<f32>()) as i32;
+}
+
+/// Process capture stream: run the plugin chain and write to the shared buffer.
+fn process_capture(data: &mut CaptureData, samples: &mut [f32], n_frames: usize) {
+    // Ensure pre-allocated buffers are large enough (rare edge case)
+    if data.left_in.len() < n_frames {
+        data.left_in.resize(n_frames, 0.0);
+        data.right_in.resize(n_frames, 0.0);
+        data.left_out.resize(n_frames, 0.0);
+        data.right_out.resize(n_frames, 0.0);
+    }
+
+    let left_in = &mut data.left_in[..n_frames];
+    let right_in = &mut data.right_in[..n_frames];
+    for i in 0..n_frames {
+        left_in[i] = samples[i * NUM_CHANNELS];
+        right_in[i] = samples[i * NUM_CHANNELS + 1];
+    }
+
+    data.context
+        .noise
+        .process(&mut [&mut *left_in, &mut *right_in]);
+
+    let inputs: [&[f32]; 2] = [left_in, right_in];
+    let mut outputs: [&mut [f32]; 2] = [
+        &mut data.left_out[..n_frames],
+        &mut data.right_out[..n_frames],
+    ];
+
+    data.context.process_audio(&inputs, &mut outputs);
+
+    for i in 0..n_frames {
+        samples[i * NUM_CHANNELS] = outputs[0][i];
+        samples[i * NUM_CHANNELS + 1] = outputs[1][i];
+    }
+
+    data.audio_buffer.borrow_mut().write(samples);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_empty_chain_passes_through() {
+        let instances: SharedPluginInstances = Arc::new(Mutex::new(HashMap::new()));
+        let mut context = PluginChainContext::new(instances, Vec::new());
+
+        let left = [0.1, 0.2, 0.3];
+        let right = [-0.1, -0.2, -0.3];
+        let mut out_l = [0.0; 3];
+        let mut out_r = [0.0; 3];
+
+        let processed = context.process_audio(&[&left, &right], &mut [&mut out_l, &mut out_r]);
+
+        assert!(!processed);
+        assert_eq!(out_l, left);
+        assert_eq!(out_r, right);
+    }
+
+    #[test]
+    fn test_missing_instances_are_skipped() {
+        let instances: SharedPluginInstances = Arc::new(Mutex::new(HashMap::new()));
+        let mut context = PluginChainContext::new(instances, vec![Uuid::new_v4()]);
+
+        let left = [0.5; 4];
+        let right = [0.25; 4];
+        let mut out_l = [0.0; 4];
+        let mut out_r = [0.0; 4];
+
+        context.process_audio(&[&left, &right], &mut [&mut out_l, &mut out_r]);
+
+        assert_eq!(out_l, left);
+        assert_eq!(out_r, right);
+    }
+
+    #[test]
+    fn test_compensation_delays_output() {
+        let instances: SharedPluginInstances = Arc::new(Mutex::new(HashMap::new()));
+        let mut context = PluginChainContext::new(instances, Vec::new());
+        context.apply(&mut ContextUpdate {
+            delay_buffers: Some(DelayLine::new_buffers()),
+            compensation: Some(3),
+            ..ContextUpdate::default()
+        });
+
+        let left = [1.0, 2.0, 3.0, 4.0];
+        let right = [-1.0, -2.0, -3.0, -4.0];
+        let mut out_l = [0.0; 4];
+        let mut out_r = [0.0; 4];
+        context.process_audio(&[&left, &right], &mut [&mut out_l, &mut out_r]);
+        assert_eq!(out_l, [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(out_r, [0.0, 0.0, 0.0, -1.0]);
+
+        context.process_audio(&[&left, &right], &mut [&mut out_l, &mut out_r]);
+        assert_eq!(out_l, [2.0, 3.0, 4.0, 1.0]);
+    }
+
+    #[test]
+    fn test_talk_gate_follows_chain() {
+        let instances: SharedPluginInstances = Arc::new(Mutex::new(HashMap::new()));
+        let mut context = PluginChainContext::new(instances, Vec::new());
+        context.talk.reset(Some(TalkGateSettings {
+            open: false,
+            fade_ms: 20.0,
+        }));
+
+        let left = [0.5; 4];
+        let right = [0.25; 4];
+        let mut out_l = [1.0; 4];
+        let mut out_r = [1.0; 4];
+        context.process_audio(&[&left, &right], &mut [&mut out_l, &mut out_r]);
+        assert_eq!(out_l, [0.0; 4]);
+        assert_eq!(out_r, [0.0; 4]);
+
+        context.apply(&mut ContextUpdate {
+            talk_gate: Some(None),
+            ..ContextUpdate::default()
+        });
+        context.process_audio(&[&left, &right], &mut [&mut out_l, &mut out_r]);
+        assert!(out_l[0] > 0.0 && out_l[3] < 0.5);
+    }
+
+    #[test]
+    fn test_update_returns_replaced_values() {
+        let instances: SharedPluginInstances = Arc::new(Mutex::new(HashMap::new()));
+        let old_chain = vec![Uuid::new_v4()];
+        let mut context = PluginChainContext::new(instances, old_chain.clone());
+
+        let new_chain = vec![Uuid::new_v4(), Uuid::new_v4()];
+        let mut update = ContextUpdate {
+            plugin_chain: Some(new_chain.clone()),
+            compensation: Some(3),
+            ..ContextUpdate::default()
+        };
+        context.apply(&mut update);
+
+        assert_eq!(context.plugin_chain, new_chain);
+        assert_eq!(update.plugin_chain, Some(old_chain));
+        // No delay until the line has buffers
+        assert_eq!(context.compensation.delay, 0);
+    }
+
+    #[test]
+    fn test_audio_ring_buffer_underrun() {
+        let mut buffer = AudioRingBuffer::new(16);
+        buffer.write(&[1.0, 2.0]);
+
+        let mut output = vec![0.0; 4];
+        let frames = buffer.read(&mut output);
+        assert_eq!(frames, 1);
+        assert_eq!(output, vec![1.0, 2.0, 0.0, 0.0]);
+    }
+}
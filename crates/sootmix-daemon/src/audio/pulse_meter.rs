@@ -20,12 +20,23 @@
 //! - `PEAK_DETECT` flag enables efficient server-side peak calculation
 //! - Handles all format conversion automatically
 //! - Works with any audio source (hardware, virtual, monitor ports)
+//!
+//! # Loudness
+//!
+//! Meters given a [`SharedLoudness`] also record the source at full rate on
+//! a second stream of the same context and run a [`LoudnessMeter`] over it.
+//! While the spectrum analyzer taps the meter, that audio is copied to its
+//! [`SpectrumFeed`] as well.
 
+use crate::audio::loudness::{LoudnessMeter, SharedLoudness};
 use crate::audio::native_loopback::AtomicMeterLevels;
+use crate::audio::spectrum::SpectrumFeed;
 use libpulse_binding::context::{Context, FlagSet as ContextFlagSet, State as ContextState};
+use libpulse_binding::def::BufferAttr;
 use libpulse_binding::mainloop::standard::Mainloop;
 use libpulse_binding::sample::{Format, Spec};
 use libpulse_binding::stream::{FlagSet as StreamFlagSet, State as StreamState, Stream};
+use parking_lot::Mutex;
 use std::cell::RefCell;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -33,6 +44,13 @@ use std::thread::{self, JoinHandle};
 use tracing::{debug, error, info, trace, warn};
 use uuid::Uuid;
 
+/// Sample rate of the loudness stream.
+const LOUDNESS_RATE: u32 = 48_000;
+/// Stereo float frames, in bytes.
+const LOUDNESS_FRAME_BYTES: u32 = 2 * 4;
+/// Loudness stream fragment size: 20 ms of audio.
+const LOUDNESS_FRAGMENT_BYTES: u32 = LOUDNESS_RATE / 50 * LOUDNESS_FRAME_BYTES;
+
 /// PulseAudio-based meter for input channels.
 ///
 /// Runs a dedicated thread with its own PA mainloop to capture peak levels
@@ -44,6 +62,10 @@ pub struct PulseAudioMeter {
     source_name: String,
     /// Atomic levels shared with the main thread.
     levels: Arc<AtomicMeterLevels>,
+    /// Loudness readings, if loudness metering is enabled.
+    loudness: Option<Arc<SharedLoudness>>,
+    /// Spectrum analyzer feed while the analyzer taps this meter.
+    spectrum: Arc<Mutex<Option<Arc<SpectrumFeed>>>>,
     /// Flag to signal the meter thread to stop.
     running: Arc<AtomicBool>,
     /// Thread handle (if started).
@@ -73,11 +95,25 @@ impl PulseAudioMeter {
             channel_id,
             source_name: source,
             levels,
+            loudness: None,
+            spectrum: Arc::new(Mutex::new(None)),
             running: Arc::new(AtomicBool::new(false)),
             thread_handle: RefCell::new(None),
         }
     }
 
+    /// Also measure loudness, publishing it to `loudness`.
+    pub fn with_loudness(mut self, loudness: Arc<SharedLoudness>) -> Self {
+        self.loudness = Some(loudness);
+        self
+    }
+
+    /// Copy the full-rate audio to `feed` (`None` to stop). Only meters
+    /// measuring loudness record at full rate.
+    pub fn set_spectrum(&self, feed: Option<Arc<SpectrumFeed>>) {
+        *self.spectrum.lock() = feed;
+    }
+
     /// Start the meter thread.
     ///
     /// Spawns a background thread that runs the PulseAudio mainloop
@@ -96,12 +132,14 @@ impl PulseAudioMeter {
         let channel_id = self.channel_id;
         let source_name = self.source_name.clone();
         let levels = Arc::clone(&self.levels);
+        let loudness = self.loudness.clone();
+        let spectrum = Arc::clone(&self.spectrum);
         let running = Arc::clone(&self.running);
 
         let handle = thread::Builder::new()
             .name(format!("pa-meter-{}", channel_id))
             .spawn(move || {
-                meter_thread(channel_id, source_name, levels, running);
+                meter_thread(channel_id, source_name, levels, loudness, spectrum, running);
             })
             .expect("Failed to spawn PA meter thread");
 
@@ -120,6 +158,9 @@ impl PulseAudioMeter {
 
         // Reset levels to zero
         self.levels.store(0.0, 0.0);
+        if let Some(loudness) = &self.loudness {
+            loudness.store(Default::default());
+        }
 
         // Wait for thread to finish (with timeout)
         if let Some(handle) = self.thread_handle.borrow_mut().take() {
@@ -134,6 +175,11 @@ impl PulseAudioMeter {
         &self.levels
     }
 
+    /// Get the loudness readings, if loudness metering is enabled.
+    pub fn loudness(&self) -> Option<&Arc<SharedLoudness>> {
+        self.loudness.as_ref()
+    }
+
     /// Check if the meter is running.
     #[cfg(test)]
     fn is_running(&self) -> bool {
@@ -155,6 +201,8 @@ fn meter_thread(
     channel_id: Uuid,
     source_name: String,
     levels: Arc<AtomicMeterLevels>,
+    loudness: Option<Arc<SharedLoudness>>,
+    spectrum: Arc<Mutex<Option<Arc<SpectrumFeed>>>>,
     running: Arc<AtomicBool>,
 ) {
     debug!("PA meter thread starting for channel {}", channel_id);
@@ -315,6 +363,13 @@ fn meter_thread(
         channel_id
     );
 
+    // Loudness is best effort: peak metering carries on without it
+    let mut loudness_stream = loudness.and_then(|shared| {
+        let stream = connect_loudness_stream(&mut mainloop, &mut context, source, channel_id)?;
+        Some((stream, LoudnessMeter::new(LOUDNESS_RATE), shared))
+    });
+    let mut loudness_samples: Vec<f32> = Vec::new();
+
     // Main loop - read peaks until stopped
     while running.load(Ordering::Relaxed) {
         // Iterate mainloop (non-blocking to avoid deadlocks)
@@ -366,16 +421,111 @@ fn meter_thread(
             }
         }
 
+        if let Some((stream, meter, shared)) = loudness_stream.as_mut() {
+            if shared.take_reset() {
+                meter.reset();
+            }
+            while let Some(readable) = stream.readable_size() {
+                if readable == 0 {
+                    break;
+                }
+                match stream.peek() {
+                    Ok(libpulse_binding::stream::PeekResult::Data(data)) => {
+                        loudness_samples.clear();
+                        loudness_samples.extend(
+                            data.chunks_exact(4)
+                                .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]])),
+                        );
+                        meter.process(&loudness_samples);
+                        if let Some(feed) = spectrum.lock().as_ref() {
+                            feed.push_interleaved(&loudness_samples, LOUDNESS_RATE);
+                        }
+                        let _ = stream.discard();
+                    }
+                    Ok(libpulse_binding::stream::PeekResult::Hole(_)) => {
+                        let _ = stream.discard();
+                    }
+                    Ok(libpulse_binding::stream::PeekResult::Empty) => break,
+                    Err(e) => {
+                        warn!("PA loudness peek error for channel {}: {:?}", channel_id, e);
+                        break;
+                    }
+                }
+            }
+            shared.store(meter.levels());
+        }
+
         // Short sleep - 5ms gives ~200Hz polling which should catch all 60Hz PA samples
         std::thread::sleep(std::time::Duration::from_millis(5));
     }
 
     // Cleanup
     stream.disconnect().ok();
+    if let Some((mut stream, _, _)) = loudness_stream {
+        stream.disconnect().ok();
+    }
 
     debug!("PA meter thread exiting for channel {}", channel_id);
 }
 
+/// Connect a full-rate stereo record stream to `source` for loudness
+/// metering and wait for it to be ready.
+fn connect_loudness_stream(
+    mainloop: &mut Mainloop,
+    context: &mut Context,
+    source: Option<&str>,
+    channel_id: Uuid,
+) -> Option<Stream> {
+    let spec = Spec {
+        format: Format::FLOAT32NE,
+        rate: LOUDNESS_RATE,
+        channels: 2,
+    };
+    let mut stream = Stream::new(context, "loudness-meter", &spec, None)?;
+
+    // Small fragments keep the momentary reading current
+    let attr = BufferAttr {
+        maxlength: u32::MAX,
+        tlength: u32::MAX,
+        prebuf: u32::MAX,
+        minreq: u32::MAX,
+        fragsize: LOUDNESS_FRAGMENT_BYTES,
+    };
+    let flags = StreamFlagSet::ADJUST_LATENCY | StreamFlagSet::DONT_MOVE;
+    if stream.connect_record(source, Some(&attr), flags).is_err() {
+        warn!(
+            "Failed to connect PA loudness stream for channel {}",
+            channel_id
+        );
+        return None;
+    }
+
+    loop {
+        match mainloop.iterate(true) {
+            libpulse_binding::mainloop::standard::IterateResult::Success(_) => {}
+            _ => {
+                warn!(
+                    "PA mainloop iteration failed waiting for loudness stream {}",
+                    channel_id
+                );
+                return None;
+            }
+        }
+
+        match stream.get_state() {
+            StreamState::Ready => break,
+            StreamState::Failed | StreamState::Terminated => {
+                warn!("PA loudness stream failed for channel {}", channel_id);
+                return None;
+            }
+            _ => continue,
+        }
+    }
+
+    debug!("PA loudness stream ready for channel {}", channel_id);
+    Some(stream)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,4 +547,14 @@ mod tests {
         let meter = PulseAudioMeter::new(Uuid::new_v4(), "", Arc::clone(&levels));
         assert_eq!(meter.source_name, "@DEFAULT_SOURCE@");
     }
+
+    #[test]
+    fn test_loudness_is_optional() {
+        let levels = Arc::new(AtomicMeterLevels::new());
+        let meter = PulseAudioMeter::new(Uuid::new_v4(), "", Arc::clone(&levels));
+        assert!(meter.loudness().is_none());
+
+        let meter = meter.with_loudness(Arc::new(SharedLoudness::new()));
+        assert!(meter.loudness().is_some());
+    }
 }
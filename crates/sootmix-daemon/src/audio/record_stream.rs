@@ -0,0 +1,260 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Multichannel capture stream feeding a recording or a replay buffer.
+//!
+//! One stream per recording, with two `AUXn` channels per recorded source,
+//! and one stereo stream per replay-enabled channel. The stream never
+//! autoconnects: the service links each source's left and right output
+//! ports to `input_AUX{2k}` / `input_AUX{2k+1}` once the stream's ports are
+//! in the registry. Everything arriving in one process cycle lands in the
+//! same interleaved block, which keeps a recording's tracks sample-aligned.
+
+use crate::recording::replay::ReplayWriter;
+use crate::recording::{CaptureBuffer, TRACK_CHANNELS};
+use pipewire::properties::properties;
+use pipewire::spa::param::format::{MediaSubtype, MediaType};
+use pipewire::spa::param::format_utils;
+use pipewire::spa::param::ParamType;
+use pipewire::spa::pod::Pod;
+use pipewire::stream::{Stream, StreamFlags, StreamListener, StreamRc};
+use std::sync::Arc;
+use tracing::{debug, info, trace};
+use uuid::Uuid;
+
+/// Node name of a recording's capture stream.
+pub fn record_node_name(recording_id: Uuid) -> String {
+    format!("sootmix.record.{}", recording_id)
+}
+
+/// Node name of a channel's replay capture stream.
+pub fn replay_node_name(channel_id: Uuid) -> String {
+    format!("sootmix.replay.{}", channel_id)
+}
+
+/// Name of the capture stream's input port for stream channel `index`.
+pub fn record_port_name(index: usize) -> String {
+    format!("input_AUX{}", index)
+}
+
+/// Where a capture stream's audio goes.
+pub enum CaptureSink {
+    /// A recording's writer thread.
+    Recording(Arc<CaptureBuffer>),
+    /// A channel's replay history (always stereo).
+    Replay(ReplayWriter),
+}
+
+/// RT-side state of a [`CaptureSink`].
+enum SinkState {
+    Recording {
+        buffer: Arc<CaptureBuffer>,
+        /// Blocks parked while the writer holds the buffer lock.
+        pending: Vec<f32>,
+    },
+    Replay(ReplayWriter),
+}
+
+impl SinkState {
+    fn channels(&self) -> usize {
+        match self {
+            Self::Recording { buffer, .. } => buffer.channels(),
+            Self::Replay(_) => TRACK_CHANNELS,
+        }
+    }
+
+    fn set_sample_rate(&self, rate: u32) {
+        match self {
+            Self::Recording { buffer, .. } => buffer.set_sample_rate(rate),
+            Self::Replay(writer) => writer.set_sample_rate(rate),
+        }
+    }
+
+    fn push(&mut self, block: &[f32]) {
+        match self {
+            Self::Recording { buffer, pending } => buffer.push(pending, block),
+            Self::Replay(writer) => writer.push(block),
+        }
+    }
+}
+
+/// User data passed to the process callback.
+struct RecordUserData {
+    node_name: String,
+    sink: SinkState,
+}
+
+/// A recording's or replay buffer's capture stream.
+pub struct RecordStream {
+    stream: StreamRc,
+    _listener: StreamListener<RecordUserData>,
+    node_name: String,
+    channels: usize,
+}
+
+impl RecordStream {
+    /// Create a capture stream named `node_name` writing into `sink`.
+    pub fn new(
+        core: &pipewire::core::CoreRc,
+        node_name: String,
+        sink: CaptureSink,
+    ) -> Result<Self, pipewire::Error> {
+        let sink = match sink {
+            CaptureSink::Recording(buffer) => SinkState::Recording {
+                pending: buffer.pending_buffer(),
+                buffer,
+            },
+            CaptureSink::Replay(writer) => SinkState::Replay(writer),
+        };
+        let channels = sink.channels();
+        let positions: Vec<String> = (0..channels).map(|i| format!("AUX{}", i)).collect();
+
+        info!(
+            "Creating record stream {} with {} channels",
+            node_name, channels
+        );
+
+        let stream = StreamRc::new(
+            core.clone(),
+            &node_name,
+            properties! {
+                "media.type" => "Audio",
+                "media.class" => "Stream/Input/Audio",
+                "media.category" => "Capture",
+                "media.role" => "Production",
+                "node.name" => node_name.clone(),
+                "node.description" => "SootMix Recorder",
+                "audio.channels" => channels.to_string(),
+                "audio.position" => positions.join(","),
+                "node.autoconnect" => "false",
+                "node.dont-move" => "true"
+            },
+        )?;
+
+        let user_data = RecordUserData {
+            node_name: node_name.clone(),
+            sink,
+        };
+
+        let listener = stream
+            .add_local_listener_with_user_data(user_data)
+            .state_changed(|_stream, user_data, old, new| {
+                debug!(
+                    "Record stream state: {:?} -> {:?} ({})",
+                    old, new, user_data.node_name
+                );
+            })
+            .param_changed(|_stream, user_data, id, param| {
+                let Some(param) = param else {
+                    return;
+                };
+                if id != ParamType::Format.as_raw() {
+                    return;
+                }
+                if !matches!(
+                    format_utils::parse_format(param),
+                    Ok((MediaType::Audio, MediaSubtype::Raw))
+                ) {
+                    return;
+                }
+                let mut info = pipewire::spa::param::audio::AudioInfoRaw::new();
+                if info.parse(param).is_ok() {
+                    debug!(
+                        "Record stream {} format: {} Hz, {} channels",
+                        user_data.node_name,
+                        info.rate(),
+                        info.channels()
+                    );
+                    user_data.sink.set_sample_rate(info.rate());
+                }
+            })
+            .process(process_callback)
+            .register()?;
+
+        Ok(Self {
+            stream,
+            _listener: listener,
+            node_name,
+            channels,
+        })
+    }
+
+    /// Connect the stream. Sources are linked in by the service.
+    pub fn connect(&self) -> Result<(), pipewire::Error> {
+        // F32 interleaved at the graph rate, with one AUX position per channel
+        let mut info = pipewire::spa::param::audio::AudioInfoRaw::new();
+        info.set_format(pipewire::spa::param::audio::AudioFormat::F32LE);
+        info.set_channels(self.channels as u32);
+        let mut position = [0; 64];
+        for (i, pos) in position.iter_mut().take(self.channels).enumerate() {
+            *pos = libspa::sys::SPA_AUDIO_CHANNEL_AUX0 + i as u32;
+        }
+        info.set_position(position);
+
+        let values: Vec<u8> = pipewire::spa::pod::serialize::PodSerializer::serialize(
+            std::io::Cursor::new(Vec::new()),
+            &pipewire::spa::pod::Value::Object(pipewire::spa::pod::Object {
+                type_: pipewire::spa::utils::SpaTypes::ObjectParamFormat.as_raw(),
+                id: ParamType::EnumFormat.as_raw(),
+                properties: info.into(),
+            }),
+        )
+        .map_err(|_| pipewire::Error::CreationFailed)?
+        .0
+        .into_inner();
+        let mut params = [Pod::from_bytes(&values).ok_or(pipewire::Error::CreationFailed)?];
+
+        self.stream.connect(
+            libspa::utils::Direction::Input,
+            None,
+            StreamFlags::MAP_BUFFERS | StreamFlags::RT_PROCESS,
+            &mut params,
+        )?;
+        self.stream.set_active(true)?;
+        Ok(())
+    }
+
+    /// Disconnect the stream.
+    pub fn disconnect(&self) -> Result<(), pipewire::Error> {
+        info!("Disconnecting record stream {}", self.node_name);
+        self.stream.disconnect()
+    }
+}
+
+/// Process callback: queue the captured block for the writer or keeper thread.
+fn process_callback(stream: &Stream, user_data: &mut RecordUserData) {
+    let Some(mut buffer) = stream.dequeue_buffer() else {
+        trace!("No buffer available");
+        return;
+    };
+
+    let datas = buffer.datas_mut();
+    if datas.is_empty() {
+        return;
+    }
+
+    let data = &mut datas[0];
+    let channels = user_data.sink.channels();
+    let offset = data.chunk().offset() as usize;
+    let size = data.chunk().size() as usize;
+    let n_frames = size / (channels * std::mem::size_of::<f32>());
+    if n_frames == 0 {
+        return;
+    }
+
+    let Some(bytes) = data.data() else {
+        return;
+    };
+    if offset + size > bytes.len() {
+        return;
+    }
+
+    let samples: &[f32] = unsafe {
+        std::slice::from_raw_parts(
+            bytes.as_ptr().add(offset) as *const f32,
+            n_frames * channels,
+        )
+    };
+    user_data.sink.push(samples);
+}
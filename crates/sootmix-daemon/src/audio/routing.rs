@@ -4,191 +4,195 @@
 
 //! Audio routing - creating and managing links between nodes.
 
-use std::process::Command;
-use thiserror::Error;
-use tracing::{debug, info};
-
-#[derive(Debug, Error)]
-pub enum RoutingError {
-    #[error("Failed to execute pw-link: {0}")]
-    PwLinkFailed(String),
-    #[error("Link creation failed: {0}")]
-    LinkFailed(String),
-}
+use crate::audio::types::{AudioChannel, PwPort};
+use std::collections::HashSet;
+
+/// Gain of a speaker folded onto other positions when downmixing (-3 dB, as
+/// in the ITU-R BS.775 downmix). Links can't carry gain, so this is applied
+/// through the source node's channel volumes.
+pub const DOWNMIX_GAIN: f32 = std::f32::consts::FRAC_1_SQRT_2;
 
-/// Create a link between two ports using pw-link.
-pub fn create_link(output_port: u32, input_port: u32) -> Result<(), RoutingError> {
-    info!("Creating link: port {} -> port {}", output_port, input_port);
-
-    let output = Command::new("pw-link")
-        .arg("-L")
-        .arg(output_port.to_string())
-        .arg(input_port.to_string())
-        .output()
-        .map_err(|e| RoutingError::PwLinkFailed(e.to_string()))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if !stderr.contains("already linked") {
-            return Err(RoutingError::LinkFailed(stderr.to_string()));
+/// Pair a node's output ports with another node's input ports.
+///
+/// Ports with known positions are matched by position, folding positions the
+/// inputs lack onto ones they have (5.1 into stereo, stereo into mono), so
+/// one output may feed several inputs and several outputs one input;
+/// PipeWire mixes links into the same port. Numbered ports without positions
+/// (e.g. Bluetooth `capture_0`) are paired by index suffix, then by order.
+pub fn pair_ports(outputs: &[&PwPort], inputs: &[&PwPort]) -> Vec<(u32, u32)> {
+    let mut outputs = outputs.to_vec();
+    let mut inputs = inputs.to_vec();
+    // Sort by channel first, then by port ID for stability
+    outputs.sort_by(|a, b| (&a.channel, a.id).cmp(&(&b.channel, b.id)));
+    inputs.sort_by(|a, b| (&a.channel, a.id).cmp(&(&b.channel, b.id)));
+
+    let mut pairs = Vec::new();
+    if is_positioned(&outputs) && is_positioned(&inputs) {
+        let positions: Vec<AudioChannel> = inputs.iter().map(|p| p.channel.clone()).collect();
+        for out_port in &outputs {
+            for target in out_port.channel.mix_targets(&positions) {
+                if let Some(in_port) = inputs.iter().find(|p| p.channel == target) {
+                    pairs.push((out_port.id, in_port.id));
+                }
+            }
+        }
+        if !pairs.is_empty() {
+            return pairs;
         }
-        debug!("Ports already linked");
     }
 
-    Ok(())
-}
-/// Set the target sink for a stream node using WirePlumber metadata.
-/// This tells WirePlumber to route the stream to a specific sink and prevents
-/// it from auto-linking to the default sink.
-pub fn set_stream_target(stream_node_id: u32, target_sink_id: u32) -> Result<(), RoutingError> {
-    info!(
-        "Setting stream {} target to sink {}",
-        stream_node_id, target_sink_id
-    );
-
-    // Use pw-metadata to set the target.node for this stream
-    // This is the WirePlumber-compatible way to force routing
-    let output = Command::new("pw-metadata")
-        .args([
-            "-n",
-            "default",
-            &stream_node_id.to_string(),
-            "target.node",
-            &target_sink_id.to_string(),
-        ])
-        .output()
-        .map_err(|e| RoutingError::PwLinkFailed(format!("pw-metadata failed: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        debug!("pw-metadata set target.node failed: {}", stderr);
-        // Continue anyway - the link creation may still work
+    let mut used_inputs: HashSet<u32> = HashSet::new();
+    for out_port in &outputs {
+        for in_port in &inputs {
+            if used_inputs.contains(&in_port.id) {
+                continue;
+            }
+            let out_name = out_port.name.to_lowercase();
+            let in_name = in_port.name.to_lowercase();
+            let is_match = (out_name.contains("_0") && in_name.contains("_0"))
+                || (out_name.contains("_1") && in_name.contains("_1"));
+            if is_match {
+                pairs.push((out_port.id, in_port.id));
+                used_inputs.insert(in_port.id);
+                break;
+            }
+        }
     }
 
-    // Give WirePlumber time to process the metadata change before we manipulate links.
-    // Without this delay, WirePlumber may race with our link creation and re-route
-    // the stream to a different sink.
-    std::thread::sleep(std::time::Duration::from_millis(50));
-
-    // Verify the metadata was actually set
-    if let Some(current_target) = get_stream_target(stream_node_id) {
-        if current_target != target_sink_id {
-            debug!(
-                "Warning: stream {} target is {} but we set {}, retrying",
-                stream_node_id, current_target, target_sink_id
-            );
-            // Retry once
-            let _ = Command::new("pw-metadata")
-                .args([
-                    "-n",
-                    "default",
-                    &stream_node_id.to_string(),
-                    "target.node",
-                    &target_sink_id.to_string(),
-                ])
-                .output();
-            std::thread::sleep(std::time::Duration::from_millis(30));
+    // Fallback: pair by sorted position (deterministic order)
+    if pairs.is_empty() {
+        for (out_port, in_port) in outputs.iter().zip(inputs.iter()) {
+            pairs.push((out_port.id, in_port.id));
         }
     }
 
-    Ok(())
+    pairs
 }
 
-/// Get the current target sink for a stream node from WirePlumber metadata.
-pub fn get_stream_target(stream_node_id: u32) -> Option<u32> {
-    let output = Command::new("pw-metadata")
-        .args(["-n", "default", &stream_node_id.to_string(), "target.node"])
-        .output()
-        .ok()?;
+/// Output positions that `pair_ports` folds onto other positions, and so
+/// should be played at `DOWNMIX_GAIN`.
+pub fn folded_positions(outputs: &[&PwPort], inputs: &[&PwPort]) -> Vec<AudioChannel> {
+    if !is_positioned(outputs) || !is_positioned(inputs) {
+        return Vec::new();
+    }
+    let positions: Vec<AudioChannel> = inputs.iter().map(|p| p.channel.clone()).collect();
+    outputs
+        .iter()
+        .map(|p| p.channel.clone())
+        .filter(|c| !positions.contains(c) && !c.mix_targets(&positions).is_empty())
+        .collect()
+}
 
-    if !output.status.success() {
-        return None;
+fn is_positioned(ports: &[&PwPort]) -> bool {
+    !ports.is_empty() && ports.iter().all(|p| p.channel != AudioChannel::Unknown)
+}
+
+/// Node name in a `default` metadata value such as `default.audio.sink`,
+/// which WirePlumber stores as JSON: `{ "name": "alsa_output.pci-0000_00_1f.3" }`.
+pub fn parse_metadata_node_name(value: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(value).ok()?;
+    value.get("name")?.as_str().map(str::to_string)
+}
+
+/// `default` metadata value naming a node, as read by `parse_metadata_node_name`.
+pub fn metadata_node_name_value(name: &str) -> String {
+    serde_json::json!({ "name": name }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::types::PortDirection;
+
+    fn ports(
+        node_id: u32,
+        first_id: u32,
+        direction: PortDirection,
+        positions: &[&str],
+    ) -> Vec<PwPort> {
+        positions
+            .iter()
+            .enumerate()
+            .map(|(i, position)| {
+                let mut port = PwPort::new(first_id + i as u32, node_id);
+                port.name = format!("port_{}", position);
+                port.direction = direction;
+                port.channel = AudioChannel::from_position(position);
+                port
+            })
+            .collect()
     }
 
-    // Output format: "found 'target.node' '42'"
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
-        if line.contains("target.node") {
-            // Extract the value after the last single quote pair
-            if let Some(start) = line.rfind('\'') {
-                let before_end = &line[..start];
-                if let Some(value_start) = before_end.rfind('\'') {
-                    let value_str = &before_end[value_start + 1..];
-                    return value_str.trim().parse().ok();
-                }
-            }
-        }
+    fn pairs(outputs: &[&str], inputs: &[&str]) -> Vec<(u32, u32)> {
+        let outputs = ports(1, 10, PortDirection::Output, outputs);
+        let inputs = ports(2, 20, PortDirection::Input, inputs);
+        let mut pairs = pair_ports(
+            &outputs.iter().collect::<Vec<_>>(),
+            &inputs.iter().collect::<Vec<_>>(),
+        );
+        pairs.sort();
+        pairs
     }
-    None
-}
 
-/// Get the WirePlumber default audio sink node ID.
-///
-/// Uses `wpctl inspect @DEFAULT_AUDIO_SINK@` to find the system's current
-/// default output device. Returns `None` if the command fails or no default
-/// is set.
-pub fn get_default_sink_id() -> Option<u32> {
-    let output = Command::new("wpctl")
-        .args(["inspect", "@DEFAULT_AUDIO_SINK@"])
-        .output()
-        .ok()?;
-
-    if !output.status.success() {
-        return None;
+    #[test]
+    fn test_surround_matches_by_position() {
+        let layout = ["FL", "FR", "FC", "LFE", "RL", "RR", "SL", "SR"];
+        let expected: Vec<(u32, u32)> = (0..8).map(|i| (10 + i, 20 + i)).collect();
+        assert_eq!(pairs(&layout, &layout), expected);
     }
 
-    // First line looks like: "id 42, type PipeWire:Interface:Node/3"
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let first_line = stdout.lines().next()?;
-    let id_str = first_line.strip_prefix("id ")?.split(',').next()?.trim();
-    let id = id_str.parse::<u32>().ok()?;
-    debug!("WirePlumber default sink: node {}", id);
-    Some(id)
-}
+    #[test]
+    fn test_surround_folds_into_stereo() {
+        // FL FR FC LFE RL RR -> FL FR: centre to both, rears to their side, LFE dropped
+        assert_eq!(
+            pairs(&["FL", "FR", "FC", "LFE", "RL", "RR"], &["FL", "FR"]),
+            [(10, 20), (11, 21), (12, 20), (12, 21), (14, 20), (15, 21)]
+        );
+        // 7.1 sides land on the rears of a 5.1 device
+        assert_eq!(
+            pairs(&["SL", "SR"], &["FL", "FR", "RL", "RR"]),
+            [(10, 22), (11, 23)]
+        );
+    }
 
-/// Get the system's default audio source (microphone) ID from WirePlumber.
-///
-/// Uses `wpctl inspect @DEFAULT_AUDIO_SOURCE@` to find the system's current
-/// default input device. Returns `None` if the command fails or no default
-/// is set.
-pub fn get_default_source_id() -> Option<u32> {
-    let output = Command::new("wpctl")
-        .args(["inspect", "@DEFAULT_AUDIO_SOURCE@"])
-        .output()
-        .ok()?;
-
-    if !output.status.success() {
-        return None;
+    #[test]
+    fn test_mono_up_and_down() {
+        assert_eq!(pairs(&["FL", "FR"], &["MONO"]), [(10, 20), (11, 20)]);
+        assert_eq!(pairs(&["MONO"], &["FL", "FR"]), [(10, 20), (10, 21)]);
     }
 
-    // First line looks like: "id 42, type PipeWire:Interface:Node/3"
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let first_line = stdout.lines().next()?;
-    let id_str = first_line.strip_prefix("id ")?.split(',').next()?.trim();
-    let id = id_str.parse::<u32>().ok()?;
-    debug!("WirePlumber default source: node {}", id);
-    Some(id)
-}
+    #[test]
+    fn test_numbered_ports_pair_in_order() {
+        assert_eq!(
+            pairs(&["FL", "FR"], &["AUX0", "AUX1"]),
+            [(10, 20), (11, 21)]
+        );
+    }
 
-/// Clear the target sink for a stream node, allowing WirePlumber to manage it again.
-pub fn clear_stream_target(stream_node_id: u32) -> Result<(), RoutingError> {
-    info!("Clearing stream {} target", stream_node_id);
-
-    let output = Command::new("pw-metadata")
-        .args([
-            "-n",
-            "default",
-            "-d",
-            &stream_node_id.to_string(),
-            "target.node",
-        ])
-        .output()
-        .map_err(|e| RoutingError::PwLinkFailed(format!("pw-metadata failed: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        debug!("pw-metadata delete target.node failed: {}", stderr);
+    #[test]
+    fn test_folded_positions() {
+        let outputs = ports(1, 10, PortDirection::Output, &["FL", "FR", "FC", "LFE"]);
+        let inputs = ports(2, 20, PortDirection::Input, &["FL", "FR"]);
+        assert_eq!(
+            folded_positions(
+                &outputs.iter().collect::<Vec<_>>(),
+                &inputs.iter().collect::<Vec<_>>()
+            ),
+            [AudioChannel::FrontCenter]
+        );
     }
 
-    Ok(())
+    #[test]
+    fn test_metadata_node_name() {
+        assert_eq!(
+            parse_metadata_node_name(r#"{ "name": "alsa_output.usb-headset" }"#).as_deref(),
+            Some("alsa_output.usb-headset")
+        );
+        assert_eq!(
+            parse_metadata_node_name(&metadata_node_name_value("bluez_output.a \"b\"")).as_deref(),
+            Some("bluez_output.a \"b\"")
+        );
+        assert_eq!(parse_metadata_node_name("42"), None);
+        assert_eq!(parse_metadata_node_name("{}"), None);
+    }
 }
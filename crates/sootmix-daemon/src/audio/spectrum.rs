@@ -0,0 +1,317 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Real-time spectrum analyzer.
+//!
+//! One channel, or the master output, is analyzed at a time. Its tap copies
+//! audio into a [`SpectrumFeed`]: before the plugin chain that is the
+//! full-rate stream the channel's PulseAudio meter records for loudness,
+//! after it the plugin filter's process callback. The daemon's meter loop
+//! runs a [`SpectrumAnalyzer`] over the latest [`FFT_SIZE`] samples of the
+//! feed on every tick and groups the bins into `SPECTRUM_BANDS` log-spaced
+//! bands, with a slow release and peak hold so the display is readable.
+
+use parking_lot::Mutex;
+use realfft::num_complex::Complex;
+use realfft::{RealFftPlanner, RealToComplex};
+use sootmix_ipc::{spectrum_band_frequency, SpectrumTap, SPECTRUM_BANDS, SPECTRUM_FLOOR_DB};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Samples per analysis (85 ms at 48 kHz, 11.7 Hz bins).
+pub const FFT_SIZE: usize = 4096;
+
+/// Rate assumed until a tap reports its own.
+const DEFAULT_RATE: u32 = 48_000;
+
+/// How fast band levels fall, in dB per second.
+const RELEASE_DB_PER_SEC: f32 = 48.0;
+
+/// How long a band peak is held before it falls, in seconds.
+const PEAK_HOLD_SECS: f32 = 1.5;
+
+/// How fast a band peak falls after the hold, in dB per second.
+const PEAK_FALL_DB_PER_SEC: f32 = 12.0;
+
+const FLOOR_DB: f32 = SPECTRUM_FLOOR_DB as f32;
+
+// ============================================================================
+// FEED
+// ============================================================================
+
+/// Where the analyzer listens, as handed to the PipeWire thread.
+#[derive(Debug, Clone)]
+pub struct SpectrumTapPoint {
+    /// Analyzed channel; `None` for the master output.
+    pub channel_id: Option<Uuid>,
+    pub tap: SpectrumTap,
+    pub feed: Arc<SpectrumFeed>,
+}
+
+/// The latest [`FFT_SIZE`] samples of a tap, mixed to mono.
+#[derive(Debug)]
+pub struct SpectrumFeed {
+    samples: Mutex<VecDeque<f32>>,
+    sample_rate: AtomicU32,
+}
+
+impl Default for SpectrumFeed {
+    fn default() -> Self {
+        Self {
+            samples: Mutex::new(VecDeque::with_capacity(FFT_SIZE)),
+            sample_rate: AtomicU32::new(DEFAULT_RATE),
+        }
+    }
+}
+
+impl SpectrumFeed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append interleaved stereo audio at `sample_rate`.
+    pub fn push_interleaved(&self, samples: &[f32], sample_rate: u32) {
+        let mono = samples
+            .chunks_exact(2)
+            .map(|frame| (frame[0] + frame[1]) * 0.5);
+        append(&mut self.samples.lock(), mono);
+        self.sample_rate.store(sample_rate, Ordering::Relaxed);
+    }
+
+    /// Append deinterleaved stereo audio at `sample_rate`, dropping the block
+    /// if the analyzer is reading (for RT callbacks).
+    pub fn try_push_planar(&self, left: &[f32], right: &[f32], sample_rate: u32) {
+        let Some(mut history) = self.samples.try_lock() else {
+            return;
+        };
+        let mono = left.iter().zip(right).map(|(l, r)| (l + r) * 0.5);
+        append(&mut history, mono);
+        self.sample_rate.store(sample_rate, Ordering::Relaxed);
+    }
+
+    /// Sample rate of the audio in the feed.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate.load(Ordering::Relaxed)
+    }
+
+    /// Copy the latest samples into the end of `out`, zeroing the start
+    /// while fewer than `out.len()` have arrived.
+    fn copy_latest(&self, out: &mut [f32]) {
+        let history = self.samples.lock();
+        let count = history.len().min(out.len());
+        let (silence, latest) = out.split_at_mut(out.len() - count);
+        silence.fill(0.0);
+        for (dst, src) in latest
+            .iter_mut()
+            .zip(history.range(history.len() - count..))
+        {
+            *dst = *src;
+        }
+    }
+}
+
+fn append(history: &mut VecDeque<f32>, samples: impl Iterator<Item = f32>) {
+    for sample in samples {
+        if history.len() == FFT_SIZE {
+            history.pop_front();
+        }
+        history.push_back(sample);
+    }
+}
+
+// ============================================================================
+// ANALYZER
+// ============================================================================
+
+/// FFT, band grouping and ballistics for the analyzed tap.
+pub struct SpectrumAnalyzer {
+    fft: Arc<dyn RealToComplex<f32>>,
+    /// Hann window, scaled so a full-scale sine reads 0 dB.
+    window: Vec<f32>,
+    input: Vec<f32>,
+    bins: Vec<Complex<f32>>,
+    scratch: Vec<Complex<f32>>,
+    /// Band levels in dB.
+    levels: Vec<f32>,
+    /// Held band peaks in dB.
+    peaks: Vec<f32>,
+    /// Seconds since each peak was set.
+    peak_age: Vec<f32>,
+}
+
+impl Default for SpectrumAnalyzer {
+    fn default() -> Self {
+        let fft = RealFftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+        let hann = (0..FFT_SIZE).map(|n| {
+            let phase = std::f32::consts::TAU * n as f32 / FFT_SIZE as f32;
+            0.5 - 0.5 * phase.cos()
+        });
+        // A sine of amplitude A peaks at A * sum(w) / 2
+        let gain = 2.0 / (FFT_SIZE as f32 * 0.5);
+        Self {
+            window: hann.map(|w| w * gain).collect(),
+            input: fft.make_input_vec(),
+            bins: fft.make_output_vec(),
+            scratch: fft.make_scratch_vec(),
+            fft,
+            levels: vec![FLOOR_DB; SPECTRUM_BANDS],
+            peaks: vec![FLOOR_DB; SPECTRUM_BANDS],
+            peak_age: vec![0.0; SPECTRUM_BANDS],
+        }
+    }
+}
+
+impl SpectrumAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Band levels in dBFS.
+    pub fn levels_db(&self) -> &[f32] {
+        &self.levels
+    }
+
+    /// Held band peaks in dBFS.
+    pub fn peaks_db(&self) -> &[f32] {
+        &self.peaks
+    }
+
+    /// Analyze the latest audio of `feed`, `dt` seconds after the last call.
+    pub fn update(&mut self, feed: &SpectrumFeed, dt: f32) {
+        feed.copy_latest(&mut self.input);
+        for (sample, w) in self.input.iter_mut().zip(&self.window) {
+            *sample *= w;
+        }
+        if self
+            .fft
+            .process_with_scratch(&mut self.input, &mut self.bins, &mut self.scratch)
+            .is_err()
+        {
+            return;
+        }
+
+        let bin_hz = feed.sample_rate() as f32 / FFT_SIZE as f32;
+        for band in 0..SPECTRUM_BANDS {
+            let level = self.band_level(band, bin_hz);
+
+            let released = self.levels[band] - RELEASE_DB_PER_SEC * dt;
+            self.levels[band] = level.max(released).max(FLOOR_DB);
+
+            self.peak_age[band] += dt;
+            if level >= self.peaks[band] {
+                self.peaks[band] = level;
+                self.peak_age[band] = 0.0;
+            } else if self.peak_age[band] > PEAK_HOLD_SECS {
+                let fallen = self.peaks[band] - PEAK_FALL_DB_PER_SEC * dt;
+                self.peaks[band] = fallen.max(self.levels[band]);
+            }
+        }
+    }
+
+    /// Level of `band` in dB: the loudest bin between the band's edges, or,
+    /// for bands narrower than a bin, the bins interpolated at its center.
+    fn band_level(&self, band: usize, bin_hz: f32) -> f32 {
+        let center = spectrum_band_frequency(band) as f32;
+        let half_width = (spectrum_band_frequency(1) / spectrum_band_frequency(0)).sqrt() as f32;
+        let (low, high) = (center / half_width, center * half_width);
+        let last = self.bins.len() - 1;
+
+        let position = (center / bin_hz).min(last as f32);
+        let below = position.floor() as usize;
+        let above = (below + 1).min(last);
+        let frac = position - below as f32;
+        let mut magnitude = self.bins[below].norm() * (1.0 - frac) + self.bins[above].norm() * frac;
+
+        let first_bin = (low / bin_hz).ceil() as usize;
+        let last_bin = ((high / bin_hz).floor() as usize).min(last);
+        for bin in &self.bins[first_bin.min(last_bin + 1)..=last_bin] {
+            magnitude = magnitude.max(bin.norm());
+        }
+
+        if magnitude > 0.0 {
+            (20.0 * magnitude.log10()).max(FLOOR_DB)
+        } else {
+            FLOOR_DB
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+
+    /// Stereo sine of `amplitude` at `freq` Hz, one analysis long.
+    fn sine(freq: f32, amplitude: f32) -> Vec<f32> {
+        (0..FFT_SIZE)
+            .flat_map(|n| {
+                let t = n as f32 / RATE as f32;
+                let x = amplitude * (std::f32::consts::TAU * freq * t).sin();
+                [x, x]
+            })
+            .collect()
+    }
+
+    /// Band whose center is closest to `freq`.
+    fn band_at(freq: f64) -> usize {
+        (0..SPECTRUM_BANDS)
+            .min_by(|&a, &b| {
+                let distance = |band| (spectrum_band_frequency(band) / freq).ln().abs();
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_full_scale_sine_reads_zero_db() {
+        let feed = SpectrumFeed::new();
+        feed.push_interleaved(&sine(1000.0, 1.0), RATE);
+        let mut analyzer = SpectrumAnalyzer::new();
+        analyzer.update(&feed, 0.03);
+
+        let levels = analyzer.levels_db();
+        assert!(
+            levels[band_at(1000.0)].abs() < 1.5,
+            "{}",
+            levels[band_at(1000.0)]
+        );
+        assert!(levels[band_at(100.0)] < -60.0);
+        assert!(levels[band_at(10_000.0)] < -60.0);
+    }
+
+    #[test]
+    fn test_peaks_hold_after_signal_stops() {
+        let feed = SpectrumFeed::new();
+        let mut analyzer = SpectrumAnalyzer::new();
+        feed.push_interleaved(&sine(500.0, 0.5), RATE);
+        analyzer.update(&feed, 0.03);
+        let band = band_at(500.0);
+        let peak = analyzer.peaks_db()[band];
+
+        feed.push_interleaved(&vec![0.0; FFT_SIZE * 2], RATE);
+        analyzer.update(&feed, 0.5);
+        assert!(analyzer.levels_db()[band] < peak - 20.0);
+        assert_eq!(analyzer.peaks_db()[band], peak);
+
+        analyzer.update(&feed, 1.5);
+        assert!(analyzer.peaks_db()[band] < peak);
+    }
+
+    #[test]
+    fn test_feed_keeps_latest_samples() {
+        let feed = SpectrumFeed::new();
+        let ramp: Vec<f32> = (0..FFT_SIZE + 10).flat_map(|n| [n as f32; 2]).collect();
+        feed.try_push_planar(&[1.0, 1.0], &[3.0, 3.0], 44_100);
+        feed.push_interleaved(&ramp, RATE);
+
+        let mut out = vec![0.0; FFT_SIZE];
+        feed.copy_latest(&mut out);
+        assert_eq!(out[0], 10.0);
+        assert_eq!(out[FFT_SIZE - 1], (FFT_SIZE + 9) as f32);
+        assert_eq!(feed.sample_rate(), RATE);
+    }
+}
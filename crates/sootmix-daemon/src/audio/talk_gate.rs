@@ -0,0 +1,152 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Push-to-talk and push-to-mute for input channels.
+//!
+//! The service decides whether an input channel's mic is keyed open (talk
+//! key, release tail, mode); the gate at the end of the channel's plugin
+//! filter fades the audio in and out accordingly. The fade runs per sample,
+//! so opening and closing never clicks the way a node volume step would.
+//!
+//! ```text
+//! [Mic] → [Plugin Sink] → [RNNoise → Plugin Chain → Talk Gate] → [Plugin Output] → [Capture]
+//! ```
+
+/// Whether the talk gate lets audio through, and how fast it gets there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TalkGateSettings {
+    pub open: bool,
+    /// Time to fade fully in or out, in ms.
+    pub fade_ms: f32,
+}
+
+/// Gain ramp toward open (1.0) or closed (0.0).
+pub struct TalkGate {
+    /// Current position of the ramp, 0.0-1.0.
+    position: f32,
+    settings: TalkGateSettings,
+}
+
+impl TalkGate {
+    /// An open gate.
+    pub fn new() -> Self {
+        Self {
+            position: 1.0,
+            settings: TalkGateSettings {
+                open: true,
+                fade_ms: 0.0,
+            },
+        }
+    }
+
+    /// Fade toward the new settings (`None` to let everything through).
+    pub fn set(&mut self, settings: Option<TalkGateSettings>) {
+        self.settings = settings.unwrap_or(TalkGateSettings {
+            open: true,
+            ..self.settings
+        });
+    }
+
+    /// Jump straight to the new settings, for a filter that is just starting.
+    pub fn reset(&mut self, settings: Option<TalkGateSettings>) {
+        self.set(settings);
+        self.position = self.target();
+    }
+
+    fn target(&self) -> f32 {
+        if self.settings.open {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Apply the gate to a block in place.
+    pub fn process(&mut self, block: &mut [&mut [f32]; 2], sample_rate: u32) {
+        let target = self.target();
+        if self.position == target {
+            if target == 0.0 {
+                for channel in block.iter_mut() {
+                    channel.fill(0.0);
+                }
+            }
+            return;
+        }
+
+        let fade_samples = self.settings.fade_ms / 1000.0 * sample_rate as f32;
+        let step = if fade_samples >= 1.0 {
+            1.0 / fade_samples
+        } else {
+            1.0
+        };
+        let [left, right] = block;
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            self.position = if self.position < target {
+                (self.position + step).min(target)
+            } else {
+                (self.position - step).max(target)
+            };
+            // Smoothstep: no corner in the gain where the fade starts or ends
+            let gain = self.position * self.position * (3.0 - 2.0 * self.position);
+            *l *= gain;
+            *r *= gain;
+        }
+    }
+}
+
+impl Default for TalkGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(open: bool, fade_ms: f32) -> Option<TalkGateSettings> {
+        Some(TalkGateSettings { open, fade_ms })
+    }
+
+    fn process(gate: &mut TalkGate, frames: usize) -> Vec<f32> {
+        let mut left = vec![1.0; frames];
+        let mut right = vec![1.0; frames];
+        gate.process(&mut [&mut left, &mut right], 1000);
+        assert_eq!(left, right);
+        left
+    }
+
+    #[test]
+    fn test_open_gate_passes_through() {
+        let mut gate = TalkGate::new();
+        assert_eq!(process(&mut gate, 4), [1.0; 4]);
+        gate.set(settings(true, 10.0));
+        assert_eq!(process(&mut gate, 4), [1.0; 4]);
+    }
+
+    #[test]
+    fn test_closing_fades_out() {
+        let mut gate = TalkGate::new();
+        // 4 ms at 1 kHz: four samples
+        gate.set(settings(false, 4.0));
+        let out = process(&mut gate, 6);
+        assert!(out.windows(2).all(|w| w[1] <= w[0]));
+        assert!(out[0] > 0.5 && out[0] < 1.0);
+        assert_eq!(&out[3..], [0.0; 3]);
+        assert_eq!(process(&mut gate, 2), [0.0; 2]);
+
+        // Letting go of the settings opens it again
+        gate.set(None);
+        let out = process(&mut gate, 6);
+        assert!(out[0] > 0.0 && out[0] < 0.5);
+        assert_eq!(&out[3..], [1.0; 3]);
+    }
+
+    #[test]
+    fn test_reset_jumps() {
+        let mut gate = TalkGate::new();
+        gate.reset(settings(false, 100.0));
+        assert_eq!(process(&mut gate, 3), [0.0; 3]);
+    }
+}
@@ -7,6 +7,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub use sootmix_ipc::AudioChannel;
+
 /// Represents a PipeWire node (app, device, or virtual sink).
 #[derive(Debug, Clone)]
 pub struct PwNode {
@@ -41,7 +43,6 @@ impl PwNode {
         matches!(self.media_class, MediaClass::StreamOutputAudio)
     }
 
-    #[allow(dead_code)]
     pub fn is_sink(&self) -> bool {
         matches!(self.media_class, MediaClass::AudioSink)
     }
@@ -153,69 +154,6 @@ impl PortDirection {
     }
 }
 
-/// Audio channel position.
-/// Ordered by standard channel layout for consistent pairing.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub enum AudioChannel {
-    FrontLeft,
-    FrontRight,
-    FrontCenter,
-    LowFrequency,
-    RearLeft,
-    RearRight,
-    Mono,
-    Unknown,
-}
-
-impl AudioChannel {
-    pub fn from_str(s: &str) -> Self {
-        let s_lower = s.to_lowercase();
-        if s_lower.contains("fl")
-            || s_lower.contains("front_left")
-            || s_lower.contains("playback_fl")
-        {
-            Self::FrontLeft
-        } else if s_lower.contains("fr")
-            || s_lower.contains("front_right")
-            || s_lower.contains("playback_fr")
-        {
-            Self::FrontRight
-        } else if s_lower.contains("fc") || s_lower.contains("front_center") {
-            Self::FrontCenter
-        } else if s_lower.contains("mono") {
-            Self::Mono
-        } else if s_lower.contains("rl") || s_lower.contains("rear_left") {
-            Self::RearLeft
-        } else if s_lower.contains("rr") || s_lower.contains("rear_right") {
-            Self::RearRight
-        } else if s_lower.contains("lfe") || s_lower.contains("subwoofer") {
-            Self::LowFrequency
-        } else {
-            Self::Unknown
-        }
-    }
-
-    /// Check if two channels are compatible for linking.
-    /// Allows matching same channels or mono to stereo mappings.
-    pub fn is_compatible(&self, other: &Self) -> bool {
-        if self == other {
-            return true;
-        }
-        matches!(
-            (self, other),
-            // Mono can connect to either stereo channel (for stereo↔mono downmix/upmix)
-            (Self::Mono, Self::FrontLeft)
-                | (Self::FrontLeft, Self::Mono)
-                | (Self::Mono, Self::FrontRight)
-                | (Self::FrontRight, Self::Mono)
-                // Unknown channels (e.g. Bluetooth numeric ports like capture_0)
-                // are compatible with any named channel — positional pairing handles order
-                | (Self::Unknown, _)
-                | (_, Self::Unknown)
-        )
-    }
-}
-
 /// A link between two ports in the PipeWire graph.
 #[derive(Debug, Clone)]
 pub struct PwLink {
@@ -3,55 +3,29 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 //! Virtual sink creation and management using pw-loopback.
+//!
+//! This module only spawns and stops the pw-loopback processes. The nodes
+//! they register are discovered, and stale ones destroyed, by the PipeWire
+//! thread through its registry.
 
+use parking_lot::Mutex;
+use sootmix_ipc::ChannelLayout;
 use std::collections::HashMap;
 use std::process::{Child, Command};
-use parking_lot::Mutex;
 use thiserror::Error;
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
 #[derive(Debug, Error)]
 pub enum VirtualSinkError {
     #[error("Failed to spawn pw-loopback: {0}")]
     SpawnFailed(#[from] std::io::Error),
-    #[error("Failed to find created node")]
-    NodeNotFound,
-    #[error("pw-dump failed: {0}")]
-    PwDumpFailed(String),
-    #[error("Invalid JSON from pw-dump")]
-    InvalidJson,
 }
 
-/// Check availability of PipeWire CLI tools at startup.
-/// Logs warnings for any missing tools with guidance.
-#[allow(dead_code)]
-pub fn check_pipewire_tools() {
-    let tools = [
-        ("pw-loopback", "Required for creating virtual sinks"),
-        ("pw-cli", "Required for node management and link creation"),
-        ("pw-dump", "Required for discovering PipeWire nodes"),
-        ("pw-link", "Used for port linking operations"),
-        ("wpctl", "Required for volume control and default sink management"),
-    ];
+/// Running pw-loopback processes, keyed by the node name of their sink or
+/// source.
+static LOOPBACK_PROCESSES: Mutex<Option<HashMap<String, Child>>> = Mutex::new(None);
 
-    for (tool, purpose) in &tools {
-        match Command::new("which").arg(tool).output() {
-            Ok(output) if output.status.success() => {
-                debug!("{} found", tool);
-            }
-            _ => {
-                warn!(
-                    "PipeWire tool '{}' not found in PATH. {}: {}",
-                    tool, "This tool is needed", purpose
-                );
-            }
-        }
-    }
-}
-
-static LOOPBACK_PROCESSES: Mutex<Option<HashMap<u32, Child>>> = Mutex::new(None);
-
-fn get_processes() -> parking_lot::MutexGuard<'static, Option<HashMap<u32, Child>>> {
+fn get_processes() -> parking_lot::MutexGuard<'static, Option<HashMap<String, Child>>> {
     LOOPBACK_PROCESSES.lock()
 }
 
@@ -62,471 +36,110 @@ fn ensure_processes_map() {
     }
 }
 
-/// Result of creating a virtual sink.
-#[derive(Debug, Clone, Copy)]
-pub struct VirtualSinkResult {
-    pub sink_node_id: u32,
-    pub loopback_output_node_id: Option<u32>,
+/// Names of the two nodes a pw-loopback registers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopbackNodeNames {
+    /// The `Audio/Sink` of a virtual sink, or the `Audio/Source` of a
+    /// virtual source.
+    pub main: String,
+    /// The loopback output stream of a sink, or the capture stream of a
+    /// source.
+    pub stream: String,
 }
 
-/// Create a virtual sink using pw-loopback.
-pub fn create_virtual_sink_full(
-    name: &str,
-    description: &str,
-) -> Result<VirtualSinkResult, VirtualSinkError> {
-    ensure_processes_map();
-
-    let safe_name: String = name
-        .chars()
+fn safe_name(name: &str) -> String {
+    name.chars()
         .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
-        .collect();
-    let sink_node_name = format!("sootmix.{}", safe_name);
-    let loopback_node_name = format!("sootmix.{}.output", safe_name);
-
-    // Check if a node with this name already exists and destroy it first
-    if let Ok(existing_id) = find_node_by_name(&sink_node_name) {
-        warn!(
-            "Found existing orphaned node '{}' (id={}), destroying it first",
-            sink_node_name, existing_id
-        );
+        .collect()
+}
 
-        // Remove from process tracking map before destroying
-        if let Some(ref mut map) = *get_processes() {
-            if let Some(mut child) = map.remove(&existing_id) {
-                info!(
-                    "Killing orphaned pw-loopback process for node {}",
-                    existing_id
-                );
-                let _ = child.kill();
-                let _ = child.wait();
-            }
-        }
+/// Node names of the virtual sink called `name`.
+pub fn sink_node_names(name: &str) -> LoopbackNodeNames {
+    let safe_name = safe_name(name);
+    LoopbackNodeNames {
+        main: format!("sootmix.{}", safe_name),
+        stream: format!("output.sootmix.{}.output", safe_name),
+    }
+}
 
-        if let Err(e) = Command::new("pw-cli")
-            .args(["destroy", &existing_id.to_string()])
-            .output()
-        {
-            warn!("pw-cli destroy failed for node {}: {}", existing_id, e);
-        }
-        // Also destroy the output node if it exists
-        let full_loopback_name = format!("output.{}", loopback_node_name);
-        if let Ok(output_id) =
-            find_node_by_name_and_class(&full_loopback_name, "Stream/Output/Audio")
-        {
-            // Remove output node from process tracking as well (in case it was tracked separately)
-            if let Some(ref mut map) = *get_processes() {
-                if let Some(mut child) = map.remove(&output_id) {
-                    let _ = child.kill();
-                    let _ = child.wait();
-                }
-            }
-            if let Err(e) = Command::new("pw-cli")
-                .args(["destroy", &output_id.to_string()])
-                .output()
-            {
-                warn!("pw-cli destroy failed for output node {}: {}", output_id, e);
-            }
-        }
-        std::thread::sleep(std::time::Duration::from_millis(100));
+/// Node names of the virtual source called `name`.
+pub fn source_node_names(name: &str) -> LoopbackNodeNames {
+    let safe_name = safe_name(name);
+    LoopbackNodeNames {
+        main: format!("sootmix.{}", safe_name),
+        stream: format!("input.sootmix.{}.input", safe_name),
     }
+}
+
+/// Spawn a pw-loopback for a virtual sink. The sink and its loopback output
+/// both get the layout's channel map, so a surround source keeps its
+/// positions all the way to the device.
+///
+/// Returns the names of the nodes it will register; any node already using
+/// them has to be destroyed first.
+pub fn spawn_virtual_sink(
+    name: &str,
+    description: &str,
+    layout: ChannelLayout,
+) -> Result<LoopbackNodeNames, VirtualSinkError> {
+    ensure_processes_map();
 
+    let names = sink_node_names(name);
+    let sink_node_name = &names.main;
+    let loopback_node_name = format!("{}.output", sink_node_name);
+    stop_loopback(sink_node_name);
+
+    let positions = layout.positions().join(" ");
     let capture_props = format!(
-        "media.class=Audio/Sink node.name={} node.description=\"{}\" audio.position=[FL FR] priority.session=2000",
-        sink_node_name, description
+        "media.class=Audio/Sink node.name={} node.description=\"{}\" audio.position=[{}] priority.session=2000",
+        sink_node_name, description, positions
     );
 
     let playback_props = format!(
-        "media.class=Stream/Output/Audio application.name=SootMix node.autoconnect=false node.dont-move=true audio.position=[FL FR] node.description=\"{}\"",
-        description
+        "media.class=Stream/Output/Audio application.name=SootMix node.autoconnect=false node.dont-move=true audio.position=[{}] node.description=\"{}\"",
+        positions, description
     );
 
     info!(
-        "Creating virtual sink: {} (description: {})",
-        sink_node_name, description
+        "Creating virtual sink: {} (description: {}, layout: {})",
+        sink_node_name, description, layout
     );
 
     let child = Command::new("pw-loopback")
         .arg("--name")
         .arg(&loopback_node_name)
+        .arg("--channels")
+        .arg(layout.channels().to_string())
         .arg("--capture-props")
         .arg(&capture_props)
         .arg("--playback-props")
         .arg(&playback_props)
         .spawn()?;
 
-    let pid = child.id();
-    debug!("pw-loopback spawned with PID: {}", pid);
-
-    std::thread::sleep(std::time::Duration::from_millis(200));
-
-    let sink_node_id = find_node_by_name(&sink_node_name)?;
-    let full_loopback_name = format!("output.{}", loopback_node_name);
-    let loopback_output_node_id =
-        find_node_by_name_and_class(&full_loopback_name, "Stream/Output/Audio").ok();
+    debug!("pw-loopback spawned with PID: {}", child.id());
 
     if let Some(ref mut map) = *get_processes() {
-        map.insert(sink_node_id, child);
-    }
-
-    info!(
-        "Created virtual sink '{}' with sink_id={}, loopback_output_id={:?}",
-        sink_node_name, sink_node_id, loopback_output_node_id
-    );
-
-    Ok(VirtualSinkResult {
-        sink_node_id,
-        loopback_output_node_id,
-    })
-}
-
-/// Update the description of an existing node.
-pub fn update_node_description(
-    node_id: u32,
-    new_description: &str,
-) -> Result<(), VirtualSinkError> {
-    info!(
-        "Updating node {} description to '{}'",
-        node_id, new_description
-    );
-
-    let props_json = format!(
-        "{{ params = [ \"node.description\" \"{}\" ] }}",
-        new_description.replace('"', "\\\"")
-    );
-
-    let output = Command::new("pw-cli")
-        .args(["set-param", &node_id.to_string(), "Props", &props_json])
-        .output()
-        .map_err(|e| VirtualSinkError::PwDumpFailed(format!("Failed to run pw-cli: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(VirtualSinkError::PwDumpFailed(format!(
-            "pw-cli set-param failed: {}",
-            stderr.trim()
-        )));
-    }
-
-    Ok(())
-}
-
-/// Destroy a virtual sink by killing its pw-loopback process.
-pub fn destroy_virtual_sink(node_id: u32) -> Result<(), VirtualSinkError> {
-    // First try tracked process
-    if let Some(ref mut map) = *get_processes() {
-        if let Some(mut child) = map.remove(&node_id) {
-            info!("Destroying virtual sink with node ID {} (tracked process)", node_id);
-            let _ = child.kill();
-            let _ = child.wait();
-            return Ok(());
-        }
-    }
-
-    // Process not tracked (e.g., daemon restarted). Look up node name and kill by pattern.
-    warn!(
-        "No tracked process for node {}, looking up node name to kill pw-loopback",
-        node_id
-    );
-
-    // Get the node name from PipeWire
-    if let Ok(node_name) = get_node_name(node_id) {
-        info!("Found node name '{}' for node {}, killing matching pw-loopback", node_name, node_id);
-        // Kill pw-loopback processes that have this node name in their arguments
-        // Match both the source name (sootmix.X) and the input name (sootmix.X.input)
-        let _ = Command::new("pkill")
-            .args(["-f", &format!("pw-loopback.*{}", node_name)])
-            .output();
-        // Give process time to die
-        std::thread::sleep(std::time::Duration::from_millis(50));
-    }
-
-    // Also try pw-cli destroy as final fallback
-    match Command::new("pw-cli")
-        .args(["destroy", &node_id.to_string()])
-        .output()
-    {
-        Ok(output) if !output.status.success() => {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            debug!("pw-cli destroy for node {}: {}", node_id, stderr.trim());
-        }
-        Err(e) => {
-            debug!("pw-cli destroy failed for node {}: {}", node_id, e);
-        }
-        _ => {}
-    }
-
-    Ok(())
-}
-
-/// Get the node name for a given node ID from PipeWire.
-fn get_node_name(node_id: u32) -> Result<String, VirtualSinkError> {
-    let output = Command::new("pw-cli")
-        .args(["info", &node_id.to_string()])
-        .output()?;
-
-    if !output.status.success() {
-        return Err(VirtualSinkError::NodeNotFound);
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    // Parse "node.name = \"sootmix.Foo\"" from pw-cli info output
-    for line in stdout.lines() {
-        let line = line.trim();
-        if line.starts_with("node.name") {
-            if let Some(value) = line.split('=').nth(1) {
-                let name = value.trim().trim_matches('"').trim_matches('\'');
-                return Ok(name.to_string());
-            }
-        }
-    }
-
-    Err(VirtualSinkError::NodeNotFound)
-}
-
-/// Destroy all virtual sinks (cleanup on exit).
-/// This kills tracked pw-loopback processes and cleans up any orphaned nodes
-/// that may have been created by processes that respawned after PipeWire restart.
-pub fn destroy_all_virtual_sinks() {
-    info!("Destroying all virtual sinks");
-
-    // First, kill all tracked pw-loopback processes
-    if let Some(ref mut map) = *get_processes() {
-        for (node_id, mut child) in map.drain() {
-            debug!(
-                "Killing pw-loopback for sink node {} (pid: {:?})",
-                node_id,
-                child.id()
-            );
-            if let Err(e) = child.kill() {
-                // Process may already be dead if PipeWire restarted
-                debug!("Process kill returned error (may be already dead): {}", e);
-            }
-            let _ = child.wait();
-        }
-    }
-
-    // Also kill any pw-loopback processes that may have respawned
-    // This handles the case where PipeWire restarted and pw-loopback auto-restarted
-    // with a new PID that we don't have tracked
-    let _ = std::process::Command::new("pkill")
-        .args(["-f", "pw-loopback.*--name.*sootmix\\."])
-        .output();
-
-    // Give processes time to die and nodes to be removed
-    std::thread::sleep(std::time::Duration::from_millis(100));
-}
-
-/// Clean up orphaned sootmix nodes from previous runs.
-/// This should be called on daemon startup before creating new channels.
-pub fn cleanup_orphaned_nodes() {
-    info!("Scanning for orphaned sootmix nodes...");
-
-    let output = match Command::new("pw-dump").output() {
-        Ok(o) => o,
-        Err(e) => {
-            warn!("Failed to run pw-dump for orphan cleanup: {}", e);
-            return;
-        }
-    };
-
-    if !output.status.success() {
-        warn!("pw-dump failed during orphan cleanup");
-        return;
-    }
-
-    let json_str = String::from_utf8_lossy(&output.stdout);
-    let objects: Vec<serde_json::Value> = match serde_json::from_str(&json_str) {
-        Ok(o) => o,
-        Err(_) => {
-            warn!("Failed to parse pw-dump JSON for orphan cleanup");
-            return;
-        }
-    };
-
-    let mut orphaned_ids: Vec<u32> = Vec::new();
-
-    for obj in objects {
-        let obj_type = obj.get("type").and_then(|v| v.as_str()).unwrap_or("");
-        if obj_type != "PipeWire:Interface:Node" {
-            continue;
-        }
-
-        let props = obj.get("info").and_then(|i| i.get("props"));
-        let node_name = props
-            .and_then(|p| p.get("node.name"))
-            .and_then(|n| n.as_str())
-            .unwrap_or("");
-
-        // Check if this is a sootmix node (either sink or output)
-        if node_name.starts_with("sootmix.") || node_name.starts_with("output.sootmix.") {
-            if let Some(id) = obj.get("id").and_then(|v| v.as_u64()) {
-                orphaned_ids.push(id as u32);
-            }
-        }
-    }
-
-    if orphaned_ids.is_empty() {
-        info!("No orphaned sootmix nodes found");
-        return;
-    }
-
-    info!(
-        "Found {} orphaned sootmix nodes, cleaning up...",
-        orphaned_ids.len()
-    );
-    for id in orphaned_ids {
-        debug!("Destroying orphaned node {}", id);
-        if let Err(e) = Command::new("pw-cli")
-            .args(["destroy", &id.to_string()])
-            .output()
-        {
-            warn!("pw-cli destroy failed for orphaned node {}: {}", id, e);
-        }
+        map.insert(names.main.clone(), child);
     }
-
-    // Give PipeWire time to process the destructions
-    std::thread::sleep(std::time::Duration::from_millis(200));
-    info!("Orphan cleanup complete");
-}
-
-fn find_node_by_name(name: &str) -> Result<u32, VirtualSinkError> {
-    find_node_by_name_and_class(name, "Audio/Sink")
+    Ok(names)
 }
 
-fn find_node_by_name_and_class(name: &str, target_class: &str) -> Result<u32, VirtualSinkError> {
-    let output = Command::new("pw-dump")
-        .output()
-        .map_err(|e| VirtualSinkError::PwDumpFailed(e.to_string()))?;
-
-    if !output.status.success() {
-        return Err(VirtualSinkError::PwDumpFailed(
-            String::from_utf8_lossy(&output.stderr).to_string(),
-        ));
-    }
-
-    let json_str = String::from_utf8_lossy(&output.stdout);
-    let objects: Vec<serde_json::Value> =
-        serde_json::from_str(&json_str).map_err(|_| VirtualSinkError::InvalidJson)?;
-
-    for obj in objects {
-        let obj_type = obj.get("type").and_then(|v| v.as_str()).unwrap_or("");
-        if obj_type != "PipeWire:Interface:Node" {
-            continue;
-        }
-
-        let props = obj.get("info").and_then(|i| i.get("props"));
-        let node_name = props
-            .and_then(|p| p.get("node.name"))
-            .and_then(|n| n.as_str())
-            .unwrap_or("");
-        let media_class = props
-            .and_then(|p| p.get("media.class"))
-            .and_then(|c| c.as_str())
-            .unwrap_or("");
-
-        if node_name == name && media_class == target_class {
-            if let Some(id) = obj.get("id").and_then(|v| v.as_u64()) {
-                debug!(
-                    "Found node '{}' with ID {} (class={})",
-                    name, id, media_class
-                );
-                return Ok(id as u32);
-            }
-        }
-    }
-
-    // Fallback: use wpctl status
-    debug!("Node not found in pw-dump, trying wpctl status");
-    let wpctl_output = Command::new("wpctl")
-        .arg("status")
-        .output()
-        .map_err(|e| VirtualSinkError::PwDumpFailed(e.to_string()))?;
-
-    let status_str = String::from_utf8_lossy(&wpctl_output.stdout);
-
-    for line in status_str.lines() {
-        if line.contains(name) {
-            let trimmed = line.trim();
-            if let Some(dot_pos) = trimmed.find('.') {
-                if let Ok(id) = trimmed[..dot_pos].trim().parse::<u32>() {
-                    debug!("Found node '{}' via wpctl with ID {}", name, id);
-                    return Ok(id);
-                }
-            }
-        }
-    }
-
-    Err(VirtualSinkError::NodeNotFound)
-}
-
-/// Result of creating a virtual source.
-#[derive(Debug, Clone, Copy)]
-pub struct VirtualSourceResult {
-    pub source_node_id: u32,
-    pub capture_stream_node_id: Option<u32>,
-}
-
-/// Create a virtual source for recording.
-/// If `target_device` is Some, the capture stream will target that device (mic).
-pub fn create_virtual_source(name: &str, target_device: Option<&str>) -> Result<VirtualSourceResult, VirtualSinkError> {
+/// Spawn a pw-loopback for a virtual source, used for input channels and
+/// recording sources. Its capture stream doesn't connect by itself; the
+/// PipeWire thread links it to `target_device` (or the default microphone).
+///
+/// Returns the names of the nodes it will register; any node already using
+/// them has to be destroyed first.
+pub fn spawn_virtual_source(
+    name: &str,
+    target_device: Option<&str>,
+) -> Result<LoopbackNodeNames, VirtualSinkError> {
     ensure_processes_map();
 
-    let safe_name = name
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
-        .collect::<String>();
-
-    let source_name = format!("sootmix.{}", safe_name);
-    let loopback_node_name = format!("sootmix.{}.input", safe_name);
-
-    // Check if a node with this name already exists and destroy it first
-    // (handles races during rapid input device switching)
-    if let Ok(existing_id) = find_node_by_name_and_class(&source_name, "Audio/Source") {
-        warn!(
-            "Found existing orphaned source node '{}' (id={}), destroying it first",
-            source_name, existing_id
-        );
-
-        if let Some(ref mut map) = *get_processes() {
-            if let Some(mut child) = map.remove(&existing_id) {
-                info!(
-                    "Killing orphaned pw-loopback process for source node {}",
-                    existing_id
-                );
-                let _ = child.kill();
-                let _ = child.wait();
-            }
-        }
-
-        if let Err(e) = Command::new("pw-cli")
-            .args(["destroy", &existing_id.to_string()])
-            .output()
-        {
-            warn!("pw-cli destroy failed for source node {}: {}", existing_id, e);
-        }
-        // Also destroy the capture stream node if it exists
-        let capture_stream_name = format!("input.{}", loopback_node_name);
-        if let Ok(capture_id) =
-            find_node_by_name_and_class(&capture_stream_name, "Stream/Input/Audio")
-        {
-            if let Some(ref mut map) = *get_processes() {
-                if let Some(mut child) = map.remove(&capture_id) {
-                    let _ = child.kill();
-                    let _ = child.wait();
-                }
-            }
-            if let Err(e) = Command::new("pw-cli")
-                .args(["destroy", &capture_id.to_string()])
-                .output()
-            {
-                warn!(
-                    "pw-cli destroy failed for capture stream node {}: {}",
-                    capture_id, e
-                );
-            }
-        }
-        std::thread::sleep(std::time::Duration::from_millis(100));
-    }
+    let names = source_node_names(name);
+    let source_name = &names.main;
+    let loopback_node_name = format!("{}.input", source_name);
+    stop_loopback(source_name);
 
     // Build capture props - ALWAYS disable autoconnect to prevent WirePlumber from
     // linking the capture stream to all available sources. We'll manage links ourselves.
@@ -561,43 +174,47 @@ pub fn create_virtual_source(name: &str, target_device: Option<&str>) -> Result<
         .arg(&playback_props)
         .spawn()?;
 
-    let pid = child.id();
-    debug!("pw-loopback (source) spawned with PID: {}", pid);
-
-    std::thread::sleep(std::time::Duration::from_millis(200));
-
-    let source_node_id = find_node_by_name_and_class(&source_name, "Audio/Source")?;
-
-    // Find the capture stream node (the input side that captures from the mic)
-    let input_node_name = format!("input.{}", loopback_node_name);
-    let capture_stream_node_id = match find_node_by_name_and_class(&input_node_name, "Stream/Input/Audio") {
-        Ok(id) => Some(id),
-        Err(e) => {
-            warn!(
-                "Failed to find capture stream node '{}' for virtual source '{}': {}. \
-                 Input channel mic linking will not work.",
-                input_node_name, name, e
-            );
-            None
-        }
-    };
+    debug!("pw-loopback (source) spawned with PID: {}", child.id());
 
     if let Some(ref mut map) = *get_processes() {
-        map.insert(source_node_id, child);
+        map.insert(names.main.clone(), child);
     }
+    Ok(names)
+}
 
-    info!(
-        "Created virtual source '{}' with source_id={}, capture_stream_id={:?}",
-        name, source_node_id, capture_stream_node_id
-    );
-
-    Ok(VirtualSourceResult {
-        source_node_id,
-        capture_stream_node_id,
-    })
+/// Kill the pw-loopback whose sink or source is named `node_name`.
+/// Returns false if no such process is tracked.
+pub fn stop_loopback(node_name: &str) -> bool {
+    let Some(mut child) = get_processes()
+        .as_mut()
+        .and_then(|map| map.remove(node_name))
+    else {
+        return false;
+    };
+    info!("Stopping pw-loopback for '{}'", node_name);
+    let _ = child.kill();
+    let _ = child.wait();
+    true
 }
 
-/// Destroy a virtual source.
-pub fn destroy_virtual_source(node_id: u32) -> Result<(), VirtualSinkError> {
-    destroy_virtual_sink(node_id)
+/// Destroy all virtual sinks (cleanup on exit) by killing the tracked
+/// pw-loopback processes. Nodes left behind by untracked processes are
+/// destroyed on the next startup.
+pub fn destroy_all_virtual_sinks() {
+    info!("Destroying all virtual sinks");
+
+    if let Some(ref mut map) = *get_processes() {
+        for (node_name, mut child) in map.drain() {
+            debug!(
+                "Killing pw-loopback for '{}' (pid: {:?})",
+                node_name,
+                child.id()
+            );
+            if let Err(e) = child.kill() {
+                // Process may already be dead if PipeWire restarted
+                debug!("Process kill returned error (may be already dead): {}", e);
+            }
+            let _ = child.wait();
+        }
+    }
 }
@@ -4,6 +4,7 @@
 
 //! Configuration management for the daemon.
 
+use regex::RegexBuilder;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -59,7 +60,7 @@ pub enum ConfigError {
 /// - UI uses `HashMap<u32, f32>` (indexed)
 ///
 /// We use a custom deserializer to accept both formats for backwards compatibility.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PluginSlotConfig {
     pub plugin_id: String,
     #[serde(default)]
@@ -68,10 +69,10 @@ pub struct PluginSlotConfig {
     pub parameters: HashMap<u32, f32>,
     #[serde(default)]
     pub sidechain_source: Option<Uuid>,
-    /// Plugin type (e.g., "builtin", "lv2", "vst3"). UI-owned field, round-tripped by daemon.
+    /// Plugin type (e.g., "builtin", "lv2", "vst3"). Informational; the daemon loads by `plugin_id`.
     #[serde(default)]
     pub plugin_type: Option<String>,
-    /// External plugin ID (LV2 URI or VST3 ID). UI-owned field, round-tripped by daemon.
+    /// External plugin ID (LV2 URI or VST3 ID). Informational; the daemon loads by `plugin_id`.
     #[serde(default)]
     pub external_id: Option<String>,
 }
@@ -92,6 +93,9 @@ pub struct SavedChannel {
     pub eq_enabled: bool,
     #[serde(default = "default_eq_preset")]
     pub eq_preset: String,
+    /// EQ bands edited after choosing `eq_preset`; `None` follows the preset.
+    #[serde(default)]
+    pub eq_bands: Option<Vec<EqBand>>,
     #[serde(default)]
     pub assigned_apps: Vec<String>,
     #[serde(default)]
@@ -110,21 +114,47 @@ pub struct SavedChannel {
     /// VAD threshold for noise suppression (0-100%). Higher = more aggressive noise gating.
     #[serde(default = "default_vad_threshold")]
     pub vad_threshold: f32,
+    /// Noise suppression model, VAD grace periods and attenuation limit.
+    #[serde(default)]
+    pub noise_suppression: NoiseSuppressionConfig,
+    /// Push-to-talk / push-to-mute.
+    #[serde(default)]
+    pub talk: TalkConfig,
     /// Hardware microphone gain in dB (-12.0 to +12.0). Controls the physical input device level.
     #[serde(default)]
     pub input_gain_db: f32,
-    /// Whether sidetone monitoring is enabled. UI-owned field, round-tripped by daemon.
+    /// Whether the channel is heard on the monitor bus as sidetone (input channels).
     #[serde(default)]
     pub sidetone_enabled: bool,
-    /// Sidetone volume in dB. UI-owned field, round-tripped by daemon.
-    #[serde(default)]
+    /// Sidetone level in dB.
+    #[serde(default = "default_sidetone_db")]
     pub sidetone_volume_db: f32,
+    /// Pan/balance (-1.0 left to 1.0 right).
+    #[serde(default)]
+    pub pan: f32,
+    /// Surround width (0.0 front only to 1.0 all speakers).
+    #[serde(default = "default_width")]
+    pub width: f32,
+    /// Speaker layout of the channel's sink (output channels).
+    #[serde(default)]
+    pub layout: sootmix_ipc::ChannelLayout,
+    /// Automatic loudness normalization.
+    #[serde(default)]
+    pub auto_level: AutoLevelConfig,
 }
 
 fn default_vad_threshold() -> f32 {
     95.0
 }
 
+fn default_width() -> f32 {
+    1.0
+}
+
+fn default_sidetone_db() -> f32 {
+    -20.0
+}
+
 fn default_true() -> bool {
     true
 }
@@ -133,6 +163,315 @@ fn default_eq_preset() -> String {
     "Flat".to_string()
 }
 
+/// Automatic loudness normalization ("auto level") of a channel: a make-up
+/// gain after the fader that slowly steers the channel toward a target
+/// loudness.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutoLevelConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Short-term loudness to steer toward, in LUFS.
+    #[serde(default = "default_auto_level_target")]
+    pub target_lufs: f32,
+    /// Most gain added to quiet sources, in dB.
+    #[serde(default = "default_auto_level_range")]
+    pub max_boost_db: f32,
+    /// Most gain taken from loud sources, in dB (positive).
+    #[serde(default = "default_auto_level_range")]
+    pub max_cut_db: f32,
+    /// How fast the gain follows the source, in dB per second.
+    #[serde(default = "default_auto_level_speed")]
+    pub speed_db_per_sec: f32,
+}
+
+impl Default for AutoLevelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_lufs: default_auto_level_target(),
+            max_boost_db: default_auto_level_range(),
+            max_cut_db: default_auto_level_range(),
+            speed_db_per_sec: default_auto_level_speed(),
+        }
+    }
+}
+
+fn default_auto_level_target() -> f32 {
+    -18.0
+}
+
+fn default_auto_level_range() -> f32 {
+    12.0
+}
+
+fn default_auto_level_speed() -> f32 {
+    1.5
+}
+
+/// Noise suppression tuning of an input channel.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NoiseSuppressionConfig {
+    #[serde(default)]
+    pub model: sootmix_ipc::NoiseModel,
+    /// How long the mic stays open after the voice stops, in ms.
+    #[serde(default = "default_vad_grace")]
+    pub grace_ms: f32,
+    /// How long before the voice starts the mic opens, in ms.
+    #[serde(default)]
+    pub retro_grace_ms: f32,
+    /// Most the mic is turned down while noise is removed, in dB.
+    #[serde(default = "default_attenuation_limit")]
+    pub attenuation_limit_db: f32,
+}
+
+impl Default for NoiseSuppressionConfig {
+    fn default() -> Self {
+        Self {
+            model: sootmix_ipc::NoiseModel::default(),
+            grace_ms: default_vad_grace(),
+            retro_grace_ms: 0.0,
+            attenuation_limit_db: default_attenuation_limit(),
+        }
+    }
+}
+
+fn default_vad_grace() -> f32 {
+    200.0
+}
+
+fn default_attenuation_limit() -> f32 {
+    sootmix_ipc::NOISE_MAX_ATTENUATION_DB as f32
+}
+
+/// Push-to-talk / push-to-mute of an input channel. Whether the talk key is
+/// held is not persisted.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TalkConfig {
+    #[serde(default)]
+    pub mode: sootmix_ipc::TalkMode,
+    /// How long the key stays held after it is let go, in ms.
+    #[serde(default = "default_talk_release")]
+    pub release_ms: f32,
+    /// How long the mic takes to open or close, in ms.
+    #[serde(default = "default_talk_fade")]
+    pub fade_ms: f32,
+}
+
+impl Default for TalkConfig {
+    fn default() -> Self {
+        Self {
+            mode: sootmix_ipc::TalkMode::default(),
+            release_ms: default_talk_release(),
+            fade_ms: default_talk_fade(),
+        }
+    }
+}
+
+fn default_talk_release() -> f32 {
+    200.0
+}
+
+fn default_talk_fade() -> f32 {
+    20.0
+}
+
+/// Version written to EQ preset files. Files without one were saved by the
+/// fixed five-band EQ and are rewritten by
+/// [`ConfigManager::migrate_eq_presets`].
+pub const EQ_PRESET_VERSION: u32 = 2;
+
+/// One band of a parametric EQ.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EqBand {
+    /// Filter shape (bands of old presets are all peaking).
+    #[serde(rename = "type", default)]
+    pub kind: sootmix_ipc::EqBandType,
+    /// Frequency in Hz.
+    pub freq: f32,
+    /// Gain in dB (peaking and shelves only).
+    #[serde(default)]
+    pub gain: f32,
+    /// Q factor.
+    #[serde(default = "default_eq_q")]
+    pub q: f32,
+    /// Roll-off of pass filters in dB per octave.
+    #[serde(default = "default_eq_slope")]
+    pub slope: u32,
+}
+
+fn default_eq_q() -> f32 {
+    1.0
+}
+
+fn default_eq_slope() -> u32 {
+    sootmix_ipc::EQ_SLOPES[0]
+}
+
+impl EqBand {
+    /// A flat peaking band at `freq` Hz.
+    pub fn new(freq: f32) -> Self {
+        Self {
+            kind: sootmix_ipc::EqBandType::Peaking,
+            freq,
+            gain: 0.0,
+            q: default_eq_q(),
+            slope: default_eq_slope(),
+        }
+    }
+
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        let max = sootmix_ipc::EQ_MAX_GAIN_DB as f32;
+        self.gain = gain.clamp(-max, max);
+        self
+    }
+
+    pub fn with_q(mut self, q: f32) -> Self {
+        self.q = q.clamp(sootmix_ipc::EQ_MIN_Q as f32, sootmix_ipc::EQ_MAX_Q as f32);
+        self
+    }
+}
+
+impl From<&EqBand> for sootmix_ipc::EqBandInfo {
+    fn from(band: &EqBand) -> Self {
+        Self {
+            band_type: band.kind,
+            freq_hz: band.freq as f64,
+            gain_db: band.gain as f64,
+            q: band.q as f64,
+            slope_db: band.slope,
+        }
+    }
+}
+
+impl From<&sootmix_ipc::EqBandInfo> for EqBand {
+    fn from(band: &sootmix_ipc::EqBandInfo) -> Self {
+        Self {
+            kind: band.band_type,
+            freq: band.freq_hz as f32,
+            gain: band.gain_db as f32,
+            q: band.q as f32,
+            slope: band.slope_db,
+        }
+    }
+}
+
+/// A parametric EQ preset.
+///
+/// Same on-disk format as the UI's presets in `eq_presets/`, so presets
+/// saved from the UI can be applied by the daemon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EqPreset {
+    pub name: String,
+    /// Format version (0 for presets of the five-band EQ).
+    #[serde(default)]
+    pub version: u32,
+    pub bands: Vec<EqBand>,
+}
+
+impl Default for EqPreset {
+    fn default() -> Self {
+        Self::flat()
+    }
+}
+
+impl EqPreset {
+    fn new(name: &str, bands: Vec<EqBand>) -> Self {
+        Self {
+            name: name.to_string(),
+            version: EQ_PRESET_VERSION,
+            bands,
+        }
+    }
+
+    /// Create a flat EQ preset.
+    pub fn flat() -> Self {
+        Self::new(
+            "Flat",
+            vec![
+                EqBand::new(60.0),
+                EqBand::new(250.0),
+                EqBand::new(1000.0),
+                EqBand::new(4000.0),
+                EqBand::new(16000.0),
+            ],
+        )
+    }
+
+    /// Get all built-in presets (kept in sync with the UI's `EqPreset`).
+    pub fn builtin_presets() -> Vec<Self> {
+        vec![
+            Self::flat(),
+            Self::new(
+                "Bass Boost",
+                vec![
+                    EqBand::new(60.0).with_gain(6.0).with_q(0.8),
+                    EqBand::new(250.0).with_gain(3.0),
+                    EqBand::new(1000.0),
+                    EqBand::new(4000.0),
+                    EqBand::new(16000.0).with_gain(-2.0),
+                ],
+            ),
+            Self::new(
+                "Vocal Clarity",
+                vec![
+                    EqBand::new(60.0).with_gain(-2.0),
+                    EqBand::new(250.0).with_gain(-1.0),
+                    EqBand::new(1000.0).with_gain(2.0).with_q(1.5),
+                    EqBand::new(4000.0).with_gain(4.0).with_q(1.2),
+                    EqBand::new(16000.0).with_gain(1.0),
+                ],
+            ),
+            Self::new(
+                "Treble Boost",
+                vec![
+                    EqBand::new(60.0).with_gain(-2.0),
+                    EqBand::new(250.0),
+                    EqBand::new(1000.0),
+                    EqBand::new(4000.0).with_gain(3.0),
+                    EqBand::new(16000.0).with_gain(5.0),
+                ],
+            ),
+            Self::new(
+                "Cinema",
+                vec![
+                    EqBand::new(60.0).with_gain(4.0).with_q(0.7),
+                    EqBand::new(250.0).with_gain(1.0),
+                    EqBand::new(1000.0).with_gain(-1.0),
+                    EqBand::new(4000.0).with_gain(2.0),
+                    EqBand::new(16000.0).with_gain(3.0),
+                ],
+            ),
+        ]
+    }
+
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+}
+
+/// Parse a preset file, returning it in the current format if it was saved
+/// by an older version (`None` if it is current or can't be parsed).
+///
+/// Five-band presets load as-is: their bands default to peaking and their
+/// integer frequencies read as floats.
+fn migrate_eq_preset(content: &str) -> Option<EqPreset> {
+    match EqPreset::from_toml(content) {
+        Ok(preset) if preset.version < EQ_PRESET_VERSION => Some(EqPreset {
+            version: EQ_PRESET_VERSION,
+            ..preset
+        }),
+        Ok(_) => None,
+        Err(e) => {
+            warn!("Skipping EQ preset migration: {}", e);
+            None
+        }
+    }
+}
+
 /// Master output configuration.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MasterConfig {
@@ -141,9 +480,66 @@ pub struct MasterConfig {
     #[serde(default)]
     pub muted: bool,
     pub output_device: Option<String>,
-    /// PFL/solo monitor device. UI-owned field, round-tripped by daemon.
+    /// Device the monitor bus plays on (solo/PFL and sidetone). Falls back
+    /// to the master output when unset.
     #[serde(default)]
     pub monitor_device: Option<String>,
+    /// Monitor bus level in dB.
+    #[serde(default)]
+    pub monitor_volume_db: f32,
+    #[serde(default)]
+    pub monitor_muted: bool,
+    /// How soloed channels are heard.
+    #[serde(default)]
+    pub solo_mode: sootmix_ipc::SoloMode,
+}
+
+/// A sidechain ducking relationship between channels.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuckingConfig {
+    pub id: Uuid,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Channel whose meter level triggers ducking.
+    pub trigger_channel: Uuid,
+    /// Channels attenuated while the trigger is active.
+    #[serde(default)]
+    pub ducked_channels: Vec<Uuid>,
+    /// Trigger level in dB above which ducking engages.
+    #[serde(default = "default_duck_threshold")]
+    pub threshold_db: f32,
+    /// Attenuation in dB (positive) applied to the ducked channels.
+    #[serde(default = "default_duck_amount")]
+    pub amount_db: f32,
+    /// Time to reach full attenuation.
+    #[serde(default = "default_duck_attack")]
+    pub attack_ms: f32,
+    /// Time attenuation is held after the trigger falls below the threshold.
+    #[serde(default = "default_duck_hold")]
+    pub hold_ms: f32,
+    /// Time to recover from full attenuation.
+    #[serde(default = "default_duck_release")]
+    pub release_ms: f32,
+}
+
+fn default_duck_threshold() -> f32 {
+    -40.0
+}
+
+fn default_duck_amount() -> f32 {
+    12.0
+}
+
+fn default_duck_attack() -> f32 {
+    50.0
+}
+
+fn default_duck_hold() -> f32 {
+    300.0
+}
+
+fn default_duck_release() -> f32 {
+    500.0
 }
 
 /// Complete mixer state configuration.
@@ -153,6 +549,62 @@ pub struct MixerConfig {
     pub master: MasterConfig,
     #[serde(default)]
     pub channels: Vec<SavedChannel>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ducking: Vec<DuckingConfig>,
+}
+
+/// A named snapshot of the whole mixer, stored as `scenes/<name>.toml`.
+///
+/// Covers everything in `mixer.toml` (channel set, volumes, mutes, devices,
+/// plugin chains, noise suppression, ducking) plus the routing rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scene {
+    pub name: String,
+    #[serde(default)]
+    pub master: MasterConfig,
+    #[serde(default)]
+    pub channels: Vec<SavedChannel>,
+    #[serde(default)]
+    pub ducking: Vec<DuckingConfig>,
+    #[serde(default)]
+    pub routing_rules: Vec<RoutingRule>,
+}
+
+impl Scene {
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+}
+
+/// File stem for a user-given name: lowercase, with anything but ASCII
+/// alphanumerics and the `allowed` characters replaced by `_`, so names
+/// can't escape the directory.
+fn file_stem(name: &str, allowed: &[char]) -> String {
+    name.trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || allowed.contains(&c) {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// File stem for a scene name.
+fn scene_file_stem(name: &str) -> String {
+    file_stem(name, &['-', '_'])
+}
+
+/// File stem for a user EQ preset. Spaces are kept, as the GUI saves
+/// presets under their lowercased name.
+fn eq_preset_file_stem(name: &str) -> String {
+    file_stem(name, &['-', '_', ' '])
 }
 
 impl MixerConfig {
@@ -206,7 +658,10 @@ impl Default for MatchTarget {
 }
 
 /// Match type for routing rules.
+///
+/// Tagged the same way as the GUI's, which shares `routing_rules.toml`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "pattern")]
 pub enum MatchType {
     Contains(String),
     Exact(String),
@@ -232,6 +687,88 @@ impl MatchType {
             MatchType::Glob(_) => "glob",
         }
     }
+
+    /// Check a value against the pattern. Only regexes are case-sensitive.
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            MatchType::Contains(p) => value.to_lowercase().contains(&p.to_lowercase()),
+            MatchType::Exact(p) => value.eq_ignore_ascii_case(p),
+            MatchType::Regex(p) => RegexBuilder::new(p)
+                .size_limit(1 << 20)
+                .build()
+                .is_ok_and(|re| re.is_match(value)),
+            MatchType::Glob(p) => glob_match(p, value),
+        }
+    }
+}
+
+/// Case-insensitive glob match supporting `*`, `?` and `[abc]`/`[!abc]`.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let value: Vec<char> = value.to_lowercase().chars().collect();
+    let (mut p, mut v) = (0, 0);
+    // Pattern position after the last `*`, and where in the value it resumes.
+    let mut star = None;
+
+    while v < value.len() {
+        if pattern.get(p) == Some(&'*') {
+            p += 1;
+            star = Some((p, v));
+        } else if let Some(len) = glob_token(&pattern[p..], value[v]) {
+            p += len;
+            v += 1;
+        } else if let Some((resume, skipped)) = star {
+            // Let the `*` swallow one more character and retry.
+            p = resume;
+            v = skipped + 1;
+            star = Some((resume, v));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Match the first glob token against `c`, returning how many pattern
+/// characters it spans.
+fn glob_token(pattern: &[char], c: char) -> Option<usize> {
+    match *pattern.first()? {
+        '?' => Some(1),
+        '[' => {
+            let negated = matches!(pattern.get(1), Some('!' | '^'));
+            let start = 1 + usize::from(negated);
+            let end = start + pattern[start..].iter().position(|&ch| ch == ']')?;
+            (pattern[start..end].contains(&c) != negated).then_some(end + 1)
+        }
+        literal => (literal == c).then_some(1),
+    }
+}
+
+/// A routing rule condition on one PipeWire node property.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyCondition {
+    /// Node property key (e.g. "media.role").
+    pub property: String,
+    pub match_type: MatchType,
+}
+
+impl PropertyCondition {
+    /// Check the node's properties. A missing property never matches.
+    pub fn matches(&self, properties: &HashMap<String, String>) -> bool {
+        properties
+            .get(&self.property)
+            .is_some_and(|value| self.match_type.matches(value))
+    }
+}
+
+/// How a rule's conditions are combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionCombine {
+    /// Every condition must match.
+    #[default]
+    All,
+    /// At least one condition must match.
+    Any,
 }
 
 /// A routing rule for auto-assigning apps to channels.
@@ -247,17 +784,69 @@ pub struct RoutingRule {
     pub target_channel: String,
     #[serde(default = "default_priority")]
     pub priority: u32,
+    /// Extra conditions on node properties. The name/binary match counts as
+    /// one more condition unless its pattern is empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<PropertyCondition>,
+    #[serde(default)]
+    pub combine: ConditionCombine,
 }
 
 fn default_priority() -> u32 {
     100
 }
 
+impl RoutingRule {
+    /// Check an app's node against the rule.
+    ///
+    /// The name/binary match counts as one condition unless its pattern is
+    /// empty, so a rule can match on properties alone. A rule with no
+    /// conditions at all never matches.
+    pub fn matches_node(
+        &self,
+        app_name: &str,
+        binary: Option<&str>,
+        properties: &HashMap<String, String>,
+    ) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let app_condition =
+            (!self.match_type.pattern().is_empty()).then(|| self.matches_app(app_name, binary));
+        let mut results = app_condition
+            .into_iter()
+            .chain(self.conditions.iter().map(|c| c.matches(properties)))
+            .peekable();
+
+        if results.peek().is_none() {
+            return false;
+        }
+        match self.combine {
+            ConditionCombine::All => results.all(|matched| matched),
+            ConditionCombine::Any => results.any(|matched| matched),
+        }
+    }
+
+    fn matches_app(&self, app_name: &str, binary: Option<&str>) -> bool {
+        let binary_matches = || binary.is_some_and(|b| self.match_type.matches(b));
+        match self.match_target {
+            MatchTarget::Name => self.match_type.matches(app_name),
+            MatchTarget::Binary => binary_matches(),
+            MatchTarget::Either => self.match_type.matches(app_name) || binary_matches(),
+        }
+    }
+}
+
 /// Collection of routing rules.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RoutingRulesConfig {
     #[serde(default)]
     pub rules: Vec<RoutingRule>,
+    /// The GUI's app grouping mode. The daemon doesn't use it, but keeps it
+    /// so saving the rules doesn't reset the GUI's setting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_grouping: Option<String>,
 }
 
 impl RoutingRulesConfig {
@@ -269,24 +858,167 @@ impl RoutingRulesConfig {
         toml::to_string_pretty(self)
     }
 
-    #[allow(dead_code)] // Will be used when daemon-side rule evaluation is implemented
+    /// The highest-priority (lowest value) enabled rule matching an app's node.
+    pub fn find_match(
+        &self,
+        app_name: &str,
+        binary: Option<&str>,
+        properties: &HashMap<String, String>,
+    ) -> Option<&RoutingRule> {
+        self.rules
+            .iter()
+            .filter(|r| r.matches_node(app_name, binary, properties))
+            .min_by_key(|r| r.priority)
+    }
+
     pub fn get_rule(&self, id: Uuid) -> Option<&RoutingRule> {
         self.rules.iter().find(|r| r.id == id)
     }
 
-    #[allow(dead_code)] // Will be used when daemon-side rule evaluation is implemented
     pub fn toggle_rule(&mut self, id: Uuid) {
         if let Some(rule) = self.rules.iter_mut().find(|r| r.id == id) {
             rule.enabled = !rule.enabled;
         }
     }
 
-    #[allow(dead_code)] // Will be used when daemon-side rule evaluation is implemented
     pub fn remove_rule(&mut self, id: Uuid) {
         self.rules.retain(|r| r.id != id);
     }
 }
 
+/// Recording settings (`recording.toml`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingConfig {
+    /// Where recordings and replay clips are written. Defaults to `<Music>/SootMix`.
+    #[serde(default)]
+    pub directory: Option<PathBuf>,
+    /// Length of the instant-replay history kept per channel.
+    #[serde(default = "default_replay_seconds")]
+    pub replay_seconds: u32,
+    /// Channels with an instant-replay buffer.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replay_channels: Vec<Uuid>,
+}
+
+fn default_replay_seconds() -> u32 {
+    crate::recording::replay::DEFAULT_REPLAY_SECONDS
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            replay_seconds: default_replay_seconds(),
+            replay_channels: Vec::new(),
+        }
+    }
+}
+
+impl RecordingConfig {
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+
+    /// The configured directory, or the default one.
+    pub fn directory(&self) -> PathBuf {
+        self.directory.clone().unwrap_or_else(|| {
+            let dirs = directories::UserDirs::new();
+            dirs.as_ref()
+                .and_then(|d| d.audio_dir())
+                .or_else(|| dirs.as_ref().map(|d| d.home_dir()))
+                .map(|d| d.join("SootMix"))
+                .unwrap_or_else(|| PathBuf::from("SootMix"))
+        })
+    }
+}
+
+/// Kind of MIDI control a binding listens to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MidiControlKind {
+    /// Control change (knobs, faders, some buttons).
+    Cc,
+    /// Note on/off (most buttons and pads).
+    Note,
+}
+
+/// What a MIDI control drives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MidiTarget {
+    ChannelVolume {
+        channel: Uuid,
+    },
+    ChannelMute {
+        channel: Uuid,
+    },
+    ChannelSolo {
+        channel: Uuid,
+    },
+    /// Momentary: held down like the channel's talk key.
+    ChannelTalk {
+        channel: Uuid,
+    },
+    MasterVolume,
+    MasterMute,
+    /// A parameter of the plugin in `slot` of a channel's chain.
+    PluginParameter {
+        channel: Uuid,
+        slot: usize,
+        param_index: u32,
+    },
+}
+
+impl MidiTarget {
+    /// Channel the target belongs to, if any.
+    pub fn channel(&self) -> Option<Uuid> {
+        match *self {
+            Self::ChannelVolume { channel }
+            | Self::ChannelMute { channel }
+            | Self::ChannelSolo { channel }
+            | Self::ChannelTalk { channel }
+            | Self::PluginParameter { channel, .. } => Some(channel),
+            Self::MasterVolume | Self::MasterMute => None,
+        }
+    }
+}
+
+/// A MIDI control bound to a mixer target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MidiBinding {
+    pub id: Uuid,
+    /// Device the control was learned from; `None` matches every device.
+    #[serde(default)]
+    pub device: Option<String>,
+    /// MIDI channel, 1-16.
+    pub midi_channel: u8,
+    pub control: MidiControlKind,
+    /// Controller or note number.
+    pub number: u8,
+    pub target: MidiTarget,
+}
+
+/// MIDI controller bindings (`midi.toml`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MidiConfig {
+    #[serde(default)]
+    pub bindings: Vec<MidiBinding>,
+}
+
+impl MidiConfig {
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string_pretty(self)
+    }
+}
+
 /// Configuration manager handles loading and saving config files.
 pub struct ConfigManager {
     config_dir: PathBuf,
@@ -306,6 +1038,12 @@ impl ConfigManager {
         Ok(Self { config_dir })
     }
 
+    /// Create a config manager reading and writing `config_dir`.
+    #[cfg(test)]
+    pub fn with_dir(config_dir: PathBuf) -> Self {
+        Self { config_dir }
+    }
+
     /// Get the path to a config file.
     fn config_path(&self, name: &str) -> PathBuf {
         self.config_dir.join(name)
@@ -393,8 +1131,220 @@ impl ConfigManager {
         }
     }
 
+    /// List available EQ preset names (built-in first, then user presets).
+    pub fn list_eq_presets(&self) -> Result<Vec<String>, ConfigError> {
+        let mut presets: Vec<String> = EqPreset::builtin_presets()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+
+        let dir = self.config_path("eq_presets");
+        if dir.exists() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|e| e == "toml") {
+                    if let Some(stem) = path.file_stem() {
+                        let name = stem.to_string_lossy().to_string();
+                        if !presets.iter().any(|p| p.eq_ignore_ascii_case(&name)) {
+                            presets.push(name);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(presets)
+    }
+
+    /// Load an EQ preset by name.
+    ///
+    /// Built-in presets are matched case-insensitively; user presets are read
+    /// from `eq_presets/<name>.toml`. Returns `None` if no preset matches.
+    pub fn load_eq_preset(&self, name: &str) -> Result<Option<EqPreset>, ConfigError> {
+        if let Some(preset) = EqPreset::builtin_presets()
+            .into_iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+        {
+            return Ok(Some(preset));
+        }
+
+        let path = self
+            .config_path("eq_presets")
+            .join(format!("{}.toml", eq_preset_file_stem(name)));
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(EqPreset::from_toml(&content)?))
+    }
+
+    /// Rewrite user EQ presets saved by older versions in the current format.
+    /// Presets that fail to parse are left alone. Returns how many were
+    /// migrated.
+    pub fn migrate_eq_presets(&self) -> Result<usize, ConfigError> {
+        let dir = self.config_path("eq_presets");
+        if !dir.exists() {
+            return Ok(0);
+        }
+
+        let mut migrated = 0;
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|e| e != "toml") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)?;
+            let Some(preset) = migrate_eq_preset(&content) else {
+                continue;
+            };
+            // Written aside first so a crash can't leave a truncated preset
+            let tmp_path = path.with_extension("toml.tmp");
+            fs::write(&tmp_path, preset.to_toml()?)?;
+            fs::rename(&tmp_path, &path)?;
+            info!(
+                "Migrated EQ preset {:?} to version {}",
+                path, EQ_PRESET_VERSION
+            );
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+
+    fn scene_path(&self, name: &str) -> PathBuf {
+        self.config_path("scenes")
+            .join(format!("{}.toml", scene_file_stem(name)))
+    }
+
+    /// List saved scene names, sorted case-insensitively. Unreadable scene
+    /// files are skipped with a warning.
+    pub fn list_scenes(&self) -> Result<Vec<String>, ConfigError> {
+        let dir = self.config_path("scenes");
+        let mut names = Vec::new();
+        if dir.exists() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|e| e == "toml") {
+                    match fs::read_to_string(&path)
+                        .map_err(ConfigError::from)
+                        .and_then(|content| Ok(Scene::from_toml(&content)?))
+                    {
+                        Ok(scene) => names.push(scene.name),
+                        Err(e) => warn!("Skipping unreadable scene {:?}: {}", path, e),
+                    }
+                }
+            }
+        }
+        names.sort_by_key(|n| n.to_lowercase());
+        Ok(names)
+    }
+
+    /// Read the scene in the file `name` maps to, whatever it is named.
+    fn read_scene_file(&self, name: &str) -> Result<Option<Scene>, ConfigError> {
+        let path = self.scene_path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(Some(Scene::from_toml(&content)?))
+    }
+
+    /// Name of the scene saved in the file `name` maps to. Names that differ
+    /// only in case or punctuation share a file.
+    pub fn scene_file_owner(&self, name: &str) -> Result<Option<String>, ConfigError> {
+        Ok(self.read_scene_file(name)?.map(|scene| scene.name))
+    }
+
+    /// Load a scene by name, ignoring case. Returns `None` if no such scene
+    /// is saved.
+    pub fn load_scene(&self, name: &str) -> Result<Option<Scene>, ConfigError> {
+        Ok(self
+            .read_scene_file(name)?
+            .filter(|scene| scene.name.eq_ignore_ascii_case(name.trim())))
+    }
+
+    /// Save a scene atomically, replacing any scene with the same file name.
+    pub fn save_scene(&self, scene: &Scene) -> Result<(), ConfigError> {
+        let dir = self.config_path("scenes");
+        fs::create_dir_all(&dir)?;
+        let path = self.scene_path(&scene.name);
+        let tmp_path = path.with_extension("toml.tmp");
+        let content = scene.to_toml()?;
+        fs::write(&tmp_path, &content)?;
+        fs::rename(&tmp_path, &path)?;
+        debug!("Saved scene to {:?}", path);
+        Ok(())
+    }
+
+    /// Delete a scene. Returns whether it existed.
+    pub fn delete_scene(&self, name: &str) -> Result<bool, ConfigError> {
+        if self.load_scene(name)?.is_none() {
+            return Ok(false);
+        }
+        let path = self.scene_path(name);
+        fs::remove_file(&path)?;
+        debug!("Deleted scene {:?}", path);
+        Ok(true)
+    }
+
+    /// Load recording settings, falling back to defaults if missing or unreadable.
+    pub fn load_recording_config(&self) -> RecordingConfig {
+        let path = self.config_path("recording.toml");
+        if !path.exists() {
+            return RecordingConfig::default();
+        }
+        match fs::read_to_string(&path)
+            .map_err(ConfigError::from)
+            .and_then(|content| Ok(RecordingConfig::from_toml(&content)?))
+        {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Failed to load recording config: {}, using defaults", e);
+                RecordingConfig::default()
+            }
+        }
+    }
+
+    /// Save recording settings atomically.
+    pub fn save_recording_config(&self, config: &RecordingConfig) -> Result<(), ConfigError> {
+        let path = self.config_path("recording.toml");
+        let tmp_path = self.config_path("recording.toml.tmp");
+        let content = config.to_toml()?;
+        fs::write(&tmp_path, &content)?;
+        fs::rename(&tmp_path, &path)?;
+        debug!("Saved recording config to {:?}", path);
+        Ok(())
+    }
+
+    /// Load MIDI bindings, falling back to none if missing or unreadable.
+    pub fn load_midi_config(&self) -> MidiConfig {
+        let path = self.config_path("midi.toml");
+        if !path.exists() {
+            return MidiConfig::default();
+        }
+        match fs::read_to_string(&path)
+            .map_err(ConfigError::from)
+            .and_then(|content| Ok(MidiConfig::from_toml(&content)?))
+        {
+            Ok(config) => config,
+            Err(e) => {
+                warn!("Failed to load MIDI config: {}, using defaults", e);
+                MidiConfig::default()
+            }
+        }
+    }
+
+    /// Save MIDI bindings atomically.
+    pub fn save_midi_config(&self, config: &MidiConfig) -> Result<(), ConfigError> {
+        let path = self.config_path("midi.toml");
+        let tmp_path = self.config_path("midi.toml.tmp");
+        let content = config.to_toml()?;
+        fs::write(&tmp_path, &content)?;
+        fs::rename(&tmp_path, &path)?;
+        debug!("Saved MIDI config to {:?}", path);
+        Ok(())
+    }
+
     /// Save routing rules atomically (write to temp file, then rename).
-    #[allow(dead_code)]
     pub fn save_routing_rules(&self, config: &RoutingRulesConfig) -> Result<(), ConfigError> {
         let path = self.config_path("routing_rules.toml");
         let tmp_path = self.config_path("routing_rules.toml.tmp");
@@ -405,3 +1355,164 @@ impl ConfigManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_five_band_eq_preset() {
+        let mut old = String::from("name = \"Old\"\n");
+        for freq in [60, 250, 1000, 4000, 16000] {
+            old.push_str(&format!(
+                "\n[[bands]]\nfreq = {freq}\ngain = 2.5\nq = 0.8\n"
+            ));
+        }
+
+        let preset = migrate_eq_preset(&old).expect("five-band preset needs migrating");
+        assert_eq!(preset.version, EQ_PRESET_VERSION);
+        assert_eq!(preset.bands.len(), 5);
+        assert_eq!(preset.bands[4].freq, 16000.0);
+        assert!(preset.bands.iter().all(|band| {
+            band.kind == sootmix_ipc::EqBandType::Peaking && band.gain == 2.5 && band.slope == 12
+        }));
+
+        let migrated = preset.to_toml().unwrap();
+        assert_eq!(migrate_eq_preset(&migrated), None);
+        assert_eq!(EqPreset::from_toml(&migrated).unwrap(), preset);
+    }
+
+    #[test]
+    fn test_eq_preset_name_stays_in_directory() {
+        let root = tempfile::tempdir().unwrap();
+        let config_dir = root.path().join("config");
+        fs::create_dir_all(config_dir.join("eq_presets")).unwrap();
+        let preset = EqPreset::new("Escaped", vec![EqBand::new(1000.0)]);
+        fs::write(root.path().join("escaped.toml"), preset.to_toml().unwrap()).unwrap();
+        fs::write(
+            config_dir.join("eq_presets").join("my preset.toml"),
+            preset.to_toml().unwrap(),
+        )
+        .unwrap();
+
+        let manager = ConfigManager::with_dir(config_dir);
+        assert!(manager.load_eq_preset("../escaped").unwrap().is_none());
+        assert!(manager.load_eq_preset("My Preset").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_scene_names_sharing_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = ConfigManager::with_dir(dir.path().to_path_buf());
+        let scene = Scene {
+            name: "My Scene".to_string(),
+            master: MasterConfig::default(),
+            channels: Vec::new(),
+            ducking: Vec::new(),
+            routing_rules: Vec::new(),
+        };
+        manager.save_scene(&scene).unwrap();
+
+        assert_eq!(
+            manager.scene_file_owner("my_scene").unwrap().as_deref(),
+            Some("My Scene")
+        );
+        assert!(manager.load_scene("my_scene").unwrap().is_none());
+        assert!(!manager.delete_scene("my_scene").unwrap());
+        assert!(manager.load_scene("my scene").unwrap().is_some());
+        assert!(manager.delete_scene("MY SCENE").unwrap());
+    }
+
+    #[test]
+    fn test_conditions_only_rule() {
+        let mut rule = RoutingRule {
+            id: Uuid::new_v4(),
+            name: "Calls".to_string(),
+            enabled: true,
+            match_target: MatchTarget::Either,
+            match_type: MatchType::Contains(String::new()),
+            target_channel: "Voice".to_string(),
+            priority: 100,
+            conditions: vec![PropertyCondition {
+                property: "media.role".to_string(),
+                match_type: MatchType::Exact("Communication".to_string()),
+            }],
+            combine: ConditionCombine::All,
+        };
+        let call = HashMap::from([("media.role".to_string(), "communication".to_string())]);
+        let music = HashMap::from([("media.role".to_string(), "Music".to_string())]);
+
+        assert!(rule.matches_node("Chromium", Some("chromium"), &call));
+        assert!(!rule.matches_node("Chromium", Some("chromium"), &music));
+        assert!(!rule.matches_node("Chromium", Some("chromium"), &HashMap::new()));
+
+        rule.match_type = MatchType::Glob("chrom*".to_string());
+        rule.combine = ConditionCombine::Any;
+        assert!(rule.matches_node("Chromium", None, &music));
+        assert!(!rule.matches_node("Firefox", Some("firefox"), &music));
+
+        rule.match_type = MatchType::Contains(String::new());
+        rule.conditions.clear();
+        assert!(!rule.matches_node("Chromium", Some("chromium"), &call));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*fox", "Firefox"));
+        assert!(glob_match("f?re*", "firefox"));
+        assert!(glob_match("[!x]*[abc]", "mpv-c"));
+        assert!(!glob_match("[abc]", "d"));
+        assert!(!glob_match("*fox", "foxes"));
+        assert!(glob_match("**", ""));
+    }
+
+    #[test]
+    fn test_routing_rules_gui_round_trip() {
+        // As written by the GUI's `save_routing_rules`.
+        let gui = r#"app_grouping = "Individual"
+
+[[rules]]
+id = "6f1c2a8e-1d2b-4c55-9a0e-3b7d5e2f9c41"
+name = "Browsers"
+match_target = "AppName"
+target_channel = "Web"
+enabled = true
+priority = 50
+
+[rules.match_type]
+type = "Contains"
+pattern = "firefox"
+
+[[rules]]
+id = "0b9e4d3a-7c21-4f6e-8a5d-2c1f0e9b8a77"
+name = "Players"
+match_target = "Binary"
+target_channel = "Music"
+enabled = false
+priority = 100
+
+[rules.match_type]
+type = "Regex"
+pattern = "^(mpv|vlc)$"
+"#;
+
+        let config = RoutingRulesConfig::from_toml(gui).unwrap();
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].match_target, MatchTarget::Name);
+        assert!(matches!(&config.rules[0].match_type, MatchType::Contains(p) if p == "firefox"));
+        assert!(matches!(&config.rules[1].match_type, MatchType::Regex(p) if p == "^(mpv|vlc)$"));
+        assert!(!config.rules[1].enabled);
+        assert_eq!(config.app_grouping.as_deref(), Some("Individual"));
+
+        let saved = config.to_toml().unwrap();
+        let value: toml::Value = toml::from_str(&saved).unwrap();
+        assert_eq!(value["app_grouping"].as_str(), Some("Individual"));
+        let match_type = &value["rules"][0]["match_type"];
+        assert_eq!(match_type["type"].as_str(), Some("Contains"));
+        assert_eq!(match_type["pattern"].as_str(), Some("firefox"));
+
+        let reloaded = RoutingRulesConfig::from_toml(&saved).unwrap();
+        assert_eq!(reloaded.rules[1].priority, 100);
+        assert!(matches!(&reloaded.rules[1].match_type, MatchType::Regex(p) if p == "^(mpv|vlc)$"));
+    }
+}
@@ -5,7 +5,12 @@
 //! D-Bus interface implementation for the daemon.
 
 use crate::service::DaemonService;
-use sootmix_ipc::{AppInfo, ChannelInfo, InputInfo, MeterData, OutputInfo, RoutingRuleInfo};
+use sootmix_ipc::{
+    AppInfo, AutoLevelInfo, ChannelInfo, ChannelLayout, DuckingInfo, EqBandInfo, InputInfo,
+    LoudnessData, MeterData, MidiBindingInfo, MonitorInfo, NoiseSuppressionInfo, OutputInfo,
+    PluginSlotInfo, RecordingStatus, ReplayBufferInfo, RoutingRuleInfo, SoloMode, SpectrumData,
+    SpectrumTap, TalkInfo,
+};
 use std::sync::{Arc, Mutex};
 use tracing::debug;
 use zbus::interface;
@@ -40,6 +45,16 @@ mod validate {
         Ok(volume_db.clamp(-96.0, 24.0))
     }
 
+    /// Validate a pan or width value: reject NaN/Infinity (the service clamps).
+    pub fn validate_balance(value: f64) -> Result<f32, zbus::fdo::Error> {
+        if !value.is_finite() {
+            return Err(zbus::fdo::Error::InvalidArgs(
+                "Pan and width must be finite numbers".into(),
+            ));
+        }
+        Ok(value as f32)
+    }
+
     /// Validate a device name: non-empty, max 256 chars, no control characters.
     pub fn validate_device_name(name: &str) -> Result<(), zbus::fdo::Error> {
         if name.is_empty() {
@@ -68,6 +83,27 @@ impl DaemonDbusService {
     pub fn new(service: Arc<Mutex<DaemonService>>) -> Self {
         Self { service }
     }
+
+    /// Apply a monitor bus setting and emit `MonitorChanged` with the result.
+    async fn update_monitor(
+        &self,
+        ctx: &zbus::SignalContext<'_>,
+        update: impl FnOnce(&mut DaemonService) -> Result<(), crate::service::ServiceError>,
+    ) -> zbus::fdo::Result<()> {
+        let monitor = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            update(&mut *service).map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.get_monitor()
+        };
+
+        // Emit signal after releasing the lock
+        let _ = Self::monitor_changed(ctx, monitor).await;
+        Ok(())
+    }
 }
 
 #[interface(name = "com.sootmix.Daemon")]
@@ -274,6 +310,80 @@ impl DaemonDbusService {
         Ok(())
     }
 
+    /// Set channel pan (-1.0 left to 1.0 right) and width (0.0 front
+    /// speakers only to 1.0 all speakers; surround layouts only).
+    async fn set_channel_balance(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        channel_id: &str,
+        pan: f64,
+        width: f64,
+    ) -> zbus::fdo::Result<()> {
+        let pan = validate::validate_balance(pan)?;
+        let width = validate::validate_balance(width)?;
+        debug!(
+            "D-Bus: set_channel_balance({}, {}, {})",
+            channel_id, pan, width
+        );
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_balance(channel_id, pan, width)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
+
+        // Emit signal after releasing the lock
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
+    }
+
+    /// Set an output channel's speaker layout ("mono", "stereo", "5.1" or
+    /// "7.1"). The channel's sink is recreated, so audio drops out briefly.
+    /// Surround layouts need the EQ off and no plugins, which run in stereo.
+    async fn set_channel_layout(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        channel_id: &str,
+        layout: &str,
+    ) -> zbus::fdo::Result<()> {
+        let layout: ChannelLayout = layout.parse().map_err(zbus::fdo::Error::InvalidArgs)?;
+        debug!("D-Bus: set_channel_layout({}, {})", channel_id, layout);
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_layout(channel_id, layout)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
+
+        // Emit signal after releasing the lock
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
+    }
+
     /// Enable or disable noise suppression on an input channel.
     async fn set_channel_noise_suppression(
         &self,
@@ -316,6 +426,90 @@ impl DaemonDbusService {
         Ok(())
     }
 
+    /// Set the noise suppression model, VAD grace periods and attenuation
+    /// limit of an input channel.
+    async fn set_channel_noise_suppression_settings(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        channel_id: &str,
+        settings: NoiseSuppressionInfo,
+    ) -> zbus::fdo::Result<()> {
+        debug!(
+            "D-Bus: set_channel_noise_suppression_settings({}, {:?})",
+            channel_id, settings
+        );
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_noise_suppression_settings(channel_id, &settings)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
+
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
+    }
+
+    /// Set the push-to-talk / push-to-mute mode, release tail and fade of
+    /// an input channel.
+    async fn set_channel_talk_settings(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        channel_id: &str,
+        settings: TalkInfo,
+    ) -> zbus::fdo::Result<()> {
+        debug!(
+            "D-Bus: set_channel_talk_settings({}, {:?})",
+            channel_id, settings
+        );
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_talk_settings(channel_id, &settings)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
+
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
+    }
+
+    /// Press or let go of the talk key of an input channel, or of every
+    /// input channel with a talk mode if `channel_id` is empty.
+    async fn set_talk_key(&self, channel_id: &str, pressed: bool) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_talk_key({}, {})", channel_id, pressed);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_talk_key(channel_id, pressed)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(())
+    }
+
     /// Set the hardware microphone gain for an input channel.
     /// This controls the physical input device level, separate from the channel volume.
     async fn set_channel_input_gain(
@@ -346,6 +540,40 @@ impl DaemonDbusService {
         Ok(())
     }
 
+    /// Set a channel's automatic loudness normalization. The applied gain is
+    /// reported in `ChannelInfo` and in meter updates.
+    async fn set_channel_auto_level(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        channel_id: &str,
+        settings: AutoLevelInfo,
+    ) -> zbus::fdo::Result<()> {
+        debug!(
+            "D-Bus: set_channel_auto_level({}, {:?})",
+            channel_id, settings
+        );
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .set_channel_auto_level(channel_id, &settings)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
+
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
+    }
+
     /// Set master volume in dB.
     async fn set_master_volume(
         &self,
@@ -526,21 +754,42 @@ impl DaemonDbusService {
 
     // ==================== EQ ====================
 
-    /// Toggle EQ on/off for a channel.
+    /// Toggle EQ on/off for a channel. Not available on surround channels.
     async fn set_channel_eq_enabled(
         &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
         channel_id: &str,
         enabled: bool,
     ) -> zbus::fdo::Result<()> {
         debug!("D-Bus: set_channel_eq_enabled({}, {})", channel_id, enabled);
-        Err(zbus::fdo::Error::NotSupported(
-            "EQ is not yet implemented in the daemon".into(),
-        ))
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_eq_enabled(channel_id, enabled)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
+
+        // Emit signal after releasing the lock
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
     }
 
     /// Set EQ preset for a channel.
     async fn set_channel_eq_preset(
         &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
         channel_id: &str,
         preset_name: &str,
     ) -> zbus::fdo::Result<()> {
@@ -548,61 +797,717 @@ impl DaemonDbusService {
             "D-Bus: set_channel_eq_preset({}, {})",
             channel_id, preset_name
         );
-        Err(zbus::fdo::Error::NotSupported(
-            "EQ is not yet implemented in the daemon".into(),
-        ))
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_eq_preset(channel_id, preset_name)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
+
+        // Emit signal after releasing the lock
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
     }
 
-    // ==================== Routing Rules ====================
+    /// Replace the bands of a channel's EQ (marks it as edited).
+    async fn set_channel_eq_bands(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        channel_id: &str,
+        bands: Vec<EqBandInfo>,
+    ) -> zbus::fdo::Result<()> {
+        debug!(
+            "D-Bus: set_channel_eq_bands({}, {} bands)",
+            channel_id,
+            bands.len()
+        );
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_eq_bands(channel_id, &bands)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
 
-    /// Get all routing rules.
-    async fn get_routing_rules(&self) -> zbus::fdo::Result<Vec<RoutingRuleInfo>> {
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
+    }
+
+    /// Get the names of all available EQ presets (built-in and user).
+    async fn get_eq_presets(&self) -> zbus::fdo::Result<Vec<String>> {
         let service = self
             .service
             .lock()
             .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
-        Ok(service.state.get_routing_rules())
-    }
-
-    /// Add or update a routing rule.
-    async fn set_routing_rule(&self, rule: RoutingRuleInfo) -> zbus::fdo::Result<()> {
-        debug!("D-Bus: set_routing_rule({:?})", rule);
-        Err(zbus::fdo::Error::NotSupported(
-            "Routing rules are evaluated by the UI only. Daemon-side evaluation is not yet implemented.".into(),
-        ))
+        service
+            .get_eq_presets()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
     }
 
-    /// Delete a routing rule.
-    async fn delete_routing_rule(&self, rule_id: &str) -> zbus::fdo::Result<()> {
-        debug!("D-Bus: delete_routing_rule({})", rule_id);
-        Err(zbus::fdo::Error::NotSupported(
-            "Routing rules are evaluated by the UI only. Daemon-side evaluation is not yet implemented.".into(),
-        ))
-    }
+    // ==================== Plugins ====================
 
-    /// Toggle a routing rule's enabled state.
-    async fn toggle_routing_rule(&self, rule_id: &str) -> zbus::fdo::Result<()> {
-        debug!("D-Bus: toggle_routing_rule({})", rule_id);
-        Err(zbus::fdo::Error::NotSupported(
-            "Routing rules are evaluated by the UI only. Daemon-side evaluation is not yet implemented.".into(),
-        ))
-    }
+    /// Load a plugin into a new slot at the end of a channel's chain.
+    /// Returns the new instance ID. Not available on surround channels.
+    async fn add_channel_plugin(
+        &self,
+        channel_id: &str,
+        plugin_id: &str,
+    ) -> zbus::fdo::Result<String> {
+        debug!("D-Bus: add_channel_plugin({}, {})", channel_id, plugin_id);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service.process_pw_events();
+        service
+            .add_channel_plugin(channel_id, plugin_id)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Remove a slot from a channel's plugin chain.
+    async fn remove_channel_plugin(&self, channel_id: &str, slot: u32) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: remove_channel_plugin({}, {})", channel_id, slot);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service.process_pw_events();
+        service
+            .remove_channel_plugin(channel_id, slot as usize)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Move a plugin slot to a new position in a channel's chain.
+    async fn move_channel_plugin(
+        &self,
+        channel_id: &str,
+        from_slot: u32,
+        to_slot: u32,
+    ) -> zbus::fdo::Result<()> {
+        debug!(
+            "D-Bus: move_channel_plugin({}, {} -> {})",
+            channel_id, from_slot, to_slot
+        );
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service.process_pw_events();
+        service
+            .move_channel_plugin(channel_id, from_slot as usize, to_slot as usize)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Bypass or re-enable a plugin slot.
+    async fn set_channel_plugin_bypassed(
+        &self,
+        channel_id: &str,
+        slot: u32,
+        bypassed: bool,
+    ) -> zbus::fdo::Result<()> {
+        debug!(
+            "D-Bus: set_channel_plugin_bypassed({}, {}, {})",
+            channel_id, slot, bypassed
+        );
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service.process_pw_events();
+        service
+            .set_channel_plugin_bypassed(channel_id, slot as usize, bypassed)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Set a parameter (normalized 0.0-1.0) on a plugin slot.
+    async fn set_channel_plugin_parameter(
+        &self,
+        channel_id: &str,
+        slot: u32,
+        param_index: u32,
+        value: f64,
+    ) -> zbus::fdo::Result<()> {
+        if !value.is_finite() {
+            return Err(zbus::fdo::Error::InvalidArgs(
+                "Parameter value must be a finite number".into(),
+            ));
+        }
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_channel_plugin_parameter(
+                channel_id,
+                slot as usize,
+                param_index,
+                value.clamp(0.0, 1.0) as f32,
+            )
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Get the slots of a channel's plugin chain, in processing order.
+    async fn get_channel_plugins(
+        &self,
+        channel_id: &str,
+    ) -> zbus::fdo::Result<Vec<PluginSlotInfo>> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .get_channel_plugins(channel_id)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    // ==================== Routing Rules ====================
+
+    /// Get all routing rules.
+    async fn get_routing_rules(&self) -> zbus::fdo::Result<Vec<RoutingRuleInfo>> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.state.get_routing_rules())
+    }
+
+    /// Add or update a routing rule. An empty rule ID creates a new rule.
+    async fn set_routing_rule(&self, rule: RoutingRuleInfo) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_routing_rule({:?})", rule);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_routing_rule(rule)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Delete a routing rule.
+    async fn delete_routing_rule(&self, rule_id: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: delete_routing_rule({})", rule_id);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .delete_routing_rule(rule_id)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Toggle a routing rule's enabled state.
+    async fn toggle_routing_rule(&self, rule_id: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: toggle_routing_rule({})", rule_id);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .toggle_routing_rule(rule_id)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    // ==================== Ducking ====================
+
+    /// Get all ducking relationships.
+    async fn get_ducking(&self) -> zbus::fdo::Result<Vec<DuckingInfo>> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.get_ducking())
+    }
+
+    /// Add or update a ducking relationship. An empty ID creates a new one.
+    /// Returns the relationship ID.
+    async fn set_ducking(&self, ducking: DuckingInfo) -> zbus::fdo::Result<String> {
+        debug!("D-Bus: set_ducking({:?})", ducking);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_ducking(ducking)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Delete a ducking relationship.
+    async fn delete_ducking(&self, ducking_id: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: delete_ducking({})", ducking_id);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .delete_ducking(ducking_id)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    // ==================== Scenes ====================
+
+    /// List saved scene names.
+    async fn list_scenes(&self) -> zbus::fdo::Result<Vec<String>> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .list_scenes()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Save the current mixer state as a named scene.
+    async fn save_scene(&self, name: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: save_scene({})", name);
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .save_scene(name)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Recall a scene, fading volume changes over `fade_ms` milliseconds.
+    async fn recall_scene(&self, name: &str, fade_ms: u32) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: recall_scene({}, {})", name, fade_ms);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service.process_pw_events();
+        service
+            .recall_scene(name, fade_ms)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Delete a saved scene.
+    async fn delete_scene(&self, name: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: delete_scene({})", name);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .delete_scene(name)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    // ==================== Recording ====================
+
+    /// Enable or disable master recording output.
+    async fn set_master_recording(&self, enabled: bool) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_master_recording({})", enabled);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service.process_pw_events();
+        service
+            .set_master_recording(enabled)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Start recording `sources` ("master" or channel IDs), one file each,
+    /// as "wav" or "flac". Returns the recording ID.
+    async fn start_recording(
+        &self,
+        sources: Vec<String>,
+        format: &str,
+    ) -> zbus::fdo::Result<String> {
+        debug!("D-Bus: start_recording({:?}, {})", sources, format);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service.process_pw_events();
+        service
+            .start_recording(&sources, format)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Stop a recording and finalize its files.
+    async fn stop_recording(&self, recording_id: &str) -> zbus::fdo::Result<RecordingStatus> {
+        debug!("D-Bus: stop_recording({})", recording_id);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .stop_recording(recording_id)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Get the status of all running recordings.
+    async fn get_recordings(&self) -> zbus::fdo::Result<Vec<RecordingStatus>> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.get_recordings())
+    }
+
+    /// Get the directory new recordings are written to.
+    async fn get_recording_directory(&self) -> zbus::fdo::Result<String> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.get_recording_directory())
+    }
+
+    /// Set the recording directory (empty for the default).
+    async fn set_recording_directory(&self, directory: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_recording_directory({})", directory);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_recording_directory(directory)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Enable or disable a channel's instant-replay buffer.
+    async fn set_channel_replay(&self, channel_id: &str, enabled: bool) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_channel_replay({}, {})", channel_id, enabled);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_channel_replay(channel_id, enabled)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Get the channels with a replay buffer and how much each holds.
+    async fn get_replay_buffers(&self) -> zbus::fdo::Result<Vec<ReplayBufferInfo>> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.get_replay_buffers())
+    }
+
+    /// Get the replay buffer length in seconds.
+    async fn get_replay_length(&self) -> zbus::fdo::Result<u32> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.get_replay_length())
+    }
+
+    /// Set the replay buffer length in seconds.
+    async fn set_replay_length(&self, seconds: u32) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_replay_length({})", seconds);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_replay_length(seconds)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Save the last `seconds` (0 = all) of the given channels' replay
+    /// buffers (all buffers if empty) as "wav" or "flac". Returns the files.
+    async fn save_replay(
+        &self,
+        channel_ids: Vec<String>,
+        seconds: u32,
+        format: &str,
+    ) -> zbus::fdo::Result<Vec<String>> {
+        debug!(
+            "D-Bus: save_replay({:?}, {}, {})",
+            channel_ids, seconds, format
+        );
+        let save = {
+            let service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .snapshot_replay(&channel_ids, seconds, format)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?
+        };
+        // Encoding minutes of audio takes a while; keep it off the service
+        // lock and the D-Bus executor
+        let (tx, rx) = futures::channel::oneshot::channel();
+        std::thread::Builder::new()
+            .name("sootmix-replay-save".to_string())
+            .spawn(move || {
+                let _ = tx.send(save.write());
+            })
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        rx.await
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    // ==================== Monitor ====================
+
+    /// Solo or unsolo a channel.
+    async fn set_channel_solo(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        channel_id: &str,
+        solo: bool,
+    ) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_channel_solo({}, {})", channel_id, solo);
+        {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_solo(channel_id, solo)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        }
+
+        // Emit signal after releasing the lock
+        let _ = Self::solo_changed(&ctx, channel_id, solo).await;
+        Ok(())
+    }
+
+    /// Unsolo every channel.
+    async fn clear_solo(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+    ) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: clear_solo()");
+        let cleared = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service.clear_solo()
+        };
+
+        // Emit signals after releasing the lock
+        for channel_id in cleared {
+            let _ = Self::solo_changed(&ctx, &channel_id, false).await;
+        }
+        Ok(())
+    }
+
+    /// Turn an input channel's sidetone on or off and set its level in dB.
+    async fn set_channel_sidetone(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        channel_id: &str,
+        enabled: bool,
+        volume_db: f64,
+    ) -> zbus::fdo::Result<()> {
+        let volume_db = validate::validate_volume_db(volume_db)?;
+        debug!(
+            "D-Bus: set_channel_sidetone({}, {}, {})",
+            channel_id, enabled, volume_db
+        );
+        let channel_info = {
+            let mut service = self
+                .service
+                .lock()
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service.process_pw_events();
+            service
+                .set_channel_sidetone(channel_id, enabled, volume_db as f32)
+                .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+            service
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id.to_string() == channel_id)
+                .map(|c| c.to_channel_info())
+        };
 
-    // ==================== Recording ====================
+        // Emit signal after releasing the lock
+        if let Some(info) = channel_info {
+            let _ = Self::channel_updated(&ctx, info).await;
+        }
+        Ok(())
+    }
 
-    /// Enable or disable master recording output.
-    async fn set_master_recording(&self, enabled: bool) -> zbus::fdo::Result<()> {
-        debug!("D-Bus: set_master_recording({})", enabled);
+    /// Get the monitor bus settings.
+    async fn get_monitor(&self) -> zbus::fdo::Result<MonitorInfo> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.get_monitor())
+    }
+
+    /// Set the device the monitor bus plays on (empty = the master output).
+    async fn set_monitor_device(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        device_name: &str,
+    ) -> zbus::fdo::Result<()> {
+        if !device_name.is_empty() {
+            validate::validate_device_name(device_name)?;
+        }
+        debug!("D-Bus: set_monitor_device({})", device_name);
+        self.update_monitor(&ctx, |service| service.set_monitor_device(device_name))
+            .await
+    }
+
+    /// Set the monitor bus level in dB.
+    async fn set_monitor_volume(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        volume_db: f64,
+    ) -> zbus::fdo::Result<()> {
+        let volume_db = validate::validate_volume_db(volume_db)?;
+        debug!("D-Bus: set_monitor_volume({})", volume_db);
+        self.update_monitor(&ctx, |service| service.set_monitor_volume(volume_db))
+            .await
+    }
+
+    /// Mute or unmute the monitor bus.
+    async fn set_monitor_mute(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        muted: bool,
+    ) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_monitor_mute({})", muted);
+        self.update_monitor(&ctx, |service| service.set_monitor_mute(muted))
+            .await
+    }
+
+    /// Set how soloed channels are heard ("pfl" or "in-place").
+    async fn set_solo_mode(
+        &self,
+        #[zbus(signal_context)] ctx: zbus::SignalContext<'_>,
+        mode: &str,
+    ) -> zbus::fdo::Result<()> {
+        let mode: SoloMode = mode.parse().map_err(zbus::fdo::Error::InvalidArgs)?;
+        debug!("D-Bus: set_solo_mode({})", mode);
+        self.update_monitor(&ctx, |service| service.set_solo_mode(mode))
+            .await
+    }
+
+    // ==================== LOUDNESS ====================
+
+    /// Restart loudness integration of a channel, of the master output
+    /// ("master"), or of every meter (empty ID).
+    async fn reset_loudness(&self, channel_id: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: reset_loudness({})", channel_id);
         let mut service = self
             .service
             .lock()
             .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
-        service.process_pw_events();
         service
-            .set_master_recording(enabled)
+            .reset_loudness(channel_id)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Analyze the spectrum of a channel or the master output ("master") at
+    /// the "pre" or "post" plugin tap, or stop the analyzer (empty target).
+    async fn set_spectrum(&self, target: &str, tap: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: set_spectrum({}, {})", target, tap);
+        let tap: SpectrumTap = tap.parse().map_err(zbus::fdo::Error::InvalidArgs)?;
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_spectrum(target, tap)
             .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
     }
 
+    // ==================== MIDI ====================
+
+    /// Get the connected MIDI controllers.
+    async fn get_midi_devices(&self) -> zbus::fdo::Result<Vec<String>> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.get_midi_devices())
+    }
+
+    /// Get all MIDI bindings.
+    async fn get_midi_bindings(&self) -> zbus::fdo::Result<Vec<MidiBindingInfo>> {
+        let service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        Ok(service.get_midi_bindings())
+    }
+
+    /// Add or update a MIDI binding (empty ID creates one). Returns its ID.
+    async fn set_midi_binding(&self, binding: MidiBindingInfo) -> zbus::fdo::Result<String> {
+        debug!("D-Bus: set_midi_binding({:?})", binding);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .set_midi_binding(binding)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Delete a MIDI binding.
+    async fn delete_midi_binding(&self, binding_id: &str) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: delete_midi_binding({})", binding_id);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .delete_midi_binding(binding_id)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Bind the next control moved on any controller to the binding's
+    /// target. The result arrives as a `MidiBindingLearned` signal.
+    async fn start_midi_learn(&self, binding: MidiBindingInfo) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: start_midi_learn({:?})", binding);
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service
+            .start_midi_learn(binding)
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Leave MIDI learn mode without binding anything.
+    async fn cancel_midi_learn(&self) -> zbus::fdo::Result<()> {
+        debug!("D-Bus: cancel_midi_learn()");
+        let mut service = self
+            .service
+            .lock()
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        service.cancel_midi_learn();
+        Ok(())
+    }
+
     // ==================== State Getters ====================
 
     /// Get all channels.
@@ -754,6 +1659,18 @@ impl DaemonDbusService {
     #[zbus(signal)]
     async fn meter_update(ctx: &zbus::SignalContext<'_>, data: Vec<MeterData>) -> zbus::Result<()>;
 
+    /// Emitted with loudness readings of every channel and the master output.
+    #[zbus(signal)]
+    async fn loudness_update(
+        ctx: &zbus::SignalContext<'_>,
+        data: Vec<LoudnessData>,
+    ) -> zbus::Result<()>;
+
+    /// Emitted with the spectrum of the analyzed channel while the analyzer runs.
+    #[zbus(signal)]
+    async fn spectrum_update(ctx: &zbus::SignalContext<'_>, data: SpectrumData)
+        -> zbus::Result<()>;
+
     /// Emitted when master volume changes.
     #[zbus(signal)]
     async fn master_volume_changed(
@@ -779,6 +1696,52 @@ impl DaemonDbusService {
         ctx: &zbus::SignalContext<'_>,
         channel: ChannelInfo,
     ) -> zbus::Result<()>;
+
+    /// Emitted about once a second while recording, and when a recording stops.
+    #[zbus(signal)]
+    async fn recording_progress(
+        ctx: &zbus::SignalContext<'_>,
+        status: RecordingStatus,
+    ) -> zbus::Result<()>;
+
+    /// Emitted when MIDI learn binds a control.
+    #[zbus(signal)]
+    async fn midi_binding_learned(
+        ctx: &zbus::SignalContext<'_>,
+        binding: MidiBindingInfo,
+    ) -> zbus::Result<()>;
+
+    /// Emitted when a channel is soloed or unsoloed.
+    #[zbus(signal)]
+    async fn solo_changed(
+        ctx: &zbus::SignalContext<'_>,
+        channel_id: &str,
+        solo: bool,
+    ) -> zbus::Result<()>;
+
+    /// Emitted when noise suppression starts or stops hearing a voice on an
+    /// input channel.
+    #[zbus(signal)]
+    async fn channel_talking(
+        ctx: &zbus::SignalContext<'_>,
+        channel_id: &str,
+        talking: bool,
+    ) -> zbus::Result<()>;
+
+    /// Emitted when a channel's mic goes live or silent (mute or talk key).
+    #[zbus(signal)]
+    async fn channel_live_changed(
+        ctx: &zbus::SignalContext<'_>,
+        channel_id: &str,
+        live: bool,
+    ) -> zbus::Result<()>;
+
+    /// Emitted when a monitor bus setting changes.
+    #[zbus(signal)]
+    async fn monitor_changed(
+        ctx: &zbus::SignalContext<'_>,
+        monitor: MonitorInfo,
+    ) -> zbus::Result<()>;
 }
 
 // ==================== Public Signal Emission Helpers ====================
@@ -861,6 +1824,38 @@ pub async fn emit_meter_update(
         .await
 }
 
+/// Emit LoudnessUpdate signal with loudness readings.
+pub async fn emit_loudness_update(
+    ctx: &zbus::SignalContext<'_>,
+    data: Vec<LoudnessData>,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "LoudnessUpdate",
+            &(data,),
+        )
+        .await
+}
+
+/// Emit SpectrumUpdate signal with the analyzed channel's spectrum.
+pub async fn emit_spectrum_update(
+    ctx: &zbus::SignalContext<'_>,
+    data: SpectrumData,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "SpectrumUpdate",
+            &(data,),
+        )
+        .await
+}
+
 /// Emit MasterVolumeChanged signal (for external volume changes).
 pub async fn emit_master_volume_changed(
     ctx: &zbus::SignalContext<'_>,
@@ -892,3 +1887,136 @@ pub async fn emit_master_mute_changed(
         )
         .await
 }
+
+/// Emit RecordingProgress signal.
+pub async fn emit_recording_progress(
+    ctx: &zbus::SignalContext<'_>,
+    status: RecordingStatus,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "RecordingProgress",
+            &(status,),
+        )
+        .await
+}
+
+/// Emit VolumeChanged signal.
+pub async fn emit_volume_changed(
+    ctx: &zbus::SignalContext<'_>,
+    channel_id: &str,
+    volume_db: f64,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "VolumeChanged",
+            &(channel_id, volume_db),
+        )
+        .await
+}
+
+/// Emit MuteChanged signal.
+pub async fn emit_mute_changed(
+    ctx: &zbus::SignalContext<'_>,
+    channel_id: &str,
+    muted: bool,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "MuteChanged",
+            &(channel_id, muted),
+        )
+        .await
+}
+
+/// Emit MidiBindingLearned signal.
+pub async fn emit_midi_binding_learned(
+    ctx: &zbus::SignalContext<'_>,
+    binding: MidiBindingInfo,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "MidiBindingLearned",
+            &(binding,),
+        )
+        .await
+}
+
+/// Emit ChannelUpdated signal.
+pub async fn emit_channel_updated(
+    ctx: &zbus::SignalContext<'_>,
+    channel: ChannelInfo,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "ChannelUpdated",
+            &(channel,),
+        )
+        .await
+}
+
+/// Emit SoloChanged signal.
+pub async fn emit_solo_changed(
+    ctx: &zbus::SignalContext<'_>,
+    channel_id: &str,
+    solo: bool,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "SoloChanged",
+            &(channel_id, solo),
+        )
+        .await
+}
+
+/// Emit ChannelTalking signal.
+pub async fn emit_channel_talking(
+    ctx: &zbus::SignalContext<'_>,
+    channel_id: &str,
+    talking: bool,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "ChannelTalking",
+            &(channel_id, talking),
+        )
+        .await
+}
+
+/// Emit ChannelLiveChanged signal.
+pub async fn emit_channel_live_changed(
+    ctx: &zbus::SignalContext<'_>,
+    channel_id: &str,
+    live: bool,
+) -> zbus::Result<()> {
+    ctx.connection()
+        .emit_signal(
+            ctx.destination(),
+            ctx.path(),
+            INTERFACE_NAME,
+            "ChannelLiveChanged",
+            &(channel_id, live),
+        )
+        .await
+}
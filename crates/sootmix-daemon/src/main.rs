@@ -10,11 +10,15 @@
 mod audio;
 mod config;
 mod dbus;
+mod midi;
+mod realtime;
+mod recording;
 mod service;
+mod shortcuts;
 
 use dbus::DaemonDbusService;
 use service::SignalEvent;
-use sootmix_ipc::{MeterData, DBUS_NAME, DBUS_PATH};
+use sootmix_ipc::{LoudnessData, MeterData, SpectrumData, DBUS_NAME, DBUS_PATH};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc as tokio_mpsc;
@@ -47,6 +51,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config_manager = config::ConfigManager::new()?;
     let mixer_config = config_manager.load_mixer_config().unwrap_or_default();
     let routing_rules = config_manager.load_routing_rules().unwrap_or_default();
+    match config_manager.migrate_eq_presets() {
+        Ok(0) => {}
+        Ok(count) => info!("Migrated {} EQ presets", count),
+        Err(e) => warn!("Failed to migrate EQ presets: {}", e),
+    }
 
     info!(
         "Loaded config: {} channels, {} routing rules",
@@ -74,7 +83,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     daemon_service.wait_for_discovery();
 
     // Clean up orphaned sootmix nodes from previous runs before restoring channels
-    crate::audio::virtual_sink::cleanup_orphaned_nodes();
+    daemon_service.cleanup_orphaned_nodes();
 
     // Restore channels from config
     if let Err(e) = daemon_service.restore_channels() {
@@ -100,6 +109,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Shutdown flag for graceful termination
     let shutdown_flag = Arc::new(AtomicBool::new(false));
 
+    // Global push-to-talk key, registered once a channel has a talk mode
+    tokio::spawn(shortcuts::run(
+        connection.clone(),
+        service.clone(),
+        shutdown_flag.clone(),
+    ));
+
     // Spawn event processing task
     let service_events = service.clone();
     let shutdown_flag_events = shutdown_flag.clone();
@@ -108,6 +124,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
             if let Ok(mut svc) = service_events.lock() {
                 svc.process_pw_events();
+                svc.collect_plugin_faults();
             }
         }
     });
@@ -116,16 +133,47 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let service_meters = service.clone();
     let shutdown_flag_meters = shutdown_flag.clone();
     let meter_task = tokio::spawn(async move {
+        let mut last_tick = std::time::Instant::now();
+        // Loudness moves in 100 ms blocks, so every third tick is enough
+        let mut loudness_countdown = 0u32;
         while !shutdown_flag_meters.load(Ordering::Relaxed) {
             tokio::time::sleep(tokio::time::Duration::from_millis(33)).await;
+            let now = std::time::Instant::now();
+            let dt = now.duration_since(last_tick).as_secs_f32();
+            last_tick = now;
 
             // Read meter levels from all channels
-            let meter_data: Vec<MeterData> = {
-                let Ok(svc) = service_meters.lock() else {
+            let (meter_data, loudness_data, spectrum_data): (
+                Vec<MeterData>,
+                Vec<LoudnessData>,
+                Option<SpectrumData>,
+            ) = {
+                let Ok(mut svc) = service_meters.lock() else {
                     continue;
                 };
 
-                svc.state
+                // Ducking and auto level are driven by the same meters, at the
+                // same rate, and scene fades, talking changes and talk key
+                // release tails step along with them
+                svc.update_ducking(dt);
+                svc.update_auto_level(dt);
+                svc.update_voice_activity();
+                svc.update_talk(dt);
+                svc.update_fades(dt);
+                svc.update_recordings(dt);
+                svc.update_midi(dt);
+
+                let loudness_data = if loudness_countdown == 0 {
+                    loudness_countdown = 2;
+                    svc.loudness_data()
+                } else {
+                    loudness_countdown -= 1;
+                    Vec::new()
+                };
+                let spectrum_data = svc.spectrum_data(dt);
+
+                let meter_data = svc
+                    .state
                     .channels
                     .iter()
                     .filter_map(|ch| {
@@ -136,15 +184,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             let right_db = linear_to_db(right);
                             // For now, peak = level (could track peak hold separately)
                             MeterData::new(ch.id, left_db, right_db, left_db, right_db)
+                                .with_gain_reduction(ch.duck_reduction_db as f64)
+                                .with_auto_level_gain(ch.auto_level_gain_db as f64)
                         })
                     })
-                    .collect()
+                    .collect();
+                (meter_data, loudness_data, spectrum_data)
             };
 
             // Only send if we have any meter data
             if !meter_data.is_empty() {
                 let _ = meter_signal_tx.send(SignalEvent::MeterUpdate(meter_data));
             }
+            if !loudness_data.is_empty() {
+                let _ = meter_signal_tx.send(SignalEvent::LoudnessUpdate(loudness_data));
+            }
+            if let Some(data) = spectrum_data {
+                let _ = meter_signal_tx.send(SignalEvent::SpectrumUpdate(data));
+            }
         }
     });
 
@@ -189,6 +246,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 warn!("Failed to emit MeterUpdate signal: {}", e);
                             }
                         }
+                        SignalEvent::LoudnessUpdate(data) => {
+                            if let Err(e) = dbus::emit_loudness_update(ctx, data).await {
+                                warn!("Failed to emit LoudnessUpdate signal: {}", e);
+                            }
+                        }
+                        SignalEvent::SpectrumUpdate(data) => {
+                            if let Err(e) = dbus::emit_spectrum_update(ctx, data).await {
+                                warn!("Failed to emit SpectrumUpdate signal: {}", e);
+                            }
+                        }
                         SignalEvent::MasterVolumeChanged(volume_db) => {
                             if let Err(e) = dbus::emit_master_volume_changed(ctx, volume_db).await {
                                 warn!("Failed to emit MasterVolumeChanged signal: {}", e);
@@ -199,6 +266,46 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 warn!("Failed to emit MasterMuteChanged signal: {}", e);
                             }
                         }
+                        SignalEvent::RecordingProgress(status) => {
+                            if let Err(e) = dbus::emit_recording_progress(ctx, status).await {
+                                warn!("Failed to emit RecordingProgress signal: {}", e);
+                            }
+                        }
+                        SignalEvent::ChannelVolumeChanged { channel_id, volume_db } => {
+                            if let Err(e) = dbus::emit_volume_changed(ctx, &channel_id, volume_db).await {
+                                warn!("Failed to emit VolumeChanged signal: {}", e);
+                            }
+                        }
+                        SignalEvent::ChannelMuteChanged { channel_id, muted } => {
+                            if let Err(e) = dbus::emit_mute_changed(ctx, &channel_id, muted).await {
+                                warn!("Failed to emit MuteChanged signal: {}", e);
+                            }
+                        }
+                        SignalEvent::ChannelSoloChanged { channel_id, solo } => {
+                            if let Err(e) = dbus::emit_solo_changed(ctx, &channel_id, solo).await {
+                                warn!("Failed to emit SoloChanged signal: {}", e);
+                            }
+                        }
+                        SignalEvent::ChannelTalking { channel_id, talking } => {
+                            if let Err(e) = dbus::emit_channel_talking(ctx, &channel_id, talking).await {
+                                warn!("Failed to emit ChannelTalking signal: {}", e);
+                            }
+                        }
+                        SignalEvent::ChannelLiveChanged { channel_id, live } => {
+                            if let Err(e) = dbus::emit_channel_live_changed(ctx, &channel_id, live).await {
+                                warn!("Failed to emit ChannelLiveChanged signal: {}", e);
+                            }
+                        }
+                        SignalEvent::ChannelUpdated(channel) => {
+                            if let Err(e) = dbus::emit_channel_updated(ctx, channel).await {
+                                warn!("Failed to emit ChannelUpdated signal: {}", e);
+                            }
+                        }
+                        SignalEvent::MidiBindingLearned(binding) => {
+                            if let Err(e) = dbus::emit_midi_binding_learned(ctx, binding).await {
+                                warn!("Failed to emit MidiBindingLearned signal: {}", e);
+                            }
+                        }
                     }
                 }
                 _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
@@ -0,0 +1,233 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! MIDI control surface support.
+//!
+//! Incoming CC and note messages from hardware controllers drive channel
+//! volume, mute and solo, the master bus and plugin parameters through
+//! bindings stored in `midi.toml`. The service applies queued messages on
+//! its 30 Hz tick and sends the bound value back (motor faders, button
+//! LEDs) whenever it changes, whatever changed it. Messages that no binding
+//! claims are passed on to loaded plugins as MIDI events.
+//!
+//! [`ports`] owns the ALSA sequencer connections (PipeWire's MIDI bridge
+//! shows up there too); this module holds the message decoding and value
+//! scaling shared by input and feedback.
+
+pub mod ports;
+
+use crate::config::MidiControlKind;
+use sootmix_plugin_api::{PluginEvent, PluginEventKind};
+
+/// Fader range mapped onto 0-127, matching the mixer's faders.
+pub const FADER_MIN_DB: f32 = -60.0;
+pub const FADER_MAX_DB: f32 = 12.0;
+
+/// A decoded channel message we can bind to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MidiMessage {
+    /// MIDI channel, 1-16.
+    pub channel: u8,
+    pub kind: MidiControlKind,
+    /// Controller or note number.
+    pub number: u8,
+    /// Controller value or velocity (0 for note off).
+    pub value: u8,
+}
+
+impl MidiMessage {
+    /// Decode a raw message. Anything other than control change, note on
+    /// and note off is ignored.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let [status, number, value, ..] = *bytes else {
+            return None;
+        };
+        let channel = (status & 0x0F) + 1;
+        let (kind, value) = match status & 0xF0 {
+            0xB0 => (MidiControlKind::Cc, value),
+            0x90 => (MidiControlKind::Note, value),
+            0x80 => (MidiControlKind::Note, 0),
+            _ => return None,
+        };
+        Some(Self {
+            channel,
+            kind,
+            number: number & 0x7F,
+            value: value & 0x7F,
+        })
+    }
+
+    /// Encode a feedback message for a control.
+    pub fn encode(channel: u8, kind: MidiControlKind, number: u8, value: u8) -> [u8; 3] {
+        let status = match kind {
+            MidiControlKind::Cc => 0xB0,
+            // Note on with velocity 0 turns LEDs off on every surface we know of
+            MidiControlKind::Note => 0x90,
+        };
+        [
+            status | (channel.clamp(1, 16) - 1),
+            number & 0x7F,
+            value.min(127),
+        ]
+    }
+
+    /// Whether this is a button press (as opposed to a release).
+    pub fn is_press(&self) -> bool {
+        self.value > 0
+    }
+
+    /// The message as a plugin event at the start of the next block.
+    pub fn to_plugin_event(&self) -> PluginEvent {
+        let channel = self.channel.saturating_sub(1);
+        let kind = match self.kind {
+            MidiControlKind::Cc => PluginEventKind::ControlChange {
+                channel,
+                controller: self.number,
+                value: self.value,
+            },
+            MidiControlKind::Note if self.is_press() => PluginEventKind::NoteOn {
+                channel,
+                note: self.number,
+                velocity: self.value,
+            },
+            MidiControlKind::Note => PluginEventKind::NoteOff {
+                channel,
+                note: self.number,
+                velocity: 0,
+            },
+        };
+        PluginEvent {
+            sample_offset: 0,
+            kind,
+        }
+    }
+}
+
+/// Map a 0-127 controller value to a fader position in dB.
+pub fn value_to_db(value: u8) -> f32 {
+    FADER_MIN_DB + value.min(127) as f32 / 127.0 * (FADER_MAX_DB - FADER_MIN_DB)
+}
+
+/// Map a fader position in dB to a 0-127 controller value.
+pub fn db_to_value(db: f32) -> u8 {
+    let normalized = (db - FADER_MIN_DB) / (FADER_MAX_DB - FADER_MIN_DB);
+    (normalized.clamp(0.0, 1.0) * 127.0).round() as u8
+}
+
+/// Map a 0-127 controller value to 0.0-1.0.
+pub fn value_to_normalized(value: u8) -> f32 {
+    value.min(127) as f32 / 127.0
+}
+
+/// Map 0.0-1.0 to a 0-127 controller value.
+pub fn normalized_to_value(normalized: f32) -> u8 {
+    (normalized.clamp(0.0, 1.0) * 127.0).round() as u8
+}
+
+/// Device name of a sequencer port: the port name without its trailing
+/// `client:port` address, which changes when the device is replugged.
+pub fn device_name(port_name: &str) -> &str {
+    let Some((name, address)) = port_name.trim_end().rsplit_once(' ') else {
+        return port_name.trim_end();
+    };
+    let is_address = address.split_once(':').is_some_and(|(client, port)| {
+        !client.is_empty()
+            && !port.is_empty()
+            && client.chars().all(|c| c.is_ascii_digit())
+            && port.chars().all(|c| c.is_ascii_digit())
+    });
+    if is_address {
+        name
+    } else {
+        port_name.trim_end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_channel_messages() {
+        assert_eq!(
+            MidiMessage::parse(&[0xB3, 7, 100]),
+            Some(MidiMessage {
+                channel: 4,
+                kind: MidiControlKind::Cc,
+                number: 7,
+                value: 100,
+            })
+        );
+        let note_off = MidiMessage::parse(&[0x80, 60, 64]).unwrap();
+        assert_eq!(note_off.kind, MidiControlKind::Note);
+        assert!(!note_off.is_press());
+        assert!(MidiMessage::parse(&[0x90, 60, 1]).unwrap().is_press());
+        // Pitch bend, program change and truncated messages are ignored
+        assert_eq!(MidiMessage::parse(&[0xE0, 0, 64]), None);
+        assert_eq!(MidiMessage::parse(&[0xC0, 5]), None);
+        assert_eq!(MidiMessage::parse(&[0xB0]), None);
+    }
+
+    #[test]
+    fn test_encode_feedback() {
+        assert_eq!(
+            MidiMessage::encode(1, MidiControlKind::Cc, 7, 127),
+            [0xB0, 7, 127]
+        );
+        assert_eq!(
+            MidiMessage::encode(16, MidiControlKind::Note, 36, 0),
+            [0x9F, 36, 0]
+        );
+    }
+
+    #[test]
+    fn test_plugin_event_conversion() {
+        let cc = MidiMessage::parse(&[0xB2, 74, 64])
+            .unwrap()
+            .to_plugin_event();
+        assert_eq!(
+            cc.kind,
+            PluginEventKind::ControlChange {
+                channel: 2,
+                controller: 74,
+                value: 64,
+            }
+        );
+        let off = MidiMessage::parse(&[0x80, 60, 64])
+            .unwrap()
+            .to_plugin_event();
+        assert!(matches!(
+            off.kind,
+            PluginEventKind::NoteOff { note: 60, .. }
+        ));
+        let on = MidiMessage::parse(&[0x90, 60, 100])
+            .unwrap()
+            .to_plugin_event();
+        assert!(matches!(
+            on.kind,
+            PluginEventKind::NoteOn { velocity: 100, .. }
+        ));
+    }
+
+    #[test]
+    fn test_fader_scaling_round_trips() {
+        assert_eq!(value_to_db(0), FADER_MIN_DB);
+        assert_eq!(value_to_db(127), FADER_MAX_DB);
+        for value in 0..=127 {
+            assert_eq!(db_to_value(value_to_db(value)), value);
+        }
+        assert_eq!(db_to_value(-100.0), 0);
+        assert_eq!(db_to_value(20.0), 127);
+    }
+
+    #[test]
+    fn test_device_name_strips_address() {
+        assert_eq!(
+            device_name("X-TOUCH MINI:X-TOUCH MINI MIDI 1 24:0"),
+            "X-TOUCH MINI:X-TOUCH MINI MIDI 1"
+        );
+        assert_eq!(device_name("nanoKONTROL2"), "nanoKONTROL2");
+        assert_eq!(device_name("Some Port 2"), "Some Port 2");
+    }
+}
@@ -0,0 +1,172 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! ALSA sequencer connections to MIDI controllers.
+//!
+//! Every external input port is listened to and every external output port
+//! is opened for feedback. Ports are matched to bindings by device name, so
+//! a controller keeps its bindings when it is replugged.
+
+use super::{device_name, MidiMessage};
+use midir::{Ignore, MidiInput, MidiInputConnection, MidiOutput, MidiOutputConnection};
+use std::collections::HashMap;
+use std::sync::mpsc;
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+/// Sequencer client name of the daemon's own ports.
+const CLIENT_NAME: &str = "SootMix";
+
+#[derive(Debug, Error)]
+pub enum MidiError {
+    #[error("Failed to open ALSA sequencer: {0}")]
+    Init(#[from] midir::InitError),
+}
+
+/// A message received from a controller.
+#[derive(Debug, Clone)]
+pub struct MidiEvent {
+    /// Device name of the port it arrived on (see [`device_name`]).
+    pub device: String,
+    pub message: MidiMessage,
+}
+
+/// Ports that belong to a controller rather than to us or the kernel's
+/// loopback client.
+fn is_external(port_name: &str) -> bool {
+    !port_name.starts_with(CLIENT_NAME) && !port_name.contains("Midi Through")
+}
+
+/// Open connections to every MIDI controller.
+pub struct MidiPorts {
+    /// Used only to enumerate ports; connecting consumes a client.
+    probe_in: MidiInput,
+    probe_out: MidiOutput,
+    /// Input connections by port name.
+    inputs: HashMap<String, MidiInputConnection<()>>,
+    /// Feedback connections by port name.
+    outputs: HashMap<String, MidiOutputConnection>,
+    event_tx: mpsc::Sender<MidiEvent>,
+    event_rx: mpsc::Receiver<MidiEvent>,
+}
+
+impl MidiPorts {
+    pub fn new() -> Result<Self, MidiError> {
+        let (event_tx, event_rx) = mpsc::channel();
+        Ok(Self {
+            probe_in: MidiInput::new(CLIENT_NAME)?,
+            probe_out: MidiOutput::new(CLIENT_NAME)?,
+            inputs: HashMap::new(),
+            outputs: HashMap::new(),
+            event_tx,
+            event_rx,
+        })
+    }
+
+    /// Connect to ports that appeared and drop the ones that went away.
+    pub fn refresh(&mut self) {
+        let input_ports: Vec<_> = self
+            .probe_in
+            .ports()
+            .into_iter()
+            .filter_map(|p| Some((self.probe_in.port_name(&p).ok()?, p)))
+            .filter(|(name, _)| is_external(name))
+            .collect();
+        self.inputs
+            .retain(|name, _| input_ports.iter().any(|(n, _)| n == name));
+
+        for (name, port) in input_ports {
+            if self.inputs.contains_key(&name) {
+                continue;
+            }
+            let mut input = match MidiInput::new(CLIENT_NAME) {
+                Ok(input) => input,
+                Err(e) => {
+                    warn!("Failed to create MIDI input client: {}", e);
+                    return;
+                }
+            };
+            // Sysex, clock and active sensing are never bound
+            input.ignore(Ignore::All);
+
+            let tx = self.event_tx.clone();
+            let device = device_name(&name).to_string();
+            let callback = move |_stamp: u64, bytes: &[u8], _: &mut ()| {
+                if let Some(message) = MidiMessage::parse(bytes) {
+                    let _ = tx.send(MidiEvent {
+                        device: device.clone(),
+                        message,
+                    });
+                }
+            };
+            match input.connect(&port, "sootmix-in", callback, ()) {
+                Ok(connection) => {
+                    info!("Listening to MIDI port {}", name);
+                    self.inputs.insert(name, connection);
+                }
+                Err(e) => warn!("Failed to connect MIDI port {}: {}", name, e),
+            }
+        }
+
+        let output_ports: Vec<_> = self
+            .probe_out
+            .ports()
+            .into_iter()
+            .filter_map(|p| Some((self.probe_out.port_name(&p).ok()?, p)))
+            .filter(|(name, _)| is_external(name))
+            .collect();
+        self.outputs
+            .retain(|name, _| output_ports.iter().any(|(n, _)| n == name));
+
+        for (name, port) in output_ports {
+            if self.outputs.contains_key(&name) {
+                continue;
+            }
+            let output = match MidiOutput::new(CLIENT_NAME) {
+                Ok(output) => output,
+                Err(e) => {
+                    warn!("Failed to create MIDI output client: {}", e);
+                    return;
+                }
+            };
+            match output.connect(&port, "sootmix-feedback") {
+                Ok(connection) => {
+                    debug!("Opened MIDI feedback port {}", name);
+                    self.outputs.insert(name, connection);
+                }
+                Err(e) => warn!("Failed to open MIDI feedback port {}: {}", name, e),
+            }
+        }
+    }
+
+    /// Device names of the connected controllers, sorted.
+    pub fn devices(&self) -> Vec<String> {
+        let mut devices: Vec<String> = self
+            .inputs
+            .keys()
+            .map(|name| device_name(name).to_string())
+            .collect();
+        devices.sort();
+        devices.dedup();
+        devices
+    }
+
+    /// Messages received since the last call, oldest first.
+    pub fn poll(&self) -> Vec<MidiEvent> {
+        self.event_rx.try_iter().collect()
+    }
+
+    /// Send a message to `device`'s feedback ports, or to every controller
+    /// if `device` is `None`.
+    pub fn send(&mut self, device: Option<&str>, bytes: &[u8]) {
+        for (name, output) in &mut self.outputs {
+            if device.is_some_and(|d| d != device_name(name)) {
+                continue;
+            }
+            if let Err(e) = output.send(bytes) {
+                debug!("Failed to send MIDI feedback to {}: {}", name, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Lock-free single-slot mailbox.
+//!
+//! Hands a boxed value to another thread by swapping a pointer. The sender
+//! can take back a value the receiver hasn't picked up yet and merge into it,
+//! so changes made faster than an RT callback runs collapse into one. Boxes
+//! are allocated and freed by the caller; the mailbox only moves them.
+
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// A slot holding at most one boxed value.
+pub struct Mailbox<T> {
+    slot: AtomicPtr<T>,
+}
+
+// SAFETY: The slot owns its value, and ownership moves whole between threads
+// through atomic swaps, so no two threads ever reach the value at once.
+unsafe impl<T: Send> Send for Mailbox<T> {}
+unsafe impl<T: Send> Sync for Mailbox<T> {}
+
+impl<T> Mailbox<T> {
+    /// Create an empty mailbox.
+    pub fn new() -> Self {
+        Self {
+            slot: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Take the value, leaving the slot empty.
+    pub fn take(&self) -> Option<Box<T>> {
+        let value = self.slot.swap(ptr::null_mut(), Ordering::AcqRel);
+        // SAFETY: Non-null pointers in the slot come from `Box::into_raw`,
+        // and the swap took them out, so this is the only owner.
+        (!value.is_null()).then(|| unsafe { Box::from_raw(value) })
+    }
+
+    /// Put a value in the slot, returning the one it displaces.
+    pub fn replace(&self, value: Box<T>) -> Option<Box<T>> {
+        let old = self.slot.swap(Box::into_raw(value), Ordering::AcqRel);
+        // SAFETY: As in `take`.
+        (!old.is_null()).then(|| unsafe { Box::from_raw(old) })
+    }
+}
+
+impl<T> Default for Mailbox<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for Mailbox<T> {
+    fn drop(&mut self) {
+        drop(self.take());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_take_empties_slot() {
+        let mailbox = Mailbox::new();
+        assert!(mailbox.take().is_none());
+
+        assert!(mailbox.replace(Box::new(1)).is_none());
+        assert_eq!(mailbox.take().as_deref(), Some(&1));
+        assert!(mailbox.take().is_none());
+    }
+
+    #[test]
+    fn test_replace_returns_displaced() {
+        let mailbox = Mailbox::new();
+        mailbox.replace(Box::new(1));
+        assert_eq!(mailbox.replace(Box::new(2)).as_deref(), Some(&1));
+        assert_eq!(mailbox.take().as_deref(), Some(&2));
+    }
+
+    #[test]
+    fn test_drop_frees_value() {
+        let value = Arc::new(());
+        let mailbox = Mailbox::new();
+        mailbox.replace(Box::new(Arc::clone(&value)));
+        drop(mailbox);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+}
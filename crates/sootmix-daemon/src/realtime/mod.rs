@@ -0,0 +1,14 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Real-time safe utilities for audio processing.
+//!
+//! Lock-free data structures for handing data between PipeWire's RT
+//! callbacks and other daemon threads. Nothing here allocates, locks or
+//! blocks on the RT side.
+
+pub mod mailbox;
+
+pub use mailbox::Mailbox;
+pub use sootmix_realtime::{RingBuffer, RingBufferReader, RingBufferWriter};
@@ -0,0 +1,573 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Minimal streaming FLAC encoder for recordings.
+//!
+//! Writes fixed-blocksize frames using FLAC's fixed linear predictors
+//! (orders 0-4, the cheapest picked per subframe), constant subframes for
+//! digital silence and partitioned Rice coding of the residual with 5-bit
+//! parameters, which 24-bit residuals need. Only one
+//! block per channel is ever buffered, so hour-long recordings stream
+//! straight to disk. STREAMINFO is patched with the final sample count and
+//! frame sizes on [`FlacWriter::finish`]; the MD5 signature is left zero
+//! ("not computed"), which the format allows.
+
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// Samples per channel per frame.
+pub const BLOCK_SIZE: usize = 4096;
+
+/// Bits per sample written to the file.
+pub const BITS_PER_SAMPLE: u32 = 24;
+
+/// Largest Rice parameter expressible with 5-bit parameters (31 is the escape code).
+const MAX_RICE_PARAM: u32 = 30;
+
+/// Bits per Rice parameter in a partition header.
+const RICE_PARAM_BITS: u32 = 5;
+
+/// Largest partition order tried for residual coding.
+const MAX_PARTITION_ORDER: u32 = 8;
+
+/// Byte offset of the STREAMINFO block body ("fLaC" + 4-byte block header).
+const STREAMINFO_OFFSET: u64 = 8;
+
+/// Streaming FLAC encoder for interleaved `f32` input.
+pub struct FlacWriter<W: Write + Seek> {
+    out: W,
+    channels: usize,
+    sample_rate: u32,
+    /// One block of pending samples per channel.
+    pending: Vec<Vec<i32>>,
+    frame_number: u64,
+    total_samples: u64,
+    min_frame_size: u32,
+    max_frame_size: u32,
+    bytes_written: u64,
+}
+
+impl<W: Write + Seek> FlacWriter<W> {
+    /// Start a FLAC stream, writing the signature and a placeholder STREAMINFO.
+    pub fn new(mut out: W, channels: usize, sample_rate: u32) -> io::Result<Self> {
+        if !(1..=8).contains(&channels) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "FLAC supports 1 to 8 channels",
+            ));
+        }
+
+        out.write_all(b"fLaC")?;
+        // Last-metadata-block flag + type 0 (STREAMINFO), 34-byte body
+        out.write_all(&[0x80, 0x00, 0x00, 34])?;
+        out.write_all(&[0u8; 34])?;
+
+        let mut writer = Self {
+            out,
+            channels,
+            sample_rate,
+            pending: vec![Vec::with_capacity(BLOCK_SIZE); channels],
+            frame_number: 0,
+            total_samples: 0,
+            min_frame_size: u32::MAX,
+            max_frame_size: 0,
+            bytes_written: STREAMINFO_OFFSET + 34,
+        };
+        writer.write_streaminfo()?;
+        Ok(writer)
+    }
+
+    /// Bytes written so far (including the header).
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Append interleaved samples in the range -1.0..=1.0.
+    pub fn write_interleaved(&mut self, samples: &[f32]) -> io::Result<()> {
+        for frame in samples.chunks_exact(self.channels) {
+            for (channel, &sample) in self.pending.iter_mut().zip(frame) {
+                channel.push(f32_to_i24(sample));
+            }
+            if self.pending[0].len() == BLOCK_SIZE {
+                self.flush_block()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Encode any partial block, patch STREAMINFO and return the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.pending[0].is_empty() {
+            self.flush_block()?;
+        }
+        self.write_streaminfo()?;
+        self.out.seek(SeekFrom::End(0))?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn flush_block(&mut self) -> io::Result<()> {
+        let frame = encode_frame(&self.pending, self.frame_number, self.sample_rate);
+        self.out.write_all(&frame)?;
+
+        let size = frame.len() as u32;
+        self.min_frame_size = self.min_frame_size.min(size);
+        self.max_frame_size = self.max_frame_size.max(size);
+        self.bytes_written += frame.len() as u64;
+        self.total_samples += self.pending[0].len() as u64;
+        self.frame_number += 1;
+        for channel in &mut self.pending {
+            channel.clear();
+        }
+        Ok(())
+    }
+
+    fn write_streaminfo(&mut self) -> io::Result<()> {
+        let mut bits = BitWriter::new();
+        bits.write(BLOCK_SIZE as u64, 16); // min block size
+        bits.write(BLOCK_SIZE as u64, 16); // max block size
+        let min_frame = if self.min_frame_size == u32::MAX {
+            0
+        } else {
+            self.min_frame_size
+        };
+        bits.write(min_frame as u64, 24);
+        bits.write(self.max_frame_size as u64, 24);
+        bits.write(self.sample_rate as u64, 20);
+        bits.write(self.channels as u64 - 1, 3);
+        bits.write(BITS_PER_SAMPLE as u64 - 1, 5);
+        bits.write(self.total_samples, 36);
+        for _ in 0..16 {
+            bits.write(0, 8); // MD5 not computed
+        }
+
+        let end = self.out.stream_position()?;
+        self.out.seek(SeekFrom::Start(STREAMINFO_OFFSET))?;
+        self.out.write_all(&bits.into_bytes())?;
+        self.out.seek(SeekFrom::Start(end))?;
+        Ok(())
+    }
+}
+
+/// Convert a float sample to a signed 24-bit integer, clipping out-of-range input.
+fn f32_to_i24(sample: f32) -> i32 {
+    const MAX: f32 = ((1 << (BITS_PER_SAMPLE - 1)) - 1) as f32;
+    if sample.is_nan() {
+        return 0;
+    }
+    (sample.clamp(-1.0, 1.0) * MAX).round() as i32
+}
+
+/// Frame header sample rate code. Common rates are spelled out because some
+/// decoders don't fall back to STREAMINFO; others use code 0 (STREAMINFO).
+fn sample_rate_code(sample_rate: u32) -> u64 {
+    match sample_rate {
+        88_200 => 0b0001,
+        176_400 => 0b0010,
+        192_000 => 0b0011,
+        8_000 => 0b0100,
+        16_000 => 0b0101,
+        22_050 => 0b0110,
+        24_000 => 0b0111,
+        32_000 => 0b1000,
+        44_100 => 0b1001,
+        48_000 => 0b1010,
+        96_000 => 0b1011,
+        _ => 0b0000,
+    }
+}
+
+/// Encode one frame holding a block of samples per channel.
+fn encode_frame(channels: &[Vec<i32>], frame_number: u64, sample_rate: u32) -> Vec<u8> {
+    let block_size = channels[0].len();
+    let mut bits = BitWriter::new();
+
+    // Frame header
+    bits.write(0b11_1111_1111_1110, 14); // sync code
+    bits.write(0, 1); // reserved
+    bits.write(0, 1); // fixed-blocksize stream
+    bits.write(0b0111, 4); // block size: 16-bit (n - 1) at end of header
+    bits.write(sample_rate_code(sample_rate), 4);
+    bits.write(channels.len() as u64 - 1, 4); // independent channels
+    bits.write(0b110, 3); // sample size: 24 bits
+    bits.write(0, 1); // reserved
+    for byte in utf8_frame_number(frame_number) {
+        bits.write(byte as u64, 8);
+    }
+    bits.write(block_size as u64 - 1, 16);
+    let crc = crc8(bits.bytes());
+    bits.write(crc as u64, 8);
+
+    for samples in channels {
+        encode_subframe(&mut bits, samples);
+    }
+
+    bits.align();
+    let crc = crc16(bits.bytes());
+    bits.write(crc as u64, 16);
+    bits.into_bytes()
+}
+
+/// Encode one channel's subframe, choosing the cheapest representation.
+fn encode_subframe(bits: &mut BitWriter, samples: &[i32]) {
+    let bps = BITS_PER_SAMPLE;
+
+    if samples.iter().all(|&s| s == samples[0]) {
+        bits.write(0, 1); // padding
+        bits.write(0b000000, 6); // CONSTANT
+        bits.write(0, 1); // no wasted bits
+        bits.write_signed(samples[0] as i64, bps);
+        return;
+    }
+
+    let verbatim_bits = samples.len() as u64 * bps as u64;
+    let best = (0..=4usize)
+        .filter(|&order| order < samples.len())
+        .map(|order| {
+            let residual = fixed_residual(samples, order);
+            let (partition_order, params, cost) =
+                best_partitioning(&residual, order, samples.len());
+            let cost = cost + order as u64 * bps as u64;
+            (order, residual, partition_order, params, cost)
+        })
+        .min_by_key(|(.., cost)| *cost);
+
+    match best {
+        Some((order, residual, partition_order, params, cost)) if cost < verbatim_bits => {
+            bits.write(0, 1);
+            bits.write(0b001000 | order as u64, 6); // FIXED, predictor order
+            bits.write(0, 1);
+            for &warmup in &samples[..order] {
+                bits.write_signed(warmup as i64, bps);
+            }
+            bits.write(0b01, 2); // Rice coding, 5-bit parameters
+            bits.write(partition_order as u64, 4);
+            let mut start = 0;
+            for (partition, &param) in params.iter().enumerate() {
+                let len = partition_len(samples.len(), partition_order, order, partition);
+                bits.write(param as u64, RICE_PARAM_BITS);
+                for &r in &residual[start..start + len] {
+                    bits.write_rice(zigzag(r), param);
+                }
+                start += len;
+            }
+        }
+        _ => {
+            bits.write(0, 1);
+            bits.write(0b000001, 6); // VERBATIM
+            bits.write(0, 1);
+            for &s in samples {
+                bits.write_signed(s as i64, bps);
+            }
+        }
+    }
+}
+
+/// Residual of FLAC's fixed predictor of the given order (excluding warm-up samples).
+fn fixed_residual(samples: &[i32], order: usize) -> Vec<i64> {
+    let s = |i: usize| samples[i] as i64;
+    (order..samples.len())
+        .map(|i| match order {
+            0 => s(i),
+            1 => s(i) - s(i - 1),
+            2 => s(i) - 2 * s(i - 1) + s(i - 2),
+            3 => s(i) - 3 * s(i - 1) + 3 * s(i - 2) - s(i - 3),
+            _ => s(i) - 4 * s(i - 1) + 6 * s(i - 2) - 4 * s(i - 3) + s(i - 4),
+        })
+        .collect()
+}
+
+/// Number of residual samples in a partition. The first partition is
+/// shortened by the predictor's warm-up samples.
+fn partition_len(
+    block_size: usize,
+    partition_order: u32,
+    predictor_order: usize,
+    partition: usize,
+) -> usize {
+    let len = block_size >> partition_order;
+    if partition == 0 {
+        len - predictor_order
+    } else {
+        len
+    }
+}
+
+/// Pick the partition order and per-partition Rice parameters with the
+/// lowest total bit cost. Returns `(partition_order, params, bits)`.
+fn best_partitioning(
+    residual: &[i64],
+    predictor_order: usize,
+    block_size: usize,
+) -> (u32, Vec<u32>, u64) {
+    let mut best: Option<(u32, Vec<u32>, u64)> = None;
+
+    for partition_order in 0..=MAX_PARTITION_ORDER {
+        let partitions = 1usize << partition_order;
+        // Partitions must divide the block evenly and hold the warm-up samples
+        if !block_size.is_multiple_of(partitions)
+            || (block_size >> partition_order) <= predictor_order
+        {
+            break;
+        }
+
+        let mut params = Vec::with_capacity(partitions);
+        let mut cost = 6u64; // coding method + partition order
+        let mut start = 0;
+        for partition in 0..partitions {
+            let len = partition_len(block_size, partition_order, predictor_order, partition);
+            let (param, bits) = best_rice_param(&residual[start..start + len]);
+            params.push(param);
+            cost += RICE_PARAM_BITS as u64 + bits;
+            start += len;
+        }
+
+        if best
+            .as_ref()
+            .is_none_or(|(_, _, best_cost)| cost < *best_cost)
+        {
+            best = Some((partition_order, params, cost));
+        }
+    }
+
+    best.unwrap_or((0, vec![0], u64::MAX))
+}
+
+/// Cheapest Rice parameter for a run of residuals and its bit cost.
+fn best_rice_param(residual: &[i64]) -> (u32, u64) {
+    (0..=MAX_RICE_PARAM)
+        .map(|k| {
+            let bits: u64 = residual
+                .iter()
+                .map(|&r| (zigzag(r) >> k) + 1 + k as u64)
+                .sum();
+            (k, bits)
+        })
+        .min_by_key(|&(_, bits)| bits)
+        .unwrap_or((0, 0))
+}
+
+/// Fold a signed residual into an unsigned value (0, -1, 1, -2, ... -> 0, 1, 2, 3, ...).
+fn zigzag(r: i64) -> u64 {
+    ((r << 1) ^ (r >> 63)) as u64
+}
+
+/// FLAC's UTF-8-style variable-length coding of the frame number.
+fn utf8_frame_number(n: u64) -> Vec<u8> {
+    if n < 0x80 {
+        return vec![n as u8];
+    }
+    // (continuation bytes, leading marker) for 2..=7 byte encodings
+    let (extra, marker) = match n {
+        0..=0x7FF => (1, 0xC0u8),
+        0x800..=0xFFFF => (2, 0xE0),
+        0x1_0000..=0x1F_FFFF => (3, 0xF0),
+        0x20_0000..=0x3FF_FFFF => (4, 0xF8),
+        0x400_0000..=0x7FFF_FFFF => (5, 0xFC),
+        _ => (6, 0xFE),
+    };
+    let mut bytes = vec![marker | (n >> (6 * extra)) as u8];
+    for i in (0..extra).rev() {
+        bytes.push(0x80 | ((n >> (6 * i)) & 0x3F) as u8);
+    }
+    bytes
+}
+
+/// CRC-8, polynomial x^8 + x^2 + x + 1, initial value 0 (frame header).
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |mut crc, &byte| {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// CRC-16, polynomial x^16 + x^15 + x^2 + 1, initial value 0 (whole frame).
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0u16, |mut crc, &byte| {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x8005
+            } else {
+                crc << 1
+            };
+        }
+        crc
+    })
+}
+
+/// MSB-first bit writer.
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    acc_bits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            acc: 0,
+            acc_bits: 0,
+        }
+    }
+
+    /// Write the low `n` bits of `value` (n <= 57).
+    fn write(&mut self, value: u64, n: u32) {
+        debug_assert!(n <= 57);
+        if n == 0 {
+            return;
+        }
+        self.acc = (self.acc << n) | (value & ((1u64 << n) - 1));
+        self.acc_bits += n;
+        while self.acc_bits >= 8 {
+            self.acc_bits -= 8;
+            self.bytes.push((self.acc >> self.acc_bits) as u8);
+        }
+        self.acc &= (1u64 << self.acc_bits) - 1;
+    }
+
+    /// Write a two's-complement signed value in `n` bits.
+    fn write_signed(&mut self, value: i64, n: u32) {
+        self.write(value as u64, n);
+    }
+
+    /// Write `value` Rice-coded with parameter `k`: quotient in unary
+    /// (zeros terminated by a one), then the `k` low bits.
+    fn write_rice(&mut self, value: u64, k: u32) {
+        let mut quotient = value >> k;
+        while quotient >= 32 {
+            self.write(0, 32);
+            quotient -= 32;
+        }
+        self.write(1, quotient as u32 + 1);
+        self.write(value, k);
+    }
+
+    /// Pad with zero bits to a byte boundary.
+    fn align(&mut self) {
+        if self.acc_bits > 0 {
+            self.write(0, 8 - self.acc_bits);
+        }
+    }
+
+    /// Complete bytes written so far.
+    fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn into_bytes(mut self) -> Vec<u8> {
+        self.align();
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Decode with claxon, checking the frame CRCs and STREAMINFO along the way.
+    fn decode(data: &[u8]) -> (u32, usize, Vec<Vec<i32>>) {
+        let mut reader = claxon::FlacReader::new(Cursor::new(data)).unwrap();
+        let info = reader.streaminfo();
+        assert_eq!(info.bits_per_sample, BITS_PER_SAMPLE);
+        let channels = info.channels as usize;
+
+        let mut out = vec![Vec::new(); channels];
+        let samples: Vec<i32> = reader.samples().map(|s| s.unwrap()).collect();
+        for frame in samples.chunks_exact(channels) {
+            for (channel, &sample) in out.iter_mut().zip(frame) {
+                channel.push(sample);
+            }
+        }
+        assert_eq!(info.samples, Some(out[0].len() as u64));
+        (info.sample_rate, channels, out)
+    }
+
+    fn encode(samples: &[f32], channels: usize) -> Vec<u8> {
+        let mut writer = FlacWriter::new(Cursor::new(Vec::new()), channels, 48000).unwrap();
+        writer.write_interleaved(samples).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_crc_check_values() {
+        assert_eq!(crc8(b"123456789"), 0xF4);
+        assert_eq!(crc16(b"123456789"), 0xFEE8);
+    }
+
+    #[test]
+    fn test_utf8_frame_number() {
+        assert_eq!(utf8_frame_number(0x7F), [0x7F]);
+        assert_eq!(utf8_frame_number(0x80), [0xC2, 0x80]);
+        assert_eq!(utf8_frame_number(0x800), [0xE0, 0xA0, 0x80]);
+    }
+
+    #[test]
+    fn test_round_trip_stereo() {
+        // Sine on the left, noise-ish ramp on the right, spanning several
+        // blocks plus a partial one
+        let frames = BLOCK_SIZE * 2 + 1000;
+        let mut samples = Vec::with_capacity(frames * 2);
+        for i in 0..frames {
+            samples.push((i as f32 * 0.01).sin() * 0.5);
+            samples.push(((i * 7919) % 2000) as f32 / 1000.0 - 1.0);
+        }
+
+        let data = encode(&samples, 2);
+        let (rate, channels, decoded) = decode(&data);
+
+        assert_eq!(rate, 48000);
+        assert_eq!(channels, 2);
+        assert_eq!(decoded[0].len(), frames);
+        for i in 0..frames {
+            assert_eq!(decoded[0][i], f32_to_i24(samples[i * 2]));
+            assert_eq!(decoded[1][i], f32_to_i24(samples[i * 2 + 1]));
+        }
+        // The smooth sine channel should compress well below verbatim size
+        assert!(data.len() < frames * 2 * 3);
+    }
+
+    #[test]
+    fn test_noise_needs_large_rice_params() {
+        // Noise around -26 dBFS leaves residuals near 2^19, which only Rice
+        // parameters above 14 code more tightly than verbatim samples
+        let mut seed = 1u32;
+        let samples: Vec<f32> = (0..BLOCK_SIZE * 2)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 8) as f32 / (1 << 24) as f32 * 0.1 - 0.05
+            })
+            .collect();
+
+        let data = encode(&samples, 1);
+        let (_, _, decoded) = decode(&data);
+        let expected: Vec<i32> = samples.iter().map(|&s| f32_to_i24(s)).collect();
+        assert_eq!(decoded[0], expected);
+        assert!(data.len() < samples.len() * 3);
+    }
+
+    #[test]
+    fn test_silence_uses_constant_subframes() {
+        let data = encode(&vec![0.0; BLOCK_SIZE * 2], 2);
+        // Header + one ~20-byte frame
+        assert!(data.len() < 42 + 32);
+        let (_, _, decoded) = decode(&data);
+        assert!(decoded[0].iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_clipping_and_nan() {
+        assert_eq!(f32_to_i24(2.0), 8_388_607);
+        assert_eq!(f32_to_i24(-2.0), -8_388_607);
+        assert_eq!(f32_to_i24(f32::NAN), 0);
+    }
+}
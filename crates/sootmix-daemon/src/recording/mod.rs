@@ -0,0 +1,585 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Recording channels and the master bus to disk.
+//!
+//! A recording captures all of its sources through a single multichannel
+//! PipeWire stream (two stream channels per source, see
+//! [`crate::audio::record_stream`]), so every track shares one clock and the
+//! files stay sample-aligned. The stream's RT callback hands interleaved
+//! blocks to a [`CaptureBuffer`]; a writer thread drains it, splits the
+//! blocks into one stereo file per source and keeps running totals for
+//! progress reporting.
+
+pub mod flac;
+pub mod replay;
+
+use flac::FlacWriter;
+use parking_lot::Mutex;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+/// Channels per recorded track (every file is stereo).
+pub const TRACK_CHANNELS: usize = 2;
+
+/// Sample rate assumed if the capture stream never reported one.
+const FALLBACK_SAMPLE_RATE: u32 = 48000;
+
+/// Capture buffer size in frames (per stream channel). Covers ~2.7 s at
+/// 48 kHz, far more than one writer interval.
+const CAPTURE_CAPACITY_FRAMES: usize = 131072;
+
+/// How often the writer thread drains the capture buffer.
+const WRITER_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Error)]
+pub enum RecordingError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("WAV error: {0}")]
+    Wav(#[from] hound::Error),
+    #[error("Writer thread panicked")]
+    WriterPanicked,
+}
+
+/// Output file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingFormat {
+    /// 32-bit float WAV.
+    Wav,
+    /// 24-bit FLAC.
+    Flac,
+}
+
+impl RecordingFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "wav" => Some(Self::Wav),
+            "flac" => Some(Self::Flac),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Flac => "flac",
+        }
+    }
+}
+
+// ============================================================================
+// CAPTURE BUFFER
+// ============================================================================
+
+/// Hand-off between the capture stream's RT callback and the writer thread.
+pub struct CaptureBuffer {
+    /// Interleaved samples, `channels` per frame. Never grown past its
+    /// initial capacity so the RT side doesn't allocate.
+    samples: Mutex<Vec<f32>>,
+    /// Interleaved channels per frame.
+    channels: usize,
+    /// Negotiated sample rate (0 until the stream's format is known).
+    sample_rate: AtomicU32,
+    /// Frames dropped because the buffer was full.
+    dropped_frames: AtomicU64,
+}
+
+impl CaptureBuffer {
+    pub fn new(channels: usize) -> Self {
+        Self {
+            samples: Mutex::new(Vec::with_capacity(CAPTURE_CAPACITY_FRAMES * channels)),
+            channels,
+            sample_rate: AtomicU32::new(0),
+            dropped_frames: AtomicU64::new(0),
+        }
+    }
+
+    /// Interleaved channels per frame.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    pub fn set_sample_rate(&self, rate: u32) {
+        self.sample_rate.store(rate, Ordering::Relaxed);
+    }
+
+    /// Negotiated sample rate, or the fallback if none was reported.
+    pub fn sample_rate(&self) -> u32 {
+        match self.sample_rate.load(Ordering::Relaxed) {
+            0 => FALLBACK_SAMPLE_RATE,
+            rate => rate,
+        }
+    }
+
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames.load(Ordering::Relaxed)
+    }
+
+    /// A scratch buffer sized for [`CaptureBuffer::push`]'s `pending` argument.
+    pub fn pending_buffer(&self) -> Vec<f32> {
+        Vec::with_capacity(CAPTURE_CAPACITY_FRAMES * self.channels)
+    }
+
+    /// Queue an interleaved block from the RT thread.
+    ///
+    /// Never blocks: if the writer holds the lock, the block is parked in
+    /// `pending` (owned by the caller) and flushed on the next call. Blocks
+    /// that don't fit anywhere are dropped and counted.
+    pub fn push(&self, pending: &mut Vec<f32>, block: &[f32]) {
+        let Some(mut shared) = self.samples.try_lock() else {
+            self.append_or_drop(pending, block);
+            return;
+        };
+        if !pending.is_empty() {
+            self.append_or_drop(&mut shared, pending);
+            pending.clear();
+        }
+        self.append_or_drop(&mut shared, block);
+    }
+
+    fn append_or_drop(&self, dst: &mut Vec<f32>, block: &[f32]) {
+        if dst.len() + block.len() <= dst.capacity() {
+            dst.extend_from_slice(block);
+        } else {
+            self.dropped_frames
+                .fetch_add((block.len() / self.channels) as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Swap the queued samples into `out`, which must be empty. `out`'s
+    /// allocation becomes the new queue, so pass a buffer of full capacity.
+    fn take(&self, out: &mut Vec<f32>) {
+        debug_assert!(out.is_empty());
+        std::mem::swap(&mut *self.samples.lock(), out);
+    }
+}
+
+impl std::fmt::Debug for CaptureBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CaptureBuffer")
+            .field("channels", &self.channels)
+            .field("sample_rate", &self.sample_rate)
+            .field("dropped_frames", &self.dropped_frames)
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// FILE NAMING
+// ============================================================================
+
+/// Convert days since the Unix epoch to a `(year, month, day)` civil date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days_from_civil inverse
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// UTC timestamp used in file names: `YYYYMMDD-HHMMSS`.
+fn timestamp(unix_secs: u64) -> String {
+    let secs = unix_secs as i64;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+    let tod = secs.rem_euclid(86_400);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        tod / 3600,
+        tod % 3600 / 60,
+        tod % 60
+    )
+}
+
+/// File stem for a recorded source, e.g. `sootmix-20240501-183000-music`.
+pub fn file_stem(unix_secs: u64, label: &str) -> String {
+    let label: String = label
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("sootmix-{}-{}", timestamp(unix_secs), label)
+}
+
+/// Pick output paths for one recording: one file per label in `dir`, with a
+/// numeric suffix where a name is taken (on disk or by an earlier label).
+pub fn track_paths(
+    dir: &Path,
+    unix_secs: u64,
+    labels: &[String],
+    format: RecordingFormat,
+) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::with_capacity(labels.len());
+    for label in labels {
+        let stem = file_stem(unix_secs, label);
+        let path = (1..)
+            .map(|n| {
+                let name = if n == 1 {
+                    format!("{}.{}", stem, format.as_str())
+                } else {
+                    format!("{}-{}.{}", stem, n, format.as_str())
+                };
+                dir.join(name)
+            })
+            .find(|p| !p.exists() && !paths.contains(p))
+            .expect("unbounded suffix search");
+        paths.push(path);
+    }
+    paths
+}
+
+// ============================================================================
+// WRITER
+// ============================================================================
+
+/// One output file.
+enum TrackWriter {
+    Wav(hound::WavWriter<BufWriter<File>>),
+    Flac(FlacWriter<BufWriter<File>>),
+}
+
+impl TrackWriter {
+    fn create(
+        path: &Path,
+        format: RecordingFormat,
+        sample_rate: u32,
+    ) -> Result<Self, RecordingError> {
+        Ok(match format {
+            RecordingFormat::Wav => {
+                let spec = hound::WavSpec {
+                    channels: TRACK_CHANNELS as u16,
+                    sample_rate,
+                    bits_per_sample: 32,
+                    sample_format: hound::SampleFormat::Float,
+                };
+                Self::Wav(hound::WavWriter::create(path, spec)?)
+            }
+            RecordingFormat::Flac => {
+                let file = BufWriter::new(File::create(path)?);
+                Self::Flac(FlacWriter::new(file, TRACK_CHANNELS, sample_rate)?)
+            }
+        })
+    }
+
+    fn write(&mut self, samples: &[f32]) -> Result<(), RecordingError> {
+        match self {
+            Self::Wav(w) => {
+                for &s in samples {
+                    w.write_sample(s)?;
+                }
+            }
+            Self::Flac(w) => w.write_interleaved(samples)?,
+        }
+        Ok(())
+    }
+
+    /// Bytes written so far. WAV headers are updated here so the file on
+    /// disk is playable even if the daemon dies mid-recording.
+    fn bytes(&mut self, path: &Path) -> Result<u64, RecordingError> {
+        match self {
+            Self::Wav(w) => {
+                w.flush()?;
+                Ok(fs::metadata(path)?.len())
+            }
+            Self::Flac(w) => Ok(w.bytes_written()),
+        }
+    }
+
+    fn finish(self) -> Result<(), RecordingError> {
+        match self {
+            Self::Wav(w) => w.finalize()?,
+            Self::Flac(w) => {
+                let mut file = w.finish()?;
+                io::Write::flush(&mut file)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Progress counters shared with the writer thread.
+#[derive(Default)]
+struct WriterStats {
+    frames: AtomicU64,
+    bytes: AtomicU64,
+}
+
+/// Drain `buffer` into one file per track until `stop` is set.
+///
+/// Files are created on the first block so the header carries the
+/// negotiated sample rate (known by then).
+fn run_writer(
+    buffer: Arc<CaptureBuffer>,
+    stats: Arc<WriterStats>,
+    stop: Arc<AtomicBool>,
+    paths: Vec<PathBuf>,
+    format: RecordingFormat,
+) -> Result<(), RecordingError> {
+    let channels = buffer.channels();
+    let mut block = buffer.pending_buffer();
+    let mut track = Vec::new();
+    let mut writers: Vec<TrackWriter> = Vec::new();
+
+    loop {
+        let stopping = stop.load(Ordering::Acquire);
+        buffer.take(&mut block);
+
+        if !block.is_empty() || (stopping && writers.is_empty()) {
+            if writers.is_empty() {
+                let rate = buffer.sample_rate();
+                info!("Recording {} file(s) at {} Hz", paths.len(), rate);
+                for path in &paths {
+                    writers.push(TrackWriter::create(path, format, rate)?);
+                }
+            }
+
+            for (index, writer) in writers.iter_mut().enumerate() {
+                let first = index * TRACK_CHANNELS;
+                track.clear();
+                track.extend(
+                    block
+                        .chunks_exact(channels)
+                        .flat_map(|frame| &frame[first..first + TRACK_CHANNELS]),
+                );
+                writer.write(&track)?;
+            }
+
+            let mut bytes = 0;
+            for (writer, path) in writers.iter_mut().zip(&paths) {
+                bytes += writer.bytes(path)?;
+            }
+            stats.bytes.store(bytes, Ordering::Relaxed);
+            stats
+                .frames
+                .fetch_add((block.len() / channels) as u64, Ordering::Relaxed);
+            block.clear();
+        }
+
+        if stopping {
+            break;
+        }
+        thread::sleep(WRITER_INTERVAL);
+    }
+
+    for writer in writers {
+        writer.finish()?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// SESSION
+// ============================================================================
+
+/// A running recording: the capture buffer its stream feeds and the writer
+/// thread emptying it.
+pub struct RecordingSession {
+    pub id: Uuid,
+    pub format: RecordingFormat,
+    /// Output files, one per track.
+    pub paths: Vec<PathBuf>,
+    buffer: Arc<CaptureBuffer>,
+    stats: Arc<WriterStats>,
+    stop: Arc<AtomicBool>,
+    writer: Option<JoinHandle<Result<(), RecordingError>>>,
+}
+
+impl RecordingSession {
+    /// Start the writer thread for one file per path. The directory is
+    /// created if needed; files appear once the first audio arrives.
+    pub fn start(paths: Vec<PathBuf>, format: RecordingFormat) -> Result<Self, RecordingError> {
+        for dir in paths.iter().filter_map(|p| p.parent()) {
+            fs::create_dir_all(dir)?;
+        }
+
+        let id = Uuid::new_v4();
+        let buffer = Arc::new(CaptureBuffer::new(paths.len() * TRACK_CHANNELS));
+        let stats = Arc::new(WriterStats::default());
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let writer = {
+            let (buffer, stats, stop) =
+                (Arc::clone(&buffer), Arc::clone(&stats), Arc::clone(&stop));
+            let paths = paths.clone();
+            thread::Builder::new()
+                .name("sootmix-recorder".to_string())
+                .spawn(move || {
+                    let result = run_writer(buffer, stats, stop, paths, format);
+                    if let Err(ref e) = result {
+                        error!("Recording {} failed: {}", id, e);
+                    }
+                    result
+                })?
+        };
+
+        debug!("Recording {} started: {:?}", id, paths);
+        Ok(Self {
+            id,
+            format,
+            paths,
+            buffer,
+            stats,
+            stop,
+            writer: Some(writer),
+        })
+    }
+
+    /// Buffer the capture stream writes into.
+    pub fn buffer(&self) -> &Arc<CaptureBuffer> {
+        &self.buffer
+    }
+
+    /// Seconds of audio written so far.
+    pub fn elapsed_secs(&self) -> f64 {
+        self.stats.frames.load(Ordering::Relaxed) as f64 / self.buffer.sample_rate() as f64
+    }
+
+    /// Combined size of the output files.
+    pub fn bytes(&self) -> u64 {
+        self.stats.bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn dropped_frames(&self) -> u64 {
+        self.buffer.dropped_frames()
+    }
+
+    /// Whether the writer thread has exited (only happens early on error).
+    pub fn has_failed(&self) -> bool {
+        self.writer.as_ref().is_none_or(|w| w.is_finished())
+    }
+
+    /// Flush what's buffered, finalize the files and join the writer.
+    pub fn stop(&mut self) -> Result<(), RecordingError> {
+        self.stop.store(true, Ordering::Release);
+        match self.writer.take() {
+            Some(writer) => writer.join().map_err(|_| RecordingError::WriterPanicked)?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for RecordingSession {
+    fn drop(&mut self) {
+        if let Err(e) = self.stop() {
+            error!("Failed to finalize recording {}: {}", self.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_is_utc_civil_time() {
+        assert_eq!(timestamp(0), "19700101-000000");
+        assert_eq!(timestamp(951_782_400), "20000229-000000");
+        assert_eq!(timestamp(1_700_000_000), "20231114-221320");
+    }
+
+    #[test]
+    fn test_track_paths_are_unique() {
+        let dir = tempfile::tempdir().unwrap();
+        let taken = dir.path().join("sootmix-19700101-000000-master.flac");
+        fs::write(&taken, b"").unwrap();
+
+        let labels = vec![
+            "master".to_string(),
+            "Voice Chat".to_string(),
+            "voice/chat".to_string(),
+        ];
+        let paths = track_paths(dir.path(), 0, &labels, RecordingFormat::Flac);
+        let names: Vec<_> = paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+
+        assert_eq!(
+            names,
+            [
+                "sootmix-19700101-000000-master-2.flac",
+                "sootmix-19700101-000000-voice_chat.flac",
+                "sootmix-19700101-000000-voice_chat-2.flac",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_capture_buffer_parks_blocks_while_locked() {
+        let buffer = CaptureBuffer::new(2);
+        let mut pending = buffer.pending_buffer();
+
+        {
+            let _writer = buffer.samples.lock();
+            buffer.push(&mut pending, &[1.0, 2.0]);
+        }
+        buffer.push(&mut pending, &[3.0, 4.0]);
+
+        let mut out = Vec::new();
+        buffer.take(&mut out);
+        assert_eq!(out, [1.0, 2.0, 3.0, 4.0]);
+        assert!(pending.is_empty());
+        assert_eq!(buffer.dropped_frames(), 0);
+    }
+
+    #[test]
+    fn test_capture_buffer_drops_when_full() {
+        let buffer = CaptureBuffer::new(2);
+        let mut pending = buffer.pending_buffer();
+        let block = vec![0.0; CAPTURE_CAPACITY_FRAMES * 2];
+
+        buffer.push(&mut pending, &block);
+        buffer.push(&mut pending, &[0.0; 8]);
+
+        assert_eq!(buffer.dropped_frames(), 4);
+    }
+
+    #[test]
+    fn test_session_splits_tracks() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = vec![dir.path().join("a.wav"), dir.path().join("b.wav")];
+        let mut session = RecordingSession::start(paths.clone(), RecordingFormat::Wav).unwrap();
+        session.buffer().set_sample_rate(44100);
+
+        // Two frames of a four-channel capture: tracks a = (0.1, 0.2), b = (0.3, 0.4)
+        let mut pending = session.buffer().pending_buffer();
+        session
+            .buffer()
+            .push(&mut pending, &[0.1, 0.2, 0.3, 0.4, 0.1, 0.2, 0.3, 0.4]);
+        session.stop().unwrap();
+
+        for (path, expected) in paths.iter().zip([[0.1, 0.2], [0.3, 0.4]]) {
+            let mut reader = hound::WavReader::open(path).unwrap();
+            assert_eq!(reader.spec().sample_rate, 44100);
+            assert_eq!(reader.spec().channels, 2);
+            let samples: Vec<f32> = reader.samples::<f32>().map(Result::unwrap).collect();
+            assert_eq!(samples, [expected, expected].concat());
+        }
+        assert_eq!(session.elapsed_secs(), 2.0 / 44100.0);
+        assert!(session.bytes() > 0);
+    }
+}
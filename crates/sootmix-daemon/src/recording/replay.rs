@@ -0,0 +1,360 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Instant replay: a rolling in-memory history of a channel's audio.
+//!
+//! Every replay-enabled channel has a stereo capture stream (see
+//! [`crate::audio::record_stream`]) whose RT callback pushes samples into a
+//! lock-free [`RingBuffer`]. A keeper thread drains the ring into a history
+//! trimmed to the configured length, so nothing touches the disk until a
+//! clip of the last N seconds is saved.
+
+use super::{RecordingError, RecordingFormat, TrackWriter, FALLBACK_SAMPLE_RATE, TRACK_CHANNELS};
+use crate::realtime::{RingBuffer, RingBufferReader, RingBufferWriter};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tracing::debug;
+
+/// Replay length used until one is configured.
+pub const DEFAULT_REPLAY_SECONDS: u32 = 60;
+
+/// Accepted replay lengths. Five minutes of stereo f32 at 48 kHz is ~110 MB
+/// per channel, so the upper bound stays modest.
+pub const REPLAY_SECONDS_RANGE: RangeInclusive<u32> = 30..=300;
+
+/// Ring size in samples (interleaved stereo): ~1.4 s at 48 kHz, far more
+/// than one keeper interval.
+const FEED_CAPACITY: usize = 1 << 17;
+
+/// How often the keeper thread drains the ring into the history.
+const KEEPER_INTERVAL: Duration = Duration::from_millis(50);
+
+/// State shared between a replay buffer, its keeper thread and the RT side.
+struct ReplayShared {
+    /// Interleaved stereo history, oldest first.
+    history: Mutex<VecDeque<f32>>,
+    /// Configured length in seconds.
+    seconds: AtomicU32,
+    /// Negotiated sample rate (0 until the stream's format is known).
+    sample_rate: AtomicU32,
+    /// Frames dropped because the ring was full.
+    dropped_frames: AtomicU64,
+}
+
+impl ReplayShared {
+    fn sample_rate(&self) -> u32 {
+        match self.sample_rate.load(Ordering::Relaxed) {
+            0 => FALLBACK_SAMPLE_RATE,
+            rate => rate,
+        }
+    }
+
+    /// History size limit in samples.
+    fn capacity_samples(&self) -> usize {
+        self.seconds.load(Ordering::Relaxed) as usize * self.sample_rate() as usize * TRACK_CHANNELS
+    }
+
+    /// Append samples and drop whatever falls out of the window. Whole
+    /// frames are dropped so the history stays frame-aligned.
+    fn append(&self, samples: &[f32]) {
+        let mut history = self.history.lock();
+        history.extend(samples);
+        trim(&mut history, self.capacity_samples());
+    }
+}
+
+fn trim(history: &mut VecDeque<f32>, capacity: usize) {
+    let excess = history.len().saturating_sub(capacity);
+    history.drain(..excess - excess % TRACK_CHANNELS);
+}
+
+// ============================================================================
+// RT SIDE
+// ============================================================================
+
+/// RT side of a replay buffer, owned by the capture stream's callback.
+pub struct ReplayWriter {
+    ring: RingBufferWriter<f32>,
+    shared: Arc<ReplayShared>,
+}
+
+impl ReplayWriter {
+    pub fn set_sample_rate(&self, rate: u32) {
+        self.shared.sample_rate.store(rate, Ordering::Relaxed);
+    }
+
+    /// Queue an interleaved stereo block. A block that doesn't fit is dropped
+    /// whole (and counted) rather than overwriting samples not yet drained.
+    pub fn push(&mut self, block: &[f32]) {
+        if self.ring.available() + block.len() > FEED_CAPACITY {
+            self.shared
+                .dropped_frames
+                .fetch_add((block.len() / TRACK_CHANNELS) as u64, Ordering::Relaxed);
+            return;
+        }
+        self.ring.push_slice(block);
+    }
+}
+
+/// Hands a replay buffer's [`ReplayWriter`] to its capture stream.
+///
+/// Cloneable so it can travel in a `PwCommand`; the stream takes the writer
+/// exactly once.
+#[derive(Clone)]
+pub struct ReplayFeed(Arc<Mutex<Option<ReplayWriter>>>);
+
+impl ReplayFeed {
+    pub fn take(&self) -> Option<ReplayWriter> {
+        self.0.lock().take()
+    }
+}
+
+impl std::fmt::Debug for ReplayFeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplayFeed")
+            .field("taken", &self.0.lock().is_none())
+            .finish()
+    }
+}
+
+// ============================================================================
+// BUFFER
+// ============================================================================
+
+/// Drain the ring into the history until `stop` is set.
+fn run_keeper(mut ring: RingBufferReader<f32>, shared: Arc<ReplayShared>, stop: Arc<AtomicBool>) {
+    let mut block = Vec::with_capacity(FEED_CAPACITY);
+    while !stop.load(Ordering::Acquire) {
+        while let Some(sample) = ring.pop() {
+            block.push(sample);
+        }
+        if !block.is_empty() {
+            shared.append(&block);
+            block.clear();
+        }
+        thread::sleep(KEEPER_INTERVAL);
+    }
+}
+
+/// A channel's rolling replay history and the keeper thread filling it.
+pub struct ReplayBuffer {
+    shared: Arc<ReplayShared>,
+    feed: ReplayFeed,
+    stop: Arc<AtomicBool>,
+    keeper: Option<JoinHandle<()>>,
+}
+
+impl ReplayBuffer {
+    /// Start an empty buffer keeping the last `seconds` of audio.
+    pub fn start(seconds: u32) -> Result<Self, RecordingError> {
+        let shared = Arc::new(ReplayShared {
+            history: Mutex::new(VecDeque::new()),
+            seconds: AtomicU32::new(seconds),
+            sample_rate: AtomicU32::new(0),
+            dropped_frames: AtomicU64::new(0),
+        });
+        let (ring, reader) = RingBuffer::new(FEED_CAPACITY).split();
+        let feed = ReplayFeed(Arc::new(Mutex::new(Some(ReplayWriter {
+            ring,
+            shared: Arc::clone(&shared),
+        }))));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let keeper = {
+            let (shared, stop) = (Arc::clone(&shared), Arc::clone(&stop));
+            thread::Builder::new()
+                .name("sootmix-replay".to_string())
+                .spawn(move || run_keeper(reader, shared, stop))?
+        };
+
+        Ok(Self {
+            shared,
+            feed,
+            stop,
+            keeper: Some(keeper),
+        })
+    }
+
+    /// Hand-off for the capture stream feeding this buffer.
+    pub fn feed(&self) -> ReplayFeed {
+        self.feed.clone()
+    }
+
+    /// Change the history length. Shrinking discards the oldest audio.
+    pub fn set_seconds(&self, seconds: u32) {
+        self.shared.seconds.store(seconds, Ordering::Relaxed);
+        trim(
+            &mut self.shared.history.lock(),
+            self.shared.capacity_samples(),
+        );
+    }
+
+    /// Seconds of audio currently held.
+    pub fn buffered_secs(&self) -> f64 {
+        let samples = self.shared.history.lock().len();
+        (samples / TRACK_CHANNELS) as f64 / self.shared.sample_rate() as f64
+    }
+
+    pub fn dropped_frames(&self) -> u64 {
+        self.shared.dropped_frames.load(Ordering::Relaxed)
+    }
+
+    /// Copy of the last `seconds` of history (all of it if `seconds` is 0
+    /// or more than is held).
+    pub fn clip(&self, seconds: u32) -> ReplayClip {
+        let sample_rate = self.shared.sample_rate();
+        let history = self.shared.history.lock();
+        let frames = history.len() / TRACK_CHANNELS;
+        let wanted = match seconds {
+            0 => frames,
+            s => (s as usize * sample_rate as usize).min(frames),
+        };
+        let samples = history
+            .range((frames - wanted) * TRACK_CHANNELS..frames * TRACK_CHANNELS)
+            .copied()
+            .collect();
+        ReplayClip {
+            samples,
+            sample_rate,
+        }
+    }
+}
+
+impl Drop for ReplayBuffer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(keeper) = self.keeper.take() {
+            let _ = keeper.join();
+        }
+    }
+}
+
+/// Audio copied out of a replay buffer, ready to be written to disk.
+pub struct ReplayClip {
+    /// Interleaved stereo samples.
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl ReplayClip {
+    pub fn duration_secs(&self) -> f64 {
+        (self.samples.len() / TRACK_CHANNELS) as f64 / self.sample_rate as f64
+    }
+
+    /// Write the clip as one stereo file, creating the directory if needed.
+    pub fn write(&self, path: &Path, format: RecordingFormat) -> Result<(), RecordingError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut writer = TrackWriter::create(path, format, self.sample_rate)?;
+        writer.write(&self.samples)?;
+        writer.finish()?;
+        debug!(
+            "Saved {:.1}s replay clip to {}",
+            self.duration_secs(),
+            path.display()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer_at(rate: u32, seconds: u32) -> (ReplayBuffer, ReplayWriter) {
+        let buffer = ReplayBuffer::start(seconds).unwrap();
+        let writer = buffer.feed().take().unwrap();
+        writer.set_sample_rate(rate);
+        (buffer, writer)
+    }
+
+    /// Interleaved stereo frames numbered from `start`.
+    fn frames(start: usize, count: usize) -> Vec<f32> {
+        (start..start + count)
+            .flat_map(|i| [i as f32, -(i as f32)])
+            .collect()
+    }
+
+    #[test]
+    fn test_history_keeps_last_seconds() {
+        let (buffer, _writer) = buffer_at(100, 2);
+        buffer.shared.append(&frames(0, 150));
+        buffer.shared.append(&frames(150, 150));
+
+        let clip = buffer.clip(0);
+        assert_eq!(clip.samples, frames(100, 200));
+        assert!((buffer.buffered_secs() - 2.0).abs() < 1e-9);
+
+        buffer.set_seconds(1);
+        assert_eq!(buffer.clip(0).samples, frames(200, 100));
+    }
+
+    #[test]
+    fn test_clip_takes_most_recent_frames() {
+        let (buffer, _writer) = buffer_at(100, 10);
+        buffer.shared.append(&frames(0, 250));
+        // A partial frame at the end is left out
+        buffer.shared.append(&[999.0]);
+
+        let clip = buffer.clip(1);
+        assert_eq!(clip.samples, frames(150, 100));
+        assert_eq!(clip.sample_rate, 100);
+        assert_eq!(buffer.clip(60).samples.len(), 500);
+    }
+
+    #[test]
+    fn test_writer_feeds_keeper() {
+        let (buffer, mut writer) = buffer_at(1000, 10);
+        assert!(buffer.feed().take().is_none());
+
+        writer.push(&frames(0, 500));
+        for _ in 0..100 {
+            if buffer.buffered_secs() >= 0.5 {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(buffer.clip(0).samples, frames(0, 500));
+    }
+
+    #[test]
+    fn test_writer_drops_blocks_that_do_not_fit() {
+        let (buffer, mut writer) = buffer_at(48000, 10);
+        // Stop the keeper so nothing drains the ring
+        buffer.stop.store(true, Ordering::Release);
+        thread::sleep(KEEPER_INTERVAL * 2);
+
+        let block = vec![0.0; FEED_CAPACITY / 2 + 2];
+        writer.push(&block);
+        writer.push(&block);
+        assert_eq!(
+            buffer.dropped_frames(),
+            (block.len() / TRACK_CHANNELS) as u64
+        );
+    }
+
+    #[test]
+    fn test_clip_writes_wav() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("replay").join("clip.wav");
+        let clip = ReplayClip {
+            samples: frames(0, 48),
+            sample_rate: 48000,
+        };
+        clip.write(&path, RecordingFormat::Wav).unwrap();
+
+        let reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().channels, 2);
+        assert_eq!(reader.spec().sample_rate, 48000);
+        assert_eq!(reader.len(), 96);
+    }
+}
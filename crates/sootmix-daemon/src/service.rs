@@ -4,12 +4,39 @@
 
 //! Core daemon service logic and state management.
 
-use crate::audio::pipewire_thread::{PwCommand, PwEvent, PwThread};
-use crate::audio::types::{MediaClass, PortDirection, PwLink, PwNode, PwPort};
-use crate::config::{ConfigManager, MixerConfig, RoutingRulesConfig, SavedChannel};
-use sootmix_ipc::{AppInfo, ChannelInfo, ChannelKind, InputInfo, OutputInfo, RoutingRuleInfo};
+use crate::audio::auto_level::AutoLevel;
+use crate::audio::backend::{self, BackendFactory, GraphBackend};
+use crate::audio::ducking::DuckEnvelope;
+use crate::audio::fade::{VolumeFade, FADE_FLOOR_DB};
+use crate::audio::loudness::{LoudnessLevels, SharedLoudness};
+use crate::audio::monitor::{plan_solo, MonitorStage, StageNodes};
+use crate::audio::noise_suppression::{Model, NoiseSuppression, VoiceActivity};
+use crate::audio::pipewire_thread::{PwCommand, PwEvent};
+use crate::audio::record_stream::record_port_name;
+use crate::audio::spectrum::{SpectrumAnalyzer, SpectrumFeed, SpectrumTapPoint};
+use crate::audio::talk_gate::TalkGateSettings;
+use crate::audio::types::{AudioChannel, MediaClass, PortDirection, PwLink, PwNode, PwPort};
+use crate::config::{
+    AutoLevelConfig, ConditionCombine, ConfigManager, DuckingConfig, EqBand, EqPreset, MatchTarget,
+    MatchType, MidiBinding, MidiConfig, MidiControlKind, MidiTarget, MixerConfig,
+    NoiseSuppressionConfig, PluginSlotConfig, PropertyCondition, RecordingConfig, RoutingRule,
+    RoutingRulesConfig, SavedChannel, Scene, TalkConfig,
+};
+use crate::midi::ports::MidiPorts;
+use crate::midi::MidiMessage;
+use crate::recording::replay::{ReplayBuffer, ReplayClip, REPLAY_SECONDS_RANGE};
+use crate::recording::{RecordingFormat, RecordingSession, TRACK_CHANNELS};
+use sootmix_ipc::{
+    AppInfo, AutoLevelInfo, Balance, ChannelInfo, ChannelKind, ChannelLayout, DuckingInfo,
+    EqBandInfo, InputInfo, LoudnessData, MidiBindingInfo, MonitorInfo, NoiseModel,
+    NoiseSuppressionInfo, OutputInfo, PluginSlotInfo, RecordingStatus, ReplayBufferInfo,
+    RoutingRuleInfo, RuleConditionInfo, SoloMode, SpectrumData, SpectrumTap, TalkInfo, TalkMode,
+    NOISE_MAX_ATTENUATION_DB, NOISE_MAX_GRACE_MS, NOISE_MAX_RETRO_GRACE_MS, SPECTRUM_BANDS,
+    TALK_MAX_FADE_MS, TALK_MAX_RELEASE_MS,
+};
+use sootmix_plugin_host::PluginManager;
 use std::collections::{HashMap, HashSet};
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::mpsc as tokio_mpsc;
@@ -24,17 +51,116 @@ pub enum SignalEvent {
     #[allow(dead_code)]
     OutputsChanged,
     MeterUpdate(Vec<sootmix_ipc::MeterData>),
+    /// Periodic loudness readings.
+    LoudnessUpdate(Vec<LoudnessData>),
+    /// Periodic spectrum of the analyzed channel.
+    SpectrumUpdate(SpectrumData),
     /// Master volume changed externally (hardware knob, pavucontrol, etc.)
     MasterVolumeChanged(f64),
     /// Master mute changed externally.
     MasterMuteChanged(bool),
+    /// Periodic progress of a running recording (and its final state on stop).
+    RecordingProgress(RecordingStatus),
+    /// Channel volume changed from a MIDI controller.
+    ChannelVolumeChanged {
+        channel_id: String,
+        volume_db: f64,
+    },
+    /// Channel mute changed from a MIDI controller.
+    ChannelMuteChanged {
+        channel_id: String,
+        muted: bool,
+    },
+    /// Channel solo changed from a MIDI controller.
+    ChannelSoloChanged {
+        channel_id: String,
+        solo: bool,
+    },
+    /// Noise suppression started or stopped hearing a voice on an input
+    /// channel.
+    ChannelTalking {
+        channel_id: String,
+        talking: bool,
+    },
+    /// A channel's mic went live or silent (mute or talk key).
+    ChannelLiveChanged {
+        channel_id: String,
+        live: bool,
+    },
+    /// MIDI learn bound a control.
+    MidiBindingLearned(MidiBindingInfo),
+    /// Channel properties changed outside a D-Bus call on that channel
+    /// (e.g. its latency, after a plugin change on another channel).
+    ChannelUpdated(ChannelInfo),
 }
 
+/// Smallest change in ducking gain reduction worth sending to PipeWire.
+const DUCK_MIN_STEP_DB: f32 = 0.1;
+
+/// Smallest change in auto level gain worth sending to PipeWire.
+const AUTO_LEVEL_MIN_STEP_DB: f32 = 0.1;
+
+/// Seconds between `RecordingProgress` signals.
+const RECORDING_PROGRESS_INTERVAL: f32 = 1.0;
+
+/// Seconds between scans for plugged or unplugged MIDI controllers.
+const MIDI_REFRESH_INTERVAL: f32 = 2.0;
+
 /// Convert a linear volume value to dB.
 fn linear_to_db(linear: f32) -> f32 {
     20.0 * linear.max(1e-10).log10()
 }
 
+/// IPC form of a channel's (or the master's) loudness readings.
+fn loudness_data(id: Uuid, levels: LoudnessLevels) -> LoudnessData {
+    LoudnessData::new(
+        id,
+        levels.momentary as f64,
+        levels.short_term as f64,
+        levels.integrated as f64,
+        levels.range as f64,
+        linear_to_db(levels.true_peak).max(-96.0) as f64,
+    )
+}
+
+/// IPC form of a stored MIDI binding.
+fn midi_binding_info(binding: &MidiBinding) -> MidiBindingInfo {
+    let mut info = MidiBindingInfo {
+        id: binding.id.to_string(),
+        device: binding.device.clone().unwrap_or_default(),
+        midi_channel: binding.midi_channel,
+        control: match binding.control {
+            MidiControlKind::Cc => "cc",
+            MidiControlKind::Note => "note",
+        }
+        .to_string(),
+        number: binding.number,
+        channel_id: binding
+            .target
+            .channel()
+            .map(|id| id.to_string())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+    info.target = match binding.target {
+        MidiTarget::ChannelVolume { .. } => "channel_volume",
+        MidiTarget::ChannelMute { .. } => "channel_mute",
+        MidiTarget::ChannelSolo { .. } => "channel_solo",
+        MidiTarget::ChannelTalk { .. } => "channel_talk",
+        MidiTarget::MasterVolume => "master_volume",
+        MidiTarget::MasterMute => "master_mute",
+        MidiTarget::PluginParameter {
+            slot, param_index, ..
+        } => {
+            info.plugin_slot = slot as u32;
+            info.param_index = param_index;
+            "plugin_parameter"
+        }
+    }
+    .to_string();
+    info
+}
+
 #[derive(Debug, Error)]
 pub enum ServiceError {
     #[error("PipeWire error: {0}")]
@@ -43,8 +169,14 @@ pub enum ServiceError {
     ChannelNotFound(String),
     #[error("App not found: {0}")]
     AppNotFound(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
     #[error("Config error: {0}")]
     Config(#[from] crate::config::ConfigError),
+    #[error("Plugin error: {0}")]
+    Plugin(#[from] sootmix_plugin_host::PluginLoadError),
+    #[error("Recording error: {0}")]
+    Recording(#[from] crate::recording::RecordingError),
 }
 
 /// Internal channel state.
@@ -56,15 +188,30 @@ pub struct ChannelState {
     pub muted: bool,
     pub eq_enabled: bool,
     pub eq_preset: String,
+    /// EQ bands: those of `eq_preset`, or edited ones if `eq_custom`.
+    pub eq_bands: Vec<EqBand>,
+    pub eq_custom: bool,
+    /// Bands the running (or starting) EQ filter was created with.
+    pub eq_filter_bands: Vec<EqBand>,
     pub assigned_apps: Vec<String>,
     pub is_managed: bool,
     pub sink_name: Option<String>,
     pub output_device_name: Option<String>,
     pub pw_sink_id: Option<u32>,
     pub pw_loopback_output_id: Option<u32>,
+    /// EQ filter sink node ID (fed by the loopback output while EQ is active).
+    pub pw_eq_sink_id: Option<u32>,
+    /// EQ filter output stream node ID (routed to the output device while EQ is active).
+    pub pw_eq_output_id: Option<u32>,
+    /// Plugin chain sink node ID (fed by the loopback output or mic while plugins are loaded).
+    pub pw_plugin_sink_id: Option<u32>,
+    /// Plugin chain output stream node ID (routed to the next stage while plugins are loaded).
+    pub pw_plugin_output_id: Option<u32>,
     pub meter_levels: (f32, f32),
     /// Atomic meter levels from native loopback (for real-time reading).
     pub atomic_meter_levels: Option<std::sync::Arc<crate::audio::AtomicMeterLevels>>,
+    /// Loudness readings from the channel's meter.
+    pub loudness: Option<Arc<SharedLoudness>>,
     /// Whether this is an output or input channel.
     pub kind: ChannelKind,
     /// Input device name (for input channels).
@@ -77,14 +224,54 @@ pub struct ChannelState {
     pub noise_suppression_enabled: bool,
     /// VAD threshold for noise suppression (0-100%). Higher = more aggressive noise gating.
     pub vad_threshold: f32,
+    /// Noise suppression model, VAD grace periods and attenuation limit.
+    pub noise_suppression_settings: NoiseSuppressionConfig,
+    /// Voice activity published by the noise suppression in the plugin filter.
+    pub voice_activity: Arc<VoiceActivity>,
+    /// Whether the last ChannelTalking signal reported a voice (not persisted).
+    pub talking: bool,
+    /// Push-to-talk / push-to-mute settings.
+    pub talk: TalkConfig,
+    /// Whether the talk key is held (not persisted).
+    pub talk_pressed: bool,
+    /// Seconds the talk key stays held after it was let go.
+    pub talk_release_left: f32,
+    /// Whether the last ChannelLiveChanged signal reported a live mic.
+    pub live: bool,
     /// Hardware microphone gain in dB (-12.0 to +12.0). Controls the physical input device level.
     pub input_gain_db: f32,
-    /// Plugin chain configuration (round-tripped from config, not used by daemon at runtime).
-    pub plugin_chain: Vec<crate::config::PluginSlotConfig>,
-    /// Whether sidetone monitoring is enabled (UI-owned, round-tripped by daemon).
+    /// Plugin chain configuration, one entry per slot.
+    pub plugin_chain: Vec<PluginSlotConfig>,
+    /// Loaded plugin instance for each slot in `plugin_chain` (`None` if loading failed).
+    pub plugin_instance_ids: Vec<Option<Uuid>>,
+    /// Whether the channel is heard on the monitor bus as sidetone (input channels).
     pub sidetone_enabled: bool,
-    /// Sidetone volume in dB (UI-owned, round-tripped by daemon).
+    /// Sidetone level in dB, applied on the channel's sidetone stage.
     pub sidetone_volume_db: f32,
+    /// Whether the channel is soloed (not persisted).
+    pub solo: bool,
+    /// Whether solo-in-place currently silences this channel.
+    pub solo_cut: bool,
+    /// Gain reduction currently applied by ducking, in dB (0.0 = none).
+    pub duck_reduction_db: f32,
+    /// Automatic loudness normalization settings.
+    pub auto_level: AutoLevelConfig,
+    /// Auto level gain, moving toward the target every meter tick.
+    pub auto_level_state: AutoLevel,
+    /// Auto level gain last applied to the volume node, in dB.
+    pub auto_level_gain_db: f32,
+    /// Pan/balance (-1.0 left to 1.0 right).
+    pub pan: f32,
+    /// Surround width (0.0 front only to 1.0 all speakers).
+    pub width: f32,
+    /// Speaker layout of the virtual sink (output channels).
+    pub layout: ChannelLayout,
+    /// Latency reported by the active plugin chain, in samples.
+    pub plugin_latency: u32,
+    /// Delay added to line up with other channels on the same output, in samples.
+    pub latency_compensation: u32,
+    /// Delay a surround channel would need to line up, but doesn't get.
+    pub uncompensated_latency: u32,
 }
 
 impl ChannelState {
@@ -96,24 +283,52 @@ impl ChannelState {
             muted: false,
             eq_enabled: false,
             eq_preset: "Flat".to_string(),
+            eq_bands: EqPreset::flat().bands,
+            eq_custom: false,
+            eq_filter_bands: Vec::new(),
             assigned_apps: Vec::new(),
             is_managed: true,
             sink_name: None,
             output_device_name: None,
             pw_sink_id: None,
             pw_loopback_output_id: None,
+            pw_eq_sink_id: None,
+            pw_eq_output_id: None,
+            pw_plugin_sink_id: None,
+            pw_plugin_output_id: None,
             meter_levels: (0.0, 0.0),
             atomic_meter_levels: None,
+            loudness: None,
             kind: ChannelKind::Output,
             input_device_name: None,
             pw_source_id: None,
             pw_loopback_capture_id: None,
             noise_suppression_enabled: false,
             vad_threshold: 95.0,
+            noise_suppression_settings: NoiseSuppressionConfig::default(),
+            voice_activity: Arc::default(),
+            talking: false,
+            talk: TalkConfig::default(),
+            talk_pressed: false,
+            talk_release_left: 0.0,
+            live: true,
             input_gain_db: 0.0,
             plugin_chain: Vec::new(),
+            plugin_instance_ids: Vec::new(),
             sidetone_enabled: false,
-            sidetone_volume_db: 0.0,
+            sidetone_volume_db: -20.0,
+            solo: false,
+            solo_cut: false,
+            duck_reduction_db: 0.0,
+            auto_level: AutoLevelConfig::default(),
+            auto_level_state: AutoLevel::new(),
+            auto_level_gain_db: 0.0,
+            pan: 0.0,
+            width: 1.0,
+            layout: ChannelLayout::Stereo,
+            plugin_latency: 0,
+            latency_compensation: 0,
+            uncompensated_latency: 0,
         }
     }
 
@@ -125,54 +340,118 @@ impl ChannelState {
             muted: false,
             eq_enabled: false,
             eq_preset: "Flat".to_string(),
+            eq_bands: EqPreset::flat().bands,
+            eq_custom: false,
+            eq_filter_bands: Vec::new(),
             assigned_apps: Vec::new(),
             is_managed: true,
             sink_name: None,
             output_device_name: None,
             pw_sink_id: None,
             pw_loopback_output_id: None,
+            pw_eq_sink_id: None,
+            pw_eq_output_id: None,
+            pw_plugin_sink_id: None,
+            pw_plugin_output_id: None,
             meter_levels: (0.0, 0.0),
             atomic_meter_levels: None,
+            loudness: None,
             kind: ChannelKind::Input,
             input_device_name: None,
             pw_source_id: None,
             pw_loopback_capture_id: None,
             noise_suppression_enabled: false,
             vad_threshold: 95.0,
+            noise_suppression_settings: NoiseSuppressionConfig::default(),
+            voice_activity: Arc::default(),
+            talking: false,
+            talk: TalkConfig::default(),
+            talk_pressed: false,
+            talk_release_left: 0.0,
+            live: true,
             input_gain_db: 0.0,
             plugin_chain: Vec::new(),
+            plugin_instance_ids: Vec::new(),
             sidetone_enabled: false,
-            sidetone_volume_db: 0.0,
+            sidetone_volume_db: -20.0,
+            solo: false,
+            solo_cut: false,
+            duck_reduction_db: 0.0,
+            auto_level: AutoLevelConfig::default(),
+            auto_level_state: AutoLevel::new(),
+            auto_level_gain_db: 0.0,
+            pan: 0.0,
+            width: 1.0,
+            layout: ChannelLayout::Stereo,
+            plugin_latency: 0,
+            latency_compensation: 0,
+            uncompensated_latency: 0,
         }
     }
 
     pub fn from_saved(saved: &SavedChannel) -> Self {
-        Self {
+        let mut channel = Self {
             id: saved.id,
             name: saved.name.clone(),
             volume_db: saved.volume_db,
             muted: saved.muted,
             eq_enabled: saved.eq_enabled,
             eq_preset: saved.eq_preset.clone(),
+            // Bands of a preset are filled in by the service, which can load it
+            eq_bands: saved.eq_bands.clone().unwrap_or_default(),
+            eq_custom: saved.eq_bands.is_some(),
+            eq_filter_bands: Vec::new(),
             assigned_apps: saved.assigned_apps.clone(),
             is_managed: saved.is_managed,
             sink_name: saved.sink_name.clone(),
             output_device_name: saved.output_device_name.clone(),
             pw_sink_id: None,
             pw_loopback_output_id: None,
+            pw_eq_sink_id: None,
+            pw_eq_output_id: None,
+            pw_plugin_sink_id: None,
+            pw_plugin_output_id: None,
             meter_levels: (0.0, 0.0),
             atomic_meter_levels: None,
+            loudness: None,
             kind: saved.kind,
             input_device_name: saved.input_device_name.clone(),
             pw_source_id: None,
             pw_loopback_capture_id: None,
             noise_suppression_enabled: saved.noise_suppression_enabled,
             vad_threshold: saved.vad_threshold,
+            noise_suppression_settings: saved.noise_suppression,
+            voice_activity: Arc::default(),
+            talking: false,
+            talk: saved.talk,
+            talk_pressed: false,
+            talk_release_left: 0.0,
+            live: false,
             input_gain_db: saved.input_gain_db,
             plugin_chain: saved.plugin_chain.clone(),
+            plugin_instance_ids: Vec::new(),
             sidetone_enabled: saved.sidetone_enabled,
             sidetone_volume_db: saved.sidetone_volume_db,
-        }
+            solo: false,
+            solo_cut: false,
+            duck_reduction_db: 0.0,
+            auto_level: saved.auto_level,
+            auto_level_state: AutoLevel::new(),
+            auto_level_gain_db: 0.0,
+            pan: saved.pan,
+            // Width only reaches surround speakers
+            width: if saved.layout.has_surround() {
+                saved.width
+            } else {
+                1.0
+            },
+            layout: saved.layout,
+            plugin_latency: 0,
+            latency_compensation: 0,
+            uncompensated_latency: 0,
+        };
+        channel.live = channel.is_live();
+        channel
     }
 
     /// Whether this is an input (mic) channel.
@@ -180,6 +459,135 @@ impl ChannelState {
         self.kind == ChannelKind::Input
     }
 
+    /// The node whose output feeds the channel's output device: the last
+    /// running processing stage (EQ, then plugins), otherwise the loopback
+    /// output. Always `None` for input channels.
+    pub fn output_stage_id(&self) -> Option<u32> {
+        if self.is_input() {
+            return None;
+        }
+        self.pw_eq_output_id
+            .or(self.pw_plugin_output_id)
+            .or(self.pw_loopback_output_id)
+    }
+
+    /// Links between the channel's processing stages as `(from, to)` node
+    /// pairs in signal order: loopback output -> plugins -> EQ for output
+    /// channels, plugin output -> capture stream for input channels. The mic
+    /// feeding an input channel's plugin sink is linked by name instead.
+    pub fn stage_links(&self) -> Vec<(u32, u32)> {
+        let plugins = self.pw_plugin_sink_id.zip(self.pw_plugin_output_id);
+
+        if self.is_input() {
+            return plugins
+                .zip(self.pw_loopback_capture_id)
+                .map(|((_, output), capture)| vec![(output, capture)])
+                .unwrap_or_default();
+        }
+
+        let mut links = Vec::new();
+        let mut upstream = self.pw_loopback_output_id;
+        for (sink, output) in [plugins, self.pw_eq_sink_id.zip(self.pw_eq_output_id)]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(from) = upstream {
+                links.push((from, sink));
+            }
+            upstream = Some(output);
+        }
+        links
+    }
+
+    /// Whether any slot has a loaded plugin instance.
+    pub fn has_plugin_instances(&self) -> bool {
+        self.plugin_instance_ids.iter().any(Option::is_some)
+    }
+
+    /// Whether the channel needs a plugin filter: it has plugins loaded, is
+    /// delayed to line up with other channels, denoises its mic or has a
+    /// talk mode. Never on a surround channel; see [`Self::fits_filters`].
+    pub fn needs_plugin_filter(&self) -> bool {
+        self.fits_filters()
+            && (self.has_plugin_instances()
+                || self.latency_compensation > 0
+                || self.noise_suppression().is_some()
+                || self.talk_gate().is_some())
+    }
+
+    /// Whether the EQ and plugin filters, which run in stereo, can take the
+    /// channel without folding its surround channels down.
+    pub fn fits_filters(&self) -> bool {
+        !self.layout.has_surround()
+    }
+
+    /// Whether the mic passes audio as far as the talk key goes: held (or
+    /// in its release tail) for push-to-talk, let go for push-to-mute.
+    pub fn talk_open(&self) -> bool {
+        let held = self.talk_pressed || self.talk_release_left > 0.0;
+        match self.talk.mode {
+            TalkMode::Off => true,
+            TalkMode::PushToTalk => held,
+            TalkMode::PushToMute => !held,
+        }
+    }
+
+    /// Talk gate for the plugin filter, if the channel has a talk mode.
+    pub fn talk_gate(&self) -> Option<TalkGateSettings> {
+        (self.is_input() && self.talk.mode != TalkMode::Off).then(|| TalkGateSettings {
+            open: self.talk_open(),
+            fade_ms: self.talk.fade_ms,
+        })
+    }
+
+    /// Whether the channel is heard: not muted and, on input channels, keyed
+    /// open.
+    pub fn is_live(&self) -> bool {
+        !self.muted && (!self.is_input() || self.talk_open())
+    }
+
+    /// Noise suppression settings for the plugin filter, if it is on.
+    pub fn noise_suppression(&self) -> Option<NoiseSuppression> {
+        let settings = &self.noise_suppression_settings;
+        (self.is_input() && self.noise_suppression_enabled).then_some(NoiseSuppression {
+            model: match settings.model {
+                NoiseModel::RNNoise => Model::RNNoise,
+                NoiseModel::Gate => Model::Gate,
+            },
+            vad_threshold: self.vad_threshold,
+            grace_ms: settings.grace_ms,
+            retro_grace_ms: settings.retro_grace_ms,
+            attenuation_limit_db: settings.attenuation_limit_db,
+        })
+    }
+
+    /// Loaded, non-bypassed plugin instances in processing order.
+    pub fn active_plugin_chain(&self) -> Vec<Uuid> {
+        self.plugin_chain
+            .iter()
+            .zip(&self.plugin_instance_ids)
+            .filter(|(slot, _)| !slot.bypassed)
+            .filter_map(|(_, id)| *id)
+            .collect()
+    }
+
+    /// Slot info for the D-Bus API.
+    pub fn plugin_slots(&self) -> Vec<PluginSlotInfo> {
+        self.plugin_chain
+            .iter()
+            .zip(&self.plugin_instance_ids)
+            .map(|(slot, instance_id)| PluginSlotInfo {
+                plugin_id: slot.plugin_id.clone(),
+                instance_id: instance_id.map(|id| id.to_string()).unwrap_or_default(),
+                bypassed: slot.bypassed,
+                sidechain_source: slot
+                    .sidechain_source
+                    .map(|id| id.to_string())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
     pub fn to_channel_info(&self) -> ChannelInfo {
         let (left_db, right_db) = self.meter_levels_db();
         ChannelInfo {
@@ -189,11 +597,42 @@ impl ChannelState {
             muted: self.muted,
             eq_enabled: self.eq_enabled,
             eq_preset: self.eq_preset.clone(),
+            eq_bands: self.eq_bands.iter().map(EqBandInfo::from).collect(),
+            eq_custom: self.eq_custom,
             assigned_apps: self.assigned_apps.clone(),
             output_device: self.output_device_name.clone().unwrap_or_default(),
             meter_levels: (left_db as f64, right_db as f64),
             kind: self.kind,
             input_gain_db: self.input_gain_db as f64,
+            pan: self.pan as f64,
+            width: self.width as f64,
+            layout: self.layout,
+            solo: self.solo,
+            sidetone_enabled: self.sidetone_enabled,
+            sidetone_volume_db: self.sidetone_volume_db as f64,
+            latency_samples: self.plugin_latency + self.latency_compensation,
+            latency_compensation_samples: self.latency_compensation,
+            auto_level: AutoLevelInfo {
+                enabled: self.auto_level.enabled,
+                target_lufs: self.auto_level.target_lufs as f64,
+                max_boost_db: self.auto_level.max_boost_db as f64,
+                max_cut_db: self.auto_level.max_cut_db as f64,
+                speed_db_per_sec: self.auto_level.speed_db_per_sec as f64,
+            },
+            auto_level_gain_db: self.auto_level_gain_db as f64,
+            noise_suppression: NoiseSuppressionInfo {
+                model: self.noise_suppression_settings.model,
+                grace_ms: self.noise_suppression_settings.grace_ms as f64,
+                retro_grace_ms: self.noise_suppression_settings.retro_grace_ms as f64,
+                attenuation_limit_db: self.noise_suppression_settings.attenuation_limit_db as f64,
+            },
+            talking: self.talking,
+            talk: TalkInfo {
+                mode: self.talk.mode,
+                release_ms: self.talk.release_ms as f64,
+                fade_ms: self.talk.fade_ms as f64,
+            },
+            live: self.is_live(),
         }
     }
 
@@ -212,13 +651,77 @@ impl ChannelState {
         )
     }
 
+    /// Effective node volume: fader level plus the auto level gain, minus
+    /// any ducking gain reduction; silent while muted or cut by
+    /// solo-in-place.
     pub fn volume_linear(&self) -> f32 {
-        if self.muted {
+        if self.muted || self.solo_cut {
             0.0
         } else {
-            db_to_linear(self.volume_db)
+            db_to_linear(self.volume_db + self.auto_level_gain_db - self.duck_reduction_db)
+        }
+    }
+
+    /// Node whose volume the channel fader controls: the Audio/Source node
+    /// for input channels, the loopback output stream for output channels.
+    pub fn volume_node(&self) -> Option<u32> {
+        if self.is_input() {
+            self.pw_source_id
+        } else {
+            self.pw_loopback_output_id
         }
     }
+
+    /// Pan and width as applied to the channel's volume node.
+    pub fn balance(&self) -> Balance {
+        Balance::new(self.pan, self.width)
+    }
+}
+
+/// Look up an EQ preset by name, falling back to flat if it no longer exists.
+fn resolve_eq_preset(config_manager: &ConfigManager, name: &str) -> EqPreset {
+    match config_manager.load_eq_preset(name) {
+        Ok(Some(preset)) => preset,
+        Ok(None) => {
+            warn!("EQ preset '{}' not found, using flat", name);
+            EqPreset::flat()
+        }
+        Err(e) => {
+            warn!("Failed to load EQ preset '{}': {}, using flat", name, e);
+            EqPreset::flat()
+        }
+    }
+}
+
+/// Instantiate a channel's configured plugin chain, replacing its instance list.
+fn load_plugin_chain(plugin_manager: &mut PluginManager, channel: &mut ChannelState) {
+    channel.plugin_instance_ids = channel
+        .plugin_chain
+        .iter()
+        .map(|slot| match plugin_manager.load(&slot.plugin_id) {
+            Ok(instance_id) => {
+                for (&index, &value) in &slot.parameters {
+                    plugin_manager.set_parameter(instance_id, index, value);
+                }
+                Some(instance_id)
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to load plugin '{}' for channel '{}': {}",
+                    slot.plugin_id, channel.name, e
+                );
+                None
+            }
+        })
+        .collect();
+
+    if channel.has_plugin_instances() {
+        info!(
+            "Loaded {} plugin(s) for channel '{}'",
+            channel.plugin_instance_ids.iter().flatten().count(),
+            channel.name
+        );
+    }
 }
 
 // NOTE: Keep in sync with canonical implementation in src/audio/control.rs
@@ -230,6 +733,98 @@ fn db_to_linear(db: f32) -> f32 {
     }
 }
 
+/// Convert a D-Bus routing rule into its config form, validating the strings.
+fn routing_rule_from_info(info: &RoutingRuleInfo) -> Result<RoutingRule, ServiceError> {
+    let id = if info.id.is_empty() {
+        Uuid::new_v4()
+    } else {
+        Uuid::parse_str(&info.id)
+            .map_err(|_| ServiceError::InvalidArgument(format!("Invalid rule ID: {}", info.id)))?
+    };
+    let match_target = match info.match_target.to_lowercase().as_str() {
+        "name" => MatchTarget::Name,
+        "binary" => MatchTarget::Binary,
+        "" | "either" => MatchTarget::Either,
+        other => {
+            return Err(ServiceError::InvalidArgument(format!(
+                "Unknown match target: {}",
+                other
+            )))
+        }
+    };
+    let match_type = match_type_from_info(&info.match_type, &info.pattern)?;
+    let conditions = info
+        .conditions
+        .iter()
+        .map(|c| {
+            let property = c.property.trim();
+            if property.is_empty() {
+                return Err(ServiceError::InvalidArgument(
+                    "Rule condition needs a property name".to_string(),
+                ));
+            }
+            Ok(PropertyCondition {
+                property: property.to_string(),
+                match_type: match_type_from_info(&c.match_type, &c.pattern)?,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let combine = match info.combine.to_lowercase().as_str() {
+        "" | "all" | "and" => ConditionCombine::All,
+        "any" | "or" => ConditionCombine::Any,
+        other => {
+            return Err(ServiceError::InvalidArgument(format!(
+                "Unknown condition combine mode: {}",
+                other
+            )))
+        }
+    };
+    if info.pattern.is_empty() && conditions.is_empty() {
+        return Err(ServiceError::InvalidArgument(
+            "Routing rule needs a pattern or at least one property condition".to_string(),
+        ));
+    }
+    if info.target_channel.is_empty() {
+        return Err(ServiceError::InvalidArgument(
+            "Routing rule needs a target channel".to_string(),
+        ));
+    }
+
+    Ok(RoutingRule {
+        id,
+        name: info.name.clone(),
+        enabled: info.enabled,
+        match_target,
+        match_type,
+        target_channel: info.target_channel.clone(),
+        priority: info.priority,
+        conditions,
+        combine,
+    })
+}
+
+/// Parse a D-Bus match type name and pattern, validating regexes.
+fn match_type_from_info(match_type: &str, pattern: &str) -> Result<MatchType, ServiceError> {
+    let pattern = pattern.to_string();
+    Ok(match match_type.to_lowercase().as_str() {
+        "contains" => MatchType::Contains(pattern),
+        "exact" => MatchType::Exact(pattern),
+        "regex" => {
+            regex::Regex::new(&pattern).map_err(|e| {
+                ServiceError::InvalidArgument(format!("Invalid regex '{}': {}", pattern, e))
+            })?;
+            MatchType::Regex(pattern)
+        }
+        "glob" => MatchType::Glob(pattern),
+        other => {
+            return Err(ServiceError::InvalidArgument(format!(
+                "Unknown match type: {}",
+                other
+            )))
+        }
+    })
+}
+
 /// Check if a media name is generic/unhelpful for identification.
 fn is_generic_media_name(name: &str) -> bool {
     matches!(
@@ -532,9 +1127,20 @@ pub struct PwGraphState {
     pub nodes: HashMap<u32, PwNode>,
     pub ports: HashMap<u32, PwPort>,
     pub links: HashMap<u32, PwLink>,
+    /// Node name of WirePlumber's default sink.
+    pub default_sink: Option<String>,
 }
 
 impl PwGraphState {
+    /// The WirePlumber default sink, if it's in the graph.
+    pub fn default_sink_id(&self) -> Option<u32> {
+        let name = self.default_sink.as_deref()?;
+        self.nodes
+            .values()
+            .find(|n| n.media_class == MediaClass::AudioSink && n.name == name)
+            .map(|n| n.id)
+    }
+
     pub fn playback_streams(&self) -> Vec<&PwNode> {
         self.nodes
             .values()
@@ -616,72 +1222,40 @@ impl PwGraphState {
         Some(chosen.id)
     }
 
+    /// Port pairs for linking `output_node` into `input_node`, folding
+    /// channel layouts that don't match (see `crate::audio::routing::pair_ports`).
     pub fn find_port_pairs(&self, output_node: u32, input_node: u32) -> Vec<(u32, u32)> {
-        // Collect and sort ports by channel for consistent ordering
-        let mut output_ports: Vec<_> = self
+        let output_ports: Vec<_> = self
             .ports
             .values()
             .filter(|p| p.node_id == output_node && p.direction == PortDirection::Output)
             .collect();
-        let mut input_ports: Vec<_> = self
+        let input_ports: Vec<_> = self
             .ports
             .values()
             .filter(|p| p.node_id == input_node && p.direction == PortDirection::Input)
             .collect();
+        crate::audio::routing::pair_ports(&output_ports, &input_ports)
+    }
 
-        // Sort by channel first, then by port ID for stability
-        output_ports.sort_by(|a, b| (&a.channel, a.id).cmp(&(&b.channel, b.id)));
-        input_ports.sort_by(|a, b| (&a.channel, a.id).cmp(&(&b.channel, b.id)));
-
-        let mut pairs = Vec::new();
-        let mut used_inputs: std::collections::HashSet<u32> = std::collections::HashSet::new();
-
-        // First pass: match by compatible channels
-        // When there are more outputs than inputs (e.g. stereo→mono), allow
-        // multiple outputs to link to the same input for downmixing.
-        let allow_reuse = output_ports.len() > input_ports.len();
-        for out_port in &output_ports {
-            for in_port in &input_ports {
-                if !allow_reuse && used_inputs.contains(&in_port.id) {
-                    continue;
-                }
-                if out_port.channel.is_compatible(&in_port.channel) {
-                    pairs.push((out_port.id, in_port.id));
-                    used_inputs.insert(in_port.id);
-                    break;
-                }
-            }
-        }
-
-        // Second pass: if no channel matches found, try name-based matching
-        if pairs.is_empty() {
-            for out_port in &output_ports {
-                for in_port in &input_ports {
-                    if used_inputs.contains(&in_port.id) {
-                        continue;
-                    }
-                    let out_name = out_port.name.to_lowercase();
-                    let in_name = in_port.name.to_lowercase();
-                    let is_match = (out_name.contains("_0") && in_name.contains("_0"))
-                        || (out_name.contains("_1") && in_name.contains("_1"));
-
-                    if is_match {
-                        pairs.push((out_port.id, in_port.id));
-                        used_inputs.insert(in_port.id);
-                        break;
-                    }
-                }
-            }
-        }
-
-        // Fallback: pair by sorted position (deterministic order)
-        if pairs.is_empty() && !output_ports.is_empty() && !input_ports.is_empty() {
-            for (out_port, in_port) in output_ports.iter().zip(input_ports.iter()) {
-                pairs.push((out_port.id, in_port.id));
-            }
+    /// Left and right output ports of a node, for taps that always want a
+    /// stereo pair. A node with a single (mono) port feeds both sides.
+    pub fn stereo_output_ports(&self, node_id: u32) -> Option<(u32, u32)> {
+        let mut ports: Vec<_> = self
+            .ports
+            .values()
+            .filter(|p| p.node_id == node_id && p.direction == PortDirection::Output)
+            .collect();
+        ports.sort_by(|a, b| (&a.channel, a.id).cmp(&(&b.channel, b.id)));
+
+        let left = ports.iter().find(|p| p.channel == AudioChannel::FrontLeft);
+        let right = ports.iter().find(|p| p.channel == AudioChannel::FrontRight);
+        match (left, right, ports.as_slice()) {
+            (Some(l), Some(r), _) => Some((l.id, r.id)),
+            (_, _, [only]) => Some((only.id, only.id)),
+            (_, _, [l, r, ..]) => Some((l.id, r.id)),
+            _ => None,
         }
-
-        pairs
     }
 
     pub fn links_from_node(&self, node_id: u32) -> Vec<&PwLink> {
@@ -705,6 +1279,8 @@ pub struct DaemonState {
     pub master_recording_enabled: bool,
     pub master_recording_source_id: Option<u32>,
     pub routing_rules: RoutingRulesConfig,
+    /// App stream nodes already auto-routed, so later property updates
+    /// don't route them again.
     pub auto_routed_apps: HashSet<u32>,
     /// Channels waiting for their sink ports to be ready for auto-routing
     pub pending_auto_route_channels: HashSet<Uuid>,
@@ -718,18 +1294,36 @@ pub struct DaemonState {
     pub last_reconnect_attempt: Option<Instant>,
     /// Number of consecutive reconnection failures (for exponential backoff)
     pub reconnect_failures: u32,
-    /// Pending replacement commands after a noise filter or virtual source destroy.
-    /// When toggling noise suppression, we need to destroy the old node before creating
-    /// the replacement to avoid same-name node conflicts. This map stores the create
-    /// command to dispatch after the destroy event is received.
-    pub pending_ns_replacements: HashMap<Uuid, PwCommand>,
+    /// Pending replacement commands after a virtual source destroy.
+    /// When an input channel's mic changes, we need to destroy the old node before
+    /// creating the replacement to avoid same-name node conflicts. This map stores
+    /// the create command to dispatch after the destroy event is received.
+    pub pending_source_replacements: HashMap<Uuid, PwCommand>,
     /// Port pairs of links being intentionally destroyed during reroute.
     /// Prevents `check_and_restore_managed_link` from restoring links that
     /// were removed on purpose. Each entry is consumed (removed) when the
     /// corresponding `LinkRemoved` event arrives.
     pub suppressed_restores: HashSet<(u32, u32)>,
-    /// PFL/solo monitor device name (UI-owned, round-tripped by daemon).
+    /// Device the monitor bus plays on (`None` = the master output).
     pub monitor_device: Option<String>,
+    /// Monitor bus level in dB.
+    pub monitor_volume_db: f32,
+    pub monitor_muted: bool,
+    /// How soloed channels are heard.
+    pub solo_mode: SoloMode,
+    /// Channels with a CreateEqFilter in flight. Creating a filter replaces any
+    /// existing one for the channel, so overlapping creates would kill each other.
+    pub pending_eq_creates: HashSet<Uuid>,
+    /// Channels with a CreatePluginFilter in flight (same reasoning as `pending_eq_creates`).
+    pub pending_plugin_creates: HashSet<Uuid>,
+    /// Sidechain ducking relationships between channels.
+    pub ducking: Vec<DuckingConfig>,
+    /// Envelope state per ducking relationship, keyed by relationship ID.
+    pub duck_envelopes: HashMap<Uuid, DuckEnvelope>,
+    /// Channel volume fades in progress (scene recall), keyed by channel ID.
+    pub channel_fades: HashMap<Uuid, VolumeFade>,
+    /// Master volume fade in progress (scene recall).
+    pub master_fade: Option<VolumeFade>,
 }
 
 impl DaemonState {
@@ -757,9 +1351,18 @@ impl DaemonState {
             refresh_counter: 0,
             last_reconnect_attempt: None,
             reconnect_failures: 0,
-            pending_ns_replacements: HashMap::new(),
+            pending_source_replacements: HashMap::new(),
             suppressed_restores: HashSet::new(),
             monitor_device: mixer_config.master.monitor_device,
+            monitor_volume_db: mixer_config.master.monitor_volume_db,
+            monitor_muted: mixer_config.master.monitor_muted,
+            solo_mode: mixer_config.master.solo_mode,
+            pending_eq_creates: HashSet::new(),
+            pending_plugin_creates: HashSet::new(),
+            ducking: mixer_config.ducking,
+            duck_envelopes: HashMap::new(),
+            channel_fades: HashMap::new(),
+            master_fade: None,
         }
     }
 
@@ -850,6 +1453,26 @@ impl DaemonState {
         self.apps = apps;
     }
 
+    /// The channel an app should be routed to: an explicit assignment first
+    /// (by identifier or display name), then the best matching routing rule.
+    fn channel_for_app(&self, app: &AppState) -> Option<&ChannelState> {
+        let identifier = app.identifier();
+        let assigned = self.channels.iter().find(|c| {
+            c.assigned_apps
+                .iter()
+                .any(|a| *a == identifier || *a == app.name)
+        });
+        if assigned.is_some() {
+            return assigned;
+        }
+
+        let properties = &self.pw_graph.nodes.get(&app.node_id)?.properties;
+        let rule = self
+            .routing_rules
+            .find_match(&app.name, app.binary.as_deref(), properties)?;
+        self.channels.iter().find(|c| c.name == rule.target_channel)
+    }
+
     pub fn get_routing_rules(&self) -> Vec<RoutingRuleInfo> {
         self.routing_rules
             .rules
@@ -867,19 +1490,162 @@ impl DaemonState {
                 pattern: r.match_type.pattern().to_string(),
                 target_channel: r.target_channel.clone(),
                 priority: r.priority,
+                conditions: r
+                    .conditions
+                    .iter()
+                    .map(|c| RuleConditionInfo {
+                        property: c.property.clone(),
+                        match_type: c.match_type.type_name().to_string(),
+                        pattern: c.match_type.pattern().to_string(),
+                    })
+                    .collect(),
+                combine: match r.combine {
+                    ConditionCombine::All => "all".to_string(),
+                    ConditionCombine::Any => "any".to_string(),
+                },
             })
             .collect()
     }
 }
 
 /// The main daemon service.
+/// What one recording track captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecordSource {
+    Master,
+    Channel(Uuid),
+}
+
+impl RecordSource {
+    /// Source name of the master bus on the D-Bus API.
+    const MASTER: &'static str = "master";
+}
+
+/// A recording in progress: its file writer and how its capture stream is wired.
+struct ActiveRecording {
+    session: RecordingSession,
+    /// One source per track, in stream channel order.
+    sources: Vec<RecordSource>,
+    /// Capture stream node, once it's in the registry.
+    node_id: Option<u32>,
+    /// Which tracks have been linked to their source.
+    linked: Vec<bool>,
+}
+
+impl ActiveRecording {
+    fn status(&self) -> RecordingStatus {
+        RecordingStatus {
+            id: self.session.id.to_string(),
+            sources: self
+                .sources
+                .iter()
+                .map(|s| match s {
+                    RecordSource::Master => RecordSource::MASTER.to_string(),
+                    RecordSource::Channel(id) => id.to_string(),
+                })
+                .collect(),
+            files: self
+                .session
+                .paths
+                .iter()
+                .map(|p| p.to_string_lossy().to_string())
+                .collect(),
+            format: self.session.format.as_str().to_string(),
+            elapsed_secs: self.session.elapsed_secs(),
+            bytes: self.session.bytes(),
+            dropped_frames: self.session.dropped_frames(),
+        }
+    }
+}
+
+/// A channel's instant-replay buffer and how its capture stream is wired.
+struct ActiveReplay {
+    buffer: ReplayBuffer,
+    /// Capture stream node, once it's in the registry.
+    node_id: Option<u32>,
+    /// Whether the channel has been linked into the capture stream.
+    linked: bool,
+}
+
+/// The running spectrum analyzer and what it listens to.
+struct SpectrumSession {
+    /// Analyzed channel; `None` for the master output.
+    channel_id: Option<Uuid>,
+    tap: SpectrumTap,
+    feed: Arc<SpectrumFeed>,
+    analyzer: SpectrumAnalyzer,
+    /// Response of the channel's EQ per band, keyed by the EQ's bands and
+    /// the sample rate.
+    eq_curve: Option<(Vec<EqBand>, u32, Vec<f64>)>,
+}
+
+/// Graph side of the monitor bus (see `crate::audio::monitor`).
+#[derive(Default)]
+struct MonitorBus {
+    /// Stages that exist or are being created (`None` until created).
+    stages: HashMap<MonitorStage, Option<StageNodes>>,
+    /// Port pairs with a CreateLink in flight.
+    pending_links: HashSet<(u32, u32)>,
+    /// Links with a DestroyLink in flight.
+    pending_unlinks: HashSet<u32>,
+}
+
+/// Replay clips copied out of their buffers, to be written off the service lock.
+pub struct ReplaySave {
+    format: RecordingFormat,
+    clips: Vec<(std::path::PathBuf, ReplayClip)>,
+}
+
+impl ReplaySave {
+    /// Write every clip. Returns the file paths.
+    pub fn write(self) -> Result<Vec<String>, ServiceError> {
+        let mut paths = Vec::with_capacity(self.clips.len());
+        for (path, clip) in &self.clips {
+            clip.write(path, self.format)?;
+            paths.push(path.to_string_lossy().to_string());
+        }
+        Ok(paths)
+    }
+}
+
 pub struct DaemonService {
     pub state: DaemonState,
-    pw_thread: Option<PwThread>,
+    /// Running graph backend (`None` while disconnected).
+    backend: Option<Box<dyn GraphBackend>>,
+    /// Starts the graph backend, on startup and on every reconnect.
+    backend_factory: BackendFactory,
     pw_event_rx: Option<mpsc::Receiver<PwEvent>>,
     config_manager: ConfigManager,
     /// Sender for D-Bus signal events.
     signal_tx: Option<tokio_mpsc::UnboundedSender<SignalEvent>>,
+    /// Owns every channel's plugin instances; shared with the PW thread's plugin filters.
+    plugin_manager: PluginManager,
+    /// Recording settings.
+    recording_config: RecordingConfig,
+    /// Running recordings, in start order.
+    recordings: Vec<ActiveRecording>,
+    /// Seconds since the last `RecordingProgress` signals.
+    recording_progress_timer: f32,
+    /// Instant-replay buffers per channel.
+    replays: HashMap<Uuid, ActiveReplay>,
+    /// MIDI controller bindings.
+    midi_config: MidiConfig,
+    /// Controller connections (`None` if the ALSA sequencer is unavailable).
+    midi_ports: Option<MidiPorts>,
+    /// Target waiting for the next control to be moved (learn mode).
+    midi_learn: Option<MidiTarget>,
+    /// Last value sent to or received from each binding's control.
+    midi_feedback: HashMap<Uuid, u8>,
+    /// Seconds since the last controller scan.
+    midi_refresh_timer: f32,
+    /// Monitor bus stages and links.
+    monitor: MonitorBus,
+    /// Loudness readings of the master output.
+    master_loudness: Arc<SharedLoudness>,
+    /// Monitor source the master loudness meter records (`None` while stopped).
+    master_meter_source: Option<String>,
+    /// Spectrum analyzer (`None` while stopped).
+    spectrum: Option<SpectrumSession>,
 }
 
 impl DaemonService {
@@ -888,12 +1654,84 @@ impl DaemonService {
         routing_rules: RoutingRulesConfig,
         config_manager: ConfigManager,
     ) -> Self {
-        Self {
+        let mut plugin_manager = PluginManager::new();
+        let plugin_count = plugin_manager.scan();
+        info!("Plugin scan complete: {} plugins available", plugin_count);
+
+        Self::with_backend(
+            mixer_config,
+            routing_rules,
+            config_manager,
+            plugin_manager,
+            backend::pipewire_backend(),
+        )
+    }
+
+    /// Create a service whose graph is driven through `backend_factory`
+    /// instead of the PipeWire thread. Plugins are loaded from
+    /// `plugin_manager` as given; `new` scans the system's plugin directories.
+    pub fn with_backend(
+        mixer_config: MixerConfig,
+        routing_rules: RoutingRulesConfig,
+        config_manager: ConfigManager,
+        plugin_manager: PluginManager,
+        backend_factory: BackendFactory,
+    ) -> Self {
+        let mut recording_config = config_manager.load_recording_config();
+        // Lengths saved before the range was narrowed
+        recording_config.replay_seconds = recording_config
+            .replay_seconds
+            .clamp(*REPLAY_SECONDS_RANGE.start(), *REPLAY_SECONDS_RANGE.end());
+        let midi_config = config_manager.load_midi_config();
+        let midi_ports = match MidiPorts::new() {
+            Ok(mut ports) => {
+                ports.refresh();
+                Some(ports)
+            }
+            Err(e) => {
+                warn!("MIDI control unavailable: {}", e);
+                None
+            }
+        };
+        let mut service = Self {
             state: DaemonState::new(mixer_config, routing_rules),
-            pw_thread: None,
+            backend: None,
+            backend_factory,
             pw_event_rx: None,
             config_manager,
             signal_tx: None,
+            plugin_manager,
+            recording_config,
+            recordings: Vec::new(),
+            recording_progress_timer: 0.0,
+            replays: HashMap::new(),
+            midi_config,
+            midi_ports,
+            midi_learn: None,
+            midi_feedback: HashMap::new(),
+            midi_refresh_timer: 0.0,
+            monitor: MonitorBus::default(),
+            master_loudness: Arc::new(SharedLoudness::new()),
+            master_meter_source: None,
+            spectrum: None,
+        };
+        service.load_plugin_chains();
+        service.load_eq_presets();
+        service
+    }
+
+    /// Instantiate the saved plugin chain of every channel. Slots whose plugin
+    /// fails to load stay in the chain (so the config isn't lost) but are skipped.
+    fn load_plugin_chains(&mut self) {
+        for channel in &mut self.state.channels {
+            load_plugin_chain(&mut self.plugin_manager, channel);
+        }
+    }
+
+    /// Fill in the bands of every channel that follows an EQ preset.
+    fn load_eq_presets(&mut self) {
+        for channel in self.state.channels.iter_mut().filter(|c| !c.eq_custom) {
+            channel.eq_bands = resolve_eq_preset(&self.config_manager, &channel.eq_preset).bands;
         }
     }
 
@@ -976,14 +1814,14 @@ impl DaemonService {
         }
     }
 
-    /// Start the PipeWire thread.
+    /// Start the graph backend (the PipeWire thread).
     pub fn start_pipewire(&mut self) -> Result<(), ServiceError> {
         let (event_tx, event_rx) = mpsc::channel();
 
-        let pw_thread =
-            PwThread::spawn(event_tx).map_err(|e| ServiceError::PipeWire(e.to_string()))?;
+        let backend =
+            (self.backend_factory)(event_tx).map_err(|e| ServiceError::PipeWire(e.to_string()))?;
 
-        self.pw_thread = Some(pw_thread);
+        self.backend = Some(backend);
         self.pw_event_rx = Some(event_rx);
 
         info!("PipeWire thread started");
@@ -992,16 +1830,16 @@ impl DaemonService {
 
     /// Wait for initial PipeWire discovery to complete.
     pub fn wait_for_discovery(&mut self) {
-        let max_wait = Duration::from_millis(1500);
-        let min_wait = Duration::from_millis(300);
-        let poll_interval = Duration::from_millis(50);
-        let start = std::time::Instant::now();
+        const POLL_INTERVAL: Duration = Duration::from_millis(50);
+        const MIN_POLLS: u32 = 6; // 300ms
+        const MAX_POLLS: u32 = 30; // 1.5s
+        const STABILITY_THRESHOLD: u32 = 4; // 200ms of no new nodes
+        let start = Instant::now();
 
         let mut last_node_count = 0;
         let mut stable_iterations = 0;
-        const STABILITY_THRESHOLD: u32 = 4; // 200ms of no new nodes
 
-        while start.elapsed() < max_wait {
+        for poll in 0..MAX_POLLS {
             self.process_pw_events();
 
             let current_count = self.state.pw_graph.nodes.len();
@@ -1013,15 +1851,12 @@ impl DaemonService {
             }
 
             // Exit early if we have nodes and they've been stable for a bit
-            // but ensure we wait at least min_wait
-            if start.elapsed() >= min_wait
-                && current_count > 0
-                && stable_iterations >= STABILITY_THRESHOLD
-            {
+            // but ensure we wait at least MIN_POLLS
+            if poll >= MIN_POLLS && current_count > 0 && stable_iterations >= STABILITY_THRESHOLD {
                 break;
             }
 
-            std::thread::sleep(poll_interval);
+            self.wait_for_graph(POLL_INTERVAL);
         }
 
         // Final refresh of app list
@@ -1036,18 +1871,65 @@ impl DaemonService {
         );
     }
 
+    /// Destroy sootmix nodes left over from previous runs (e.g. after a
+    /// crash). Must run after discovery and before channels are restored.
+    pub fn cleanup_orphaned_nodes(&mut self) {
+        let orphaned: Vec<u32> = self
+            .state
+            .pw_graph
+            .nodes
+            .values()
+            .filter(|n| {
+                ["sootmix.", "output.sootmix.", "input.sootmix."]
+                    .iter()
+                    .any(|prefix| n.name.starts_with(prefix))
+            })
+            .map(|n| n.id)
+            .collect();
+
+        if orphaned.is_empty() {
+            info!("No orphaned sootmix nodes found");
+            return;
+        }
+
+        info!(
+            "Found {} orphaned sootmix nodes, cleaning up...",
+            orphaned.len()
+        );
+        for node_id in orphaned {
+            debug!("Destroying orphaned node {}", node_id);
+            self.send_pw_command(PwCommand::DestroyNode { node_id });
+        }
+
+        // Let the removals reach the graph before channels reuse the names
+        self.wait_for_graph(Duration::from_millis(100));
+        self.process_pw_events();
+    }
+
     /// Restore channels from config.
     pub fn restore_channels(&mut self) -> Result<(), ServiceError> {
+        self.create_channel_nodes();
+        self.start_replay_buffers();
+        self.sync_master_meter();
+
+        self.wait_for_graph(Duration::from_millis(300));
+        self.process_pw_events();
+
+        Ok(())
+    }
+
+    /// Create the virtual sink/source of every channel that doesn't have one yet.
+    fn create_channel_nodes(&mut self) {
         // Restore output channels (virtual sinks)
-        let sinks_to_create: Vec<(Uuid, String)> = self
+        let sinks_to_create: Vec<(Uuid, String, ChannelLayout)> = self
             .state
             .channels
             .iter()
             .filter(|c| c.is_managed && !c.is_input() && c.pw_sink_id.is_none())
-            .map(|c| (c.id, c.name.clone()))
+            .map(|c| (c.id, c.name.clone(), c.layout))
             .collect();
 
-        for (id, name) in sinks_to_create {
+        for (id, name, layout) in sinks_to_create {
             // Get target device for this channel (per-channel or master)
             let target_device = self
                 .state
@@ -1065,11 +1947,13 @@ impl DaemonService {
                 channel_id: id,
                 name,
                 target_device,
+                layout,
             });
         }
 
         // Restore input channels (virtual sources)
-        let sources_to_create: Vec<(Uuid, String, Option<String>, bool, f32)> = self
+        // Noise suppression comes up with the plugin filter once the source is ready
+        let sources_to_create: Vec<(Uuid, String, Option<String>, bool)> = self
             .state
             .channels
             .iter()
@@ -1080,39 +1964,27 @@ impl DaemonService {
                     c.name.clone(),
                     c.input_device_name.clone(),
                     c.noise_suppression_enabled,
-                    c.vad_threshold,
                 )
             })
             .collect();
 
-        for (id, name, target_device, ns_enabled, vad_threshold) in sources_to_create {
+        for (id, name, target_device, ns_enabled) in sources_to_create {
             info!(
                 "Restoring input channel: {} ({}) target={:?} ns={}",
                 name, id, target_device, ns_enabled
             );
-
-            if ns_enabled {
-                // Create with noise suppression
-                self.send_pw_command(PwCommand::CreateNativeNoiseFilter {
-                    channel_id: id,
-                    name,
-                    target_mic: target_device,
-                    vad_threshold,
-                });
-            } else {
-                // Create plain virtual source
-                self.send_pw_command(PwCommand::CreateVirtualSource {
-                    channel_id: id,
-                    name,
-                    target_device,
-                });
-            }
+            self.send_pw_command(PwCommand::CreateVirtualSource {
+                channel_id: id,
+                name,
+                target_device,
+            });
         }
+    }
 
-        std::thread::sleep(Duration::from_millis(300));
-        self.process_pw_events();
-
-        Ok(())
+    /// Log and drop the WASM plugin instances that trapped on an audio
+    /// thread.
+    pub fn collect_plugin_faults(&self) {
+        self.plugin_manager.collect_faults();
     }
 
     /// Process pending PipeWire events.
@@ -1130,7 +2002,7 @@ impl DaemonService {
         }
 
         // If PW disconnected during event processing, the receiver was dropped
-        if !self.state.pw_connected && self.pw_thread.is_none() {
+        if !self.state.pw_connected && self.backend.is_none() {
             self.attempt_reconnect_if_needed();
         }
 
@@ -1198,19 +2070,27 @@ impl DaemonService {
                 self.state.pw_connected = false;
                 warn!("PipeWire disconnected, will attempt reconnection");
                 // Drop the old PW thread so we can create a new one
-                self.pw_thread = None;
+                self.backend = None;
                 self.pw_event_rx = None;
                 // Clear stale PW state
                 self.state.pw_graph = PwGraphState::default();
                 for channel in &mut self.state.channels {
                     channel.pw_sink_id = None;
                     channel.pw_loopback_output_id = None;
+                    channel.pw_eq_sink_id = None;
+                    channel.pw_eq_output_id = None;
+                    channel.pw_plugin_sink_id = None;
+                    channel.pw_plugin_output_id = None;
                 }
                 self.state.auto_routed_apps.clear();
                 self.state.pending_auto_route_channels.clear();
                 self.state.pending_route_loopbacks.clear();
                 self.state.suppressed_restores.clear();
+                self.state.pending_eq_creates.clear();
+                self.state.pending_plugin_creates.clear();
                 self.state.master_recording_source_id = None;
+                self.monitor = MonitorBus::default();
+                self.master_meter_source = None;
             }
             PwEvent::NodeAdded(node) => {
                 let node_id = node.id;
@@ -1237,6 +2117,7 @@ impl DaemonService {
                 // re-route any channels whose output device matches this sink.
                 if is_hw_sink {
                     self.try_reroute_channels_to_device(node_id, &node_name, &node_desc);
+                    self.sync_master_meter();
                 }
             }
             PwEvent::NodeRemoved(id) => {
@@ -1253,10 +2134,14 @@ impl DaemonService {
                 });
 
                 self.state.pw_graph.nodes.remove(&id);
+                self.state.auto_routed_apps.remove(&id);
                 self.update_apps_and_emit_signals();
 
                 // Check if this was a channel's sink or loopback output and clear stale IDs
-                let mut channels_to_recreate: Vec<(Uuid, String, Option<String>)> = Vec::new();
+                let mut channels_to_recreate: Vec<(Uuid, String, Option<String>, ChannelLayout)> =
+                    Vec::new();
+                let mut eq_to_recreate: Vec<Uuid> = Vec::new();
+                let mut plugins_to_recreate: Vec<Uuid> = Vec::new();
                 for channel in &mut self.state.channels {
                     if channel.pw_sink_id == Some(id) {
                         warn!(
@@ -1270,7 +2155,12 @@ impl DaemonService {
                                 .output_device_name
                                 .clone()
                                 .or_else(|| self.state.master_output.clone());
-                            channels_to_recreate.push((channel.id, channel.name.clone(), target));
+                            channels_to_recreate.push((
+                                channel.id,
+                                channel.name.clone(),
+                                target,
+                                channel.layout,
+                            ));
                         }
                     } else if channel.pw_loopback_output_id == Some(id) {
                         warn!(
@@ -1278,11 +2168,45 @@ impl DaemonService {
                             channel.name, id
                         );
                         channel.pw_loopback_output_id = None;
+                    } else if channel.pw_eq_sink_id == Some(id)
+                        || channel.pw_eq_output_id == Some(id)
+                    {
+                        warn!(
+                            "Channel '{}' EQ node {} was removed externally",
+                            channel.name, id
+                        );
+                        channel.pw_eq_sink_id = None;
+                        channel.pw_eq_output_id = None;
+                        if channel.eq_enabled {
+                            eq_to_recreate.push(channel.id);
+                        }
+                    } else if channel.pw_plugin_sink_id == Some(id)
+                        || channel.pw_plugin_output_id == Some(id)
+                    {
+                        warn!(
+                            "Channel '{}' plugin filter node {} was removed externally",
+                            channel.name, id
+                        );
+                        channel.pw_plugin_sink_id = None;
+                        channel.pw_plugin_output_id = None;
+                        plugins_to_recreate.push(channel.id);
                     }
                 }
 
+                // Bypass the dead EQ so the channel keeps playing, then bring it back
+                for channel_id in eq_to_recreate {
+                    self.route_channel_stages(channel_id);
+                    self.send_create_eq_filter(channel_id);
+                }
+
+                // Same for a dead plugin filter (recreating replaces its surviving half)
+                for channel_id in plugins_to_recreate {
+                    self.route_channel_stages(channel_id);
+                    self.sync_plugin_filter(channel_id);
+                }
+
                 // Auto-recreate managed virtual sinks that were killed externally
-                for (channel_id, name, target_device) in channels_to_recreate {
+                for (channel_id, name, target_device, layout) in channels_to_recreate {
                     info!(
                         "Recreating virtual sink for channel '{}' after external removal",
                         name
@@ -1291,6 +2215,7 @@ impl DaemonService {
                         channel_id,
                         name,
                         target_device,
+                        layout,
                     });
                 }
 
@@ -1298,11 +2223,42 @@ impl DaemonService {
                 // to the fallback device (configured master or system default)
                 if was_hw_sink {
                     self.try_fallback_orphaned_channels();
+                    self.sync_master_meter();
+                }
+
+                // A monitor stage killed externally is recreated; a lost
+                // monitor device falls back to the master output
+                let dead_stage = self
+                    .monitor
+                    .stages
+                    .iter()
+                    .find(|(_, n)| n.is_some_and(|n| n.sink == id || n.output == id))
+                    .map(|(&stage, n)| (stage, n.map(|n| n.sink)));
+                if let Some((stage, sink)) = dead_stage {
+                    warn!(
+                        "Monitor stage {:?} node {} was removed externally",
+                        stage, id
+                    );
+                    self.monitor.stages.remove(&stage);
+                    if let Some(sink) = sink.filter(|&sink| sink != id) {
+                        self.send_pw_command(PwCommand::DestroyVirtualSink { node_id: sink });
+                    }
+                }
+                if dead_stage.is_some() || (was_hw_sink && !self.monitor.stages.is_empty()) {
+                    self.sync_monitor();
                 }
             }
             PwEvent::NodeChanged(node) => {
+                let node_id = node.id;
+                let node_name = node.name.clone();
                 self.state.pw_graph.nodes.insert(node.id, node);
                 self.update_apps_and_emit_signals();
+
+                // Binding a stream fills in properties the registry global
+                // lacked, which a rule's conditions may be waiting on
+                if !self.state.auto_routed_apps.contains(&node_id) {
+                    self.try_auto_route_app(node_id, &node_name);
+                }
             }
             PwEvent::PortAdded(port) => {
                 let port_node_id = port.node_id;
@@ -1338,7 +2294,7 @@ impl DaemonService {
                     .state
                     .channels
                     .iter()
-                    .find(|c| c.pw_sink_id == Some(port_node_id) && !c.assigned_apps.is_empty())
+                    .find(|c| c.pw_sink_id == Some(port_node_id))
                     .map(|c| c.id);
 
                 if let Some(channel_id) = channel_to_route {
@@ -1349,15 +2305,28 @@ impl DaemonService {
                     self.try_auto_route_pending_apps(channel_id);
                 }
 
-                // Check if this port belongs to a loopback output that needs routing
-                // to the hardware sink. This handles the timing issue where
-                // RouteChannelToDevice fires before the loopback output ports exist.
-                let loopback_route = self
-                    .state
-                    .channels
-                    .iter()
-                    .find(|c| c.pw_loopback_output_id == Some(port_node_id))
-                    .map(|c| (c.id, c.pw_loopback_output_id));
+                // Recording and replay taps wait for both the source's and the
+                // capture stream's ports, which can arrive in either order
+                if self.recordings.iter().any(|r| r.linked.contains(&false))
+                    || self.replays.values().any(|r| !r.linked)
+                {
+                    self.link_recordings();
+                }
+
+                // Same for the monitor bus: stages, channel taps and devices
+                if !self.monitor.stages.is_empty() && self.is_monitor_port_node(port_node_id) {
+                    self.sync_monitor();
+                }
+
+                // Check if this port belongs to a loopback output (or EQ output) that
+                // needs routing to the hardware sink. This handles the timing issue where
+                // RouteChannelToDevice fires before the output stage ports exist.
+                let loopback_route = self
+                    .state
+                    .channels
+                    .iter()
+                    .find(|c| c.output_stage_id() == Some(port_node_id))
+                    .map(|c| (c.id, c.output_stage_id()));
 
                 if let Some((channel_id, Some(loopback_id))) = loopback_route {
                     // Check if this loopback output already has links or a pending route
@@ -1384,6 +2353,52 @@ impl DaemonService {
                     }
                 }
 
+                // Same timing issue for the links between a channel's processing
+                // stages (loopback -> plugins -> EQ): either side's ports may
+                // arrive after the route was dispatched.
+                let stage_routes: Vec<(Uuid, u32, u32)> = self
+                    .state
+                    .channels
+                    .iter()
+                    .flat_map(|c| {
+                        c.stage_links()
+                            .into_iter()
+                            .map(move |(from, to)| (c.id, from, to))
+                    })
+                    .filter(|&(_, from, to)| from == port_node_id || to == port_node_id)
+                    .collect();
+
+                for (channel_id, from, to) in stage_routes {
+                    let linked = self
+                        .state
+                        .pw_graph
+                        .links
+                        .values()
+                        .any(|l| l.output_node == from && l.input_node == to);
+                    let is_pending = self.state.pending_route_loopbacks.contains(&from);
+
+                    if !linked && !is_pending {
+                        debug!(
+                            "Stage nodes {} -> {} got new ports, retrying route",
+                            from, to
+                        );
+                        self.suppress_and_route(from, Some(to), Some(channel_id));
+                    }
+                }
+
+                // An input channel's mic is linked into its plugin sink, whose
+                // ports may likewise arrive after the filter was reported.
+                let plugin_input_channel = self
+                    .state
+                    .channels
+                    .iter()
+                    .find(|c| c.is_input() && c.pw_plugin_sink_id == Some(port_node_id))
+                    .map(|c| c.id);
+
+                if let Some(channel_id) = plugin_input_channel {
+                    self.relink_input_mic(channel_id);
+                }
+
                 // Check if this port belongs to a hardware sink that channels are
                 // routed to. This handles the timing issue where a Bluetooth device
                 // reconnects and try_reroute_channels_to_device fires before the
@@ -1398,14 +2413,14 @@ impl DaemonService {
                     });
 
                 if is_hw_sink {
-                    // Find channels whose loopback output should be linked to this
+                    // Find channels whose output stage should be linked to this
                     // sink but currently has no links (routing raced ahead of ports).
                     let orphaned_loopbacks: Vec<(Uuid, u32)> = self
                         .state
                         .channels
                         .iter()
                         .filter_map(|c| {
-                            let loopback_id = c.pw_loopback_output_id?;
+                            let loopback_id = c.output_stage_id()?;
                             if self.state.pending_route_loopbacks.contains(&loopback_id) {
                                 return None;
                             }
@@ -1449,6 +2464,9 @@ impl DaemonService {
 
                 // Clear pending route flag now that a link from this loopback exists
                 self.state.pending_route_loopbacks.remove(&link.output_node);
+                self.monitor
+                    .pending_links
+                    .remove(&(link.output_port, link.input_port));
 
                 // Check if this is a link from an assigned app to the wrong sink.
                 // WirePlumber may create these links competing with our routing.
@@ -1459,6 +2477,15 @@ impl DaemonService {
                 let removed_link = self.state.pw_graph.links.remove(&id);
                 if let Some(link) = removed_link {
                     self.check_and_restore_managed_link(&link);
+                    self.handle_recording_link_removed(&link);
+                    // Restore monitor links unless we removed them
+                    let intended = self.monitor.pending_unlinks.remove(&id);
+                    if !intended
+                        && (self.is_monitor_node(link.input_node)
+                            || self.is_monitor_node(link.output_node))
+                    {
+                        self.sync_monitor();
+                    }
                 }
             }
             PwEvent::RouteFinished {
@@ -1485,8 +2512,11 @@ impl DaemonService {
                     }
                 }
             }
-            PwEvent::DefaultSinkChanged => {
+            PwEvent::DefaultSinkChanged { name } => {
+                self.state.pw_graph.default_sink = name;
                 self.reroute_system_default_channels();
+                self.sync_monitor();
+                self.sync_master_meter();
             }
             PwEvent::NodeVolumeChanged {
                 node_id,
@@ -1522,6 +2552,7 @@ impl DaemonService {
                 node_id,
                 loopback_output_node_id,
                 meter_levels,
+                loudness,
             } => {
                 let channel_update = self
                     .state
@@ -1532,6 +2563,7 @@ impl DaemonService {
                         channel.pw_sink_id = Some(node_id);
                         channel.pw_loopback_output_id = loopback_output_node_id;
                         channel.atomic_meter_levels = meter_levels;
+                        channel.loudness = loudness;
                         info!(
                             "Virtual sink created for channel '{}': sink={}, loopback={:?}",
                             channel.name, node_id, loopback_output_node_id
@@ -1539,11 +2571,16 @@ impl DaemonService {
                         (
                             channel.volume_linear(),
                             channel.muted,
+                            channel.balance(),
                             loopback_output_node_id,
                         )
                     });
 
-                if let Some((volume, muted, Some(loopback_id))) = channel_update {
+                if let Some((volume, muted, balance, Some(loopback_id))) = channel_update {
+                    self.send_pw_command(PwCommand::SetBalance {
+                        node_id: loopback_id,
+                        balance,
+                    });
                     self.send_pw_command(PwCommand::SetVolume {
                         node_id: loopback_id,
                         volume,
@@ -1554,9 +2591,9 @@ impl DaemonService {
                             muted: true,
                         });
                     }
-                    // Route loopback output to the channel's configured device (or master fallback)
-                    let target_device_id =
-                        self.desired_output_node_for_channel(channel_id);
+                    // Route loopback output to the channel's first running stage
+                    // (plugins or EQ) or its configured device (or master fallback)
+                    let target_device_id = self.next_stage_target(channel_id, loopback_id);
                     self.suppress_and_route(
                         loopback_id,
                         target_device_id,
@@ -1564,30 +2601,52 @@ impl DaemonService {
                     );
                 }
 
+                // Bring up the channel's EQ; audio goes straight to the device until
+                // the filter is ready and EqFilterCreated re-routes through it.
+                let needs_eq = self
+                    .state
+                    .channels
+                    .iter()
+                    .any(|c| c.id == channel_id && c.eq_enabled && c.pw_eq_sink_id.is_none());
+                if needs_eq {
+                    self.send_create_eq_filter(channel_id);
+                }
+                self.sync_plugin_filter(channel_id);
+
                 // Mark channel for pending auto-routing (ports may not be ready yet)
                 self.state.pending_auto_route_channels.insert(channel_id);
 
                 // Try immediately in case ports are already available
                 self.try_auto_route_pending_apps(channel_id);
+                self.sync_monitor();
             }
             PwEvent::VirtualSinkDestroyed { node_id } => {
                 let mut replacement_channel_id = None;
+                let mut orphaned_plugin_filter = None;
                 for channel in &mut self.state.channels {
                     if channel.pw_sink_id == Some(node_id) {
                         channel.pw_sink_id = None;
                         channel.pw_loopback_output_id = None;
                     }
-                    // Also handle input channel source destruction (used when enabling NS)
+                    // Also handle input channel source destruction (used when changing the mic)
                     if channel.pw_source_id == Some(node_id) {
                         channel.pw_source_id = None;
                         channel.pw_loopback_capture_id = None;
                         replacement_channel_id = Some(channel.id);
+                        if channel.pw_plugin_sink_id.is_some() {
+                            orphaned_plugin_filter = Some(channel.id);
+                        }
                     }
                 }
-                // Check for pending replacement (e.g., creating noise filter after
-                // the plain virtual source was destroyed when toggling NS on)
+                // The plugin filter fed the capture stream that just went away; it is
+                // recreated with the next virtual source.
+                if let Some(ch_id) = orphaned_plugin_filter {
+                    self.remove_plugin_filter(ch_id);
+                }
+                // Check for pending replacement (e.g., recreating the virtual source
+                // on a new mic once the old one is gone)
                 if let Some(ch_id) = replacement_channel_id {
-                    if let Some(cmd) = self.state.pending_ns_replacements.remove(&ch_id) {
+                    if let Some(cmd) = self.state.pending_source_replacements.remove(&ch_id) {
                         info!("Dispatching pending replacement for channel {}", ch_id);
                         self.send_pw_command(cmd);
                     }
@@ -1598,14 +2657,16 @@ impl DaemonService {
                 source_node_id,
                 loopback_capture_node_id,
                 meter_levels,
+                loudness,
             } => {
                 // Get channel info and update state
-                let (target_mic, capture_id, volume, muted) = if let Some(channel) =
+                let (target_mic, capture_id, volume, muted, balance) = if let Some(channel) =
                     self.state.channels.iter_mut().find(|c| c.id == channel_id)
                 {
                     channel.pw_source_id = Some(source_node_id);
                     channel.pw_loopback_capture_id = loopback_capture_node_id;
                     channel.atomic_meter_levels = meter_levels;
+                    channel.loudness = loudness;
                     info!(
                         "Virtual source created for input channel '{}': source={}, capture={:?}",
                         channel.name, source_node_id, loopback_capture_node_id
@@ -1615,12 +2676,17 @@ impl DaemonService {
                         loopback_capture_node_id,
                         channel.volume_linear(),
                         channel.muted,
+                        channel.balance(),
                     )
                 } else {
-                    (None, None, 1.0, false)
+                    (None, None, 1.0, false, Balance::default())
                 };
 
-                // Apply saved volume and mute to the new source node
+                // Apply saved volume, balance and mute to the new source node
+                self.send_pw_command(PwCommand::SetBalance {
+                    node_id: source_node_id,
+                    balance,
+                });
                 self.send_pw_command(PwCommand::SetVolume {
                     node_id: source_node_id,
                     volume,
@@ -1654,6 +2720,9 @@ impl DaemonService {
                          The pw-loopback capture node was not found during virtual source creation."
                     );
                 }
+
+                self.sync_plugin_filter(channel_id);
+                self.sync_monitor();
             }
             PwEvent::RecordingSourceCreated { name, node_id } => {
                 info!("Recording source created: {} (node {})", name, node_id);
@@ -1665,61 +2734,183 @@ impl DaemonService {
                     self.state.master_recording_enabled = false;
                 }
             }
-            PwEvent::NativeNoiseFilterCreated {
+            PwEvent::EqFilterCreated {
                 channel_id,
-                source_node_id,
+                sink_node_id,
+                output_node_id,
             } => {
-                info!(
-                    "Native noise filter created for channel {}: source_node={}",
-                    channel_id, source_node_id
-                );
-                // Update channel state - apps should connect to the noise-filtered source
-                let (volume, muted) = if let Some(channel) =
-                    self.state.channels.iter_mut().find(|c| c.id == channel_id)
-                {
-                    channel.noise_suppression_enabled = true;
-                    channel.pw_source_id = Some(source_node_id);
-                    // Clear the old loopback capture ID since we destroyed it
-                    channel.pw_loopback_capture_id = None;
-                    (channel.volume_linear(), channel.muted)
-                } else {
-                    (1.0, false)
+                self.state.pending_eq_creates.remove(&channel_id);
+                let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == channel_id)
+                else {
+                    // Channel was deleted while the filter was starting
+                    self.send_pw_command(PwCommand::DestroyEqFilter { channel_id });
+                    return;
                 };
 
-                // Apply saved volume and mute to the new noise filter source node
-                self.send_pw_command(PwCommand::SetVolume {
-                    node_id: source_node_id,
-                    volume,
-                });
-                if muted {
-                    self.send_pw_command(PwCommand::SetMute {
-                        node_id: source_node_id,
-                        muted: true,
-                    });
+                if !channel.eq_enabled {
+                    // EQ was switched off while the filter was starting
+                    info!(
+                        "EQ for channel '{}' disabled before filter was ready, destroying",
+                        channel.name
+                    );
+                    self.send_pw_command(PwCommand::DestroyEqFilter { channel_id });
+                    return;
                 }
+
+                info!(
+                    "EQ filter created for channel '{}': sink={}, output={}",
+                    channel.name, sink_node_id, output_node_id
+                );
+                channel.pw_eq_sink_id = Some(sink_node_id);
+                channel.pw_eq_output_id = Some(output_node_id);
+
+                // previous stage -> EQ sink, EQ output -> device
+                self.route_channel_stages(channel_id);
+                // The filter was created with the bands of the time; apply a
+                // preset chosen or bands edited while it was starting
+                self.apply_channel_eq(channel_id);
             }
-            PwEvent::NativeNoiseFilterDestroyed { channel_id } => {
-                info!("Native noise filter destroyed for channel {}", channel_id);
+            PwEvent::EqFilterDestroyed { channel_id } => {
+                debug!("EQ filter destroyed for channel {}", channel_id);
+            }
+            PwEvent::EqFilterFailed { channel_id, error } => {
+                error!("EQ filter failed for channel {}: {}", channel_id, error);
+                self.state.pending_eq_creates.remove(&channel_id);
                 if let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == channel_id) {
-                    channel.noise_suppression_enabled = false;
-                    channel.pw_source_id = None;
+                    channel.eq_enabled = false;
+                    let info = channel.to_channel_info();
+                    self.emit_signal(SignalEvent::ChannelUpdated(info));
+                    self.save_config();
+                }
+            }
+            PwEvent::PluginFilterCreated {
+                channel_id,
+                sink_node_id,
+                output_node_id,
+            } => {
+                self.state.pending_plugin_creates.remove(&channel_id);
+                let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == channel_id)
+                else {
+                    // Channel was deleted while the filter was starting
+                    self.send_pw_command(PwCommand::DestroyPluginFilter { channel_id });
+                    return;
+                };
+
+                if !channel.needs_plugin_filter() {
+                    // Last plugin was removed while the filter was starting
+                    info!(
+                        "Plugins for channel '{}' removed before filter was ready, destroying",
+                        channel.name
+                    );
+                    self.send_pw_command(PwCommand::DestroyPluginFilter { channel_id });
+                    return;
                 }
-                // Check for pending replacement (e.g., creating a plain virtual source
-                // after noise filter was destroyed when toggling NS off)
-                if let Some(cmd) = self.state.pending_ns_replacements.remove(&channel_id) {
-                    info!("Dispatching pending replacement for channel {}", channel_id);
-                    self.send_pw_command(cmd);
+
+                info!(
+                    "Plugin filter created for channel '{}': sink={}, output={}",
+                    channel.name, sink_node_id, output_node_id
+                );
+                channel.pw_plugin_sink_id = Some(sink_node_id);
+                channel.pw_plugin_output_id = Some(output_node_id);
+
+                // The chain may have been edited while the filter was starting
+                let plugin_chain = channel.active_plugin_chain();
+                let noise_suppression = channel.noise_suppression();
+                let talk_gate = channel.talk_gate();
+                let compensation = channel.latency_compensation;
+                let reported = channel.plugin_latency + compensation;
+                self.send_pw_command(PwCommand::UpdatePluginChain {
+                    channel_id,
+                    plugin_chain,
+                });
+                self.send_pw_command(PwCommand::SetNoiseSuppression {
+                    channel_id,
+                    settings: noise_suppression,
+                });
+                self.send_pw_command(PwCommand::SetTalkGate {
+                    channel_id,
+                    settings: talk_gate,
+                });
+                self.send_pw_command(PwCommand::SetPluginLatency {
+                    channel_id,
+                    compensation,
+                    reported,
+                });
+
+                // previous stage (or mic) -> plugin sink, plugin output -> next stage
+                self.route_channel_stages(channel_id);
+            }
+            PwEvent::PluginFilterFailed { channel_id, error } => {
+                error!("Plugin filter failed for channel {}: {}", channel_id, error);
+                self.state.pending_plugin_creates.remove(&channel_id);
+            }
+            PwEvent::RecordStreamCreated {
+                recording_id,
+                node_id,
+            } => {
+                if let Some(recording) = self
+                    .recordings
+                    .iter_mut()
+                    .find(|r| r.session.id == recording_id)
+                {
+                    recording.node_id = Some(node_id);
+                    self.link_recordings();
                 }
             }
-            PwEvent::NativeNoiseFilterFailed { channel_id, error } => {
+            PwEvent::RecordStreamFailed {
+                recording_id,
+                error,
+            } => {
                 error!(
-                    "Native noise filter failed for channel {}: {}",
-                    channel_id, error
+                    "Record stream failed for recording {}: {}",
+                    recording_id, error
                 );
-                if let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == channel_id) {
-                    channel.noise_suppression_enabled = false;
+                if let Err(e) = self.stop_recording(&recording_id.to_string()) {
+                    error!("Failed to stop recording {}: {}", recording_id, e);
+                }
+            }
+            PwEvent::ReplayStreamCreated {
+                channel_id,
+                node_id,
+            } => {
+                if let Some(replay) = self.replays.get_mut(&channel_id) {
+                    replay.node_id = Some(node_id);
+                    self.link_recordings();
                 }
             }
+            PwEvent::ReplayStreamFailed { channel_id, error } => {
+                error!("Replay stream failed for channel {}: {}", channel_id, error);
+                self.replays.remove(&channel_id);
+            }
+            PwEvent::MonitorStageCreated {
+                stage,
+                sink_node_id,
+                output_node_id,
+            } => {
+                if self.monitor.stages.get(&stage) == Some(&None) {
+                    info!(
+                        "Monitor stage {:?} created: sink={}, output={}",
+                        stage, sink_node_id, output_node_id
+                    );
+                    self.monitor.stages.insert(
+                        stage,
+                        Some(StageNodes {
+                            sink: sink_node_id,
+                            output: output_node_id,
+                        }),
+                    );
+                    self.apply_monitor_levels(stage);
+                    self.sync_monitor();
+                } else {
+                    self.send_pw_command(PwCommand::DestroyVirtualSink {
+                        node_id: sink_node_id,
+                    });
+                }
+            }
+            PwEvent::MonitorStageFailed { stage, error } => {
+                error!("Monitor stage {:?} failed: {}", stage, error);
+                self.monitor.stages.remove(&stage);
+            }
             PwEvent::Error(msg) => {
                 error!("PipeWire error: {}", msg);
             }
@@ -1727,29 +2918,54 @@ impl DaemonService {
     }
 
     pub fn send_pw_command(&self, cmd: PwCommand) {
-        if let Some(ref pw) = self.pw_thread {
-            if let Err(e) = pw.send(cmd) {
+        if let Some(ref backend) = self.backend {
+            if let Err(e) = backend.send(cmd) {
                 error!("Failed to send PW command: {}", e);
             }
         }
     }
 
+    /// Give the backend `delay` to act on the commands sent so far.
+    fn wait_for_graph(&self, delay: Duration) {
+        if let Some(ref backend) = self.backend {
+            backend.wait(delay);
+        }
+    }
+
     pub fn shutdown(&mut self) {
         info!("Shutting down daemon service");
         self.save_config();
+        for mut recording in self.recordings.drain(..) {
+            if let Err(e) = recording.session.stop() {
+                error!(
+                    "Failed to finalize recording {}: {}",
+                    recording.session.id, e
+                );
+            }
+        }
+        self.replays.clear();
         crate::audio::virtual_sink::destroy_all_virtual_sinks();
-        if let Some(pw) = self.pw_thread.take() {
-            pw.shutdown();
+        crate::audio::eq_filter::destroy_all_eq_filters();
+        if let Some(backend) = self.backend.take() {
+            backend.shutdown();
         }
     }
 
-    pub fn save_config(&self) {
-        let config = MixerConfig {
+    /// Snapshot of the persisted mixer state. Volumes mid-fade are saved at
+    /// their fade target.
+    fn mixer_config(&self) -> MixerConfig {
+        MixerConfig {
             master: crate::config::MasterConfig {
-                volume_db: self.state.master_volume_db,
+                volume_db: self
+                    .state
+                    .master_fade
+                    .map_or(self.state.master_volume_db, |f| f.target_db()),
                 muted: self.state.master_muted,
                 output_device: self.state.master_output.clone(),
                 monitor_device: self.state.monitor_device.clone(),
+                monitor_volume_db: self.state.monitor_volume_db,
+                monitor_muted: self.state.monitor_muted,
+                solo_mode: self.state.solo_mode,
             },
             channels: self
                 .state
@@ -1760,10 +2976,15 @@ impl DaemonService {
                     name: c.name.clone(),
                     is_managed: c.is_managed,
                     sink_name: c.sink_name.clone(),
-                    volume_db: c.volume_db,
+                    volume_db: self
+                        .state
+                        .channel_fades
+                        .get(&c.id)
+                        .map_or(c.volume_db, |f| f.target_db()),
                     muted: c.muted,
                     eq_enabled: c.eq_enabled,
                     eq_preset: c.eq_preset.clone(),
+                    eq_bands: c.eq_custom.then(|| c.eq_bands.clone()),
                     assigned_apps: c.assigned_apps.clone(),
                     plugin_chain: c.plugin_chain.clone(),
                     output_device_name: c.output_device_name.clone(),
@@ -1771,13 +2992,23 @@ impl DaemonService {
                     input_device_name: c.input_device_name.clone(),
                     noise_suppression_enabled: c.noise_suppression_enabled,
                     vad_threshold: c.vad_threshold,
+                    noise_suppression: c.noise_suppression_settings,
+                    talk: c.talk,
                     input_gain_db: c.input_gain_db,
                     sidetone_enabled: c.sidetone_enabled,
                     sidetone_volume_db: c.sidetone_volume_db,
+                    pan: c.pan,
+                    width: c.width,
+                    layout: c.layout,
+                    auto_level: c.auto_level,
                 })
                 .collect(),
-        };
+            ducking: self.state.ducking.clone(),
+        }
+    }
 
+    pub fn save_config(&self) {
+        let config = self.mixer_config();
         if let Err(e) = self.config_manager.save_mixer_config(&config) {
             error!("Failed to save config: {}", e);
         }
@@ -1799,6 +3030,7 @@ impl DaemonService {
             channel_id: id,
             name: name.to_string(),
             target_device,
+            layout: ChannelLayout::Stereo,
         });
 
         self.save_config();
@@ -1835,6 +3067,11 @@ impl DaemonService {
 
         let is_input = channel.is_input();
         let is_managed = channel.is_managed;
+        let has_eq = channel.pw_eq_sink_id.is_some();
+        let has_plugin_filter = channel.pw_plugin_sink_id.is_some()
+            || self.state.pending_plugin_creates.contains(&id);
+        let plugin_instance_ids: Vec<Uuid> =
+            channel.plugin_instance_ids.iter().flatten().copied().collect();
 
         if is_input {
             // Destroy virtual source for input channels
@@ -1853,10 +3090,41 @@ impl DaemonService {
                     self.send_pw_command(PwCommand::DestroyVirtualSink { node_id: sink_id });
                 }
             }
+            if has_eq {
+                self.send_pw_command(PwCommand::DestroyEqFilter { channel_id: id });
+            }
+        }
+
+        if has_plugin_filter {
+            self.state.pending_plugin_creates.remove(&id);
+            self.send_pw_command(PwCommand::DestroyPluginFilter { channel_id: id });
+        }
+        for instance_id in plugin_instance_ids {
+            self.plugin_manager.unload(instance_id);
         }
 
         self.state.channels.retain(|c| c.id != id);
+        self.state.channel_fades.remove(&id);
+        self.sync_monitor();
+        self.sync_latency();
+        self.state.ducking.retain(|d| d.trigger_channel != id);
+        for ducking in &mut self.state.ducking {
+            ducking.ducked_channels.retain(|&c| c != id);
+        }
         self.save_config();
+        let bindings = self.midi_config.bindings.len();
+        self.midi_config
+            .bindings
+            .retain(|b| b.target.channel() != Some(id));
+        if self.midi_config.bindings.len() != bindings {
+            self.config_manager.save_midi_config(&self.midi_config)?;
+        }
+        if self.recording_config.replay_channels.contains(&id) {
+            self.stop_replay(id);
+            self.recording_config.replay_channels.retain(|&c| c != id);
+            self.config_manager
+                .save_recording_config(&self.recording_config)?;
+        }
         Ok(())
     }
 
@@ -1933,6 +3201,9 @@ impl DaemonService {
         let id = Uuid::parse_str(channel_id)
             .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
 
+        // A manual move takes over from any scene fade in progress
+        self.state.channel_fades.remove(&id);
+
         let (volume, node_id) = {
             let channel = self
                 .state
@@ -1983,14 +3254,120 @@ impl DaemonService {
             self.send_pw_command(PwCommand::SetMute { node_id, muted });
         }
 
+        self.update_live(id);
+        self.save_config();
+        Ok(())
+    }
+
+    /// Set a channel's pan (-1.0 left to 1.0 right) and width (0.0 front
+    /// speakers only to 1.0 all speakers). Values are clamped. Width needs a
+    /// surround layout; other layouts only take 1.0.
+    pub fn set_channel_balance(
+        &mut self,
+        channel_id: &str,
+        pan: f32,
+        width: f32,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        let balance = Balance::new(pan, width);
+        if balance.width != 1.0 && !channel.layout.has_surround() {
+            return Err(ServiceError::InvalidArgument(format!(
+                "Width needs a surround layout, channel '{}' is {}",
+                channel.name, channel.layout
+            )));
+        }
+        channel.pan = balance.pan;
+        channel.width = balance.width;
+        if let Some(node_id) = channel.volume_node() {
+            self.send_pw_command(PwCommand::SetBalance { node_id, balance });
+        }
+
+        self.save_config();
+        Ok(())
+    }
+
+    /// Change an output channel's speaker layout. The virtual sink is
+    /// recreated with the new channel map; assigned apps are re-linked once
+    /// it's back, like after an external removal.
+    pub fn set_channel_layout(
+        &mut self,
+        channel_id: &str,
+        layout: ChannelLayout,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        if channel.is_input() {
+            return Err(ServiceError::InvalidArgument(
+                "Only output channels have a speaker layout".to_string(),
+            ));
+        }
+        if !channel.is_managed {
+            return Err(ServiceError::InvalidArgument(
+                "Adopted sinks keep their own layout".to_string(),
+            ));
+        }
+        if channel.layout == layout {
+            return Ok(());
+        }
+        if layout.has_surround() && (channel.eq_enabled || !channel.plugin_chain.is_empty()) {
+            return Err(ServiceError::InvalidArgument(format!(
+                "The EQ and plugins run in stereo; turn off the EQ and remove plugins before \
+                 switching to {}",
+                layout
+            )));
+        }
+
+        info!(
+            "Changing channel '{}' layout from {} to {}",
+            channel.name, channel.layout, layout
+        );
+        channel.layout = layout;
+        if !layout.has_surround() {
+            channel.width = 1.0;
+        }
+        let name = channel.name.clone();
+        let target_device = channel
+            .output_device_name
+            .clone()
+            .or_else(|| self.state.master_output.clone());
+        // Cleared first so the old sink's removal isn't taken for an external kill
+        let old_sink = channel.pw_sink_id.take();
+        channel.pw_loopback_output_id = None;
+
+        // Without a sink yet, the layout is used when it gets created
+        if let Some(node_id) = old_sink {
+            self.send_pw_command(PwCommand::DestroyVirtualSink { node_id });
+            self.send_pw_command(PwCommand::CreateVirtualSink {
+                channel_id: id,
+                name,
+                target_device,
+                layout,
+            });
+        }
+
         self.save_config();
         Ok(())
     }
 
     /// Enable or disable noise suppression on an input channel.
     ///
-    /// Note: Noise suppression requires the RNNoise LADSPA plugin to be installed.
-    /// This is currently a stub - full implementation pending native API migration.
+    /// RNNoise runs in the channel's plugin filter, which is created or
+    /// removed as needed; the channel's source node is left alone.
     pub fn set_channel_noise_suppression(
         &mut self,
         channel_id: &str,
@@ -2002,7 +3379,7 @@ impl DaemonService {
         let channel = self
             .state
             .channels
-            .iter()
+            .iter_mut()
             .find(|c| c.id == id)
             .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
 
@@ -2012,72 +3389,31 @@ impl DaemonService {
             ));
         }
 
-        let channel_name = channel.name.clone();
-        let target_mic = channel.input_device_name.clone();
-        let existing_source_id = channel.pw_source_id;
-        let already_enabled = channel.noise_suppression_enabled;
-        let vad_threshold = channel.vad_threshold;
-
         // Skip if state isn't actually changing
-        if enabled == already_enabled {
+        if channel.noise_suppression_enabled == enabled {
             debug!(
                 "Noise suppression already {} for channel '{}', skipping",
                 if enabled { "enabled" } else { "disabled" },
-                channel_name
+                channel.name
             );
             return Ok(());
         }
 
-        if enabled {
-            info!(
-                "Enabling noise suppression for channel '{}' (target_mic: {:?})",
-                channel_name, target_mic
-            );
-            let create_cmd = PwCommand::CreateNativeNoiseFilter {
-                channel_id: id,
-                name: channel_name,
-                target_mic,
-                vad_threshold,
-            };
-            if let Some(source_id) = existing_source_id {
-                // Defer the noise filter creation until the old loopback is destroyed,
-                // to avoid same-name node conflicts (same race as the disable path).
-                info!(
-                    "Destroying existing loopback source {} before creating noise filter",
-                    source_id
-                );
-                self.state.pending_ns_replacements.insert(id, create_cmd);
-                self.send_pw_command(PwCommand::DestroyVirtualSink { node_id: source_id });
-            } else {
-                // No existing source, create directly
-                self.send_pw_command(create_cmd);
-            }
-        } else {
-            info!("Disabling noise suppression for channel '{}'", channel_name);
-            // Store the replacement command to dispatch AFTER the noise filter is fully
-            // destroyed. Sending both commands at once causes a race: both spawn_cli_work
-            // threads run concurrently, and the new pw-loopback's find_node_by_name can
-            // find the old (dying) noise filter node instead of the newly created one.
-            self.state.pending_ns_replacements.insert(
-                id,
-                PwCommand::CreateVirtualSource {
-                    channel_id: id,
-                    name: channel_name,
-                    target_device: target_mic,
-                },
-            );
-            // Destroy the noise filter; NativeNoiseFilterDestroyed handler will
-            // dispatch the pending CreateVirtualSource.
-            self.send_pw_command(PwCommand::DestroyNativeNoiseFilter { channel_id: id });
-        }
+        info!(
+            "{} noise suppression for channel '{}'",
+            if enabled { "Enabling" } else { "Disabling" },
+            channel.name
+        );
+        channel.noise_suppression_enabled = enabled;
 
+        self.apply_noise_suppression(id);
+        self.save_config();
         Ok(())
     }
 
     /// Set the VAD threshold for noise suppression on an input channel.
     ///
-    /// If noise suppression is currently enabled, the filter will be recreated
-    /// with the new threshold.
+    /// Applied live if noise suppression is running.
     pub fn set_channel_vad_threshold(
         &mut self,
         channel_id: &str,
@@ -2107,71 +3443,289 @@ impl DaemonService {
             return Ok(());
         }
 
-        let was_enabled = channel.noise_suppression_enabled;
-        let channel_name = channel.name.clone();
-        let target_mic = channel.input_device_name.clone();
-
-        // Update the threshold
+        debug!(
+            "VAD threshold for channel '{}': {}%",
+            channel.name, threshold
+        );
         channel.vad_threshold = threshold;
 
-        // If noise suppression is enabled, recreate the filter with new threshold
-        if was_enabled {
-            info!(
-                "Updating VAD threshold to {}% for channel '{}', recreating filter",
-                threshold, channel_name
-            );
-
-            // Defer the new filter creation until the old one is fully destroyed
-            self.state.pending_ns_replacements.insert(
-                id,
-                PwCommand::CreateNativeNoiseFilter {
-                    channel_id: id,
-                    name: channel_name,
-                    target_mic,
-                    vad_threshold: threshold,
-                },
-            );
-            self.send_pw_command(PwCommand::DestroyNativeNoiseFilter { channel_id: id });
-        }
-
+        self.apply_noise_suppression(id);
         self.save_config();
         Ok(())
     }
 
-    /// Set the hardware microphone gain for an input channel.
-    /// This controls the physical input device level, separate from the channel volume.
-    pub fn set_channel_input_gain(
+    /// Set the noise suppression model, VAD grace periods and attenuation
+    /// limit of an input channel.
+    ///
+    /// Applied live if noise suppression is running; the retroactive grace
+    /// changes the channel's latency.
+    pub fn set_channel_noise_suppression_settings(
         &mut self,
         channel_id: &str,
-        gain_db: f64,
+        settings: &NoiseSuppressionInfo,
     ) -> Result<(), ServiceError> {
+        if !(0.0..=NOISE_MAX_GRACE_MS).contains(&settings.grace_ms)
+            || !(0.0..=NOISE_MAX_RETRO_GRACE_MS).contains(&settings.retro_grace_ms)
+            || !(0.0..=NOISE_MAX_ATTENUATION_DB).contains(&settings.attenuation_limit_db)
+        {
+            return Err(ServiceError::InvalidArgument(format!(
+                "VAD grace must be within 0..{} ms, retroactive grace within 0..{} ms and \
+                 attenuation limit within 0..{} dB",
+                NOISE_MAX_GRACE_MS, NOISE_MAX_RETRO_GRACE_MS, NOISE_MAX_ATTENUATION_DB
+            )));
+        }
+
         let id = Uuid::parse_str(channel_id)
             .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
 
-        // Get channel info and validate
-        let (input_device_name, gain_db) = {
-            let channel = self
-                .state
-                .channels
-                .iter_mut()
-                .find(|c| c.id == id)
-                .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
-
-            if !channel.is_input() {
-                return Err(ServiceError::ChannelNotFound(
-                    "Input gain is only available on input channels".to_string(),
-                ));
-            }
-
-            // Clamp to valid range (-12dB to +12dB)
-            let gain_db = (gain_db as f32).clamp(-12.0, 12.0);
-            channel.input_gain_db = gain_db;
+        if !channel.is_input() {
+            return Err(ServiceError::ChannelNotFound(
+                "Noise suppression is only available on input channels".to_string(),
+            ));
+        }
 
-            (channel.input_device_name.clone(), gain_db)
+        let settings = NoiseSuppressionConfig {
+            model: settings.model,
+            grace_ms: settings.grace_ms as f32,
+            retro_grace_ms: settings.retro_grace_ms as f32,
+            attenuation_limit_db: settings.attenuation_limit_db as f32,
         };
+        if channel.noise_suppression_settings == settings {
+            return Ok(());
+        }
+        debug!(
+            "Noise suppression for channel '{}': {:?}",
+            channel.name, settings
+        );
+        channel.noise_suppression_settings = settings;
 
-        // Resolve device name to node ID and apply volume
-        if let Some(device_name) = input_device_name {
+        self.apply_noise_suppression(id);
+        self.save_config();
+        Ok(())
+    }
+
+    /// Emit ChannelTalking when noise suppression starts or stops hearing a
+    /// voice on an input channel. Called every meter tick.
+    pub fn update_voice_activity(&mut self) {
+        let mut changes = Vec::new();
+        for channel in &mut self.state.channels {
+            let talking = channel.is_input()
+                && channel.noise_suppression_enabled
+                && channel.pw_plugin_sink_id.is_some()
+                && channel.voice_activity.is_talking();
+            if talking != channel.talking {
+                channel.talking = talking;
+                changes.push((channel.id.to_string(), talking));
+            }
+        }
+
+        for (channel_id, talking) in changes {
+            self.emit_signal(SignalEvent::ChannelTalking {
+                channel_id,
+                talking,
+            });
+        }
+    }
+
+    /// Set the talk mode, release tail and fade of an input channel.
+    ///
+    /// A talk mode puts a gate at the end of the channel's plugin filter,
+    /// which is created or removed as needed.
+    pub fn set_channel_talk_settings(
+        &mut self,
+        channel_id: &str,
+        settings: &TalkInfo,
+    ) -> Result<(), ServiceError> {
+        if !(0.0..=TALK_MAX_RELEASE_MS).contains(&settings.release_ms)
+            || !(0.0..=TALK_MAX_FADE_MS).contains(&settings.fade_ms)
+        {
+            return Err(ServiceError::InvalidArgument(format!(
+                "Talk release must be within 0..{} ms and fade within 0..{} ms",
+                TALK_MAX_RELEASE_MS, TALK_MAX_FADE_MS
+            )));
+        }
+
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        if !channel.is_input() {
+            return Err(ServiceError::ChannelNotFound(
+                "Push-to-talk is only available on input channels".to_string(),
+            ));
+        }
+
+        let settings = TalkConfig {
+            mode: settings.mode,
+            release_ms: settings.release_ms as f32,
+            fade_ms: settings.fade_ms as f32,
+        };
+        if channel.talk == settings {
+            return Ok(());
+        }
+        info!(
+            "Talk settings for channel '{}': {:?}",
+            channel.name, settings
+        );
+        channel.talk = settings;
+
+        self.apply_talk_gate(id);
+        self.sync_plugin_filter(id);
+        self.save_config();
+        Ok(())
+    }
+
+    /// Press or let go of the talk key of an input channel, or of every
+    /// input channel with a talk mode if `channel_id` is empty.
+    ///
+    /// Letting go starts the channel's release tail; the mic follows once
+    /// it runs out (see [`Self::update_talk`]).
+    pub fn set_talk_key(&mut self, channel_id: &str, pressed: bool) -> Result<(), ServiceError> {
+        let ids: Vec<Uuid> = if channel_id.is_empty() {
+            self.state
+                .channels
+                .iter()
+                .filter(|c| c.is_input() && c.talk.mode != TalkMode::Off)
+                .map(|c| c.id)
+                .collect()
+        } else {
+            let id = Uuid::parse_str(channel_id)
+                .ok()
+                .filter(|id| {
+                    self.state
+                        .channels
+                        .iter()
+                        .any(|c| c.id == *id && c.is_input())
+                })
+                .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+            vec![id]
+        };
+
+        for id in ids {
+            let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == id) else {
+                continue;
+            };
+            if channel.talk_pressed == pressed {
+                continue;
+            }
+            debug!(
+                "Talk key {} for channel '{}'",
+                if pressed { "pressed" } else { "released" },
+                channel.name
+            );
+            channel.talk_pressed = pressed;
+            channel.talk_release_left = if pressed {
+                0.0
+            } else {
+                channel.talk.release_ms / 1000.0
+            };
+            self.apply_talk_gate(id);
+        }
+        Ok(())
+    }
+
+    /// Run down the release tails of let-go talk keys by `dt` seconds and
+    /// close (or open) the mics whose tail ran out. Called every meter tick.
+    pub fn update_talk(&mut self, dt: f32) {
+        let mut released = Vec::new();
+        for channel in &mut self.state.channels {
+            if channel.talk_release_left <= 0.0 {
+                continue;
+            }
+            channel.talk_release_left -= dt;
+            if channel.talk_release_left <= 0.0 {
+                channel.talk_release_left = 0.0;
+                released.push(channel.id);
+            }
+        }
+        for id in released {
+            self.apply_talk_gate(id);
+        }
+    }
+
+    /// Push a channel's talk gate to its running plugin filter and emit
+    /// ChannelLiveChanged if that changed whether the mic is heard.
+    fn apply_talk_gate(&mut self, channel_id: Uuid) {
+        let Some((settings, is_running)) = self
+            .state
+            .channels
+            .iter()
+            .find(|c| c.id == channel_id)
+            .map(|c| (c.talk_gate(), c.pw_plugin_sink_id.is_some()))
+        else {
+            return;
+        };
+        if is_running {
+            self.send_pw_command(PwCommand::SetTalkGate {
+                channel_id,
+                settings,
+            });
+        }
+        self.update_live(channel_id);
+    }
+
+    /// Emit ChannelLiveChanged if a channel's mic went live or silent since
+    /// the last one.
+    fn update_live(&mut self, channel_id: Uuid) {
+        let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == channel_id) else {
+            return;
+        };
+        let live = channel.is_live();
+        if live == channel.live {
+            return;
+        }
+        channel.live = live;
+        self.emit_signal(SignalEvent::ChannelLiveChanged {
+            channel_id: channel_id.to_string(),
+            live,
+        });
+    }
+
+    /// Set the hardware microphone gain for an input channel.
+    /// This controls the physical input device level, separate from the channel volume.
+    pub fn set_channel_input_gain(
+        &mut self,
+        channel_id: &str,
+        gain_db: f64,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        // Get channel info and validate
+        let (input_device_name, gain_db) = {
+            let channel = self
+                .state
+                .channels
+                .iter_mut()
+                .find(|c| c.id == id)
+                .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+            if !channel.is_input() {
+                return Err(ServiceError::ChannelNotFound(
+                    "Input gain is only available on input channels".to_string(),
+                ));
+            }
+
+            // Clamp to valid range (-12dB to +12dB)
+            let gain_db = (gain_db as f32).clamp(-12.0, 12.0);
+            channel.input_gain_db = gain_db;
+
+            (channel.input_device_name.clone(), gain_db)
+        };
+
+        // Resolve device name to node ID and apply volume
+        if let Some(device_name) = input_device_name {
             if device_name != "system-default" {
                 if let Some(node_id) = self.resolve_input_device_to_node_id(&device_name) {
                     // Convert dB to linear for PipeWire (0dB = 1.0)
@@ -2193,21 +3747,2395 @@ impl DaemonService {
             }
         }
 
-        self.save_config();
-        Ok(())
+        self.save_config();
+        Ok(())
+    }
+
+    /// Resolve an input device name to its PipeWire node ID.
+    fn resolve_input_device_to_node_id(&self, device_name: &str) -> Option<u32> {
+        self.state
+            .pw_graph
+            .nodes
+            .values()
+            .find(|n| n.is_audio_input() && (n.name == device_name || n.description == device_name))
+            .map(|n| n.id)
+    }
+
+    /// Enable or disable the EQ on an output channel.
+    pub fn set_channel_eq_enabled(
+        &mut self,
+        channel_id: &str,
+        enabled: bool,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        let (has_sink, eq_running) = {
+            let channel = self
+                .state
+                .channels
+                .iter_mut()
+                .find(|c| c.id == id)
+                .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+            if channel.is_input() {
+                return Err(ServiceError::InvalidArgument(
+                    "EQ is only available on output channels".to_string(),
+                ));
+            }
+            if channel.eq_enabled == enabled {
+                return Ok(());
+            }
+            if enabled && !channel.fits_filters() {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "The EQ runs in stereo and isn't available on {} channels",
+                    channel.layout
+                )));
+            }
+            channel.eq_enabled = enabled;
+            info!(
+                "{} EQ for channel '{}'",
+                if enabled { "Enabling" } else { "Disabling" },
+                channel.name
+            );
+            (
+                channel.pw_loopback_output_id.is_some(),
+                channel.pw_eq_sink_id.is_some(),
+            )
+        };
+
+        if enabled {
+            // Without a sink the filter is created once VirtualSinkCreated fires
+            if has_sink {
+                self.send_create_eq_filter(id);
+            }
+        } else if eq_running {
+            // Re-route around the filter before killing it, so the link removals
+            // caused by the kill aren't treated as managed links to restore.
+            if let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == id) {
+                channel.pw_eq_sink_id = None;
+                channel.pw_eq_output_id = None;
+            }
+            self.route_channel_stages(id);
+            self.send_pw_command(PwCommand::DestroyEqFilter { channel_id: id });
+        }
+
+        self.save_config();
+        Ok(())
+    }
+
+    /// Set the EQ preset for a channel, applying it live if the EQ is running.
+    pub fn set_channel_eq_preset(
+        &mut self,
+        channel_id: &str,
+        preset_name: &str,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        let preset = self
+            .config_manager
+            .load_eq_preset(preset_name)?
+            .ok_or_else(|| {
+                ServiceError::InvalidArgument(format!("EQ preset not found: {}", preset_name))
+            })?;
+
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        if channel.is_input() {
+            return Err(ServiceError::InvalidArgument(
+                "EQ is only available on output channels".to_string(),
+            ));
+        }
+        channel.eq_preset = preset.name;
+        channel.eq_bands = preset.bands;
+        channel.eq_custom = false;
+
+        self.apply_channel_eq(id);
+        self.save_config();
+        Ok(())
+    }
+
+    /// Replace the bands of a channel's EQ, applying them live if the EQ is
+    /// running. Values out of range are clamped.
+    pub fn set_channel_eq_bands(
+        &mut self,
+        channel_id: &str,
+        bands: &[EqBandInfo],
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        if bands.len() > sootmix_ipc::EQ_MAX_BANDS {
+            return Err(ServiceError::InvalidArgument(format!(
+                "An EQ has at most {} bands",
+                sootmix_ipc::EQ_MAX_BANDS
+            )));
+        }
+
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        if channel.is_input() {
+            return Err(ServiceError::InvalidArgument(
+                "EQ is only available on output channels".to_string(),
+            ));
+        }
+        channel.eq_bands = bands.iter().map(|b| EqBand::from(&b.clamped())).collect();
+        channel.eq_custom = true;
+
+        self.apply_channel_eq(id);
+        self.save_config();
+        Ok(())
+    }
+
+    /// List the names of all available EQ presets.
+    pub fn get_eq_presets(&self) -> Result<Vec<String>, ServiceError> {
+        Ok(self.config_manager.list_eq_presets()?)
+    }
+
+    /// Load a plugin into a new slot at the end of a channel's chain.
+    /// Returns the new instance ID.
+    pub fn add_channel_plugin(
+        &mut self,
+        channel_id: &str,
+        plugin_id: &str,
+    ) -> Result<String, ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        if !channel.fits_filters() {
+            return Err(ServiceError::InvalidArgument(format!(
+                "Plugins run in stereo and aren't available on {} channels",
+                channel.layout
+            )));
+        }
+
+        let plugin_type = self
+            .plugin_manager
+            .registry()
+            .read()
+            .get(plugin_id)
+            .map(|meta| meta.plugin_type);
+        let instance_id = self.plugin_manager.load(plugin_id)?;
+
+        // Type names match the UI's serialized PluginType; LV2/VST3 slots also
+        // record their URI / class ID as the external ID.
+        let is_external = plugin_type.is_some_and(|t| {
+            !matches!(
+                t,
+                sootmix_plugin_host::PluginType::Native
+                    | sootmix_plugin_host::PluginType::Wasm
+                    | sootmix_plugin_host::PluginType::Builtin
+            )
+        });
+        let slot = PluginSlotConfig {
+            plugin_id: plugin_id.to_string(),
+            bypassed: false,
+            parameters: HashMap::new(),
+            sidechain_source: None,
+            plugin_type: plugin_type.map(|t| format!("{:?}", t)),
+            external_id: is_external.then(|| plugin_id.to_string()),
+        };
+
+        if let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == id) {
+            channel.plugin_chain.push(slot);
+            channel.plugin_instance_ids.push(Some(instance_id));
+            info!(
+                "Added plugin '{}' to channel '{}' ({} slots)",
+                plugin_id,
+                channel.name,
+                channel.plugin_chain.len()
+            );
+        }
+
+        self.sync_plugin_filter(id);
+        self.save_config();
+        Ok(instance_id.to_string())
+    }
+
+    /// Remove a slot from a channel's plugin chain and unload its instance.
+    pub fn remove_channel_plugin(
+        &mut self,
+        channel_id: &str,
+        slot: usize,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        let instance_id = {
+            let channel = self
+                .state
+                .channels
+                .iter_mut()
+                .find(|c| c.id == id)
+                .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+            if slot >= channel.plugin_chain.len() {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "Plugin slot {} out of range",
+                    slot
+                )));
+            }
+            let removed = channel.plugin_chain.remove(slot);
+            info!(
+                "Removed plugin '{}' from channel '{}'",
+                removed.plugin_id, channel.name
+            );
+            channel.plugin_instance_ids.remove(slot)
+        };
+
+        // Take the instance out of the running chain before unloading it
+        self.sync_plugin_filter(id);
+        if let Some(instance_id) = instance_id {
+            self.plugin_manager.unload(instance_id);
+        }
+
+        self.save_config();
+        Ok(())
+    }
+
+    /// Move a plugin slot to a new position in a channel's chain.
+    pub fn move_channel_plugin(
+        &mut self,
+        channel_id: &str,
+        from: usize,
+        to: usize,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        {
+            let channel = self
+                .state
+                .channels
+                .iter_mut()
+                .find(|c| c.id == id)
+                .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+            let len = channel.plugin_chain.len();
+            if from >= len || to >= len {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "Plugin slot move {} -> {} out of range",
+                    from, to
+                )));
+            }
+            if from == to {
+                return Ok(());
+            }
+            let slot = channel.plugin_chain.remove(from);
+            channel.plugin_chain.insert(to, slot);
+            let instance_id = channel.plugin_instance_ids.remove(from);
+            channel.plugin_instance_ids.insert(to, instance_id);
+        }
+
+        self.sync_plugin_filter(id);
+        self.save_config();
+        Ok(())
+    }
+
+    /// Bypass or re-enable a slot in a channel's plugin chain.
+    pub fn set_channel_plugin_bypassed(
+        &mut self,
+        channel_id: &str,
+        slot: usize,
+        bypassed: bool,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        {
+            let channel = self
+                .state
+                .channels
+                .iter_mut()
+                .find(|c| c.id == id)
+                .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+            let slot_config = channel.plugin_chain.get_mut(slot).ok_or_else(|| {
+                ServiceError::InvalidArgument(format!("Plugin slot {} out of range", slot))
+            })?;
+            if slot_config.bypassed == bypassed {
+                return Ok(());
+            }
+            slot_config.bypassed = bypassed;
+        }
+
+        self.sync_plugin_filter(id);
+        self.save_config();
+        Ok(())
+    }
+
+    /// Set a parameter on a channel's plugin slot.
+    pub fn set_channel_plugin_parameter(
+        &mut self,
+        channel_id: &str,
+        slot: usize,
+        param_index: u32,
+        value: f32,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        let instance_id = {
+            let channel = self
+                .state
+                .channels
+                .iter_mut()
+                .find(|c| c.id == id)
+                .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+            let slot_config = channel.plugin_chain.get_mut(slot).ok_or_else(|| {
+                ServiceError::InvalidArgument(format!("Plugin slot {} out of range", slot))
+            })?;
+            slot_config.parameters.insert(param_index, value);
+            channel.plugin_instance_ids.get(slot).copied().flatten()
+        };
+
+        if let Some(instance_id) = instance_id {
+            self.plugin_manager
+                .set_parameter(instance_id, param_index, value);
+            // Lookahead and similar parameters change the plugin's latency
+            self.sync_latency();
+        }
+
+        self.save_config();
+        Ok(())
+    }
+
+    /// Get the slots of a channel's plugin chain, in processing order.
+    pub fn get_channel_plugins(
+        &self,
+        channel_id: &str,
+    ) -> Result<Vec<PluginSlotInfo>, ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        self.state
+            .channels
+            .iter()
+            .find(|c| c.id == id)
+            .map(|c| c.plugin_slots())
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))
+    }
+
+    /// Add a routing rule, or replace the rule with the same ID.
+    ///
+    /// An empty ID creates a new rule. Rules are only stored here; the UI
+    /// still evaluates them against discovered apps.
+    pub fn set_routing_rule(&mut self, info: RoutingRuleInfo) -> Result<(), ServiceError> {
+        let rule = routing_rule_from_info(&info)?;
+        let rules = &mut self.state.routing_rules.rules;
+        match rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => *existing = rule,
+            None => rules.push(rule),
+        }
+        self.save_routing_rules()
+    }
+
+    /// Delete a routing rule by ID.
+    pub fn delete_routing_rule(&mut self, rule_id: &str) -> Result<(), ServiceError> {
+        let id = self.find_routing_rule(rule_id)?;
+        self.state.routing_rules.remove_rule(id);
+        self.save_routing_rules()
+    }
+
+    /// Flip a routing rule's enabled state.
+    pub fn toggle_routing_rule(&mut self, rule_id: &str) -> Result<(), ServiceError> {
+        let id = self.find_routing_rule(rule_id)?;
+        self.state.routing_rules.toggle_rule(id);
+        self.save_routing_rules()
+    }
+
+    fn find_routing_rule(&self, rule_id: &str) -> Result<Uuid, ServiceError> {
+        Uuid::parse_str(rule_id)
+            .ok()
+            .and_then(|id| self.state.routing_rules.get_rule(id))
+            .map(|r| r.id)
+            .ok_or_else(|| ServiceError::InvalidArgument(format!("No routing rule {}", rule_id)))
+    }
+
+    fn save_routing_rules(&self) -> Result<(), ServiceError> {
+        Ok(self
+            .config_manager
+            .save_routing_rules(&self.state.routing_rules)?)
+    }
+
+    // ==================== Ducking ====================
+
+    pub fn get_ducking(&self) -> Vec<DuckingInfo> {
+        self.state
+            .ducking
+            .iter()
+            .map(|d| DuckingInfo {
+                id: d.id.to_string(),
+                enabled: d.enabled,
+                trigger_channel: d.trigger_channel.to_string(),
+                ducked_channels: d.ducked_channels.iter().map(Uuid::to_string).collect(),
+                threshold_db: d.threshold_db as f64,
+                amount_db: d.amount_db as f64,
+                attack_ms: d.attack_ms as f64,
+                hold_ms: d.hold_ms as f64,
+                release_ms: d.release_ms as f64,
+            })
+            .collect()
+    }
+
+    /// Add or update a ducking relationship. An empty ID creates a new one.
+    /// Returns the relationship ID.
+    pub fn set_ducking(&mut self, info: DuckingInfo) -> Result<String, ServiceError> {
+        let ducking = self.ducking_from_info(&info)?;
+        let id = ducking.id;
+        match self.state.ducking.iter_mut().find(|d| d.id == id) {
+            Some(existing) => *existing = ducking,
+            None => self.state.ducking.push(ducking),
+        }
+        self.save_config();
+        Ok(id.to_string())
+    }
+
+    /// Delete a ducking relationship. Its gain reduction is released on the
+    /// next meter tick.
+    pub fn delete_ducking(&mut self, ducking_id: &str) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(ducking_id)
+            .ok()
+            .filter(|id| self.state.ducking.iter().any(|d| d.id == *id))
+            .ok_or_else(|| {
+                ServiceError::InvalidArgument(format!("No ducking relationship {}", ducking_id))
+            })?;
+        self.state.ducking.retain(|d| d.id != id);
+        self.state.duck_envelopes.remove(&id);
+        self.save_config();
+        Ok(())
+    }
+
+    fn ducking_from_info(&self, info: &DuckingInfo) -> Result<DuckingConfig, ServiceError> {
+        let id = if info.id.is_empty() {
+            Uuid::new_v4()
+        } else {
+            Uuid::parse_str(&info.id).map_err(|_| {
+                ServiceError::InvalidArgument(format!("Invalid ducking ID: {}", info.id))
+            })?
+        };
+
+        let channel = |s: &str| {
+            Uuid::parse_str(s)
+                .ok()
+                .filter(|id| self.state.channels.iter().any(|c| c.id == *id))
+                .ok_or_else(|| ServiceError::ChannelNotFound(s.to_string()))
+        };
+        let trigger_channel = channel(&info.trigger_channel)?;
+        let ducked_channels = info
+            .ducked_channels
+            .iter()
+            .map(|s| channel(s.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if ducked_channels.contains(&trigger_channel) {
+            return Err(ServiceError::InvalidArgument(
+                "A channel cannot duck itself".to_string(),
+            ));
+        }
+
+        let times = [info.attack_ms, info.hold_ms, info.release_ms];
+        if !info.threshold_db.is_finite()
+            || !info.amount_db.is_finite()
+            || info.amount_db < 0.0
+            || times.iter().any(|t| !t.is_finite() || *t < 0.0)
+        {
+            return Err(ServiceError::InvalidArgument(
+                "Ducking threshold, amount and times must be finite; amount and times non-negative"
+                    .to_string(),
+            ));
+        }
+
+        Ok(DuckingConfig {
+            id,
+            enabled: info.enabled,
+            trigger_channel,
+            ducked_channels,
+            threshold_db: info.threshold_db as f32,
+            amount_db: info.amount_db as f32,
+            attack_ms: info.attack_ms as f32,
+            hold_ms: info.hold_ms as f32,
+            release_ms: info.release_ms as f32,
+        })
+    }
+
+    /// Advance every ducking envelope by `dt` seconds from the trigger
+    /// channels' meter levels and apply the resulting gain reduction to the
+    /// ducked channels' volume nodes. A channel ducked by several triggers
+    /// takes the deepest reduction.
+    pub fn update_ducking(&mut self, dt: f32) {
+        let mut reductions: HashMap<Uuid, f32> = HashMap::new();
+        for ducking in &self.state.ducking {
+            let trigger_db = self
+                .state
+                .channels
+                .iter()
+                .find(|c| c.id == ducking.trigger_channel)
+                .filter(|c| ducking.enabled && !c.muted)
+                .and_then(|c| c.atomic_meter_levels.as_ref())
+                .map(|levels| {
+                    let (left, right) = levels.load();
+                    linear_to_db(left.max(right))
+                })
+                .unwrap_or(f32::NEG_INFINITY);
+
+            let reduction = self
+                .state
+                .duck_envelopes
+                .entry(ducking.id)
+                .or_default()
+                .update(ducking, trigger_db, dt);
+            for &channel_id in &ducking.ducked_channels {
+                let entry = reductions.entry(channel_id).or_insert(0.0);
+                *entry = entry.max(reduction);
+            }
+        }
+
+        let mut volume_updates = Vec::new();
+        for channel in &mut self.state.channels {
+            let reduction = reductions.get(&channel.id).copied().unwrap_or(0.0);
+            // Every update is a node param write on the PipeWire thread, so
+            // skip steps too small to hear, but always land exactly on 0 dB.
+            let delta = (reduction - channel.duck_reduction_db).abs();
+            if delta == 0.0 || (delta < DUCK_MIN_STEP_DB && reduction != 0.0) {
+                continue;
+            }
+            channel.duck_reduction_db = reduction;
+            if let Some(node_id) = channel.volume_node().filter(|_| !channel.muted) {
+                volume_updates.push((node_id, channel.volume_linear()));
+            }
+        }
+
+        for (node_id, volume) in volume_updates {
+            self.send_pw_command(PwCommand::SetVolume { node_id, volume });
+        }
+    }
+
+    /// Advance every channel's auto level gain by `dt` seconds from its
+    /// short-term loudness and apply it to the channel's volume node.
+    pub fn update_auto_level(&mut self, dt: f32) {
+        let mut volume_updates = Vec::new();
+        for channel in &mut self.state.channels {
+            if !channel.auto_level.enabled && channel.auto_level_gain_db == 0.0 {
+                continue;
+            }
+            let short_term = channel
+                .loudness
+                .as_ref()
+                .map_or(f32::NEG_INFINITY, |l| l.load().short_term);
+            let gain = channel
+                .auto_level_state
+                .update(&channel.auto_level, short_term, dt);
+
+            // Same trade-off as ducking: skip inaudible steps, but always
+            // land exactly on 0 dB
+            let delta = (gain - channel.auto_level_gain_db).abs();
+            if delta == 0.0 || (delta < AUTO_LEVEL_MIN_STEP_DB && gain != 0.0) {
+                continue;
+            }
+            channel.auto_level_gain_db = gain;
+            if let Some(node_id) = channel.volume_node().filter(|_| !channel.muted) {
+                volume_updates.push((node_id, channel.volume_linear()));
+            }
+        }
+
+        for (node_id, volume) in volume_updates {
+            self.send_pw_command(PwCommand::SetVolume { node_id, volume });
+        }
+    }
+
+    /// Set a channel's automatic loudness normalization.
+    pub fn set_channel_auto_level(
+        &mut self,
+        channel_id: &str,
+        settings: &AutoLevelInfo,
+    ) -> Result<(), ServiceError> {
+        let values = [
+            settings.target_lufs,
+            settings.max_boost_db,
+            settings.max_cut_db,
+            settings.speed_db_per_sec,
+        ];
+        if values.iter().any(|v| !v.is_finite())
+            || !(-60.0..=0.0).contains(&settings.target_lufs)
+            || !(0.0..=24.0).contains(&settings.max_boost_db)
+            || !(0.0..=24.0).contains(&settings.max_cut_db)
+            || settings.speed_db_per_sec <= 0.0
+        {
+            return Err(ServiceError::InvalidArgument(
+                "Auto level target must be within -60..0 LUFS, boost and cut within 0..24 dB \
+                 and speed positive"
+                    .to_string(),
+            ));
+        }
+
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        channel.auto_level = AutoLevelConfig {
+            enabled: settings.enabled,
+            target_lufs: settings.target_lufs as f32,
+            max_boost_db: settings.max_boost_db as f32,
+            max_cut_db: settings.max_cut_db as f32,
+            speed_db_per_sec: settings.speed_db_per_sec as f32,
+        };
+
+        self.save_config();
+        Ok(())
+    }
+
+    // ==================== Scenes ====================
+
+    pub fn list_scenes(&self) -> Result<Vec<String>, ServiceError> {
+        Ok(self.config_manager.list_scenes()?)
+    }
+
+    /// Save the current mixer state and routing rules as a named scene,
+    /// replacing any scene with the same name.
+    pub fn save_scene(&self, name: &str) -> Result<(), ServiceError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ServiceError::InvalidArgument(
+                "Scene name cannot be empty".to_string(),
+            ));
+        }
+
+        // Don't let a name that differs only in punctuation replace another
+        // scene's file. An unreadable file is fine to overwrite.
+        if let Ok(Some(existing)) = self.config_manager.scene_file_owner(name) {
+            if !existing.eq_ignore_ascii_case(name) {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "Scene name '{}' clashes with saved scene '{}'",
+                    name, existing
+                )));
+            }
+        }
+
+        let mixer = self.mixer_config();
+        let scene = Scene {
+            name: name.to_string(),
+            master: mixer.master,
+            channels: mixer.channels,
+            ducking: mixer.ducking,
+            routing_rules: self.state.routing_rules.rules.clone(),
+        };
+        self.config_manager.save_scene(&scene)?;
+        info!("Saved scene '{}'", name);
+        Ok(())
+    }
+
+    pub fn delete_scene(&mut self, name: &str) -> Result<(), ServiceError> {
+        if !self.config_manager.delete_scene(name)? {
+            return Err(ServiceError::InvalidArgument(format!(
+                "No scene named '{}'",
+                name
+            )));
+        }
+        info!("Deleted scene '{}'", name);
+        Ok(())
+    }
+
+    /// Recall a saved scene.
+    ///
+    /// Channels not in the scene are deleted and missing ones created; the
+    /// rest are updated through the same paths as the individual setters, so
+    /// only settings that differ touch the graph. Volume changes fade over
+    /// `fade_ms` instead of jumping (new channels fade in from the floor).
+    pub fn recall_scene(&mut self, name: &str, fade_ms: u32) -> Result<(), ServiceError> {
+        let scene = self
+            .config_manager
+            .load_scene(name)?
+            .ok_or_else(|| ServiceError::InvalidArgument(format!("No scene named '{}'", name)))?;
+        info!(
+            "Recalling scene '{}' ({} channels, {} ms fade)",
+            scene.name,
+            scene.channels.len(),
+            fade_ms
+        );
+
+        let removed: Vec<Uuid> = self
+            .state
+            .channels
+            .iter()
+            .filter(|c| {
+                !scene
+                    .channels
+                    .iter()
+                    .any(|saved| saved.id == c.id && saved.kind == c.kind)
+            })
+            .map(|c| c.id)
+            .collect();
+        for id in removed {
+            self.delete_channel(&id.to_string())?;
+        }
+
+        let mut created = false;
+        for saved in &scene.channels {
+            if self.state.channels.iter().any(|c| c.id == saved.id) {
+                self.apply_saved_channel(saved)?;
+            } else {
+                let mut channel = ChannelState::from_saved(saved);
+                channel.volume_db = FADE_FLOOR_DB;
+                if !channel.eq_custom {
+                    channel.eq_bands =
+                        resolve_eq_preset(&self.config_manager, &channel.eq_preset).bands;
+                }
+                load_plugin_chain(&mut self.plugin_manager, &mut channel);
+                self.state.channels.push(channel);
+                created = true;
+            }
+            self.fade_channel_volume(saved.id, saved.volume_db, fade_ms);
+        }
+        if created {
+            self.create_channel_nodes();
+        }
+        self.state
+            .channels
+            .sort_by_key(|c| scene.channels.iter().position(|saved| saved.id == c.id));
+
+        let master = &scene.master;
+        if master.output_device != self.state.master_output {
+            self.set_master_output(master.output_device.as_deref().unwrap_or(""))?;
+        }
+        if master.muted != self.state.master_muted {
+            self.set_master_mute(master.muted)?;
+        }
+        self.state.monitor_device = master.monitor_device.clone();
+        self.state.monitor_volume_db = master.monitor_volume_db;
+        self.state.monitor_muted = master.monitor_muted;
+        self.state.solo_mode = master.solo_mode;
+        self.apply_monitor_levels(MonitorStage::Bus);
+        self.sync_monitor();
+        if master.volume_db != self.state.master_volume_db {
+            self.state.master_fade = Some(VolumeFade::new(
+                self.state.master_volume_db,
+                master.volume_db,
+                fade_ms,
+            ));
+        }
+
+        self.state.ducking = scene.ducking;
+        let ducking = &self.state.ducking;
+        self.state
+            .duck_envelopes
+            .retain(|id, _| ducking.iter().any(|d| d.id == *id));
+
+        self.state.routing_rules.rules = scene.routing_rules;
+        self.save_routing_rules()?;
+        self.save_config();
+        Ok(())
+    }
+
+    /// Bring an existing channel in line with its saved scene state (except
+    /// volume, which the caller fades).
+    fn apply_saved_channel(&mut self, saved: &SavedChannel) -> Result<(), ServiceError> {
+        let Some(current) = self
+            .state
+            .channels
+            .iter()
+            .find(|c| c.id == saved.id)
+            .cloned()
+        else {
+            return Err(ServiceError::ChannelNotFound(saved.id.to_string()));
+        };
+        let id = saved.id.to_string();
+
+        if current.name != saved.name {
+            self.rename_channel(&id, &saved.name)?;
+        }
+        let (device, current_device) = if current.is_input() {
+            (&saved.input_device_name, &current.input_device_name)
+        } else {
+            (&saved.output_device_name, &current.output_device_name)
+        };
+        if device != current_device {
+            self.set_channel_output(&id, device.as_deref().unwrap_or(""))?;
+        }
+        if current.muted != saved.muted {
+            self.set_channel_mute(&id, saved.muted)?;
+        }
+        // Scenes don't carry the layout, so a width saved on surround is
+        // dropped if the channel has since gone back to stereo
+        let width = if current.layout.has_surround() {
+            saved.width
+        } else {
+            1.0
+        };
+        if (current.pan, current.width) != (saved.pan, width) {
+            self.set_channel_balance(&id, saved.pan, width)?;
+        }
+        match &saved.eq_bands {
+            Some(bands) if !current.eq_custom || current.eq_bands != *bands => {
+                let bands: Vec<EqBandInfo> = bands.iter().map(EqBandInfo::from).collect();
+                self.set_channel_eq_bands(&id, &bands)?;
+                if let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == saved.id) {
+                    channel.eq_preset = saved.eq_preset.clone();
+                }
+            }
+            None if current.eq_custom || current.eq_preset != saved.eq_preset => {
+                self.set_channel_eq_preset(&id, &saved.eq_preset)?;
+            }
+            _ => {}
+        }
+        if current.eq_enabled != saved.eq_enabled {
+            self.set_channel_eq_enabled(&id, saved.eq_enabled)?;
+        }
+
+        if current.is_input() {
+            if current.input_gain_db != saved.input_gain_db {
+                self.set_channel_input_gain(&id, saved.input_gain_db as f64)?;
+            }
+            let ns = &saved.noise_suppression;
+            if current.noise_suppression_settings != *ns {
+                let settings = NoiseSuppressionInfo {
+                    model: ns.model,
+                    grace_ms: ns.grace_ms as f64,
+                    retro_grace_ms: ns.retro_grace_ms as f64,
+                    attenuation_limit_db: ns.attenuation_limit_db as f64,
+                };
+                self.set_channel_noise_suppression_settings(&id, &settings)?;
+            }
+            if current.vad_threshold != saved.vad_threshold {
+                self.set_channel_vad_threshold(&id, saved.vad_threshold)?;
+            }
+            if current.noise_suppression_enabled != saved.noise_suppression_enabled {
+                self.set_channel_noise_suppression(&id, saved.noise_suppression_enabled)?;
+            }
+            if current.talk != saved.talk {
+                let settings = TalkInfo {
+                    mode: saved.talk.mode,
+                    release_ms: saved.talk.release_ms as f64,
+                    fade_ms: saved.talk.fade_ms as f64,
+                };
+                self.set_channel_talk_settings(&id, &settings)?;
+            }
+            if (current.sidetone_enabled, current.sidetone_volume_db)
+                != (saved.sidetone_enabled, saved.sidetone_volume_db)
+            {
+                self.set_channel_sidetone(&id, saved.sidetone_enabled, saved.sidetone_volume_db)?;
+            }
+        }
+
+        if current.plugin_chain != saved.plugin_chain {
+            let old_instances: Vec<Uuid> = current
+                .plugin_instance_ids
+                .iter()
+                .flatten()
+                .copied()
+                .collect();
+            if let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == saved.id) {
+                channel.plugin_chain = saved.plugin_chain.clone();
+                load_plugin_chain(&mut self.plugin_manager, channel);
+            }
+            // Swap the running chain over before unloading the old instances
+            self.sync_plugin_filter(saved.id);
+            for instance_id in old_instances {
+                self.plugin_manager.unload(instance_id);
+            }
+        }
+
+        if let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == saved.id) {
+            channel.assigned_apps = saved.assigned_apps.clone();
+            // Picked up by the next auto level update
+            channel.auto_level = saved.auto_level;
+        }
+        // Newly assigned apps that are already playing move now; apps no
+        // longer assigned stay where they are until they reconnect.
+        self.try_auto_route_pending_apps(saved.id);
+        Ok(())
+    }
+
+    /// Start (or retarget) a fade of a channel's fader level.
+    fn fade_channel_volume(&mut self, channel_id: Uuid, to_db: f32, fade_ms: u32) {
+        let Some(channel) = self.state.channels.iter().find(|c| c.id == channel_id) else {
+            return;
+        };
+        if channel.volume_db == to_db {
+            self.state.channel_fades.remove(&channel_id);
+        } else {
+            self.state.channel_fades.insert(
+                channel_id,
+                VolumeFade::new(channel.volume_db, to_db, fade_ms),
+            );
+        }
+    }
+
+    /// Advance scene volume fades by `dt` seconds and push the new levels.
+    pub fn update_fades(&mut self, dt: f32) {
+        let mut volume_updates = Vec::new();
+
+        let fades = &mut self.state.channel_fades;
+        for channel in &mut self.state.channels {
+            let Some(fade) = fades.get_mut(&channel.id) else {
+                continue;
+            };
+            channel.volume_db = fade.advance(dt);
+            if fade.is_done() {
+                fades.remove(&channel.id);
+            }
+            if let Some(node_id) = channel.volume_node() {
+                volume_updates.push((node_id, channel.volume_linear()));
+            }
+        }
+
+        if let Some(fade) = self.state.master_fade.as_mut() {
+            self.state.master_volume_db = fade.advance(dt);
+            if fade.is_done() {
+                self.state.master_fade = None;
+            }
+            if let Some(node_id) = self.get_master_output_device_id() {
+                volume_updates.push((node_id, db_to_linear(self.state.master_volume_db)));
+            }
+        }
+
+        for (node_id, volume) in volume_updates {
+            self.send_pw_command(PwCommand::SetVolume { node_id, volume });
+        }
+    }
+
+    // ==================== Recording ====================
+
+    /// Start recording `sources` ("master" or channel UUIDs) to one file each
+    /// in the recording directory. Returns the recording ID.
+    pub fn start_recording(
+        &mut self,
+        sources: &[String],
+        format: &str,
+    ) -> Result<String, ServiceError> {
+        let format = RecordingFormat::parse(format).ok_or_else(|| {
+            ServiceError::InvalidArgument(format!(
+                "Unknown recording format '{}' (expected wav or flac)",
+                format
+            ))
+        })?;
+        if sources.is_empty() {
+            return Err(ServiceError::InvalidArgument(
+                "Nothing to record".to_string(),
+            ));
+        }
+
+        let mut resolved = Vec::with_capacity(sources.len());
+        let mut labels = Vec::with_capacity(sources.len());
+        for source in sources {
+            let (source, label) = if source.eq_ignore_ascii_case(RecordSource::MASTER) {
+                (RecordSource::Master, RecordSource::MASTER.to_string())
+            } else {
+                let id = Uuid::parse_str(source)
+                    .map_err(|_| ServiceError::ChannelNotFound(source.clone()))?;
+                let channel = self
+                    .state
+                    .channels
+                    .iter()
+                    .find(|c| c.id == id)
+                    .ok_or_else(|| ServiceError::ChannelNotFound(source.clone()))?;
+                (RecordSource::Channel(id), channel.name.clone())
+            };
+            if resolved.contains(&source) {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "Source '{}' listed twice",
+                    label
+                )));
+            }
+            resolved.push(source);
+            labels.push(label);
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let paths =
+            crate::recording::track_paths(&self.recording_config.directory(), now, &labels, format);
+        let session = RecordingSession::start(paths, format)?;
+        let id = session.id;
+
+        info!(
+            "Recording {} started: {:?} -> {:?}",
+            id, labels, session.paths
+        );
+        self.send_pw_command(PwCommand::CreateRecordStream {
+            recording_id: id,
+            buffer: std::sync::Arc::clone(session.buffer()),
+        });
+        self.recordings.push(ActiveRecording {
+            linked: vec![false; resolved.len()],
+            sources: resolved,
+            node_id: None,
+            session,
+        });
+        Ok(id.to_string())
+    }
+
+    /// Stop a recording and finalize its files. Returns its final status.
+    pub fn stop_recording(&mut self, recording_id: &str) -> Result<RecordingStatus, ServiceError> {
+        let id = Uuid::parse_str(recording_id).map_err(|_| {
+            ServiceError::InvalidArgument(format!("Invalid recording ID: {}", recording_id))
+        })?;
+        let index = self
+            .recordings
+            .iter()
+            .position(|r| r.session.id == id)
+            .ok_or_else(|| {
+                ServiceError::InvalidArgument(format!("No such recording: {}", recording_id))
+            })?;
+
+        let mut recording = self.recordings.remove(index);
+        self.send_pw_command(PwCommand::DestroyRecordStream { recording_id: id });
+        let result = recording.session.stop();
+        let status = recording.status();
+        self.emit_signal(SignalEvent::RecordingProgress(status.clone()));
+        result?;
+
+        info!(
+            "Recording {} stopped: {:.1}s, {} bytes",
+            id, status.elapsed_secs, status.bytes
+        );
+        Ok(status)
+    }
+
+    pub fn get_recordings(&self) -> Vec<RecordingStatus> {
+        self.recordings
+            .iter()
+            .map(ActiveRecording::status)
+            .collect()
+    }
+
+    pub fn get_recording_directory(&self) -> String {
+        self.recording_config
+            .directory()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Set where new recordings are written. An empty string restores the default.
+    pub fn set_recording_directory(&mut self, directory: &str) -> Result<(), ServiceError> {
+        let directory = directory.trim();
+        let directory = if directory.is_empty() {
+            None
+        } else {
+            let path = std::path::PathBuf::from(directory);
+            if !path.is_absolute() {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "Recording directory must be an absolute path: {}",
+                    directory
+                )));
+            }
+            Some(path)
+        };
+
+        self.recording_config.directory = directory;
+        self.config_manager
+            .save_recording_config(&self.recording_config)?;
+        Ok(())
+    }
+
+    /// Node a recording source's audio is taken from: the master output
+    /// device's monitor, an output channel's last processing stage, or an
+    /// input channel's virtual source.
+    fn record_source_node(&self, source: RecordSource) -> Option<u32> {
+        match source {
+            RecordSource::Master => self.get_master_output_device_id(),
+            RecordSource::Channel(id) => {
+                let channel = self.state.channels.iter().find(|c| c.id == id)?;
+                if channel.is_input() {
+                    channel.pw_source_id
+                } else {
+                    channel.output_stage_id()
+                }
+            }
+        }
+    }
+
+    /// Links feeding `source` into capture stream channels `first_channel`
+    /// and `first_channel + 1` of `capture_node`, if all four ports exist.
+    fn capture_links(
+        &self,
+        capture_node: u32,
+        first_channel: usize,
+        source: RecordSource,
+    ) -> Option<[(u32, u32); 2]> {
+        let graph = &self.state.pw_graph;
+        let (left, right) = self
+            .record_source_node(source)
+            .and_then(|node| graph.stereo_output_ports(node))?;
+        let capture_port = |channel: usize| {
+            let name = record_port_name(first_channel + channel);
+            graph
+                .ports
+                .values()
+                .find(|p| {
+                    p.node_id == capture_node
+                        && p.direction == PortDirection::Input
+                        && p.name == name
+                })
+                .map(|p| p.id)
+        };
+        Some([(left, capture_port(0)?), (right, capture_port(1)?)])
+    }
+
+    /// Link every recording track and replay buffer whose links aren't in
+    /// place yet. Those whose source or capture ports haven't appeared are
+    /// retried on the next `PortAdded`.
+    fn link_recordings(&mut self) {
+        let mut links = Vec::new();
+
+        for index in 0..self.recordings.len() {
+            let Some(record_node) = self.recordings[index].node_id else {
+                continue;
+            };
+            for track in 0..self.recordings[index].sources.len() {
+                if self.recordings[index].linked[track] {
+                    continue;
+                }
+                let source = self.recordings[index].sources[track];
+                let Some(pairs) = self.capture_links(record_node, track * TRACK_CHANNELS, source)
+                else {
+                    continue;
+                };
+
+                debug!(
+                    "Linking recording {} track {} ({:?})",
+                    self.recordings[index].session.id, track, source
+                );
+                links.extend(pairs);
+                self.recordings[index].linked[track] = true;
+            }
+        }
+
+        let unlinked: Vec<(Uuid, u32)> = self
+            .replays
+            .iter()
+            .filter(|(_, r)| !r.linked)
+            .filter_map(|(&id, r)| Some((id, r.node_id?)))
+            .collect();
+        for (channel_id, replay_node) in unlinked {
+            let Some(pairs) = self.capture_links(replay_node, 0, RecordSource::Channel(channel_id))
+            else {
+                continue;
+            };
+            debug!("Linking replay buffer of channel {}", channel_id);
+            links.extend(pairs);
+            if let Some(replay) = self.replays.get_mut(&channel_id) {
+                replay.linked = true;
+            }
+        }
+
+        for (output_port, input_port) in links {
+            self.send_pw_command(PwCommand::CreateLink {
+                output_port,
+                input_port,
+            });
+        }
+    }
+
+    /// A link into a recording's capture stream went away (typically a
+    /// channel reroute or a processing stage coming or going). Mark the
+    /// track for relinking against the source's current node.
+    fn handle_recording_link_removed(&mut self, link: &PwLink) {
+        if let Some(replay) = self
+            .replays
+            .values_mut()
+            .find(|r| r.node_id == Some(link.input_node))
+        {
+            replay.linked = false;
+            self.link_recordings();
+            return;
+        }
+
+        let Some(recording) = self
+            .recordings
+            .iter_mut()
+            .find(|r| r.node_id == Some(link.input_node))
+        else {
+            return;
+        };
+        let track = self
+            .state
+            .pw_graph
+            .ports
+            .get(&link.input_port)
+            .and_then(|p| p.name.strip_prefix("input_AUX"))
+            .and_then(|n| n.parse::<usize>().ok())
+            .map(|channel| channel / TRACK_CHANNELS);
+        if let Some(linked) = track.and_then(|t| recording.linked.get_mut(t)) {
+            *linked = false;
+        }
+        self.link_recordings();
+    }
+
+    /// Whether `node_id` is a recording's or replay buffer's capture stream.
+    fn is_record_node(&self, node_id: u32) -> bool {
+        self.recordings.iter().any(|r| r.node_id == Some(node_id))
+            || self.replays.values().any(|r| r.node_id == Some(node_id))
+    }
+
+    /// Drop recordings whose writer failed and emit progress signals about
+    /// once per second. Called from the meter polling loop.
+    pub fn update_recordings(&mut self, dt: f32) {
+        let failed: Vec<Uuid> = self
+            .recordings
+            .iter()
+            .filter(|r| r.session.has_failed())
+            .map(|r| r.session.id)
+            .collect();
+        for id in failed {
+            error!("Recording {} stopped: writer failed", id);
+            if let Err(e) = self.stop_recording(&id.to_string()) {
+                error!("Failed to stop recording {}: {}", id, e);
+            }
+        }
+
+        if self.recordings.is_empty() {
+            self.recording_progress_timer = 0.0;
+            return;
+        }
+        self.recording_progress_timer += dt;
+        if self.recording_progress_timer < RECORDING_PROGRESS_INTERVAL {
+            return;
+        }
+        self.recording_progress_timer = 0.0;
+
+        for recording in &self.recordings {
+            self.emit_signal(SignalEvent::RecordingProgress(recording.status()));
+        }
+    }
+
+    // ==================== Instant Replay ====================
+
+    /// Start the replay buffers saved in the recording config. Called once
+    /// the channels' nodes are being created.
+    fn start_replay_buffers(&mut self) {
+        let channel_ids: Vec<Uuid> = self
+            .recording_config
+            .replay_channels
+            .iter()
+            .copied()
+            .filter(|id| self.state.channels.iter().any(|c| c.id == *id))
+            .collect();
+        for channel_id in channel_ids {
+            if let Err(e) = self.start_replay(channel_id) {
+                error!(
+                    "Failed to start replay buffer for channel {}: {}",
+                    channel_id, e
+                );
+            }
+        }
+    }
+
+    fn start_replay(&mut self, channel_id: Uuid) -> Result<(), ServiceError> {
+        if self.replays.contains_key(&channel_id) {
+            return Ok(());
+        }
+        let buffer = ReplayBuffer::start(self.recording_config.replay_seconds)?;
+        info!(
+            "Replay buffer started for channel {} ({}s)",
+            channel_id, self.recording_config.replay_seconds
+        );
+        self.send_pw_command(PwCommand::CreateReplayStream {
+            channel_id,
+            feed: buffer.feed(),
+        });
+        self.replays.insert(
+            channel_id,
+            ActiveReplay {
+                buffer,
+                node_id: None,
+                linked: false,
+            },
+        );
+        Ok(())
+    }
+
+    fn stop_replay(&mut self, channel_id: Uuid) {
+        if self.replays.remove(&channel_id).is_some() {
+            info!("Replay buffer stopped for channel {}", channel_id);
+            self.send_pw_command(PwCommand::DestroyReplayStream { channel_id });
+        }
+    }
+
+    /// Enable or disable the instant-replay buffer of a channel.
+    pub fn set_channel_replay(
+        &mut self,
+        channel_id: &str,
+        enabled: bool,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        if !self.state.channels.iter().any(|c| c.id == id) {
+            return Err(ServiceError::ChannelNotFound(channel_id.to_string()));
+        }
+
+        if enabled {
+            self.start_replay(id)?;
+            if !self.recording_config.replay_channels.contains(&id) {
+                self.recording_config.replay_channels.push(id);
+            }
+        } else {
+            self.stop_replay(id);
+            self.recording_config.replay_channels.retain(|&c| c != id);
+        }
+        self.config_manager
+            .save_recording_config(&self.recording_config)?;
+        Ok(())
+    }
+
+    /// Replay buffers in channel order.
+    pub fn get_replay_buffers(&self) -> Vec<ReplayBufferInfo> {
+        self.state
+            .channels
+            .iter()
+            .filter_map(|c| {
+                let replay = self.replays.get(&c.id)?;
+                Some(ReplayBufferInfo {
+                    channel_id: c.id.to_string(),
+                    buffered_secs: replay.buffer.buffered_secs(),
+                    dropped_frames: replay.buffer.dropped_frames(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn get_replay_length(&self) -> u32 {
+        self.recording_config.replay_seconds
+    }
+
+    /// Set how many seconds every replay buffer keeps. Shrinking discards
+    /// the oldest audio.
+    pub fn set_replay_length(&mut self, seconds: u32) -> Result<(), ServiceError> {
+        if !REPLAY_SECONDS_RANGE.contains(&seconds) {
+            return Err(ServiceError::InvalidArgument(format!(
+                "Replay length must be {}-{} seconds (got {})",
+                REPLAY_SECONDS_RANGE.start(),
+                REPLAY_SECONDS_RANGE.end(),
+                seconds
+            )));
+        }
+
+        self.recording_config.replay_seconds = seconds;
+        for replay in self.replays.values() {
+            replay.buffer.set_seconds(seconds);
+        }
+        self.config_manager
+            .save_recording_config(&self.recording_config)?;
+        Ok(())
+    }
+
+    /// Copy the last `seconds` (0 = everything buffered) of the given
+    /// channels' replay buffers, or of every buffer if `channel_ids` is
+    /// empty. The clips are written by [`ReplaySave::write`], one file per
+    /// channel in the recording directory.
+    pub fn snapshot_replay(
+        &self,
+        channel_ids: &[String],
+        seconds: u32,
+        format: &str,
+    ) -> Result<ReplaySave, ServiceError> {
+        let format = RecordingFormat::parse(format).ok_or_else(|| {
+            ServiceError::InvalidArgument(format!(
+                "Unknown recording format '{}' (expected wav or flac)",
+                format
+            ))
+        })?;
+
+        let channels: Vec<&ChannelState> = if channel_ids.is_empty() {
+            self.state
+                .channels
+                .iter()
+                .filter(|c| self.replays.contains_key(&c.id))
+                .collect()
+        } else {
+            channel_ids
+                .iter()
+                .map(|channel_id| {
+                    Uuid::parse_str(channel_id)
+                        .ok()
+                        .and_then(|id| self.state.channels.iter().find(|c| c.id == id))
+                        .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.clone()))
+                })
+                .collect::<Result<_, _>>()?
+        };
+        if channels.is_empty() {
+            return Err(ServiceError::InvalidArgument(
+                "No channel has a replay buffer".to_string(),
+            ));
+        }
+
+        let mut clips = Vec::with_capacity(channels.len());
+        let mut labels = Vec::with_capacity(channels.len());
+        for channel in channels {
+            let replay = self.replays.get(&channel.id).ok_or_else(|| {
+                ServiceError::InvalidArgument(format!(
+                    "Channel '{}' has no replay buffer",
+                    channel.name
+                ))
+            })?;
+            let clip = replay.buffer.clip(seconds);
+            if clip.samples.is_empty() {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "Replay buffer of '{}' is empty",
+                    channel.name
+                )));
+            }
+            clips.push(clip);
+            labels.push(format!("{}-replay", channel.name));
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let paths =
+            crate::recording::track_paths(&self.recording_config.directory(), now, &labels, format);
+        info!("Saving replay clips: {:?}", paths);
+        Ok(ReplaySave {
+            format,
+            clips: paths.into_iter().zip(clips).collect(),
+        })
+    }
+
+    // ==================== Monitor ====================
+
+    pub fn get_monitor(&self) -> MonitorInfo {
+        MonitorInfo {
+            device: self.state.monitor_device.clone().unwrap_or_default(),
+            volume_db: self.state.monitor_volume_db as f64,
+            muted: self.state.monitor_muted,
+            solo_mode: self.state.solo_mode,
+        }
+    }
+
+    /// Solo or unsolo a channel. Solo state isn't saved.
+    pub fn set_channel_solo(&mut self, channel_id: &str, solo: bool) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        channel.solo = solo;
+        self.sync_monitor();
+        Ok(())
+    }
+
+    /// Loudness readings of every metered channel, then the master output.
+    pub fn loudness_data(&self) -> Vec<LoudnessData> {
+        let master = self
+            .master_meter_source
+            .is_some()
+            .then_some((Uuid::nil(), &self.master_loudness));
+        self.state
+            .channels
+            .iter()
+            .filter_map(|c| c.loudness.as_ref().map(|loudness| (c.id, loudness)))
+            .chain(master)
+            .map(|(id, loudness)| loudness_data(id, loudness.load()))
+            .collect()
+    }
+
+    /// Restart loudness integration (integrated loudness, loudness range and
+    /// true peak) of a channel, of the master output for "master", or of
+    /// every meter for an empty ID.
+    pub fn reset_loudness(&mut self, channel_id: &str) -> Result<(), ServiceError> {
+        if channel_id.is_empty() {
+            self.master_loudness.request_reset();
+            let channels = self.state.channels.iter();
+            for loudness in channels.filter_map(|c| c.loudness.as_ref()) {
+                loudness.request_reset();
+            }
+            return Ok(());
+        }
+
+        if channel_id == RecordSource::MASTER {
+            self.master_loudness.request_reset();
+            return Ok(());
+        }
+
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        if let Some(loudness) = &channel.loudness {
+            loudness.request_reset();
+        }
+        Ok(())
+    }
+
+    /// Analyze the spectrum of a channel, or of the master output for
+    /// "master", at `tap`; an empty target stops the analyzer. The master
+    /// output has no plugin chain, so it's always analyzed pre-plugin.
+    pub fn set_spectrum(&mut self, target: &str, tap: SpectrumTap) -> Result<(), ServiceError> {
+        let channel_id = match target {
+            "" => {
+                self.stop_spectrum();
+                return Ok(());
+            }
+            RecordSource::MASTER => None,
+            _ => {
+                let id = Uuid::parse_str(target)
+                    .ok()
+                    .filter(|id| self.state.channels.iter().any(|c| c.id == *id))
+                    .ok_or_else(|| ServiceError::ChannelNotFound(target.to_string()))?;
+                Some(id)
+            }
+        };
+        let tap = if channel_id.is_some() {
+            tap
+        } else {
+            SpectrumTap::Pre
+        };
+
+        let feed = Arc::new(SpectrumFeed::new());
+        self.send_pw_command(PwCommand::SetSpectrumTap {
+            tap: Some(SpectrumTapPoint {
+                channel_id,
+                tap,
+                feed: Arc::clone(&feed),
+            }),
+        });
+        self.spectrum = Some(SpectrumSession {
+            channel_id,
+            tap,
+            feed,
+            analyzer: SpectrumAnalyzer::new(),
+            eq_curve: None,
+        });
+        Ok(())
+    }
+
+    fn stop_spectrum(&mut self) {
+        if self.spectrum.take().is_some() {
+            self.send_pw_command(PwCommand::SetSpectrumTap { tap: None });
+        }
+    }
+
+    /// Analyze the latest audio of the spectrum tap, `dt` seconds after the
+    /// last call. Returns `None` while the analyzer is stopped, and stops it
+    /// if its channel was removed.
+    pub fn spectrum_data(&mut self, dt: f32) -> Option<SpectrumData> {
+        let channel = match self.spectrum.as_ref()?.channel_id {
+            None => None,
+            Some(id) => match self.state.channels.iter().find(|c| c.id == id) {
+                Some(channel) => Some(channel),
+                None => {
+                    self.stop_spectrum();
+                    return None;
+                }
+            },
+        };
+        let eq_bands = channel.filter(|c| c.eq_enabled).map(|c| c.eq_bands.clone());
+
+        let session = self.spectrum.as_mut()?;
+        session.analyzer.update(&session.feed, dt);
+        let data = SpectrumData::new(
+            session.channel_id.unwrap_or(Uuid::nil()),
+            session.tap,
+            session.analyzer.levels_db(),
+            session.analyzer.peaks_db(),
+        );
+
+        let Some(eq_bands) = eq_bands else {
+            return Some(data);
+        };
+        let sample_rate = session.feed.sample_rate();
+        let stale = !matches!(
+            &session.eq_curve,
+            Some((bands, rate, _)) if *bands == eq_bands && *rate == sample_rate
+        );
+        if stale {
+            let curve = (0..SPECTRUM_BANDS)
+                .map(|band| {
+                    crate::audio::eq_filter::eq_response_db(
+                        &eq_bands,
+                        sootmix_ipc::spectrum_band_frequency(band),
+                        f64::from(sample_rate),
+                    )
+                })
+                .collect();
+            session.eq_curve = Some((eq_bands, sample_rate, curve));
+        }
+        let (_, _, curve) = session.eq_curve.as_ref()?;
+        Some(data.with_eq(curve.clone()))
+    }
+
+    /// Point the master loudness meter at the monitor source of the device
+    /// the master output currently resolves to.
+    fn sync_master_meter(&mut self) {
+        if self.backend.is_none() {
+            return;
+        }
+        let source_name = self
+            .get_master_output_device_id()
+            .and_then(|id| self.state.pw_graph.nodes.get(&id))
+            .map(|node| format!("{}.monitor", node.name));
+        if source_name == self.master_meter_source {
+            return;
+        }
+
+        self.master_meter_source = source_name.clone();
+        self.send_pw_command(PwCommand::SetMasterMeter {
+            source_name,
+            loudness: Arc::clone(&self.master_loudness),
+        });
+    }
+
+    /// Unsolo every channel. Returns the IDs of the channels that were soloed.
+    pub fn clear_solo(&mut self) -> Vec<String> {
+        let mut cleared = Vec::new();
+        for channel in self.state.channels.iter_mut().filter(|c| c.solo) {
+            channel.solo = false;
+            cleared.push(channel.id.to_string());
+        }
+        self.sync_monitor();
+        cleared
+    }
+
+    /// Turn an input channel's sidetone on or off and set its level.
+    pub fn set_channel_sidetone(
+        &mut self,
+        channel_id: &str,
+        enabled: bool,
+        volume_db: f32,
+    ) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(channel_id)
+            .map_err(|_| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+        let channel = self
+            .state
+            .channels
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| ServiceError::ChannelNotFound(channel_id.to_string()))?;
+
+        if enabled && !channel.is_input() {
+            return Err(ServiceError::InvalidArgument(
+                "Sidetone is only available on input channels".to_string(),
+            ));
+        }
+        channel.sidetone_enabled = enabled;
+        channel.sidetone_volume_db = volume_db;
+
+        self.sync_monitor();
+        self.apply_monitor_levels(MonitorStage::Sidetone(id));
+        self.save_config();
+        Ok(())
+    }
+
+    /// Set the device the monitor bus plays on (empty = the master output).
+    pub fn set_monitor_device(&mut self, device_name: &str) -> Result<(), ServiceError> {
+        self.state.monitor_device = (!device_name.is_empty()).then(|| device_name.to_string());
+        self.sync_monitor();
+        self.save_config();
+        Ok(())
+    }
+
+    pub fn set_monitor_volume(&mut self, volume_db: f64) -> Result<(), ServiceError> {
+        self.state.monitor_volume_db = volume_db as f32;
+        self.apply_monitor_levels(MonitorStage::Bus);
+        self.save_config();
+        Ok(())
+    }
+
+    pub fn set_monitor_mute(&mut self, muted: bool) -> Result<(), ServiceError> {
+        self.state.monitor_muted = muted;
+        self.apply_monitor_levels(MonitorStage::Bus);
+        self.save_config();
+        Ok(())
+    }
+
+    pub fn set_solo_mode(&mut self, mode: SoloMode) -> Result<(), ServiceError> {
+        self.state.solo_mode = mode;
+        self.sync_monitor();
+        self.save_config();
+        Ok(())
+    }
+
+    /// Node the monitor bus plays on: the configured monitor device if it's
+    /// present, otherwise the master output.
+    fn monitor_device_node_id(&self) -> Option<u32> {
+        if let Some(name) = self.state.monitor_device.as_ref() {
+            if name == Self::SYSTEM_DEFAULT_SENTINEL {
+                return self.state.pw_graph.default_sink_id();
+            }
+            if let Some(output) = self
+                .state
+                .get_outputs()
+                .iter()
+                .find(|o| o.node_id != 0 && (o.description == *name || o.name == *name))
+            {
+                return Some(output.node_id);
+            }
+            debug!(
+                "Monitor device '{}' not found, falling back to the master output",
+                name
+            );
+        }
+        self.get_master_output_device_id()
+    }
+
+    /// Node a channel is heard from on the monitor bus: an input channel's
+    /// virtual source, or an output channel's sink monitor (pre-fader).
+    fn monitor_tap_node(channel: &ChannelState) -> Option<u32> {
+        if channel.is_input() {
+            channel.pw_source_id
+        } else {
+            channel.pw_sink_id
+        }
+    }
+
+    /// Whether `node_id` is one of the monitor bus's own nodes.
+    fn is_monitor_node(&self, node_id: u32) -> bool {
+        self.monitor
+            .stages
+            .values()
+            .flatten()
+            .any(|n| n.sink == node_id || n.output == node_id)
+    }
+
+    /// Whether ports appearing on `node_id` may complete a monitor link: a
+    /// stage, a channel's tap or source, or an output device.
+    fn is_monitor_port_node(&self, node_id: u32) -> bool {
+        self.is_monitor_node(node_id)
+            || self
+                .state
+                .channels
+                .iter()
+                .any(|c| c.pw_sink_id == Some(node_id) || c.pw_source_id == Some(node_id))
+            || self
+                .state
+                .pw_graph
+                .nodes
+                .get(&node_id)
+                .is_some_and(|n| n.media_class == MediaClass::AudioSink)
+    }
+
+    /// Push a stage's level (and the bus mute) to its loopback output.
+    fn apply_monitor_levels(&mut self, stage: MonitorStage) {
+        let Some(output) = self
+            .monitor
+            .stages
+            .get(&stage)
+            .copied()
+            .flatten()
+            .map(|n| n.output)
+        else {
+            return;
+        };
+        match stage {
+            MonitorStage::Bus => {
+                self.send_pw_command(PwCommand::SetVolume {
+                    node_id: output,
+                    volume: db_to_linear(self.state.monitor_volume_db),
+                });
+                self.send_pw_command(PwCommand::SetMute {
+                    node_id: output,
+                    muted: self.state.monitor_muted,
+                });
+            }
+            MonitorStage::Sidetone(channel_id) => {
+                let Some(volume_db) = self
+                    .state
+                    .channels
+                    .iter()
+                    .find(|c| c.id == channel_id)
+                    .map(|c| c.sidetone_volume_db)
+                else {
+                    return;
+                };
+                self.send_pw_command(PwCommand::SetVolume {
+                    node_id: output,
+                    volume: db_to_linear(volume_db),
+                });
+            }
+        }
+    }
+
+    /// Bring the monitor bus in line with the solo and sidetone state: apply
+    /// solo-in-place cuts, create or destroy stages, and create or destroy
+    /// the links between channels, stages and the monitor device. Links whose
+    /// ports haven't appeared yet are retried on the next `PortAdded`.
+    fn sync_monitor(&mut self) {
+        let plan = plan_solo(
+            self.state.solo_mode,
+            self.state
+                .channels
+                .iter()
+                .map(|c| (c.id, c.is_input(), c.solo)),
+        );
+
+        let mut volume_updates = Vec::new();
+        for channel in &mut self.state.channels {
+            let cut = plan.cut.contains(&channel.id);
+            if channel.solo_cut != cut {
+                channel.solo_cut = cut;
+                if let Some(node_id) = channel.volume_node() {
+                    volume_updates.push((node_id, channel.volume_linear()));
+                }
+            }
+        }
+        for (node_id, volume) in volume_updates {
+            self.send_pw_command(PwCommand::SetVolume { node_id, volume });
+        }
+
+        // Stages are rebuilt on reconnect
+        if self.backend.is_none() {
+            return;
+        }
+
+        let sidetones: Vec<Uuid> = self
+            .state
+            .channels
+            .iter()
+            .filter(|c| c.is_input() && c.sidetone_enabled)
+            .map(|c| c.id)
+            .collect();
+        let mut wanted: HashSet<MonitorStage> = sidetones
+            .iter()
+            .map(|&id| MonitorStage::Sidetone(id))
+            .collect();
+        if !wanted.is_empty() || !plan.monitored.is_empty() {
+            wanted.insert(MonitorStage::Bus);
+        }
+
+        // Stages still being created are destroyed once they report in
+        let unwanted: Vec<(MonitorStage, StageNodes)> = self
+            .monitor
+            .stages
+            .iter()
+            .filter(|(stage, _)| !wanted.contains(stage))
+            .filter_map(|(&stage, nodes)| Some((stage, (*nodes)?)))
+            .collect();
+        for (stage, nodes) in unwanted {
+            debug!("Destroying monitor stage {:?}", stage);
+            self.monitor.stages.remove(&stage);
+            self.send_pw_command(PwCommand::DestroyVirtualSink {
+                node_id: nodes.sink,
+            });
+        }
+        for stage in wanted {
+            if !self.monitor.stages.contains_key(&stage) {
+                self.monitor.stages.insert(stage, None);
+                self.send_pw_command(PwCommand::CreateMonitorStage { stage });
+            }
+        }
+
+        // Node pairs that should be linked
+        let stage = |stage: MonitorStage| self.monitor.stages.get(&stage).copied().flatten();
+        let channel = |id: Uuid| self.state.channels.iter().find(|c| c.id == id);
+        let bus = stage(MonitorStage::Bus);
+        let mut desired: Vec<(u32, u32)> = Vec::new();
+        if let Some(bus) = bus {
+            if let Some(device) = self.monitor_device_node_id() {
+                desired.push((bus.output, device));
+            }
+            for &id in &plan.monitored {
+                if let Some(tap) = channel(id).and_then(Self::monitor_tap_node) {
+                    desired.push((tap, bus.sink));
+                }
+            }
+        }
+        for &id in &sidetones {
+            let Some(sidetone) = stage(MonitorStage::Sidetone(id)) else {
+                continue;
+            };
+            if let Some(source) = channel(id).and_then(|c| c.pw_source_id) {
+                desired.push((source, sidetone.sink));
+            }
+            if let Some(bus) = bus {
+                desired.push((sidetone.output, bus.sink));
+            }
+        }
+
+        let graph = &self.state.pw_graph;
+        let mut create = Vec::new();
+        for &(from, to) in &desired {
+            for pair in graph.find_port_pairs(from, to) {
+                let linked = graph
+                    .links
+                    .values()
+                    .any(|l| (l.output_port, l.input_port) == pair);
+                if !linked && !self.monitor.pending_links.contains(&pair) {
+                    create.push(pair);
+                }
+            }
+        }
+
+        // Anything else feeding a stage sink or leaving a stage output is stale
+        let stage_sinks: HashSet<u32> = self
+            .monitor
+            .stages
+            .values()
+            .flatten()
+            .map(|n| n.sink)
+            .collect();
+        let stage_outputs: HashSet<u32> = self
+            .monitor
+            .stages
+            .values()
+            .flatten()
+            .map(|n| n.output)
+            .collect();
+        let stale: Vec<u32> = graph
+            .links
+            .values()
+            .filter(|l| {
+                stage_sinks.contains(&l.input_node) || stage_outputs.contains(&l.output_node)
+            })
+            .filter(|l| !desired.contains(&(l.output_node, l.input_node)))
+            .filter(|l| !self.monitor.pending_unlinks.contains(&l.id))
+            .map(|l| l.id)
+            .collect();
+
+        for link_id in stale {
+            debug!("Removing stale monitor link {}", link_id);
+            self.monitor.pending_unlinks.insert(link_id);
+            self.send_pw_command(PwCommand::DestroyLink { link_id });
+        }
+        for (output_port, input_port) in create {
+            debug!("Linking monitor ports {} -> {}", output_port, input_port);
+            self.monitor.pending_links.insert((output_port, input_port));
+            self.send_pw_command(PwCommand::CreateLink {
+                output_port,
+                input_port,
+            });
+        }
+    }
+
+    // ==================== MIDI ====================
+
+    pub fn get_midi_devices(&self) -> Vec<String> {
+        self.midi_ports
+            .as_ref()
+            .map(MidiPorts::devices)
+            .unwrap_or_default()
+    }
+
+    pub fn get_midi_bindings(&self) -> Vec<MidiBindingInfo> {
+        self.midi_config
+            .bindings
+            .iter()
+            .map(midi_binding_info)
+            .collect()
+    }
+
+    /// Add or update a MIDI binding. An empty ID creates a new one. Returns
+    /// the binding ID.
+    pub fn set_midi_binding(&mut self, info: MidiBindingInfo) -> Result<String, ServiceError> {
+        let id = if info.id.is_empty() {
+            Uuid::new_v4()
+        } else {
+            Uuid::parse_str(&info.id).map_err(|_| {
+                ServiceError::InvalidArgument(format!("Invalid MIDI binding ID: {}", info.id))
+            })?
+        };
+        let control = match info.control.as_str() {
+            "cc" => MidiControlKind::Cc,
+            "note" => MidiControlKind::Note,
+            other => {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "Unknown MIDI control '{}' (expected cc or note)",
+                    other
+                )))
+            }
+        };
+        if !(1..=16).contains(&info.midi_channel) || info.number > 127 {
+            return Err(ServiceError::InvalidArgument(
+                "MIDI channel must be 1-16 and number 0-127".to_string(),
+            ));
+        }
+
+        let binding = MidiBinding {
+            id,
+            device: Some(info.device.clone()).filter(|d| !d.is_empty()),
+            midi_channel: info.midi_channel,
+            control,
+            number: info.number,
+            target: self.midi_target_from_info(&info)?,
+        };
+        self.add_midi_binding(binding);
+        self.config_manager.save_midi_config(&self.midi_config)?;
+        Ok(id.to_string())
+    }
+
+    pub fn delete_midi_binding(&mut self, binding_id: &str) -> Result<(), ServiceError> {
+        let id = Uuid::parse_str(binding_id)
+            .ok()
+            .filter(|id| self.midi_config.bindings.iter().any(|b| b.id == *id))
+            .ok_or_else(|| {
+                ServiceError::InvalidArgument(format!("No MIDI binding {}", binding_id))
+            })?;
+        self.midi_config.bindings.retain(|b| b.id != id);
+        self.midi_feedback.remove(&id);
+        self.config_manager.save_midi_config(&self.midi_config)?;
+        Ok(())
+    }
+
+    /// Bind the next control moved on any controller to the target of
+    /// `info` (its control fields are ignored).
+    pub fn start_midi_learn(&mut self, info: MidiBindingInfo) -> Result<(), ServiceError> {
+        if self.midi_ports.is_none() {
+            return Err(ServiceError::InvalidArgument(
+                "MIDI control is unavailable".to_string(),
+            ));
+        }
+        let target = self.midi_target_from_info(&info)?;
+        info!("MIDI learn started for {:?}", target);
+        self.midi_learn = Some(target);
+        Ok(())
+    }
+
+    pub fn cancel_midi_learn(&mut self) {
+        if self.midi_learn.take().is_some() {
+            info!("MIDI learn cancelled");
+        }
+    }
+
+    fn midi_target_from_info(&self, info: &MidiBindingInfo) -> Result<MidiTarget, ServiceError> {
+        let channel = || {
+            Uuid::parse_str(&info.channel_id)
+                .ok()
+                .filter(|id| self.state.channels.iter().any(|c| c.id == *id))
+                .ok_or_else(|| ServiceError::ChannelNotFound(info.channel_id.clone()))
+        };
+        Ok(match info.target.as_str() {
+            "channel_volume" => MidiTarget::ChannelVolume {
+                channel: channel()?,
+            },
+            "channel_mute" => MidiTarget::ChannelMute {
+                channel: channel()?,
+            },
+            "channel_solo" => MidiTarget::ChannelSolo {
+                channel: channel()?,
+            },
+            "channel_talk" => MidiTarget::ChannelTalk {
+                channel: channel()?,
+            },
+            "master_volume" => MidiTarget::MasterVolume,
+            "master_mute" => MidiTarget::MasterMute,
+            "plugin_parameter" => MidiTarget::PluginParameter {
+                channel: channel()?,
+                slot: info.plugin_slot as usize,
+                param_index: info.param_index,
+            },
+            other => {
+                return Err(ServiceError::InvalidArgument(format!(
+                    "Unknown MIDI target '{}'",
+                    other
+                )))
+            }
+        })
+    }
+
+    /// Store a binding, replacing the one with the same ID and any other
+    /// binding of the same control (a control drives one target).
+    fn add_midi_binding(&mut self, binding: MidiBinding) {
+        self.midi_config.bindings.retain(|b| {
+            b.id != binding.id
+                && !(b.device == binding.device
+                    && b.midi_channel == binding.midi_channel
+                    && b.control == binding.control
+                    && b.number == binding.number)
+        });
+        self.midi_feedback.remove(&binding.id);
+        self.midi_config.bindings.push(binding);
+    }
+
+    /// Apply messages from controllers, finish MIDI learn and send feedback
+    /// for bound values that changed. Called from the meter polling loop.
+    pub fn update_midi(&mut self, dt: f32) {
+        let Some(ports) = self.midi_ports.as_mut() else {
+            return;
+        };
+        self.midi_refresh_timer += dt;
+        if self.midi_refresh_timer >= MIDI_REFRESH_INTERVAL {
+            self.midi_refresh_timer = 0.0;
+            let before = ports.devices();
+            ports.refresh();
+            if ports.devices() != before {
+                // Bring (re)connected surfaces up to date
+                self.midi_feedback.clear();
+            }
+        }
+
+        // A fader sweep sends dozens of values per tick; only the last one
+        // per binding matters. Button presses are applied one by one.
+        let mut continuous: Vec<(Uuid, MidiTarget, u8)> = Vec::new();
+        for event in ports.poll() {
+            let message = event.message;
+            if let Some(target) = self.midi_learn {
+                if !message.is_press() {
+                    continue;
+                }
+                self.midi_learn = None;
+                let binding = MidiBinding {
+                    id: Uuid::new_v4(),
+                    device: Some(event.device.clone()),
+                    midi_channel: message.channel,
+                    control: message.kind,
+                    number: message.number,
+                    target,
+                };
+                info!(
+                    "MIDI learn: {} ch {} {:?} {} -> {:?}",
+                    event.device, message.channel, message.kind, message.number, target
+                );
+                self.emit_signal(SignalEvent::MidiBindingLearned(midi_binding_info(&binding)));
+                self.add_midi_binding(binding);
+                if let Err(e) = self.config_manager.save_midi_config(&self.midi_config) {
+                    error!("Failed to save MIDI config: {}", e);
+                }
+                continue;
+            }
+
+            let matches: Vec<(Uuid, MidiTarget)> = self
+                .midi_config
+                .bindings
+                .iter()
+                .filter(|b| {
+                    b.midi_channel == message.channel
+                        && b.control == message.kind
+                        && b.number == message.number
+                        && b.device.as_deref().is_none_or(|d| d == event.device)
+                })
+                .map(|b| (b.id, b.target))
+                .collect();
+            if matches.is_empty() {
+                // Not a mixer control: let plugins that take MIDI have it
+                self.plugin_manager
+                    .queue_event_all(message.to_plugin_event());
+                continue;
+            }
+            for (binding_id, target) in matches {
+                match target {
+                    MidiTarget::ChannelMute { .. }
+                    | MidiTarget::ChannelSolo { .. }
+                    | MidiTarget::MasterMute => {
+                        if message.is_press() {
+                            self.apply_midi_toggle(target);
+                        }
+                    }
+                    MidiTarget::ChannelTalk { channel } => {
+                        // Held like a key: press and release both count
+                        if let Err(e) = self.set_talk_key(&channel.to_string(), message.is_press())
+                        {
+                            warn!("MIDI control for {:?} failed: {}", target, e);
+                        }
+                    }
+                    _ => {
+                        continuous.retain(|(id, _, _)| *id != binding_id);
+                        continuous.push((binding_id, target, message.value));
+                    }
+                }
+            }
+        }
+        for (binding_id, target, value) in continuous {
+            // Don't echo the value back at the control that sent it
+            self.midi_feedback.insert(binding_id, value);
+            self.apply_midi_value(target, value);
+        }
+
+        self.send_midi_feedback();
+    }
+
+    /// Flip a mute or solo from a button press.
+    fn apply_midi_toggle(&mut self, target: MidiTarget) {
+        let result = match target {
+            MidiTarget::ChannelMute { channel } => {
+                let Some(muted) = self
+                    .state
+                    .channels
+                    .iter()
+                    .find(|c| c.id == channel)
+                    .map(|c| !c.muted)
+                else {
+                    return;
+                };
+                self.set_channel_mute(&channel.to_string(), muted)
+                    .map(|()| {
+                        self.emit_signal(SignalEvent::ChannelMuteChanged {
+                            channel_id: channel.to_string(),
+                            muted,
+                        })
+                    })
+            }
+            MidiTarget::ChannelSolo { channel } => {
+                let Some(solo) = self
+                    .state
+                    .channels
+                    .iter()
+                    .find(|c| c.id == channel)
+                    .map(|c| !c.solo)
+                else {
+                    return;
+                };
+                self.set_channel_solo(&channel.to_string(), solo).map(|()| {
+                    self.emit_signal(SignalEvent::ChannelSoloChanged {
+                        channel_id: channel.to_string(),
+                        solo,
+                    })
+                })
+            }
+            MidiTarget::MasterMute => {
+                let muted = !self.state.master_muted;
+                self.set_master_mute(muted)
+                    .map(|()| self.emit_signal(SignalEvent::MasterMuteChanged(muted)))
+            }
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            warn!("MIDI control for {:?} failed: {}", target, e);
+        }
+    }
+
+    /// Set a fader or plugin parameter from a 0-127 controller value.
+    fn apply_midi_value(&mut self, target: MidiTarget, value: u8) {
+        let result = match target {
+            MidiTarget::ChannelVolume { channel } => {
+                let volume_db = crate::midi::value_to_db(value) as f64;
+                self.set_channel_volume(&channel.to_string(), volume_db)
+                    .map(|()| {
+                        self.emit_signal(SignalEvent::ChannelVolumeChanged {
+                            channel_id: channel.to_string(),
+                            volume_db,
+                        })
+                    })
+            }
+            MidiTarget::MasterVolume => {
+                let volume_db = crate::midi::value_to_db(value) as f64;
+                self.set_master_volume(volume_db)
+                    .map(|()| self.emit_signal(SignalEvent::MasterVolumeChanged(volume_db)))
+            }
+            MidiTarget::PluginParameter {
+                channel,
+                slot,
+                param_index,
+            } => {
+                let Some(info) = self
+                    .plugin_instance(channel, slot)
+                    .and_then(|id| self.plugin_manager.get_parameter_info(id, param_index))
+                else {
+                    return;
+                };
+                let mut param = sootmix_plugin_api::denormalize(
+                    crate::midi::value_to_normalized(value),
+                    info.min,
+                    info.max,
+                    info.curve,
+                );
+                if info.step > 0.0 {
+                    param = (info.min + ((param - info.min) / info.step).round() * info.step)
+                        .clamp(info.min, info.max);
+                }
+                self.set_channel_plugin_parameter(&channel.to_string(), slot, param_index, param)
+            }
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            warn!("MIDI control for {:?} failed: {}", target, e);
+        }
     }
 
-    /// Resolve an input device name to its PipeWire node ID.
-    fn resolve_input_device_to_node_id(&self, device_name: &str) -> Option<u32> {
+    /// Loaded plugin instance in `slot` of a channel's chain.
+    fn plugin_instance(&self, channel: Uuid, slot: usize) -> Option<Uuid> {
         self.state
-            .pw_graph
-            .nodes
-            .values()
-            .find(|n| n.is_audio_input() && (n.name == device_name || n.description == device_name))
-            .map(|n| n.id)
+            .channels
+            .iter()
+            .find(|c| c.id == channel)?
+            .plugin_instance_ids
+            .get(slot)
+            .copied()
+            .flatten()
+    }
+
+    /// Current value of a target as a 0-127 controller value.
+    fn midi_target_value(&self, target: MidiTarget) -> Option<u8> {
+        let toggle = |on: bool| if on { 127 } else { 0 };
+        let channel = |id: Uuid| self.state.channels.iter().find(|c| c.id == id);
+        match target {
+            MidiTarget::ChannelVolume { channel: id } => {
+                channel(id).map(|c| crate::midi::db_to_value(c.volume_db))
+            }
+            MidiTarget::ChannelMute { channel: id } => channel(id).map(|c| toggle(c.muted)),
+            MidiTarget::ChannelSolo { channel: id } => channel(id).map(|c| toggle(c.solo)),
+            MidiTarget::ChannelTalk { channel: id } => channel(id).map(|c| toggle(c.live)),
+            MidiTarget::MasterVolume => Some(crate::midi::db_to_value(self.state.master_volume_db)),
+            MidiTarget::MasterMute => Some(toggle(self.state.master_muted)),
+            MidiTarget::PluginParameter {
+                channel,
+                slot,
+                param_index,
+            } => {
+                let id = self.plugin_instance(channel, slot)?;
+                let info = self.plugin_manager.get_parameter_info(id, param_index)?;
+                let param = self.plugin_manager.get_parameter(id, param_index)?;
+                Some(crate::midi::normalized_to_value(
+                    sootmix_plugin_api::normalize(param, info.min, info.max, info.curve),
+                ))
+            }
+        }
+    }
+
+    /// Send every bound value that differs from what its control last
+    /// showed (motor faders, LEDs), whatever changed it.
+    fn send_midi_feedback(&mut self) {
+        let mut updates = Vec::new();
+        for binding in &self.midi_config.bindings {
+            let Some(value) = self.midi_target_value(binding.target) else {
+                continue;
+            };
+            if self.midi_feedback.get(&binding.id) == Some(&value) {
+                continue;
+            }
+            updates.push((binding.id, value));
+        }
+        if updates.is_empty() {
+            return;
+        }
+
+        let Some(ports) = self.midi_ports.as_mut() else {
+            return;
+        };
+        for (binding_id, value) in updates {
+            let Some(binding) = self
+                .midi_config
+                .bindings
+                .iter()
+                .find(|b| b.id == binding_id)
+            else {
+                continue;
+            };
+            let bytes =
+                MidiMessage::encode(binding.midi_channel, binding.control, binding.number, value);
+            ports.send(binding.device.as_deref(), &bytes);
+            self.midi_feedback.insert(binding_id, value);
+        }
     }
 
     pub fn set_master_volume(&mut self, volume_db: f64) -> Result<(), ServiceError> {
+        self.state.master_fade = None;
         self.state.master_volume_db = volume_db as f32;
 
         if let Some(node_id) = self.get_master_output_device_id() {
@@ -2278,9 +6206,10 @@ impl DaemonService {
         for node_id in &all_node_ids {
             // Set the stream's target to our sink - this tells WirePlumber to stop
             // auto-managing this stream and prevents it from recreating links to default sink
-            if let Err(e) = crate::audio::routing::set_stream_target(*node_id, sink_node_id) {
-                warn!("Failed to set stream target for node {}: {}", node_id, e);
-            }
+            self.send_pw_command(PwCommand::SetStreamTarget {
+                stream_node_id: *node_id,
+                target_node_id: Some(sink_node_id),
+            });
 
             // Destroy links to non-sootmix sinks FIRST
             let links_to_destroy: Vec<u32> = self
@@ -2394,12 +6323,12 @@ impl DaemonService {
             }
         }
 
-        // Case 2: Link was from a channel's loopback output to a hardware device
+        // Case 2: Link was from a channel's output stage (loopback or EQ output) to a hardware device
         let is_loopback_output = self
             .state
             .channels
             .iter()
-            .any(|c| c.pw_loopback_output_id == Some(link.output_node));
+            .any(|c| c.output_stage_id() == Some(link.output_node));
 
         if is_loopback_output {
             // If this link was intentionally destroyed during a reroute, don't restore it
@@ -2434,11 +6363,11 @@ impl DaemonService {
         }
 
         // Case 3: Link was from a mic to an input channel's capture stream
-        let is_capture_stream = self
-            .state
-            .channels
-            .iter()
-            .any(|c| c.pw_loopback_capture_id == Some(link.input_node));
+        // (or its plugin sink while the plugin filter runs)
+        let is_capture_stream = self.state.channels.iter().any(|c| {
+            c.is_input()
+                && c.pw_plugin_sink_id.or(c.pw_loopback_capture_id) == Some(link.input_node)
+        });
 
         if is_capture_stream {
             let source_is_mic = self
@@ -2557,9 +6486,10 @@ impl DaemonService {
         // Unroute ALL matching nodes
         for node_id in &all_node_ids {
             // Clear the stream's target so WirePlumber can manage it again
-            if let Err(e) = crate::audio::routing::clear_stream_target(*node_id) {
-                warn!("Failed to clear stream target for node {}: {}", node_id, e);
-            }
+            self.send_pw_command(PwCommand::SetStreamTarget {
+                stream_node_id: *node_id,
+                target_node_id: None,
+            });
 
             // Find hardware sink to reconnect to
             if let Some(default_id) = default_output {
@@ -2639,7 +6569,7 @@ impl DaemonService {
                 channel.kind,
                 channel.name.clone(),
                 channel.pw_sink_id,
-                channel.pw_loopback_output_id,
+                channel.output_stage_id(),
             )
         };
 
@@ -2658,7 +6588,7 @@ impl DaemonService {
                     channel_name
                 );
                 self.state
-                    .pending_ns_replacements
+                    .pending_source_replacements
                     .insert(channel_uuid, create_cmd);
                 self.send_pw_command(PwCommand::DestroyVirtualSink { node_id: source_id });
             } else {
@@ -2685,6 +6615,8 @@ impl DaemonService {
                     Some(channel_uuid),
                 );
             }
+            // The channel now lines up with the channels on its new device
+            self.sync_latency();
         }
 
         self.save_config();
@@ -2714,12 +6646,14 @@ impl DaemonService {
             .channels
             .iter()
             .filter(|c| c.output_device_name.is_none())
-            .filter_map(|c| c.pw_loopback_output_id.map(|lid| (c.id, lid)))
+            .filter_map(|c| c.output_stage_id().map(|lid| (c.id, lid)))
             .collect();
 
         for (channel_id, loopback_id) in loopback_info {
             self.suppress_and_route(loopback_id, target_device_id, Some(channel_id));
         }
+        self.sync_latency();
+        self.sync_master_meter();
 
         self.save_config();
         Ok(())
@@ -2738,7 +6672,7 @@ impl DaemonService {
         if let Some(name) = self.state.master_output.as_ref() {
             // "system-default" means always follow WirePlumber's default
             if name == Self::SYSTEM_DEFAULT_SENTINEL {
-                return crate::audio::routing::get_default_sink_id();
+                return self.state.pw_graph.default_sink_id();
             }
 
             let outputs = self.state.get_outputs();
@@ -2758,24 +6692,26 @@ impl DaemonService {
             // below prefers analog/speaker over Bluetooth/USB headsets and
             // routes to speakers even when the OS has promoted a headset to
             // default.
-            if let Some(id) = crate::audio::routing::get_default_sink_id() {
+            if let Some(id) = self.state.pw_graph.default_sink_id() {
                 return Some(id);
             }
         }
 
         // Use smart fallback: prefer speakers/analog over HDMI/DisplayPort
-        let exclude: Vec<&str> = self
+        let mut exclude: Vec<&str> = self
             .state
             .channels
             .iter()
             .filter_map(|c| c.pw_sink_id.map(|_| c.name.as_str()))
             .collect();
+        // Never the monitor bus's own sinks (`sootmix._monitor`, `sootmix._sidetone-*`)
+        exclude.push("sootmix._");
         if let Some(id) = self.state.pw_graph.best_fallback_sink(&exclude) {
             return Some(id);
         }
 
         // Last resort: WirePlumber default
-        crate::audio::routing::get_default_sink_id()
+        self.state.pw_graph.default_sink_id()
     }
 
     /// Get the target output device node ID for a specific channel.
@@ -2818,6 +6754,397 @@ impl DaemonService {
         self.get_master_output_device_id()
     }
 
+    /// Get the node a channel stage's output should be linked to: the next
+    /// running stage (plugins, then EQ), otherwise the output device.
+    fn next_stage_target(&self, channel_id: Uuid, from_node: u32) -> Option<u32> {
+        self.state
+            .channels
+            .iter()
+            .find(|c| c.id == channel_id)
+            .and_then(|c| {
+                c.stage_links()
+                    .into_iter()
+                    .find(|&(from, _)| from == from_node)
+                    .map(|(_, to)| to)
+            })
+            .or_else(|| self.desired_output_node_for_channel(channel_id))
+    }
+
+    /// Whether `from_node` is linked to `to_node` and nothing else.
+    /// Taps into recording capture streams don't count.
+    fn is_linked_exclusively(&self, from_node: u32, to_node: u32) -> bool {
+        let mut links = self
+            .state
+            .pw_graph
+            .links_from_node(from_node)
+            .into_iter()
+            .filter(|l| !self.is_record_node(l.input_node))
+            .peekable();
+        links.peek().is_some() && links.all(|l| l.input_node == to_node)
+    }
+
+    /// Re-link a channel's processing chain after a stage was inserted or
+    /// removed. Links that are already correct are left alone so unaffected
+    /// hops don't glitch.
+    fn route_channel_stages(&mut self, channel_id: Uuid) {
+        let Some((stage_links, output_stage, is_input)) = self
+            .state
+            .channels
+            .iter()
+            .find(|c| c.id == channel_id)
+            .map(|c| (c.stage_links(), c.output_stage_id(), c.is_input()))
+        else {
+            return;
+        };
+
+        for (from, to) in stage_links {
+            if !self.is_linked_exclusively(from, to) {
+                self.suppress_and_route(from, Some(to), Some(channel_id));
+            }
+        }
+
+        if let Some(output_stage) = output_stage {
+            let target_device_id = self.desired_output_node_for_channel(channel_id);
+            let already_routed =
+                target_device_id.is_some_and(|t| self.is_linked_exclusively(output_stage, t));
+            if !already_routed {
+                self.suppress_and_route(output_stage, target_device_id, Some(channel_id));
+            }
+        }
+
+        if is_input {
+            self.relink_input_mic(channel_id);
+        }
+    }
+
+    /// Link an input channel's mic into the node that should receive it: the
+    /// plugin sink while the plugin filter runs, otherwise the capture stream.
+    /// Mic links into the capture stream are dropped while plugins sit in between.
+    fn relink_input_mic(&mut self, channel_id: Uuid) {
+        let Some((target, bypassed_capture, mic_name)) = self
+            .state
+            .channels
+            .iter()
+            .find(|c| c.id == channel_id && c.is_input())
+            .map(|c| {
+                (
+                    c.pw_plugin_sink_id.or(c.pw_loopback_capture_id),
+                    c.pw_plugin_sink_id.and(c.pw_loopback_capture_id),
+                    c.input_device_name.clone(),
+                )
+            })
+        else {
+            return;
+        };
+        let Some(target) = target else {
+            return;
+        };
+
+        let graph = &self.state.pw_graph;
+        let is_mic = |node_id: u32| {
+            graph
+                .nodes
+                .get(&node_id)
+                .is_some_and(|n| n.is_audio_input() && !n.name.starts_with("sootmix."))
+        };
+
+        let stale_links: Vec<u32> = bypassed_capture
+            .map(|capture_id| {
+                graph
+                    .links
+                    .values()
+                    .filter(|l| l.input_node == capture_id && is_mic(l.output_node))
+                    .map(|l| l.id)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Skip if the target's input ports aren't registered yet (PortAdded
+        // retries) or are all already fed by the mic
+        let target_ports = graph
+            .ports
+            .values()
+            .filter(|p| p.node_id == target && p.direction == PortDirection::Input)
+            .count();
+        let linked_ports: HashSet<u32> = graph
+            .links
+            .values()
+            .filter(|l| l.input_node == target && is_mic(l.output_node))
+            .map(|l| l.input_port)
+            .collect();
+        let needs_link = target_ports > 0 && linked_ports.len() < target_ports;
+
+        for link_id in stale_links {
+            self.send_pw_command(PwCommand::DestroyLink { link_id });
+        }
+
+        if needs_link {
+            if let Some(mic_name) = mic_name.filter(|n| n != "system-default") {
+                self.send_pw_command(PwCommand::LinkInputChannelToMic {
+                    capture_node_id: target,
+                    target_mic_name: mic_name,
+                });
+            } else {
+                self.send_pw_command(PwCommand::LinkInputChannelToDefaultMic {
+                    capture_node_id: target,
+                });
+            }
+        }
+    }
+
+    /// Bring a channel's plugin filter in line with its plugin chain, then
+    /// realign channel latencies, which the chain may have changed.
+    fn sync_plugin_filter(&mut self, channel_id: Uuid) {
+        self.update_plugin_filter(channel_id);
+        self.sync_latency();
+    }
+
+    /// Create a channel's plugin filter once its audio path exists, push the
+    /// chain to a running filter, or tear it down when no longer needed.
+    fn update_plugin_filter(&mut self, channel_id: Uuid) {
+        let Some(channel) = self.state.channels.iter().find(|c| c.id == channel_id) else {
+            return;
+        };
+        let name = channel.name.clone();
+        let needs_filter = channel.needs_plugin_filter();
+        let is_running = channel.pw_plugin_sink_id.is_some();
+        let plugin_chain = channel.active_plugin_chain();
+        let upstream_ready = if channel.is_input() {
+            channel.pw_loopback_capture_id.is_some()
+        } else {
+            channel.pw_loopback_output_id.is_some()
+        };
+        let noise_suppression = channel.noise_suppression();
+        let voice_activity = channel
+            .is_input()
+            .then(|| Arc::clone(&channel.voice_activity));
+        let talk_gate = channel.talk_gate();
+        let is_pending = self.state.pending_plugin_creates.contains(&channel_id);
+
+        if !needs_filter {
+            if is_running || is_pending {
+                self.remove_plugin_filter(channel_id);
+            }
+            return;
+        }
+
+        if is_running {
+            self.send_pw_command(PwCommand::UpdatePluginChain {
+                channel_id,
+                plugin_chain,
+            });
+            return;
+        }
+
+        // Without a sink/source the filter is created once it comes up
+        if !upstream_ready {
+            return;
+        }
+
+        if !self.state.pending_plugin_creates.insert(channel_id) {
+            debug!("Plugin filter for channel {} already being created", channel_id);
+            return;
+        }
+
+        self.send_pw_command(PwCommand::CreatePluginFilter {
+            channel_id,
+            name,
+            plugin_instances: self.plugin_manager.shared_instances(),
+            plugin_chain,
+            noise_suppression,
+            voice_activity,
+            talk_gate,
+        });
+    }
+
+    /// Recompute each channel's plugin latency and the delays that line up
+    /// output channels playing to the same device, and apply changes.
+    ///
+    /// Channels without plugins get a plugin filter while they are delayed.
+    /// Surround channels can't take the stereo filter, so they are left out
+    /// of sync and reported instead.
+    fn sync_latency(&mut self) {
+        let previous: HashMap<Uuid, (u32, u32)> = self
+            .state
+            .channels
+            .iter()
+            .map(|c| (c.id, (c.plugin_latency, c.latency_compensation)))
+            .collect();
+
+        for channel in &mut self.state.channels {
+            let denoise_latency = channel
+                .noise_suppression()
+                .map_or(0, |settings| settings.latency());
+            channel.plugin_latency = self
+                .plugin_manager
+                .chain_latency(&channel.active_plugin_chain())
+                + denoise_latency;
+        }
+
+        let outputs: Vec<(Uuid, Option<u32>, u32)> = self
+            .state
+            .channels
+            .iter()
+            .filter(|c| !c.is_input())
+            .map(|c| {
+                (
+                    c.id,
+                    self.desired_output_node_for_channel(c.id),
+                    c.plugin_latency,
+                )
+            })
+            .collect();
+        let plan = crate::audio::latency::plan_compensation(outputs);
+
+        let mut changed = Vec::new();
+        for channel in &mut self.state.channels {
+            let delay = plan.get(&channel.id).copied().unwrap_or(0);
+            let (compensation, uncompensated) = if channel.fits_filters() {
+                (delay, 0)
+            } else {
+                (0, delay)
+            };
+            channel.latency_compensation = compensation;
+            if channel.uncompensated_latency != uncompensated {
+                channel.uncompensated_latency = uncompensated;
+                if uncompensated > 0 {
+                    warn!(
+                        "{} channel '{}' is {} samples ahead of its output's plugin \
+                         channels; surround channels can't be delayed to match",
+                        channel.layout, channel.name, uncompensated
+                    );
+                }
+            }
+            let latency = (channel.plugin_latency, channel.latency_compensation);
+            if previous.get(&channel.id) != Some(&latency) {
+                changed.push((channel.id, latency));
+            }
+        }
+
+        for (channel_id, (plugin_latency, compensation)) in changed {
+            debug!(
+                "Channel {} latency: {} samples from plugins, {} compensation",
+                channel_id, plugin_latency, compensation
+            );
+            // Filters only needed for the delay come and go with it; new
+            // filters get their latency once they're up
+            self.update_plugin_filter(channel_id);
+            let running = self
+                .state
+                .channels
+                .iter()
+                .any(|c| c.id == channel_id && c.pw_plugin_sink_id.is_some());
+            if running {
+                self.send_pw_command(PwCommand::SetPluginLatency {
+                    channel_id,
+                    compensation,
+                    reported: plugin_latency + compensation,
+                });
+            }
+            if let Some(channel) = self.state.channels.iter().find(|c| c.id == channel_id) {
+                self.emit_signal(SignalEvent::ChannelUpdated(channel.to_channel_info()));
+            }
+        }
+    }
+
+    /// Route a channel around its plugin filter, then destroy the filter.
+    fn remove_plugin_filter(&mut self, channel_id: Uuid) {
+        self.state.pending_plugin_creates.remove(&channel_id);
+        if let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == channel_id) {
+            channel.pw_plugin_sink_id = None;
+            channel.pw_plugin_output_id = None;
+        }
+        // Re-route before the destroy, so the link removals caused by it
+        // aren't treated as managed links to restore.
+        self.route_channel_stages(channel_id);
+        self.send_pw_command(PwCommand::DestroyPluginFilter { channel_id });
+    }
+
+    /// Push a channel's noise suppression settings to its running plugin
+    /// filter, then bring up or remove the filter if that changed whether
+    /// the channel needs one.
+    fn apply_noise_suppression(&mut self, channel_id: Uuid) {
+        let Some((settings, is_running)) = self
+            .state
+            .channels
+            .iter()
+            .find(|c| c.id == channel_id)
+            .map(|c| (c.noise_suppression(), c.pw_plugin_sink_id.is_some()))
+        else {
+            return;
+        };
+        if is_running {
+            self.send_pw_command(PwCommand::SetNoiseSuppression {
+                channel_id,
+                settings,
+            });
+        }
+        self.sync_plugin_filter(channel_id);
+    }
+
+    /// Dispatch a CreateEqFilter for a channel using its current bands.
+    fn send_create_eq_filter(&mut self, channel_id: Uuid) {
+        if self.state.pending_eq_creates.contains(&channel_id) {
+            debug!("EQ filter for channel {} already being created", channel_id);
+            return;
+        }
+        let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == channel_id) else {
+            return;
+        };
+        // Only reachable through a config saved before surround was refused
+        if !channel.fits_filters() {
+            warn!(
+                "Not starting the stereo EQ on {} channel '{}'",
+                channel.layout, channel.name
+            );
+            return;
+        }
+
+        self.state.pending_eq_creates.insert(channel_id);
+        channel.eq_filter_bands = channel.eq_bands.clone();
+        let command = PwCommand::CreateEqFilter {
+            channel_id,
+            name: channel.name.clone(),
+            bands: channel.eq_bands.clone(),
+        };
+        self.send_pw_command(command);
+    }
+
+    /// Bring a channel's running EQ filter in line with its bands: update it
+    /// in place if its layout still fits, otherwise replace it. A filter
+    /// that is still starting is checked once it is ready.
+    fn apply_channel_eq(&mut self, channel_id: Uuid) {
+        let Some(channel) = self.state.channels.iter_mut().find(|c| c.id == channel_id) else {
+            return;
+        };
+        let Some(sink_node_id) = channel.pw_eq_sink_id else {
+            return;
+        };
+        if channel.eq_filter_bands == channel.eq_bands {
+            return;
+        }
+
+        if crate::audio::eq_filter::same_layout(&channel.eq_filter_bands, &channel.eq_bands) {
+            channel.eq_filter_bands = channel.eq_bands.clone();
+            let command = PwCommand::UpdateEqFilter {
+                sink_node_id,
+                bands: channel.eq_bands.clone(),
+            };
+            self.send_pw_command(command);
+        } else {
+            info!(
+                "EQ layout of channel '{}' changed, recreating filter",
+                channel.name
+            );
+            // Route around the old filter while the new one starts; creating
+            // it kills the old process.
+            channel.pw_eq_sink_id = None;
+            channel.pw_eq_output_id = None;
+            self.route_channel_stages(channel_id);
+            self.send_create_eq_filter(channel_id);
+        }
+    }
+
     pub fn set_master_recording(&mut self, enabled: bool) -> Result<(), ServiceError> {
         if enabled {
             if !self.state.master_recording_enabled {
@@ -2838,25 +7165,16 @@ impl DaemonService {
     /// Called when a new node appears in PipeWire.
     fn try_auto_route_app(&mut self, node_id: u32, _node_name: &str) {
         // Find the app by node_id
-        let (app_identifier, app_name) = match self.state.apps.iter().find(|a| a.node_id == node_id)
-        {
-            Some(app) => (app.identifier().to_string(), app.name.clone()),
-            None => {
-                debug!("try_auto_route_app: node {} not in apps list", node_id);
-                return;
-            }
+        let Some(app) = self.state.apps.iter().find(|a| a.node_id == node_id) else {
+            debug!("try_auto_route_app: node {} not in apps list", node_id);
+            return;
         };
+        let app_identifier = app.identifier();
 
-        // Find a channel that has this app in its assigned_apps
-        // Check both identifier (binary name) and display name for compatibility
+        // Find the channel this app is assigned to, or that a rule sends it to
         let channel_match = self
             .state
-            .channels
-            .iter()
-            .find(|c| {
-                c.assigned_apps.contains(&app_identifier)
-                    || c.assigned_apps.iter().any(|a| a == &app_name)
-            })
+            .channel_for_app(app)
             .map(|c| (c.id, c.pw_sink_id, c.name.clone()));
 
         let (channel_id, sink_id, channel_name) = match channel_match {
@@ -2882,12 +7200,14 @@ impl DaemonService {
             "Auto-routing app '{}' (node {}) to channel {}",
             app_identifier, node_id, channel_id
         );
+        self.state.auto_routed_apps.insert(node_id);
 
         // Set the stream's target to our sink - this tells WirePlumber to stop
         // auto-managing this stream and prevents it from recreating links to default sink
-        if let Err(e) = crate::audio::routing::set_stream_target(node_id, sink_id) {
-            warn!("Failed to set stream target for auto-route: {}", e);
-        }
+        self.send_pw_command(PwCommand::SetStreamTarget {
+            stream_node_id: node_id,
+            target_node_id: Some(sink_id),
+        });
 
         let our_sinks: Vec<u32> = self
             .state
@@ -2920,35 +7240,28 @@ impl DaemonService {
         }
     }
 
-    /// Try to auto-route all apps that match a channel's assigned_apps.
+    /// Try to auto-route all apps assigned (or routed by a rule) to a channel.
     /// Called when a virtual sink is created (sink is now ready to receive apps).
     fn try_auto_route_pending_apps(&mut self, channel_id: Uuid) {
         // Get channel info
-        let (assigned_apps, sink_id) = match self
+        let Some(sink_id) = self
             .state
             .channels
             .iter()
             .find(|c| c.id == channel_id)
-            .map(|c| (c.assigned_apps.clone(), c.pw_sink_id))
-        {
-            Some((apps, Some(sink_id))) => (apps, sink_id),
-            _ => return,
-        };
-
-        if assigned_apps.is_empty() {
+            .and_then(|c| c.pw_sink_id)
+        else {
             return;
-        }
+        };
 
-        // Find apps that match the assigned list
-        // Check both identifier (binary name) and display name for compatibility
         let apps_to_route: Vec<(u32, String)> = self
             .state
             .apps
             .iter()
             .filter(|app| {
-                let id = app.identifier().to_string();
-                let name = &app.name;
-                assigned_apps.contains(&id) || assigned_apps.iter().any(|a| a == name)
+                self.state
+                    .channel_for_app(app)
+                    .is_some_and(|c| c.id == channel_id)
             })
             .map(|app| (app.node_id, app.name.clone()))
             .collect();
@@ -2973,11 +7286,13 @@ impl DaemonService {
             }
 
             info!("Auto-routing app '{}' to channel", app_identifier);
+            self.state.auto_routed_apps.insert(app_node_id);
 
             // Set the stream's target to our sink - prevents WirePlumber from recreating links
-            if let Err(e) = crate::audio::routing::set_stream_target(app_node_id, sink_id) {
-                warn!("Failed to set stream target: {}", e);
-            }
+            self.send_pw_command(PwCommand::SetStreamTarget {
+                stream_node_id: app_node_id,
+                target_node_id: Some(sink_id),
+            });
 
             // Destroy links to non-sootmix sinks FIRST
             let links_to_destroy: Vec<u32> = self
@@ -3017,7 +7332,7 @@ impl DaemonService {
             .channels
             .iter()
             .filter_map(|c| {
-                let loopback_id = c.pw_loopback_output_id?;
+                let loopback_id = c.output_stage_id()?;
                 if self.state.pending_route_loopbacks.contains(&loopback_id) {
                     return None;
                 }
@@ -3094,7 +7409,7 @@ impl DaemonService {
         let mut loopbacks_to_route: Vec<(Uuid, u32, String, Option<u32>)> = Vec::new();
 
         for c in &self.state.channels {
-            let loopback_id = match c.pw_loopback_output_id {
+            let loopback_id = match c.output_stage_id() {
                 Some(id) => id,
                 None => {
                     debug!("Channel '{}': no loopback output ID, skipping", c.name);
@@ -3172,7 +7487,7 @@ impl DaemonService {
                 } else {
                     // Check if existing links still point to the current system default.
                     // If the default changed (e.g. bluetooth connected), re-route.
-                    let current_default = crate::audio::routing::get_default_sink_id();
+                    let current_default = self.state.pw_graph.default_sink_id();
                     if let Some(default_id) = current_default {
                         let linked_to_default = self
                             .state
@@ -3213,7 +7528,7 @@ impl DaemonService {
     /// is plugged in and gets promoted to default. Without this pass, such
     /// channels stay linked to the previous default until the daemon restarts.
     fn reroute_system_default_channels(&mut self) {
-        let new_default = match crate::audio::routing::get_default_sink_id() {
+        let new_default = match self.state.pw_graph.default_sink_id() {
             Some(id) => id,
             None => {
                 debug!("DefaultSinkChanged fired but no default sink resolvable yet");
@@ -3232,7 +7547,7 @@ impl DaemonService {
             .channels
             .iter()
             .filter_map(|c| {
-                let loopback_id = c.pw_loopback_output_id?;
+                let loopback_id = c.output_stage_id()?;
                 if self.state.pending_route_loopbacks.contains(&loopback_id) {
                     return None;
                 }
@@ -3280,3 +7595,6 @@ impl DaemonService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,490 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Routing scenarios run against the in-memory graph.
+
+use super::*;
+use crate::audio::fake_graph::FakeGraph;
+
+const SPEAKERS: &str = "alsa_output.pci-0000_00_1f.3.analog-stereo";
+const HEADSET: &str = "bluez_output.00_11_22_33_44_55.1";
+
+/// One "Music" output channel that Firefox is assigned to.
+const MUSIC_CHANNEL: &str = r#"
+[[channels]]
+id = "6f1c2d3e-4b5a-4c6d-8e7f-8091a2b3c4d5"
+name = "Music"
+assigned_apps = ["firefox"]
+"#;
+
+/// One "Mic" input channel on the default mic.
+const MIC_CHANNEL: &str = r#"
+[[channels]]
+id = "0a1b2c3d-4e5f-4a6b-8c7d-9e0f1a2b3c4d"
+name = "Mic"
+kind = "Input"
+"#;
+
+/// One "Voice" output channel with nothing assigned to it.
+const VOICE_CHANNEL: &str = r#"
+[[channels]]
+id = "3c4d5e6f-7a8b-4c9d-8e0f-1a2b3c4d5e6f"
+name = "Voice"
+"#;
+
+/// A rule sending calls to Voice on `media.role` alone.
+const CALLS_RULE: &str = r#"
+[[rules]]
+id = "9d8c7b6a-5f4e-4d3c-8b2a-1f0e9d8c7b6a"
+name = "Calls"
+enabled = true
+target_channel = "Voice"
+
+[rules.match_type]
+type = "Contains"
+pattern = ""
+
+[[rules.conditions]]
+property = "media.role"
+
+[rules.conditions.match_type]
+type = "Exact"
+pattern = "Communication"
+"#;
+
+/// A service started against a fake graph, as the daemon starts up.
+struct Scenario {
+    graph: FakeGraph,
+    service: DaemonService,
+    _config_dir: tempfile::TempDir,
+}
+
+impl Scenario {
+    fn start(graph: FakeGraph, mixer_toml: &str) -> Self {
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut service = DaemonService::with_backend(
+            MixerConfig::from_toml(mixer_toml).unwrap(),
+            RoutingRulesConfig::default(),
+            ConfigManager::with_dir(config_dir.path().to_path_buf()),
+            // Never scanned, so no plugins are picked up from the system
+            PluginManager::new(),
+            graph.backend_factory(),
+        );
+        service.start_pipewire().unwrap();
+        service.wait_for_discovery();
+        service.cleanup_orphaned_nodes();
+        service.restore_channels().unwrap();
+
+        let mut scenario = Self {
+            graph,
+            service,
+            _config_dir: config_dir,
+        };
+        scenario.settle();
+        scenario
+    }
+
+    /// Handle events until the service stops changing the graph.
+    fn settle(&mut self) {
+        for _ in 0..100 {
+            let sent = self.graph.events_sent();
+            self.service.process_pw_events();
+            if self.graph.events_sent() == sent {
+                return;
+            }
+        }
+        panic!("graph never settled");
+    }
+
+    fn node(&self, name: &str) -> u32 {
+        self.graph
+            .node_id(name)
+            .unwrap_or_else(|| panic!("no node named {}", name))
+    }
+
+    /// The Music channel's sink and loopback output.
+    fn music(&self) -> (u32, u32) {
+        (
+            self.node("sootmix.Music"),
+            self.node("output.sootmix.Music.output"),
+        )
+    }
+}
+
+/// Speakers as the only (and default) device.
+fn speakers_only() -> (FakeGraph, u32) {
+    let graph = FakeGraph::new();
+    let speakers = graph.add_sink(SPEAKERS, "Built-in Audio Analog Stereo");
+    graph.set_default_sink(SPEAKERS);
+    (graph, speakers)
+}
+
+#[test]
+fn test_channel_follows_headset_hotplug() {
+    let (graph, speakers) = speakers_only();
+    let mut scenario = Scenario::start(graph, MUSIC_CHANNEL);
+    let (sink, loopback) = scenario.music();
+    assert_eq!(scenario.graph.linked_to(loopback), vec![speakers]);
+    let channel = &scenario.service.state.channels[0];
+    assert_eq!(channel.pw_sink_id, Some(sink));
+    assert_eq!(channel.pw_loopback_output_id, Some(loopback));
+
+    // WirePlumber promotes the headset to default when it connects
+    let headset = scenario.graph.add_sink(HEADSET, "WH-1000XM4");
+    scenario.graph.set_default_sink(HEADSET);
+    scenario.settle();
+    assert_eq!(scenario.graph.linked_to(loopback), vec![headset]);
+    assert_eq!(scenario.graph.link_count(loopback, headset), 2);
+
+    // ...and falls back to the speakers when it disconnects
+    scenario.graph.remove_node(headset);
+    scenario.graph.set_default_sink(SPEAKERS);
+    scenario.settle();
+    assert_eq!(scenario.graph.linked_to(loopback), vec![speakers]);
+    assert_eq!(scenario.graph.link_count(loopback, speakers), 2);
+}
+
+#[test]
+fn test_channel_with_output_device_ignores_default() {
+    let (graph, speakers) = speakers_only();
+    let mixer_toml = format!("{}output_device_name = \"{}\"\n", MUSIC_CHANNEL, SPEAKERS);
+    let mut scenario = Scenario::start(graph, &mixer_toml);
+    let (_, loopback) = scenario.music();
+    assert_eq!(scenario.graph.linked_to(loopback), vec![speakers]);
+
+    scenario.graph.add_sink(HEADSET, "WH-1000XM4");
+    scenario.graph.set_default_sink(HEADSET);
+    scenario.settle();
+    assert_eq!(scenario.graph.linked_to(loopback), vec![speakers]);
+}
+
+#[test]
+fn test_assigned_app_is_routed_after_restart() {
+    let (graph, speakers) = speakers_only();
+    let mut scenario = Scenario::start(graph, MUSIC_CHANNEL);
+    let (sink, _) = scenario.music();
+
+    for _ in 0..2 {
+        // WirePlumber links every new stream to the default sink
+        let app = scenario.graph.add_app("Firefox", "firefox");
+        scenario.graph.link_nodes(app, speakers);
+        scenario.settle();
+        assert_eq!(scenario.graph.linked_to(app), vec![sink]);
+        assert_eq!(scenario.graph.link_count(app, sink), 2);
+        assert_eq!(scenario.graph.stream_target(app), Some(sink));
+
+        scenario.graph.remove_node(app);
+        scenario.settle();
+    }
+}
+
+#[test]
+fn test_rule_routes_on_node_properties() {
+    let (graph, speakers) = speakers_only();
+    let mut scenario = Scenario::start(graph, VOICE_CHANNEL);
+    scenario.service.state.routing_rules = RoutingRulesConfig::from_toml(CALLS_RULE).unwrap();
+    let sink = scenario.node("sootmix.Voice");
+
+    let app = scenario.graph.add_app("Chromium", "chromium");
+    scenario.graph.link_nodes(app, speakers);
+    scenario.settle();
+    assert_eq!(scenario.graph.linked_to(app), vec![speakers]);
+
+    // The role only shows up once the stream is bound
+    scenario
+        .graph
+        .set_node_property(app, "media.role", "Communication");
+    scenario.settle();
+    assert_eq!(scenario.graph.linked_to(app), vec![sink]);
+    assert_eq!(scenario.graph.stream_target(app), Some(sink));
+}
+
+#[test]
+fn test_rogue_link_is_replaced() {
+    let (graph, speakers) = speakers_only();
+    let mut scenario = Scenario::start(graph, MUSIC_CHANNEL);
+    let (sink, _) = scenario.music();
+    let app = scenario.graph.add_app("Firefox", "firefox");
+    scenario.graph.link_nodes(app, speakers);
+    scenario.settle();
+    assert_eq!(scenario.graph.linked_to(app), vec![sink]);
+
+    // WirePlumber relinks the stream behind our back
+    assert_eq!(scenario.graph.link_nodes(app, speakers).len(), 2);
+    scenario.settle();
+    assert_eq!(scenario.graph.linked_to(app), vec![sink]);
+    assert_eq!(scenario.graph.link_count(app, sink), 2);
+
+    // Unassigned apps are left alone
+    let other = scenario.graph.add_app("mpv", "mpv");
+    scenario.graph.link_nodes(other, speakers);
+    scenario.settle();
+    assert_eq!(scenario.graph.linked_to(other), vec![speakers]);
+}
+
+#[test]
+fn test_killed_sink_is_recreated() {
+    let (graph, speakers) = speakers_only();
+    let mut scenario = Scenario::start(graph, MUSIC_CHANNEL);
+    let (sink, loopback) = scenario.music();
+    let app = scenario.graph.add_app("Firefox", "firefox");
+    scenario.graph.link_nodes(app, speakers);
+    scenario.settle();
+
+    // pw-loopback is killed: both of its nodes go away
+    scenario.graph.remove_node(loopback);
+    scenario.graph.remove_node(sink);
+    scenario.settle();
+
+    let creates = scenario
+        .graph
+        .commands()
+        .iter()
+        .filter(|cmd| matches!(cmd, PwCommand::CreateVirtualSink { name, .. } if name == "Music"))
+        .count();
+    assert_eq!(creates, 2);
+    let (sink, loopback) = scenario.music();
+    assert_eq!(scenario.service.state.channels[0].pw_sink_id, Some(sink));
+    assert_eq!(scenario.graph.linked_to(loopback), vec![speakers]);
+    assert_eq!(scenario.graph.linked_to(app), vec![sink]);
+}
+
+#[test]
+fn test_reconnect_restores_channels() {
+    let (graph, speakers) = speakers_only();
+    let mut scenario = Scenario::start(graph, MUSIC_CHANNEL);
+    let (old_sink, _) = scenario.music();
+
+    scenario.graph.disconnect();
+    scenario.settle();
+
+    assert!(scenario.service.state.pw_connected);
+    let (sink, loopback) = scenario.music();
+    assert_ne!(sink, old_sink);
+    assert_eq!(scenario.service.state.channels[0].pw_sink_id, Some(sink));
+    assert_eq!(scenario.graph.linked_to(loopback), vec![speakers]);
+}
+
+#[test]
+fn test_noise_suppression_keeps_source_node() {
+    let (graph, _) = speakers_only();
+    let mut scenario = Scenario::start(graph, MIC_CHANNEL);
+    let source = scenario.node("sootmix.Mic");
+    let id = scenario.service.state.channels[0].id.to_string();
+
+    let service = &mut scenario.service;
+    service.set_channel_noise_suppression(&id, true).unwrap();
+    service.set_channel_vad_threshold(&id, 80.0).unwrap();
+    service.set_channel_noise_suppression(&id, false).unwrap();
+    scenario.settle();
+
+    // Suppression comes and goes with the plugin filter in front of the source
+    assert_eq!(scenario.node("sootmix.Mic"), source);
+    let commands = scenario.graph.commands();
+    assert!(commands.iter().any(|cmd| matches!(
+        cmd,
+        PwCommand::CreatePluginFilter { noise_suppression: Some(ns), .. } if ns.vad_threshold == 95.0
+    )));
+    assert!(commands
+        .iter()
+        .any(|cmd| matches!(cmd, PwCommand::DestroyPluginFilter { .. })));
+    let source_commands = commands
+        .iter()
+        .filter(|cmd| {
+            matches!(
+                cmd,
+                PwCommand::CreateVirtualSource { .. } | PwCommand::DestroyVirtualSink { .. }
+            )
+        })
+        .count();
+    assert_eq!(source_commands, 1);
+}
+
+#[test]
+fn test_retroactive_grace_adds_latency() {
+    let (graph, _) = speakers_only();
+    let mut scenario = Scenario::start(graph, MIC_CHANNEL);
+    let id = scenario.service.state.channels[0].id.to_string();
+
+    scenario
+        .service
+        .set_channel_noise_suppression(&id, true)
+        .unwrap();
+    scenario.settle();
+    let latency = scenario.service.state.channels[0].plugin_latency;
+
+    let mut settings = NoiseSuppressionInfo {
+        retro_grace_ms: 100.0,
+        ..Default::default()
+    };
+    scenario
+        .service
+        .set_channel_noise_suppression_settings(&id, &settings)
+        .unwrap();
+    scenario.settle();
+
+    // Ten more 10 ms frames are held back, applied to the running filter
+    let channel = &scenario.service.state.channels[0];
+    assert_eq!(channel.plugin_latency, latency + 4800);
+    assert!(scenario.graph.commands().iter().any(|cmd| matches!(
+        cmd,
+        PwCommand::SetNoiseSuppression { settings: Some(ns), .. } if ns.retro_grace_ms == 100.0
+    )));
+
+    settings.retro_grace_ms = 1000.0;
+    assert!(scenario
+        .service
+        .set_channel_noise_suppression_settings(&id, &settings)
+        .is_err());
+}
+
+#[test]
+fn test_push_to_talk_keys_the_mic() {
+    let (graph, _) = speakers_only();
+    let mut scenario = Scenario::start(graph, MIC_CHANNEL);
+    let id = scenario.service.state.channels[0].id.to_string();
+    let (tx, mut signals) = tokio_mpsc::unbounded_channel();
+    scenario.service.set_signal_sender(tx);
+    let mut live_changes = move || {
+        std::iter::from_fn(|| signals.try_recv().ok())
+            .filter_map(|event| match event {
+                SignalEvent::ChannelLiveChanged { live, .. } => Some(live),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let last_gate = |scenario: &Scenario| {
+        scenario
+            .graph
+            .commands()
+            .iter()
+            .rev()
+            .find_map(|cmd| match cmd {
+                PwCommand::SetTalkGate { settings, .. } => Some(*settings),
+                _ => None,
+            })
+            .flatten()
+    };
+
+    let settings = TalkInfo {
+        mode: TalkMode::PushToTalk,
+        release_ms: 100.0,
+        ..Default::default()
+    };
+    scenario
+        .service
+        .set_channel_talk_settings(&id, &settings)
+        .unwrap();
+    scenario.settle();
+
+    // The gate starts closed, in a plugin filter created just for it
+    assert!(scenario.graph.commands().iter().any(|cmd| matches!(
+        cmd,
+        PwCommand::CreatePluginFilter { talk_gate: Some(gate), .. } if !gate.open
+    )));
+    assert!(!scenario.service.state.channels[0].live);
+    assert_eq!(live_changes(), vec![false]);
+
+    scenario.service.set_talk_key("", true).unwrap();
+    assert!(last_gate(&scenario).is_some_and(|gate| gate.open));
+    assert_eq!(live_changes(), vec![true]);
+
+    // Letting go keeps the mic open for the release tail
+    scenario.service.set_talk_key("", false).unwrap();
+    scenario.service.update_talk(0.05);
+    assert!(scenario.service.state.channels[0].live);
+    scenario.service.update_talk(0.06);
+    assert!(last_gate(&scenario).is_some_and(|gate| !gate.open));
+    assert_eq!(live_changes(), vec![false]);
+}
+
+#[test]
+fn test_eq_preset_chosen_while_filter_starts_is_applied() {
+    let (graph, _) = speakers_only();
+    let mut scenario = Scenario::start(graph, MUSIC_CHANNEL);
+    let id = scenario.service.state.channels[0].id.to_string();
+
+    // The filter is created with the flat preset, then a preset is chosen
+    // before it is up
+    let service = &mut scenario.service;
+    service.set_channel_eq_enabled(&id, true).unwrap();
+    service.set_channel_eq_preset(&id, "Bass Boost").unwrap();
+    scenario.settle();
+
+    let channel = &scenario.service.state.channels[0];
+    assert!(channel.pw_eq_sink_id.is_some());
+    let bass_boost = EqPreset::builtin_presets()
+        .into_iter()
+        .find(|p| p.name == "Bass Boost")
+        .unwrap();
+    assert!(scenario.graph.commands().iter().any(|cmd| matches!(
+        cmd,
+        PwCommand::UpdateEqFilter { bands, .. } if *bands == bass_boost.bands
+    )));
+}
+
+#[test]
+fn test_width_needs_surround_layout() {
+    let (graph, _) = speakers_only();
+    // A width left over from when the channel was 5.1
+    let mixer_toml = format!("{}width = 0.5\n", MUSIC_CHANNEL);
+    let mut scenario = Scenario::start(graph, &mixer_toml);
+    let id = scenario.service.state.channels[0].id.to_string();
+    assert_eq!(scenario.service.state.channels[0].width, 1.0);
+
+    let service = &mut scenario.service;
+    assert!(matches!(
+        service.set_channel_balance(&id, 0.0, 0.5),
+        Err(ServiceError::InvalidArgument(_))
+    ));
+    service.set_channel_balance(&id, -0.5, 1.0).unwrap();
+
+    service
+        .set_channel_layout(&id, ChannelLayout::Surround51)
+        .unwrap();
+    scenario.settle();
+    scenario
+        .service
+        .set_channel_balance(&id, -0.5, 0.5)
+        .unwrap();
+    assert_eq!(scenario.service.state.channels[0].width, 0.5);
+
+    scenario
+        .service
+        .set_channel_layout(&id, ChannelLayout::Stereo)
+        .unwrap();
+    let channel = &scenario.service.state.channels[0];
+    assert_eq!((channel.pan, channel.width), (-0.5, 1.0));
+}
+
+#[test]
+fn test_surround_channels_refuse_stereo_filters() {
+    let (graph, _) = speakers_only();
+    let mut scenario = Scenario::start(graph, MUSIC_CHANNEL);
+    let id = scenario.service.state.channels[0].id.to_string();
+
+    let service = &mut scenario.service;
+    service.set_channel_eq_enabled(&id, true).unwrap();
+    assert!(matches!(
+        service.set_channel_layout(&id, ChannelLayout::Surround71),
+        Err(ServiceError::InvalidArgument(_))
+    ));
+    service.set_channel_eq_enabled(&id, false).unwrap();
+    service
+        .set_channel_layout(&id, ChannelLayout::Surround71)
+        .unwrap();
+    scenario.settle();
+
+    let service = &mut scenario.service;
+    assert!(matches!(
+        service.set_channel_eq_enabled(&id, true),
+        Err(ServiceError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        service.add_channel_plugin(&id, "builtin:gain"),
+        Err(ServiceError::InvalidArgument(_))
+    ));
+    let channel = &service.state.channels[0];
+    assert!(!channel.eq_enabled && channel.plugin_chain.is_empty());
+}
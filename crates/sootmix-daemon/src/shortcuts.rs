@@ -0,0 +1,213 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Global push-to-talk key through the XDG GlobalShortcuts portal.
+//!
+//! Once an input channel has a talk mode, the daemon registers a single
+//! "push-to-talk" shortcut with the portal; the desktop lets the user pick
+//! its key. Holding it holds the talk key of every input channel with a talk
+//! mode. The portal only reports the key to the session that registered it,
+//! so no input devices are read directly.
+//!
+//! Desktops without the portal can bind `sootmixctl talk-key press` and
+//! `sootmixctl talk-key release` to a key's press and release instead.
+
+use crate::service::DaemonService;
+use futures::StreamExt;
+use sootmix_ipc::TalkMode;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, Value};
+
+/// ID of the shortcut, as the portal and desktop settings know it.
+const SHORTCUT_ID: &str = "push-to-talk";
+
+/// How often to look for a channel with a talk mode before registering.
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+#[zbus::proxy(
+    interface = "org.freedesktop.portal.GlobalShortcuts",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+trait GlobalShortcuts {
+    fn create_session(&self, options: HashMap<&str, Value<'_>>) -> zbus::Result<OwnedObjectPath>;
+
+    fn bind_shortcuts(
+        &self,
+        session_handle: &ObjectPath<'_>,
+        shortcuts: &[(&str, HashMap<&str, Value<'_>>)],
+        parent_window: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+
+    #[zbus(signal)]
+    fn activated(
+        &self,
+        session_handle: ObjectPath<'_>,
+        shortcut_id: &str,
+        timestamp: u64,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn deactivated(
+        &self,
+        session_handle: ObjectPath<'_>,
+        shortcut_id: &str,
+        timestamp: u64,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<()>;
+}
+
+#[zbus::proxy(
+    interface = "org.freedesktop.portal.Request",
+    default_service = "org.freedesktop.portal.Desktop"
+)]
+trait Request {
+    #[zbus(signal)]
+    fn response(&self, response: u32, results: HashMap<&str, Value<'_>>) -> zbus::Result<()>;
+}
+
+/// Register the shortcut once a channel needs it and feed its presses to
+/// the service until shutdown.
+pub async fn run(
+    connection: zbus::Connection,
+    service: Arc<Mutex<DaemonService>>,
+    shutdown_flag: Arc<AtomicBool>,
+) {
+    loop {
+        if shutdown_flag.load(Ordering::Relaxed) {
+            return;
+        }
+        let wanted = service.lock().is_ok_and(|svc| {
+            svc.state
+                .channels
+                .iter()
+                .any(|c| c.is_input() && c.talk.mode != TalkMode::Off)
+        });
+        if wanted {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    if let Err(e) = listen(&connection, &service).await {
+        warn!("Global push-to-talk key unavailable: {}", e);
+    }
+}
+
+async fn listen(
+    connection: &zbus::Connection,
+    service: &Arc<Mutex<DaemonService>>,
+) -> zbus::Result<()> {
+    let portal = GlobalShortcutsProxy::new(connection).await?;
+
+    let mut response = receive_response(connection, "sootmix_session").await?;
+    portal
+        .create_session(HashMap::from([
+            ("handle_token", Value::from("sootmix_session")),
+            ("session_handle_token", Value::from("sootmix")),
+        ]))
+        .await?;
+    let results = wait_for_response(&mut response).await?;
+    let session = match results.get("session_handle") {
+        Some(Value::Str(handle)) => ObjectPath::try_from(handle.to_string())?,
+        Some(Value::ObjectPath(handle)) => handle.to_owned(),
+        _ => {
+            return Err(zbus::Error::Failure(
+                "portal returned no session handle".to_string(),
+            ))
+        }
+    };
+
+    // Subscribe before binding, so a key held during the dialog isn't lost
+    let mut activated = portal.receive_activated().await?;
+    let mut deactivated = portal.receive_deactivated().await?;
+
+    let mut response = receive_response(connection, "sootmix_bind").await?;
+    let shortcut = HashMap::from([("description", Value::from("Push to talk / push to mute"))]);
+    portal
+        .bind_shortcuts(
+            &session,
+            &[(SHORTCUT_ID, shortcut)],
+            "",
+            HashMap::from([("handle_token", Value::from("sootmix_bind"))]),
+        )
+        .await?;
+    wait_for_response(&mut response).await?;
+    info!("Global push-to-talk key registered");
+
+    loop {
+        let pressed = tokio::select! {
+            Some(signal) = activated.next() => {
+                if signal.args()?.shortcut_id != SHORTCUT_ID {
+                    continue;
+                }
+                true
+            }
+            Some(signal) = deactivated.next() => {
+                if signal.args()?.shortcut_id != SHORTCUT_ID {
+                    continue;
+                }
+                false
+            }
+            else => return Ok(()),
+        };
+        let Ok(mut svc) = service.lock() else {
+            continue;
+        };
+        if let Err(e) = svc.set_talk_key("", pressed) {
+            warn!("Push-to-talk key failed: {}", e);
+        }
+    }
+}
+
+/// Subscribe to the response of the request a portal call with
+/// `handle_token` creates. Subscribing first means a quick response can't be
+/// missed.
+async fn receive_response(
+    connection: &zbus::Connection,
+    handle_token: &str,
+) -> zbus::Result<ResponseStream<'static>> {
+    let sender = connection
+        .unique_name()
+        .ok_or_else(|| zbus::Error::Failure("connection has no unique name".to_string()))?
+        .trim_start_matches(':')
+        .replace('.', "_");
+    let path = format!(
+        "/org/freedesktop/portal/desktop/request/{}/{}",
+        sender, handle_token
+    );
+    let request = RequestProxy::builder(connection)
+        .path(path)?
+        .build()
+        .await?;
+    request.receive_response().await
+}
+
+/// Results of a portal request, or an error if it was cancelled or failed.
+async fn wait_for_response(
+    response: &mut ResponseStream<'static>,
+) -> zbus::Result<HashMap<String, Value<'static>>> {
+    let signal = response
+        .next()
+        .await
+        .ok_or_else(|| zbus::Error::Failure("portal request vanished".to_string()))?;
+    let args = signal.args()?;
+    if args.response != 0 {
+        return Err(zbus::Error::Failure(format!(
+            "portal request ended with response {}",
+            args.response
+        )));
+    }
+    Ok(args
+        .results
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.try_to_owned().map(Value::from)))
+        .filter_map(|(key, value)| Some((key, value.ok()?)))
+        .collect())
+}
@@ -0,0 +1,161 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Per-channel pan/balance and width, applied as per-speaker volumes.
+//!
+//! Channels are plain `pw-loopback` nodes, so the only per-speaker control
+//! PipeWire gives us is `SPA_PROP_channelVolumes`: one gain per position in
+//! the node's channel map. Pan is a balance law over those gains (the far
+//! side fades out, the near side stays at unity) and works for any layout.
+//! Width fades the side and rear speakers, narrowing a surround image to the
+//! front stage. Gains can't mix left into right, so there is no width for
+//! layouts without surround speakers (see [`ChannelLayout::has_surround`]).
+//!
+//! [`ChannelLayout::has_surround`]: crate::ChannelLayout::has_surround
+
+use crate::AudioChannel;
+use std::ops::RangeInclusive;
+
+/// Pan range: -1.0 is hard left, 1.0 hard right.
+pub const PAN_RANGE: RangeInclusive<f32> = -1.0..=1.0;
+
+/// Width range: 0.0 is front speakers only, 1.0 every speaker at full level.
+pub const WIDTH_RANGE: RangeInclusive<f32> = 0.0..=1.0;
+
+/// A channel's stereo placement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Balance {
+    pub pan: f32,
+    pub width: f32,
+}
+
+impl Default for Balance {
+    fn default() -> Self {
+        Self {
+            pan: 0.0,
+            width: 1.0,
+        }
+    }
+}
+
+impl Balance {
+    pub fn new(pan: f32, width: f32) -> Self {
+        Self {
+            pan: pan.clamp(*PAN_RANGE.start(), *PAN_RANGE.end()),
+            width: width.clamp(*WIDTH_RANGE.start(), *WIDTH_RANGE.end()),
+        }
+    }
+
+    /// Whether every speaker gets the same volume.
+    pub fn is_centered(&self) -> bool {
+        self.pan == 0.0 && self.width == 1.0
+    }
+
+    /// Gain of one speaker, before the fader volume.
+    fn speaker_gain(&self, position: &AudioChannel) -> f32 {
+        let side = lateral_position(position);
+        let mut gain = 1.0;
+        if side * self.pan < 0.0 {
+            gain *= 1.0 - self.pan.abs() * side.abs();
+        }
+        if is_surround(position) {
+            gain *= self.width;
+        }
+        gain
+    }
+
+    /// Volume of each speaker in channel-map order, for a node whose fader
+    /// is at `volume`. A stereo node without known positions (numbered
+    /// ports) is taken as front left/right; no positions at all gives the
+    /// plain stereo pair.
+    pub fn speaker_volumes(&self, volume: f32, positions: &[AudioChannel]) -> Vec<f32> {
+        const STEREO: [AudioChannel; 2] = [AudioChannel::FrontLeft, AudioChannel::FrontRight];
+        let positions = if positions.is_empty()
+            || (positions.len() == 2 && positions.iter().all(|p| *p == AudioChannel::Unknown))
+        {
+            &STEREO[..]
+        } else {
+            positions
+        };
+        positions
+            .iter()
+            .map(|p| volume * self.speaker_gain(p))
+            .collect()
+    }
+}
+
+/// Left/right position of a speaker: -1.0 left, 0.0 centre, 1.0 right.
+fn lateral_position(position: &AudioChannel) -> f32 {
+    match position {
+        AudioChannel::FrontLeft | AudioChannel::SideLeft | AudioChannel::RearLeft => -1.0,
+        AudioChannel::FrontRight | AudioChannel::SideRight | AudioChannel::RearRight => 1.0,
+        AudioChannel::FrontCenter
+        | AudioChannel::LowFrequency
+        | AudioChannel::Mono
+        | AudioChannel::Unknown => 0.0,
+    }
+}
+
+/// Speakers beside or behind the listener.
+fn is_surround(position: &AudioChannel) -> bool {
+    matches!(
+        position,
+        AudioChannel::SideLeft
+            | AudioChannel::SideRight
+            | AudioChannel::RearLeft
+            | AudioChannel::RearRight
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SURROUND_51: [AudioChannel; 6] = [
+        AudioChannel::FrontLeft,
+        AudioChannel::FrontRight,
+        AudioChannel::FrontCenter,
+        AudioChannel::LowFrequency,
+        AudioChannel::RearLeft,
+        AudioChannel::RearRight,
+    ];
+
+    #[test]
+    fn test_centered_is_uniform() {
+        let balance = Balance::default();
+        assert!(balance.is_centered());
+        assert_eq!(balance.speaker_volumes(0.5, &[]), [0.5, 0.5]);
+        assert_eq!(balance.speaker_volumes(0.5, &SURROUND_51), [0.5; 6]);
+    }
+
+    #[test]
+    fn test_pan_fades_far_side() {
+        let balance = Balance::new(0.5, 1.0);
+        let stereo = [AudioChannel::FrontLeft, AudioChannel::FrontRight];
+        assert_eq!(balance.speaker_volumes(1.0, &stereo), [0.5, 1.0]);
+        // Numbered ports are taken as a stereo pair
+        let unknown = [AudioChannel::Unknown, AudioChannel::Unknown];
+        assert_eq!(balance.speaker_volumes(1.0, &unknown), [0.5, 1.0]);
+
+        let hard_left = Balance::new(-3.0, 1.0);
+        assert_eq!(hard_left.pan, -1.0);
+        // Centre and LFE are unaffected by pan
+        assert_eq!(
+            hard_left.speaker_volumes(1.0, &SURROUND_51),
+            [1.0, 0.0, 1.0, 1.0, 1.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_width_fades_surround_speakers() {
+        let balance = Balance::new(0.0, 0.25);
+        assert_eq!(
+            balance.speaker_volumes(1.0, &SURROUND_51),
+            [1.0, 1.0, 1.0, 1.0, 0.25, 0.25]
+        );
+        // Nothing to narrow on a stereo device
+        let stereo = [AudioChannel::FrontLeft, AudioChannel::FrontRight];
+        assert_eq!(balance.speaker_volumes(1.0, &stereo), [1.0, 1.0]);
+    }
+}
@@ -7,6 +7,12 @@
 //! This crate defines the communication protocol between the SootMix daemon
 //! and UI client via D-Bus.
 
+mod balance;
+mod proxy;
+
+pub use balance::{Balance, PAN_RANGE, WIDTH_RANGE};
+pub use proxy::DaemonProxy;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use zbus::zvariant::Type;
@@ -28,6 +34,250 @@ pub enum ChannelKind {
     Input,
 }
 
+/// Speaker layout of an output channel's virtual sink. Sent over D-Bus as
+/// its name ("mono", "stereo", "5.1" or "7.1").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+pub enum ChannelLayout {
+    #[serde(rename = "mono")]
+    Mono,
+    #[default]
+    #[serde(rename = "stereo")]
+    Stereo,
+    #[serde(rename = "5.1")]
+    Surround51,
+    #[serde(rename = "7.1")]
+    Surround71,
+}
+
+impl ChannelLayout {
+    pub const ALL: [ChannelLayout; 4] = [
+        ChannelLayout::Mono,
+        ChannelLayout::Stereo,
+        ChannelLayout::Surround51,
+        ChannelLayout::Surround71,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChannelLayout::Mono => "mono",
+            ChannelLayout::Stereo => "stereo",
+            ChannelLayout::Surround51 => "5.1",
+            ChannelLayout::Surround71 => "7.1",
+        }
+    }
+
+    /// SPA channel positions in channel-map order, as used in `audio.position`.
+    pub fn positions(&self) -> &'static [&'static str] {
+        match self {
+            ChannelLayout::Mono => &["MONO"],
+            ChannelLayout::Stereo => &["FL", "FR"],
+            ChannelLayout::Surround51 => &["FL", "FR", "FC", "LFE", "RL", "RR"],
+            ChannelLayout::Surround71 => &["FL", "FR", "FC", "LFE", "RL", "RR", "SL", "SR"],
+        }
+    }
+
+    /// Number of channels.
+    pub fn channels(&self) -> usize {
+        self.positions().len()
+    }
+
+    /// Whether the layout has side or rear speakers, which width fades.
+    pub fn has_surround(&self) -> bool {
+        matches!(self, ChannelLayout::Surround51 | ChannelLayout::Surround71)
+    }
+}
+
+impl std::fmt::Display for ChannelLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ChannelLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|layout| layout.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown channel layout '{}' (mono, stereo, 5.1, 7.1)", s))
+    }
+}
+
+/// Audio channel position.
+/// Ordered by standard channel layout for consistent pairing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AudioChannel {
+    FrontLeft,
+    FrontRight,
+    FrontCenter,
+    LowFrequency,
+    RearLeft,
+    RearRight,
+    SideLeft,
+    SideRight,
+    Mono,
+    Unknown,
+}
+
+impl AudioChannel {
+    /// Parse a SPA channel position name (the `audio.channel` port
+    /// property, e.g. "FL" or "LFE").
+    pub fn from_position(position: &str) -> Self {
+        match position.to_ascii_uppercase().as_str() {
+            "FL" => Self::FrontLeft,
+            "FR" => Self::FrontRight,
+            "FC" => Self::FrontCenter,
+            "LFE" => Self::LowFrequency,
+            "RL" => Self::RearLeft,
+            "RR" => Self::RearRight,
+            "SL" => Self::SideLeft,
+            "SR" => Self::SideRight,
+            "MONO" => Self::Mono,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Guess the position from a port name (e.g. "playback_FL"), for ports
+    /// without an `audio.channel` property.
+    pub fn from_port_name(s: &str) -> Self {
+        let s_lower = s.to_lowercase();
+        if s_lower.contains("fl")
+            || s_lower.contains("front_left")
+            || s_lower.contains("playback_fl")
+        {
+            Self::FrontLeft
+        } else if s_lower.contains("fr")
+            || s_lower.contains("front_right")
+            || s_lower.contains("playback_fr")
+        {
+            Self::FrontRight
+        } else if s_lower.contains("fc") || s_lower.contains("front_center") {
+            Self::FrontCenter
+        } else if s_lower.contains("mono") {
+            Self::Mono
+        } else if s_lower.contains("rl") || s_lower.contains("rear_left") {
+            Self::RearLeft
+        } else if s_lower.contains("rr") || s_lower.contains("rear_right") {
+            Self::RearRight
+        } else if s_lower.contains("lfe") || s_lower.contains("subwoofer") {
+            Self::LowFrequency
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Positions of a node with `inputs` that this output position feeds:
+    /// the same position when the node has it, otherwise the ones it folds
+    /// onto (centre onto both fronts, sides and rears onto each other or the
+    /// front of their side, everything onto mono). LFE is dropped when there's
+    /// nowhere to put it, as in a standard downmix.
+    pub fn mix_targets(&self, inputs: &[AudioChannel]) -> Vec<AudioChannel> {
+        use AudioChannel::*;
+
+        if inputs.contains(self) {
+            return vec![self.clone()];
+        }
+        if inputs.contains(&Mono) {
+            return match self {
+                LowFrequency | Unknown => Vec::new(),
+                _ => vec![Mono],
+            };
+        }
+        let first_of = |candidates: &[AudioChannel]| -> Vec<AudioChannel> {
+            candidates
+                .iter()
+                .find(|c| inputs.contains(c))
+                .cloned()
+                .into_iter()
+                .collect()
+        };
+        match self {
+            Mono | FrontCenter => {
+                let fronts: Vec<AudioChannel> = [FrontLeft, FrontRight]
+                    .into_iter()
+                    .filter(|c| inputs.contains(c))
+                    .collect();
+                if fronts.is_empty() && *self == Mono {
+                    first_of(&[FrontCenter])
+                } else {
+                    fronts
+                }
+            }
+            SideLeft => first_of(&[RearLeft, FrontLeft]),
+            SideRight => first_of(&[RearRight, FrontRight]),
+            RearLeft => first_of(&[SideLeft, FrontLeft]),
+            RearRight => first_of(&[SideRight, FrontRight]),
+            FrontLeft | FrontRight | LowFrequency | Unknown => Vec::new(),
+        }
+    }
+
+    /// Check if two channels are compatible for linking.
+    /// Allows matching same channels or mono to stereo mappings.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        if self == other {
+            return true;
+        }
+        matches!(
+            (self, other),
+            // Mono can connect to either stereo channel (for stereo↔mono downmix/upmix)
+            (Self::Mono, Self::FrontLeft)
+                | (Self::FrontLeft, Self::Mono)
+                | (Self::Mono, Self::FrontRight)
+                | (Self::FrontRight, Self::Mono)
+                // Unknown channels (e.g. Bluetooth numeric ports like capture_0)
+                // are compatible with any named channel — positional pairing handles order
+                | (Self::Unknown, _)
+                | (_, Self::Unknown)
+        )
+    }
+}
+
+/// How a soloed channel is heard. Sent over D-Bus as its name ("pfl" or
+/// "in-place").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+pub enum SoloMode {
+    /// Pre-fader listen: soloed channels are copied to the monitor bus and
+    /// the main mix is untouched.
+    #[default]
+    #[serde(rename = "pfl")]
+    Pfl,
+    /// Solo in place: while any output channel is soloed, every other output
+    /// channel is cut from the main mix.
+    #[serde(rename = "in-place")]
+    InPlace,
+}
+
+impl SoloMode {
+    pub const ALL: [SoloMode; 2] = [SoloMode::Pfl, SoloMode::InPlace];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SoloMode::Pfl => "pfl",
+            SoloMode::InPlace => "in-place",
+        }
+    }
+}
+
+impl std::fmt::Display for SoloMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for SoloMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown solo mode '{}' (pfl, in-place)", s))
+    }
+}
+
 /// Information about a mixer channel.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct ChannelInfo {
@@ -41,8 +291,13 @@ pub struct ChannelInfo {
     pub muted: bool,
     /// Whether EQ is enabled for this channel.
     pub eq_enabled: bool,
-    /// Name of the EQ preset applied.
+    /// Name of the EQ preset applied, or the one last chosen when
+    /// `eq_custom` is set.
     pub eq_preset: String,
+    /// Bands of the channel's EQ.
+    pub eq_bands: Vec<EqBandInfo>,
+    /// Whether the bands were edited after choosing the preset.
+    pub eq_custom: bool,
     /// App identifiers assigned to this channel.
     pub assigned_apps: Vec<String>,
     /// Output device name for this channel (empty string = default).
@@ -53,6 +308,38 @@ pub struct ChannelInfo {
     pub kind: ChannelKind,
     /// Hardware microphone gain in dB (-12.0 to +12.0). Only applies to input channels.
     pub input_gain_db: f64,
+    /// Pan/balance (-1.0 left to 1.0 right).
+    pub pan: f64,
+    /// Surround width (0.0 front speakers only to 1.0 all speakers).
+    pub width: f64,
+    /// Speaker layout of the channel's sink (always stereo for input channels).
+    pub layout: ChannelLayout,
+    /// Whether the channel is soloed.
+    pub solo: bool,
+    /// Whether the channel is heard on the monitor bus as sidetone (input
+    /// channels only).
+    pub sidetone_enabled: bool,
+    /// Sidetone level in dB.
+    pub sidetone_volume_db: f64,
+    /// Latency of the channel's processing in samples: what its plugins
+    /// report plus `latency_compensation_samples`.
+    pub latency_samples: u32,
+    /// Delay added to line up with other channels on the same output, in
+    /// samples.
+    pub latency_compensation_samples: u32,
+    /// Automatic loudness normalization settings.
+    pub auto_level: AutoLevelInfo,
+    /// Gain currently applied by auto level, in dB (positive = boost).
+    pub auto_level_gain_db: f64,
+    /// Noise suppression tuning (input channels only).
+    pub noise_suppression: NoiseSuppressionInfo,
+    /// Whether noise suppression currently hears a voice on the channel.
+    pub talking: bool,
+    /// Push-to-talk or push-to-mute (input channels only).
+    pub talk: TalkInfo,
+    /// Whether the channel's mic is heard: not muted and, with a talk mode,
+    /// keyed open.
+    pub live: bool,
 }
 
 impl ChannelInfo {
@@ -65,11 +352,27 @@ impl ChannelInfo {
             muted: false,
             eq_enabled: false,
             eq_preset: "Flat".to_string(),
+            eq_bands: Vec::new(),
+            eq_custom: false,
             assigned_apps: Vec::new(),
             output_device: String::new(),
             meter_levels: (-60.0, -60.0),
             kind: ChannelKind::Output,
             input_gain_db: 0.0,
+            pan: 0.0,
+            width: 1.0,
+            layout: ChannelLayout::Stereo,
+            solo: false,
+            sidetone_enabled: false,
+            sidetone_volume_db: -20.0,
+            latency_samples: 0,
+            latency_compensation_samples: 0,
+            auto_level: AutoLevelInfo::default(),
+            auto_level_gain_db: 0.0,
+            noise_suppression: NoiseSuppressionInfo::default(),
+            talking: false,
+            talk: TalkInfo::default(),
+            live: true,
         }
     }
 
@@ -246,6 +549,10 @@ pub struct MeterData {
     pub peak_left_db: f64,
     /// Right peak hold level in dB.
     pub peak_right_db: f64,
+    /// Gain reduction currently applied by ducking, in dB (0.0 = none).
+    pub gain_reduction_db: f64,
+    /// Gain currently applied by auto level, in dB (0.0 = none).
+    pub auto_level_gain_db: f64,
 }
 
 impl MeterData {
@@ -258,14 +565,666 @@ impl MeterData {
             level_right_db: right,
             peak_left_db: peak_left,
             peak_right_db: peak_right,
+            gain_reduction_db: 0.0,
+            auto_level_gain_db: 0.0,
+        }
+    }
+
+    /// Attach the channel's current ducking gain reduction.
+    pub fn with_gain_reduction(mut self, reduction_db: f64) -> Self {
+        self.gain_reduction_db = reduction_db;
+        self
+    }
+
+    /// Attach the channel's current auto level gain.
+    pub fn with_auto_level_gain(mut self, gain_db: f64) -> Self {
+        self.auto_level_gain_db = gain_db;
+        self
+    }
+
+    pub fn channel_id(&self) -> Uuid {
+        let high = (self.channel_id_high as u128) << 64;
+        let low = self.channel_id_low as u128;
+        Uuid::from_u128(high | low)
+    }
+}
+
+/// Reported loudness when there is nothing to measure, in LUFS (the
+/// BS.1770 absolute gate).
+pub const LOUDNESS_FLOOR_LUFS: f64 = -70.0;
+
+/// Loudness readings of a channel or the master output (EBU R128).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type)]
+pub struct LoudnessData {
+    /// Channel ID (high 64 bits); nil for the master output.
+    pub channel_id_high: u64,
+    /// Channel ID (low 64 bits); nil for the master output.
+    pub channel_id_low: u64,
+    /// Momentary loudness (400 ms window) in LUFS.
+    pub momentary_lufs: f64,
+    /// Short-term loudness (3 s window) in LUFS.
+    pub short_term_lufs: f64,
+    /// Integrated loudness since the last reset in LUFS.
+    pub integrated_lufs: f64,
+    /// Loudness range since the last reset in LU.
+    pub range_lu: f64,
+    /// Highest true peak since the last reset in dBTP.
+    pub true_peak_dbtp: f64,
+}
+
+impl LoudnessData {
+    /// Readings for `channel_id` (`Uuid::nil()` for the master output).
+    /// Loudness values are clamped to [`LOUDNESS_FLOOR_LUFS`].
+    pub fn new(
+        channel_id: Uuid,
+        momentary: f64,
+        short_term: f64,
+        integrated: f64,
+        range: f64,
+        true_peak: f64,
+    ) -> Self {
+        let bytes = channel_id.as_u128();
+        Self {
+            channel_id_high: (bytes >> 64) as u64,
+            channel_id_low: bytes as u64,
+            momentary_lufs: momentary.max(LOUDNESS_FLOOR_LUFS),
+            short_term_lufs: short_term.max(LOUDNESS_FLOOR_LUFS),
+            integrated_lufs: integrated.max(LOUDNESS_FLOOR_LUFS),
+            range_lu: range,
+            true_peak_dbtp: true_peak,
+        }
+    }
+
+    pub fn channel_id(&self) -> Uuid {
+        let high = (self.channel_id_high as u128) << 64;
+        let low = self.channel_id_low as u128;
+        Uuid::from_u128(high | low)
+    }
+
+    /// Whether these are the master output's readings.
+    pub fn is_master(&self) -> bool {
+        self.channel_id().is_nil()
+    }
+}
+
+/// Number of log-spaced bands in a spectrum.
+pub const SPECTRUM_BANDS: usize = 96;
+
+/// Center frequency of the lowest spectrum band, in Hz.
+pub const SPECTRUM_MIN_HZ: f64 = 20.0;
+
+/// Center frequency of the highest spectrum band, in Hz.
+pub const SPECTRUM_MAX_HZ: f64 = 20_000.0;
+
+/// Reported level of silent spectrum bands, in dBFS.
+pub const SPECTRUM_FLOOR_DB: f64 = -100.0;
+
+/// Center frequency of spectrum band `band`, in Hz.
+pub fn spectrum_band_frequency(band: usize) -> f64 {
+    let position = band as f64 / (SPECTRUM_BANDS - 1) as f64;
+    SPECTRUM_MIN_HZ * (SPECTRUM_MAX_HZ / SPECTRUM_MIN_HZ).powf(position)
+}
+
+/// Where the spectrum analyzer taps a channel. Sent over D-Bus as its name
+/// ("pre" or "post").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+pub enum SpectrumTap {
+    /// Before the plugin chain (and before the fader).
+    #[default]
+    #[serde(rename = "pre")]
+    Pre,
+    /// After the plugin chain.
+    #[serde(rename = "post")]
+    Post,
+}
+
+impl SpectrumTap {
+    pub const ALL: [SpectrumTap; 2] = [SpectrumTap::Pre, SpectrumTap::Post];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpectrumTap::Pre => "pre",
+            SpectrumTap::Post => "post",
+        }
+    }
+}
+
+impl std::fmt::Display for SpectrumTap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for SpectrumTap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|tap| tap.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown spectrum tap '{}' (pre, post)", s))
+    }
+}
+
+/// Spectrum of the analyzed channel or master output, one value per band
+/// (see [`spectrum_band_frequency`]).
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SpectrumData {
+    /// Channel ID (high 64 bits); nil for the master output.
+    pub channel_id_high: u64,
+    /// Channel ID (low 64 bits); nil for the master output.
+    pub channel_id_low: u64,
+    /// Tap the spectrum was taken from.
+    pub tap: SpectrumTap,
+    /// Band levels in dBFS (a full-scale sine reads 0 dB).
+    pub levels_db: Vec<f64>,
+    /// Held band peaks in dBFS.
+    pub peaks_db: Vec<f64>,
+    /// Response of the channel's EQ per band in dB; empty when the EQ is off.
+    pub eq_db: Vec<f64>,
+}
+
+impl SpectrumData {
+    /// Spectrum of `channel_id` (`Uuid::nil()` for the master output).
+    /// Levels are clamped to [`SPECTRUM_FLOOR_DB`].
+    pub fn new(channel_id: Uuid, tap: SpectrumTap, levels_db: &[f32], peaks_db: &[f32]) -> Self {
+        let bytes = channel_id.as_u128();
+        let clamp = |db: &f32| (*db as f64).max(SPECTRUM_FLOOR_DB);
+        Self {
+            channel_id_high: (bytes >> 64) as u64,
+            channel_id_low: bytes as u64,
+            tap,
+            levels_db: levels_db.iter().map(clamp).collect(),
+            peaks_db: peaks_db.iter().map(clamp).collect(),
+            eq_db: Vec::new(),
         }
     }
 
+    /// Attach the channel's EQ response.
+    pub fn with_eq(mut self, eq_db: Vec<f64>) -> Self {
+        self.eq_db = eq_db;
+        self
+    }
+
     pub fn channel_id(&self) -> Uuid {
         let high = (self.channel_id_high as u128) << 64;
         let low = self.channel_id_low as u128;
         Uuid::from_u128(high | low)
     }
+
+    /// Whether this is the master output's spectrum.
+    pub fn is_master(&self) -> bool {
+        self.channel_id().is_nil()
+    }
+}
+
+/// Automatic loudness normalization ("auto level") of a channel: a make-up
+/// gain after the fader that slowly steers the channel's short-term
+/// loudness toward a target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct AutoLevelInfo {
+    /// Whether auto level is on.
+    pub enabled: bool,
+    /// Loudness to steer toward, in LUFS.
+    pub target_lufs: f64,
+    /// Most gain added to quiet sources, in dB.
+    pub max_boost_db: f64,
+    /// Most gain taken from loud sources, in dB (positive).
+    pub max_cut_db: f64,
+    /// How fast the gain follows the source, in dB per second.
+    pub speed_db_per_sec: f64,
+}
+
+impl Default for AutoLevelInfo {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_lufs: -18.0,
+            max_boost_db: 12.0,
+            max_cut_db: 12.0,
+            speed_db_per_sec: 1.5,
+        }
+    }
+}
+
+/// How noise suppression finds voice. Sent over D-Bus as its name
+/// ("rnnoise" or "gate").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+pub enum NoiseModel {
+    /// RNNoise: removes noise and gates on its voice detection.
+    #[default]
+    #[serde(rename = "rnnoise")]
+    RNNoise,
+    /// Level-based gate: much lighter, but leaves the noise under speech.
+    #[serde(rename = "gate")]
+    Gate,
+}
+
+impl NoiseModel {
+    pub const ALL: [NoiseModel; 2] = [NoiseModel::RNNoise, NoiseModel::Gate];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NoiseModel::RNNoise => "rnnoise",
+            NoiseModel::Gate => "gate",
+        }
+    }
+}
+
+impl std::fmt::Display for NoiseModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for NoiseModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|model| model.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown noise model '{}' (rnnoise, gate)", s))
+    }
+}
+
+/// Longest VAD grace period, in ms.
+pub const NOISE_MAX_GRACE_MS: f64 = 1000.0;
+
+/// Longest retroactive VAD grace, in ms.
+pub const NOISE_MAX_RETRO_GRACE_MS: f64 = 500.0;
+
+/// Attenuation limit at which noise is removed completely, in dB.
+pub const NOISE_MAX_ATTENUATION_DB: f64 = 100.0;
+
+/// Tuning of an input channel's noise suppression. Whether it is on and its
+/// VAD threshold are set on their own.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct NoiseSuppressionInfo {
+    /// How voice is found.
+    pub model: NoiseModel,
+    /// How long the mic stays open after the voice stops, in ms.
+    pub grace_ms: f64,
+    /// How long before the voice starts the mic opens, in ms. Delays the
+    /// channel by as much.
+    pub retro_grace_ms: f64,
+    /// Most the mic is turned down while noise is removed, in dB
+    /// (`NOISE_MAX_ATTENUATION_DB` = no limit).
+    pub attenuation_limit_db: f64,
+}
+
+impl Default for NoiseSuppressionInfo {
+    fn default() -> Self {
+        Self {
+            model: NoiseModel::RNNoise,
+            grace_ms: 200.0,
+            retro_grace_ms: 0.0,
+            attenuation_limit_db: NOISE_MAX_ATTENUATION_DB,
+        }
+    }
+}
+
+/// How an input channel's talk key works. Sent over D-Bus as its name
+/// ("off", "push-to-talk" or "push-to-mute").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+pub enum TalkMode {
+    /// The mic is open; the talk key does nothing.
+    #[default]
+    #[serde(rename = "off")]
+    Off,
+    /// The mic is silent until the talk key is held.
+    #[serde(rename = "push-to-talk")]
+    PushToTalk,
+    /// The mic is open until the talk key is held.
+    #[serde(rename = "push-to-mute")]
+    PushToMute,
+}
+
+impl TalkMode {
+    pub const ALL: [TalkMode; 3] = [TalkMode::Off, TalkMode::PushToTalk, TalkMode::PushToMute];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TalkMode::Off => "off",
+            TalkMode::PushToTalk => "push-to-talk",
+            TalkMode::PushToMute => "push-to-mute",
+        }
+    }
+}
+
+impl std::fmt::Display for TalkMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TalkMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown talk mode '{}' (off, push-to-talk, push-to-mute)",
+                    s
+                )
+            })
+    }
+}
+
+/// Longest talk key release tail, in ms.
+pub const TALK_MAX_RELEASE_MS: f64 = 2000.0;
+
+/// Longest fade when the talk key opens or closes the mic, in ms.
+pub const TALK_MAX_FADE_MS: f64 = 500.0;
+
+/// Push-to-talk / push-to-mute of an input channel.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct TalkInfo {
+    /// What holding the talk key does.
+    pub mode: TalkMode,
+    /// How long the key stays held after it is let go, in ms, so the end
+    /// of a word isn't cut off.
+    pub release_ms: f64,
+    /// How long the mic takes to open or close, in ms.
+    pub fade_ms: f64,
+}
+
+impl Default for TalkInfo {
+    fn default() -> Self {
+        Self {
+            mode: TalkMode::Off,
+            release_ms: 200.0,
+            fade_ms: 20.0,
+        }
+    }
+}
+
+/// Most bands an EQ can have.
+pub const EQ_MAX_BANDS: usize = 16;
+
+/// Lowest EQ band frequency, in Hz.
+pub const EQ_MIN_HZ: f64 = 20.0;
+
+/// Highest EQ band frequency, in Hz.
+pub const EQ_MAX_HZ: f64 = 20_000.0;
+
+/// Largest boost or cut of an EQ band, in dB.
+pub const EQ_MAX_GAIN_DB: f64 = 24.0;
+
+/// Narrowest and widest EQ band Q.
+pub const EQ_MIN_Q: f64 = 0.1;
+pub const EQ_MAX_Q: f64 = 18.0;
+
+/// Roll-offs a high- or low-pass band can have, in dB per octave.
+pub const EQ_SLOPES: [u32; 4] = [12, 24, 36, 48];
+
+/// Lowest response reported for an EQ, in dB (the depth of a notch or the
+/// stop band of a pass filter).
+pub const EQ_RESPONSE_FLOOR_DB: f64 = -120.0;
+
+/// Filter shape of an EQ band. Sent over D-Bus as its name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Type)]
+#[zvariant(signature = "s")]
+pub enum EqBandType {
+    /// Bell boosting or cutting around the frequency.
+    #[default]
+    #[serde(rename = "peaking")]
+    Peaking,
+    /// Boost or cut below the frequency.
+    #[serde(rename = "low_shelf")]
+    LowShelf,
+    /// Boost or cut above the frequency.
+    #[serde(rename = "high_shelf")]
+    HighShelf,
+    /// Remove everything below the frequency.
+    #[serde(rename = "high_pass")]
+    HighPass,
+    /// Remove everything above the frequency.
+    #[serde(rename = "low_pass")]
+    LowPass,
+    /// Remove a narrow range around the frequency.
+    #[serde(rename = "notch")]
+    Notch,
+    /// Keep only a range around the frequency.
+    #[serde(rename = "band_pass")]
+    BandPass,
+}
+
+impl EqBandType {
+    pub const ALL: [EqBandType; 7] = [
+        EqBandType::Peaking,
+        EqBandType::LowShelf,
+        EqBandType::HighShelf,
+        EqBandType::HighPass,
+        EqBandType::LowPass,
+        EqBandType::Notch,
+        EqBandType::BandPass,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EqBandType::Peaking => "peaking",
+            EqBandType::LowShelf => "low_shelf",
+            EqBandType::HighShelf => "high_shelf",
+            EqBandType::HighPass => "high_pass",
+            EqBandType::LowPass => "low_pass",
+            EqBandType::Notch => "notch",
+            EqBandType::BandPass => "band_pass",
+        }
+    }
+
+    /// Human-readable name.
+    pub fn label(&self) -> &'static str {
+        match self {
+            EqBandType::Peaking => "Bell",
+            EqBandType::LowShelf => "Low Shelf",
+            EqBandType::HighShelf => "High Shelf",
+            EqBandType::HighPass => "High Pass",
+            EqBandType::LowPass => "Low Pass",
+            EqBandType::Notch => "Notch",
+            EqBandType::BandPass => "Band Pass",
+        }
+    }
+
+    /// Whether the band's gain has an effect.
+    pub fn has_gain(&self) -> bool {
+        matches!(
+            self,
+            EqBandType::Peaking | EqBandType::LowShelf | EqBandType::HighShelf
+        )
+    }
+
+    /// Whether the band has a selectable slope.
+    pub fn has_slope(&self) -> bool {
+        matches!(self, EqBandType::HighPass | EqBandType::LowPass)
+    }
+}
+
+impl std::fmt::Display for EqBandType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for EqBandType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown EQ band type '{}' (peaking, low_shelf, high_shelf, high_pass, low_pass, notch, band_pass)",
+                    s
+                )
+            })
+    }
+}
+
+/// One band of a channel's parametric EQ.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type)]
+pub struct EqBandInfo {
+    /// Filter shape.
+    pub band_type: EqBandType,
+    /// Center (peaking, notch, band-pass), corner (shelves) or cutoff (pass
+    /// filters) frequency in Hz.
+    pub freq_hz: f64,
+    /// Boost or cut in dB (peaking and shelves only).
+    pub gain_db: f64,
+    /// Bandwidth of peaking, notch and band-pass bands; steepness of shelves;
+    /// resonance of 12 dB/octave pass filters.
+    pub q: f64,
+    /// Roll-off of pass filters in dB per octave (one of [`EQ_SLOPES`]).
+    pub slope_db: u32,
+}
+
+impl EqBandInfo {
+    /// A flat band of `band_type` at `freq_hz`.
+    pub fn new(band_type: EqBandType, freq_hz: f64) -> Self {
+        Self {
+            band_type,
+            freq_hz,
+            gain_db: 0.0,
+            q: std::f64::consts::FRAC_1_SQRT_2,
+            slope_db: EQ_SLOPES[0],
+        }
+    }
+
+    /// This band with every value moved into its allowed range.
+    pub fn clamped(self) -> Self {
+        let slope_db = EQ_SLOPES
+            .into_iter()
+            .min_by_key(|slope| slope.abs_diff(self.slope_db))
+            .unwrap_or(EQ_SLOPES[0]);
+        Self {
+            band_type: self.band_type,
+            freq_hz: self.freq_hz.clamp(EQ_MIN_HZ, EQ_MAX_HZ),
+            gain_db: self.gain_db.clamp(-EQ_MAX_GAIN_DB, EQ_MAX_GAIN_DB),
+            q: self.q.clamp(EQ_MIN_Q, EQ_MAX_Q),
+            slope_db,
+        }
+    }
+
+    /// Q of each biquad the band is made of: one, except for pass filters
+    /// steeper than 12 dB/octave, which are Butterworth cascades of
+    /// `slope_db / 12` sections.
+    pub fn stage_qs(&self) -> Vec<f64> {
+        let sections = if self.band_type.has_slope() {
+            (self.slope_db / 12).max(1)
+        } else {
+            1
+        };
+        if sections == 1 {
+            return vec![self.q];
+        }
+        let order = f64::from(sections * 2);
+        (1..=sections)
+            .map(|k| {
+                let angle = std::f64::consts::PI * (2 * k - 1) as f64 / (2.0 * order);
+                1.0 / (2.0 * angle.cos())
+            })
+            .collect()
+    }
+
+    /// Response of the band at `freq` Hz in dB, for audio at `sample_rate`.
+    ///
+    /// Evaluates the RBJ cookbook biquads PipeWire's filter-chain runs for
+    /// the band (`bq_peaking`, `bq_lowshelf` and so on).
+    pub fn response_db(&self, freq: f64, sample_rate: f64) -> f64 {
+        let nyquist = sample_rate / 2.0;
+        if self.freq_hz >= nyquist || (self.band_type.has_gain() && self.gain_db == 0.0) {
+            return 0.0;
+        }
+
+        let w = std::f64::consts::PI * freq.min(nyquist) / nyquist;
+        let response: f64 = self
+            .stage_qs()
+            .into_iter()
+            .map(|q| {
+                let (b, a) = self.coefficients(q, nyquist);
+                let power = |c: [f64; 3]| {
+                    let re = c[0] + c[1] * w.cos() + c[2] * (2.0 * w).cos();
+                    let im = c[1] * w.sin() + c[2] * (2.0 * w).sin();
+                    re * re + im * im
+                };
+                10.0 * (power(b) / power(a)).log10()
+            })
+            .sum();
+        response.max(EQ_RESPONSE_FLOOR_DB)
+    }
+
+    /// Numerator and denominator of one section with quality `q`.
+    fn coefficients(&self, q: f64, nyquist: f64) -> ([f64; 3], [f64; 3]) {
+        let w0 = std::f64::consts::PI * self.freq_hz / nyquist;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+        let a = 10f64.powf(self.gain_db / 40.0);
+        let shelf = 2.0 * a.sqrt() * alpha;
+
+        match self.band_type {
+            EqBandType::Peaking => (
+                [1.0 + alpha * a, -2.0 * cos, 1.0 - alpha * a],
+                [1.0 + alpha / a, -2.0 * cos, 1.0 - alpha / a],
+            ),
+            EqBandType::LowShelf => (
+                [
+                    a * ((a + 1.0) - (a - 1.0) * cos + shelf),
+                    2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+                    a * ((a + 1.0) - (a - 1.0) * cos - shelf),
+                ],
+                [
+                    (a + 1.0) + (a - 1.0) * cos + shelf,
+                    -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+                    (a + 1.0) + (a - 1.0) * cos - shelf,
+                ],
+            ),
+            EqBandType::HighShelf => (
+                [
+                    a * ((a + 1.0) + (a - 1.0) * cos + shelf),
+                    -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+                    a * ((a + 1.0) + (a - 1.0) * cos - shelf),
+                ],
+                [
+                    (a + 1.0) - (a - 1.0) * cos + shelf,
+                    2.0 * ((a - 1.0) - (a + 1.0) * cos),
+                    (a + 1.0) - (a - 1.0) * cos - shelf,
+                ],
+            ),
+            EqBandType::HighPass => (
+                [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+                [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+            ),
+            EqBandType::LowPass => (
+                [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+                [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+            ),
+            EqBandType::Notch => (
+                [1.0, -2.0 * cos, 1.0],
+                [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+            ),
+            EqBandType::BandPass => ([alpha, 0.0, -alpha], [1.0 + alpha, -2.0 * cos, 1.0 - alpha]),
+        }
+    }
+}
+
+/// Response of an EQ made of `bands` at `freq` Hz in dB, for audio at
+/// `sample_rate`.
+pub fn eq_response_db(bands: &[EqBandInfo], freq: f64, sample_rate: f64) -> f64 {
+    bands
+        .iter()
+        .map(|band| band.response_db(freq, sample_rate))
+        .sum::<f64>()
+        .max(EQ_RESPONSE_FLOOR_DB)
 }
 
 /// Routing rule information.
@@ -287,6 +1246,114 @@ pub struct RoutingRuleInfo {
     pub target_channel: String,
     /// Priority (lower = higher priority).
     pub priority: u32,
+    /// Extra conditions on PipeWire node properties.
+    pub conditions: Vec<RuleConditionInfo>,
+    /// How conditions combine: "all" (AND) or "any" (OR). The name/binary
+    /// match counts as a condition unless `pattern` is empty.
+    pub combine: String,
+}
+
+/// A routing rule condition on one PipeWire node property.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RuleConditionInfo {
+    /// Property key (e.g. "media.role", "pipewire.access.portal.app_id").
+    pub property: String,
+    /// Match type: "contains", "exact", "regex", or "glob".
+    pub match_type: String,
+    /// Pattern string.
+    pub pattern: String,
+}
+
+/// A ducking relationship: while the trigger channel is above the threshold,
+/// the ducked channels are attenuated.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DuckingInfo {
+    /// Relationship ID (UUID as string, empty to create a new one).
+    pub id: String,
+    /// Whether the relationship is active.
+    pub enabled: bool,
+    /// Channel whose level triggers ducking (UUID as string).
+    pub trigger_channel: String,
+    /// Channels attenuated while the trigger is active (UUIDs as strings).
+    pub ducked_channels: Vec<String>,
+    /// Trigger level in dB above which ducking engages.
+    pub threshold_db: f64,
+    /// Attenuation applied to the ducked channels, in dB (positive).
+    pub amount_db: f64,
+    /// Time to reach full attenuation, in milliseconds.
+    pub attack_ms: f64,
+    /// Time attenuation is held after the trigger falls silent, in milliseconds.
+    pub hold_ms: f64,
+    /// Time to recover from full attenuation, in milliseconds.
+    pub release_ms: f64,
+}
+
+/// The daemon's monitor bus, which plays soloed channels (PFL) and sidetone
+/// on a separate device such as headphones.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct MonitorInfo {
+    /// Monitor device name (empty = the master output).
+    pub device: String,
+    /// Monitor bus level in dB.
+    pub volume_db: f64,
+    /// Whether the monitor bus is muted.
+    pub muted: bool,
+    /// How soloed channels are heard.
+    pub solo_mode: SoloMode,
+}
+
+/// State of a running recording.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecordingStatus {
+    /// Recording ID (UUID as string).
+    pub id: String,
+    /// Recorded sources, one per file: "master" or a channel UUID.
+    pub sources: Vec<String>,
+    /// Output file paths, in the same order as `sources`.
+    pub files: Vec<String>,
+    /// File format ("wav" or "flac").
+    pub format: String,
+    /// Recorded audio so far, in seconds.
+    pub elapsed_secs: f64,
+    /// Combined size of the output files, in bytes.
+    pub bytes: u64,
+    /// Frames lost because the writer fell behind.
+    pub dropped_frames: u64,
+}
+
+/// A MIDI control bound to a mixer target.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct MidiBindingInfo {
+    /// Binding ID (UUID as string). Empty when creating a new binding.
+    pub id: String,
+    /// Device the binding listens to; empty for any device.
+    pub device: String,
+    /// MIDI channel, 1-16.
+    pub midi_channel: u8,
+    /// "cc" or "note".
+    pub control: String,
+    /// Controller or note number, 0-127.
+    pub number: u8,
+    /// "channel_volume", "channel_mute", "channel_solo", "channel_talk",
+    /// "master_volume", "master_mute" or "plugin_parameter".
+    pub target: String,
+    /// Target channel ID (empty for master targets).
+    pub channel_id: String,
+    /// Plugin chain slot (plugin_parameter only).
+    pub plugin_slot: u32,
+    /// Plugin parameter index (plugin_parameter only).
+    pub param_index: u32,
+}
+
+/// State of a channel's instant-replay buffer.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReplayBufferInfo {
+    /// Channel ID (UUID as string).
+    pub channel_id: String,
+    /// Audio currently held, in seconds (up to the configured length).
+    pub buffered_secs: f64,
+    /// Frames lost because the buffer fell behind.
+    pub dropped_frames: u64,
 }
 
 /// Error types for daemon operations.
@@ -323,6 +1390,27 @@ mod tests {
         assert_eq!(channel.uuid(), Some(id));
     }
 
+    #[test]
+    fn test_channel_layout_names() {
+        for layout in ChannelLayout::ALL {
+            assert_eq!(layout.to_string().parse::<ChannelLayout>(), Ok(layout));
+        }
+        assert_eq!(ChannelLayout::Surround71.channels(), 8);
+        assert!(ChannelLayout::Surround51.has_surround());
+        assert!(!ChannelLayout::Stereo.has_surround());
+        assert!("quad".parse::<ChannelLayout>().is_err());
+        for mode in SoloMode::ALL {
+            assert_eq!(mode.to_string().parse::<SoloMode>(), Ok(mode));
+        }
+        for mode in TalkMode::ALL {
+            assert_eq!(mode.to_string().parse::<TalkMode>(), Ok(mode));
+        }
+        assert_eq!(
+            ChannelLayout::signature(),
+            zbus::zvariant::Signature::from_static_str_unchecked("s")
+        );
+    }
+
     #[test]
     fn test_meter_data_round_trip() {
         let id = Uuid::new_v4();
@@ -330,5 +1418,85 @@ mod tests {
         assert_eq!(data.channel_id(), id);
         assert_eq!(data.level_left_db, -20.0);
         assert_eq!(data.level_right_db, -18.0);
+        assert_eq!(data.gain_reduction_db, 0.0);
+        assert_eq!(data.with_gain_reduction(6.0).gain_reduction_db, 6.0);
+    }
+
+    #[test]
+    fn test_loudness_data_floor() {
+        let id = Uuid::new_v4();
+        let data = LoudnessData::new(id, -14.0, f64::NEG_INFINITY, -90.0, 4.0, -1.0);
+        assert_eq!(data.channel_id(), id);
+        assert!(!data.is_master());
+        assert_eq!(data.momentary_lufs, -14.0);
+        assert_eq!(data.short_term_lufs, LOUDNESS_FLOOR_LUFS);
+        assert_eq!(data.integrated_lufs, LOUDNESS_FLOOR_LUFS);
+        assert!(LoudnessData::new(Uuid::nil(), 0.0, 0.0, 0.0, 0.0, 0.0).is_master());
+    }
+
+    #[test]
+    fn test_spectrum_bands_span_audible_range() {
+        assert_eq!(spectrum_band_frequency(0), SPECTRUM_MIN_HZ);
+        assert!((spectrum_band_frequency(SPECTRUM_BANDS - 1) - SPECTRUM_MAX_HZ).abs() < 1e-6);
+        assert!(spectrum_band_frequency(1) > SPECTRUM_MIN_HZ);
+        for tap in SpectrumTap::ALL {
+            assert_eq!(tap.to_string().parse::<SpectrumTap>(), Ok(tap));
+        }
+
+        let data = SpectrumData::new(Uuid::nil(), SpectrumTap::Post, &[-3.0, -200.0], &[0.0; 2]);
+        assert!(data.is_master());
+        assert_eq!(data.levels_db, vec![-3.0, SPECTRUM_FLOOR_DB]);
+        assert!(data.eq_db.is_empty());
+    }
+
+    #[test]
+    fn test_eq_band_response() {
+        const RATE: f64 = 48_000.0;
+        for kind in EqBandType::ALL {
+            assert_eq!(kind.to_string().parse::<EqBandType>(), Ok(kind));
+        }
+
+        let mut bell = EqBandInfo::new(EqBandType::Peaking, 1000.0);
+        assert_eq!(eq_response_db(&[bell], 1000.0, RATE), 0.0);
+        bell.gain_db = 6.0;
+        bell.q = 2.0;
+        assert!((bell.response_db(1000.0, RATE) - 6.0).abs() < 1e-6);
+        assert!(bell.response_db(100.0, RATE).abs() < 0.1);
+
+        let mut shelf = EqBandInfo::new(EqBandType::LowShelf, 200.0);
+        shelf.gain_db = -9.0;
+        assert!((shelf.response_db(25.0, RATE) + 9.0).abs() < 0.1);
+        assert!(shelf.response_db(5000.0, RATE).abs() < 0.1);
+
+        // -3 dB at the cutoff and the slope's worth an octave below it
+        let mut high_pass = EqBandInfo::new(EqBandType::HighPass, 400.0);
+        for slope in EQ_SLOPES {
+            high_pass.slope_db = slope;
+            assert!((high_pass.response_db(400.0, RATE) + 3.0).abs() < 0.1);
+            let octave_down =
+                high_pass.response_db(100.0, RATE) - high_pass.response_db(200.0, RATE);
+            assert!(
+                (octave_down + slope as f64).abs() < 1.5,
+                "{slope}: {octave_down}"
+            );
+        }
+        assert_eq!(high_pass.stage_qs().len(), 4);
+
+        let notch = EqBandInfo::new(EqBandType::Notch, 50.0);
+        assert_eq!(notch.response_db(50.0, RATE), EQ_RESPONSE_FLOOR_DB);
+        assert!(notch.response_db(1000.0, RATE).abs() < 0.1);
+
+        let wild = EqBandInfo {
+            band_type: EqBandType::LowPass,
+            freq_hz: 40_000.0,
+            gain_db: -40.0,
+            q: 0.0,
+            slope_db: 30,
+        }
+        .clamped();
+        assert_eq!(wild.freq_hz, EQ_MAX_HZ);
+        assert_eq!(wild.gain_db, -EQ_MAX_GAIN_DB);
+        assert_eq!(wild.q, EQ_MIN_Q);
+        assert_eq!(wild.slope_db, 24);
     }
 }
@@ -0,0 +1,180 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Client-side proxy for the daemon's D-Bus interface.
+//!
+//! Shared by the GUI and `sootmixctl` so both speak the same method and
+//! signal signatures as the daemon's `com.sootmix.Daemon` object.
+
+use crate::{
+    AppInfo, AutoLevelInfo, ChannelInfo, DuckingInfo, EqBandInfo, InputInfo, LoudnessData,
+    MeterData, MidiBindingInfo, MonitorInfo, NoiseSuppressionInfo, OutputInfo, PluginSlotInfo,
+    RecordingStatus, ReplayBufferInfo, RoutingRuleInfo, SpectrumData, TalkInfo,
+};
+use zbus::{proxy, Result as ZbusResult};
+
+/// D-Bus proxy for the daemon interface.
+#[proxy(
+    interface = "com.sootmix.Daemon",
+    default_service = "com.sootmix.Daemon",
+    default_path = "/com/sootmix/Daemon"
+)]
+pub trait Daemon {
+    // Methods
+    fn create_channel(&self, name: &str) -> ZbusResult<String>;
+    fn create_input_channel(&self, name: &str) -> ZbusResult<String>;
+    fn delete_channel(&self, channel_id: &str) -> ZbusResult<()>;
+    fn rename_channel(&self, channel_id: &str, name: &str) -> ZbusResult<()>;
+    fn move_channel(&self, channel_id: &str, direction: i32) -> ZbusResult<()>;
+    fn set_channel_volume(&self, channel_id: &str, volume_db: f64) -> ZbusResult<()>;
+    fn set_channel_mute(&self, channel_id: &str, muted: bool) -> ZbusResult<()>;
+    fn set_channel_balance(&self, channel_id: &str, pan: f64, width: f64) -> ZbusResult<()>;
+    fn set_channel_layout(&self, channel_id: &str, layout: &str) -> ZbusResult<()>;
+    fn set_channel_noise_suppression(&self, channel_id: &str, enabled: bool) -> ZbusResult<()>;
+    fn set_channel_vad_threshold(&self, channel_id: &str, threshold: f64) -> ZbusResult<()>;
+    fn set_channel_noise_suppression_settings(
+        &self,
+        channel_id: &str,
+        settings: NoiseSuppressionInfo,
+    ) -> ZbusResult<()>;
+    fn set_channel_talk_settings(&self, channel_id: &str, settings: TalkInfo) -> ZbusResult<()>;
+    fn set_talk_key(&self, channel_id: &str, pressed: bool) -> ZbusResult<()>;
+    fn set_channel_input_gain(&self, channel_id: &str, gain_db: f64) -> ZbusResult<()>;
+    fn set_channel_auto_level(&self, channel_id: &str, settings: AutoLevelInfo) -> ZbusResult<()>;
+    fn set_channel_eq_enabled(&self, channel_id: &str, enabled: bool) -> ZbusResult<()>;
+    fn set_channel_eq_preset(&self, channel_id: &str, preset_name: &str) -> ZbusResult<()>;
+    fn set_channel_eq_bands(&self, channel_id: &str, bands: Vec<EqBandInfo>) -> ZbusResult<()>;
+    fn get_eq_presets(&self) -> ZbusResult<Vec<String>>;
+    fn add_channel_plugin(&self, channel_id: &str, plugin_id: &str) -> ZbusResult<String>;
+    fn remove_channel_plugin(&self, channel_id: &str, slot: u32) -> ZbusResult<()>;
+    fn move_channel_plugin(&self, channel_id: &str, from_slot: u32, to_slot: u32)
+        -> ZbusResult<()>;
+    fn set_channel_plugin_bypassed(
+        &self,
+        channel_id: &str,
+        slot: u32,
+        bypassed: bool,
+    ) -> ZbusResult<()>;
+    fn set_channel_plugin_parameter(
+        &self,
+        channel_id: &str,
+        slot: u32,
+        param_index: u32,
+        value: f64,
+    ) -> ZbusResult<()>;
+    fn get_channel_plugins(&self, channel_id: &str) -> ZbusResult<Vec<PluginSlotInfo>>;
+    fn set_master_volume(&self, volume_db: f64) -> ZbusResult<()>;
+    fn set_master_mute(&self, muted: bool) -> ZbusResult<()>;
+    fn assign_app(&self, app_id: &str, channel_id: &str) -> ZbusResult<()>;
+    fn unassign_app(&self, app_id: &str, channel_id: &str) -> ZbusResult<()>;
+    fn set_channel_output(&self, channel_id: &str, device_name: &str) -> ZbusResult<()>;
+    fn set_master_output(&self, device_name: &str) -> ZbusResult<()>;
+    fn set_master_recording(&self, enabled: bool) -> ZbusResult<()>;
+    fn set_channel_solo(&self, channel_id: &str, solo: bool) -> ZbusResult<()>;
+    fn clear_solo(&self) -> ZbusResult<()>;
+    fn set_channel_sidetone(
+        &self,
+        channel_id: &str,
+        enabled: bool,
+        volume_db: f64,
+    ) -> ZbusResult<()>;
+    fn get_monitor(&self) -> ZbusResult<MonitorInfo>;
+    fn set_monitor_device(&self, device_name: &str) -> ZbusResult<()>;
+    fn set_monitor_volume(&self, volume_db: f64) -> ZbusResult<()>;
+    fn set_monitor_mute(&self, muted: bool) -> ZbusResult<()>;
+    fn set_solo_mode(&self, mode: &str) -> ZbusResult<()>;
+    fn reset_loudness(&self, channel_id: &str) -> ZbusResult<()>;
+    fn set_spectrum(&self, target: &str, tap: &str) -> ZbusResult<()>;
+    fn get_channels(&self) -> ZbusResult<Vec<ChannelInfo>>;
+    fn get_apps(&self) -> ZbusResult<Vec<AppInfo>>;
+    fn get_outputs(&self) -> ZbusResult<Vec<OutputInfo>>;
+    fn get_inputs(&self) -> ZbusResult<Vec<InputInfo>>;
+    fn get_master_volume(&self) -> ZbusResult<f64>;
+    fn get_master_muted(&self) -> ZbusResult<bool>;
+    fn get_master_output(&self) -> ZbusResult<String>;
+    fn get_connected(&self) -> ZbusResult<bool>;
+    fn get_master_recording_enabled(&self) -> ZbusResult<bool>;
+    fn get_routing_rules(&self) -> ZbusResult<Vec<RoutingRuleInfo>>;
+    fn set_routing_rule(&self, rule: RoutingRuleInfo) -> ZbusResult<()>;
+    fn delete_routing_rule(&self, rule_id: &str) -> ZbusResult<()>;
+    fn toggle_routing_rule(&self, rule_id: &str) -> ZbusResult<()>;
+    fn get_ducking(&self) -> ZbusResult<Vec<DuckingInfo>>;
+    fn set_ducking(&self, ducking: DuckingInfo) -> ZbusResult<String>;
+    fn delete_ducking(&self, ducking_id: &str) -> ZbusResult<()>;
+    fn list_scenes(&self) -> ZbusResult<Vec<String>>;
+    fn save_scene(&self, name: &str) -> ZbusResult<()>;
+    fn recall_scene(&self, name: &str, fade_ms: u32) -> ZbusResult<()>;
+    fn delete_scene(&self, name: &str) -> ZbusResult<()>;
+    fn start_recording(&self, sources: Vec<String>, format: &str) -> ZbusResult<String>;
+    fn stop_recording(&self, recording_id: &str) -> ZbusResult<RecordingStatus>;
+    fn get_recordings(&self) -> ZbusResult<Vec<RecordingStatus>>;
+    fn get_recording_directory(&self) -> ZbusResult<String>;
+    fn set_recording_directory(&self, directory: &str) -> ZbusResult<()>;
+    fn set_channel_replay(&self, channel_id: &str, enabled: bool) -> ZbusResult<()>;
+    fn get_replay_buffers(&self) -> ZbusResult<Vec<ReplayBufferInfo>>;
+    fn get_replay_length(&self) -> ZbusResult<u32>;
+    fn set_replay_length(&self, seconds: u32) -> ZbusResult<()>;
+    fn save_replay(
+        &self,
+        channel_ids: Vec<String>,
+        seconds: u32,
+        format: &str,
+    ) -> ZbusResult<Vec<String>>;
+    fn get_midi_devices(&self) -> ZbusResult<Vec<String>>;
+    fn get_midi_bindings(&self) -> ZbusResult<Vec<MidiBindingInfo>>;
+    fn set_midi_binding(&self, binding: MidiBindingInfo) -> ZbusResult<String>;
+    fn delete_midi_binding(&self, binding_id: &str) -> ZbusResult<()>;
+    fn start_midi_learn(&self, binding: MidiBindingInfo) -> ZbusResult<()>;
+    fn cancel_midi_learn(&self) -> ZbusResult<()>;
+
+    // Signals
+    #[zbus(signal)]
+    fn channel_added(&self, channel: ChannelInfo) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn channel_removed(&self, channel_id: &str) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn channel_updated(&self, channel: ChannelInfo) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn volume_changed(&self, channel_id: &str, volume_db: f64) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn mute_changed(&self, channel_id: &str, muted: bool) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn app_discovered(&self, app: AppInfo) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn app_removed(&self, app_id: &str) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn app_routed(&self, app_id: &str, channel_id: &str) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn app_unrouted(&self, app_id: &str, channel_id: &str) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn connection_changed(&self, connected: bool) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn error_occurred(&self, message: &str) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn meter_update(&self, data: Vec<MeterData>) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn loudness_update(&self, data: Vec<LoudnessData>) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn spectrum_update(&self, data: SpectrumData) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn master_volume_changed(&self, volume_db: f64) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn master_mute_changed(&self, muted: bool) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn outputs_changed(&self) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn inputs_changed(&self) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn recording_progress(&self, status: RecordingStatus) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn midi_binding_learned(&self, binding: MidiBindingInfo) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn solo_changed(&self, channel_id: &str, solo: bool) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn channel_talking(&self, channel_id: &str, talking: bool) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn channel_live_changed(&self, channel_id: &str, live: bool) -> ZbusResult<()>;
+    #[zbus(signal)]
+    fn monitor_changed(&self, monitor: MonitorInfo) -> ZbusResult<()>;
+}
@@ -40,13 +40,30 @@
 //!     // ... implement other methods
 //! }
 //! ```
+//!
+//! # Events
+//!
+//! Since API 0.2 the host delivers time-stamped [`PluginEvent`]s (parameter
+//! changes, MIDI notes and controllers) and a [`TransportInfo`] block with
+//! each buffer through [`AudioEffect::process_events`]. Plugins that don't
+//! override it get their parameter changes applied at the right sample by
+//! [`process_split`] and never see MIDI.
+//!
+//! # Parameter text
+//!
+//! Since API 0.3 plugins can format parameter values for display
+//! ([`AudioEffect::parameter_to_text`]), parse what the user types
+//! ([`AudioEffect::parameter_from_text`]) and name discrete values
+//! ([`AudioEffect::parameter_value_labels`]). The defaults build on
+//! [`ParameterInfo`] through [`format_parameter_value`] and
+//! [`parse_parameter_text`], which hosts also use for older plugins.
 
 #![warn(missing_docs)]
 #![allow(non_local_definitions)]
 
 use abi_stable::{
     sabi_trait,
-    std_types::{RBox, ROption, RResult, RSlice, RSliceMut, RString, RVec},
+    std_types::{RBox, ROption, RResult, RSlice, RSliceMut, RStr, RString, RVec},
     StableAbi,
 };
 use serde::{Deserialize, Serialize};
@@ -55,7 +72,21 @@ use serde::{Deserialize, Serialize};
 /// Increment MAJOR for breaking changes, MINOR for additions.
 pub const API_VERSION_MAJOR: u32 = 0;
 /// Minor API version.
-pub const API_VERSION_MINOR: u32 = 1;
+pub const API_VERSION_MINOR: u32 = 3;
+
+/// First minor version whose plugins have [`AudioEffect::process_events`].
+///
+/// Plugins built against an older minor version are driven through
+/// [`process_split`] by the host instead.
+pub const EVENTS_API_VERSION_MINOR: u32 = 2;
+
+/// First minor version whose plugins have [`AudioEffect::parameter_to_text`],
+/// [`AudioEffect::parameter_from_text`] and
+/// [`AudioEffect::parameter_value_labels`].
+///
+/// For older plugins the host formats and parses values itself with
+/// [`format_parameter_value`] and [`parse_parameter_text`].
+pub const PARAMETER_TEXT_API_VERSION_MINOR: u32 = 3;
 
 // ============================================================================
 // Plugin Metadata
@@ -260,9 +291,32 @@ impl ParameterInfo {
     }
 }
 
+/// A named value of a discrete parameter, such as one mode of an enumeration.
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, StableAbi)]
+pub struct ParameterValueLabel {
+    /// Value in the parameter's range (not normalized), like
+    /// [`ParameterInfo::default`].
+    pub value: f32,
+
+    /// Text shown for the value.
+    pub label: RString,
+}
+
+impl ParameterValueLabel {
+    /// Create a new value label.
+    pub fn new(value: f32, label: &str) -> Self {
+        Self {
+            value,
+            label: label.into(),
+        }
+    }
+}
+
 /// Parameter value scaling/curve type.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, StableAbi, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, StableAbi, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ParameterCurve {
     /// Linear mapping from min to max.
     #[default]
@@ -319,6 +373,149 @@ impl std::fmt::Display for PluginError {
 
 impl std::error::Error for PluginError {}
 
+// ============================================================================
+// Events and Transport
+// ============================================================================
+
+/// What a [`PluginEvent`] carries.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, StableAbi)]
+pub enum PluginEventKind {
+    /// Set a parameter from this sample on.
+    ParameterChange {
+        /// Parameter index.
+        index: u32,
+        /// New value (normalized 0.0-1.0).
+        value: f32,
+    },
+    /// MIDI note on.
+    NoteOn {
+        /// MIDI channel, 0-15.
+        channel: u8,
+        /// Note number, 0-127.
+        note: u8,
+        /// Velocity, 1-127.
+        velocity: u8,
+    },
+    /// MIDI note off (including note on with velocity 0).
+    NoteOff {
+        /// MIDI channel, 0-15.
+        channel: u8,
+        /// Note number, 0-127.
+        note: u8,
+        /// Release velocity, 0-127.
+        velocity: u8,
+    },
+    /// MIDI control change.
+    ControlChange {
+        /// MIDI channel, 0-15.
+        channel: u8,
+        /// Controller number, 0-127.
+        controller: u8,
+        /// Controller value, 0-127.
+        value: u8,
+    },
+}
+
+/// An event that takes effect at a given sample of the current block.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, StableAbi)]
+pub struct PluginEvent {
+    /// Frame within the block (0-based) at which the event happens.
+    pub sample_offset: u32,
+    /// The event itself.
+    pub kind: PluginEventKind,
+}
+
+impl PluginEvent {
+    /// A parameter change at `sample_offset`.
+    pub fn parameter_change(sample_offset: u32, index: u32, value: f32) -> Self {
+        Self {
+            sample_offset,
+            kind: PluginEventKind::ParameterChange { index, value },
+        }
+    }
+
+    /// Decode a raw MIDI channel message. Only note on, note off and
+    /// control change are represented; anything else returns `None`.
+    pub fn from_midi(sample_offset: u32, bytes: &[u8]) -> Option<Self> {
+        let [status, data1, data2, ..] = *bytes else {
+            return None;
+        };
+        let channel = status & 0x0F;
+        let (data1, data2) = (data1 & 0x7F, data2 & 0x7F);
+        let kind = match status & 0xF0 {
+            0x80 => PluginEventKind::NoteOff {
+                channel,
+                note: data1,
+                velocity: data2,
+            },
+            0x90 if data2 == 0 => PluginEventKind::NoteOff {
+                channel,
+                note: data1,
+                velocity: 0,
+            },
+            0x90 => PluginEventKind::NoteOn {
+                channel,
+                note: data1,
+                velocity: data2,
+            },
+            0xB0 => PluginEventKind::ControlChange {
+                channel,
+                controller: data1,
+                value: data2,
+            },
+            _ => return None,
+        };
+        Some(Self {
+            sample_offset,
+            kind,
+        })
+    }
+}
+
+/// Host transport and tempo at the first sample of the current block.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, StableAbi)]
+pub struct TransportInfo {
+    /// Whether the transport is running.
+    pub playing: bool,
+    /// Tempo in beats (quarter notes) per minute.
+    pub tempo_bpm: f64,
+    /// Time signature numerator (beats per bar).
+    pub time_signature_numerator: u32,
+    /// Time signature denominator (note value of one beat).
+    pub time_signature_denominator: u32,
+    /// Position in samples since the transport started.
+    pub position_samples: u64,
+    /// Position in quarter notes since the transport started.
+    pub position_beats: f64,
+}
+
+impl Default for TransportInfo {
+    fn default() -> Self {
+        Self {
+            playing: false,
+            tempo_bpm: 120.0,
+            time_signature_numerator: 4,
+            time_signature_denominator: 4,
+            position_samples: 0,
+            position_beats: 0.0,
+        }
+    }
+}
+
+impl TransportInfo {
+    /// Move the position forward by one block, if playing.
+    pub fn advance(&mut self, frames: u32, sample_rate: f32) {
+        if !self.playing || sample_rate <= 0.0 {
+            return;
+        }
+        self.position_samples += frames as u64;
+        self.position_beats += frames as f64 / sample_rate as f64 * self.tempo_bpm / 60.0;
+    }
+}
+
 // ============================================================================
 // Audio Effect Trait
 // ============================================================================
@@ -398,9 +595,157 @@ pub trait AudioEffect: Send + Sync {
     }
 
     /// Get tail length in samples (reverb/delay tail).
+    ///
+    /// Methods after this one were added in later minor versions and may be
+    /// missing from plugins built against older ones.
+    #[sabi(last_prefix_field)]
     fn tail_length(&self) -> u32 {
         0
     }
+
+    /// Process audio together with the events that fall inside this block.
+    ///
+    /// `events` are sorted by `sample_offset`, and every offset is below the
+    /// block length. The default implementation hands the block to
+    /// [`process_split`], which applies parameter changes at their offsets
+    /// and drops MIDI events.
+    ///
+    /// Added in API 0.2; the host only calls it on plugins whose
+    /// [`PluginEntry`] reports [`EVENTS_API_VERSION_MINOR`] or later.
+    ///
+    /// # Real-time Safety
+    /// This method MUST be real-time safe. See trait documentation.
+    fn process_events(
+        &mut self,
+        inputs: RSlice<RSlice<f32>>,
+        outputs: RSliceMut<RSliceMut<f32>>,
+        events: RSlice<PluginEvent>,
+        transport: &TransportInfo,
+    ) {
+        let _ = transport;
+        process_split(self, inputs, outputs, events);
+    }
+
+    /// Format a parameter value (normalized 0.0-1.0) for display, without
+    /// the unit.
+    ///
+    /// The default implementation uses [`format_parameter_value`] with this
+    /// plugin's [`ParameterInfo`] and value labels.
+    ///
+    /// Added in API 0.3; the host only calls it on plugins whose
+    /// [`PluginEntry`] reports [`PARAMETER_TEXT_API_VERSION_MINOR`] or later.
+    fn parameter_to_text(&self, index: u32, value: f32) -> RString {
+        match self.parameter_info(index) {
+            ROption::RSome(info) => {
+                let labels = self.parameter_value_labels(index);
+                format_parameter_value(&info, &labels, value).into()
+            }
+            ROption::RNone => format!("{:.2}", value).into(),
+        }
+    }
+
+    /// Parse text entered by the user into a parameter value (normalized
+    /// 0.0-1.0). Returns `RNone` if the text isn't understood.
+    ///
+    /// The default implementation uses [`parse_parameter_text`] with this
+    /// plugin's [`ParameterInfo`] and value labels.
+    ///
+    /// Added in API 0.3, like [`AudioEffect::parameter_to_text`].
+    fn parameter_from_text(&self, index: u32, text: RStr) -> ROption<f32> {
+        match self.parameter_info(index) {
+            ROption::RSome(info) => {
+                let labels = self.parameter_value_labels(index);
+                parse_parameter_text(&info, &labels, text.as_str()).into()
+            }
+            ROption::RNone => ROption::RNone,
+        }
+    }
+
+    /// Named values of a parameter, e.g. the modes of an enumeration, in
+    /// display order. Empty (the default) for continuous parameters.
+    ///
+    /// Added in API 0.3, like [`AudioEffect::parameter_to_text`].
+    fn parameter_value_labels(&self, index: u32) -> RVec<ParameterValueLabel> {
+        let _ = index;
+        RVec::new()
+    }
+}
+
+/// Channel count up to which [`process_split`] can split a block without
+/// allocating. Wider plugins get every parameter change at the block start.
+const MAX_SPLIT_CHANNELS: usize = 16;
+
+/// Process a block with [`AudioEffect::process`], splitting it at every
+/// parameter change so each one lands on its sample.
+///
+/// MIDI events are ignored. This is the default behavior of
+/// [`AudioEffect::process_events`], and what hosts use for plugins built
+/// before events existed.
+pub fn process_split<P: AudioEffect + ?Sized>(
+    plugin: &mut P,
+    inputs: RSlice<RSlice<f32>>,
+    mut outputs: RSliceMut<RSliceMut<f32>>,
+    events: RSlice<PluginEvent>,
+) {
+    let apply = |plugin: &mut P, event: &PluginEvent| {
+        if let PluginEventKind::ParameterChange { index, value } = event.kind {
+            plugin.set_parameter(index, value);
+        }
+    };
+
+    if inputs.len() > MAX_SPLIT_CHANNELS || outputs.len() > MAX_SPLIT_CHANNELS {
+        for event in events.iter() {
+            apply(plugin, event);
+        }
+        plugin.process(inputs, outputs);
+        return;
+    }
+
+    let (num_in, num_out) = (inputs.len(), outputs.len());
+    let frames = inputs
+        .first()
+        .map(|c| c.len())
+        .or_else(|| outputs.first().map(|c| c.len()))
+        .unwrap_or(0);
+    let mut next = 0;
+    let mut start = 0;
+    while start < frames {
+        while let Some(event) = events.get(next) {
+            if event.sample_offset as usize > start {
+                break;
+            }
+            apply(plugin, event);
+            next += 1;
+        }
+        let end = events[next..]
+            .iter()
+            .find(|e| matches!(e.kind, PluginEventKind::ParameterChange { .. }))
+            .map_or(frames, |e| (e.sample_offset as usize).min(frames));
+
+        let ins: [RSlice<f32>; MAX_SPLIT_CHANNELS] = std::array::from_fn(|c| {
+            RSlice::from_slice(
+                inputs
+                    .get(c)
+                    .and_then(|ch| ch.get(start..end))
+                    .unwrap_or_default(),
+            )
+        });
+        let mut outs: [RSliceMut<f32>; MAX_SPLIT_CHANNELS] =
+            std::array::from_fn(|_| RSliceMut::from_mut_slice(&mut []));
+        for (slot, ch) in outs.iter_mut().zip(outputs.iter_mut()) {
+            *slot = RSliceMut::from_mut_slice(ch.get_mut(start..end).unwrap_or_default());
+        }
+        plugin.process(
+            RSlice::from_slice(&ins[..num_in]),
+            RSliceMut::from_mut_slice(&mut outs[..num_out]),
+        );
+        start = end;
+    }
+
+    // Changes at or past the end of the block still take effect
+    for event in &events[next..] {
+        apply(plugin, event);
+    }
 }
 
 /// Type alias for boxed plugin instance.
@@ -418,7 +763,9 @@ pub type PluginFactoryFn = extern "C" fn() -> PluginBox;
 /// Plugin entry point structure for native plugins.
 ///
 /// Native plugins must export a function `sootmix_plugin_entry` that returns
-/// this structure.
+/// this structure. The version fields record the API the plugin was built
+/// against; the host uses the minor version to decide which optional trait
+/// methods (see [`EVENTS_API_VERSION_MINOR`]) it may call.
 ///
 /// Note: This struct is #[repr(C)] but not StableAbi because function pointers
 /// are handled separately. The ABI stability is ensured by:
@@ -435,6 +782,19 @@ pub struct PluginEntry {
     pub create: PluginFactoryFn,
 }
 
+impl PluginEntry {
+    /// Whether the plugin was built with [`AudioEffect::process_events`].
+    pub fn supports_events(&self) -> bool {
+        self.api_version_minor >= EVENTS_API_VERSION_MINOR
+    }
+
+    /// Whether the plugin was built with the parameter text methods
+    /// ([`AudioEffect::parameter_to_text`] and friends).
+    pub fn supports_parameter_text(&self) -> bool {
+        self.api_version_minor >= PARAMETER_TEXT_API_VERSION_MINOR
+    }
+}
+
 /// Macro to declare a native plugin entry point.
 ///
 /// # Example
@@ -489,11 +849,26 @@ macro_rules! declare_plugin {
 /// Module for WASM plugin interface definitions.
 ///
 /// WASM plugins communicate via a simplified interface using flat memory
-/// layouts and exported functions.
+/// layouts and exported functions. The host wraps a module in an
+/// [`AudioEffect`](crate::AudioEffect), so WASM and native plugins look the
+/// same to the rest of SootMix.
+///
+/// # Sandbox
+///
+/// Modules must not import anything: no WASI, no host functions. Each
+/// instance runs with a memory cap and a fuel (instruction) budget per call;
+/// exceeding either traps the instance and the host passes audio through
+/// unprocessed until the plugin is re-activated.
 pub mod wasm {
+    use crate::{ParameterCurve, ParameterInfo, PluginCategory, PluginInfo};
+    use serde::{Deserialize, Serialize};
+
     /// WASM plugin must export these functions:
     ///
-    /// - `plugin_info() -> *const u8` - Returns pointer to JSON plugin info
+    /// - `plugin_info() -> *const u8` - Returns pointer to NUL-terminated
+    ///   UTF-8 JSON encoding a [`WasmPluginInfo`]
+    /// - `plugin_alloc(bytes: u32) -> *mut u8` - Reserves memory for the host's
+    ///   audio buffers; returns 0 on failure
     /// - `plugin_activate(sample_rate: f32, max_block_size: u32)`
     /// - `plugin_deactivate()`
     /// - `plugin_process(input_ptr: *const f32, output_ptr: *mut f32, frames: u32)`
@@ -502,9 +877,16 @@ pub mod wasm {
     /// - `plugin_set_parameter(index: u32, value: f32)`
     /// - `plugin_reset()`
     ///
-    /// Memory allocation is handled by the WASM runtime.
+    /// Memory allocation is handled by the WASM runtime. The module must also
+    /// export its linear memory as [`MEMORY_EXPORT`].
+    ///
+    /// After `plugin_activate` the host calls `plugin_alloc` twice, once for
+    /// inputs and once for outputs. Buffers are planar: channel `c` starts at
+    /// `ptr + c * max_block_size * 4` and holds `frames` samples. Parameter
+    /// values are normalized (0.0-1.0), as in `AudioEffect`.
     pub const REQUIRED_EXPORTS: &[&str] = &[
         "plugin_info",
+        "plugin_alloc",
         "plugin_activate",
         "plugin_deactivate",
         "plugin_process",
@@ -513,6 +895,88 @@ pub mod wasm {
         "plugin_set_parameter",
         "plugin_reset",
     ];
+
+    /// Name of the exported linear memory.
+    pub const MEMORY_EXPORT: &str = "memory";
+
+    /// Optional export: `plugin_latency() -> u32`, in samples.
+    pub const LATENCY_EXPORT: &str = "plugin_latency";
+
+    /// Plugin metadata as returned (as JSON) by `plugin_info`.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct WasmPluginInfo {
+        /// Unique identifier (reverse domain notation recommended).
+        pub id: String,
+        /// Human-readable name.
+        pub name: String,
+        /// Plugin vendor/author.
+        #[serde(default)]
+        pub vendor: String,
+        /// Version string.
+        #[serde(default)]
+        pub version: String,
+        /// Plugin category.
+        #[serde(default)]
+        pub category: PluginCategory,
+        /// Number of input audio channels.
+        #[serde(default = "default_channels")]
+        pub input_channels: u32,
+        /// Number of output audio channels.
+        #[serde(default = "default_channels")]
+        pub output_channels: u32,
+        /// Parameters, in index order.
+        #[serde(default)]
+        pub parameters: Vec<WasmParameterInfo>,
+    }
+
+    fn default_channels() -> u32 {
+        2
+    }
+
+    impl WasmPluginInfo {
+        /// Convert to the host's plugin metadata.
+        pub fn to_plugin_info(&self) -> PluginInfo {
+            PluginInfo::new(&self.id, &self.name)
+                .with_vendor(&self.vendor)
+                .with_version(&self.version)
+                .with_category(self.category)
+                .with_channels(self.input_channels, self.output_channels)
+        }
+    }
+
+    /// Parameter metadata within [`WasmPluginInfo`].
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct WasmParameterInfo {
+        /// Internal identifier for automation/presets.
+        pub id: String,
+        /// Display name.
+        pub name: String,
+        /// Unit label (e.g., "dB", "Hz", "%").
+        #[serde(default)]
+        pub unit: String,
+        /// Minimum value.
+        pub min: f32,
+        /// Maximum value.
+        pub max: f32,
+        /// Default value.
+        pub default: f32,
+        /// Value curve/scaling.
+        #[serde(default)]
+        pub curve: ParameterCurve,
+        /// Step size for discrete parameters (0.0 for continuous).
+        #[serde(default)]
+        pub step: f32,
+    }
+
+    impl WasmParameterInfo {
+        /// Convert to the host's parameter metadata.
+        pub fn to_parameter_info(&self, index: u32) -> ParameterInfo {
+            ParameterInfo::new(index, &self.id, &self.name, self.min, self.max, self.default)
+                .with_unit(&self.unit)
+                .with_curve(self.curve)
+                .with_step(self.step)
+        }
+    }
 }
 
 // ============================================================================
@@ -571,6 +1035,76 @@ pub fn denormalize(normalized: f32, min: f32, max: f32, curve: ParameterCurve) -
     }
 }
 
+/// Format a parameter value (normalized 0.0-1.0) for display, without the
+/// unit.
+///
+/// A value matching one of `labels` is shown as that label. Otherwise the
+/// value is denormalized and printed as a number: whole numbers for
+/// parameters with an integer step, fewer decimals for large values.
+pub fn format_parameter_value(
+    info: &ParameterInfo,
+    labels: &[ParameterValueLabel],
+    value: f32,
+) -> String {
+    let plain = denormalize(value, info.min, info.max, info.curve);
+
+    let tolerance = if info.step > 0.0 {
+        info.step / 2.0
+    } else {
+        (info.max - info.min).abs() * 1e-4
+    };
+    if let Some(label) = labels.iter().find(|l| (l.value - plain).abs() <= tolerance) {
+        return label.label.to_string();
+    }
+
+    let decimals = if info.step >= 1.0 && info.step.fract() == 0.0 {
+        0
+    } else if plain.abs() >= 100.0 {
+        1
+    } else {
+        2
+    };
+    format!("{:.*}", decimals, plain)
+}
+
+/// Parse text entered by the user into a parameter value (normalized
+/// 0.0-1.0).
+///
+/// Accepts one of `labels` (case-insensitive) or a number in the parameter's
+/// range, optionally followed by its unit. Numbers are clamped to the range
+/// and snapped to the step of discrete parameters.
+pub fn parse_parameter_text(
+    info: &ParameterInfo,
+    labels: &[ParameterValueLabel],
+    text: &str,
+) -> Option<f32> {
+    let text = text.trim();
+    if let Some(label) = labels
+        .iter()
+        .find(|l| l.label.as_str().eq_ignore_ascii_case(text))
+    {
+        return Some(normalize(label.value, info.min, info.max, info.curve));
+    }
+
+    let unit = info.unit.as_str();
+    let number = text
+        .len()
+        .checked_sub(unit.len())
+        .filter(|_| !unit.is_empty())
+        .and_then(|end| Some((text.get(..end)?, text.get(end..)?)))
+        .filter(|(_, suffix)| suffix.eq_ignore_ascii_case(unit))
+        .map_or(text, |(number, _)| number.trim_end());
+
+    let mut plain: f32 = number.parse().ok()?;
+    if !plain.is_finite() {
+        return None;
+    }
+    if info.step > 0.0 {
+        plain = info.min + ((plain - info.min) / info.step).round() * info.step;
+    }
+    Some(normalize(plain, info.min, info.max, info.curve))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,4 +1131,162 @@ mod tests {
         let denormalized = denormalize(normalized, 0.0, 100.0, ParameterCurve::Linear);
         assert!((denormalized - original).abs() < 0.0001);
     }
+
+    /// Records the block sizes it processes and the gain in effect for each.
+    #[derive(Default)]
+    struct BlockRecorder {
+        gain: f32,
+        blocks: Vec<(usize, f32)>,
+    }
+
+    impl AudioEffect for BlockRecorder {
+        fn info(&self) -> PluginInfo {
+            PluginInfo::new("test.recorder", "Recorder")
+        }
+        fn activate(&mut self, _context: ActivationContext) {}
+        fn deactivate(&mut self) {}
+        fn process(&mut self, inputs: RSlice<RSlice<f32>>, mut outputs: RSliceMut<RSliceMut<f32>>) {
+            self.blocks.push((inputs[0].len(), self.gain));
+            for (input, output) in inputs.iter().zip(outputs.iter_mut()) {
+                for (i, o) in input.iter().zip(output.iter_mut()) {
+                    *o = *i * self.gain;
+                }
+            }
+        }
+        fn parameter_count(&self) -> u32 {
+            1
+        }
+        fn parameter_info(&self, _index: u32) -> ROption<ParameterInfo> {
+            ROption::RNone
+        }
+        fn get_parameter(&self, _index: u32) -> f32 {
+            self.gain
+        }
+        fn set_parameter(&mut self, _index: u32, value: f32) {
+            self.gain = value;
+        }
+        fn save_state(&self) -> RVec<u8> {
+            RVec::new()
+        }
+        fn load_state(&mut self, _data: RSlice<u8>) -> RResult<(), PluginError> {
+            RResult::ROk(())
+        }
+        fn reset(&mut self) {}
+    }
+
+    #[test]
+    fn test_process_split_applies_changes_on_their_sample() {
+        let mut plugin = BlockRecorder::default();
+        let input = [1.0f32; 8];
+        let mut output = [0.0f32; 8];
+        let events = [
+            PluginEvent::parameter_change(0, 0, 1.0),
+            PluginEvent::from_midi(2, &[0x90, 60, 100]).unwrap(),
+            PluginEvent::parameter_change(3, 0, 0.5),
+            PluginEvent::parameter_change(6, 0, 0.25),
+        ];
+
+        {
+            let inputs = [RSlice::from_slice(&input[..])];
+            let mut outputs = [RSliceMut::from_mut_slice(&mut output[..])];
+            process_split(
+                &mut plugin,
+                RSlice::from_slice(&inputs),
+                RSliceMut::from_mut_slice(&mut outputs),
+                RSlice::from_slice(&events),
+            );
+        }
+
+        assert_eq!(plugin.blocks, vec![(3, 1.0), (3, 0.5), (2, 0.25)]);
+        assert_eq!(output, [1.0, 1.0, 1.0, 0.5, 0.5, 0.5, 0.25, 0.25]);
+    }
+
+    #[test]
+    fn test_midi_decoding() {
+        assert_eq!(
+            PluginEvent::from_midi(5, &[0x93, 64, 90]),
+            Some(PluginEvent {
+                sample_offset: 5,
+                kind: PluginEventKind::NoteOn {
+                    channel: 3,
+                    note: 64,
+                    velocity: 90,
+                },
+            })
+        );
+        // Note on with velocity 0 is a note off
+        assert!(matches!(
+            PluginEvent::from_midi(0, &[0x90, 64, 0]).unwrap().kind,
+            PluginEventKind::NoteOff { velocity: 0, .. }
+        ));
+        assert!(matches!(
+            PluginEvent::from_midi(0, &[0xB0, 7, 127]).unwrap().kind,
+            PluginEventKind::ControlChange {
+                controller: 7,
+                value: 127,
+                ..
+            }
+        ));
+        assert_eq!(PluginEvent::from_midi(0, &[0xE0, 0, 64]), None);
+        assert_eq!(PluginEvent::from_midi(0, &[0xC0, 5]), None);
+    }
+
+    fn gain_info() -> ParameterInfo {
+        ParameterInfo::new(0, "gain", "Gain", -24.0, 24.0, 0.0).with_unit("dB")
+    }
+
+    fn mode_labels() -> Vec<ParameterValueLabel> {
+        vec![
+            ParameterValueLabel::new(0.0, "Peak"),
+            ParameterValueLabel::new(1.0, "RMS"),
+            ParameterValueLabel::new(2.0, "Hybrid"),
+        ]
+    }
+
+    #[test]
+    fn test_format_parameter_value() {
+        let gain = gain_info();
+        assert_eq!(format_parameter_value(&gain, &[], 0.75), "12.00");
+
+        let freq = ParameterInfo::new(1, "freq", "Frequency", 20.0, 20000.0, 1000.0)
+            .with_curve(ParameterCurve::Logarithmic);
+        assert_eq!(format_parameter_value(&freq, &[], 1.0), "20000.0");
+
+        let mode = ParameterInfo::new(2, "mode", "Mode", 0.0, 2.0, 0.0).with_step(1.0);
+        assert_eq!(format_parameter_value(&mode, &mode_labels(), 0.5), "RMS");
+        assert_eq!(format_parameter_value(&mode, &[], 0.5), "1");
+    }
+
+    #[test]
+    fn test_parse_parameter_text() {
+        let gain = gain_info();
+        assert_eq!(parse_parameter_text(&gain, &[], " 12 dB"), Some(0.75));
+        assert_eq!(parse_parameter_text(&gain, &[], "12db"), Some(0.75));
+        assert_eq!(parse_parameter_text(&gain, &[], "-12"), Some(0.25));
+        assert_eq!(parse_parameter_text(&gain, &[], "99"), Some(1.0));
+        assert_eq!(parse_parameter_text(&gain, &[], "loud"), None);
+        assert_eq!(parse_parameter_text(&gain, &[], "NaN"), None);
+
+        let mode = ParameterInfo::new(2, "mode", "Mode", 0.0, 2.0, 0.0).with_step(1.0);
+        assert_eq!(
+            parse_parameter_text(&mode, &mode_labels(), "hybrid"),
+            Some(1.0)
+        );
+        assert_eq!(
+            parse_parameter_text(&mode, &mode_labels(), "0.8"),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn test_transport_advance() {
+        let mut transport = TransportInfo::default();
+        transport.advance(48000, 48000.0);
+        assert_eq!(transport.position_samples, 0);
+
+        transport.playing = true;
+        transport.advance(24000, 48000.0);
+        assert_eq!(transport.position_samples, 24000);
+        assert!((transport.position_beats - 1.0).abs() < 1e-9);
+    }
 }
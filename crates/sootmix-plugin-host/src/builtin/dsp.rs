@@ -34,11 +34,6 @@ impl OnePoleSmooth {
         }
     }
 
-    /// Set the smoothing time.
-    pub fn set_time(&mut self, time_sec: f32, sample_rate: f32) {
-        self.coeff = time_to_coeff(time_sec, sample_rate);
-    }
-
     /// Process one sample toward the target value.
     #[inline]
     pub fn process(&mut self, target: f32) -> f32 {
@@ -50,12 +45,6 @@ impl OnePoleSmooth {
     pub fn set(&mut self, value: f32) {
         self.current = value;
     }
-
-    /// Get the current smoothed value.
-    #[inline]
-    pub fn value(&self) -> f32 {
-        self.current
-    }
 }
 
 /// Biquad filter coefficients (normalized).
@@ -158,7 +147,7 @@ mod tests {
         for _ in 0..48000 {
             smoother.process(1.0);
         }
-        assert!((smoother.value() - 1.0).abs() < 0.001);
+        assert!((smoother.current - 1.0).abs() < 0.001);
     }
 
     #[test]
@@ -9,7 +9,7 @@ use abi_stable::std_types::{ROption, RResult, RSlice, RSliceMut, RVec};
 use serde::{Deserialize, Serialize};
 use sootmix_plugin_api::{
     ActivationContext, ParameterCurve, ParameterInfo, PluginCategory, PluginError, PluginInfo,
-    db_to_linear, linear_to_db,
+    db_to_linear,
 };
 
 const PARAM_THRESHOLD: u32 = 0;
@@ -211,7 +211,7 @@ impl sootmix_plugin_api::AudioEffect for NoiseGatePlugin {
                 }
                 GateState::Closing => {
                     // Ramp gain down using release coefficient
-                    self.gain = release_coeff * self.gain;
+                    self.gain *= release_coeff;
                     if level >= open_thresh {
                         self.state = GateState::Opening;
                     } else if self.gain <= range_gain + 0.001 {
@@ -5,12 +5,13 @@
 //! LV2 plugin adapter implementing the AudioEffect trait.
 
 use super::{Lv2PluginMeta, Lv2World};
-use crate::plugins::PluginLoadError;
+use crate::PluginLoadError;
 use abi_stable::std_types::{ROption, RResult, RSlice, RSliceMut, RString, RVec};
 use lilv::instance::ActiveInstance;
 use lilv::plugin::Plugin;
 use sootmix_plugin_api::{
-    ActivationContext, AudioEffect, ParameterCurve, ParameterInfo, PluginError, PluginInfo,
+    ActivationContext, AudioEffect, ParameterCurve, ParameterInfo, ParameterValueLabel,
+    PluginError, PluginInfo,
 };
 use std::sync::Arc;
 use tracing::{debug, warn};
@@ -314,26 +315,51 @@ impl AudioEffect for Lv2PluginAdapter {
             None => return ROption::RNone,
         };
 
-        let curve = if port.logarithmic {
+        // Same mapping as normalize_value: log only for strictly positive ranges
+        let curve = if port.logarithmic && port.min > 0.0 {
             ParameterCurve::Logarithmic
         } else {
             ParameterCurve::Linear
         };
 
+        let step = if port.integer || port.toggled || port.enumeration {
+            1.0
+        } else {
+            0.0
+        };
+
         ROption::RSome(ParameterInfo {
             index,
             id: RString::from(port.symbol.as_str()),
             name: RString::from(port.name.as_str()),
-            unit: RString::new(),
-            min: 0.0,  // Normalized
-            max: 1.0,  // Normalized
-            default: self.normalize_value(index as usize, port.default),
+            unit: RString::from(port.unit.as_str()),
+            min: port.min,
+            max: port.max,
+            default: port.default,
             curve,
-            step: 0.0,
+            step,
             hint: sootmix_plugin_api::ParameterHint::None,
         })
     }
 
+    fn parameter_value_labels(&self, index: u32) -> RVec<ParameterValueLabel> {
+        let Some(port) = self.meta.control_ports.get(index as usize) else {
+            return RVec::new();
+        };
+
+        if port.scale_points.is_empty() && port.toggled {
+            return RVec::from(vec![
+                ParameterValueLabel::new(port.min, "Off"),
+                ParameterValueLabel::new(port.max, "On"),
+            ]);
+        }
+
+        port.scale_points
+            .iter()
+            .map(|(value, label)| ParameterValueLabel::new(*value, label))
+            .collect()
+    }
+
     fn get_parameter(&self, index: u32) -> f32 {
         let idx = index as usize;
         if idx < self.control_values.len() {
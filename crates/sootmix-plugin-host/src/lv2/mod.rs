@@ -14,14 +14,12 @@
 //! - `Lv2PluginAdapter` - Wraps LV2 instance to implement AudioEffect trait
 //! - `Lv2PluginLoader` - Handles scanning and loading LV2 plugins
 
-#![allow(dead_code)]
-
 mod adapter;
 mod scanner;
 mod world;
 
 pub use adapter::Lv2PluginAdapter;
-pub use scanner::Lv2PluginMeta;
+pub use scanner::{Lv2PluginMeta, Lv2PortInfo, Lv2PortType};
 pub use world::Lv2World;
 
 use super::{PluginLoadError, PluginResult};
@@ -94,9 +92,9 @@ impl Lv2PluginLoader {
 
         // Add standard paths
         for path in LV2_SEARCH_PATHS {
-            let expanded = if path.starts_with("~/") {
+            let expanded = if let Some(rest) = path.strip_prefix("~/") {
                 if let Some(home) = dirs::home_dir() {
-                    home.join(&path[2..])
+                    home.join(rest)
                 } else {
                     PathBuf::from(path)
                 }
@@ -4,8 +4,6 @@
 
 //! LV2 plugin scanning and metadata extraction.
 
-#![allow(dead_code)]
-
 use super::Lv2World;
 use sootmix_plugin_api::PluginCategory;
 use std::sync::Arc;
@@ -53,6 +51,16 @@ pub struct Lv2PortInfo {
     pub default: f32,
     /// Whether the port uses logarithmic scale.
     pub logarithmic: bool,
+    /// Unit label from `units:unit` (empty if none or unknown).
+    pub unit: String,
+    /// Whether the port only takes whole numbers (`lv2:integer`).
+    pub integer: bool,
+    /// Whether the port is an on/off switch (`lv2:toggled`).
+    pub toggled: bool,
+    /// Whether the port only takes its scale point values (`lv2:enumeration`).
+    pub enumeration: bool,
+    /// Named values (`lv2:scalePoint`) as `(value, label)`, sorted by value.
+    pub scale_points: Vec<(f32, String)>,
     /// Whether this is an input (true) or output (false) port.
     pub is_input: bool,
 }
@@ -81,6 +89,10 @@ pub fn scan_plugins(world: &Arc<Lv2World>) -> Vec<Lv2PluginMeta> {
     let input_port_uri = inner.new_uri("http://lv2plug.in/ns/lv2core#InputPort");
     let output_port_uri = inner.new_uri("http://lv2plug.in/ns/lv2core#OutputPort");
     let log_property_uri = inner.new_uri("http://lv2plug.in/ns/ext/port-props#logarithmic");
+    let integer_property_uri = inner.new_uri("http://lv2plug.in/ns/lv2core#integer");
+    let toggled_property_uri = inner.new_uri("http://lv2plug.in/ns/lv2core#toggled");
+    let enumeration_property_uri = inner.new_uri("http://lv2plug.in/ns/lv2core#enumeration");
+    let unit_uri = inner.new_uri("http://lv2plug.in/ns/extensions/units#unit");
 
     let mut result = Vec::new();
 
@@ -164,6 +176,21 @@ pub fn scan_plugins(world: &Arc<Lv2World>) -> Vec<Lv2PluginMeta> {
                 // Check for logarithmic property
                 let logarithmic = port.has_property(&log_property_uri);
 
+                let unit = port
+                    .get(&unit_uri)
+                    .and_then(|u| u.as_uri().map(unit_label))
+                    .unwrap_or_default();
+
+                let mut scale_points: Vec<(f32, String)> = port
+                    .scale_points()
+                    .filter_map(|point| {
+                        let value = point.value().as_float()?;
+                        let label = point.label().as_str()?.to_string();
+                        value.is_finite().then_some((value, label))
+                    })
+                    .collect();
+                scale_points.sort_by(|a, b| a.0.total_cmp(&b.0));
+
                 control_ports.push(Lv2PortInfo {
                     index: port_index,
                     symbol,
@@ -173,6 +200,11 @@ pub fn scan_plugins(world: &Arc<Lv2World>) -> Vec<Lv2PluginMeta> {
                     max,
                     default,
                     logarithmic,
+                    unit,
+                    integer: port.has_property(&integer_property_uri),
+                    toggled: port.has_property(&toggled_property_uri),
+                    enumeration: port.has_property(&enumeration_property_uri),
+                    scale_points,
                     is_input: true,
                 });
             }
@@ -203,6 +235,32 @@ pub fn scan_plugins(world: &Arc<Lv2World>) -> Vec<Lv2PluginMeta> {
     result
 }
 
+/// Map an LV2 units ontology URI (e.g. `units:db`) to a unit label.
+fn unit_label(uri: &str) -> String {
+    let name = uri.rsplit('#').next().unwrap_or(uri);
+    match name {
+        "db" => "dB",
+        "hz" => "Hz",
+        "khz" => "kHz",
+        "mhz" => "MHz",
+        "ms" => "ms",
+        "s" => "s",
+        "min" => "min",
+        "pc" => "%",
+        "bpm" => "BPM",
+        "cent" => "ct",
+        "semitone12TET" => "st",
+        "oct" => "oct",
+        "degree" => "°",
+        "frame" => "frames",
+        "beat" => "beats",
+        "bar" => "bars",
+        "coef" => "",
+        other => other,
+    }
+    .to_string()
+}
+
 /// Map LV2 plugin class to SootMix category.
 fn map_lv2_class_to_category(class: Option<&str>) -> PluginCategory {
     let class = match class {
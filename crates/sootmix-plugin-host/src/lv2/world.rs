@@ -8,9 +8,7 @@
 //! must outlive all plugin instances. We use a global singleton pattern with
 //! reference counting to ensure proper lifetime management.
 
-#![allow(dead_code)]
-
-use crate::plugins::PluginLoadError;
+use crate::PluginLoadError;
 use lilv::World;
 use std::sync::{Arc, OnceLock};
 use tracing::{debug, info};
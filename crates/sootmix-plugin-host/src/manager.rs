@@ -9,7 +9,13 @@ use super::{builtin::{self, BuiltinRegistry}, native::{NativePluginLoader, check
 use super::lv2::Lv2PluginLoader;
 #[cfg(feature = "vst3-plugins")]
 use super::vst3::Vst3PluginLoader;
-use sootmix_plugin_api::{ActivationContext, PluginBox, PluginInfo};
+#[cfg(feature = "wasm-plugins")]
+use super::wasm::WasmPluginLoader;
+use sootmix_plugin_api::{
+    format_parameter_value, parse_parameter_text, process_split, ActivationContext,
+    ParameterValueLabel, PluginBox, PluginEvent, PluginInfo, TransportInfo, API_VERSION_MINOR,
+    EVENTS_API_VERSION_MINOR, PARAMETER_TEXT_API_VERSION_MINOR,
+};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -161,6 +167,9 @@ impl PluginRegistry {
     }
 }
 
+/// Events that can wait for the next block before new ones are dropped.
+const EVENT_QUEUE_CAPACITY: usize = 512;
+
 /// A loaded and active plugin instance.
 pub struct PluginInstance {
     /// Unique instance ID.
@@ -175,6 +184,12 @@ pub struct PluginInstance {
     sample_rate: f32,
     /// Source path of the loaded library (for unloading from NativePluginLoader).
     source_path: PathBuf,
+    /// Plugin API minor version the plugin was built against.
+    api_version_minor: u32,
+    /// Events for upcoming blocks, sorted by offset from the next block's start.
+    events: Vec<PluginEvent>,
+    /// Free-running transport handed to the plugin with every block.
+    transport: TransportInfo,
 }
 
 impl PluginInstance {
@@ -188,9 +203,18 @@ impl PluginInstance {
             activated: false,
             sample_rate: 48000.0,
             source_path,
+            api_version_minor: API_VERSION_MINOR,
+            events: Vec::with_capacity(EVENT_QUEUE_CAPACITY),
+            transport: TransportInfo::default(),
         }
     }
 
+    /// Record the API minor version a native plugin was built against.
+    pub(crate) fn with_api_version_minor(mut self, minor: u32) -> Self {
+        self.api_version_minor = minor;
+        self
+    }
+
     /// Get plugin info.
     pub fn info(&self) -> PluginInfo {
         self.plugin.info()
@@ -210,6 +234,11 @@ impl PluginInstance {
         self.plugin.activate(context);
         self.sample_rate = sample_rate;
         self.activated = true;
+        // The mixer has no timeline: the transport runs from activation on
+        self.transport = TransportInfo {
+            playing: true,
+            ..TransportInfo::default()
+        };
 
         debug!(
             "Plugin {} activated (sr={}, block={})",
@@ -260,7 +289,40 @@ impl PluginInstance {
             .collect();
         let outputs_slice = RSliceMut::from_mut_slice(&mut outputs_r);
 
-        self.plugin.process(inputs_slice, outputs_slice);
+        // Events due in this block; later ones wait, shifted to the next block
+        let frames = inputs.first().map_or(0, |c| c.len());
+        let due = self
+            .events
+            .partition_point(|e| (e.sample_offset as usize) < frames);
+        let events = RSlice::from_slice(&self.events[..due]);
+
+        if self.api_version_minor >= EVENTS_API_VERSION_MINOR {
+            self.plugin
+                .process_events(inputs_slice, outputs_slice, events, &self.transport);
+        } else {
+            process_split(&mut self.plugin, inputs_slice, outputs_slice, events);
+        }
+
+        self.events.drain(..due);
+        for event in &mut self.events {
+            event.sample_offset -= frames as u32;
+        }
+        self.transport.advance(frames as u32, self.sample_rate);
+    }
+
+    /// Queue an event for the plugin. `sample_offset` counts from the start
+    /// of the next block processed.
+    ///
+    /// Returns false (dropping the event) if the queue is full.
+    pub fn queue_event(&mut self, event: PluginEvent) -> bool {
+        if self.events.len() >= EVENT_QUEUE_CAPACITY {
+            return false;
+        }
+        let index = self
+            .events
+            .partition_point(|e| e.sample_offset <= event.sample_offset);
+        self.events.insert(index, event);
+        true
     }
 
     /// Get parameter count.
@@ -286,13 +348,46 @@ impl PluginInstance {
     /// Reset the plugin state.
     pub fn reset(&mut self) {
         self.plugin.reset();
+        self.events.clear();
     }
 
     /// Get plugin latency in samples.
     pub fn latency(&self) -> u32 {
         self.plugin.latency()
     }
-}
+
+    fn has_parameter_text(&self) -> bool {
+        self.api_version_minor >= PARAMETER_TEXT_API_VERSION_MINOR
+    }
+
+    /// Get the named values of a parameter (empty for continuous ones).
+    pub fn parameter_value_labels(&self, index: u32) -> Vec<ParameterValueLabel> {
+        if self.has_parameter_text() {
+            self.plugin.parameter_value_labels(index).into_vec()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Format a normalized parameter value for display, without the unit.
+    pub fn parameter_to_text(&self, index: u32, value: f32) -> String {
+        if self.has_parameter_text() {
+            return self.plugin.parameter_to_text(index, value).into_string();
+        }
+        match self.parameter_info(index) {
+            Some(info) => format_parameter_value(&info, &[], value),
+            None => format!("{:.2}", value),
+        }
+    }
+
+    /// Parse user-entered text into a normalized parameter value.
+    pub fn parameter_from_text(&self, index: u32, text: &str) -> Option<f32> {
+        if self.has_parameter_text() {
+            return self.plugin.parameter_from_text(index, text.into()).into();
+        }
+        let info = self.parameter_info(index)?;
+        parse_parameter_text(&info, &[], text)
+    }}
 
 impl Drop for PluginInstance {
     fn drop(&mut self) {
@@ -300,6 +395,16 @@ impl Drop for PluginInstance {
     }
 }
 
+impl std::fmt::Debug for PluginInstance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginInstance")
+            .field("id", &self.id)
+            .field("plugin_type", &self.metadata.plugin_type)
+            .field("activated", &self.activated)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Thread-safe plugin instances storage.
 ///
 /// Used to share plugin instances between UI thread and RT audio thread.
@@ -323,6 +428,9 @@ pub struct PluginManager {
     /// VST3 plugin loader.
     #[cfg(feature = "vst3-plugins")]
     vst3_loader: Vst3PluginLoader,
+    /// WASM plugin loader.
+    #[cfg(feature = "wasm-plugins")]
+    wasm_loader: Option<WasmPluginLoader>,
     /// Active plugin instances (thread-safe).
     instances: SharedPluginInstances,
     /// Default sample rate for activation.
@@ -351,6 +459,16 @@ impl PluginManager {
             }
         };
 
+        // Initialize WASM loader
+        #[cfg(feature = "wasm-plugins")]
+        let wasm_loader = match WasmPluginLoader::new() {
+            Ok(loader) => Some(loader),
+            Err(e) => {
+                warn!("Failed to initialize WASM loader: {}", e);
+                None
+            }
+        };
+
         Self {
             registry: Arc::new(RwLock::new(registry)),
             native_loader: NativePluginLoader::new(),
@@ -358,6 +476,8 @@ impl PluginManager {
             lv2_loader,
             #[cfg(feature = "vst3-plugins")]
             vst3_loader: Vst3PluginLoader::new(),
+            #[cfg(feature = "wasm-plugins")]
+            wasm_loader,
             instances: Arc::new(Mutex::new(HashMap::new())),
             sample_rate: 48000.0,
             block_size: 512,
@@ -502,9 +622,20 @@ impl PluginManager {
 
         let plugin = match plugin_type {
             PluginType::Native => self.native_loader.load(path)?,
+            #[cfg(feature = "wasm-plugins")]
+            PluginType::Wasm => {
+                if let Some(ref wasm_loader) = self.wasm_loader {
+                    wasm_loader.load(path)?
+                } else {
+                    return Err(PluginLoadError::WasmInstantiation(
+                        "WASM loader not initialized".to_string(),
+                    ));
+                }
+            }
+            #[cfg(not(feature = "wasm-plugins"))]
             PluginType::Wasm => {
                 return Err(PluginLoadError::Initialization(
-                    "WASM plugins not yet implemented".to_string(),
+                    "SootMix was built without WASM plugin support".to_string(),
                 ));
             }
             PluginType::Builtin => {
@@ -536,6 +667,9 @@ impl PluginManager {
         };
 
         let mut instance = PluginInstance::new(metadata, plugin);
+        if let Some(minor) = self.native_loader.api_version_minor(path) {
+            instance = instance.with_api_version_minor(minor);
+        }
 
         // Activate with current audio parameters
         instance.activate(self.sample_rate, self.block_size);
@@ -559,6 +693,16 @@ impl PluginManager {
         self.load_from_path(Path::new(class_id), PluginType::Vst3)
     }
 
+    /// Log and drop the WASM instances that trapped on the audio thread.
+    /// Call periodically from the main loop. Returns how many there were.
+    pub fn collect_faults(&self) -> usize {
+        #[cfg(feature = "wasm-plugins")]
+        if let Some(ref wasm_loader) = self.wasm_loader {
+            return wasm_loader.collect_faults();
+        }
+        0
+    }
+
     /// Unload a plugin instance.
     ///
     /// Removes the instance first (dropping the PluginBox), then unloads the
@@ -581,10 +725,8 @@ impl PluginManager {
         };
 
         // Now safe to unload the library since the PluginBox has been dropped
-        if plugin_type == PluginType::Native {
-            if self.native_loader.unload(&source_path) {
-                debug!("Unloaded native library: {:?}", source_path);
-            }
+        if plugin_type == PluginType::Native && self.native_loader.unload(&source_path) {
+            debug!("Unloaded native library: {:?}", source_path);
         }
 
         true
@@ -611,6 +753,16 @@ impl PluginManager {
         instances.get(&id).and_then(|i| i.parameter_info(index))
     }
 
+    /// Total latency in samples reported by a chain of plugin instances.
+    pub fn chain_latency(&self, chain: &[Uuid]) -> u32 {
+        let instances = self.instances.lock();
+        chain
+            .iter()
+            .filter_map(|id| instances.get(id))
+            .map(|i| i.latency())
+            .sum()
+    }
+
     /// Get parameter value for a plugin instance.
     pub fn get_parameter(&self, id: Uuid, index: u32) -> Option<f32> {
         let instances = self.instances.lock();
@@ -631,6 +783,29 @@ impl PluginManager {
             .unwrap_or_default()
     }
 
+    /// Get the named values of a plugin instance's parameter.
+    pub fn get_parameter_labels(&self, id: Uuid, index: u32) -> Vec<ParameterValueLabel> {
+        let instances = self.instances.lock();
+        instances
+            .get(&id)
+            .map(|i| i.parameter_value_labels(index))
+            .unwrap_or_default()
+    }
+
+    /// Format a normalized parameter value as the plugin displays it.
+    pub fn format_parameter(&self, id: Uuid, index: u32, value: f32) -> Option<String> {
+        let instances = self.instances.lock();
+        instances.get(&id).map(|i| i.parameter_to_text(index, value))
+    }
+
+    /// Parse text into a normalized parameter value the way the plugin does.
+    pub fn parse_parameter(&self, id: Uuid, index: u32, text: &str) -> Option<f32> {
+        let instances = self.instances.lock();
+        instances
+            .get(&id)
+            .and_then(|i| i.parameter_from_text(index, text))
+    }
+
     /// Set parameter value for a plugin instance.
     ///
     /// This acquires a lock. For RT-safe parameter updates, use
@@ -642,6 +817,27 @@ impl PluginManager {
         }
     }
 
+    /// Queue an event for a plugin instance's next block.
+    pub fn queue_event(&self, id: Uuid, event: PluginEvent) -> bool {
+        let mut instances = self.instances.lock();
+        instances
+            .get_mut(&id)
+            .is_some_and(|instance| instance.queue_event(event))
+    }
+
+    /// Queue an event for every plugin instance.
+    pub fn queue_event_all(&self, event: PluginEvent) {
+        let mut instances = self.instances.lock();
+        for instance in instances.values_mut() {
+            if !instance.queue_event(event) {
+                debug!(
+                    "Event queue full for plugin {}, dropping event",
+                    instance.id
+                );
+            }
+        }
+    }
+
     /// Execute a function with access to a plugin instance.
     ///
     /// This provides safe access without exposing references outside the lock scope.
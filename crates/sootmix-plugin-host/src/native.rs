@@ -9,7 +9,9 @@
 
 use super::{PluginLoadError, PluginResult};
 use libloading::{Library, Symbol};
-use sootmix_plugin_api::{PluginBox, PluginEntry, API_VERSION_MAJOR, API_VERSION_MINOR};
+use sootmix_plugin_api::{
+    PluginBox, PluginEntry, API_VERSION_MAJOR, API_VERSION_MINOR, EVENTS_API_VERSION_MINOR,
+};
 use std::collections::HashMap;
 use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
@@ -56,6 +58,8 @@ pub(super) fn check_plugin_permissions(path: &Path) -> PluginResult<()> {
 pub struct NativePluginLoader {
     /// Loaded libraries (kept alive to prevent unloading).
     libraries: HashMap<PathBuf, Library>,
+    /// API minor version each loaded library was built against.
+    api_versions: HashMap<PathBuf, u32>,
 }
 
 impl NativePluginLoader {
@@ -63,6 +67,7 @@ impl NativePluginLoader {
     pub fn new() -> Self {
         Self {
             libraries: HashMap::new(),
+            api_versions: HashMap::new(),
         }
     }
 
@@ -116,11 +121,20 @@ impl NativePluginLoader {
             );
         }
 
+        if !entry.supports_events() {
+            debug!(
+                "Plugin {:?} predates API 0.{}; events are limited to parameter changes",
+                path, EVENTS_API_VERSION_MINOR
+            );
+        }
+
         // Create the plugin instance
         let plugin = (entry.create)();
 
         // Store the library to keep it loaded
         self.libraries.insert(path.to_path_buf(), library);
+        self.api_versions
+            .insert(path.to_path_buf(), entry.api_version_minor);
 
         let info = plugin.info();
         info!(
@@ -135,6 +149,7 @@ impl NativePluginLoader {
     ///
     /// Note: The plugin instances must be dropped first!
     pub fn unload(&mut self, path: &Path) -> bool {
+        self.api_versions.remove(path);
         if self.libraries.remove(path).is_some() {
             debug!("Unloaded library: {:?}", path);
             true
@@ -148,6 +163,11 @@ impl NativePluginLoader {
         self.libraries.contains_key(path)
     }
 
+    /// API minor version a loaded library was built against.
+    pub fn api_version_minor(&self, path: &Path) -> Option<u32> {
+        self.api_versions.get(path).copied()
+    }
+
     /// Get number of loaded libraries.
     pub fn loaded_count(&self) -> usize {
         self.libraries.len()
@@ -5,10 +5,11 @@
 //! VST3 plugin adapter implementing the AudioEffect trait.
 
 use super::{Vst3Module, Vst3PluginMeta};
-use crate::plugins::PluginLoadError;
-use abi_stable::std_types::{ROption, RResult, RSlice, RSliceMut, RString, RVec};
+use crate::PluginLoadError;
+use abi_stable::std_types::{ROption, RResult, RSlice, RSliceMut, RStr, RString, RVec};
 use sootmix_plugin_api::{
-    ActivationContext, AudioEffect, ParameterCurve, ParameterInfo, PluginError, PluginInfo,
+    format_parameter_value, parse_parameter_text, ActivationContext, AudioEffect, ParameterCurve,
+    ParameterInfo, ParameterValueLabel, PluginError, PluginInfo,
 };
 use std::sync::Arc;
 use tracing::{debug, warn};
@@ -20,6 +21,13 @@ use vst3::Steinberg::Vst::{
 };
 use vst3::Steinberg::{kResultOk, IPluginBaseTrait};
 
+/// `ParameterInfo::flags` bit for parameters whose steps are a list of
+/// named values (`kIsList`).
+const PARAM_IS_LIST: i32 = 1 << 3;
+
+/// Largest list parameter whose values are all enumerated as labels.
+const MAX_LIST_LABELS: i32 = 128;
+
 /// Adapter that wraps a VST3 plugin to implement AudioEffect.
 pub struct Vst3PluginAdapter {
     /// Reference to the VST3 module (must outlive components).
@@ -108,6 +116,16 @@ impl Vst3PluginAdapter {
             audio_out_buffers: Vec::new(),
         })
     }
+
+    /// Text the plugin shows for a normalized parameter value.
+    fn param_string(&self, index: u32, value: f32) -> Option<String> {
+        let controller = self.controller.as_ref()?;
+        let &param_id = self.parameter_ids.get(index as usize)?;
+
+        let mut text = [0u16; 128];
+        let result = unsafe { controller.getParamStringByValue(param_id, value as f64, &mut text) };
+        (result == kResultOk).then(|| utf16_to_string(&text))
+    }
 }
 
 impl AudioEffect for Vst3PluginAdapter {
@@ -260,16 +278,12 @@ impl AudioEffect for Vst3PluginAdapter {
         let mut input_bus: AudioBusBuffers = unsafe { std::mem::zeroed() };
         input_bus.numChannels = self.meta.audio_inputs as i32;
         input_bus.silenceFlags = 0;
-        unsafe {
-            input_bus.__field0.channelBuffers32 = in_ptrs.as_mut_ptr();
-        }
+        input_bus.__field0.channelBuffers32 = in_ptrs.as_mut_ptr();
 
         let mut output_bus: AudioBusBuffers = unsafe { std::mem::zeroed() };
         output_bus.numChannels = self.meta.audio_outputs as i32;
         output_bus.silenceFlags = 0;
-        unsafe {
-            output_bus.__field0.channelBuffers32 = out_ptrs.as_mut_ptr();
-        }
+        output_bus.__field0.channelBuffers32 = out_ptrs.as_mut_ptr();
 
         // Setup process data
         let mut process_data = ProcessData {
@@ -343,6 +357,59 @@ impl AudioEffect for Vst3PluginAdapter {
         })
     }
 
+    fn parameter_to_text(&self, index: u32, value: f32) -> RString {
+        match self.param_string(index, value) {
+            Some(text) => text.into(),
+            None => match self.parameter_info(index) {
+                ROption::RSome(info) => format_parameter_value(&info, &[], value).into(),
+                ROption::RNone => format!("{:.2}", value).into(),
+            },
+        }
+    }
+
+    fn parameter_from_text(&self, index: u32, text: RStr) -> ROption<f32> {
+        if let (Some(controller), Some(&param_id)) =
+            (&self.controller, self.parameter_ids.get(index as usize))
+        {
+            let mut utf16: Vec<u16> = text.as_str().trim().encode_utf16().collect();
+            utf16.push(0);
+            let mut value = 0.0f64;
+            let result = unsafe {
+                controller.getParamValueByString(param_id, utf16.as_mut_ptr(), &mut value)
+            };
+            if result == kResultOk && value.is_finite() {
+                return ROption::RSome(value.clamp(0.0, 1.0) as f32);
+            }
+        }
+
+        match self.parameter_info(index) {
+            ROption::RSome(info) => parse_parameter_text(&info, &[], text.as_str()).into(),
+            ROption::RNone => ROption::RNone,
+        }
+    }
+
+    fn parameter_value_labels(&self, index: u32) -> RVec<ParameterValueLabel> {
+        let Some(controller) = self.controller.as_ref() else {
+            return RVec::new();
+        };
+
+        let mut info: Vst3ParameterInfo = unsafe { std::mem::zeroed() };
+        if unsafe { controller.getParameterInfo(index as i32, &mut info) } != kResultOk
+            || info.flags & PARAM_IS_LIST == 0
+            || !(1..=MAX_LIST_LABELS).contains(&info.stepCount)
+        {
+            return RVec::new();
+        }
+
+        (0..=info.stepCount)
+            .filter_map(|step| {
+                let value = step as f32 / info.stepCount as f32;
+                let label = self.param_string(index, value)?;
+                Some(ParameterValueLabel::new(value, &label))
+            })
+            .collect()
+    }
+
     fn get_parameter(&self, index: u32) -> f32 {
         if let (Some(controller), Some(&param_id)) =
             (&self.controller, self.parameter_ids.get(index as usize))
@@ -422,7 +489,7 @@ impl AudioEffect for Vst3PluginAdapter {
 
     fn latency(&self) -> u32 {
         if let Some(ref processor) = self.processor {
-            unsafe { processor.getLatencySamples() as u32 }
+            unsafe { processor.getLatencySamples() }
         } else {
             0
         }
@@ -430,7 +497,7 @@ impl AudioEffect for Vst3PluginAdapter {
 
     fn tail_length(&self) -> u32 {
         if let Some(ref processor) = self.processor {
-            unsafe { processor.getTailSamples() as u32 }
+            unsafe { processor.getTailSamples() }
         } else {
             0
         }
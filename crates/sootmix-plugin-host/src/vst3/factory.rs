@@ -4,7 +4,7 @@
 
 //! VST3 module loading and factory management.
 
-use crate::plugins::PluginLoadError;
+use crate::PluginLoadError;
 use libloading::Library;
 use std::path::{Path, PathBuf};
 use tracing::debug;
@@ -17,8 +17,6 @@ use vst3::Steinberg::{
 
 /// A loaded VST3 module (.vst3 bundle).
 pub struct Vst3Module {
-    /// Path to the .vst3 bundle.
-    bundle_path: PathBuf,
     /// The loaded shared library.
     #[allow(dead_code)]
     library: Library,
@@ -82,7 +80,6 @@ impl Vst3Module {
         };
 
         Ok(Self {
-            bundle_path: bundle_path.to_path_buf(),
             library,
             factory,
         })
@@ -134,16 +131,6 @@ impl Vst3Module {
         ))
     }
 
-    /// Get the bundle path.
-    pub fn bundle_path(&self) -> &Path {
-        &self.bundle_path
-    }
-
-    /// Get the plugin factory.
-    pub fn factory(&self) -> &ComPtr<IPluginFactory> {
-        &self.factory
-    }
-
     /// Get the number of classes in the factory.
     pub fn class_count(&self) -> i32 {
         unsafe { self.factory.countClasses() }
@@ -185,8 +172,8 @@ impl Vst3Module {
         // createInstance takes FIDString (pointer to char8) for both cid and iid
         let result = unsafe {
             self.factory.createInstance(
-                class_id.as_ptr() as *const i8,
-                IComponent_iid.as_ptr() as *const i8,
+                class_id.as_ptr(),
+                IComponent_iid.as_ptr(),
                 &mut component,
             )
         };
@@ -228,18 +215,3 @@ pub fn tuid_to_string(tuid: &TUID) -> String {
         .collect::<Vec<_>>()
         .join("")
 }
-
-/// Parse a hex string to a VST3 TUID.
-pub fn string_to_tuid(s: &str) -> Option<TUID> {
-    if s.len() != 32 {
-        return None;
-    }
-
-    let mut tuid: TUID = [0i8; 16];
-    for (i, chunk) in s.as_bytes().chunks(2).enumerate() {
-        let hex_str = std::str::from_utf8(chunk).ok()?;
-        tuid[i] = u8::from_str_radix(hex_str, 16).ok()? as i8;
-    }
-
-    Some(tuid)
-}
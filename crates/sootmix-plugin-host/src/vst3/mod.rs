@@ -126,9 +126,9 @@ impl Vst3PluginLoader {
         let mut paths = Vec::new();
 
         for path in VST3_SEARCH_PATHS {
-            let expanded = if path.starts_with("~/") {
+            let expanded = if let Some(rest) = path.strip_prefix("~/") {
                 if let Some(home) = std::env::var("HOME").ok().map(PathBuf::from) {
-                    home.join(&path[2..])
+                    home.join(rest)
                 } else {
                     PathBuf::from(path)
                 }
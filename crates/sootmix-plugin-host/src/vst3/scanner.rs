@@ -5,7 +5,7 @@
 //! VST3 plugin scanning and metadata extraction.
 
 use super::factory::{tuid_to_string, Vst3Module};
-use crate::plugins::PluginLoadError;
+use crate::PluginLoadError;
 use sootmix_plugin_api::PluginCategory;
 use std::ffi::CStr;
 use std::path::{Path, PathBuf};
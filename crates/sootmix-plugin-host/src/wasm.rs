@@ -0,0 +1,729 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! WASM plugin loader using wasmtime.
+//!
+//! WASM plugins are `.wasm` modules implementing the export contract in
+//! `sootmix_plugin_api::wasm`. Each module is wrapped in a [`WasmEffect`] that
+//! implements `AudioEffect`, so the rest of the plugin system treats it like
+//! any other `PluginBox`.
+//!
+//! Unlike native plugins, a WASM plugin cannot touch host memory, files or the
+//! network: modules with imports are rejected outright. Every instance runs in
+//! its own store with a [`WasmBudget`]; running out of memory or fuel traps
+//! the instance, which is then dropped and the audio passed through until the
+//! plugin is re-activated. A trap in `process` happens on the RT thread, so
+//! the instance is queued back to the loader instead, and logged and dropped
+//! by [`WasmPluginLoader::collect_faults`].
+
+use super::native::check_plugin_permissions;
+use super::{PluginLoadError, PluginResult};
+use abi_stable::std_types::{ROption, RResult, RSlice, RSliceMut, RVec};
+use parking_lot::Mutex;
+use sootmix_plugin_api::wasm::{WasmPluginInfo, LATENCY_EXPORT, MEMORY_EXPORT, REQUIRED_EXPORTS};
+use sootmix_plugin_api::{
+    normalize, ActivationContext, AudioEffect, AudioEffect_TO, ParameterInfo, PluginBox,
+    PluginError, PluginInfo,
+};
+use sootmix_realtime::{RingBuffer, RingBufferReader, RingBufferWriter};
+use std::path::Path;
+use tracing::{debug, info, warn};
+use wasmtime::{
+    Config, Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+/// Longest `plugin_info` JSON the host will read.
+const MAX_INFO_LEN: usize = 64 * 1024;
+
+/// Trapped instances one plugin can queue before they're collected. An
+/// instance traps at most once per activation.
+const FAULT_QUEUE_CAPACITY: usize = 2;
+
+/// Resource budget enforced on every WASM plugin instance.
+///
+/// CPU is metered in wasmtime fuel, which is roughly one unit per executed
+/// WASM instruction. That makes the limit independent of machine speed and
+/// scheduling jitter, unlike a wall-clock timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmBudget {
+    /// Maximum linear memory per instance, in bytes.
+    pub max_memory_bytes: usize,
+    /// Fuel granted per audio frame in `plugin_process`.
+    pub fuel_per_frame: u64,
+    /// Fuel granted to every other call (activate, set_parameter, ...).
+    pub fuel_per_call: u64,
+}
+
+impl Default for WasmBudget {
+    fn default() -> Self {
+        Self {
+            max_memory_bytes: 16 * 1024 * 1024,
+            // A few thousand instructions per stereo frame is plenty for
+            // filters and dynamics, and caps a runaway plugin at a small
+            // fraction of one core at 48kHz.
+            fuel_per_frame: 5_000,
+            fuel_per_call: 10_000_000,
+        }
+    }
+}
+
+/// WASM plugin loader.
+///
+/// Owns the wasmtime engine shared by all WASM plugin instances, and the
+/// receiving end of each instance's fault queue.
+pub struct WasmPluginLoader {
+    engine: Engine,
+    budget: WasmBudget,
+    faults: Mutex<Vec<FaultQueue>>,
+}
+
+/// An instance that trapped in `process`, on its way off the RT thread.
+struct WasmFault {
+    error: wasmtime::Error,
+    runtime: WasmRuntime,
+}
+
+/// The loader's end of one plugin's fault queue.
+struct FaultQueue {
+    name: String,
+    faults: RingBufferReader<WasmFault>,
+}
+
+impl WasmPluginLoader {
+    /// Create a loader with the default budget.
+    pub fn new() -> PluginResult<Self> {
+        Self::with_budget(WasmBudget::default())
+    }
+
+    /// Create a loader whose instances run under `budget`.
+    pub fn with_budget(budget: WasmBudget) -> PluginResult<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        config.max_wasm_stack(512 * 1024);
+        let engine =
+            Engine::new(&config).map_err(|e| PluginLoadError::WasmInstantiation(e.to_string()))?;
+        Ok(Self {
+            engine,
+            budget,
+            faults: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Wrap `module` in an effect whose faults this loader collects.
+    fn effect(&self, module: Module) -> PluginResult<WasmEffect> {
+        let (writer, reader) = RingBuffer::new(FAULT_QUEUE_CAPACITY).split();
+        let effect = WasmEffect::new(module, self.budget, writer)?;
+        self.faults.lock().push(FaultQueue {
+            name: effect.info.name.clone(),
+            faults: reader,
+        });
+        Ok(effect)
+    }
+
+    /// Log and drop the instances that trapped while processing. Call
+    /// periodically, off the RT thread. Returns how many there were.
+    pub fn collect_faults(&self) -> usize {
+        let mut count = 0;
+        self.faults.lock().retain_mut(|queue| {
+            // Checked first: once the effect is gone it can't queue more
+            let abandoned = queue.faults.is_abandoned();
+            while let Some(WasmFault { error, runtime }) = queue.faults.pop() {
+                report_fault(&queue.name, &error);
+                drop(runtime);
+                count += 1;
+            }
+            !abandoned
+        });
+        count
+    }
+
+    /// Load a plugin from a `.wasm` file.
+    pub fn load(&self, path: &Path) -> PluginResult<PluginBox> {
+        if !path.exists() {
+            return Err(PluginLoadError::NotFound(path.to_path_buf()));
+        }
+
+        check_plugin_permissions(path)?;
+
+        debug!("Loading WASM plugin: {:?}", path);
+
+        let module = Module::from_file(&self.engine, path)
+            .map_err(|e| PluginLoadError::WasmInstantiation(e.to_string()))?;
+        let effect = self.effect(module)?;
+
+        info!(
+            "Loaded WASM plugin: {} v{} by {}",
+            effect.info.name, effect.info.version, effect.info.vendor
+        );
+
+        Ok(AudioEffect_TO::from_value(
+            effect,
+            abi_stable::sabi_trait::TD_Opaque,
+        ))
+    }
+}
+
+/// A live module instance with its own store and resource limits.
+struct WasmRuntime {
+    store: Store<StoreLimits>,
+    memory: Memory,
+    process: TypedFunc<(u32, u32, u32), ()>,
+    set_parameter: TypedFunc<(u32, f32), ()>,
+    reset: TypedFunc<(), ()>,
+    deactivate: TypedFunc<(), ()>,
+    input_ptr: usize,
+    output_ptr: usize,
+}
+
+impl WasmRuntime {
+    /// Instantiate `module` in a fresh store limited by `budget`.
+    fn instantiate(module: &Module, budget: &WasmBudget) -> wasmtime::Result<(Self, Instance)> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(budget.max_memory_bytes)
+            .instances(1)
+            .memories(1)
+            .tables(4)
+            .table_elements(64 * 1024)
+            .build();
+        let mut store = Store::new(module.engine(), limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(budget.fuel_per_call)?;
+
+        // No imports are provided, so a module that asks for any fails here.
+        let instance = Instance::new(&mut store, module, &[])?;
+        let memory = instance
+            .get_memory(&mut store, MEMORY_EXPORT)
+            .ok_or_else(|| wasmtime::Error::msg("module does not export its memory"))?;
+
+        let runtime = Self {
+            process: instance.get_typed_func(&mut store, "plugin_process")?,
+            set_parameter: instance.get_typed_func(&mut store, "plugin_set_parameter")?,
+            reset: instance.get_typed_func(&mut store, "plugin_reset")?,
+            deactivate: instance.get_typed_func(&mut store, "plugin_deactivate")?,
+            store,
+            memory,
+            input_ptr: 0,
+            output_ptr: 0,
+        };
+        Ok((runtime, instance))
+    }
+
+    /// Refill the fuel tank before a call.
+    fn refuel(&mut self, fuel: u64) -> wasmtime::Result<()> {
+        self.store.set_fuel(fuel)
+    }
+
+    /// Read the NUL-terminated string at `ptr`.
+    fn read_c_str(&self, ptr: u32) -> wasmtime::Result<String> {
+        let data = self.memory.data(&self.store);
+        let start = ptr as usize;
+        let tail = data
+            .get(start..)
+            .ok_or_else(|| wasmtime::Error::msg("string pointer out of bounds"))?;
+        let tail = &tail[..tail.len().min(MAX_INFO_LEN)];
+        let len = tail
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| wasmtime::Error::msg("string is not NUL-terminated"))?;
+        Ok(String::from_utf8(tail[..len].to_vec())?)
+    }
+}
+
+/// An `AudioEffect` backed by a sandboxed WASM module.
+pub struct WasmEffect {
+    module: Module,
+    budget: WasmBudget,
+    info: WasmPluginInfo,
+    /// Normalized parameter values, mirrored host-side so `get_parameter`
+    /// (which only has `&self`) never calls into the module.
+    params: Vec<f32>,
+    max_block_size: usize,
+    latency: u32,
+    /// `None` while deactivated or after the instance trapped.
+    runtime: Option<WasmRuntime>,
+    /// Where `process` sends an instance that trapped.
+    faults: RingBufferWriter<WasmFault>,
+    /// A trapped instance that found the fault queue full, dropped on the
+    /// next activation.
+    parked: Option<WasmFault>,
+}
+
+impl WasmEffect {
+    /// Validate `module` against the plugin contract and read its metadata.
+    fn new(
+        module: Module,
+        budget: WasmBudget,
+        faults: RingBufferWriter<WasmFault>,
+    ) -> PluginResult<Self> {
+        if let Some(import) = module.imports().next() {
+            return Err(PluginLoadError::WasmInstantiation(format!(
+                "WASM plugins may not import anything (found {}::{})",
+                import.module(),
+                import.name()
+            )));
+        }
+        let exports: Vec<&str> = module.exports().map(|e| e.name()).collect();
+        if let Some(missing) = REQUIRED_EXPORTS
+            .iter()
+            .chain(std::iter::once(&MEMORY_EXPORT))
+            .find(|name| !exports.contains(name))
+        {
+            return Err(PluginLoadError::EntryPointNotFound(missing.to_string()));
+        }
+
+        let info = Self::read_info(&module, &budget)
+            .map_err(|e| PluginLoadError::WasmInstantiation(e.to_string()))?;
+        let params = info
+            .parameters
+            .iter()
+            .map(|p| normalize(p.default, p.min, p.max, p.curve))
+            .collect();
+
+        Ok(Self {
+            module,
+            budget,
+            info,
+            params,
+            max_block_size: 0,
+            latency: 0,
+            runtime: None,
+            faults,
+            parked: None,
+        })
+    }
+
+    /// Instantiate the module once to fetch its `plugin_info` JSON.
+    fn read_info(module: &Module, budget: &WasmBudget) -> wasmtime::Result<WasmPluginInfo> {
+        let (mut runtime, instance) = WasmRuntime::instantiate(module, budget)?;
+        let plugin_info: TypedFunc<(), u32> =
+            instance.get_typed_func(&mut runtime.store, "plugin_info")?;
+        let ptr = plugin_info.call(&mut runtime.store, ())?;
+        let json = runtime.read_c_str(ptr)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Create and activate a fresh instance.
+    fn start(&mut self, context: &ActivationContext) -> wasmtime::Result<WasmRuntime> {
+        let (mut runtime, instance) = WasmRuntime::instantiate(&self.module, &self.budget)?;
+        let store = &mut runtime.store;
+
+        let activate: TypedFunc<(f32, u32), ()> =
+            instance.get_typed_func(&mut *store, "plugin_activate")?;
+        activate.call(&mut *store, (context.sample_rate, context.max_block_size))?;
+
+        let alloc: TypedFunc<u32, u32> = instance.get_typed_func(&mut *store, "plugin_alloc")?;
+        let channels = self.info.input_channels.max(self.info.output_channels) as usize;
+        let bytes = channels * context.max_block_size as usize * 4;
+        let bytes_u32 =
+            u32::try_from(bytes).map_err(|_| wasmtime::Error::msg("audio buffers too large"))?;
+        for ptr in [&mut runtime.input_ptr, &mut runtime.output_ptr] {
+            runtime.store.set_fuel(self.budget.fuel_per_call)?;
+            let p = alloc.call(&mut runtime.store, bytes_u32)? as usize;
+            if p == 0 || p + bytes > runtime.memory.data_size(&runtime.store) {
+                return Err(wasmtime::Error::msg("plugin_alloc returned an unusable buffer"));
+            }
+            *ptr = p;
+        }
+
+        self.latency = match instance.get_typed_func::<(), u32>(&mut runtime.store, LATENCY_EXPORT)
+        {
+            Ok(latency) => {
+                runtime.refuel(self.budget.fuel_per_call)?;
+                latency.call(&mut runtime.store, ())?
+            }
+            Err(_) => 0,
+        };
+
+        // Restore parameter values the host already knows about.
+        for (index, value) in self.params.iter().enumerate() {
+            runtime.refuel(self.budget.fuel_per_call)?;
+            runtime
+                .set_parameter
+                .call(&mut runtime.store, (index as u32, *value))?;
+        }
+
+        Ok(runtime)
+    }
+
+    /// Drop the instance after a trap or budget overrun. Not for the RT
+    /// thread; see [`Self::send_fault`].
+    fn fault(&mut self, error: wasmtime::Error) {
+        report_fault(&self.info.name, &error);
+        self.runtime = None;
+    }
+
+    /// Bypass the instance after a trap in `process`, handing it back to the
+    /// loader so its memory is unmapped and the error logged off the RT
+    /// thread.
+    fn send_fault(&mut self, error: wasmtime::Error) {
+        let Some(runtime) = self.runtime.take() else {
+            return;
+        };
+        let fault = WasmFault { error, runtime };
+        // Nothing is parked: the instance was started after the last drop
+        if self.faults.is_full() {
+            self.parked = Some(fault);
+        } else {
+            self.faults.push(fault);
+        }
+    }
+
+    /// Process one block of at most `max_block_size` frames inside the sandbox.
+    fn process_block(
+        runtime: &mut WasmRuntime,
+        budget: &WasmBudget,
+        stride: usize,
+        inputs: &[RSlice<f32>],
+        outputs: &mut [RSliceMut<f32>],
+        offset: usize,
+        frames: usize,
+    ) -> wasmtime::Result<()> {
+        let data = runtime.memory.data_mut(&mut runtime.store);
+        for (ch, input) in inputs.iter().enumerate() {
+            let base = runtime.input_ptr + ch * stride * 4;
+            for (i, sample) in input[offset..offset + frames].iter().enumerate() {
+                data[base + i * 4..base + i * 4 + 4].copy_from_slice(&sample.to_le_bytes());
+            }
+        }
+
+        runtime.refuel(budget.fuel_per_frame * frames as u64 + budget.fuel_per_call / 1000)?;
+        runtime.process.call(
+            &mut runtime.store,
+            (
+                runtime.input_ptr as u32,
+                runtime.output_ptr as u32,
+                frames as u32,
+            ),
+        )?;
+
+        // The module may have grown (and so moved) its memory; look it up again.
+        let data = runtime.memory.data(&runtime.store);
+        for (ch, output) in outputs.iter_mut().enumerate() {
+            let base = runtime.output_ptr + ch * stride * 4;
+            for (i, sample) in output[offset..offset + frames].iter_mut().enumerate() {
+                let bytes = &data[base + i * 4..base + i * 4 + 4];
+                *sample = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Log a plugin whose instance trapped or overran its budget.
+fn report_fault(name: &str, error: &wasmtime::Error) {
+    warn!(
+        "WASM plugin '{}' stopped and is bypassed until re-activated: {}",
+        name, error
+    );
+}
+
+/// Copy inputs to outputs, silencing outputs that have no matching input.
+fn pass_through(inputs: &[RSlice<f32>], outputs: &mut [RSliceMut<f32>]) {
+    for (i, output) in outputs.iter_mut().enumerate() {
+        match inputs.get(i) {
+            Some(input) => {
+                let n = input.len().min(output.len());
+                output[..n].copy_from_slice(&input[..n]);
+            }
+            None => output.fill(0.0),
+        }
+    }
+}
+
+impl AudioEffect for WasmEffect {
+    fn info(&self) -> PluginInfo {
+        self.info.to_plugin_info()
+    }
+
+    fn activate(&mut self, context: ActivationContext) {
+        if let Some(fault) = self.parked.take() {
+            report_fault(&self.info.name, &fault.error);
+        }
+        self.runtime = None;
+        self.max_block_size = context.max_block_size as usize;
+        match self.start(&context) {
+            Ok(runtime) => self.runtime = Some(runtime),
+            Err(e) => self.fault(e),
+        }
+    }
+
+    fn deactivate(&mut self) {
+        if let Some(fault) = self.parked.take() {
+            report_fault(&self.info.name, &fault.error);
+        }
+        if let Some(mut runtime) = self.runtime.take() {
+            let result = runtime
+                .refuel(self.budget.fuel_per_call)
+                .and_then(|_| runtime.deactivate.call(&mut runtime.store, ()));
+            if let Err(e) = result {
+                debug!("WASM plugin '{}' deactivate failed: {}", self.info.name, e);
+            }
+        }
+    }
+
+    fn process(&mut self, inputs: RSlice<RSlice<f32>>, mut outputs: RSliceMut<RSliceMut<f32>>) {
+        // Runs on the RT thread: work on the host's slices directly rather
+        // than collecting them, so nothing here allocates.
+        let Some(runtime) = self.runtime.as_mut().filter(|_| self.max_block_size > 0) else {
+            pass_through(&inputs, &mut outputs);
+            return;
+        };
+
+        let in_count = inputs.len().min(self.info.input_channels as usize);
+        let out_count = outputs.len().min(self.info.output_channels as usize);
+        let frames = inputs[..in_count]
+            .iter()
+            .map(|s| s.len())
+            .chain(outputs[..out_count].iter().map(|s| s.len()))
+            .min()
+            .unwrap_or(0);
+        let stride = self.max_block_size;
+
+        let mut offset = 0;
+        while offset < frames {
+            let n = (frames - offset).min(stride);
+            if let Err(e) = Self::process_block(
+                runtime,
+                &self.budget,
+                stride,
+                &inputs[..in_count],
+                &mut outputs[..out_count],
+                offset,
+                n,
+            ) {
+                self.send_fault(e);
+                pass_through(&inputs, &mut outputs);
+                return;
+            }
+            offset += n;
+        }
+    }
+
+    fn parameter_count(&self) -> u32 {
+        self.info.parameters.len() as u32
+    }
+
+    fn parameter_info(&self, index: u32) -> ROption<ParameterInfo> {
+        self.info
+            .parameters
+            .get(index as usize)
+            .map(|p| p.to_parameter_info(index))
+            .into()
+    }
+
+    fn get_parameter(&self, index: u32) -> f32 {
+        self.params.get(index as usize).copied().unwrap_or(0.0)
+    }
+
+    fn set_parameter(&mut self, index: u32, value: f32) {
+        let Some(slot) = self.params.get_mut(index as usize) else {
+            return;
+        };
+        *slot = value.clamp(0.0, 1.0);
+        let value = *slot;
+
+        if let Some(runtime) = self.runtime.as_mut() {
+            let result = runtime
+                .refuel(self.budget.fuel_per_call)
+                .and_then(|_| runtime.set_parameter.call(&mut runtime.store, (index, value)));
+            if let Err(e) = result {
+                self.fault(e);
+            }
+        }
+    }
+
+    fn save_state(&self) -> RVec<u8> {
+        self.params
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect::<Vec<u8>>()
+            .into()
+    }
+
+    fn load_state(&mut self, data: RSlice<u8>) -> RResult<(), PluginError> {
+        if data.len() != self.params.len() * 4 {
+            return RResult::RErr(PluginError::StateLoadFailed(
+                format!(
+                    "expected {} bytes of parameter state, got {}",
+                    self.params.len() * 4,
+                    data.len()
+                )
+                .into(),
+            ));
+        }
+        for (index, chunk) in data.chunks_exact(4).enumerate() {
+            let value = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+            self.set_parameter(index as u32, value);
+        }
+        RResult::ROk(())
+    }
+
+    fn reset(&mut self) {
+        if let Some(runtime) = self.runtime.as_mut() {
+            let result = runtime
+                .refuel(self.budget.fuel_per_call)
+                .and_then(|_| runtime.reset.call(&mut runtime.store, ()));
+            if let Err(e) = result {
+                self.fault(e);
+            }
+        }
+    }
+
+    fn latency(&self) -> u32 {
+        self.latency
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A stereo gain plugin; `{PROCESS}` is replaced with the process body.
+    const GAIN_WAT: &str = r#"
+        (module
+          (memory (export "memory") 2)
+          (global $heap (mut i32) (i32.const 4096))
+          (global $block (mut i32) (i32.const 0))
+          (global $gain (mut f32) (f32.const 0.5))
+          (data (i32.const 16) "{\"id\":\"test.gain\",\"name\":\"Test Gain\",\"parameters\":[{\"id\":\"gain\",\"name\":\"Gain\",\"min\":0,\"max\":1,\"default\":0.5}]}\00")
+          (func (export "plugin_info") (result i32) (i32.const 16))
+          (func (export "plugin_alloc") (param $n i32) (result i32)
+            (local $p i32)
+            (local.set $p (global.get $heap))
+            (global.set $heap (i32.add (global.get $heap) (local.get $n)))
+            (local.get $p))
+          (func (export "plugin_activate") (param f32 i32) (global.set $block (local.get 1)))
+          (func (export "plugin_deactivate"))
+          (func (export "plugin_process") (param $in i32) (param $out i32) (param $n i32)
+            (local $i i32) (local $ch i32) (local $off i32)
+            {PROCESS})
+          (func (export "plugin_parameter_count") (result i32) (i32.const 1))
+          (func (export "plugin_get_parameter") (param i32) (result f32) (global.get $gain))
+          (func (export "plugin_set_parameter") (param i32 f32) (global.set $gain (local.get 1)))
+          (func (export "plugin_reset")))
+    "#;
+
+    const GAIN_PROCESS: &str = r#"
+        (loop $chs
+          (local.set $i (i32.const 0))
+          (block $done
+            (loop $frames
+              (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+              (local.set $off (i32.shl
+                (i32.add (i32.mul (local.get $ch) (global.get $block)) (local.get $i))
+                (i32.const 2)))
+              (f32.store (i32.add (local.get $out) (local.get $off))
+                (f32.mul (f32.load (i32.add (local.get $in) (local.get $off)))
+                         (global.get $gain)))
+              (local.set $i (i32.add (local.get $i) (i32.const 1)))
+              (br $frames)))
+          (local.set $ch (i32.add (local.get $ch) (i32.const 1)))
+          (br_if $chs (i32.lt_u (local.get $ch) (i32.const 2))))
+    "#;
+
+    fn effect(process: &str) -> PluginResult<WasmEffect> {
+        effect_in(&WasmPluginLoader::new().unwrap(), process)
+    }
+
+    fn effect_in(loader: &WasmPluginLoader, process: &str) -> PluginResult<WasmEffect> {
+        let wat = GAIN_WAT.replace("{PROCESS}", process);
+        let module = Module::new(&loader.engine, wat).unwrap();
+        loader.effect(module)
+    }
+
+    fn activate(effect: &mut WasmEffect, max_block_size: u32) {
+        effect.activate(ActivationContext {
+            sample_rate: 48000.0,
+            max_block_size,
+        });
+    }
+
+    /// Run one stereo buffer of `frames` samples (left = 1.0, right = -1.0).
+    fn run(effect: &mut WasmEffect, frames: usize) -> (Vec<f32>, Vec<f32>) {
+        let left = vec![1.0f32; frames];
+        let right = vec![-1.0f32; frames];
+        let mut out_left = vec![0.0f32; frames];
+        let mut out_right = vec![0.0f32; frames];
+        {
+            let inputs = [RSlice::from_slice(&left), RSlice::from_slice(&right)];
+            let mut outputs = [
+                RSliceMut::from_mut_slice(&mut out_left),
+                RSliceMut::from_mut_slice(&mut out_right),
+            ];
+            effect.process(
+                RSlice::from_slice(&inputs),
+                RSliceMut::from_mut_slice(&mut outputs),
+            );
+        }
+        (out_left, out_right)
+    }
+
+    #[test]
+    fn test_gain_plugin_processes_in_blocks() {
+        let mut effect = effect(GAIN_PROCESS).unwrap();
+        assert_eq!(effect.info().name.as_str(), "Test Gain");
+        assert_eq!(effect.parameter_count(), 1);
+        assert!((effect.get_parameter(0) - 0.5).abs() < 1e-6);
+
+        // 100 frames through a 32-frame block exercises the chunking.
+        activate(&mut effect, 32);
+        effect.set_parameter(0, 0.25);
+        let (left, right) = run(&mut effect, 100);
+        assert!(left.iter().all(|&s| (s - 0.25).abs() < 1e-6));
+        assert!(right.iter().all(|&s| (s + 0.25).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_runaway_plugin_is_bypassed() {
+        let loader = WasmPluginLoader::new().unwrap();
+        let mut effect = effect_in(&loader, "(loop $spin (br $spin))").unwrap();
+        activate(&mut effect, 64);
+        assert!(effect.runtime.is_some());
+
+        let (left, right) = run(&mut effect, 64);
+        assert!(
+            effect.runtime.is_none(),
+            "out-of-fuel instance should be bypassed"
+        );
+        assert!(left.iter().all(|&s| s == 1.0));
+        assert!(right.iter().all(|&s| s == -1.0));
+
+        // The instance went to the loader rather than being dropped in process.
+        assert_eq!(loader.collect_faults(), 1);
+        assert_eq!(loader.collect_faults(), 0);
+
+        // Re-activating gives the plugin a fresh instance.
+        activate(&mut effect, 64);
+        assert!(effect.runtime.is_some());
+    }
+
+    #[test]
+    fn test_sandbox_violations_rejected() {
+        let loader = WasmPluginLoader::new().unwrap();
+        let importing = GAIN_WAT
+            .replace("{PROCESS}", "")
+            .replacen("(module", r#"(module (import "env" "host" (func))"#, 1);
+        let module = Module::new(&loader.engine, importing).unwrap();
+        assert!(loader.effect(module).is_err());
+
+        // 64 MiB of initial memory exceeds the default 16 MiB budget.
+        let hungry = GAIN_WAT
+            .replace("{PROCESS}", "")
+            .replace("(memory (export \"memory\") 2)", "(memory (export \"memory\") 1024)");
+        let module = Module::new(&loader.engine, hungry).unwrap();
+        assert!(loader.effect(module).is_err());
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut effect = effect(GAIN_PROCESS).unwrap();
+        effect.set_parameter(0, 0.8);
+        let state = effect.save_state();
+
+        let mut other = self::effect(GAIN_PROCESS).unwrap();
+        assert!(other.load_state(state.as_rslice()).is_ok());
+        assert!((other.get_parameter(0) - 0.8).abs() < 1e-6);
+        assert!(other.load_state(RSlice::from_slice(&[1, 2, 3])).is_err());
+    }
+}
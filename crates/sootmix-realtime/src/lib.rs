@@ -0,0 +1,12 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Real-time safe building blocks for SootMix, shared by the GUI, the daemon
+//! and the plugin host.
+//!
+//! Nothing here allocates, locks or blocks on the RT side.
+
+pub mod ringbuf;
+
+pub use ringbuf::{RingBuffer, RingBufferReader, RingBufferWriter};
@@ -4,37 +4,37 @@
 
 //! Lock-free single-producer single-consumer ring buffer.
 //!
-//! Used for passing data from the audio thread to the UI thread (e.g., meter data)
-//! without blocking.
+//! Used for passing data between an RT callback and a non-RT thread without
+//! blocking (e.g., audio for instant replay, spent plugin filter updates to
+//! free, or plugin parameter changes from the UI).
 //!
 //! # Example
 //!
-//! ```ignore
-//! use sootmix::realtime::RingBuffer;
+//! ```
+//! use sootmix_realtime::RingBuffer;
 //!
-//! // Create a ring buffer for meter data
+//! // Create a ring buffer for interleaved samples
 //! let (mut writer, mut reader) = RingBuffer::<f32>::new(1024).split();
 //!
-//! // Audio thread writes peak values
-//! writer.push(0.8);
-//! writer.push(0.75);
+//! // Audio thread writes a block
+//! writer.push_slice(&[0.5, -0.5]);
 //!
-//! // UI thread reads them
-//! while let Some(peak) = reader.pop() {
-//!     update_meter(peak);
+//! // Worker thread drains it
+//! let mut history = Vec::new();
+//! while let Some(sample) = reader.pop() {
+//!     history.push(sample);
 //! }
+//! assert_eq!(history, [0.5, -0.5]);
 //! ```
 
-#![allow(dead_code)]
-
 use std::cell::UnsafeCell;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// A lock-free single-producer single-consumer ring buffer.
 ///
-/// The buffer has a fixed capacity and will drop old data when full
-/// (writer never blocks).
+/// The buffer has a fixed capacity and drops new data when full (writer
+/// never blocks).
 pub struct RingBuffer<T> {
     /// The actual buffer storage.
     buffer: Box<[UnsafeCell<Option<T>>]>,
@@ -78,11 +78,6 @@ impl<T> RingBuffer<T> {
         }
     }
 
-    /// Get the capacity of the buffer.
-    pub fn capacity(&self) -> usize {
-        self.capacity
-    }
-
     /// Split into writer and reader handles.
     pub fn split(self) -> (RingBufferWriter<T>, RingBufferReader<T>) {
         let shared = Arc::new(self);
@@ -101,11 +96,6 @@ impl<T> RingBuffer<T> {
         write.wrapping_sub(read)
     }
 
-    /// Check if the buffer is empty.
-    fn is_empty(&self) -> bool {
-        self.available() == 0
-    }
-
     /// Check if the buffer is full.
     fn is_full(&self) -> bool {
         self.available() >= self.capacity
@@ -122,14 +112,17 @@ pub struct RingBufferWriter<T> {
 impl<T> RingBufferWriter<T> {
     /// Push an item to the buffer.
     ///
-    /// If the buffer is full, the oldest item is overwritten.
-    /// Returns true if successful, false if item was dropped due to overflow.
+    /// Returns false (dropping the item) if the buffer is full; check
+    /// [`is_full`](Self::is_full) first where dropping it here would free
+    /// memory on an RT thread.
     pub fn push(&mut self, item: T) -> bool {
         let write_pos = self.inner.write_pos.load(Ordering::Relaxed);
         let read_pos = self.inner.read_pos.load(Ordering::Acquire);
 
-        // Check if buffer is full
-        let is_full = write_pos.wrapping_sub(read_pos) >= self.inner.capacity;
+        // The reader may be taking the oldest item; never write over it
+        if write_pos.wrapping_sub(read_pos) >= self.inner.capacity {
+            return false;
+        }
 
         // Write the item
         let idx = write_pos & self.inner.mask;
@@ -143,7 +136,7 @@ impl<T> RingBufferWriter<T> {
             .write_pos
             .store(write_pos.wrapping_add(1), Ordering::Release);
 
-        !is_full
+        true
     }
 
     /// Push multiple items to the buffer.
@@ -209,142 +202,16 @@ impl<T> RingBufferReader<T> {
         item
     }
 
-    /// Pop all available items into a vector.
-    pub fn pop_all(&mut self) -> Vec<T> {
-        let mut items = Vec::new();
-        while let Some(item) = self.pop() {
-            items.push(item);
-        }
-        items
-    }
-
-    /// Peek at the next item without removing it.
-    pub fn peek(&self) -> Option<&T> {
-        let read_pos = self.inner.read_pos.load(Ordering::Relaxed);
-        let write_pos = self.inner.write_pos.load(Ordering::Acquire);
-
-        if read_pos == write_pos {
-            return None;
-        }
-
-        let idx = read_pos & self.inner.mask;
-        // SAFETY: We're the only reader
-        unsafe { (*self.inner.buffer[idx].get()).as_ref() }
-    }
-
-    /// Get the number of items available to read.
-    pub fn available(&self) -> usize {
-        self.inner.available()
-    }
-
-    /// Check if the buffer is empty.
-    pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
-    }
-
-    /// Skip n items (discard without processing).
-    pub fn skip(&mut self, n: usize) {
-        for _ in 0..n {
-            if self.pop().is_none() {
-                break;
-            }
-        }
-    }
-
-    /// Clear all items from the buffer.
-    pub fn clear(&mut self) {
-        while self.pop().is_some() {}
+    /// Whether the writer has been dropped. Items it pushed before can
+    /// still be popped.
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.inner) == 1
     }
 }
 
 // Reader can be sent to another thread
 unsafe impl<T: Send> Send for RingBufferReader<T> {}
 
-/// Meter data sent from audio thread to UI.
-#[derive(Debug, Clone, Copy, Default)]
-pub struct MeterData {
-    /// Peak level (0.0 to 1.0+).
-    pub peak: f32,
-    /// RMS level (0.0 to 1.0+).
-    pub rms: f32,
-    /// Whether clipping occurred.
-    pub clipping: bool,
-}
-
-impl MeterData {
-    /// Create new meter data.
-    pub fn new(peak: f32, rms: f32) -> Self {
-        Self {
-            peak,
-            rms,
-            clipping: peak >= 1.0,
-        }
-    }
-
-    /// Convert to decibels.
-    pub fn peak_db(&self) -> f32 {
-        if self.peak <= 0.0 {
-            -80.0
-        } else {
-            20.0 * self.peak.log10()
-        }
-    }
-
-    /// Convert RMS to decibels.
-    pub fn rms_db(&self) -> f32 {
-        if self.rms <= 0.0 {
-            -80.0
-        } else {
-            20.0 * self.rms.log10()
-        }
-    }
-}
-
-/// Stereo meter data.
-#[derive(Debug, Clone, Copy, Default)]
-pub struct StereoMeterData {
-    /// Left channel.
-    pub left: MeterData,
-    /// Right channel.
-    pub right: MeterData,
-}
-
-/// Plugin parameter update sent from UI thread to RT audio thread.
-///
-/// Used to update plugin parameters without locking in the audio callback.
-#[derive(Debug, Clone, Copy)]
-pub struct PluginParamUpdate {
-    /// Plugin instance ID.
-    pub instance_id: uuid::Uuid,
-    /// Parameter index.
-    pub param_index: u32,
-    /// New parameter value.
-    pub value: f32,
-}
-
-impl PluginParamUpdate {
-    /// Create a new parameter update.
-    pub fn new(instance_id: uuid::Uuid, param_index: u32, value: f32) -> Self {
-        Self {
-            instance_id,
-            param_index,
-            value,
-        }
-    }
-}
-
-impl StereoMeterData {
-    /// Create new stereo meter data.
-    pub fn new(left: MeterData, right: MeterData) -> Self {
-        Self { left, right }
-    }
-
-    /// Check if either channel is clipping.
-    pub fn is_clipping(&self) -> bool {
-        self.left.clipping || self.right.clipping
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -353,13 +220,13 @@ mod tests {
     fn test_basic_push_pop() {
         let (mut writer, mut reader) = RingBuffer::<i32>::new(4).split();
 
-        assert!(reader.is_empty());
+        assert_eq!(reader.pop(), None);
 
         writer.push(1);
         writer.push(2);
         writer.push(3);
 
-        assert_eq!(reader.available(), 3);
+        assert_eq!(writer.available(), 3);
         assert_eq!(reader.pop(), Some(1));
         assert_eq!(reader.pop(), Some(2));
         assert_eq!(reader.pop(), Some(3));
@@ -371,35 +238,27 @@ mod tests {
         let (mut writer, mut reader) = RingBuffer::<i32>::new(2).split();
 
         // Fill buffer (capacity is rounded to 2)
-        writer.push(1);
-        writer.push(2);
-
-        // Overflow - oldest gets overwritten
-        writer.push(3);
-
-        // Should have 2 and 3 (1 was overwritten)
-        let items = reader.pop_all();
-        assert!(items.contains(&3));
-    }
-
-    #[test]
-    fn test_peek() {
-        let (mut writer, reader) = RingBuffer::<i32>::new(4).split();
+        assert!(writer.push(1));
+        assert!(writer.push(2));
+        assert!(writer.is_full());
 
-        writer.push(42);
+        // Overflow - the new item is dropped
+        assert!(!writer.push(3));
 
-        assert_eq!(reader.peek(), Some(&42));
-        assert_eq!(reader.peek(), Some(&42)); // Still there
-        assert_eq!(reader.available(), 1);
+        assert_eq!(reader.pop(), Some(1));
+        assert_eq!(reader.pop(), Some(2));
+        assert_eq!(reader.pop(), None);
+        assert!(!writer.is_full());
     }
 
     #[test]
-    fn test_meter_data() {
-        let meter = MeterData::new(0.5, 0.3);
-        assert!(!meter.clipping);
-        assert!((meter.peak_db() - (-6.02)).abs() < 0.1);
+    fn test_abandoned_keeps_items() {
+        let (mut writer, mut reader) = RingBuffer::<i32>::new(2).split();
+        writer.push(1);
+        assert!(!reader.is_abandoned());
 
-        let clipping = MeterData::new(1.5, 0.8);
-        assert!(clipping.clipping);
+        drop(writer);
+        assert!(reader.is_abandoned());
+        assert_eq!(reader.pop(), Some(1));
     }
 }
@@ -5,12 +5,15 @@
 //! LADSPA plugin wrapping nnnoiseless for noise suppression.
 //!
 //! This creates a LADSPA-compatible plugin that can be used with
-//! PipeWire's filter-chain module.
+//! PipeWire's filter-chain module. The processing itself lives in
+//! [`suppressor`], which the daemon also runs in-process.
+
+pub mod suppressor;
 
-use nnnoiseless::DenoiseState;
 use std::ffi::CStr;
 use std::os::raw::{c_char, c_int, c_ulong};
 use std::ptr;
+use suppressor::{Model, Settings, Suppressor};
 
 // LADSPA constants
 const LADSPA_PROPERTY_REALTIME: c_int = 0x1;
@@ -23,24 +26,26 @@ const LADSPA_PORT_AUDIO: c_int = 0x8;
 
 const LADSPA_HINT_BOUNDED_BELOW: c_int = 0x1;
 const LADSPA_HINT_BOUNDED_ABOVE: c_int = 0x2;
+const LADSPA_HINT_INTEGER: c_int = 0x20;
+const LADSPA_HINT_DEFAULT_LOW: c_int = 0x80;
 const LADSPA_HINT_DEFAULT_MIDDLE: c_int = 0x100;
+const LADSPA_HINT_DEFAULT_0: c_int = 0x200;
+const LADSPA_HINT_DEFAULT_100: c_int = 0x280;
 
 // Port indices
 const PORT_VAD_THRESHOLD: c_ulong = 0;
 const PORT_INPUT: c_ulong = 1;
 const PORT_OUTPUT: c_ulong = 2;
-const PORT_COUNT: c_ulong = 3;
+const PORT_GRACE: c_ulong = 3;
+const PORT_RETRO_GRACE: c_ulong = 4;
+const PORT_ATTENUATION_LIMIT: c_ulong = 5;
+const PORT_MODEL: c_ulong = 6;
+const PORT_VAD_PROBABILITY: c_ulong = 7;
+const PORT_COUNT: c_ulong = 8;
 
 // Plugin unique ID (arbitrary, just needs to be unique)
 const PLUGIN_UNIQUE_ID: c_ulong = 0x534D5252; // "SMRR" in hex
 
-// RNNoise frame size
-const FRAME_SIZE: usize = DenoiseState::FRAME_SIZE; // 480 samples
-
-// nnnoiseless expects audio in 16-bit PCM range [-32768, 32767], not [-1.0, 1.0]
-const SCALE_IN: f32 = 32767.0;
-const SCALE_OUT: f32 = 1.0 / 32767.0;
-
 /// LADSPA port range hint
 #[repr(C)]
 struct LadspaPortRangeHint {
@@ -79,16 +84,20 @@ unsafe impl Sync for LadspaDescriptor {}
 
 /// Plugin instance data
 struct PluginInstance {
-    /// RNNoise denoiser state
-    denoiser: Box<DenoiseState<'static>>,
-    /// Input buffer for accumulating samples until we have a full frame
-    input_buffer: Vec<f32>,
-    /// Output buffer for samples waiting to be written
-    output_buffer: Vec<f32>,
-    /// Position in output buffer
-    output_pos: usize,
+    /// Mono noise suppressor
+    suppressor: Suppressor,
     /// VAD threshold control port
     vad_threshold: *mut f32,
+    /// VAD grace period control port
+    grace: *mut f32,
+    /// Retroactive VAD grace control port
+    retro_grace: *mut f32,
+    /// Attenuation limit control port
+    attenuation_limit: *mut f32,
+    /// Model control port (0 = RNNoise, 1 = gate)
+    model: *mut f32,
+    /// VAD probability output port
+    vad_probability: *mut f32,
     /// Audio input port
     input: *mut f32,
     /// Audio output port
@@ -96,20 +105,30 @@ struct PluginInstance {
 }
 
 // Static strings for LADSPA
-static LABEL: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"noise_suppressor_mono\0") };
-static NAME: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"SootMix RNNoise Mono\0") };
-static MAKER: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"SootMix (nnnoiseless)\0") };
-static COPYRIGHT: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"MPL-2.0\0") };
-
-static PORT_NAME_VAD: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"VAD Threshold\0") };
-static PORT_NAME_INPUT: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"Input\0") };
-static PORT_NAME_OUTPUT: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"Output\0") };
+static LABEL: &CStr = c"noise_suppressor_mono";
+static NAME: &CStr = c"SootMix RNNoise Mono";
+static MAKER: &CStr = c"SootMix (nnnoiseless)";
+static COPYRIGHT: &CStr = c"MPL-2.0";
+
+static PORT_NAME_VAD: &CStr = c"VAD Threshold";
+static PORT_NAME_INPUT: &CStr = c"Input";
+static PORT_NAME_OUTPUT: &CStr = c"Output";
+static PORT_NAME_GRACE: &CStr = c"VAD Grace Period (ms)";
+static PORT_NAME_RETRO_GRACE: &CStr = c"Retroactive VAD Grace (ms)";
+static PORT_NAME_ATTENUATION_LIMIT: &CStr = c"Attenuation Limit (dB)";
+static PORT_NAME_MODEL: &CStr = c"Model (0 = RNNoise, 1 = Gate)";
+static PORT_NAME_VAD_PROBABILITY: &CStr = c"VAD Probability";
 
 // Port descriptors
 static PORT_DESCRIPTORS: [c_int; PORT_COUNT as usize] = [
     LADSPA_PORT_INPUT | LADSPA_PORT_CONTROL,  // VAD threshold
     LADSPA_PORT_INPUT | LADSPA_PORT_AUDIO,    // Audio input
     LADSPA_PORT_OUTPUT | LADSPA_PORT_AUDIO,   // Audio output
+    LADSPA_PORT_INPUT | LADSPA_PORT_CONTROL,  // VAD grace period
+    LADSPA_PORT_INPUT | LADSPA_PORT_CONTROL,  // Retroactive VAD grace
+    LADSPA_PORT_INPUT | LADSPA_PORT_CONTROL,  // Attenuation limit
+    LADSPA_PORT_INPUT | LADSPA_PORT_CONTROL,  // Model
+    LADSPA_PORT_OUTPUT | LADSPA_PORT_CONTROL, // VAD probability
 ];
 
 // Wrapper to make raw pointer arrays Sync
@@ -121,6 +140,11 @@ static PORT_NAMES: SyncPortNames = SyncPortNames([
     PORT_NAME_VAD.as_ptr(),
     PORT_NAME_INPUT.as_ptr(),
     PORT_NAME_OUTPUT.as_ptr(),
+    PORT_NAME_GRACE.as_ptr(),
+    PORT_NAME_RETRO_GRACE.as_ptr(),
+    PORT_NAME_ATTENUATION_LIMIT.as_ptr(),
+    PORT_NAME_MODEL.as_ptr(),
+    PORT_NAME_VAD_PROBABILITY.as_ptr(),
 ]);
 
 // Port range hints
@@ -140,16 +164,43 @@ static PORT_RANGE_HINTS: [LadspaPortRangeHint; PORT_COUNT as usize] = [
         lower_bound: 0.0,
         upper_bound: 0.0,
     },
+    LadspaPortRangeHint {
+        hint_descriptor: LADSPA_HINT_BOUNDED_BELOW | LADSPA_HINT_BOUNDED_ABOVE | LADSPA_HINT_DEFAULT_LOW,
+        lower_bound: 0.0,
+        upper_bound: suppressor::MAX_GRACE_MS,
+    },
+    LadspaPortRangeHint {
+        hint_descriptor: LADSPA_HINT_BOUNDED_BELOW | LADSPA_HINT_BOUNDED_ABOVE | LADSPA_HINT_DEFAULT_0,
+        lower_bound: 0.0,
+        upper_bound: suppressor::MAX_RETRO_GRACE_MS,
+    },
+    LadspaPortRangeHint {
+        hint_descriptor: LADSPA_HINT_BOUNDED_BELOW | LADSPA_HINT_BOUNDED_ABOVE | LADSPA_HINT_DEFAULT_100,
+        lower_bound: 0.0,
+        upper_bound: suppressor::MAX_ATTENUATION_DB,
+    },
+    LadspaPortRangeHint {
+        hint_descriptor: LADSPA_HINT_BOUNDED_BELOW | LADSPA_HINT_BOUNDED_ABOVE | LADSPA_HINT_INTEGER | LADSPA_HINT_DEFAULT_0,
+        lower_bound: 0.0,
+        upper_bound: 1.0,
+    },
+    LadspaPortRangeHint {
+        hint_descriptor: LADSPA_HINT_BOUNDED_BELOW | LADSPA_HINT_BOUNDED_ABOVE,
+        lower_bound: 0.0,
+        upper_bound: 100.0,
+    },
 ];
 
 // LADSPA callbacks
 extern "C" fn instantiate(_descriptor: *const LadspaDescriptor, _sample_rate: c_ulong) -> *mut PluginInstance {
     let instance = Box::new(PluginInstance {
-        denoiser: DenoiseState::new(),
-        input_buffer: Vec::with_capacity(FRAME_SIZE),
-        output_buffer: Vec::with_capacity(FRAME_SIZE),
-        output_pos: 0,
+        suppressor: Suppressor::new(1, Settings::default()),
         vad_threshold: ptr::null_mut(),
+        grace: ptr::null_mut(),
+        retro_grace: ptr::null_mut(),
+        attenuation_limit: ptr::null_mut(),
+        model: ptr::null_mut(),
+        vad_probability: ptr::null_mut(),
         input: ptr::null_mut(),
         output: ptr::null_mut(),
     });
@@ -165,6 +216,11 @@ extern "C" fn connect_port(instance: *mut PluginInstance, port: c_ulong, data: *
         PORT_VAD_THRESHOLD => instance.vad_threshold = data,
         PORT_INPUT => instance.input = data,
         PORT_OUTPUT => instance.output = data,
+        PORT_GRACE => instance.grace = data,
+        PORT_RETRO_GRACE => instance.retro_grace = data,
+        PORT_ATTENUATION_LIMIT => instance.attenuation_limit = data,
+        PORT_MODEL => instance.model = data,
+        PORT_VAD_PROBABILITY => instance.vad_probability = data,
         _ => {}
     }
 }
@@ -174,9 +230,16 @@ extern "C" fn activate(instance: *mut PluginInstance) {
         return;
     }
     let instance = unsafe { &mut *instance };
-    instance.input_buffer.clear();
-    instance.output_buffer.clear();
-    instance.output_pos = 0;
+    instance.suppressor.reset();
+}
+
+/// Read a control port, or `default` if the host didn't connect it.
+fn control(port: *mut f32, default: f32) -> f32 {
+    if port.is_null() {
+        default
+    } else {
+        unsafe { *port }
+    }
 }
 
 extern "C" fn run(instance: *mut PluginInstance, sample_count: c_ulong) {
@@ -190,64 +253,28 @@ extern "C" fn run(instance: *mut PluginInstance, sample_count: c_ulong) {
     }
 
     let sample_count = sample_count as usize;
-    let input = unsafe { std::slice::from_raw_parts(instance.input, sample_count) };
-    let output = unsafe { std::slice::from_raw_parts_mut(instance.output, sample_count) };
 
-    // Get VAD threshold (0-100%) and convert to probability (0.0-1.0)
-    let vad_threshold = if !instance.vad_threshold.is_null() {
-        (unsafe { *instance.vad_threshold } / 100.0).clamp(0.0, 1.0)
-    } else {
-        0.5 // Default 50%
+    // Pick up control changes; unconnected ports keep their defaults
+    let defaults = Settings::default();
+    let model = if control(instance.model, 0.0) >= 0.5 { Model::Gate } else { Model::RNNoise };
+    let settings = Settings {
+        model,
+        vad_threshold: control(instance.vad_threshold, 50.0),
+        grace_ms: control(instance.grace, defaults.grace_ms),
+        retro_grace_ms: control(instance.retro_grace, defaults.retro_grace_ms),
+        attenuation_limit_db: control(instance.attenuation_limit, defaults.attenuation_limit_db),
     };
-
-    let mut out_idx = 0;
-
-    // First, output any buffered samples from previous processing
-    while out_idx < sample_count && instance.output_pos < instance.output_buffer.len() {
-        output[out_idx] = instance.output_buffer[instance.output_pos];
-        instance.output_pos += 1;
-        out_idx += 1;
+    if settings != instance.suppressor.settings() {
+        instance.suppressor.set_settings(settings);
     }
 
-    // Clear output buffer if fully consumed
-    if instance.output_pos >= instance.output_buffer.len() {
-        instance.output_buffer.clear();
-        instance.output_pos = 0;
-    }
-
-    // Process input samples (scale from [-1.0, 1.0] to [-32768, 32767] for nnnoiseless)
-    for &sample in input {
-        instance.input_buffer.push(sample * SCALE_IN);
-
-        // When we have a full frame, process it
-        if instance.input_buffer.len() >= FRAME_SIZE {
-            let mut frame_out = [0.0f32; FRAME_SIZE];
-            // process_frame returns VAD probability (0.0 = noise, 1.0 = voice)
-            let vad_prob = instance.denoiser.process_frame(&mut frame_out, &instance.input_buffer[..FRAME_SIZE]);
-
-            // Apply VAD gating: if voice probability is below threshold, output silence
-            let output_frame = vad_prob >= vad_threshold;
-
-            // Output processed samples (scale back to [-1.0, 1.0] for LADSPA)
-            for &s in &frame_out {
-                let scaled = if output_frame { s * SCALE_OUT } else { 0.0 };
-                if out_idx < sample_count {
-                    output[out_idx] = scaled;
-                    out_idx += 1;
-                } else {
-                    // Buffer overflow samples for next run
-                    instance.output_buffer.push(scaled);
-                }
-            }
-
-            instance.input_buffer.drain(..FRAME_SIZE);
-        }
-    }
+    // Hosts may run us in place, so copy without assuming distinct buffers
+    unsafe { ptr::copy(instance.input, instance.output, sample_count) };
+    let output = unsafe { std::slice::from_raw_parts_mut(instance.output, sample_count) };
+    instance.suppressor.process(&mut [output]);
 
-    // Zero-fill any remaining output (shouldn't happen in steady state)
-    while out_idx < sample_count {
-        output[out_idx] = 0.0;
-        out_idx += 1;
+    if !instance.vad_probability.is_null() {
+        unsafe { *instance.vad_probability = instance.suppressor.voice_probability() * 100.0 };
     }
 }
 
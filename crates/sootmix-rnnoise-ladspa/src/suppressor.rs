@@ -0,0 +1,385 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Frame-based noise suppressor shared by the LADSPA plugin and the daemon.
+//!
+//! Audio is processed in RNNoise's 10 ms frames, one frame behind the input.
+//! A voice gate follows each frame's voice probability:
+//!
+//! - it opens when the probability reaches the VAD threshold,
+//! - it stays open for the grace period after the voice stops,
+//! - with a retroactive grace it also opens the frames just before the
+//!   voice started, so word starts aren't clipped. The output is delayed by
+//!   the retroactive grace to make that possible.
+//!
+//! The attenuation limit mixes some of the dry signal back in: closed frames
+//! are turned down by at most that much instead of muted, and open frames
+//! keep that much of the noise RNNoise removed.
+
+use nnnoiseless::DenoiseState;
+
+/// Samples per frame (10 ms at 48 kHz).
+pub const FRAME_SIZE: usize = DenoiseState::FRAME_SIZE;
+
+/// Length of a frame in milliseconds.
+const FRAME_MS: f32 = 10.0;
+
+/// Longest grace period, in milliseconds.
+pub const MAX_GRACE_MS: f32 = 1000.0;
+
+/// Longest retroactive grace, in frames and milliseconds.
+const MAX_RETRO_FRAMES: usize = 50;
+pub const MAX_RETRO_GRACE_MS: f32 = MAX_RETRO_FRAMES as f32 * FRAME_MS;
+
+/// Attenuation limit at which noise is removed completely, in dB.
+pub const MAX_ATTENUATION_DB: f32 = 100.0;
+
+// nnnoiseless expects samples in 16-bit PCM range, not [-1.0, 1.0]
+const SCALE_IN: f32 = 32767.0;
+const SCALE_OUT: f32 = 1.0 / 32767.0;
+
+/// Levels the gate model maps to voice probability 0 and 1, in dBFS.
+const GATE_FLOOR_DB: f32 = -60.0;
+const GATE_FULL_DB: f32 = -30.0;
+
+/// How a frame's voice probability is found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Model {
+    /// RNNoise: removes noise and detects voice.
+    #[default]
+    RNNoise,
+    /// Level-based gate: much lighter, but leaves the noise under speech.
+    Gate,
+}
+
+/// Suppressor settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Settings {
+    pub model: Model,
+    /// Voice probability (0-100%) at which the gate opens.
+    pub vad_threshold: f32,
+    /// How long the gate stays open after the voice stops, in ms.
+    pub grace_ms: f32,
+    /// How long before the voice starts the gate opens, in ms.
+    pub retro_grace_ms: f32,
+    /// Most the signal is turned down, in dB.
+    pub attenuation_limit_db: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            model: Model::RNNoise,
+            vad_threshold: 95.0,
+            grace_ms: 200.0,
+            retro_grace_ms: 0.0,
+            attenuation_limit_db: MAX_ATTENUATION_DB,
+        }
+    }
+}
+
+impl Settings {
+    fn threshold(&self) -> f32 {
+        (self.vad_threshold / 100.0).clamp(0.0, 1.0)
+    }
+
+    fn grace_frames(&self) -> usize {
+        (self.grace_ms.clamp(0.0, MAX_GRACE_MS) / FRAME_MS).round() as usize
+    }
+
+    fn retro_frames(&self) -> usize {
+        (self.retro_grace_ms.clamp(0.0, MAX_RETRO_GRACE_MS) / FRAME_MS).round() as usize
+    }
+
+    /// Gain of the dry signal mixed back in.
+    fn dry_gain(&self) -> f32 {
+        let limit = self.attenuation_limit_db.clamp(0.0, MAX_ATTENUATION_DB);
+        if limit >= MAX_ATTENUATION_DB {
+            0.0
+        } else {
+            10.0f32.powf(-limit / 20.0)
+        }
+    }
+
+    /// Delay the suppressor adds, in samples.
+    pub fn latency(&self) -> u32 {
+        ((1 + self.retro_frames()) * FRAME_SIZE) as u32
+    }
+}
+
+/// One audio channel's denoiser and frame buffers.
+struct Channel {
+    denoiser: Box<DenoiseState<'static>>,
+    /// Input frame being filled, scaled for nnnoiseless.
+    input: [f32; FRAME_SIZE],
+    /// Frame being played out while the next one fills.
+    output: [f32; FRAME_SIZE],
+    /// Processed and dry frames waiting out the retroactive grace.
+    wet: Vec<[f32; FRAME_SIZE]>,
+    dry: Vec<[f32; FRAME_SIZE]>,
+}
+
+impl Channel {
+    fn new() -> Self {
+        Self {
+            denoiser: DenoiseState::new(),
+            input: [0.0; FRAME_SIZE],
+            output: [0.0; FRAME_SIZE],
+            wet: vec![[0.0; FRAME_SIZE]; MAX_RETRO_FRAMES + 1],
+            dry: vec![[0.0; FRAME_SIZE]; MAX_RETRO_FRAMES + 1],
+        }
+    }
+}
+
+/// Noise suppressor over blocks of any number of channels, in place.
+///
+/// All buffers are allocated up front, so [`process`](Self::process) and
+/// [`set_settings`](Self::set_settings) are real-time safe.
+pub struct Suppressor {
+    settings: Settings,
+    channels: Vec<Channel>,
+    /// Whether each queued frame is let through.
+    open: [bool; MAX_RETRO_FRAMES + 1],
+    /// Queue slot of the next frame.
+    head: usize,
+    /// Position within the current frame.
+    pos: usize,
+    /// Frames the gate stays open for before closing.
+    hold: usize,
+    /// Voice probability of the last frame (0.0-1.0).
+    probability: f32,
+    /// Whether the gate was open for the last frame.
+    gate_open: bool,
+}
+
+impl Suppressor {
+    pub fn new(channels: usize, settings: Settings) -> Self {
+        Self {
+            settings,
+            channels: (0..channels).map(|_| Channel::new()).collect(),
+            open: [false; MAX_RETRO_FRAMES + 1],
+            head: 0,
+            pos: 0,
+            hold: 0,
+            probability: 0.0,
+            gate_open: false,
+        }
+    }
+
+    pub fn settings(&self) -> Settings {
+        self.settings
+    }
+
+    /// Change the settings. Changing the retroactive grace drops the queued
+    /// frames, since the output delay changes with it.
+    pub fn set_settings(&mut self, settings: Settings) {
+        if settings.retro_frames() != self.settings.retro_frames() {
+            self.clear_queue();
+        }
+        self.settings = settings;
+    }
+
+    /// Forget all buffered audio and start again from silence.
+    pub fn reset(&mut self) {
+        for channel in &mut self.channels {
+            channel.input.fill(0.0);
+            channel.output.fill(0.0);
+        }
+        self.clear_queue();
+        self.pos = 0;
+        self.hold = 0;
+        self.probability = 0.0;
+        self.gate_open = false;
+    }
+
+    fn clear_queue(&mut self) {
+        for channel in &mut self.channels {
+            channel.wet.iter_mut().for_each(|frame| frame.fill(0.0));
+            channel.dry.iter_mut().for_each(|frame| frame.fill(0.0));
+        }
+        self.open.fill(false);
+        self.head = 0;
+    }
+
+    /// Voice probability of the last frame (0.0-1.0).
+    pub fn voice_probability(&self) -> f32 {
+        self.probability
+    }
+
+    /// Whether the gate was open for the last frame.
+    pub fn is_open(&self) -> bool {
+        self.gate_open
+    }
+
+    /// Process a block in place. Every channel slice must be the same length.
+    pub fn process(&mut self, block: &mut [&mut [f32]]) {
+        let len = block.iter().map(|samples| samples.len()).min().unwrap_or(0);
+        for i in 0..len {
+            for (samples, channel) in block.iter_mut().zip(self.channels.iter_mut()) {
+                let sample = samples[i];
+                samples[i] = channel.output[self.pos];
+                channel.input[self.pos] = sample * SCALE_IN;
+            }
+            self.pos += 1;
+            if self.pos == FRAME_SIZE {
+                self.pos = 0;
+                self.process_frame();
+            }
+        }
+    }
+
+    /// Queue the buffered frame of every channel, gate it, and move the
+    /// oldest queued frame to the output. The channels are gated together on
+    /// the highest voice probability so a stereo image doesn't wander.
+    fn process_frame(&mut self) {
+        let settings = self.settings;
+        let slots = settings.retro_frames() + 1;
+        let head = self.head;
+
+        let mut probability = 0.0f32;
+        for channel in &mut self.channels {
+            let dry = &mut channel.dry[head];
+            for (out, sample) in dry.iter_mut().zip(&channel.input) {
+                *out = sample * SCALE_OUT;
+            }
+            let wet = &mut channel.wet[head];
+            let voice = match settings.model {
+                Model::RNNoise => {
+                    let voice = channel.denoiser.process_frame(wet, &channel.input);
+                    wet.iter_mut().for_each(|sample| *sample *= SCALE_OUT);
+                    voice
+                }
+                Model::Gate => {
+                    *wet = *dry;
+                    level_probability(dry)
+                }
+            };
+            probability = probability.max(voice);
+        }
+
+        if probability >= settings.threshold() {
+            // Opens this frame and the ones still queued before it
+            self.hold = settings.grace_frames();
+            self.open[..slots].fill(true);
+        } else if self.hold > 0 {
+            self.hold -= 1;
+            self.open[head] = true;
+        } else {
+            self.open[head] = false;
+        }
+        self.probability = probability;
+        self.gate_open = self.open[head];
+
+        let oldest = (head + 1) % slots;
+        let open = self.open[oldest];
+        let dry_gain = settings.dry_gain();
+        for channel in &mut self.channels {
+            let frames = channel.wet[oldest].iter().zip(&channel.dry[oldest]);
+            for (out, (wet, dry)) in channel.output.iter_mut().zip(frames) {
+                *out = if open {
+                    wet + (dry - wet) * dry_gain
+                } else {
+                    dry * dry_gain
+                };
+            }
+        }
+        self.head = oldest;
+    }
+}
+
+/// Voice probability of a frame from its level alone.
+fn level_probability(frame: &[f32; FRAME_SIZE]) -> f32 {
+    let power = frame.iter().map(|s| s * s).sum::<f32>() / FRAME_SIZE as f32;
+    let level_db = 10.0 * power.max(1e-12).log10();
+    ((level_db - GATE_FLOOR_DB) / (GATE_FULL_DB - GATE_FLOOR_DB)).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOUD: f32 = 0.5;
+    const QUIET: f32 = 0.0001;
+
+    /// A gate that only lets loud frames through.
+    fn gate() -> Settings {
+        Settings {
+            model: Model::Gate,
+            vad_threshold: 50.0,
+            grace_ms: 0.0,
+            ..Settings::default()
+        }
+    }
+
+    /// Feed one frame per level and check the first sample of each output
+    /// frame.
+    fn run(suppressor: &mut Suppressor, levels: &[f32], expected: &[f32]) {
+        for (&level, &want) in levels.iter().zip(expected) {
+            let mut frame = [level; FRAME_SIZE];
+            suppressor.process(&mut [&mut frame]);
+            assert!((frame[0] - want).abs() < 1e-7, "{} != {}", frame[0], want);
+        }
+    }
+
+    #[test]
+    fn test_gate_follows_level_one_frame_behind() {
+        let mut suppressor = Suppressor::new(1, gate());
+        run(
+            &mut suppressor,
+            &[LOUD, QUIET, LOUD, QUIET],
+            &[0.0, LOUD, 0.0, LOUD],
+        );
+        assert!(!suppressor.is_open());
+    }
+
+    #[test]
+    fn test_grace_holds_gate_open() {
+        let settings = Settings {
+            grace_ms: 20.0,
+            ..gate()
+        };
+        let mut suppressor = Suppressor::new(1, settings);
+        run(
+            &mut suppressor,
+            &[LOUD, QUIET, QUIET, QUIET, QUIET],
+            &[0.0, LOUD, QUIET, QUIET, 0.0],
+        );
+    }
+
+    #[test]
+    fn test_retro_grace_opens_frames_before_voice() {
+        let settings = Settings {
+            retro_grace_ms: 20.0,
+            ..gate()
+        };
+        assert_eq!(settings.latency(), 3 * FRAME_SIZE as u32);
+        let mut suppressor = Suppressor::new(1, settings);
+        run(
+            &mut suppressor,
+            &[QUIET, QUIET, QUIET, LOUD, QUIET, QUIET, QUIET, QUIET],
+            &[0.0, 0.0, 0.0, 0.0, QUIET, QUIET, LOUD, 0.0],
+        );
+    }
+
+    #[test]
+    fn test_attenuation_limit_keeps_some_signal() {
+        let settings = Settings {
+            attenuation_limit_db: 20.0,
+            ..gate()
+        };
+        let mut suppressor = Suppressor::new(1, settings);
+        run(&mut suppressor, &[QUIET, QUIET], &[0.0, QUIET * 0.1]);
+    }
+
+    #[test]
+    fn test_voice_probability_is_published() {
+        let mut suppressor = Suppressor::new(1, gate());
+        run(&mut suppressor, &[LOUD], &[0.0]);
+        assert_eq!(suppressor.voice_probability(), 1.0);
+        assert!(suppressor.is_open());
+        run(&mut suppressor, &[QUIET], &[LOUD]);
+        assert_eq!(suppressor.voice_probability(), 0.0);
+        assert!(!suppressor.is_open());
+    }
+}
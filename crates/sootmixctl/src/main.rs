@@ -0,0 +1,1603 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! sootmixctl - command-line client for the SootMix daemon.
+//!
+//! Talks to `com.sootmix.Daemon` on the session bus through the same
+//! `DaemonProxy` the GUI uses, so anything the mixer window can do to
+//! channels, apps, routing rules, ducking, scenes and MIDI bindings can be
+//! scripted from a shell.
+
+mod output;
+mod resolve;
+mod watch;
+
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use futures::StreamExt;
+use serde_json::json;
+use sootmix_ipc::{
+    AutoLevelInfo, ChannelLayout, DaemonProxy, DuckingInfo, MidiBindingInfo, NoiseModel,
+    NoiseSuppressionInfo, RoutingRuleInfo, RuleConditionInfo, SoloMode, TalkInfo, TalkMode,
+};
+use std::process::ExitCode;
+use thiserror::Error;
+use zbus::Connection;
+
+/// Reserved target name that addresses the master bus instead of a channel.
+const MASTER: &str = "master";
+
+/// Errors reported to the user before exiting non-zero.
+#[derive(Debug, Error)]
+pub enum CtlError {
+    #[error("Cannot reach sootmix-daemon on the session bus ({0}). Is it running?")]
+    Connect(zbus::Error),
+    #[error("{0}")]
+    DBus(#[from] zbus::Error),
+    #[error("Channel not found: {0}")]
+    ChannelNotFound(String),
+    #[error("'{0}' matches more than one channel: {1}")]
+    AmbiguousChannel(String, String),
+    #[error("App not found: {0}")]
+    AppNotFound(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("The daemon left the bus")]
+    DaemonGone,
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sootmixctl",
+    version,
+    about = "Control the SootMix daemon from the command line"
+)]
+struct Cli {
+    /// Print machine-readable JSON instead of tables.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List mixer channels.
+    Channels,
+    /// List applications that are playing audio.
+    Apps,
+    /// List output devices.
+    Outputs,
+    /// List input devices.
+    Inputs,
+    /// Set the volume of a channel, or of the master bus with "master".
+    #[command(allow_negative_numbers = true)]
+    Volume {
+        /// Channel name, ID or ID prefix, or "master".
+        target: String,
+        /// Volume in dB (-60 to +12).
+        volume_db: f64,
+    },
+    /// Set a channel's pan/balance and surround width.
+    #[command(allow_negative_numbers = true)]
+    Pan {
+        /// Channel name, ID or ID prefix.
+        channel: String,
+        /// -1.0 (left) to 1.0 (right); 0 is centre.
+        pan: f64,
+        /// 0.0 (front speakers only) to 1.0 (all speakers). Unchanged if omitted.
+        #[arg(long)]
+        width: Option<f64>,
+    },
+    /// Set an output channel's speaker layout.
+    Layout {
+        /// Channel name, ID or ID prefix.
+        channel: String,
+        /// mono, stereo, 5.1 or 7.1.
+        layout: ChannelLayout,
+    },
+    /// Mute, unmute or toggle a channel, or the master bus with "master".
+    Mute {
+        /// Channel name, ID or ID prefix, or "master".
+        target: String,
+        #[arg(value_enum, default_value_t = MuteState::Toggle)]
+        state: MuteState,
+    },
+    /// Solo, unsolo or toggle a channel (heard on the monitor device).
+    Solo {
+        /// Channel name, ID or ID prefix.
+        channel: String,
+        #[arg(value_enum, default_value_t = MuteState::Toggle)]
+        state: MuteState,
+    },
+    /// Turn an input channel's sidetone (hearing yourself on the monitor) on or off.
+    #[command(allow_negative_numbers = true)]
+    Sidetone {
+        /// Channel name, ID or ID prefix.
+        channel: String,
+        #[arg(value_enum, default_value_t = MuteState::Toggle)]
+        state: MuteState,
+        /// Sidetone level in dB (-60 to +12). Unchanged if omitted.
+        #[arg(long)]
+        volume: Option<f64>,
+    },
+    /// Turn a channel's automatic loudness normalization (auto level) on or off.
+    #[command(allow_negative_numbers = true)]
+    AutoLevel {
+        /// Channel name, ID or ID prefix.
+        channel: String,
+        #[arg(value_enum, default_value_t = MuteState::Toggle)]
+        state: MuteState,
+        /// Target loudness in LUFS (-60 to 0). Unchanged if omitted.
+        #[arg(long)]
+        target: Option<f64>,
+        /// Most gain added to quiet sources, in dB (0 to 24). Unchanged if omitted.
+        #[arg(long)]
+        max_boost: Option<f64>,
+        /// Most gain taken from loud sources, in dB (0 to 24). Unchanged if omitted.
+        #[arg(long)]
+        max_cut: Option<f64>,
+        /// How fast the gain follows the source, in dB per second. Unchanged if omitted.
+        #[arg(long)]
+        speed: Option<f64>,
+    },
+    /// Tune an input channel's noise suppression.
+    Noise {
+        /// Channel name, ID or ID prefix.
+        channel: String,
+        /// rnnoise or gate (level-based, lighter). Unchanged if omitted.
+        #[arg(long)]
+        model: Option<NoiseModel>,
+        /// How long the mic stays open after the voice stops, in ms (0 to
+        /// 1000). Unchanged if omitted.
+        #[arg(long)]
+        grace: Option<f64>,
+        /// How long before the voice starts the mic opens, in ms (0 to 500).
+        /// Adds as much latency. Unchanged if omitted.
+        #[arg(long)]
+        retro_grace: Option<f64>,
+        /// Most the mic is turned down, in dB (0 to 100, 100 = no limit).
+        /// Unchanged if omitted.
+        #[arg(long)]
+        attenuation_limit: Option<f64>,
+    },
+    /// Set up push-to-talk or push-to-mute on an input channel.
+    Talk {
+        /// Channel name, ID or ID prefix.
+        channel: String,
+        /// off, push-to-talk or push-to-mute. Unchanged if omitted.
+        #[arg(long)]
+        mode: Option<TalkMode>,
+        /// How long the key stays held after it is let go, in ms (0 to
+        /// 2000). Unchanged if omitted.
+        #[arg(long)]
+        release: Option<f64>,
+        /// How long the mic takes to open or close, in ms (0 to 500).
+        /// Unchanged if omitted.
+        #[arg(long)]
+        fade: Option<f64>,
+    },
+    /// Press or let go of the talk key, e.g. from a desktop key binding.
+    TalkKey {
+        #[arg(value_enum)]
+        state: KeyState,
+        /// Channel name, ID or ID prefix. Every channel with a talk mode if
+        /// omitted.
+        channel: Option<String>,
+    },
+    /// Show or change the monitor bus (solo and sidetone listening).
+    #[command(subcommand)]
+    Monitor(MonitorCommand),
+    /// Restart loudness measurement (integrated, range and true peak).
+    ResetLoudness {
+        /// Channel name, ID or ID prefix, or "master". All channels and the
+        /// master bus if omitted.
+        target: Option<String>,
+    },
+    /// Route an app's streams to a channel.
+    Assign {
+        /// App name, binary, identifier or PipeWire node ID.
+        app: String,
+        /// Channel name, ID or ID prefix.
+        channel: String,
+    },
+    /// Send an app's streams back to the default output.
+    Unassign {
+        /// App name, binary, identifier or PipeWire node ID.
+        app: String,
+        /// Channel name, ID or ID prefix.
+        channel: String,
+    },
+    /// Create a channel and print its ID.
+    Create {
+        name: String,
+        /// Create an input (microphone) channel instead of an output channel.
+        #[arg(long)]
+        input: bool,
+    },
+    /// Delete a channel.
+    Delete {
+        /// Channel name, ID or ID prefix.
+        channel: String,
+    },
+    /// Manage routing rules.
+    #[command(subcommand)]
+    Rules(RulesCommand),
+    /// Manage ducking (lowering channels while another is active).
+    #[command(subcommand)]
+    Ducking(DuckingCommand),
+    /// Save and recall whole-mixer scenes.
+    #[command(subcommand)]
+    Scenes(ScenesCommand),
+    /// Record channels or the master bus to disk.
+    #[command(subcommand)]
+    Record(RecordCommand),
+    /// Keep a rolling in-memory history of channels and save the last seconds.
+    #[command(subcommand)]
+    Replay(ReplayCommand),
+    /// Bind MIDI controller knobs, faders and buttons to mixer controls.
+    #[command(subcommand)]
+    Midi(MidiCommand),
+    /// Print daemon signals as they arrive.
+    Watch {
+        /// Include meter and loudness updates (several per second).
+        #[arg(long)]
+        meters: bool,
+    },
+    /// Print a shell completion script.
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum RulesCommand {
+    /// List routing rules.
+    List,
+    /// Add a rule that routes matching apps to a channel.
+    Add {
+        /// Rule name.
+        #[arg(long)]
+        name: String,
+        /// Pattern to match against the app name/binary. May be omitted
+        /// when the rule matches on --property conditions only.
+        #[arg(long, default_value = "")]
+        pattern: String,
+        /// Target channel name.
+        #[arg(long)]
+        channel: String,
+        #[arg(long, value_enum, default_value_t = MatchType::Contains)]
+        match_type: MatchType,
+        #[arg(long, value_enum, default_value_t = MatchTarget::Either)]
+        match_target: MatchTarget,
+        /// Node property condition, e.g. `media.role=Communication`. Repeatable.
+        #[arg(long = "property", value_name = "KEY=PATTERN", value_parser = parse_property)]
+        properties: Vec<(String, String)>,
+        /// How --property patterns are matched.
+        #[arg(long, value_enum, default_value_t = MatchType::Exact)]
+        property_match: MatchType,
+        /// Match if any condition matches instead of all of them.
+        #[arg(long)]
+        any: bool,
+        /// Lower values are checked first.
+        #[arg(long, default_value_t = 100)]
+        priority: u32,
+        /// Add the rule disabled.
+        #[arg(long)]
+        disabled: bool,
+    },
+    /// Delete a rule.
+    Delete { rule_id: String },
+    /// Enable a disabled rule or disable an enabled one.
+    Toggle { rule_id: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum DuckingCommand {
+    /// List ducking relationships.
+    List,
+    /// Lower channels while a trigger channel is active, and print the ID.
+    #[command(allow_negative_numbers = true)]
+    Add {
+        /// Trigger channel name, ID or ID prefix.
+        #[arg(long)]
+        trigger: String,
+        /// Channel to lower (name, ID or ID prefix). Repeatable.
+        #[arg(long = "duck", required = true)]
+        ducked: Vec<String>,
+        /// Trigger level in dB above which ducking engages.
+        #[arg(long, default_value_t = -40.0)]
+        threshold_db: f64,
+        /// Attenuation in dB.
+        #[arg(long, default_value_t = 12.0)]
+        amount_db: f64,
+        #[arg(long, default_value_t = 50.0)]
+        attack_ms: f64,
+        #[arg(long, default_value_t = 300.0)]
+        hold_ms: f64,
+        #[arg(long, default_value_t = 500.0)]
+        release_ms: f64,
+        /// Add the relationship disabled.
+        #[arg(long)]
+        disabled: bool,
+    },
+    /// Delete a ducking relationship.
+    Delete { ducking_id: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum MonitorCommand {
+    /// Show the monitor device, level and solo mode.
+    Show,
+    /// Set the monitor device, or "master" to follow the master output.
+    Device { device: String },
+    /// Set the monitor level.
+    #[command(allow_negative_numbers = true)]
+    Volume {
+        /// Volume in dB (-60 to +12).
+        volume_db: f64,
+    },
+    /// Mute, unmute or toggle the monitor bus.
+    Mute {
+        #[arg(value_enum, default_value_t = MuteState::Toggle)]
+        state: MuteState,
+    },
+    /// Set how soloed channels are heard: pfl or in-place.
+    Mode { mode: SoloMode },
+    /// Unsolo every channel.
+    ClearSolo,
+}
+
+#[derive(Debug, Subcommand)]
+enum ScenesCommand {
+    /// List saved scenes.
+    List,
+    /// Save the current mixer state as a scene (replacing one with the same name).
+    Save { name: String },
+    /// Recall a scene.
+    Recall {
+        name: String,
+        /// Time over which volume changes fade, in milliseconds.
+        #[arg(long, default_value_t = 1000)]
+        fade_ms: u32,
+    },
+    /// Delete a scene.
+    Delete { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum RecordCommand {
+    /// Start recording, one file per source, and print the recording ID.
+    Start {
+        /// Channel name, ID or ID prefix, or "master". Several sources are
+        /// recorded sample-aligned.
+        #[arg(required = true)]
+        sources: Vec<String>,
+        #[arg(long, value_enum, default_value_t = RecordFormat::Wav)]
+        format: RecordFormat,
+    },
+    /// Stop a recording and print its files. The ID may be omitted if only
+    /// one recording is running.
+    Stop { recording_id: Option<String> },
+    /// List running recordings.
+    Status,
+    /// Print the recording directory, or set it (empty for the default).
+    Dir { directory: Option<String> },
+}
+
+#[derive(Debug, Subcommand)]
+enum ReplayCommand {
+    /// Start keeping a replay buffer for a channel.
+    Enable { channel: String },
+    /// Drop a channel's replay buffer.
+    Disable { channel: String },
+    /// List channels with a replay buffer.
+    Status,
+    /// Print the replay length in seconds, or set it.
+    Length { seconds: Option<u32> },
+    /// Save the last seconds of replay buffers, one file per channel, and
+    /// print the files.
+    Save {
+        /// Channels to save (name, ID or ID prefix). All buffers if omitted.
+        channels: Vec<String>,
+        /// How much to save. Everything buffered if omitted.
+        #[arg(long)]
+        seconds: Option<u32>,
+        #[arg(long, value_enum, default_value_t = RecordFormat::Wav)]
+        format: RecordFormat,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum MidiCommand {
+    /// List connected MIDI controllers.
+    Devices,
+    /// List MIDI bindings.
+    List,
+    /// Bind a control by number.
+    Bind {
+        #[command(flatten)]
+        target: MidiTargetArgs,
+        /// Control change number.
+        #[arg(long, conflicts_with = "note", required_unless_present = "note")]
+        cc: Option<u8>,
+        /// Note number (for buttons and pads).
+        #[arg(long)]
+        note: Option<u8>,
+        /// MIDI channel, 1-16.
+        #[arg(long, default_value_t = 1)]
+        midi_channel: u8,
+        /// Only listen to this controller (see `midi devices`). Any if omitted.
+        #[arg(long)]
+        device: Option<String>,
+    },
+    /// Bind the next control moved on any controller, and print the binding.
+    Learn {
+        #[command(flatten)]
+        target: MidiTargetArgs,
+    },
+    /// Delete a MIDI binding.
+    Delete { binding_id: String },
+}
+
+/// What a MIDI control drives.
+#[derive(Debug, Args)]
+struct MidiTargetArgs {
+    #[arg(value_enum)]
+    target: MidiTarget,
+    /// Channel name, ID or ID prefix (channel and plugin targets).
+    channel: Option<String>,
+    /// Plugin chain slot (plugin-parameter only).
+    #[arg(long, default_value_t = 0)]
+    slot: u32,
+    /// Plugin parameter index (plugin-parameter only).
+    #[arg(long, default_value_t = 0)]
+    param: u32,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum MidiTarget {
+    ChannelVolume,
+    ChannelMute,
+    ChannelSolo,
+    /// Held like the channel's talk key.
+    ChannelTalk,
+    MasterVolume,
+    MasterMute,
+    PluginParameter,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum RecordFormat {
+    Wav,
+    Flac,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum KeyState {
+    Press,
+    Release,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum MuteState {
+    On,
+    Off,
+    Toggle,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum MatchType {
+    Contains,
+    Exact,
+    Regex,
+    Glob,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum MatchTarget {
+    Name,
+    Binary,
+    Either,
+}
+
+/// Split a `KEY=PATTERN` property condition.
+fn parse_property(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, pattern)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), pattern.to_string()))
+        }
+        _ => Err(format!("expected KEY=PATTERN, got '{}'", arg)),
+    }
+}
+
+/// Lowercase name of a value enum, as the daemon spells it.
+fn value_name(value: impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .map(|v| v.get_name().to_string())
+        .unwrap_or_default()
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("sootmixctl: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn connect() -> Result<DaemonProxy<'static>, CtlError> {
+    let connection = Connection::session().await.map_err(CtlError::Connect)?;
+    let proxy = DaemonProxy::new(&connection)
+        .await
+        .map_err(CtlError::Connect)?;
+    // Fail early with a clear message instead of on the first real call.
+    proxy.get_connected().await.map_err(CtlError::Connect)?;
+    Ok(proxy)
+}
+
+async fn run(cli: Cli) -> Result<(), CtlError> {
+    let json = cli.json;
+
+    // Completions don't need the daemon.
+    if let Command::Completions { shell } = cli.command {
+        clap_complete::generate(
+            shell,
+            &mut Cli::command(),
+            "sootmixctl",
+            &mut std::io::stdout(),
+        );
+        return Ok(());
+    }
+
+    let proxy = connect().await?;
+
+    match cli.command {
+        Command::Channels => {
+            let channels = proxy.get_channels().await?;
+            if json {
+                output::json(&channels)?;
+            } else {
+                output::channels(&channels);
+            }
+        }
+        Command::Apps => {
+            let apps = proxy.get_apps().await?;
+            if json {
+                output::json(&apps)?;
+            } else {
+                output::apps(&apps);
+            }
+        }
+        Command::Outputs => {
+            let outputs = proxy.get_outputs().await?;
+            if json {
+                output::json(&outputs)?;
+            } else {
+                output::outputs(&outputs);
+            }
+        }
+        Command::Inputs => {
+            let inputs = proxy.get_inputs().await?;
+            if json {
+                output::json(&inputs)?;
+            } else {
+                output::inputs(&inputs);
+            }
+        }
+        Command::Volume { target, volume_db } => {
+            if !(-60.0..=12.0).contains(&volume_db) {
+                return Err(CtlError::InvalidArgument(format!(
+                    "volume {} dB is outside -60..12",
+                    volume_db
+                )));
+            }
+            if target.eq_ignore_ascii_case(MASTER) {
+                proxy.set_master_volume(volume_db).await?;
+            } else {
+                let channels = proxy.get_channels().await?;
+                let channel = resolve::channel(&channels, &target)?;
+                proxy.set_channel_volume(&channel.id, volume_db).await?;
+            }
+        }
+        Command::Pan {
+            channel,
+            pan,
+            width,
+        } => {
+            if !(-1.0..=1.0).contains(&pan) || width.is_some_and(|w| !(0.0..=1.0).contains(&w)) {
+                return Err(CtlError::InvalidArgument(
+                    "pan must be within -1..1 and width within 0..1".to_string(),
+                ));
+            }
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            proxy
+                .set_channel_balance(&channel.id, pan, width.unwrap_or(channel.width))
+                .await?;
+        }
+        Command::Layout { channel, layout } => {
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            proxy
+                .set_channel_layout(&channel.id, layout.as_str())
+                .await?;
+        }
+        Command::Mute { target, state } => {
+            let muted = if target.eq_ignore_ascii_case(MASTER) {
+                let muted = match state {
+                    MuteState::On => true,
+                    MuteState::Off => false,
+                    MuteState::Toggle => !proxy.get_master_muted().await?,
+                };
+                proxy.set_master_mute(muted).await?;
+                muted
+            } else {
+                let channels = proxy.get_channels().await?;
+                let channel = resolve::channel(&channels, &target)?;
+                let muted = match state {
+                    MuteState::On => true,
+                    MuteState::Off => false,
+                    MuteState::Toggle => !channel.muted,
+                };
+                proxy.set_channel_mute(&channel.id, muted).await?;
+                muted
+            };
+            if json {
+                output::json(&json!({ "muted": muted }))?;
+            }
+        }
+        Command::Solo { channel, state } => {
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            let solo = match state {
+                MuteState::On => true,
+                MuteState::Off => false,
+                MuteState::Toggle => !channel.solo,
+            };
+            proxy.set_channel_solo(&channel.id, solo).await?;
+            if json {
+                output::json(&json!({ "solo": solo }))?;
+            }
+        }
+        Command::Sidetone {
+            channel,
+            state,
+            volume,
+        } => {
+            if volume.is_some_and(|v| !(-60.0..=12.0).contains(&v)) {
+                return Err(CtlError::InvalidArgument(
+                    "sidetone volume must be within -60..12 dB".to_string(),
+                ));
+            }
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            let enabled = match state {
+                MuteState::On => true,
+                MuteState::Off => false,
+                MuteState::Toggle => !channel.sidetone_enabled,
+            };
+            proxy
+                .set_channel_sidetone(
+                    &channel.id,
+                    enabled,
+                    volume.unwrap_or(channel.sidetone_volume_db),
+                )
+                .await?;
+        }
+        Command::AutoLevel {
+            channel,
+            state,
+            target,
+            max_boost,
+            max_cut,
+            speed,
+        } => {
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            let current = channel.auto_level;
+            let settings = AutoLevelInfo {
+                enabled: match state {
+                    MuteState::On => true,
+                    MuteState::Off => false,
+                    MuteState::Toggle => !current.enabled,
+                },
+                target_lufs: target.unwrap_or(current.target_lufs),
+                max_boost_db: max_boost.unwrap_or(current.max_boost_db),
+                max_cut_db: max_cut.unwrap_or(current.max_cut_db),
+                speed_db_per_sec: speed.unwrap_or(current.speed_db_per_sec),
+            };
+            proxy.set_channel_auto_level(&channel.id, settings).await?;
+            if json {
+                output::json(&settings)?;
+            }
+        }
+        Command::Noise {
+            channel,
+            model,
+            grace,
+            retro_grace,
+            attenuation_limit,
+        } => {
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            let current = channel.noise_suppression;
+            let settings = NoiseSuppressionInfo {
+                model: model.unwrap_or(current.model),
+                grace_ms: grace.unwrap_or(current.grace_ms),
+                retro_grace_ms: retro_grace.unwrap_or(current.retro_grace_ms),
+                attenuation_limit_db: attenuation_limit.unwrap_or(current.attenuation_limit_db),
+            };
+            proxy
+                .set_channel_noise_suppression_settings(&channel.id, settings)
+                .await?;
+            if json {
+                output::json(&settings)?;
+            }
+        }
+        Command::Talk {
+            channel,
+            mode,
+            release,
+            fade,
+        } => {
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            let current = channel.talk;
+            let settings = TalkInfo {
+                mode: mode.unwrap_or(current.mode),
+                release_ms: release.unwrap_or(current.release_ms),
+                fade_ms: fade.unwrap_or(current.fade_ms),
+            };
+            proxy
+                .set_channel_talk_settings(&channel.id, settings)
+                .await?;
+            if json {
+                output::json(&settings)?;
+            }
+        }
+        Command::TalkKey { state, channel } => {
+            let channel_id = match channel {
+                Some(channel) => {
+                    let channels = proxy.get_channels().await?;
+                    resolve::channel(&channels, &channel)?.id.clone()
+                }
+                None => String::new(),
+            };
+            proxy
+                .set_talk_key(&channel_id, matches!(state, KeyState::Press))
+                .await?;
+        }
+        Command::Monitor(command) => run_monitor(&proxy, command, json).await?,
+        Command::ResetLoudness { target } => {
+            let channel_id = match target {
+                None => String::new(),
+                Some(target) if target.eq_ignore_ascii_case(MASTER) => MASTER.to_string(),
+                Some(target) => {
+                    let channels = proxy.get_channels().await?;
+                    resolve::channel(&channels, &target)?.id.clone()
+                }
+            };
+            proxy.reset_loudness(&channel_id).await?;
+        }
+        Command::Assign { app, channel } => {
+            let (app_id, channel_id) = resolve_app_and_channel(&proxy, &app, &channel).await?;
+            proxy.assign_app(&app_id, &channel_id).await?;
+        }
+        Command::Unassign { app, channel } => {
+            let (app_id, channel_id) = resolve_app_and_channel(&proxy, &app, &channel).await?;
+            proxy.unassign_app(&app_id, &channel_id).await?;
+        }
+        Command::Create { name, input } => {
+            let id = if input {
+                proxy.create_input_channel(&name).await?
+            } else {
+                proxy.create_channel(&name).await?
+            };
+            if json {
+                output::json(&json!({ "id": id }))?;
+            } else {
+                println!("{}", id);
+            }
+        }
+        Command::Delete { channel } => {
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            proxy.delete_channel(&channel.id).await?;
+        }
+        Command::Rules(command) => run_rules(&proxy, command, json).await?,
+        Command::Ducking(command) => run_ducking(&proxy, command, json).await?,
+        Command::Scenes(command) => match command {
+            ScenesCommand::List => {
+                let scenes = proxy.list_scenes().await?;
+                if json {
+                    output::json(&scenes)?;
+                } else {
+                    for name in scenes {
+                        println!("{}", name);
+                    }
+                }
+            }
+            ScenesCommand::Save { name } => proxy.save_scene(&name).await?,
+            ScenesCommand::Recall { name, fade_ms } => proxy.recall_scene(&name, fade_ms).await?,
+            ScenesCommand::Delete { name } => proxy.delete_scene(&name).await?,
+        },
+        Command::Record(command) => run_record(&proxy, command, json).await?,
+        Command::Replay(command) => run_replay(&proxy, command, json).await?,
+        Command::Midi(command) => run_midi(&proxy, command, json).await?,
+        Command::Watch { meters } => watch::run(&proxy, json, meters).await?,
+        Command::Completions { .. } => unreachable!("handled before connecting"),
+    }
+
+    Ok(())
+}
+
+async fn resolve_app_and_channel(
+    proxy: &DaemonProxy<'_>,
+    app: &str,
+    channel: &str,
+) -> Result<(String, String), CtlError> {
+    let apps = proxy.get_apps().await?;
+    let channels = proxy.get_channels().await?;
+    let node_id = resolve::app_node_id(&apps, app)?;
+    let channel = resolve::channel(&channels, channel)?;
+    Ok((node_id.to_string(), channel.id.clone()))
+}
+
+async fn run_monitor(
+    proxy: &DaemonProxy<'_>,
+    command: MonitorCommand,
+    json: bool,
+) -> Result<(), CtlError> {
+    match command {
+        MonitorCommand::Show => {
+            let monitor = proxy.get_monitor().await?;
+            if json {
+                output::json(&monitor)?;
+            } else {
+                output::monitor(&monitor);
+            }
+        }
+        MonitorCommand::Device { device } => {
+            let device = if device.eq_ignore_ascii_case(MASTER) {
+                ""
+            } else {
+                device.as_str()
+            };
+            proxy.set_monitor_device(device).await?;
+        }
+        MonitorCommand::Volume { volume_db } => {
+            if !(-60.0..=12.0).contains(&volume_db) {
+                return Err(CtlError::InvalidArgument(format!(
+                    "volume {} dB is outside -60..12",
+                    volume_db
+                )));
+            }
+            proxy.set_monitor_volume(volume_db).await?;
+        }
+        MonitorCommand::Mute { state } => {
+            let muted = match state {
+                MuteState::On => true,
+                MuteState::Off => false,
+                MuteState::Toggle => !proxy.get_monitor().await?.muted,
+            };
+            proxy.set_monitor_mute(muted).await?;
+            if json {
+                output::json(&json!({ "muted": muted }))?;
+            }
+        }
+        MonitorCommand::Mode { mode } => proxy.set_solo_mode(mode.as_str()).await?,
+        MonitorCommand::ClearSolo => proxy.clear_solo().await?,
+    }
+    Ok(())
+}
+
+async fn run_rules(
+    proxy: &DaemonProxy<'_>,
+    command: RulesCommand,
+    json: bool,
+) -> Result<(), CtlError> {
+    match command {
+        RulesCommand::List => {
+            let rules = proxy.get_routing_rules().await?;
+            if json {
+                output::json(&rules)?;
+            } else {
+                output::rules(&rules);
+            }
+        }
+        RulesCommand::Add {
+            name,
+            pattern,
+            channel,
+            match_type,
+            match_target,
+            properties,
+            property_match,
+            any,
+            priority,
+            disabled,
+        } => {
+            let conditions = properties
+                .into_iter()
+                .map(|(property, pattern)| RuleConditionInfo {
+                    property,
+                    match_type: value_name(property_match),
+                    pattern,
+                })
+                .collect();
+            // An empty ID asks the daemon to create a new rule.
+            proxy
+                .set_routing_rule(RoutingRuleInfo {
+                    id: String::new(),
+                    name,
+                    enabled: !disabled,
+                    match_target: value_name(match_target),
+                    match_type: value_name(match_type),
+                    pattern,
+                    target_channel: channel,
+                    priority,
+                    conditions,
+                    combine: if any { "any" } else { "all" }.to_string(),
+                })
+                .await?;
+        }
+        RulesCommand::Delete { rule_id } => proxy.delete_routing_rule(&rule_id).await?,
+        RulesCommand::Toggle { rule_id } => proxy.toggle_routing_rule(&rule_id).await?,
+    }
+    Ok(())
+}
+
+async fn run_ducking(
+    proxy: &DaemonProxy<'_>,
+    command: DuckingCommand,
+    json: bool,
+) -> Result<(), CtlError> {
+    match command {
+        DuckingCommand::List => {
+            let ducking = proxy.get_ducking().await?;
+            let channels = proxy.get_channels().await?;
+            if json {
+                output::json(&ducking)?;
+            } else {
+                output::ducking(&ducking, &channels);
+            }
+        }
+        DuckingCommand::Add {
+            trigger,
+            ducked,
+            threshold_db,
+            amount_db,
+            attack_ms,
+            hold_ms,
+            release_ms,
+            disabled,
+        } => {
+            let channels = proxy.get_channels().await?;
+            let trigger_channel = resolve::channel(&channels, &trigger)?.id.clone();
+            let ducked_channels = ducked
+                .iter()
+                .map(|c| resolve::channel(&channels, c).map(|c| c.id.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            // An empty ID asks the daemon to create a new relationship.
+            let id = proxy
+                .set_ducking(DuckingInfo {
+                    id: String::new(),
+                    enabled: !disabled,
+                    trigger_channel,
+                    ducked_channels,
+                    threshold_db,
+                    amount_db,
+                    attack_ms,
+                    hold_ms,
+                    release_ms,
+                })
+                .await?;
+            if json {
+                output::json(&json!({ "id": id }))?;
+            } else {
+                println!("{}", id);
+            }
+        }
+        DuckingCommand::Delete { ducking_id } => proxy.delete_ducking(&ducking_id).await?,
+    }
+    Ok(())
+}
+
+async fn run_record(
+    proxy: &DaemonProxy<'_>,
+    command: RecordCommand,
+    json: bool,
+) -> Result<(), CtlError> {
+    match command {
+        RecordCommand::Start { sources, format } => {
+            let channels = proxy.get_channels().await?;
+            let sources = sources
+                .iter()
+                .map(|s| {
+                    if s.eq_ignore_ascii_case(MASTER) {
+                        Ok(MASTER.to_string())
+                    } else {
+                        resolve::channel(&channels, s).map(|c| c.id.clone())
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let id = proxy.start_recording(sources, &value_name(format)).await?;
+            if json {
+                output::json(&json!({ "id": id }))?;
+            } else {
+                println!("{}", id);
+            }
+        }
+        RecordCommand::Stop { recording_id } => {
+            let recording_id = match recording_id {
+                Some(id) => id,
+                None => match proxy.get_recordings().await?.as_slice() {
+                    [only] => only.id.clone(),
+                    [] => {
+                        return Err(CtlError::InvalidArgument(
+                            "no recording is running".to_string(),
+                        ))
+                    }
+                    _ => {
+                        return Err(CtlError::InvalidArgument(
+                            "several recordings are running; give an ID".to_string(),
+                        ))
+                    }
+                },
+            };
+            let status = proxy.stop_recording(&recording_id).await?;
+            if json {
+                output::json(&status)?;
+            } else {
+                for file in &status.files {
+                    println!("{}", file);
+                }
+            }
+        }
+        RecordCommand::Status => {
+            let recordings = proxy.get_recordings().await?;
+            if json {
+                output::json(&recordings)?;
+            } else {
+                let channels = proxy.get_channels().await?;
+                output::recordings(&recordings, &channels);
+            }
+        }
+        RecordCommand::Dir { directory: None } => {
+            let directory = proxy.get_recording_directory().await?;
+            if json {
+                output::json(&json!({ "directory": directory }))?;
+            } else {
+                println!("{}", directory);
+            }
+        }
+        RecordCommand::Dir {
+            directory: Some(directory),
+        } => proxy.set_recording_directory(&directory).await?,
+    }
+    Ok(())
+}
+
+async fn run_replay(
+    proxy: &DaemonProxy<'_>,
+    command: ReplayCommand,
+    json: bool,
+) -> Result<(), CtlError> {
+    match command {
+        ReplayCommand::Enable { channel } => {
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            proxy.set_channel_replay(&channel.id, true).await?;
+        }
+        ReplayCommand::Disable { channel } => {
+            let channels = proxy.get_channels().await?;
+            let channel = resolve::channel(&channels, &channel)?;
+            proxy.set_channel_replay(&channel.id, false).await?;
+        }
+        ReplayCommand::Status => {
+            let buffers = proxy.get_replay_buffers().await?;
+            if json {
+                output::json(&buffers)?;
+            } else {
+                let channels = proxy.get_channels().await?;
+                output::replay_buffers(&buffers, &channels);
+            }
+        }
+        ReplayCommand::Length { seconds: None } => {
+            let seconds = proxy.get_replay_length().await?;
+            if json {
+                output::json(&json!({ "seconds": seconds }))?;
+            } else {
+                println!("{}", seconds);
+            }
+        }
+        ReplayCommand::Length {
+            seconds: Some(seconds),
+        } => proxy.set_replay_length(seconds).await?,
+        ReplayCommand::Save {
+            channels,
+            seconds,
+            format,
+        } => {
+            let all = proxy.get_channels().await?;
+            let ids = channels
+                .iter()
+                .map(|c| resolve::channel(&all, c).map(|c| c.id.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let files = proxy
+                .save_replay(ids, seconds.unwrap_or(0), &value_name(format))
+                .await?;
+            if json {
+                output::json(&files)?;
+            } else {
+                for file in &files {
+                    println!("{}", file);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Build the target half of a binding, resolving the channel.
+async fn midi_target(
+    proxy: &DaemonProxy<'_>,
+    args: MidiTargetArgs,
+) -> Result<MidiBindingInfo, CtlError> {
+    let needs_channel = !matches!(
+        args.target,
+        MidiTarget::MasterVolume | MidiTarget::MasterMute
+    );
+    let channel_id = match (needs_channel, args.channel) {
+        (true, Some(channel)) => {
+            let channels = proxy.get_channels().await?;
+            resolve::channel(&channels, &channel)?.id.clone()
+        }
+        (true, None) => {
+            return Err(CtlError::InvalidArgument(format!(
+                "{} needs a channel",
+                value_name(args.target)
+            )))
+        }
+        (false, _) => String::new(),
+    };
+    Ok(MidiBindingInfo {
+        // The daemon spells targets with underscores
+        target: value_name(args.target).replace('-', "_"),
+        channel_id,
+        plugin_slot: args.slot,
+        param_index: args.param,
+        ..Default::default()
+    })
+}
+
+async fn run_midi(
+    proxy: &DaemonProxy<'_>,
+    command: MidiCommand,
+    json: bool,
+) -> Result<(), CtlError> {
+    match command {
+        MidiCommand::Devices => {
+            let devices = proxy.get_midi_devices().await?;
+            if json {
+                output::json(&devices)?;
+            } else {
+                for device in devices {
+                    println!("{}", device);
+                }
+            }
+        }
+        MidiCommand::List => {
+            let bindings = proxy.get_midi_bindings().await?;
+            if json {
+                output::json(&bindings)?;
+            } else {
+                let channels = proxy.get_channels().await?;
+                output::midi_bindings(&bindings, &channels);
+            }
+        }
+        MidiCommand::Bind {
+            target,
+            cc,
+            note,
+            midi_channel,
+            device,
+        } => {
+            let (control, number) = match (cc, note) {
+                (Some(cc), _) => ("cc", cc),
+                (None, Some(note)) => ("note", note),
+                (None, None) => unreachable!("clap requires --cc or --note"),
+            };
+            let binding = MidiBindingInfo {
+                device: device.unwrap_or_default(),
+                midi_channel,
+                control: control.to_string(),
+                number,
+                ..midi_target(proxy, target).await?
+            };
+            let id = proxy.set_midi_binding(binding).await?;
+            if json {
+                output::json(&json!({ "id": id }))?;
+            } else {
+                println!("{}", id);
+            }
+        }
+        MidiCommand::Learn { target } => {
+            let binding = midi_target(proxy, target).await?;
+            let mut learned = proxy.receive_midi_binding_learned().await?;
+            proxy.start_midi_learn(binding).await?;
+            if !json {
+                eprintln!("Move a control on your MIDI controller (Ctrl-C to cancel)...");
+            }
+            let binding = tokio::select! {
+                Some(signal) = learned.next() => signal.args()?.binding,
+                _ = tokio::signal::ctrl_c() => {
+                    proxy.cancel_midi_learn().await?;
+                    return Ok(());
+                }
+            };
+            if json {
+                output::json(&binding)?;
+            } else {
+                let channels = proxy.get_channels().await?;
+                output::midi_bindings(&[binding], &channels);
+            }
+        }
+        MidiCommand::Delete { binding_id } => proxy.delete_midi_binding(&binding_id).await?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_negative_volume_parses() {
+        let cli = Cli::try_parse_from(["sootmixctl", "volume", "music", "-12.5"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Volume { volume_db, .. } if volume_db == -12.5
+        ));
+    }
+
+    #[test]
+    fn test_pan_parses() {
+        let cli = Cli::try_parse_from(["sootmixctl", "pan", "Music", "-0.5"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Pan { pan, width: None, .. } if pan == -0.5
+        ));
+        let cli =
+            Cli::try_parse_from(["sootmixctl", "pan", "Music", "0", "--width", "0.5"]).unwrap();
+        assert!(matches!(cli.command, Command::Pan { width: Some(w), .. } if w == 0.5));
+    }
+
+    #[test]
+    fn test_layout_parses() {
+        let cli = Cli::try_parse_from(["sootmixctl", "layout", "Game", "7.1"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Layout {
+                layout: ChannelLayout::Surround71,
+                ..
+            }
+        ));
+        assert!(Cli::try_parse_from(["sootmixctl", "layout", "Game", "quad"]).is_err());
+    }
+
+    #[test]
+    fn test_monitor_commands_parse() {
+        let cli = Cli::try_parse_from(["sootmixctl", "solo", "Game", "on"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Solo {
+                state: MuteState::On,
+                ..
+            }
+        ));
+        let cli = Cli::try_parse_from(["sootmixctl", "sidetone", "Mic", "on", "--volume", "-18"])
+            .unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Sidetone { volume: Some(v), .. } if v == -18.0
+        ));
+        let cli = Cli::try_parse_from(["sootmixctl", "monitor", "volume", "-6"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Monitor(MonitorCommand::Volume { volume_db }) if volume_db == -6.0
+        ));
+        let cli = Cli::try_parse_from(["sootmixctl", "monitor", "mode", "in-place"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Monitor(MonitorCommand::Mode {
+                mode: SoloMode::InPlace
+            })
+        ));
+        assert!(Cli::try_parse_from(["sootmixctl", "monitor", "mode", "afl"]).is_err());
+    }
+
+    #[test]
+    fn test_auto_level_parses() {
+        let cli = Cli::try_parse_from([
+            "sootmixctl",
+            "auto-level",
+            "Music",
+            "on",
+            "--target",
+            "-16",
+            "--max-boost",
+            "6",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::AutoLevel {
+                state,
+                target,
+                max_boost,
+                max_cut,
+                speed,
+                ..
+            } => {
+                assert!(matches!(state, MuteState::On));
+                assert_eq!((target, max_boost), (Some(-16.0), Some(6.0)));
+                assert_eq!((max_cut, speed), (None, None));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_noise_parses() {
+        let cli = Cli::try_parse_from([
+            "sootmixctl",
+            "noise",
+            "Mic",
+            "--model",
+            "gate",
+            "--retro-grace",
+            "100",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Noise {
+                model,
+                grace,
+                retro_grace,
+                attenuation_limit,
+                ..
+            } => {
+                assert_eq!(model, Some(NoiseModel::Gate));
+                assert_eq!((grace, retro_grace), (None, Some(100.0)));
+                assert_eq!(attenuation_limit, None);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(Cli::try_parse_from(["sootmixctl", "noise", "Mic", "--model", "speex"]).is_err());
+    }
+
+    #[test]
+    fn test_talk_parses() {
+        let cli = Cli::try_parse_from([
+            "sootmixctl",
+            "talk",
+            "Mic",
+            "--mode",
+            "push-to-talk",
+            "--release",
+            "300",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Talk {
+                mode,
+                release,
+                fade,
+                ..
+            } => {
+                assert_eq!(mode, Some(TalkMode::PushToTalk));
+                assert_eq!((release, fade), (Some(300.0), None));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let cli = Cli::try_parse_from(["sootmixctl", "talk-key", "press"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::TalkKey {
+                state: KeyState::Press,
+                channel: None
+            }
+        ));
+    }
+
+    #[test]
+    fn test_reset_loudness_target_is_optional() {
+        let cli = Cli::try_parse_from(["sootmixctl", "reset-loudness"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::ResetLoudness { target: None }
+        ));
+        let cli = Cli::try_parse_from(["sootmixctl", "reset-loudness", "master"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::ResetLoudness { target: Some(t) } if t == "master"
+        ));
+    }
+
+    #[test]
+    fn test_rule_property_conditions_parse() {
+        let cli = Cli::try_parse_from([
+            "sootmixctl",
+            "rules",
+            "add",
+            "--name",
+            "Calls",
+            "--channel",
+            "Voice",
+            "--property",
+            "media.role=Communication",
+            "--property",
+            "pipewire.access.portal.app_id=com.discordapp.Discord",
+            "--any",
+        ])
+        .unwrap();
+        let Command::Rules(RulesCommand::Add {
+            pattern,
+            properties,
+            any,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected rules add");
+        };
+        assert!(pattern.is_empty());
+        assert!(any);
+        assert_eq!(
+            properties[1],
+            (
+                "pipewire.access.portal.app_id".to_string(),
+                "com.discordapp.Discord".to_string()
+            )
+        );
+        assert!(parse_property("no-equals").is_err());
+    }
+
+    #[test]
+    fn test_ducking_add_parses() {
+        let cli = Cli::try_parse_from([
+            "sootmixctl",
+            "ducking",
+            "add",
+            "--trigger",
+            "Mic",
+            "--duck",
+            "Music",
+            "--duck",
+            "Game",
+            "--threshold-db",
+            "-35",
+        ])
+        .unwrap();
+        let Command::Ducking(DuckingCommand::Add {
+            ducked,
+            threshold_db,
+            amount_db,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected ducking add");
+        };
+        assert_eq!(ducked, ["Music", "Game"]);
+        assert_eq!(threshold_db, -35.0);
+        assert_eq!(amount_db, 12.0);
+        assert!(
+            Cli::try_parse_from(["sootmixctl", "ducking", "add", "--trigger", "Mic"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_scene_recall_fade_default() {
+        let cli = Cli::try_parse_from(["sootmixctl", "scenes", "recall", "Streaming"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Scenes(ScenesCommand::Recall { fade_ms: 1000, .. })
+        ));
+    }
+
+    #[test]
+    fn test_record_start_parses() {
+        let cli = Cli::try_parse_from(["sootmixctl", "record", "start", "Game", "master"]).unwrap();
+        match cli.command {
+            Command::Record(RecordCommand::Start { sources, format }) => {
+                assert_eq!(sources, ["Game", "master"]);
+                assert_eq!(value_name(format), "wav");
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(Cli::try_parse_from(["sootmixctl", "record", "start"]).is_err());
+    }
+
+    #[test]
+    fn test_replay_save_parses() {
+        let cli = Cli::try_parse_from(["sootmixctl", "replay", "save", "--seconds", "30", "Voice"])
+            .unwrap();
+        match cli.command {
+            Command::Replay(ReplayCommand::Save {
+                channels,
+                seconds,
+                format,
+            }) => {
+                assert_eq!(channels, ["Voice"]);
+                assert_eq!(seconds, Some(30));
+                assert_eq!(value_name(format), "wav");
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_midi_bind_parses() {
+        let cli = Cli::try_parse_from([
+            "sootmixctl",
+            "midi",
+            "bind",
+            "channel-volume",
+            "Music",
+            "--cc",
+            "7",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Midi(MidiCommand::Bind {
+                target,
+                cc,
+                note,
+                midi_channel,
+                device,
+            }) => {
+                assert_eq!(value_name(target.target), "channel-volume");
+                assert_eq!(target.channel.as_deref(), Some("Music"));
+                assert_eq!((cc, note, midi_channel), (Some(7), None, 1));
+                assert!(device.is_none());
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        // Exactly one of --cc and --note
+        assert!(Cli::try_parse_from(["sootmixctl", "midi", "bind", "master-mute"]).is_err());
+        assert!(Cli::try_parse_from([
+            "sootmixctl",
+            "midi",
+            "bind",
+            "master-mute",
+            "--cc",
+            "1",
+            "--note",
+            "2"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_value_names_match_daemon_strings() {
+        assert_eq!(value_name(MatchType::Regex), "regex");
+        assert_eq!(value_name(MatchTarget::Either), "either");
+    }
+}
@@ -0,0 +1,310 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Plain-text and JSON rendering of daemon state.
+//!
+//! Plain output is a whitespace-aligned table meant for people; JSON output
+//! is the IPC structs serialized as-is so scripts see the D-Bus field names.
+
+use crate::CtlError;
+use serde::Serialize;
+use sootmix_ipc::{
+    AppInfo, ChannelInfo, ChannelKind, DuckingInfo, InputInfo, MidiBindingInfo, MonitorInfo,
+    OutputInfo, RecordingStatus, ReplayBufferInfo, RoutingRuleInfo,
+};
+
+/// Print any serializable value as pretty JSON.
+pub fn json<T: Serialize + ?Sized>(value: &T) -> Result<(), CtlError> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Print rows as a left-aligned table with a header line.
+fn table(header: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<width$}", c, width = *w))
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+
+    line(header.to_vec());
+    for row in &rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+fn or_default(s: &str) -> &str {
+    if s.is_empty() {
+        "(default)"
+    } else {
+        s
+    }
+}
+
+/// Pan as "C", "L50" or "R100" (percent towards that side).
+fn pan(pan: f64) -> String {
+    let percent = (pan.abs() * 100.0).round();
+    if percent == 0.0 {
+        "C".to_string()
+    } else if pan < 0.0 {
+        format!("L{}", percent)
+    } else {
+        format!("R{}", percent)
+    }
+}
+
+/// Processing latency in samples, noting how much of it is alignment delay.
+fn latency(channel: &ChannelInfo) -> String {
+    match (
+        channel.latency_samples,
+        channel.latency_compensation_samples,
+    ) {
+        (0, _) => "-".to_string(),
+        (total, 0) => format!("{} smp", total),
+        (total, compensation) => format!("{} smp ({} aligned)", total, compensation),
+    }
+}
+
+/// Auto level gain and target, or "-" when auto level is off.
+fn auto_level(channel: &ChannelInfo) -> String {
+    if channel.auto_level.enabled {
+        format!(
+            "{:+.1} dB (to {:.0} LUFS)",
+            channel.auto_level_gain_db, channel.auto_level.target_lufs
+        )
+    } else {
+        "-".to_string()
+    }
+}
+
+pub fn channels(channels: &[ChannelInfo]) {
+    table(
+        &[
+            "ID", "NAME", "KIND", "LAYOUT", "VOLUME", "PAN", "MUTE", "SOLO", "AUTO", "LATENCY",
+            "OUTPUT", "APPS",
+        ],
+        channels
+            .iter()
+            .map(|c| {
+                vec![
+                    c.id.clone(),
+                    c.name.clone(),
+                    match c.kind {
+                        ChannelKind::Output => "output".to_string(),
+                        ChannelKind::Input => "input".to_string(),
+                    },
+                    c.layout.to_string(),
+                    format!("{:.1} dB", c.volume_db),
+                    pan(c.pan),
+                    if c.muted { "yes" } else { "no" }.to_string(),
+                    if c.solo { "yes" } else { "no" }.to_string(),
+                    auto_level(c),
+                    latency(c),
+                    or_default(&c.output_device).to_string(),
+                    c.assigned_apps.join(", "),
+                ]
+            })
+            .collect(),
+    );
+}
+
+pub fn monitor(monitor: &MonitorInfo) {
+    println!("device:    {}", or_default(&monitor.device));
+    println!("volume:    {:.1} dB", monitor.volume_db);
+    println!("muted:     {}", if monitor.muted { "yes" } else { "no" });
+    println!("solo mode: {}", monitor.solo_mode);
+}
+
+pub fn apps(apps: &[AppInfo]) {
+    table(
+        &["NODE", "APP", "NAME", "BINARY", "MEDIA"],
+        apps.iter()
+            .map(|a| {
+                vec![
+                    a.node_id.to_string(),
+                    a.identifier(),
+                    a.name.clone(),
+                    a.binary.clone(),
+                    a.media_name.clone(),
+                ]
+            })
+            .collect(),
+    );
+}
+
+pub fn outputs(outputs: &[OutputInfo]) {
+    table(
+        &["NODE", "NAME", "DESCRIPTION"],
+        outputs
+            .iter()
+            .map(|o| vec![o.node_id.to_string(), o.name.clone(), o.description.clone()])
+            .collect(),
+    );
+}
+
+pub fn inputs(inputs: &[InputInfo]) {
+    table(
+        &["NODE", "NAME", "DESCRIPTION"],
+        inputs
+            .iter()
+            .map(|i| vec![i.node_id.to_string(), i.name.clone(), i.description.clone()])
+            .collect(),
+    );
+}
+
+pub fn rules(rules: &[RoutingRuleInfo]) {
+    table(
+        &["ID", "NAME", "ON", "PRIO", "MATCH", "PATTERN", "CHANNEL"],
+        rules
+            .iter()
+            .map(|r| {
+                vec![
+                    r.id.clone(),
+                    r.name.clone(),
+                    if r.enabled { "yes" } else { "no" }.to_string(),
+                    r.priority.to_string(),
+                    format!("{} {}", r.match_target, r.match_type),
+                    rule_pattern(r),
+                    r.target_channel.clone(),
+                ]
+            })
+            .collect(),
+    );
+}
+
+/// The name/binary pattern followed by any property conditions.
+fn rule_pattern(rule: &RoutingRuleInfo) -> String {
+    let joiner = if rule.combine == "any" { " or " } else { " and " };
+    let mut parts: Vec<String> = Vec::new();
+    if !rule.pattern.is_empty() {
+        parts.push(rule.pattern.clone());
+    }
+    parts.extend(
+        rule.conditions
+            .iter()
+            .map(|c| format!("{} {} {}", c.property, c.match_type, c.pattern)),
+    );
+    parts.join(joiner)
+}
+
+pub fn ducking(ducking: &[DuckingInfo], channels: &[ChannelInfo]) {
+    let name = |id: &str| {
+        channels
+            .iter()
+            .find(|c| c.id == id)
+            .map_or_else(|| id.to_string(), |c| c.name.clone())
+    };
+    table(
+        &["ID", "ON", "TRIGGER", "DUCKS", "THRESHOLD", "AMOUNT", "A/H/R MS"],
+        ducking
+            .iter()
+            .map(|d| {
+                vec![
+                    d.id.clone(),
+                    if d.enabled { "yes" } else { "no" }.to_string(),
+                    name(&d.trigger_channel),
+                    d.ducked_channels
+                        .iter()
+                        .map(|c| name(c))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    format!("{:.1} dB", d.threshold_db),
+                    format!("{:.1} dB", d.amount_db),
+                    format!("{:.0}/{:.0}/{:.0}", d.attack_ms, d.hold_ms, d.release_ms),
+                ]
+            })
+            .collect(),
+    );
+}
+
+pub fn recordings(recordings: &[RecordingStatus], channels: &[ChannelInfo]) {
+    let name = |id: &str| {
+        channels
+            .iter()
+            .find(|c| c.id == id)
+            .map_or_else(|| id.to_string(), |c| c.name.clone())
+    };
+    table(
+        &["ID", "FORMAT", "TIME", "SIZE", "DROPPED", "SOURCES"],
+        recordings
+            .iter()
+            .map(|r| {
+                vec![
+                    r.id.clone(),
+                    r.format.clone(),
+                    format!("{:.1}s", r.elapsed_secs),
+                    format!("{:.1} MB", r.bytes as f64 / 1_000_000.0),
+                    r.dropped_frames.to_string(),
+                    r.sources
+                        .iter()
+                        .map(|s| name(s))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ]
+            })
+            .collect(),
+    );
+}
+
+pub fn replay_buffers(buffers: &[ReplayBufferInfo], channels: &[ChannelInfo]) {
+    table(
+        &["CHANNEL", "BUFFERED", "DROPPED"],
+        buffers
+            .iter()
+            .map(|b| {
+                vec![
+                    channels
+                        .iter()
+                        .find(|c| c.id == b.channel_id)
+                        .map_or_else(|| b.channel_id.clone(), |c| c.name.clone()),
+                    format!("{:.1}s", b.buffered_secs),
+                    b.dropped_frames.to_string(),
+                ]
+            })
+            .collect(),
+    );
+}
+
+pub fn midi_bindings(bindings: &[MidiBindingInfo], channels: &[ChannelInfo]) {
+    table(
+        &["ID", "DEVICE", "CONTROL", "TARGET"],
+        bindings
+            .iter()
+            .map(|b| {
+                let channel = channels
+                    .iter()
+                    .find(|c| c.id == b.channel_id)
+                    .map_or_else(|| b.channel_id.clone(), |c| c.name.clone());
+                let target = match b.target.as_str() {
+                    "master_volume" | "master_mute" => b.target.clone(),
+                    "plugin_parameter" => format!(
+                        "plugin_parameter {} slot {} param {}",
+                        channel, b.plugin_slot, b.param_index
+                    ),
+                    _ => format!("{} {}", b.target, channel),
+                };
+                vec![
+                    b.id.clone(),
+                    if b.device.is_empty() {
+                        "(any)".to_string()
+                    } else {
+                        b.device.clone()
+                    },
+                    format!("ch{} {} {}", b.midi_channel, b.control, b.number),
+                    target,
+                ]
+            })
+            .collect(),
+    );
+}
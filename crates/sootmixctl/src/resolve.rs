@@ -0,0 +1,160 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Resolution of user-typed channel and app references.
+//!
+//! The daemon addresses channels by UUID and apps by PipeWire node ID, neither
+//! of which anyone wants to type. These helpers accept the friendlier forms
+//! too: a channel name or ID prefix, or an app name, binary or identifier.
+
+use crate::CtlError;
+use sootmix_ipc::{AppInfo, ChannelInfo};
+
+/// Find the channel a user meant.
+///
+/// Tries, in order: exact ID, case-insensitive name, then a unique ID prefix.
+pub fn channel<'a>(channels: &'a [ChannelInfo], query: &str) -> Result<&'a ChannelInfo, CtlError> {
+    if let Some(c) = channels.iter().find(|c| c.id == query) {
+        return Ok(c);
+    }
+
+    let by_name: Vec<&ChannelInfo> = channels
+        .iter()
+        .filter(|c| c.name.eq_ignore_ascii_case(query))
+        .collect();
+    match by_name.as_slice() {
+        [c] => return Ok(c),
+        [] => {}
+        many => return Err(ambiguous(query, many.iter().map(|c| c.id.as_str()))),
+    }
+
+    let by_prefix: Vec<&ChannelInfo> = channels
+        .iter()
+        .filter(|c| c.id.starts_with(query))
+        .collect();
+    match by_prefix.as_slice() {
+        [c] => Ok(c),
+        [] => Err(CtlError::ChannelNotFound(query.to_string())),
+        many => Err(ambiguous(query, many.iter().map(|c| c.id.as_str()))),
+    }
+}
+
+/// Find the app a user meant and return the node ID the daemon expects.
+///
+/// A numeric query is taken as a node ID. Otherwise the app's identifier,
+/// name and binary are compared case-insensitively. Streams from one app share
+/// an identifier and the daemon moves them together, so any match will do.
+pub fn app_node_id(apps: &[AppInfo], query: &str) -> Result<u32, CtlError> {
+    if let Ok(node_id) = query.parse::<u32>() {
+        if apps.iter().any(|a| a.node_id == node_id) {
+            return Ok(node_id);
+        }
+    }
+
+    apps.iter()
+        .find(|a| {
+            a.identifier().eq_ignore_ascii_case(query)
+                || a.name.eq_ignore_ascii_case(query)
+                || a.binary.eq_ignore_ascii_case(query)
+        })
+        .map(|a| a.node_id)
+        .ok_or_else(|| CtlError::AppNotFound(query.to_string()))
+}
+
+fn ambiguous<'a>(query: &str, ids: impl Iterator<Item = &'a str>) -> CtlError {
+    CtlError::AmbiguousChannel(query.to_string(), ids.collect::<Vec<_>>().join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sootmix_ipc::{AutoLevelInfo, ChannelKind, ChannelLayout, NoiseSuppressionInfo, TalkInfo};
+
+    fn channel_info(id: &str, name: &str) -> ChannelInfo {
+        ChannelInfo {
+            id: id.to_string(),
+            name: name.to_string(),
+            volume_db: 0.0,
+            muted: false,
+            eq_enabled: false,
+            eq_preset: "Flat".to_string(),
+            eq_bands: Vec::new(),
+            eq_custom: false,
+            assigned_apps: Vec::new(),
+            output_device: String::new(),
+            meter_levels: (-60.0, -60.0),
+            kind: ChannelKind::Output,
+            input_gain_db: 0.0,
+            pan: 0.0,
+            width: 1.0,
+            layout: ChannelLayout::Stereo,
+            solo: false,
+            sidetone_enabled: false,
+            sidetone_volume_db: -20.0,
+            latency_samples: 0,
+            latency_compensation_samples: 0,
+            auto_level: AutoLevelInfo::default(),
+            auto_level_gain_db: 0.0,
+            noise_suppression: NoiseSuppressionInfo::default(),
+            talking: false,
+            talk: TalkInfo::default(),
+            live: true,
+        }
+    }
+
+    fn app(node_id: u32, name: &str, binary: &str) -> AppInfo {
+        AppInfo {
+            id: node_id.to_string(),
+            name: name.to_string(),
+            binary: binary.to_string(),
+            icon: String::new(),
+            media_name: String::new(),
+            node_id,
+            stream_index: 0,
+        }
+    }
+
+    #[test]
+    fn test_channel_by_name_prefix_and_id() {
+        let channels = vec![
+            channel_info("1111-aaaa", "Music"),
+            channel_info("2222-bbbb", "Game"),
+        ];
+
+        assert_eq!(channel(&channels, "music").unwrap().id, "1111-aaaa");
+        assert_eq!(channel(&channels, "2222").unwrap().name, "Game");
+        assert_eq!(channel(&channels, "2222-bbbb").unwrap().name, "Game");
+        assert!(matches!(
+            channel(&channels, "Voice"),
+            Err(CtlError::ChannelNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_channel_ambiguous() {
+        let channels = vec![
+            channel_info("1111-aaaa", "Chat"),
+            channel_info("1111-bbbb", "chat"),
+        ];
+
+        assert!(matches!(
+            channel(&channels, "CHAT"),
+            Err(CtlError::AmbiguousChannel(..))
+        ));
+        assert!(matches!(
+            channel(&channels, "1111"),
+            Err(CtlError::AmbiguousChannel(..))
+        ));
+    }
+
+    #[test]
+    fn test_app_node_id() {
+        let apps = vec![app(42, "Firefox", "firefox"), app(57, "Spotify", "spotify")];
+
+        assert_eq!(app_node_id(&apps, "57").unwrap(), 57);
+        assert_eq!(app_node_id(&apps, "firefox").unwrap(), 42);
+        assert_eq!(app_node_id(&apps, "SPOTIFY").unwrap(), 57);
+        assert!(app_node_id(&apps, "99").is_err());
+    }
+}
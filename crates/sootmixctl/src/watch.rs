@@ -0,0 +1,342 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! `sootmixctl watch`: print daemon signals as they arrive.
+//!
+//! In JSON mode each signal is one line of JSON (newline-delimited), tagged
+//! with a `signal` field, so the stream can be piped into `jq` or a script.
+
+use crate::CtlError;
+use futures::StreamExt;
+use serde::Serialize;
+use sootmix_ipc::{
+    AppInfo, ChannelInfo, DaemonProxy, LoudnessData, MeterData, MidiBindingInfo, MonitorInfo,
+    RecordingStatus,
+};
+use std::fmt;
+
+/// A daemon signal, flattened for printing.
+#[derive(Debug, Serialize)]
+#[serde(tag = "signal", rename_all = "snake_case")]
+enum WatchEvent {
+    ChannelAdded { channel: ChannelInfo },
+    ChannelRemoved { channel_id: String },
+    ChannelUpdated { channel: ChannelInfo },
+    VolumeChanged { channel_id: String, volume_db: f64 },
+    MuteChanged { channel_id: String, muted: bool },
+    AppDiscovered { app: AppInfo },
+    AppRemoved { app_id: String },
+    AppRouted { app_id: String, channel_id: String },
+    AppUnrouted { app_id: String, channel_id: String },
+    ConnectionChanged { connected: bool },
+    ErrorOccurred { message: String },
+    MeterUpdate { data: Vec<MeterData> },
+    LoudnessUpdate { data: Vec<LoudnessData> },
+    MasterVolumeChanged { volume_db: f64 },
+    MasterMuteChanged { muted: bool },
+    OutputsChanged,
+    InputsChanged,
+    RecordingProgress { status: RecordingStatus },
+    MidiBindingLearned { binding: MidiBindingInfo },
+    SoloChanged { channel_id: String, solo: bool },
+    ChannelTalking { channel_id: String, talking: bool },
+    ChannelLiveChanged { channel_id: String, live: bool },
+    MonitorChanged { monitor: MonitorInfo },
+}
+
+impl fmt::Display for WatchEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChannelAdded { channel } => {
+                write!(f, "channel-added {} ({})", channel.id, channel.name)
+            }
+            Self::ChannelRemoved { channel_id } => write!(f, "channel-removed {}", channel_id),
+            Self::ChannelUpdated { channel } => {
+                write!(f, "channel-updated {} ({})", channel.id, channel.name)
+            }
+            Self::VolumeChanged {
+                channel_id,
+                volume_db,
+            } => write!(f, "volume-changed {} {:.1} dB", channel_id, volume_db),
+            Self::MuteChanged { channel_id, muted } => {
+                write!(f, "mute-changed {} {}", channel_id, on_off(*muted))
+            }
+            Self::AppDiscovered { app } => {
+                write!(f, "app-discovered {} ({})", app.node_id, app.identifier())
+            }
+            Self::AppRemoved { app_id } => write!(f, "app-removed {}", app_id),
+            Self::AppRouted { app_id, channel_id } => {
+                write!(f, "app-routed {} -> {}", app_id, channel_id)
+            }
+            Self::AppUnrouted { app_id, channel_id } => {
+                write!(f, "app-unrouted {} -x {}", app_id, channel_id)
+            }
+            Self::ConnectionChanged { connected } => write!(
+                f,
+                "pipewire-{}",
+                if *connected {
+                    "connected"
+                } else {
+                    "disconnected"
+                }
+            ),
+            Self::ErrorOccurred { message } => write!(f, "error {}", message),
+            Self::MeterUpdate { data } => {
+                write!(f, "meters")?;
+                for m in data {
+                    write!(
+                        f,
+                        " {}={:.1}/{:.1}",
+                        m.channel_id(),
+                        m.level_left_db,
+                        m.level_right_db
+                    )?;
+                    if m.gain_reduction_db > 0.0 {
+                        write!(f, "(-{:.1})", m.gain_reduction_db)?;
+                    }
+                    if m.auto_level_gain_db != 0.0 {
+                        write!(f, "(auto {:+.1})", m.auto_level_gain_db)?;
+                    }
+                }
+                Ok(())
+            }
+            Self::LoudnessUpdate { data } => {
+                write!(f, "loudness")?;
+                for l in data {
+                    let id = if l.is_master() {
+                        "master".to_string()
+                    } else {
+                        l.channel_id().to_string()
+                    };
+                    write!(
+                        f,
+                        " {}={:.1}/{:.1}/{:.1}LUFS({:.1}LU,{:.1}dBTP)",
+                        id,
+                        l.momentary_lufs,
+                        l.short_term_lufs,
+                        l.integrated_lufs,
+                        l.range_lu,
+                        l.true_peak_dbtp
+                    )?;
+                }
+                Ok(())
+            }
+            Self::MasterVolumeChanged { volume_db } => {
+                write!(f, "master-volume-changed {:.1} dB", volume_db)
+            }
+            Self::MasterMuteChanged { muted } => {
+                write!(f, "master-mute-changed {}", on_off(*muted))
+            }
+            Self::OutputsChanged => write!(f, "outputs-changed"),
+            Self::InputsChanged => write!(f, "inputs-changed"),
+            Self::RecordingProgress { status } => write!(
+                f,
+                "recording-progress {} {:.1}s {} bytes",
+                status.id, status.elapsed_secs, status.bytes
+            ),
+            Self::MidiBindingLearned { binding } => write!(
+                f,
+                "midi-binding-learned {} {} ch {} {} {}",
+                binding.id, binding.device, binding.midi_channel, binding.control, binding.number
+            ),
+            Self::SoloChanged { channel_id, solo } => {
+                write!(f, "solo-changed {} {}", channel_id, on_off(*solo))
+            }
+            Self::ChannelTalking {
+                channel_id,
+                talking,
+            } => write!(f, "channel-talking {} {}", channel_id, on_off(*talking)),
+            Self::ChannelLiveChanged { channel_id, live } => {
+                write!(f, "channel-live-changed {} {}", channel_id, on_off(*live))
+            }
+            Self::MonitorChanged { monitor } => write!(
+                f,
+                "monitor-changed {} {:.1} dB muted={} {}",
+                if monitor.device.is_empty() {
+                    "(default)"
+                } else {
+                    &monitor.device
+                },
+                monitor.volume_db,
+                on_off(monitor.muted),
+                monitor.solo_mode
+            ),
+        }
+    }
+}
+
+fn on_off(b: bool) -> &'static str {
+    if b {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn print_event(event: &WatchEvent, json: bool) -> Result<(), CtlError> {
+    if json {
+        println!("{}", serde_json::to_string(event)?);
+    } else {
+        println!("{}", event);
+    }
+    Ok(())
+}
+
+/// Print signals until the daemon goes away or the user interrupts.
+///
+/// Meter and loudness updates arrive many times a second, so they are only
+/// printed when `meters` is set.
+pub async fn run(proxy: &DaemonProxy<'_>, json: bool, meters: bool) -> Result<(), CtlError> {
+    let mut channel_added = proxy.receive_channel_added().await?;
+    let mut channel_removed = proxy.receive_channel_removed().await?;
+    let mut channel_updated = proxy.receive_channel_updated().await?;
+    let mut volume_changed = proxy.receive_volume_changed().await?;
+    let mut mute_changed = proxy.receive_mute_changed().await?;
+    let mut app_discovered = proxy.receive_app_discovered().await?;
+    let mut app_removed = proxy.receive_app_removed().await?;
+    let mut app_routed = proxy.receive_app_routed().await?;
+    let mut app_unrouted = proxy.receive_app_unrouted().await?;
+    let mut connection_changed = proxy.receive_connection_changed().await?;
+    let mut error_occurred = proxy.receive_error_occurred().await?;
+    let mut meter_update = proxy.receive_meter_update().await?;
+    let mut loudness_update = proxy.receive_loudness_update().await?;
+    let mut master_volume_changed = proxy.receive_master_volume_changed().await?;
+    let mut master_mute_changed = proxy.receive_master_mute_changed().await?;
+    let mut outputs_changed = proxy.receive_outputs_changed().await?;
+    let mut inputs_changed = proxy.receive_inputs_changed().await?;
+    let mut recording_progress = proxy.receive_recording_progress().await?;
+    let mut midi_binding_learned = proxy.receive_midi_binding_learned().await?;
+    let mut solo_changed = proxy.receive_solo_changed().await?;
+    let mut channel_talking = proxy.receive_channel_talking().await?;
+    let mut channel_live_changed = proxy.receive_channel_live_changed().await?;
+    let mut monitor_changed = proxy.receive_monitor_changed().await?;
+    let mut owner_changed = proxy.inner().receive_owner_changed().await?;
+
+    loop {
+        let event = tokio::select! {
+            Some(signal) = channel_added.next() => {
+                let args = signal.args()?;
+                WatchEvent::ChannelAdded { channel: args.channel }
+            }
+            Some(signal) = channel_removed.next() => {
+                let args = signal.args()?;
+                WatchEvent::ChannelRemoved { channel_id: args.channel_id.to_string() }
+            }
+            Some(signal) = channel_updated.next() => {
+                let args = signal.args()?;
+                WatchEvent::ChannelUpdated { channel: args.channel }
+            }
+            Some(signal) = volume_changed.next() => {
+                let args = signal.args()?;
+                WatchEvent::VolumeChanged {
+                    channel_id: args.channel_id.to_string(),
+                    volume_db: args.volume_db,
+                }
+            }
+            Some(signal) = mute_changed.next() => {
+                let args = signal.args()?;
+                WatchEvent::MuteChanged {
+                    channel_id: args.channel_id.to_string(),
+                    muted: args.muted,
+                }
+            }
+            Some(signal) = app_discovered.next() => {
+                let args = signal.args()?;
+                WatchEvent::AppDiscovered { app: args.app }
+            }
+            Some(signal) = app_removed.next() => {
+                let args = signal.args()?;
+                WatchEvent::AppRemoved { app_id: args.app_id.to_string() }
+            }
+            Some(signal) = app_routed.next() => {
+                let args = signal.args()?;
+                WatchEvent::AppRouted {
+                    app_id: args.app_id.to_string(),
+                    channel_id: args.channel_id.to_string(),
+                }
+            }
+            Some(signal) = app_unrouted.next() => {
+                let args = signal.args()?;
+                WatchEvent::AppUnrouted {
+                    app_id: args.app_id.to_string(),
+                    channel_id: args.channel_id.to_string(),
+                }
+            }
+            Some(signal) = connection_changed.next() => {
+                let args = signal.args()?;
+                WatchEvent::ConnectionChanged { connected: args.connected }
+            }
+            Some(signal) = error_occurred.next() => {
+                let args = signal.args()?;
+                WatchEvent::ErrorOccurred { message: args.message.to_string() }
+            }
+            Some(signal) = meter_update.next() => {
+                if !meters {
+                    continue;
+                }
+                let args = signal.args()?;
+                WatchEvent::MeterUpdate { data: args.data }
+            }
+            Some(signal) = loudness_update.next() => {
+                if !meters {
+                    continue;
+                }
+                let args = signal.args()?;
+                WatchEvent::LoudnessUpdate { data: args.data }
+            }
+            Some(signal) = master_volume_changed.next() => {
+                let args = signal.args()?;
+                WatchEvent::MasterVolumeChanged { volume_db: args.volume_db }
+            }
+            Some(signal) = master_mute_changed.next() => {
+                let args = signal.args()?;
+                WatchEvent::MasterMuteChanged { muted: args.muted }
+            }
+            Some(_signal) = outputs_changed.next() => WatchEvent::OutputsChanged,
+            Some(_signal) = inputs_changed.next() => WatchEvent::InputsChanged,
+            Some(signal) = recording_progress.next() => {
+                let args = signal.args()?;
+                WatchEvent::RecordingProgress { status: args.status }
+            }
+            Some(signal) = midi_binding_learned.next() => {
+                let args = signal.args()?;
+                WatchEvent::MidiBindingLearned { binding: args.binding }
+            }
+            Some(signal) = solo_changed.next() => {
+                let args = signal.args()?;
+                WatchEvent::SoloChanged {
+                    channel_id: args.channel_id.to_string(),
+                    solo: args.solo,
+                }
+            }
+            Some(signal) = channel_talking.next() => {
+                let args = signal.args()?;
+                WatchEvent::ChannelTalking {
+                    channel_id: args.channel_id.to_string(),
+                    talking: args.talking,
+                }
+            }
+            Some(signal) = channel_live_changed.next() => {
+                let args = signal.args()?;
+                WatchEvent::ChannelLiveChanged {
+                    channel_id: args.channel_id.to_string(),
+                    live: args.live,
+                }
+            }
+            Some(signal) = monitor_changed.next() => {
+                let args = signal.args()?;
+                WatchEvent::MonitorChanged { monitor: args.monitor }
+            }
+            Some(owner) = owner_changed.next() => {
+                if owner.is_none() {
+                    return Err(CtlError::DaemonGone);
+                }
+                continue;
+            }
+            else => return Ok(()),
+        };
+
+        print_event(&event, json)?;
+    }
+}
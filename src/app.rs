@@ -6,12 +6,12 @@
 
 use crate::audio::types::PwLink;
 use crate::audio::{filter_chain, MeterManager, PluginFilterManager, PluginProcessorManager, PwCommand, PwEvent, PwThread};
-use crate::config::eq_preset::EqPreset;
+use crate::config::eq_preset::{EqBand, EqPreset};
 use crate::config::{ConfigManager, MixerConfig, SavedChannel};
 use crate::daemon_client::{self, DaemonEvent};
 use crate::message::Message;
 use crate::plugins::{PluginFilter, PluginManager, PluginSlotConfig, PluginType};
-use crate::state::{db_to_linear, AppState, EditingRule, MixerChannel, SnapshotSlot};
+use crate::state::{db_to_linear, AppState, AutoLevelInfo, ChannelLayout, ChannelSpectrum, EditingCondition, EditingRule, EqBandInfo, EqBandType, MixerChannel, NoiseSuppressionInfo, SnapshotSlot, SoloMode, SpectrumTap, TalkInfo, TalkMode};
 use crate::tray::{TrayHandle, TrayMessage};
 use crate::ui::apps_panel::apps_panel;
 use crate::ui::channel_strip::{app_card, channel_strip, master_strip};
@@ -57,6 +57,8 @@ pub struct SootMix {
     tray_handle: Option<TrayHandle>,
     /// Receiver for tray messages.
     tray_rx: Option<mpsc::Receiver<TrayMessage>>,
+    /// Whether the tray's talk item is holding the talk key down.
+    tray_talk_held: bool,
     /// Current main window ID (None when window is closed/hidden).
     main_window_id: Option<iced::window::Id>,
     /// Whether we're connected to the daemon (vs running standalone).
@@ -100,6 +102,12 @@ impl SootMix {
             }
         }
 
+        // List EQ presets for the editor
+        state.eq_presets = config_manager
+            .as_ref()
+            .and_then(|cm| cm.list_eq_presets().ok())
+            .unwrap_or_else(|| EqPreset::builtin_presets().into_iter().map(|p| p.name).collect());
+
         // Don't spawn PipeWire thread yet - wait to see if daemon is available.
         // If daemon connects, we'll use daemon mode.
         // If daemon doesn't connect, we'll spawn local PW thread on first tick.
@@ -154,6 +162,7 @@ impl SootMix {
             plugin_filter_manager,
             tray_handle,
             tray_rx,
+            tray_talk_held: false,
             main_window_id: Some(window_id),
             daemon_connected: false,
             activation_rx,
@@ -229,13 +238,34 @@ impl SootMix {
                 let editing_text = self.state.plugin_param_editing
                     .get(&(instance_id, idx))
                     .cloned();
+                let display = self
+                    .plugin_manager
+                    .format_parameter(instance_id, idx, normalized)
+                    .unwrap_or_else(|| format!("{:.2}", value));
+                let choices = self
+                    .plugin_manager
+                    .get_parameter_labels(instance_id, idx)
+                    .into_iter()
+                    .map(|label| crate::ui::plugin_chain::ParameterChoice {
+                        value: sootmix_plugin_api::normalize(
+                            label.value,
+                            param_info.min,
+                            param_info.max,
+                            param_info.curve,
+                        ),
+                        label: label.label.to_string(),
+                    })
+                    .collect();
                 Some(crate::ui::plugin_chain::PluginEditorParam {
                     index: idx,
                     name: param_info.name.to_string(),
                     unit: param_info.unit.to_string(),
                     min: param_info.min,
                     max: param_info.max,
+                    step: param_info.step,
                     value,
+                    display,
+                    choices,
                     curve: param_info.curve,
                     editing_text,
                 })
@@ -276,6 +306,59 @@ impl SootMix {
                 let new_muted = self.state.channel(id).map(|c| !c.muted).unwrap_or(false);
                 self.cmd_set_channel_mute(id, new_muted);
             }
+            Message::ChannelPanChanged(id, pan) => {
+                if let Some(channel) = self.state.channel_mut(id) {
+                    channel.pan = pan;
+                }
+            }
+            Message::ChannelWidthChanged(id, width) => {
+                if let Some(channel) = self.state.channel_mut(id) {
+                    channel.width = width;
+                }
+            }
+            Message::ChannelBalanceReleased(id) => {
+                self.cmd_set_channel_balance(id);
+            }
+            Message::ChannelLayoutChanged(id, layout) => {
+                self.cmd_set_channel_layout(id, layout);
+            }
+            Message::ChannelEqToggled(id) if self.daemon_connected => {
+                // The daemon owns the EQ filter chain when connected
+                let enabled = self.state.channel(id).map(|c| !c.eq_enabled).unwrap_or(false);
+                if let Err(e) = daemon_client::send_daemon_command(
+                    daemon_client::DaemonCommand::SetChannelEqEnabled {
+                        channel_id: id.to_string(),
+                        enabled,
+                    }
+                ) {
+                    error!("Failed to send set EQ enabled command to daemon: {}", e);
+                }
+                // Update local state immediately for responsive UI
+                if let Some(channel) = self.state.channel_mut(id) {
+                    channel.eq_enabled = enabled;
+                }
+            }
+            Message::ChannelEqPresetChanged(id, preset_name) if self.daemon_connected => {
+                if let Err(e) = daemon_client::send_daemon_command(
+                    daemon_client::DaemonCommand::SetChannelEqPreset {
+                        channel_id: id.to_string(),
+                        preset_name: preset_name.clone(),
+                    }
+                ) {
+                    error!("Failed to send set EQ preset command to daemon: {}", e);
+                }
+                // Show the preset's bands until the daemon confirms them
+                let preset = self.config_manager.as_ref()
+                    .and_then(|cm| cm.load_eq_preset(&preset_name).ok());
+                if let Some(channel) = self.state.channel_mut(id) {
+                    channel.eq_preset = preset_name;
+                    if let Some(preset) = preset {
+                        channel.eq_bands = preset.bands.iter().map(EqBandInfo::from).collect();
+                        channel.eq_custom = false;
+                    }
+                }
+                self.state.eq_selected_band = None;
+            }
             Message::ChannelEqToggled(id) => {
                 // Get channel info before mutating
                 let channel_info = self.state.channel(id).map(|c| {
@@ -409,142 +492,171 @@ impl SootMix {
             }
 
             Message::ChannelSidetoneToggled(channel_id) => {
-                let (sidetone_enabled, source_id) = self.state.channel(channel_id)
-                    .map(|c| (c.sidetone_enabled, c.pw_source_id))
-                    .unwrap_or((false, None));
-                let new_state = !sidetone_enabled;
-                if let Some(channel) = self.state.channel_mut(channel_id) {
-                    channel.sidetone_enabled = new_state;
-                }
-                // Sidetone: route the virtual source to the output device
-                if let Some(source_node_id) = source_id {
-                    if new_state {
-                        // Route source to default output
-                        if let Some(output_id) = self.get_output_device_node_id() {
-                            let port_pairs = self.state.pw_graph.find_port_pairs(source_node_id, output_id);
-                            for (out_port, in_port) in &port_pairs {
-                                self.send_pw_command(PwCommand::CreateLink {
-                                    output_port: *out_port,
-                                    input_port: *in_port,
-                                });
+                if self.daemon_connected {
+                    let (enabled, volume_db) = self.state.channel(channel_id)
+                        .map(|c| (c.sidetone_enabled, c.sidetone_volume_db))
+                        .unwrap_or((false, -20.0));
+                    self.cmd_set_channel_sidetone(channel_id, !enabled, volume_db);
+                } else {
+                    let (sidetone_enabled, source_id) = self.state.channel(channel_id)
+                        .map(|c| (c.sidetone_enabled, c.pw_source_id))
+                        .unwrap_or((false, None));
+                    let new_state = !sidetone_enabled;
+                    if let Some(channel) = self.state.channel_mut(channel_id) {
+                        channel.sidetone_enabled = new_state;
+                    }
+                    // Sidetone: route the virtual source to the output device
+                    if let Some(source_node_id) = source_id {
+                        if new_state {
+                            // Route source to default output
+                            if let Some(output_id) = self.get_output_device_node_id() {
+                                let port_pairs = self.state.pw_graph.find_port_pairs(source_node_id, output_id);
+                                for (out_port, in_port) in &port_pairs {
+                                    self.send_pw_command(PwCommand::CreateLink {
+                                        output_port: *out_port,
+                                        input_port: *in_port,
+                                    });
+                                }
                             }
-                        }
-                    } else {
-                        // Destroy sidetone links (source -> output)
-                        if let Some(output_id) = self.get_output_device_node_id() {
-                            let links: Vec<u32> = self.state.pw_graph.links.values()
-                                .filter(|l| l.output_node == source_node_id && l.input_node == output_id)
-                                .map(|l| l.id)
-                                .collect();
-                            for link_id in links {
-                                self.send_pw_command(PwCommand::DestroyLink { link_id });
+                        } else {
+                            // Destroy sidetone links (source -> output)
+                            if let Some(output_id) = self.get_output_device_node_id() {
+                                let links: Vec<u32> = self.state.pw_graph.links.values()
+                                    .filter(|l| l.output_node == source_node_id && l.input_node == output_id)
+                                    .map(|l| l.id)
+                                    .collect();
+                                for link_id in links {
+                                    self.send_pw_command(PwCommand::DestroyLink { link_id });
+                                }
                             }
                         }
                     }
+                    self.save_config();
                 }
-                self.save_config();
             }
 
             Message::ChannelSoloToggled(channel_id) => {
-                let (solo_enabled, output_node) = self.state.channel(channel_id)
-                    .map(|c| {
-                        let node = if c.is_input() {
-                            c.pw_source_id
-                        } else {
-                            c.pw_loopback_output_id
-                        };
-                        (c.solo, node)
-                    })
-                    .unwrap_or((false, None));
+                if self.daemon_connected {
+                    let solo = self.state.channel(channel_id).is_some_and(|c| c.solo);
+                    self.cmd_set_channel_solo(channel_id, !solo);
+                } else {
+                    let (solo_enabled, output_node) = self.state.channel(channel_id)
+                        .map(|c| {
+                            let node = if c.is_input() {
+                                c.pw_source_id
+                            } else {
+                                c.pw_loopback_output_id
+                            };
+                            (c.solo, node)
+                        })
+                        .unwrap_or((false, None));
 
-                let new_state = !solo_enabled;
-                if let Some(channel) = self.state.channel_mut(channel_id) {
-                    channel.solo = new_state;
-                }
+                    let new_state = !solo_enabled;
+                    if let Some(channel) = self.state.channel_mut(channel_id) {
+                        channel.solo = new_state;
+                    }
 
-                if let Some(source_node_id) = output_node {
-                    if let Some(monitor_id) = self.get_monitor_device_node_id() {
-                        if new_state {
-                            let port_pairs = self.state.pw_graph.find_port_pairs(source_node_id, monitor_id);
-                            for (out_port, in_port) in &port_pairs {
-                                self.send_pw_command(PwCommand::CreateLink {
-                                    output_port: *out_port,
-                                    input_port: *in_port,
-                                });
-                            }
-                        } else {
-                            let links: Vec<u32> = self.state.pw_graph.links.values()
-                                .filter(|l| l.output_node == source_node_id && l.input_node == monitor_id)
-                                .map(|l| l.id)
-                                .collect();
-                            for link_id in links {
-                                self.send_pw_command(PwCommand::DestroyLink { link_id });
+                    if let Some(source_node_id) = output_node {
+                        if let Some(monitor_id) = self.get_monitor_device_node_id() {
+                            if new_state {
+                                let port_pairs = self.state.pw_graph.find_port_pairs(source_node_id, monitor_id);
+                                for (out_port, in_port) in &port_pairs {
+                                    self.send_pw_command(PwCommand::CreateLink {
+                                        output_port: *out_port,
+                                        input_port: *in_port,
+                                    });
+                                }
+                            } else {
+                                let links: Vec<u32> = self.state.pw_graph.links.values()
+                                    .filter(|l| l.output_node == source_node_id && l.input_node == monitor_id)
+                                    .map(|l| l.id)
+                                    .collect();
+                                for link_id in links {
+                                    self.send_pw_command(PwCommand::DestroyLink { link_id });
+                                }
                             }
+                        } else if new_state {
+                            warn!("Solo toggled but no monitor device configured");
                         }
-                    } else if new_state {
-                        warn!("Solo toggled but no monitor device configured");
                     }
                 }
             }
 
             Message::MonitorDeviceChanged(name) => {
-                // Tear down existing solo links for all soloed channels → old monitor device
-                if let Some(old_monitor_id) = self.get_monitor_device_node_id() {
-                    let soloed: Vec<(Uuid, Option<u32>)> = self.state.channels.iter()
-                        .filter(|c| c.solo)
-                        .map(|c| {
-                            let node = if c.is_input() { c.pw_source_id } else { c.pw_loopback_output_id };
-                            (c.id, node)
-                        })
-                        .collect();
-                    for (_id, output_node) in &soloed {
-                        if let Some(source_node_id) = output_node {
-                            let links: Vec<u32> = self.state.pw_graph.links.values()
-                                .filter(|l| l.output_node == *source_node_id && l.input_node == old_monitor_id)
-                                .map(|l| l.id)
-                                .collect();
-                            for link_id in links {
-                                self.send_pw_command(PwCommand::DestroyLink { link_id });
+                if self.daemon_connected {
+                    self.cmd_set_monitor_device(name);
+                } else {
+                    // Tear down existing solo links for all soloed channels → old monitor device
+                    if let Some(old_monitor_id) = self.get_monitor_device_node_id() {
+                        let soloed: Vec<(Uuid, Option<u32>)> = self.state.channels.iter()
+                            .filter(|c| c.solo)
+                            .map(|c| {
+                                let node = if c.is_input() { c.pw_source_id } else { c.pw_loopback_output_id };
+                                (c.id, node)
+                            })
+                            .collect();
+                        for (_id, output_node) in &soloed {
+                            if let Some(source_node_id) = output_node {
+                                let links: Vec<u32> = self.state.pw_graph.links.values()
+                                    .filter(|l| l.output_node == *source_node_id && l.input_node == old_monitor_id)
+                                    .map(|l| l.id)
+                                    .collect();
+                                for link_id in links {
+                                    self.send_pw_command(PwCommand::DestroyLink { link_id });
+                                }
                             }
                         }
                     }
-                }
 
-                self.state.monitor_device = Some(name);
+                    self.state.monitor_device = Some(name);
 
-                // Re-create solo links for all soloed channels → new monitor device
-                if let Some(new_monitor_id) = self.get_monitor_device_node_id() {
-                    let soloed: Vec<Option<u32>> = self.state.channels.iter()
-                        .filter(|c| c.solo)
-                        .map(|c| if c.is_input() { c.pw_source_id } else { c.pw_loopback_output_id })
-                        .collect();
-                    for output_node in &soloed {
-                        if let Some(source_node_id) = output_node {
-                            let port_pairs = self.state.pw_graph.find_port_pairs(*source_node_id, new_monitor_id);
-                            for (out_port, in_port) in &port_pairs {
-                                self.send_pw_command(PwCommand::CreateLink {
-                                    output_port: *out_port,
-                                    input_port: *in_port,
-                                });
+                    // Re-create solo links for all soloed channels → new monitor device
+                    if let Some(new_monitor_id) = self.get_monitor_device_node_id() {
+                        let soloed: Vec<Option<u32>> = self.state.channels.iter()
+                            .filter(|c| c.solo)
+                            .map(|c| if c.is_input() { c.pw_source_id } else { c.pw_loopback_output_id })
+                            .collect();
+                        for output_node in &soloed {
+                            if let Some(source_node_id) = output_node {
+                                let port_pairs = self.state.pw_graph.find_port_pairs(*source_node_id, new_monitor_id);
+                                for (out_port, in_port) in &port_pairs {
+                                    self.send_pw_command(PwCommand::CreateLink {
+                                        output_port: *out_port,
+                                        input_port: *in_port,
+                                    });
+                                }
                             }
                         }
                     }
-                }
 
-                self.save_config();
+                    self.save_config();
+                }
             }
 
             Message::ChannelSidetoneVolumeChanged(channel_id, volume_db) => {
-                if let Some(channel) = self.state.channel_mut(channel_id) {
-                    channel.sidetone_volume_db = volume_db;
-                }
-                // Apply sidetone volume to the source node
-                if let Some(source_node_id) = self.state.channel(channel_id).and_then(|c| c.pw_source_id) {
-                    let linear = db_to_linear(volume_db);
-                    self.send_pw_command(PwCommand::SetVolume { node_id: source_node_id, volume: linear });
+                if self.daemon_connected {
+                    let enabled = self.state.channel(channel_id).is_some_and(|c| c.sidetone_enabled);
+                    self.cmd_set_channel_sidetone(channel_id, enabled, volume_db);
+                } else {
+                    if let Some(channel) = self.state.channel_mut(channel_id) {
+                        channel.sidetone_volume_db = volume_db;
+                    }
+                    // Apply sidetone volume to the source node
+                    if let Some(source_node_id) = self.state.channel(channel_id).and_then(|c| c.pw_source_id) {
+                        let linear = db_to_linear(volume_db);
+                        self.send_pw_command(PwCommand::SetVolume { node_id: source_node_id, volume: linear });
+                    }
                 }
             }
 
+            Message::SoloModeChanged(mode) => {
+                self.cmd_set_solo_mode(mode);
+            }
+
+            Message::ResetLoudness(channel_id) => {
+                self.cmd_reset_loudness(channel_id);
+            }
+
             Message::ChannelNoiseSuppressionToggled(channel_id) => {
                 let new_enabled = self.state.channel(channel_id)
                     .map(|c| !c.noise_suppression_enabled)
@@ -570,6 +682,54 @@ impl SootMix {
                 }
             }
 
+            Message::ChannelNoiseModelSelected(channel_id, model) => {
+                if let Some(channel) = self.state.channel(channel_id) {
+                    let mut settings = channel.noise_suppression;
+                    settings.model = model;
+                    self.cmd_set_channel_noise_suppression_settings(channel_id, settings);
+                }
+            }
+
+            Message::ChannelNoiseSuppressionChanged(channel_id, settings) => {
+                // Update local state only (don't send to daemon until released)
+                if let Some(channel) = self.state.channel_mut(channel_id) {
+                    channel.noise_suppression = settings;
+                }
+            }
+
+            Message::ChannelNoiseSuppressionReleased(channel_id) => {
+                if let Some(channel) = self.state.channel(channel_id) {
+                    let settings = channel.noise_suppression;
+                    self.cmd_set_channel_noise_suppression_settings(channel_id, settings);
+                }
+            }
+
+            Message::ChannelTalkModeSelected(channel_id, mode) => {
+                if let Some(channel) = self.state.channel(channel_id) {
+                    let mut settings = channel.talk;
+                    settings.mode = mode;
+                    self.cmd_set_channel_talk_settings(channel_id, settings);
+                }
+            }
+
+            Message::ChannelTalkChanged(channel_id, settings) => {
+                // Update local state only (don't send to daemon until released)
+                if let Some(channel) = self.state.channel_mut(channel_id) {
+                    channel.talk = settings;
+                }
+            }
+
+            Message::ChannelTalkReleased(channel_id) => {
+                if let Some(channel) = self.state.channel(channel_id) {
+                    let settings = channel.talk;
+                    self.cmd_set_channel_talk_settings(channel_id, settings);
+                }
+            }
+
+            Message::ChannelTalkKey(channel_id, pressed) => {
+                self.cmd_set_talk_key(Some(channel_id), pressed);
+            }
+
             Message::ChannelInputGainChanged(channel_id, gain_db) => {
                 // Update local state only (don't send to daemon until released)
                 if let Some(channel) = self.state.channel_mut(channel_id) {
@@ -585,6 +745,85 @@ impl SootMix {
                 }
             }
 
+            Message::ChannelAutoLevelToggled(channel_id) => {
+                if let Some(channel) = self.state.channel(channel_id) {
+                    let mut settings = channel.auto_level;
+                    settings.enabled = !settings.enabled;
+                    info!("Toggling auto level to {} for channel {}",
+                        settings.enabled, channel_id);
+                    self.cmd_set_channel_auto_level(channel_id, settings);
+                }
+            }
+
+            Message::ChannelAutoLevelChanged(channel_id, settings) => {
+                // Update local state only (don't send to daemon until released)
+                if let Some(channel) = self.state.channel_mut(channel_id) {
+                    channel.auto_level = settings;
+                }
+            }
+
+            Message::ChannelAutoLevelReleased(channel_id) => {
+                if let Some(channel) = self.state.channel(channel_id) {
+                    let settings = channel.auto_level;
+                    self.cmd_set_channel_auto_level(channel_id, settings);
+                }
+            }
+
+            // ==================== Parametric EQ ====================
+            Message::EqBandSelected(band) => {
+                self.state.eq_selected_band = band;
+            }
+
+            Message::EqBandChanged(channel_id, index, band) => {
+                // Update local state only (don't send to daemon until released)
+                if let Some(channel) = self.state.channel_mut(channel_id) {
+                    if let Some(slot) = channel.eq_bands.get_mut(index) {
+                        *slot = band;
+                        channel.eq_custom = true;
+                    }
+                }
+            }
+
+            Message::EqBandsReleased(channel_id) => {
+                self.cmd_set_channel_eq_bands(channel_id);
+            }
+
+            Message::EqBandSet(channel_id, index, band) => {
+                if let Some(channel) = self.state.channel_mut(channel_id) {
+                    if let Some(slot) = channel.eq_bands.get_mut(index) {
+                        *slot = band;
+                        channel.eq_custom = true;
+                    }
+                }
+                self.cmd_set_channel_eq_bands(channel_id);
+            }
+
+            Message::EqBandAdded(channel_id) => {
+                let mut added = None;
+                if let Some(channel) = self.state.channel_mut(channel_id) {
+                    if channel.eq_bands.len() < sootmix_ipc::EQ_MAX_BANDS {
+                        channel.eq_bands.push(EqBandInfo::new(EqBandType::Peaking, 1000.0));
+                        channel.eq_custom = true;
+                        added = Some(channel.eq_bands.len() - 1);
+                    }
+                }
+                if added.is_some() {
+                    self.state.eq_selected_band = added;
+                    self.cmd_set_channel_eq_bands(channel_id);
+                }
+            }
+
+            Message::EqBandRemoved(channel_id, index) => {
+                if let Some(channel) = self.state.channel_mut(channel_id) {
+                    if index < channel.eq_bands.len() {
+                        channel.eq_bands.remove(index);
+                        channel.eq_custom = true;
+                    }
+                }
+                self.state.eq_selected_band = None;
+                self.cmd_set_channel_eq_bands(channel_id);
+            }
+
             // ==================== App Drag & Drop ====================
             Message::StartDraggingApp(node_id, app_id) => {
                 info!("Started dragging app: {} (node {})", app_id, node_id);
@@ -923,11 +1162,24 @@ impl SootMix {
 
             // ==================== Layout & Selection ====================
             Message::SelectChannel(channel_id) => {
+                if channel_id != self.state.selected_channel {
+                    self.state.eq_selected_band = None;
+                }
                 self.state.selected_channel = channel_id;
                 // Close plugin browser when selecting a different channel
                 if channel_id != self.state.plugin_browser_channel {
                     self.state.plugin_browser_channel = None;
                 }
+                // The analyzer follows the focus, and stops with it
+                if let Some(spectrum) = &self.state.spectrum {
+                    if Some(spectrum.channel_id) != channel_id {
+                        let tap = channel_id.map(|_| spectrum.tap);
+                        self.cmd_set_spectrum(channel_id, tap);
+                    }
+                }
+            }
+            Message::SpectrumTapSelected(channel_id, tap) => {
+                self.cmd_set_spectrum(Some(channel_id), tap);
             }
             Message::ToggleLeftSidebar => {
                 self.state.left_sidebar_collapsed = !self.state.left_sidebar_collapsed;
@@ -1004,6 +1256,53 @@ impl SootMix {
                     }
                 }
             }
+            Message::RuleCombineChanged(combine) => {
+                if let Some(ref mut editing) = self.state.editing_rule {
+                    editing.combine = combine;
+                }
+            }
+            Message::AddRuleCondition => {
+                if let Some(ref mut editing) = self.state.editing_rule {
+                    editing.conditions.push(EditingCondition::default());
+                }
+            }
+            Message::RemoveRuleCondition(index) => {
+                if let Some(ref mut editing) = self.state.editing_rule {
+                    if index < editing.conditions.len() {
+                        editing.conditions.remove(index);
+                    }
+                }
+            }
+            Message::RuleConditionPropertyChanged(index, property) => {
+                if let Some(condition) = self
+                    .state
+                    .editing_rule
+                    .as_mut()
+                    .and_then(|e| e.conditions.get_mut(index))
+                {
+                    condition.property = property;
+                }
+            }
+            Message::RuleConditionMatchTypeChanged(index, match_type) => {
+                if let Some(condition) = self
+                    .state
+                    .editing_rule
+                    .as_mut()
+                    .and_then(|e| e.conditions.get_mut(index))
+                {
+                    condition.match_type_name = match_type;
+                }
+            }
+            Message::RuleConditionPatternChanged(index, pattern) => {
+                if let Some(condition) = self
+                    .state
+                    .editing_rule
+                    .as_mut()
+                    .and_then(|e| e.conditions.get_mut(index))
+                {
+                    condition.pattern = pattern;
+                }
+            }
             Message::SaveRoutingRule => {
                 if let Some(editing) = self.state.editing_rule.take() {
                     let rule = editing.to_rule();
@@ -1199,6 +1498,19 @@ impl SootMix {
                                 channel_name, plugin_count
                             );
 
+                            // The daemon hosts the chain while connected; the local
+                            // instance mirrors its slot for the editor
+                            if self.daemon_connected {
+                                if let Err(e) = daemon_client::send_daemon_command(
+                                    daemon_client::DaemonCommand::AddChannelPlugin {
+                                        channel_id: channel_id.to_string(),
+                                        plugin_id: plugin_id.clone(),
+                                    }
+                                ) {
+                                    error!("Failed to send add plugin command to daemon: {}", e);
+                                }
+                            }
+
                             // Update plugin processor with new chain
                             if let Err(e) = self.plugin_processor.setup_channel(channel_id, instances.clone()) {
                                 warn!("Failed to update plugin processor: {}", e);
@@ -1257,6 +1569,17 @@ impl SootMix {
                 if let Some(channel) = self.state.channel_mut(channel_id) {
                     // Find the index of the instance
                     if let Some(idx) = channel.plugin_instances.iter().position(|&id| id == instance_id) {
+                        if self.daemon_connected {
+                            if let Err(e) = daemon_client::send_daemon_command(
+                                daemon_client::DaemonCommand::RemoveChannelPlugin {
+                                    channel_id: channel_id.to_string(),
+                                    slot: idx as u32,
+                                }
+                            ) {
+                                error!("Failed to send remove plugin command to daemon: {}", e);
+                            }
+                        }
+
                         // Remove from runtime instances
                         channel.plugin_instances.remove(idx);
 
@@ -1315,6 +1638,18 @@ impl SootMix {
                         };
 
                         if new_idx != idx {
+                            if self.daemon_connected {
+                                if let Err(e) = daemon_client::send_daemon_command(
+                                    daemon_client::DaemonCommand::MoveChannelPlugin {
+                                        channel_id: channel_id.to_string(),
+                                        from_slot: idx as u32,
+                                        to_slot: new_idx as u32,
+                                    }
+                                ) {
+                                    error!("Failed to send move plugin command to daemon: {}", e);
+                                }
+                            }
+
                             // Swap in both vectors
                             channel.plugin_instances.swap(idx, new_idx);
                             if idx < channel.plugin_chain.len() && new_idx < channel.plugin_chain.len() {
@@ -1335,6 +1670,18 @@ impl SootMix {
                         if let Some(config) = channel.plugin_chain.get_mut(idx) {
                             config.bypassed = !config.bypassed;
                             info!("Plugin bypass toggled to {}", config.bypassed);
+
+                            if self.daemon_connected {
+                                if let Err(e) = daemon_client::send_daemon_command(
+                                    daemon_client::DaemonCommand::SetChannelPluginBypassed {
+                                        channel_id: channel_id.to_string(),
+                                        slot: idx as u32,
+                                        bypassed: config.bypassed,
+                                    }
+                                ) {
+                                    error!("Failed to send plugin bypass command to daemon: {}", e);
+                                }
+                            }
                         }
                     }
                 }
@@ -1376,23 +1723,14 @@ impl SootMix {
             }
             Message::PluginParamTextSubmit(instance_id, param_idx) => {
                 if let Some(text) = self.state.plugin_param_editing.remove(&(instance_id, param_idx)) {
-                    // Parse the text as a number and normalize
-                    if let Ok(display_value) = text.trim().parse::<f32>() {
-                        if let Some(param_info) = self.plugin_manager.get_parameter_info(instance_id, param_idx) {
-                            let clamped = display_value.clamp(param_info.min, param_info.max);
-                            let normalized = sootmix_plugin_api::normalize(
-                                clamped,
-                                param_info.min,
-                                param_info.max,
-                                param_info.curve,
-                            );
-                            // Reuse the existing parameter change handler
-                            return self.update(Message::PluginParameterChanged(
-                                instance_id,
-                                param_idx,
-                                normalized,
-                            ));
-                        }
+                    // Let the plugin parse the text (labels, units, its own formats)
+                    if let Some(normalized) = self.plugin_manager.parse_parameter(instance_id, param_idx, &text) {
+                        // Reuse the existing parameter change handler
+                        return self.update(Message::PluginParameterChanged(
+                            instance_id,
+                            param_idx,
+                            normalized,
+                        ));
                     }
                 }
             }
@@ -1412,6 +1750,19 @@ impl SootMix {
                             config.parameters.insert(param_idx, value);
                         }
 
+                        if self.daemon_connected {
+                            if let Err(e) = daemon_client::send_daemon_command(
+                                daemon_client::DaemonCommand::SetChannelPluginParameter {
+                                    channel_id: channel.id.to_string(),
+                                    slot: idx as u32,
+                                    param_index: param_idx,
+                                    value: value as f64,
+                                }
+                            ) {
+                                error!("Failed to send plugin parameter to daemon: {}", e);
+                            }
+                        }
+
                         // Send parameter update to the RT thread via PipeWire thread
                         // This is the primary path for RT-safe parameter updates
                         let channel_id = channel.id;
@@ -1621,6 +1972,9 @@ impl SootMix {
                 // Check for PipeWire events
                 self.poll_pw_events();
 
+                // Drop WASM plugin instances that trapped on the audio thread
+                self.plugin_manager.collect_faults();
+
                 // Poll tray messages
                 if let Some(tray_msgs) = self.poll_tray_messages() {
                     return tray_msgs;
@@ -1748,6 +2102,15 @@ impl SootMix {
                 }
             }
 
+            Message::TrayToggleTalk => {
+                self.tray_talk_held = !self.tray_talk_held;
+                info!("Tray: Talk key {}", if self.tray_talk_held { "held" } else { "released" });
+                self.cmd_set_talk_key(None, self.tray_talk_held);
+                if let Some(ref handle) = self.tray_handle {
+                    handle.set_talk_held(self.tray_talk_held);
+                }
+            }
+
             Message::TrayQuit => {
                 info!("Tray: Quit requested");
                 self.cleanup();
@@ -1868,6 +2231,7 @@ impl SootMix {
                 self.state.daemon_action_pending,
                 &self.state.available_outputs,
                 self.state.monitor_device.as_deref(),
+                self.state.solo_mode,
                 &self.state.report_status,
                 &self.state.update_status,
             );
@@ -1986,7 +2350,13 @@ impl SootMix {
 
             let panel_content: Element<Message> = if let Some(channel_id) = self.state.selected_channel {
                 if let Some(channel) = self.state.channel(channel_id) {
-                    Self::view_bottom_panel_content(channel)
+                    let spectrum = self.state.spectrum.as_ref().filter(|s| s.channel_id == channel_id);
+                    Self::view_bottom_panel_content(
+                        channel,
+                        spectrum,
+                        self.state.eq_selected_band,
+                        &self.state.eq_presets,
+                    )
                 } else {
                     self.view_bottom_panel_empty()
                 }
@@ -2038,7 +2408,12 @@ impl SootMix {
     }
 
     /// Content for the bottom panel when a channel is selected.
-    fn view_bottom_panel_content(channel: &MixerChannel) -> Element<'_, Message> {
+    fn view_bottom_panel_content<'a>(
+        channel: &'a MixerChannel,
+        spectrum: Option<&'a ChannelSpectrum>,
+        eq_selected_band: Option<usize>,
+        eq_presets: &'a [String],
+    ) -> Element<'a, Message> {
         let id = channel.id;
         let channel_name = channel.name.clone();
 
@@ -2065,7 +2440,8 @@ impl SootMix {
             .align_y(Alignment::Center);
 
         // Content sections (horizontal layout)
-        let eq_section = Self::view_bottom_eq_section(channel);
+        let eq_section = Self::view_bottom_eq_section(channel, eq_selected_band, eq_presets);
+        let spectrum_section = Self::view_bottom_spectrum_section(id, spectrum);
         let plugins_section = Self::view_bottom_plugins_section(channel);
         let routing_section = Self::view_bottom_routing_section(channel);
         let apps_section = Self::view_bottom_apps_section(channel);
@@ -2103,6 +2479,8 @@ impl SootMix {
         let content_row = row![
             eq_section,
             Space::new().width(SPACING),
+            spectrum_section,
+            Space::new().width(SPACING),
             ns_section,
             plugins_section,
             Space::new().width(SPACING),
@@ -2125,38 +2503,43 @@ impl SootMix {
     }
 
     /// EQ section for bottom panel.
-    fn view_bottom_eq_section(channel: &MixerChannel) -> Element<'_, Message> {
+    fn view_bottom_eq_section<'a>(
+        channel: &'a MixerChannel,
+        eq_selected_band: Option<usize>,
+        eq_presets: &'a [String],
+    ) -> Element<'a, Message> {
         let id = channel.id;
         let eq_enabled = channel.eq_enabled;
+        // The daemon only puts an EQ filter on output channels
+        let editor: Element<'a, Message> = if channel.is_input() {
+            text("EQ is available on output channels").size(TEXT_CAPTION).color(TEXT_DIM).into()
+        } else {
+            crate::ui::eq_editor::eq_editor(channel, eq_selected_band, eq_presets)
+        };
+        let toggle_btn = button(
+            text(if eq_enabled { "ON" } else { "OFF" })
+                .size(TEXT_CAPTION)
+                .color(if eq_enabled { TEXT } else { TEXT_DIM }),
+        )
+        .padding([SPACING_XS, SPACING_SM])
+        .style(move |_: &Theme, _| button::Style {
+            background: Some(Background::Color(if eq_enabled {
+                SOOTMIX_DARK.semantic_success.scale_alpha(0.3)
+            } else { SURFACE })),
+            border: Border::default().rounded(RADIUS_SM),
+            ..button::Style::default()
+        })
+        .on_press(Message::ChannelEqToggled(id));
         container(
             column![
-                text("EQ").size(TEXT_SMALL).color(TEXT_DIM),
-                Space::new().height(SPACING_XS),
-                container(Space::new().width(120).height(50))
-                    .style(|_| container::Style {
-                        background: Some(Background::Color(BACKGROUND)),
-                        border: Border::default().rounded(RADIUS_SM).color(SOOTMIX_DARK.border_subtle).width(1.0),
-                        ..container::Style::default()
-                    }),
+                row![text("EQ").size(TEXT_SMALL).color(TEXT_DIM), Space::new().width(Fill), toggle_btn]
+                    .align_y(Alignment::Center),
                 Space::new().height(SPACING_XS),
-                button(
-                    text(if eq_enabled { "ON" } else { "OFF" })
-                        .size(TEXT_CAPTION)
-                        .color(if eq_enabled { TEXT } else { TEXT_DIM }),
-                )
-                .padding([SPACING_XS, SPACING_SM])
-                .style(move |_: &Theme, _| button::Style {
-                    background: Some(Background::Color(if eq_enabled {
-                        SOOTMIX_DARK.semantic_success.scale_alpha(0.3)
-                    } else { SURFACE })),
-                    border: Border::default().rounded(RADIUS_SM),
-                    ..button::Style::default()
-                })
-                .on_press(Message::ChannelEqToggled(id)),
-            ]
-            .align_x(Alignment::Center),
+                editor,
+            ],
         )
         .padding(SPACING)
+        .width(Length::Fixed(320.0))
         .style(|_| container::Style {
             background: Some(Background::Color(BACKGROUND)),
             border: Border::default().rounded(RADIUS).color(SOOTMIX_DARK.border_subtle).width(1.0),
@@ -2165,6 +2548,19 @@ impl SootMix {
         .into()
     }
 
+    /// Spectrum analyzer section for bottom panel.
+    fn view_bottom_spectrum_section(id: Uuid, spectrum: Option<&ChannelSpectrum>) -> Element<'_, Message> {
+        container(crate::ui::focus_panel::spectrum_section(id, spectrum))
+            .padding(SPACING)
+            .width(Length::Fixed(280.0))
+            .style(|_| container::Style {
+                background: Some(Background::Color(BACKGROUND)),
+                border: Border::default().rounded(RADIUS).color(SOOTMIX_DARK.border_subtle).width(1.0),
+                ..container::Style::default()
+            })
+            .into()
+    }
+
     /// Plugins section for bottom panel.
     fn view_bottom_plugins_section(channel: &MixerChannel) -> Element<'_, Message> {
         let id = channel.id;
@@ -2503,7 +2899,14 @@ impl SootMix {
                     })
                     .collect();
 
-                return focus_panel(channel, &self.state.available_outputs, plugin_chain);
+                let spectrum = self.state.spectrum.as_ref().filter(|s| s.channel_id == channel_id);
+                return focus_panel(
+                    channel,
+                    &self.state.available_outputs,
+                    plugin_chain,
+                    self.daemon_connected,
+                    spectrum,
+                );
             }
         }
 
@@ -2777,6 +3180,7 @@ impl SootMix {
             self.state.output_device.as_deref(),
             &self.state.master_meter_display,
             self.state.master_recording_enabled,
+            self.state.master_loudness.as_ref(),
         );
 
         // Top padding aligns master with channels (which sit below the filter row)
@@ -3261,6 +3665,138 @@ impl SootMix {
         }
     }
 
+    /// Apply a channel's current pan and width.
+    fn cmd_set_channel_balance(&mut self, channel_id: Uuid) {
+        let Some(channel) = self.state.channel(channel_id) else {
+            return;
+        };
+        let balance = channel.balance();
+        if self.daemon_connected {
+            if let Err(e) = daemon_client::send_daemon_command(
+                daemon_client::DaemonCommand::SetChannelBalance {
+                    id: channel_id.to_string(),
+                    pan: balance.pan as f64,
+                    width: balance.width as f64,
+                }
+            ) {
+                error!("Failed to send set balance command to daemon: {}", e);
+            }
+        } else {
+            let node_id = if channel.is_input() {
+                channel.pw_source_id
+            } else {
+                channel.pw_loopback_output_id
+            };
+            if let Some(node_id) = node_id {
+                self.send_pw_command(PwCommand::SetBalance { node_id, balance });
+            }
+        }
+        self.save_config();
+    }
+
+    /// Change an output channel's speaker layout.
+    fn cmd_set_channel_layout(&mut self, channel_id: Uuid, layout: ChannelLayout) {
+        let is_input = self.state.channel(channel_id).map(|c| c.is_input()).unwrap_or(true);
+        if is_input {
+            warn!("Speaker layouts are only available on output channels");
+            return;
+        }
+
+        if !self.daemon_connected {
+            // The standalone audio thread only creates stereo sinks
+            warn!("Speaker layouts are only available when connected to daemon");
+            return;
+        }
+
+        if let Err(e) = daemon_client::send_daemon_command(
+            daemon_client::DaemonCommand::SetChannelLayout {
+                id: channel_id.to_string(),
+                layout,
+            }
+        ) {
+            error!("Failed to send set layout command to daemon: {}", e);
+        }
+        // Update local state immediately for responsive UI
+        if let Some(channel) = self.state.channel_mut(channel_id) {
+            channel.layout = layout;
+            // The daemon drops width on layouts without surround speakers
+            if !layout.has_surround() {
+                channel.width = 1.0;
+            }
+        }
+        self.save_config();
+    }
+
+    /// Solo or unsolo a channel on the daemon's monitor bus.
+    fn cmd_set_channel_solo(&mut self, channel_id: Uuid, solo: bool) {
+        if let Err(e) = daemon_client::send_daemon_command(
+            daemon_client::DaemonCommand::SetChannelSolo {
+                channel_id: channel_id.to_string(),
+                solo,
+            }
+        ) {
+            error!("Failed to send set solo command to daemon: {}", e);
+        }
+        // Update local state immediately for responsive UI
+        if let Some(channel) = self.state.channel_mut(channel_id) {
+            channel.solo = solo;
+        }
+    }
+
+    /// Set an input channel's sidetone on the daemon's monitor bus.
+    fn cmd_set_channel_sidetone(&mut self, channel_id: Uuid, enabled: bool, volume_db: f32) {
+        if let Err(e) = daemon_client::send_daemon_command(
+            daemon_client::DaemonCommand::SetChannelSidetone {
+                channel_id: channel_id.to_string(),
+                enabled,
+                volume_db: volume_db as f64,
+            }
+        ) {
+            error!("Failed to send set sidetone command to daemon: {}", e);
+        }
+        // Update local state immediately for responsive UI
+        if let Some(channel) = self.state.channel_mut(channel_id) {
+            channel.sidetone_enabled = enabled;
+            channel.sidetone_volume_db = volume_db;
+        }
+    }
+
+    /// Set the device the daemon's monitor bus plays on.
+    fn cmd_set_monitor_device(&mut self, name: String) {
+        if let Err(e) = daemon_client::send_daemon_command(
+            daemon_client::DaemonCommand::SetMonitorDevice(name.clone())
+        ) {
+            error!("Failed to send set monitor device command to daemon: {}", e);
+        }
+        self.state.monitor_device = Some(name);
+    }
+
+    /// Set how soloed channels are heard.
+    fn cmd_set_solo_mode(&mut self, mode: SoloMode) {
+        if !self.daemon_connected {
+            // Standalone solo links straight to the monitor device (PFL only)
+            warn!("Solo modes are only available when connected to daemon");
+            return;
+        }
+
+        if let Err(e) = daemon_client::send_daemon_command(
+            daemon_client::DaemonCommand::SetSoloMode(mode)
+        ) {
+            error!("Failed to send set solo mode command to daemon: {}", e);
+        }
+        self.state.solo_mode = mode;
+    }
+
+    /// Restart loudness measurement of a channel, or of the master output.
+    fn cmd_reset_loudness(&self, channel_id: Option<Uuid>) {
+        let target = channel_id.map_or_else(|| "master".to_string(), |id| id.to_string());
+        if let Err(e) = daemon_client::send_daemon_command(
+            daemon_client::DaemonCommand::ResetLoudness(target)
+        ) {
+            error!("Failed to send reset loudness command to daemon: {}", e);
+        }
+    }
+
     /// Set channel mute state.
     fn cmd_set_channel_mute(&mut self, channel_id: Uuid, muted: bool) {
         if self.daemon_connected {
@@ -3348,6 +3884,86 @@ impl SootMix {
         self.save_config();
     }
 
+    /// Set the noise suppression model, VAD grace periods and attenuation
+    /// limit of an input channel.
+    fn cmd_set_channel_noise_suppression_settings(&mut self, channel_id: Uuid, settings: NoiseSuppressionInfo) {
+        let is_input = self.state.channel(channel_id).map(|c| c.is_input()).unwrap_or(false);
+        if !is_input {
+            warn!("Noise suppression is only available on input channels");
+            return;
+        }
+
+        if self.daemon_connected {
+            if let Err(e) = daemon_client::send_daemon_command(
+                daemon_client::DaemonCommand::SetChannelNoiseSuppressionSettings {
+                    channel_id: channel_id.to_string(),
+                    settings,
+                }
+            ) {
+                error!("Failed to send set noise suppression settings command to daemon: {}", e);
+            }
+        }
+        if let Some(channel) = self.state.channel_mut(channel_id) {
+            channel.noise_suppression = settings;
+        }
+        self.save_config();
+    }
+
+    /// Set the push-to-talk mode, release tail and fade of an input channel.
+    fn cmd_set_channel_talk_settings(&mut self, channel_id: Uuid, settings: TalkInfo) {
+        let is_input = self.state.channel(channel_id).map(|c| c.is_input()).unwrap_or(false);
+        if !is_input {
+            warn!("Push-to-talk is only available on input channels");
+            return;
+        }
+
+        if self.daemon_connected {
+            if let Err(e) = daemon_client::send_daemon_command(
+                daemon_client::DaemonCommand::SetChannelTalkSettings {
+                    channel_id: channel_id.to_string(),
+                    settings,
+                }
+            ) {
+                error!("Failed to send set talk settings command to daemon: {}", e);
+            }
+        } else {
+            warn!("Push-to-talk is only available when connected to daemon");
+        }
+        if let Some(channel) = self.state.channel_mut(channel_id) {
+            channel.talk = settings;
+        }
+        self.update_tray_mic();
+        self.save_config();
+    }
+
+    /// Press or release the talk key of one input channel, or of all of them.
+    fn cmd_set_talk_key(&mut self, channel_id: Option<Uuid>, pressed: bool) {
+        if !self.daemon_connected {
+            return;
+        }
+        if let Err(e) = daemon_client::send_daemon_command(
+            daemon_client::DaemonCommand::SetTalkKey {
+                channel_id: channel_id.map(|id| id.to_string()).unwrap_or_default(),
+                pressed,
+            }
+        ) {
+            error!("Failed to send talk key command to daemon: {}", e);
+        }
+    }
+
+    /// Show in the tray whether the mic is live, if any input channel has a talk mode.
+    fn update_tray_mic(&self) {
+        let Some(ref handle) = self.tray_handle else {
+            return;
+        };
+        let mut talk_channels = self.state.channels.iter()
+            .filter(|c| c.is_input() && c.talk.mode != TalkMode::Off)
+            .peekable();
+        let mic_live = talk_channels.peek().is_some()
+            .then(|| talk_channels.any(|c| c.live));
+        handle.set_mic_live(mic_live);
+    }
+
     /// Set the hardware microphone gain for an input channel.
     fn cmd_set_channel_input_gain(&mut self, channel_id: Uuid, gain_db: f32) {
         // Only input channels support input gain
@@ -3371,6 +3987,65 @@ impl SootMix {
         self.save_config();
     }
 
+    /// Analyze a channel's spectrum at `tap`, or stop the analyzer.
+    fn cmd_set_spectrum(&mut self, channel_id: Option<Uuid>, tap: Option<SpectrumTap>) {
+        self.state.spectrum = channel_id.zip(tap).map(|(channel_id, tap)| ChannelSpectrum {
+            channel_id,
+            tap,
+            data: None,
+        });
+        // An empty target stops the analyzer
+        let (target, tap) = self.state.spectrum.as_ref()
+            .map_or((String::new(), SpectrumTap::default()), |s| (s.channel_id.to_string(), s.tap));
+        if let Err(e) = daemon_client::send_daemon_command(
+            daemon_client::DaemonCommand::SetSpectrum { target, tap }
+        ) {
+            error!("Failed to send set spectrum command to daemon: {}", e);
+        }
+    }
+
+    /// Send a channel's edited EQ bands to the daemon.
+    fn cmd_set_channel_eq_bands(&mut self, channel_id: Uuid) {
+        let Some(bands) = self.state.channel(channel_id).map(|c| c.eq_bands.clone()) else {
+            return;
+        };
+        if self.daemon_connected {
+            if let Err(e) = daemon_client::send_daemon_command(
+                daemon_client::DaemonCommand::SetChannelEqBands {
+                    channel_id: channel_id.to_string(),
+                    bands,
+                }
+            ) {
+                error!("Failed to send set EQ bands command to daemon: {}", e);
+            }
+        } else {
+            // The local filter chain only knows the flat preset
+            warn!("Parametric EQ is only available when connected to daemon");
+        }
+        self.save_config();
+    }
+
+    /// Set the auto level (loudness normalization) settings of a channel.
+    fn cmd_set_channel_auto_level(&mut self, channel_id: Uuid, settings: AutoLevelInfo) {
+        if self.daemon_connected {
+            if let Err(e) = daemon_client::send_daemon_command(
+                daemon_client::DaemonCommand::SetChannelAutoLevel {
+                    channel_id: channel_id.to_string(),
+                    settings,
+                }
+            ) {
+                error!("Failed to send set auto level command to daemon: {}", e);
+            }
+        } else {
+            // Auto level runs in the daemon's meter loop
+            warn!("Auto level is only available when connected to daemon");
+        }
+        if let Some(channel) = self.state.channel_mut(channel_id) {
+            channel.auto_level = settings;
+        }
+        self.save_config();
+    }
+
     /// Set master volume in dB.
     fn cmd_set_master_volume(&mut self, volume_db: f32) {
         self.state.master_volume_db = volume_db;
@@ -3817,6 +4492,9 @@ impl SootMix {
                     TrayMessage::ToggleMuteAll => {
                         return Some(Task::done(Message::TrayToggleMuteAll));
                     }
+                    TrayMessage::ToggleTalk => {
+                        return Some(Task::done(Message::TrayToggleTalk));
+                    }
                     TrayMessage::Quit => {
                         return Some(Task::done(Message::TrayQuit));
                     }
@@ -3927,9 +4605,9 @@ impl SootMix {
                     .unwrap_or_default();
 
                 // Get current volume settings before mutating
-                let (volume_db, muted) = self.state.channel(channel_id)
-                    .map(|c| (c.volume_db, c.muted))
-                    .unwrap_or((0.0, false));
+                let (volume_db, muted, balance) = self.state.channel(channel_id)
+                    .map(|c| (c.volume_db, c.muted, c.balance()))
+                    .unwrap_or((0.0, false, Default::default()));
 
                 if let Some(channel) = self.state.channel_mut(channel_id) {
                     channel.pw_sink_id = Some(node_id);
@@ -3948,6 +4626,7 @@ impl SootMix {
                            loopback_id, volume_db, linear);
                     // Request bind to ensure node is ready for control
                     self.send_pw_command(PwCommand::BindNode { node_id: loopback_id });
+                    self.send_pw_command(PwCommand::SetBalance { node_id: loopback_id, balance });
                     // Set volume - will use CLI fallback if native binding not yet ready
                     self.send_pw_command(PwCommand::SetVolume { node_id: loopback_id, volume: linear });
                     self.send_pw_command(PwCommand::SetMute { node_id: loopback_id, muted });
@@ -4422,7 +5101,14 @@ impl SootMix {
             }
 
             // Check if any rule matches
-            if let Some(rule) = self.state.routing_rules.find_match(&app.name, app.binary.as_deref()) {
+            let empty_properties = std::collections::HashMap::new();
+            let properties = self
+                .state
+                .pw_graph
+                .nodes
+                .get(&app.node_id)
+                .map_or(&empty_properties, |node| &node.properties);
+            if let Some(rule) = self.state.routing_rules.find_match(&app.name, app.binary.as_deref(), properties) {
                 // Find the target channel
                 if let Some(channel) = self.state.channel_by_name(&rule.target_channel) {
                     if channel.pw_sink_id.is_some() {
@@ -4535,6 +5221,9 @@ impl SootMix {
                     muted: self.state.master_muted,
                     output_device: self.state.output_device.clone(),
                     monitor_device: self.state.monitor_device.clone(),
+                    monitor_volume_db: self.state.monitor_volume_db,
+                    monitor_muted: self.state.monitor_muted,
+                    solo_mode: self.state.solo_mode,
                 },
                 channels: self
                     .state
@@ -4547,8 +5236,12 @@ impl SootMix {
                         sink_name: c.sink_name.clone(),
                         volume_db: c.volume_db,
                         muted: c.muted,
+                        pan: c.pan,
+                        width: c.width,
+                        layout: c.layout,
                         eq_enabled: c.eq_enabled,
                         eq_preset: c.eq_preset.clone(),
+                        eq_bands: c.eq_custom.then(|| c.eq_bands.iter().map(EqBand::from).collect()),
                         assigned_apps: c.assigned_apps.clone(),
                         plugin_chain: c.plugin_chain.clone(),
                         output_device_name: c.output_device_name.clone(),
@@ -4558,9 +5251,13 @@ impl SootMix {
                         sidetone_volume_db: c.sidetone_volume_db,
                         noise_suppression_enabled: c.noise_suppression_enabled,
                         vad_threshold: c.vad_threshold,
+                        noise_suppression: c.noise_suppression,
+                        talk: c.talk,
                         input_gain_db: c.input_gain_db,
+                        auto_level: c.auto_level,
                     })
                     .collect(),
+                ducking: self.state.saved_ducking.clone(),
             };
 
             if let Err(e) = cm.save_mixer_config(&config) {
@@ -4581,6 +5278,10 @@ impl SootMix {
             self.state.master_muted = config.master.muted;
             self.state.output_device = config.master.output_device.clone();
             self.state.monitor_device = config.master.monitor_device.clone();
+            self.state.monitor_volume_db = config.master.monitor_volume_db;
+            self.state.monitor_muted = config.master.monitor_muted;
+            self.state.solo_mode = config.master.solo_mode;
+            self.state.saved_ducking = config.ducking.clone();
 
             // Apply master volume/mute/device to output
             if let Some(ref device_name) = config.master.output_device {
@@ -4629,8 +5330,13 @@ impl SootMix {
                     channel.id = saved.id;
                     channel.volume_db = saved.volume_db;
                     channel.muted = saved.muted;
+                    channel.pan = saved.pan;
+                    channel.width = saved.width;
+                    channel.layout = saved.layout;
                     channel.eq_enabled = saved.eq_enabled;
                     channel.eq_preset = saved.eq_preset;
+                    channel.eq_custom = saved.eq_bands.is_some();
+                    channel.eq_bands = saved.eq_bands.iter().flatten().map(EqBandInfo::from).collect();
                     channel.assigned_apps = saved.assigned_apps;
                     channel.plugin_chain = saved.plugin_chain.clone();
                     channel.output_device_name = saved.output_device_name;
@@ -4639,7 +5345,10 @@ impl SootMix {
                     channel.sidetone_volume_db = saved.sidetone_volume_db;
                     channel.noise_suppression_enabled = saved.noise_suppression_enabled;
                     channel.vad_threshold = saved.vad_threshold;
+                    channel.noise_suppression = saved.noise_suppression;
+                    channel.talk = saved.talk;
                     channel.input_gain_db = saved.input_gain_db;
+                    channel.auto_level = saved.auto_level;
 
                     let id = channel.id;
                     let name = channel.name.clone();
@@ -4699,6 +5408,14 @@ impl SootMix {
         }
     }
 
+    /// Mirror the daemon's monitor bus settings.
+    fn apply_monitor_info(&mut self, monitor: sootmix_ipc::MonitorInfo) {
+        self.state.monitor_device = (!monitor.device.is_empty()).then_some(monitor.device);
+        self.state.monitor_volume_db = monitor.volume_db as f32;
+        self.state.monitor_muted = monitor.muted;
+        self.state.solo_mode = monitor.solo_mode;
+    }
+
     /// Handle events received from the daemon via D-Bus.
     fn handle_daemon_event(&mut self, event: DaemonEvent) {
         use crate::daemon_client::DaemonEvent::*;
@@ -4720,6 +5437,8 @@ impl SootMix {
                 warn!("Disconnected from SootMix daemon");
                 self.daemon_connected = false;
                 self.state.pw_connected = false;
+                self.state.master_loudness = None;
+                self.state.spectrum = None;
 
                 // Spawn local PW thread as fallback
                 if self.pw_thread.is_none() {
@@ -4754,6 +5473,7 @@ impl SootMix {
                 master_output,
                 connected,
                 recording_enabled,
+                monitor,
             } => {
                 info!(
                     "Received initial state from daemon: {} channels, {} apps, {} outputs, {} inputs",
@@ -4771,6 +5491,7 @@ impl SootMix {
                 if !master_output.is_empty() {
                     self.state.output_device = Some(master_output);
                 }
+                self.apply_monitor_info(monitor);
 
                 // Sync channels from daemon
                 self.state.channels.clear();
@@ -4781,8 +5502,13 @@ impl SootMix {
                             name: ch_info.name,
                             volume_db: ch_info.volume_db as f32,
                             muted: ch_info.muted,
+                            pan: ch_info.pan as f32,
+                            width: ch_info.width as f32,
+                            layout: ch_info.layout,
                             eq_enabled: ch_info.eq_enabled,
                             eq_preset: ch_info.eq_preset,
+                            eq_bands: ch_info.eq_bands,
+                            eq_custom: ch_info.eq_custom,
                             assigned_apps: ch_info.assigned_apps,
                             is_managed: true,
                             sink_name: None,
@@ -4809,12 +5535,21 @@ impl SootMix {
                             input_device_id: None,
                             pw_source_id: None,
                             pw_loopback_capture_id: None,
-                            sidetone_enabled: false,
-                            sidetone_volume_db: -20.0,
+                            sidetone_enabled: ch_info.sidetone_enabled,
+                            sidetone_volume_db: ch_info.sidetone_volume_db as f32,
                             noise_suppression_enabled: false,
                             vad_threshold: 95.0,
+                            noise_suppression: ch_info.noise_suppression,
+                            talking: ch_info.talking,
+                            talk: ch_info.talk,
+                            live: ch_info.live,
                             input_gain_db: ch_info.input_gain_db as f32,
-                            solo: false,
+                            solo: ch_info.solo,
+                            latency_samples: ch_info.latency_samples,
+                            latency_compensation_samples: ch_info.latency_compensation_samples,
+                            loudness: None,
+                            auto_level: ch_info.auto_level,
+                            auto_level_gain_db: ch_info.auto_level_gain_db as f32,
                         };
                         self.state.channels.push(channel);
                     }
@@ -4866,6 +5601,7 @@ impl SootMix {
                 }
 
                 self.state.startup_complete = true;
+                self.update_tray_mic();
                 info!("State synced from daemon - {} channels, {} apps",
                       self.state.channels.len(),
                       self.state.available_apps.len());
@@ -4880,8 +5616,13 @@ impl SootMix {
                             name: ch_info.name,
                             volume_db: ch_info.volume_db as f32,
                             muted: ch_info.muted,
+                            pan: ch_info.pan as f32,
+                            width: ch_info.width as f32,
+                            layout: ch_info.layout,
                             eq_enabled: ch_info.eq_enabled,
                             eq_preset: ch_info.eq_preset,
+                            eq_bands: ch_info.eq_bands,
+                            eq_custom: ch_info.eq_custom,
                             assigned_apps: ch_info.assigned_apps,
                             is_managed: true,
                             sink_name: None,
@@ -4908,12 +5649,21 @@ impl SootMix {
                             input_device_id: None,
                             pw_source_id: None,
                             pw_loopback_capture_id: None,
-                            sidetone_enabled: false,
-                            sidetone_volume_db: -20.0,
+                            sidetone_enabled: ch_info.sidetone_enabled,
+                            sidetone_volume_db: ch_info.sidetone_volume_db as f32,
                             noise_suppression_enabled: false,
                             vad_threshold: 95.0,
+                            noise_suppression: ch_info.noise_suppression,
+                            talking: ch_info.talking,
+                            talk: ch_info.talk,
+                            live: ch_info.live,
                             input_gain_db: ch_info.input_gain_db as f32,
-                            solo: false,
+                            solo: ch_info.solo,
+                            latency_samples: ch_info.latency_samples,
+                            latency_compensation_samples: ch_info.latency_compensation_samples,
+                            loudness: None,
+                            auto_level: ch_info.auto_level,
+                            auto_level_gain_db: ch_info.auto_level_gain_db as f32,
                         };
                         self.state.channels.push(channel);
                     }
@@ -4923,6 +5673,9 @@ impl SootMix {
                 info!("Daemon: Channel removed: {}", channel_id);
                 if let Ok(id) = Uuid::parse_str(&channel_id) {
                     self.state.channels.retain(|c| c.id != id);
+                    if self.state.spectrum.as_ref().is_some_and(|s| s.channel_id == id) {
+                        self.state.spectrum = None;
+                    }
                 }
             }
             ChannelUpdated(ch_info) => {
@@ -4932,14 +5685,31 @@ impl SootMix {
                         channel.name = ch_info.name;
                         channel.volume_db = ch_info.volume_db as f32;
                         channel.muted = ch_info.muted;
+                        channel.pan = ch_info.pan as f32;
+                        channel.width = ch_info.width as f32;
+                        channel.layout = ch_info.layout;
                         channel.eq_enabled = ch_info.eq_enabled;
                         channel.eq_preset = ch_info.eq_preset;
+                        channel.eq_bands = ch_info.eq_bands;
+                        channel.eq_custom = ch_info.eq_custom;
                         channel.assigned_apps = ch_info.assigned_apps;
+                        channel.solo = ch_info.solo;
+                        channel.sidetone_enabled = ch_info.sidetone_enabled;
+                        channel.sidetone_volume_db = ch_info.sidetone_volume_db as f32;
+                        channel.latency_samples = ch_info.latency_samples;
+                        channel.latency_compensation_samples = ch_info.latency_compensation_samples;
+                        channel.auto_level = ch_info.auto_level;
+                        channel.auto_level_gain_db = ch_info.auto_level_gain_db as f32;
+                        channel.noise_suppression = ch_info.noise_suppression;
+                        channel.talking = ch_info.talking;
+                        channel.talk = ch_info.talk;
+                        channel.live = ch_info.live;
                         if !ch_info.output_device.is_empty() {
                             channel.output_device_name = Some(ch_info.output_device);
                         }
                     }
                 }
+                self.update_tray_mic();
             }
             VolumeChanged { channel_id, volume_db } => {
                 if let Ok(id) = Uuid::parse_str(&channel_id) {
@@ -5016,11 +5786,28 @@ impl SootMix {
                         if let Some(ref meter_levels) = channel.meter_levels {
                             meter_levels.store_max(left_linear, right_linear);
                         }
+                        channel.auto_level_gain_db = meter.auto_level_gain_db as f32;
                     } else {
                         debug!("MeterUpdate: channel {} not found in GUI state", id);
                     }
                 }
             }
+            LoudnessUpdate(data) => {
+                for loudness in data {
+                    if loudness.is_master() {
+                        self.state.master_loudness = Some(loudness);
+                    } else if let Some(channel) = self.state.channel_mut(loudness.channel_id()) {
+                        channel.loudness = Some(loudness);
+                    }
+                }
+            }
+            SpectrumUpdate(data) => {
+                if let Some(spectrum) = &mut self.state.spectrum {
+                    if spectrum.channel_id == data.channel_id() && spectrum.tap == data.tap {
+                        spectrum.data = Some(data);
+                    }
+                }
+            }
             OutputsChanged => {
                 debug!("Output devices changed - will refresh on next state query");
                 // The full output list will be refreshed when needed
@@ -5029,6 +5816,31 @@ impl SootMix {
                 debug!("Input devices changed - will refresh on next state query");
                 // The full input list will be refreshed when needed
             }
+            SoloChanged { channel_id, solo } => {
+                if let Ok(id) = Uuid::parse_str(&channel_id) {
+                    if let Some(channel) = self.state.channel_mut(id) {
+                        channel.solo = solo;
+                    }
+                }
+            }
+            ChannelTalking { channel_id, talking } => {
+                if let Ok(id) = Uuid::parse_str(&channel_id) {
+                    if let Some(channel) = self.state.channel_mut(id) {
+                        channel.talking = talking;
+                    }
+                }
+            }
+            ChannelLiveChanged { channel_id, live } => {
+                if let Ok(id) = Uuid::parse_str(&channel_id) {
+                    if let Some(channel) = self.state.channel_mut(id) {
+                        channel.live = live;
+                    }
+                }
+                self.update_tray_mic();
+            }
+            MonitorChanged(monitor) => {
+                self.apply_monitor_info(monitor);
+            }
         }
     }
 }
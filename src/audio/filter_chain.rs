@@ -7,6 +7,7 @@
 #![allow(dead_code, unused_imports)]
 
 use crate::config::eq_preset::EqPreset;
+use crate::state::EqBandType;
 use std::collections::HashMap;
 use std::io::Write;
 use std::process::{Child, Command};
@@ -55,7 +56,8 @@ fn ensure_processes_map() {
     }
 }
 
-/// Generate a filter-chain config file for a 5-band parametric EQ.
+/// Generate a filter-chain config file for a parametric EQ, one biquad per
+/// band.
 ///
 /// The filter chain creates:
 /// - A sink node (Audio/Sink) for receiving audio from the loopback output
@@ -104,11 +106,12 @@ context.modules = [
             r#"                    {{
                         type = builtin
                         name = eq_band_{i}
-                        label = bq_peaking
+                        label = {label}
                         control = {{ "Freq" = {freq} "Q" = {q:.3} "Gain" = {gain:.2} }}
                     }}
 "#,
             i = i,
+            label = filter_label(band.kind),
             freq = band.freq,
             q = band.q,
             gain = band.gain
@@ -119,8 +122,9 @@ context.modules = [
                 links = [
 "#);
 
-    // Link the bands in series: band0 -> band1 -> band2 -> band3 -> band4
-    for i in 0..preset.bands.len() - 1 {
+    // Link the bands in series: band0 -> band1 -> ... -> bandN
+    let last_band = preset.bands.len().saturating_sub(1);
+    for i in 0..last_band {
         config.push_str(&format!(
             r#"                    {{ output = "eq_band_{i}:Out" input = "eq_band_{next}:In" }}
 "#,
@@ -132,7 +136,7 @@ context.modules = [
     // Configure the graph inputs/outputs
     config.push_str(&format!(r#"                ]
                 inputs = [ "eq_band_0:In" ]
-                outputs = [ "eq_band_4:Out" ]
+                outputs = [ "eq_band_{last_band}:Out" ]
             }}
             capture.props = {{
                 media.class = Audio/Sink
@@ -153,6 +157,19 @@ context.modules = [
     config
 }
 
+/// filter-chain builtin implementing a band type.
+fn filter_label(kind: EqBandType) -> &'static str {
+    match kind {
+        EqBandType::Peaking => "bq_peaking",
+        EqBandType::LowShelf => "bq_lowshelf",
+        EqBandType::HighShelf => "bq_highshelf",
+        EqBandType::HighPass => "bq_highpass",
+        EqBandType::LowPass => "bq_lowpass",
+        EqBandType::Notch => "bq_notch",
+        EqBandType::BandPass => "bq_bandpass",
+    }
+}
+
 /// Create an EQ filter chain for a channel.
 ///
 /// Returns (sink_node_id, output_node_id) of the created filter.
@@ -460,6 +477,7 @@ mod tests {
         assert!(config.contains("sootmix.eq.test"));
         assert!(config.contains("bq_peaking"));
         assert!(config.contains("eq_band_0"));
+        assert!(config.contains(r#"outputs = [ "eq_band_4:Out" ]"#));
         assert!(config.contains("sootmix.eq.test.output"));
     }
 
@@ -20,6 +20,7 @@ use pipewire::node::{Node, NodeListener};
 use pipewire::properties::properties;
 use pipewire::spa::param::ParamType;
 use pipewire::spa::pod::Pod;
+use sootmix_ipc::Balance;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
@@ -56,6 +57,9 @@ pub enum PwCommand {
     SetVolume { node_id: u32, volume: f32 },
     /// Set mute state on a node.
     SetMute { node_id: u32, muted: bool },
+    /// Set a node's pan and width; later `SetVolume`s are spread over its
+    /// speakers accordingly.
+    SetBalance { node_id: u32, balance: Balance },
     /// Set both volume and mute atomically.
     #[allow(dead_code)]
     SetVolumeMute {
@@ -369,6 +373,10 @@ struct PwThreadState {
     sample_rate: f32,
     /// PipeWire block size (quantum, defaults to 512).
     block_size: usize,
+    /// Pan/width per node (absent = centred).
+    node_balance: HashMap<u32, Balance>,
+    /// Last volume set per node, reapplied when its balance changes.
+    node_volumes: HashMap<u32, f32>,
 }
 
 impl PwThreadState {
@@ -392,6 +400,8 @@ impl PwThreadState {
             cli_in_flight: Arc::new(Mutex::new(HashSet::new())),
             sample_rate,
             block_size,
+            node_balance: HashMap::new(),
+            node_volumes: HashMap::new(),
         }
     }
 
@@ -438,24 +448,45 @@ impl PwThreadState {
         self.find_node_by_name(&output_name)
     }
 
+    /// Speaker positions of a node in channel-map order: its playback
+    /// ports for sinks, otherwise its output ports. Ports are created in
+    /// channel order, so global ID order matches the node's channel map.
+    fn node_positions(&self, node_id: u32) -> Vec<AudioChannel> {
+        let is_sink = self.nodes.get(&node_id).is_some_and(PwNode::is_sink);
+        let direction = if is_sink { PortDirection::Input } else { PortDirection::Output };
+        let mut ports: Vec<&PwPort> = self
+            .ports
+            .values()
+            .filter(|p| p.node_id == node_id && p.direction == direction)
+            .collect();
+        ports.sort_by_key(|p| p.id);
+        ports.into_iter().map(|p| p.channel.clone()).collect()
+    }
+
     /// Set volume on a bound node using native API.
     ///
-    /// Uses channelVolumes (stereo FL/FR) which is what WirePlumber/wpctl uses.
+    /// Uses channelVolumes (one per speaker, like WirePlumber/wpctl), spread
+    /// according to the node's pan/width.
     fn set_node_volume(&self, node_id: u32, volume: f32) -> Result<(), String> {
         let bound = self
             .bound_nodes
             .get(&node_id)
             .ok_or_else(|| format!("Node {} not bound", node_id))?;
 
-        // Use stereo channel volumes (FL, FR) - this is what wpctl uses
-        let pod_data = build_channel_volumes_pod(&[volume, volume]).map_err(|e| e.to_string())?;
+        let volumes = self
+            .node_balance
+            .get(&node_id)
+            .copied()
+            .unwrap_or_default()
+            .speaker_volumes(volume, &self.node_positions(node_id));
+        let pod_data = build_channel_volumes_pod(&volumes).map_err(|e| e.to_string())?;
 
         let pod = Pod::from_bytes(&pod_data)
             .ok_or_else(|| "Failed to create Pod from bytes".to_string())?;
 
         bound.proxy.set_param(ParamType::Props, 0, pod);
 
-        trace!("Native volume set on node {}: {:.3} (stereo)", node_id, volume);
+        trace!("Native volume set on node {}: {:?}", node_id, volumes);
         Ok(())
     }
 
@@ -835,6 +866,7 @@ fn handle_command(
 
         PwCommand::SetVolume { node_id, volume } => {
             trace!("PW cmd: SetVolume node={} volume={:.3}", node_id, volume);
+            state.borrow_mut().node_volumes.insert(node_id, volume);
 
             // Try native API first if node is bound
             let result = state.borrow().set_node_volume(node_id, volume);
@@ -857,6 +889,26 @@ fn handle_command(
             }
         }
 
+        PwCommand::SetBalance { node_id, balance } => {
+            trace!("PW cmd: SetBalance node={} {:?}", node_id, balance);
+            let volume = {
+                let mut st = state.borrow_mut();
+                if balance.is_centered() {
+                    st.node_balance.remove(&node_id);
+                } else {
+                    st.node_balance.insert(node_id, balance);
+                }
+                st.node_volumes.get(&node_id).copied()
+            };
+            // The CLI fallback only knows a single volume, so there's nothing
+            // to retry if the node isn't bound yet; its next SetVolume applies it
+            if let Some(volume) = volume {
+                if let Err(e) = state.borrow().set_node_volume(node_id, volume) {
+                    debug!("Could not apply balance to node {}: {}", node_id, e);
+                }
+            }
+        }
+
         PwCommand::SetMute { node_id, muted } => {
             trace!("PW cmd: SetMute node={} muted={}", node_id, muted);
 
@@ -1124,7 +1176,13 @@ fn handle_command(
             // Send the parameter update to the filter's ring buffer
             if let Some(filter_info) = state.borrow_mut().plugin_filters.get_mut(&channel_id) {
                 let update = crate::realtime::PluginParamUpdate::new(instance_id, param_index, value);
-                filter_info.param_writer.push(update);
+                // A full queue keeps the changes already in it
+                if !filter_info.param_writer.push(update) {
+                    warn!(
+                        "Parameter queue of channel {} full, dropping update",
+                        channel_id
+                    );
+                }
             }
         }
 
@@ -1537,7 +1595,14 @@ fn setup_registry_listener(
 
                     if let Some(name) = props.get("port.name") {
                         port.name = name.to_string();
-                        port.channel = AudioChannel::from_str(name);
+                        port.channel = AudioChannel::from_port_name(name);
+                    }
+                    // The position itself beats guessing from the port name
+                    if let Some(position) = props.get("audio.channel") {
+                        let channel = AudioChannel::from_position(position);
+                        if channel != AudioChannel::Unknown {
+                            port.channel = channel;
+                        }
                     }
                     if let Some(node_id) = props.get("node.id") {
                         port.node_id = node_id.parse().unwrap_or(0);
@@ -1596,6 +1661,8 @@ fn setup_registry_listener(
 
             if state.nodes.remove(&id).is_some() {
                 debug!("Node removed: {}", id);
+                state.node_balance.remove(&id);
+                state.node_volumes.remove(&id);
                 let _ = event_tx_remove.send(PwEvent::NodeRemoved(id));
             } else if state.ports.remove(&id).is_some() {
                 debug!("Port removed: {}", id);
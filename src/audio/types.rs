@@ -9,6 +9,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub use sootmix_ipc::AudioChannel;
+
 /// Represents a PipeWire node (app, device, or virtual sink).
 #[derive(Debug, Clone)]
 pub struct PwNode {
@@ -165,49 +167,6 @@ impl PortDirection {
     }
 }
 
-/// Audio channel position.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum AudioChannel {
-    FrontLeft,
-    FrontRight,
-    FrontCenter,
-    Mono,
-    RearLeft,
-    RearRight,
-    LowFrequency,
-    Unknown,
-}
-
-impl AudioChannel {
-    /// Parse from port name or channel position string.
-    pub fn from_str(s: &str) -> Self {
-        let s_lower = s.to_lowercase();
-        if s_lower.contains("fl")
-            || s_lower.contains("front_left")
-            || s_lower.contains("playback_fl")
-        {
-            Self::FrontLeft
-        } else if s_lower.contains("fr")
-            || s_lower.contains("front_right")
-            || s_lower.contains("playback_fr")
-        {
-            Self::FrontRight
-        } else if s_lower.contains("fc") || s_lower.contains("front_center") {
-            Self::FrontCenter
-        } else if s_lower.contains("mono") {
-            Self::Mono
-        } else if s_lower.contains("rl") || s_lower.contains("rear_left") {
-            Self::RearLeft
-        } else if s_lower.contains("rr") || s_lower.contains("rear_right") {
-            Self::RearRight
-        } else if s_lower.contains("lfe") || s_lower.contains("subwoofer") {
-            Self::LowFrequency
-        } else {
-            Self::Unknown
-        }
-    }
-}
-
 /// A link between two ports in the PipeWire graph.
 #[derive(Debug, Clone)]
 pub struct PwLink {
@@ -6,8 +6,11 @@
 
 #![allow(dead_code, unused_imports)]
 
+use crate::config::eq_preset::EqBand;
 use crate::plugins::PluginSlotConfig;
-use crate::state::ChannelKind;
+use crate::state::{
+    AutoLevelInfo, ChannelKind, ChannelLayout, NoiseSuppressionInfo, SoloMode, TalkInfo,
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -108,10 +111,22 @@ pub struct SavedChannel {
     pub volume_db: f32,
     /// Whether muted.
     pub muted: bool,
+    /// Pan/balance (-1.0 left to 1.0 right).
+    #[serde(default)]
+    pub pan: f32,
+    /// Surround width (0.0 to 1.0).
+    #[serde(default = "default_width")]
+    pub width: f32,
+    /// Speaker layout (mono, stereo, 5.1, 7.1).
+    #[serde(default)]
+    pub layout: ChannelLayout,
     /// Whether EQ is enabled.
     pub eq_enabled: bool,
     /// EQ preset name.
     pub eq_preset: String,
+    /// Edited EQ bands (`None` follows `eq_preset`). Daemon-owned field, round-tripped by UI.
+    #[serde(default)]
+    pub eq_bands: Option<Vec<EqBand>>,
     /// Assigned app identifiers.
     pub assigned_apps: Vec<String>,
     /// Plugin chain configuration.
@@ -138,9 +153,22 @@ pub struct SavedChannel {
     /// VAD threshold for noise suppression (0-100%). Daemon-owned field, round-tripped by UI.
     #[serde(default = "default_vad_threshold")]
     pub vad_threshold: f32,
+    /// Noise suppression tuning. Daemon-owned field, round-tripped by UI.
+    #[serde(default)]
+    pub noise_suppression: NoiseSuppressionInfo,
+    /// Push-to-talk settings. Daemon-owned field, round-tripped by UI.
+    #[serde(default)]
+    pub talk: TalkInfo,
     /// Hardware microphone gain in dB. Daemon-owned field, round-tripped by UI.
     #[serde(default)]
     pub input_gain_db: f32,
+    /// Auto level settings. Daemon-owned field, round-tripped by UI.
+    #[serde(default)]
+    pub auto_level: AutoLevelInfo,
+}
+
+fn default_width() -> f32 {
+    1.0
 }
 
 fn default_vad_threshold() -> f32 {
@@ -165,6 +193,15 @@ pub struct MasterConfig {
     /// Selected monitor output device name (for PFL solo routing).
     #[serde(default)]
     pub monitor_device: Option<String>,
+    /// Monitor bus level in dB. Daemon-owned, round-tripped by the UI.
+    #[serde(default)]
+    pub monitor_volume_db: f32,
+    /// Monitor bus mute. Daemon-owned, round-tripped by the UI.
+    #[serde(default)]
+    pub monitor_muted: bool,
+    /// How soloed channels are heard.
+    #[serde(default)]
+    pub solo_mode: SoloMode,
 }
 
 /// Complete mixer state configuration for persistence.
@@ -176,6 +213,10 @@ pub struct MixerConfig {
     /// Saved channel configurations.
     #[serde(default)]
     pub channels: Vec<SavedChannel>,
+    /// Ducking relationships. Daemon-owned; kept opaque and round-tripped
+    /// so saving from the UI doesn't drop them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ducking: Vec<toml::Value>,
 }
 
 impl MixerConfig {
@@ -6,47 +6,95 @@
 
 #![allow(dead_code, unused_imports)]
 
+use crate::state::{EqBandInfo, EqBandType};
 use serde::{Deserialize, Serialize};
 
-/// Standard EQ frequency bands.
-pub const EQ_FREQUENCIES: [u32; 5] = [60, 250, 1000, 4000, 16000];
+/// Version written to EQ preset files. Presets of the fixed five-band EQ
+/// have none; they still load, with every band peaking.
+pub const EQ_PRESET_VERSION: u32 = 2;
 
 /// A single EQ band.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct EqBand {
-    /// Center frequency in Hz.
-    pub freq: u32,
-    /// Gain in dB (-12.0 to +12.0).
+    /// Filter shape.
+    #[serde(rename = "type", default)]
+    pub kind: EqBandType,
+    /// Frequency in Hz.
+    pub freq: f32,
+    /// Gain in dB (peaking and shelves only).
+    #[serde(default)]
     pub gain: f32,
-    /// Q factor (0.5 to 5.0).
+    /// Q factor.
+    #[serde(default = "default_q")]
     pub q: f32,
+    /// Roll-off of pass filters in dB per octave.
+    #[serde(default = "default_slope")]
+    pub slope: u32,
+}
+
+fn default_q() -> f32 {
+    1.0
+}
+
+fn default_slope() -> u32 {
+    sootmix_ipc::EQ_SLOPES[0]
 }
 
 impl EqBand {
-    pub fn new(freq: u32) -> Self {
+    pub fn new(freq: f32) -> Self {
         Self {
+            kind: EqBandType::Peaking,
             freq,
             gain: 0.0,
-            q: 1.0,
+            q: default_q(),
+            slope: default_slope(),
         }
     }
 
     pub fn with_gain(mut self, gain: f32) -> Self {
-        self.gain = gain.clamp(-12.0, 12.0);
+        let max = sootmix_ipc::EQ_MAX_GAIN_DB as f32;
+        self.gain = gain.clamp(-max, max);
         self
     }
 
     pub fn with_q(mut self, q: f32) -> Self {
-        self.q = q.clamp(0.5, 5.0);
+        self.q = q.clamp(sootmix_ipc::EQ_MIN_Q as f32, sootmix_ipc::EQ_MAX_Q as f32);
         self
     }
 }
 
-/// A 5-band parametric EQ preset.
+impl From<&EqBand> for EqBandInfo {
+    fn from(band: &EqBand) -> Self {
+        Self {
+            band_type: band.kind,
+            freq_hz: band.freq as f64,
+            gain_db: band.gain as f64,
+            q: band.q as f64,
+            slope_db: band.slope,
+        }
+    }
+}
+
+impl From<&EqBandInfo> for EqBand {
+    fn from(band: &EqBandInfo) -> Self {
+        Self {
+            kind: band.band_type,
+            freq: band.freq_hz as f32,
+            gain: band.gain_db as f32,
+            q: band.q as f32,
+            slope: band.slope_db,
+        }
+    }
+}
+
+/// A parametric EQ preset.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EqPreset {
     pub name: String,
-    pub bands: [EqBand; 5],
+    /// Format version (0 for presets of the five-band EQ).
+    #[serde(default)]
+    pub version: u32,
+    pub bands: Vec<EqBand>,
 }
 
 impl Default for EqPreset {
@@ -56,74 +104,82 @@ impl Default for EqPreset {
 }
 
 impl EqPreset {
+    fn new(name: &str, bands: Vec<EqBand>) -> Self {
+        Self {
+            name: name.to_string(),
+            version: EQ_PRESET_VERSION,
+            bands,
+        }
+    }
+
     /// Create a flat EQ preset.
     pub fn flat() -> Self {
-        Self {
-            name: "Flat".to_string(),
-            bands: [
-                EqBand::new(60),
-                EqBand::new(250),
-                EqBand::new(1000),
-                EqBand::new(4000),
-                EqBand::new(16000),
+        Self::new(
+            "Flat",
+            vec![
+                EqBand::new(60.0),
+                EqBand::new(250.0),
+                EqBand::new(1000.0),
+                EqBand::new(4000.0),
+                EqBand::new(16000.0),
             ],
-        }
+        )
     }
 
     /// Bass boost preset.
     pub fn bass_boost() -> Self {
-        Self {
-            name: "Bass Boost".to_string(),
-            bands: [
-                EqBand::new(60).with_gain(6.0).with_q(0.8),
-                EqBand::new(250).with_gain(3.0),
-                EqBand::new(1000),
-                EqBand::new(4000),
-                EqBand::new(16000).with_gain(-2.0),
+        Self::new(
+            "Bass Boost",
+            vec![
+                EqBand::new(60.0).with_gain(6.0).with_q(0.8),
+                EqBand::new(250.0).with_gain(3.0),
+                EqBand::new(1000.0),
+                EqBand::new(4000.0),
+                EqBand::new(16000.0).with_gain(-2.0),
             ],
-        }
+        )
     }
 
     /// Vocal clarity preset.
     pub fn vocal_clarity() -> Self {
-        Self {
-            name: "Vocal Clarity".to_string(),
-            bands: [
-                EqBand::new(60).with_gain(-2.0),
-                EqBand::new(250).with_gain(-1.0),
-                EqBand::new(1000).with_gain(2.0).with_q(1.5),
-                EqBand::new(4000).with_gain(4.0).with_q(1.2),
-                EqBand::new(16000).with_gain(1.0),
+        Self::new(
+            "Vocal Clarity",
+            vec![
+                EqBand::new(60.0).with_gain(-2.0),
+                EqBand::new(250.0).with_gain(-1.0),
+                EqBand::new(1000.0).with_gain(2.0).with_q(1.5),
+                EqBand::new(4000.0).with_gain(4.0).with_q(1.2),
+                EqBand::new(16000.0).with_gain(1.0),
             ],
-        }
+        )
     }
 
     /// Treble boost preset.
     pub fn treble_boost() -> Self {
-        Self {
-            name: "Treble Boost".to_string(),
-            bands: [
-                EqBand::new(60).with_gain(-2.0),
-                EqBand::new(250),
-                EqBand::new(1000),
-                EqBand::new(4000).with_gain(3.0),
-                EqBand::new(16000).with_gain(5.0),
+        Self::new(
+            "Treble Boost",
+            vec![
+                EqBand::new(60.0).with_gain(-2.0),
+                EqBand::new(250.0),
+                EqBand::new(1000.0),
+                EqBand::new(4000.0).with_gain(3.0),
+                EqBand::new(16000.0).with_gain(5.0),
             ],
-        }
+        )
     }
 
     /// Cinema/movie preset.
     pub fn cinema() -> Self {
-        Self {
-            name: "Cinema".to_string(),
-            bands: [
-                EqBand::new(60).with_gain(4.0).with_q(0.7),
-                EqBand::new(250).with_gain(1.0),
-                EqBand::new(1000).with_gain(-1.0),
-                EqBand::new(4000).with_gain(2.0),
-                EqBand::new(16000).with_gain(3.0),
+        Self::new(
+            "Cinema",
+            vec![
+                EqBand::new(60.0).with_gain(4.0).with_q(0.7),
+                EqBand::new(250.0).with_gain(1.0),
+                EqBand::new(1000.0).with_gain(-1.0),
+                EqBand::new(4000.0).with_gain(2.0),
+                EqBand::new(16000.0).with_gain(3.0),
             ],
-        }
+        )
     }
 
     /// Get all built-in presets.
@@ -147,8 +203,11 @@ impl EqPreset {
         toml::to_string_pretty(self)
     }
 
-    /// Check if this preset is effectively flat (all gains near zero).
+    /// Check if this preset is effectively flat (no pass, notch or band-pass
+    /// bands and all gains near zero).
     pub fn is_flat(&self) -> bool {
-        self.bands.iter().all(|b| b.gain.abs() < 0.1)
+        self.bands
+            .iter()
+            .all(|b| b.kind.has_gain() && b.gain.abs() < 0.1)
     }
 }
@@ -14,4 +14,6 @@ pub use app_config::{AppConfig, MasterConfig, MixerConfig, SavedChannel};
 pub use eq_preset::EqPreset;
 pub use persistence::ConfigManager;
 pub use preset::GlobalPreset;
-pub use routing_rules::{MatchTarget, MatchType, RoutingRule, RoutingRulesConfig};
+pub use routing_rules::{
+    ConditionCombine, MatchTarget, MatchType, PropertyCondition, RoutingRule, RoutingRulesConfig,
+};
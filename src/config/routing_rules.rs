@@ -161,6 +161,78 @@ impl fmt::Display for MatchTarget {
     }
 }
 
+/// A condition on a single PipeWire node property.
+///
+/// Lets rules tell apart streams that share an app name and binary, e.g.
+/// Firefox tabs (`media.name`), Flatpak apps (`pipewire.access.portal.app_id`)
+/// or streams with a given `media.role`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PropertyCondition {
+    /// Node property key (e.g., "media.role", "node.name").
+    pub property: String,
+    /// How to match the property value.
+    pub match_type: MatchType,
+}
+
+impl PropertyCondition {
+    /// Property keys offered as suggestions in the UI.
+    pub const COMMON_PROPERTIES: &'static [&'static str] = &[
+        "media.role",
+        "media.name",
+        "node.name",
+        "application.process.id",
+        "pipewire.access.portal.app_id",
+    ];
+
+    /// Create a new property condition.
+    pub fn new(property: impl Into<String>, match_type: MatchType) -> Self {
+        Self {
+            property: property.into(),
+            match_type,
+        }
+    }
+
+    /// Check if the node's properties satisfy this condition.
+    /// A missing property never matches.
+    pub fn matches(&self, properties: &HashMap<String, String>) -> bool {
+        properties
+            .get(&self.property)
+            .map(|value| self.match_type.matches(value))
+            .unwrap_or(false)
+    }
+}
+
+/// How a rule's conditions are combined.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum ConditionCombine {
+    /// Every condition must match (AND).
+    #[default]
+    All,
+    /// At least one condition must match (OR).
+    Any,
+}
+
+impl ConditionCombine {
+    /// Get all variants for UI selection.
+    pub fn all() -> &'static [ConditionCombine] {
+        &[ConditionCombine::All, ConditionCombine::Any]
+    }
+
+    /// Get display name.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ConditionCombine::All => "Match all",
+            ConditionCombine::Any => "Match any",
+        }
+    }
+}
+
+impl fmt::Display for ConditionCombine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
 /// How to handle multiple audio streams from the same application.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
 pub enum AppGrouping {
@@ -219,6 +291,12 @@ pub struct RoutingRule {
     pub enabled: bool,
     /// Priority (lower = higher priority, evaluated first).
     pub priority: u32,
+    /// Extra conditions on node properties.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<PropertyCondition>,
+    /// How the name/binary match and `conditions` are combined.
+    #[serde(default)]
+    pub combine: ConditionCombine,
 }
 
 impl RoutingRule {
@@ -232,15 +310,49 @@ impl RoutingRule {
             target_channel: target_channel.into(),
             enabled: true,
             priority: 100,
+            conditions: Vec::new(),
+            combine: ConditionCombine::All,
         }
     }
 
     /// Check if an app matches this rule.
     pub fn matches(&self, app_name: &str, binary: Option<&str>) -> bool {
+        self.matches_node(app_name, binary, &HashMap::new())
+    }
+
+    /// Check if an app's node matches this rule, including property conditions.
+    ///
+    /// The name/binary match counts as one condition unless its pattern is
+    /// empty, so a rule can match on properties alone. A rule with no
+    /// conditions at all never matches.
+    pub fn matches_node(
+        &self,
+        app_name: &str,
+        binary: Option<&str>,
+        properties: &HashMap<String, String>,
+    ) -> bool {
         if !self.enabled {
             return false;
         }
 
+        let app_condition = (!self.match_type.pattern().is_empty())
+            .then(|| self.matches_app(app_name, binary));
+        let mut results = app_condition
+            .into_iter()
+            .chain(self.conditions.iter().map(|c| c.matches(properties)))
+            .peekable();
+
+        if results.peek().is_none() {
+            return false;
+        }
+        match self.combine {
+            ConditionCombine::All => results.all(|matched| matched),
+            ConditionCombine::Any => results.any(|matched| matched),
+        }
+    }
+
+    /// Check the name/binary part of the rule.
+    fn matches_app(&self, app_name: &str, binary: Option<&str>) -> bool {
         match self.match_target {
             MatchTarget::AppName => self.match_type.matches(app_name),
             MatchTarget::Binary => {
@@ -288,10 +400,17 @@ impl RoutingRulesConfig {
         self.rules.sort_by_key(|r| r.priority);
     }
 
-    /// Find the first matching rule for an app.
-    pub fn find_match(&self, app_name: &str, binary: Option<&str>) -> Option<&RoutingRule> {
+    /// Find the first matching rule for an app's node.
+    pub fn find_match(
+        &self,
+        app_name: &str,
+        binary: Option<&str>,
+        properties: &HashMap<String, String>,
+    ) -> Option<&RoutingRule> {
         // Rules are expected to be sorted by priority
-        self.rules.iter().find(|r| r.matches(app_name, binary))
+        self.rules
+            .iter()
+            .find(|r| r.matches_node(app_name, binary, properties))
     }
 
     /// Add a new rule.
@@ -511,4 +630,59 @@ mod tests {
         assert!(rule.matches("Something", Some("discord")));
         assert!(!rule.matches("Discord", Some("other-binary")));
     }
+
+    fn props(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_routing_rule_property_conditions() {
+        let mut rule = RoutingRule::new("Firefox calls", "firefox", "Communication");
+        rule.conditions
+            .push(PropertyCondition::new("media.role", MatchType::Exact("Communication".into())));
+
+        let call = props(&[("media.role", "communication")]);
+        let music = props(&[("media.role", "Music")]);
+        assert!(rule.matches_node("Firefox", Some("firefox"), &call));
+        assert!(!rule.matches_node("Firefox", Some("firefox"), &music));
+        assert!(!rule.matches_node("Firefox", Some("firefox"), &HashMap::new()));
+
+        rule.combine = ConditionCombine::Any;
+        assert!(rule.matches_node("Firefox", Some("firefox"), &music));
+        assert!(rule.matches_node("Chrome", Some("chrome"), &call));
+        assert!(!rule.matches_node("Chrome", Some("chrome"), &music));
+    }
+
+    #[test]
+    fn test_routing_rule_properties_only() {
+        let mut rule = RoutingRule::new("Flatpak Spotify", "", "Music");
+        assert!(!rule.matches("Spotify", Some("spotify")));
+
+        rule.conditions.push(PropertyCondition::new(
+            "pipewire.access.portal.app_id",
+            MatchType::Exact("com.spotify.Client".into()),
+        ));
+        let node = props(&[("pipewire.access.portal.app_id", "com.spotify.Client")]);
+        assert!(rule.matches_node("Spotify", None, &node));
+        assert!(!rule.matches_node("Spotify", None, &HashMap::new()));
+    }
+
+    #[test]
+    fn test_routing_rule_toml_round_trip() {
+        let mut rule = RoutingRule::new("Role", "", "Voice");
+        rule.combine = ConditionCombine::Any;
+        rule.conditions
+            .push(PropertyCondition::new("media.role", MatchType::Glob("comm*".into())));
+        let config = RoutingRulesConfig {
+            rules: vec![rule],
+            app_grouping: AppGrouping::default(),
+        };
+
+        let parsed = RoutingRulesConfig::from_toml(&config.to_toml().unwrap()).unwrap();
+        assert_eq!(parsed.rules[0].conditions, config.rules[0].conditions);
+        assert_eq!(parsed.rules[0].combine, ConditionCombine::Any);
+    }
 }
@@ -6,85 +6,15 @@
 
 #![allow(dead_code, unused_imports)]
 
-use sootmix_ipc::{AppInfo, ChannelInfo, InputInfo, MeterData, OutputInfo, RoutingRuleInfo};
+use sootmix_ipc::{
+    AppInfo, AutoLevelInfo, ChannelInfo, ChannelLayout, DaemonProxy, EqBandInfo, InputInfo,
+    LoudnessData, MeterData, MonitorInfo, NoiseSuppressionInfo, OutputInfo, PluginSlotInfo,
+    RoutingRuleInfo, SoloMode, SpectrumData, SpectrumTap, TalkInfo,
+};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
-use zbus::{proxy, Connection, Result as ZbusResult};
-
-/// D-Bus proxy for the daemon interface.
-#[proxy(
-    interface = "com.sootmix.Daemon",
-    default_service = "com.sootmix.Daemon",
-    default_path = "/com/sootmix/Daemon"
-)]
-trait Daemon {
-    // Methods
-    fn create_channel(&self, name: &str) -> ZbusResult<String>;
-    fn create_input_channel(&self, name: &str) -> ZbusResult<String>;
-    fn delete_channel(&self, channel_id: &str) -> ZbusResult<()>;
-    fn rename_channel(&self, channel_id: &str, name: &str) -> ZbusResult<()>;
-    fn move_channel(&self, channel_id: &str, direction: i32) -> ZbusResult<()>;
-    fn set_channel_volume(&self, channel_id: &str, volume_db: f64) -> ZbusResult<()>;
-    fn set_channel_mute(&self, channel_id: &str, muted: bool) -> ZbusResult<()>;
-    fn set_channel_noise_suppression(&self, channel_id: &str, enabled: bool) -> ZbusResult<()>;
-    fn set_channel_vad_threshold(&self, channel_id: &str, threshold: f64) -> ZbusResult<()>;
-    fn set_channel_input_gain(&self, channel_id: &str, gain_db: f64) -> ZbusResult<()>;
-    fn set_master_volume(&self, volume_db: f64) -> ZbusResult<()>;
-    fn set_master_mute(&self, muted: bool) -> ZbusResult<()>;
-    fn assign_app(&self, app_id: &str, channel_id: &str) -> ZbusResult<()>;
-    fn unassign_app(&self, app_id: &str, channel_id: &str) -> ZbusResult<()>;
-    fn set_channel_output(&self, channel_id: &str, device_name: &str) -> ZbusResult<()>;
-    fn set_master_output(&self, device_name: &str) -> ZbusResult<()>;
-    fn set_master_recording(&self, enabled: bool) -> ZbusResult<()>;
-    fn get_channels(&self) -> ZbusResult<Vec<ChannelInfo>>;
-    fn get_apps(&self) -> ZbusResult<Vec<AppInfo>>;
-    fn get_outputs(&self) -> ZbusResult<Vec<OutputInfo>>;
-    fn get_inputs(&self) -> ZbusResult<Vec<InputInfo>>;
-    fn get_master_volume(&self) -> ZbusResult<f64>;
-    fn get_master_muted(&self) -> ZbusResult<bool>;
-    fn get_master_output(&self) -> ZbusResult<String>;
-    fn get_connected(&self) -> ZbusResult<bool>;
-    fn get_master_recording_enabled(&self) -> ZbusResult<bool>;
-    fn get_routing_rules(&self) -> ZbusResult<Vec<RoutingRuleInfo>>;
-    fn set_routing_rule(&self, rule: RoutingRuleInfo) -> ZbusResult<()>;
-    fn delete_routing_rule(&self, rule_id: &str) -> ZbusResult<()>;
-    fn toggle_routing_rule(&self, rule_id: &str) -> ZbusResult<()>;
-
-    // Signals
-    #[zbus(signal)]
-    fn channel_added(&self, channel: ChannelInfo) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn channel_removed(&self, channel_id: &str) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn channel_updated(&self, channel: ChannelInfo) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn volume_changed(&self, channel_id: &str, volume_db: f64) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn mute_changed(&self, channel_id: &str, muted: bool) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn app_discovered(&self, app: AppInfo) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn app_removed(&self, app_id: &str) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn app_routed(&self, app_id: &str, channel_id: &str) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn app_unrouted(&self, app_id: &str, channel_id: &str) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn connection_changed(&self, connected: bool) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn error_occurred(&self, message: &str) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn meter_update(&self, data: Vec<MeterData>) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn master_volume_changed(&self, volume_db: f64) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn master_mute_changed(&self, muted: bool) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn outputs_changed(&self) -> ZbusResult<()>;
-    #[zbus(signal)]
-    fn inputs_changed(&self) -> ZbusResult<()>;
-}
+use zbus::Connection;
 
 /// Events received from the daemon.
 #[derive(Debug, Clone)]
@@ -103,10 +33,16 @@ pub enum DaemonEvent {
     PipeWireConnectionChanged(bool),
     Error(String),
     MeterUpdate(Vec<MeterData>),
+    LoudnessUpdate(Vec<LoudnessData>),
+    SpectrumUpdate(SpectrumData),
     MasterVolumeChanged(f64),
     MasterMuteChanged(bool),
     OutputsChanged,
     InputsChanged,
+    SoloChanged { channel_id: String, solo: bool },
+    ChannelTalking { channel_id: String, talking: bool },
+    ChannelLiveChanged { channel_id: String, live: bool },
+    MonitorChanged(MonitorInfo),
     /// Initial state snapshot after connection
     InitialState {
         channels: Vec<ChannelInfo>,
@@ -118,6 +54,7 @@ pub enum DaemonEvent {
         master_output: String,
         connected: bool,
         recording_enabled: bool,
+        monitor: MonitorInfo,
     },
 }
 
@@ -171,6 +108,8 @@ impl DaemonClient {
             .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))?;
         let recording_enabled = self.proxy.get_master_recording_enabled().await
             .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))?;
+        let monitor = self.proxy.get_monitor().await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))?;
 
         Ok(DaemonEvent::InitialState {
             channels,
@@ -182,6 +121,7 @@ impl DaemonClient {
             master_output,
             connected,
             recording_enabled,
+            monitor,
         })
     }
 
@@ -237,6 +177,20 @@ impl DaemonClient {
             .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
     }
 
+    /// Set channel pan/balance and width.
+    pub async fn set_channel_balance(&self, channel_id: &str, pan: f64, width: f64) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} pan to {:.2}, width to {:.2}", channel_id, pan, width);
+        self.proxy.set_channel_balance(channel_id, pan, width).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Set an output channel's speaker layout (recreates its sink).
+    pub async fn set_channel_layout(&self, channel_id: &str, layout: ChannelLayout) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} layout to {}", channel_id, layout);
+        self.proxy.set_channel_layout(channel_id, layout.as_str()).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
     /// Enable or disable noise suppression on an input channel.
     pub async fn set_channel_noise_suppression(&self, channel_id: &str, enabled: bool) -> Result<(), DaemonClientError> {
         debug!("Setting channel {} noise suppression to {}", channel_id, enabled);
@@ -251,6 +205,27 @@ impl DaemonClient {
             .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
     }
 
+    /// Set the noise suppression model, VAD grace periods and attenuation limit of an input channel.
+    pub async fn set_channel_noise_suppression_settings(&self, channel_id: &str, settings: NoiseSuppressionInfo) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} noise suppression to {:?}", channel_id, settings);
+        self.proxy.set_channel_noise_suppression_settings(channel_id, settings).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Set the push-to-talk mode, release tail and fade of an input channel.
+    pub async fn set_channel_talk_settings(&self, channel_id: &str, settings: TalkInfo) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} talk settings to {:?}", channel_id, settings);
+        self.proxy.set_channel_talk_settings(channel_id, settings).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Press or release the talk key of an input channel (empty ID for all of them).
+    pub async fn set_talk_key(&self, channel_id: &str, pressed: bool) -> Result<(), DaemonClientError> {
+        debug!("Setting talk key of channel '{}' to {}", channel_id, pressed);
+        self.proxy.set_talk_key(channel_id, pressed).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
     /// Set the hardware microphone gain for an input channel.
     pub async fn set_channel_input_gain(&self, channel_id: &str, gain_db: f64) -> Result<(), DaemonClientError> {
         debug!("Setting channel {} input gain to {} dB", channel_id, gain_db);
@@ -258,6 +233,84 @@ impl DaemonClient {
             .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
     }
 
+    /// Set a channel's automatic loudness normalization.
+    pub async fn set_channel_auto_level(&self, channel_id: &str, settings: AutoLevelInfo) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} auto level to {:?}", channel_id, settings);
+        self.proxy.set_channel_auto_level(channel_id, settings).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    // ==================== EQ ====================
+
+    /// Enable or disable the EQ on an output channel.
+    pub async fn set_channel_eq_enabled(&self, channel_id: &str, enabled: bool) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} EQ enabled to {}", channel_id, enabled);
+        self.proxy.set_channel_eq_enabled(channel_id, enabled).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Set the EQ preset for a channel.
+    pub async fn set_channel_eq_preset(&self, channel_id: &str, preset_name: &str) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} EQ preset to {}", channel_id, preset_name);
+        self.proxy.set_channel_eq_preset(channel_id, preset_name).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Replace a channel's EQ bands with an edited set.
+    pub async fn set_channel_eq_bands(&self, channel_id: &str, bands: Vec<EqBandInfo>) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} EQ to {} bands", channel_id, bands.len());
+        self.proxy.set_channel_eq_bands(channel_id, bands).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Get the names of all EQ presets known to the daemon.
+    pub async fn get_eq_presets(&self) -> Result<Vec<String>, DaemonClientError> {
+        self.proxy.get_eq_presets().await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    // ==================== Plugins ====================
+
+    /// Load a plugin into a new slot at the end of a channel's chain.
+    pub async fn add_channel_plugin(&self, channel_id: &str, plugin_id: &str) -> Result<String, DaemonClientError> {
+        debug!("Adding plugin {} to channel {}", plugin_id, channel_id);
+        self.proxy.add_channel_plugin(channel_id, plugin_id).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Remove a slot from a channel's plugin chain.
+    pub async fn remove_channel_plugin(&self, channel_id: &str, slot: u32) -> Result<(), DaemonClientError> {
+        debug!("Removing plugin slot {} from channel {}", slot, channel_id);
+        self.proxy.remove_channel_plugin(channel_id, slot).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Move a plugin slot within a channel's chain.
+    pub async fn move_channel_plugin(&self, channel_id: &str, from_slot: u32, to_slot: u32) -> Result<(), DaemonClientError> {
+        debug!("Moving plugin slot {} -> {} in channel {}", from_slot, to_slot, channel_id);
+        self.proxy.move_channel_plugin(channel_id, from_slot, to_slot).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Bypass or re-enable a plugin slot.
+    pub async fn set_channel_plugin_bypassed(&self, channel_id: &str, slot: u32, bypassed: bool) -> Result<(), DaemonClientError> {
+        debug!("Setting plugin slot {} bypass to {} in channel {}", slot, bypassed, channel_id);
+        self.proxy.set_channel_plugin_bypassed(channel_id, slot, bypassed).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Set a parameter (normalized 0.0-1.0) on a plugin slot.
+    pub async fn set_channel_plugin_parameter(&self, channel_id: &str, slot: u32, param_index: u32, value: f64) -> Result<(), DaemonClientError> {
+        self.proxy.set_channel_plugin_parameter(channel_id, slot, param_index, value).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Get the slots of a channel's plugin chain.
+    pub async fn get_channel_plugins(&self, channel_id: &str) -> Result<Vec<PluginSlotInfo>, DaemonClientError> {
+        self.proxy.get_channel_plugins(channel_id).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
     /// Set master volume in dB.
     pub async fn set_master_volume(&self, volume_db: f64) -> Result<(), DaemonClientError> {
         self.proxy.set_master_volume(volume_db).await
@@ -303,6 +356,56 @@ impl DaemonClient {
             .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
     }
 
+    // ==================== Monitor ====================
+
+    /// Solo or unsolo a channel.
+    pub async fn set_channel_solo(&self, channel_id: &str, solo: bool) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} solo to {}", channel_id, solo);
+        self.proxy.set_channel_solo(channel_id, solo).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Turn an input channel's sidetone on or off and set its level.
+    pub async fn set_channel_sidetone(&self, channel_id: &str, enabled: bool, volume_db: f64) -> Result<(), DaemonClientError> {
+        debug!("Setting channel {} sidetone to {} at {:.1} dB", channel_id, enabled, volume_db);
+        self.proxy.set_channel_sidetone(channel_id, enabled, volume_db).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Set the monitor bus device (empty = master output).
+    pub async fn set_monitor_device(&self, device_name: &str) -> Result<(), DaemonClientError> {
+        debug!("Setting monitor device to {}", device_name);
+        self.proxy.set_monitor_device(device_name).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    /// Set how soloed channels are heard.
+    pub async fn set_solo_mode(&self, mode: SoloMode) -> Result<(), DaemonClientError> {
+        debug!("Setting solo mode to {}", mode);
+        self.proxy.set_solo_mode(mode.as_str()).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    // ==================== Loudness ====================
+
+    /// Restart loudness measurement of a channel, of the master output
+    /// ("master"), or of every meter (empty ID).
+    pub async fn reset_loudness(&self, channel_id: &str) -> Result<(), DaemonClientError> {
+        debug!("Resetting loudness of {:?}", channel_id);
+        self.proxy.reset_loudness(channel_id).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
+    // ==================== Spectrum ====================
+
+    /// Analyze the spectrum of a channel or the master output ("master") at
+    /// `tap`, or stop the analyzer (empty target).
+    pub async fn set_spectrum(&self, target: &str, tap: SpectrumTap) -> Result<(), DaemonClientError> {
+        debug!("Setting spectrum analyzer to {:?} ({})", target, tap);
+        self.proxy.set_spectrum(target, tap.as_str()).await
+            .map_err(|e| DaemonClientError::MethodCallFailed(e.to_string()))
+    }
+
     // ==================== Recording ====================
 
     /// Enable or disable master recording output.
@@ -400,6 +503,8 @@ pub enum DaemonCommand {
     RenameChannel { id: String, name: String },
     SetChannelVolume { id: String, volume_db: f64 },
     SetChannelMute { id: String, muted: bool },
+    SetChannelBalance { id: String, pan: f64, width: f64 },
+    SetChannelLayout { id: String, layout: ChannelLayout },
     SetMasterVolume(f64),
     SetMasterMute(bool),
     AssignApp { app_id: String, channel_id: String },
@@ -409,8 +514,26 @@ pub enum DaemonCommand {
     SetMasterRecording(bool),
     SetChannelNoiseSuppression { channel_id: String, enabled: bool },
     SetChannelVadThreshold { channel_id: String, threshold: f64 },
+    SetChannelNoiseSuppressionSettings { channel_id: String, settings: NoiseSuppressionInfo },
+    SetChannelTalkSettings { channel_id: String, settings: TalkInfo },
+    SetTalkKey { channel_id: String, pressed: bool },
     SetChannelInputGain { channel_id: String, gain_db: f64 },
+    SetChannelAutoLevel { channel_id: String, settings: AutoLevelInfo },
+    SetChannelEqEnabled { channel_id: String, enabled: bool },
+    SetChannelEqPreset { channel_id: String, preset_name: String },
+    SetChannelEqBands { channel_id: String, bands: Vec<EqBandInfo> },
+    AddChannelPlugin { channel_id: String, plugin_id: String },
+    RemoveChannelPlugin { channel_id: String, slot: u32 },
+    MoveChannelPlugin { channel_id: String, from_slot: u32, to_slot: u32 },
+    SetChannelPluginBypassed { channel_id: String, slot: u32, bypassed: bool },
+    SetChannelPluginParameter { channel_id: String, slot: u32, param_index: u32, value: f64 },
     MoveChannel { channel_id: String, direction: i32 },
+    SetChannelSolo { channel_id: String, solo: bool },
+    SetChannelSidetone { channel_id: String, enabled: bool, volume_db: f64 },
+    SetMonitorDevice(String),
+    SetSoloMode(SoloMode),
+    ResetLoudness(String),
+    SetSpectrum { target: String, tap: SpectrumTap },
 }
 
 /// Global command sender for the daemon subscription.
@@ -699,6 +822,10 @@ async fn listen_to_signals(
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
     let mut meter_update = client.proxy.receive_meter_update().await
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut loudness_update = client.proxy.receive_loudness_update().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut spectrum_update = client.proxy.receive_spectrum_update().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
     let mut master_volume_changed = client.proxy.receive_master_volume_changed().await
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
     let mut master_mute_changed = client.proxy.receive_master_mute_changed().await
@@ -707,6 +834,14 @@ async fn listen_to_signals(
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
     let mut inputs_changed = client.proxy.receive_inputs_changed().await
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut solo_changed = client.proxy.receive_solo_changed().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut channel_talking = client.proxy.receive_channel_talking().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut channel_live_changed = client.proxy.receive_channel_live_changed().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut monitor_changed = client.proxy.receive_monitor_changed().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
 
     loop {
         tokio::select! {
@@ -782,6 +917,16 @@ async fn listen_to_signals(
                     let _ = tx.send(DaemonEvent::MeterUpdate(args.data));
                 }
             }
+            Some(signal) = loudness_update.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::LoudnessUpdate(args.data));
+                }
+            }
+            Some(signal) = spectrum_update.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::SpectrumUpdate(args.data));
+                }
+            }
             Some(signal) = master_volume_changed.next() => {
                 if let Ok(args) = signal.args() {
                     let _ = tx.send(DaemonEvent::MasterVolumeChanged(args.volume_db));
@@ -798,6 +943,35 @@ async fn listen_to_signals(
             Some(_signal) = inputs_changed.next() => {
                 let _ = tx.send(DaemonEvent::InputsChanged);
             }
+            Some(signal) = solo_changed.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::SoloChanged {
+                        channel_id: args.channel_id.to_string(),
+                        solo: args.solo,
+                    });
+                }
+            }
+            Some(signal) = channel_talking.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::ChannelTalking {
+                        channel_id: args.channel_id.to_string(),
+                        talking: args.talking,
+                    });
+                }
+            }
+            Some(signal) = channel_live_changed.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::ChannelLiveChanged {
+                        channel_id: args.channel_id.to_string(),
+                        live: args.live,
+                    });
+                }
+            }
+            Some(signal) = monitor_changed.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::MonitorChanged(args.monitor));
+                }
+            }
             else => {
                 // All streams ended, connection lost
                 break;
@@ -832,6 +1006,12 @@ async fn handle_daemon_command(
         DaemonCommand::SetChannelMute { id, muted } => {
             client.set_channel_mute(&id, muted).await?;
         }
+        DaemonCommand::SetChannelBalance { id, pan, width } => {
+            client.set_channel_balance(&id, pan, width).await?;
+        }
+        DaemonCommand::SetChannelLayout { id, layout } => {
+            client.set_channel_layout(&id, layout).await?;
+        }
         DaemonCommand::SetMasterVolume(volume_db) => {
             client.set_master_volume(volume_db).await?;
         }
@@ -862,9 +1042,63 @@ async fn handle_daemon_command(
         DaemonCommand::SetChannelInputGain { channel_id, gain_db } => {
             client.set_channel_input_gain(&channel_id, gain_db).await?;
         }
+        DaemonCommand::SetChannelNoiseSuppressionSettings { channel_id, settings } => {
+            client.set_channel_noise_suppression_settings(&channel_id, settings).await?;
+        }
+        DaemonCommand::SetChannelTalkSettings { channel_id, settings } => {
+            client.set_channel_talk_settings(&channel_id, settings).await?;
+        }
+        DaemonCommand::SetTalkKey { channel_id, pressed } => {
+            client.set_talk_key(&channel_id, pressed).await?;
+        }
+        DaemonCommand::SetChannelAutoLevel { channel_id, settings } => {
+            client.set_channel_auto_level(&channel_id, settings).await?;
+        }
+        DaemonCommand::SetChannelEqEnabled { channel_id, enabled } => {
+            client.set_channel_eq_enabled(&channel_id, enabled).await?;
+        }
+        DaemonCommand::SetChannelEqPreset { channel_id, preset_name } => {
+            client.set_channel_eq_preset(&channel_id, &preset_name).await?;
+        }
+        DaemonCommand::SetChannelEqBands { channel_id, bands } => {
+            client.set_channel_eq_bands(&channel_id, bands).await?;
+        }
+        DaemonCommand::AddChannelPlugin { channel_id, plugin_id } => {
+            client.add_channel_plugin(&channel_id, &plugin_id).await?;
+        }
+        DaemonCommand::RemoveChannelPlugin { channel_id, slot } => {
+            client.remove_channel_plugin(&channel_id, slot).await?;
+        }
+        DaemonCommand::MoveChannelPlugin { channel_id, from_slot, to_slot } => {
+            client.move_channel_plugin(&channel_id, from_slot, to_slot).await?;
+        }
+        DaemonCommand::SetChannelPluginBypassed { channel_id, slot, bypassed } => {
+            client.set_channel_plugin_bypassed(&channel_id, slot, bypassed).await?;
+        }
+        DaemonCommand::SetChannelPluginParameter { channel_id, slot, param_index, value } => {
+            client.set_channel_plugin_parameter(&channel_id, slot, param_index, value).await?;
+        }
         DaemonCommand::MoveChannel { channel_id, direction } => {
             client.move_channel(&channel_id, direction).await?;
         }
+        DaemonCommand::SetChannelSolo { channel_id, solo } => {
+            client.set_channel_solo(&channel_id, solo).await?;
+        }
+        DaemonCommand::SetChannelSidetone { channel_id, enabled, volume_db } => {
+            client.set_channel_sidetone(&channel_id, enabled, volume_db).await?;
+        }
+        DaemonCommand::SetMonitorDevice(device_name) => {
+            client.set_monitor_device(&device_name).await?;
+        }
+        DaemonCommand::SetSoloMode(mode) => {
+            client.set_solo_mode(mode).await?;
+        }
+        DaemonCommand::ResetLoudness(channel_id) => {
+            client.reset_loudness(&channel_id).await?;
+        }
+        DaemonCommand::SetSpectrum { target, tap } => {
+            client.set_spectrum(&target, tap).await?;
+        }
     }
     Ok(())
 }
@@ -901,6 +1135,10 @@ async fn listen_to_signals_arc(
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
     let mut meter_update = client.proxy.receive_meter_update().await
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut loudness_update = client.proxy.receive_loudness_update().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut spectrum_update = client.proxy.receive_spectrum_update().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
     let mut master_volume_changed = client.proxy.receive_master_volume_changed().await
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
     let mut master_mute_changed = client.proxy.receive_master_mute_changed().await
@@ -909,6 +1147,14 @@ async fn listen_to_signals_arc(
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
     let mut inputs_changed = client.proxy.receive_inputs_changed().await
         .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut solo_changed = client.proxy.receive_solo_changed().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut channel_talking = client.proxy.receive_channel_talking().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut channel_live_changed = client.proxy.receive_channel_live_changed().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
+    let mut monitor_changed = client.proxy.receive_monitor_changed().await
+        .map_err(|e| DaemonClientError::SignalSubscriptionFailed(e.to_string()))?;
 
     loop {
         tokio::select! {
@@ -984,6 +1230,16 @@ async fn listen_to_signals_arc(
                     let _ = tx.send(DaemonEvent::MeterUpdate(args.data));
                 }
             }
+            Some(signal) = loudness_update.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::LoudnessUpdate(args.data));
+                }
+            }
+            Some(signal) = spectrum_update.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::SpectrumUpdate(args.data));
+                }
+            }
             Some(signal) = master_volume_changed.next() => {
                 if let Ok(args) = signal.args() {
                     let _ = tx.send(DaemonEvent::MasterVolumeChanged(args.volume_db));
@@ -1000,6 +1256,35 @@ async fn listen_to_signals_arc(
             Some(_signal) = inputs_changed.next() => {
                 let _ = tx.send(DaemonEvent::InputsChanged);
             }
+            Some(signal) = solo_changed.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::SoloChanged {
+                        channel_id: args.channel_id.to_string(),
+                        solo: args.solo,
+                    });
+                }
+            }
+            Some(signal) = channel_talking.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::ChannelTalking {
+                        channel_id: args.channel_id.to_string(),
+                        talking: args.talking,
+                    });
+                }
+            }
+            Some(signal) = channel_live_changed.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::ChannelLiveChanged {
+                        channel_id: args.channel_id.to_string(),
+                        live: args.live,
+                    });
+                }
+            }
+            Some(signal) = monitor_changed.next() => {
+                if let Ok(args) = signal.args() {
+                    let _ = tx.send(DaemonEvent::MonitorChanged(args.monitor));
+                }
+            }
             else => {
                 // All streams ended, connection lost
                 break;
@@ -7,7 +7,10 @@
 use crate::audio::types::{PwLink, PwNode, PwPort};
 use crate::config::eq_preset::EqPreset;
 use crate::daemon_client::DaemonEvent;
-use crate::state::{ChannelFilter, SnapshotSlot};
+use crate::state::{
+    AutoLevelInfo, ChannelFilter, ChannelLayout, EqBandInfo, NoiseModel, NoiseSuppressionInfo,
+    SnapshotSlot, SoloMode, SpectrumTap, TalkInfo, TalkMode,
+};
 use iced::advanced::widget::Id as WidgetId;
 use uuid::Uuid;
 
@@ -21,6 +24,14 @@ pub enum Message {
     ChannelVolumeReleased(Uuid),
     /// Channel mute toggled.
     ChannelMuteToggled(Uuid),
+    /// Channel pan changed (channel_id, pan -1.0 to 1.0).
+    ChannelPanChanged(Uuid, f32),
+    /// Channel width changed (channel_id, width 0.0 to 1.0).
+    ChannelWidthChanged(Uuid, f32),
+    /// Pan or width slider released (channel_id). Applies and saves the balance.
+    ChannelBalanceReleased(Uuid),
+    /// Channel speaker layout picked (channel_id, layout).
+    ChannelLayoutChanged(Uuid, ChannelLayout),
     /// Channel EQ enabled/disabled.
     ChannelEqToggled(Uuid),
     /// Channel EQ preset changed (channel_id, preset_name).
@@ -78,6 +89,10 @@ pub enum Message {
     OutputDeviceChanged(String),
     /// Monitor output device changed (for PFL solo routing).
     MonitorDeviceChanged(String),
+    /// Solo mode changed (PFL or solo in place).
+    SoloModeChanged(SoloMode),
+    /// Restart loudness measurement of a channel, or of the master output for `None`.
+    ResetLoudness(Option<Uuid>),
     /// Toggle master recording output.
     ToggleMasterRecording,
 
@@ -99,10 +114,30 @@ pub enum Message {
     ChannelVADThresholdChanged(Uuid, f32),
     /// VAD threshold slider released (channel_id). Sends the final value to the daemon.
     ChannelVADThresholdReleased(Uuid),
+    /// Noise suppression model chosen (channel_id, model).
+    ChannelNoiseModelSelected(Uuid, NoiseModel),
+    /// Noise suppression grace or attenuation edited (channel_id, settings). Local state only.
+    ChannelNoiseSuppressionChanged(Uuid, NoiseSuppressionInfo),
+    /// Noise suppression slider released (channel_id). Sends the settings to the daemon.
+    ChannelNoiseSuppressionReleased(Uuid),
+    /// Push-to-talk mode chosen (channel_id, mode).
+    ChannelTalkModeSelected(Uuid, TalkMode),
+    /// Push-to-talk release or fade edited (channel_id, settings). Local state only.
+    ChannelTalkChanged(Uuid, TalkInfo),
+    /// Push-to-talk slider released (channel_id). Sends the settings to the daemon.
+    ChannelTalkReleased(Uuid),
+    /// Talk button pressed or released (channel_id, pressed).
+    ChannelTalkKey(Uuid, bool),
     /// Hardware microphone gain changed (channel_id, gain_db -12 to +12).
     ChannelInputGainChanged(Uuid, f32),
     /// Hardware microphone gain slider released (channel_id).
     ChannelInputGainReleased(Uuid),
+    /// Toggle auto level (loudness normalization) on a channel.
+    ChannelAutoLevelToggled(Uuid),
+    /// Auto level settings edited (channel_id, settings). Local state only.
+    ChannelAutoLevelChanged(Uuid, AutoLevelInfo),
+    /// Auto level slider released (channel_id). Sends the settings to the daemon.
+    ChannelAutoLevelReleased(Uuid),
     /// Global preset selected.
     PresetSelected(String),
     /// Save current configuration as preset.
@@ -112,6 +147,8 @@ pub enum Message {
 
     /// Select a channel for the focus panel (None to deselect).
     SelectChannel(Option<Uuid>),
+    /// Analyze a channel's spectrum at a tap, or turn the analyzer off (None).
+    SpectrumTapSelected(Uuid, Option<SpectrumTap>),
     /// Toggle the left sidebar collapsed state.
     ToggleLeftSidebar,
     /// Toggle the bottom detail panel expanded state.
@@ -121,10 +158,18 @@ pub enum Message {
     OpenEqPanel(Uuid),
     /// Close EQ panel.
     CloseEqPanel,
-    /// EQ band gain changed (band_index, gain_db).
-    EqBandChanged(usize, f32),
-    /// EQ Q factor changed (band_index, q).
-    EqQChanged(usize, f32),
+    /// EQ band clicked in the editor (`None` clears the selection).
+    EqBandSelected(Option<usize>),
+    /// EQ band dragged or slid (channel_id, band_index, band) - local only.
+    EqBandChanged(Uuid, usize, EqBandInfo),
+    /// EQ band drag or slider released - send the bands to the daemon.
+    EqBandsReleased(Uuid),
+    /// EQ band type or slope picked (channel_id, band_index, band) - sent at once.
+    EqBandSet(Uuid, usize, EqBandInfo),
+    /// Add a bell band to a channel's EQ.
+    EqBandAdded(Uuid),
+    /// Remove a band from a channel's EQ (channel_id, band_index).
+    EqBandRemoved(Uuid, usize),
     /// Reset EQ to flat.
     EqReset,
     /// Save current EQ as preset.
@@ -188,6 +233,18 @@ pub enum Message {
     RuleTargetChannelChanged(String),
     /// Update the rule priority.
     RulePriorityChanged(String),
+    /// Change how the rule's conditions are combined.
+    RuleCombineChanged(crate::config::ConditionCombine),
+    /// Add an empty property condition to the rule.
+    AddRuleCondition,
+    /// Remove a property condition by index.
+    RemoveRuleCondition(usize),
+    /// Update a condition's property key (index, key).
+    RuleConditionPropertyChanged(usize, String),
+    /// Update a condition's match type (index, type).
+    RuleConditionMatchTypeChanged(usize, String),
+    /// Update a condition's pattern (index, pattern).
+    RuleConditionPatternChanged(usize, String),
     /// Save the current rule being edited.
     SaveRoutingRule,
     /// Create a quick rule from an app (app_name, binary, target_channel).
@@ -318,6 +375,8 @@ pub enum Message {
     TrayShowWindow,
     /// Toggle mute all (from tray).
     TrayToggleMuteAll,
+    /// Hold or let go of the talk key (from tray).
+    TrayToggleTalk,
     /// Quit application (from tray).
     TrayQuit,
 
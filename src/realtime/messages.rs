@@ -0,0 +1,108 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Messages passed between the audio thread and the UI thread through the
+//! shared [`RingBuffer`](sootmix_realtime::RingBuffer).
+
+#![allow(dead_code)]
+
+/// Meter data sent from audio thread to UI.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MeterData {
+    /// Peak level (0.0 to 1.0+).
+    pub peak: f32,
+    /// RMS level (0.0 to 1.0+).
+    pub rms: f32,
+    /// Whether clipping occurred.
+    pub clipping: bool,
+}
+
+impl MeterData {
+    /// Create new meter data.
+    pub fn new(peak: f32, rms: f32) -> Self {
+        Self {
+            peak,
+            rms,
+            clipping: peak >= 1.0,
+        }
+    }
+
+    /// Convert to decibels.
+    pub fn peak_db(&self) -> f32 {
+        if self.peak <= 0.0 {
+            -80.0
+        } else {
+            20.0 * self.peak.log10()
+        }
+    }
+
+    /// Convert RMS to decibels.
+    pub fn rms_db(&self) -> f32 {
+        if self.rms <= 0.0 {
+            -80.0
+        } else {
+            20.0 * self.rms.log10()
+        }
+    }
+}
+
+/// Stereo meter data.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StereoMeterData {
+    /// Left channel.
+    pub left: MeterData,
+    /// Right channel.
+    pub right: MeterData,
+}
+
+/// Plugin parameter update sent from UI thread to RT audio thread.
+///
+/// Used to update plugin parameters without locking in the audio callback.
+#[derive(Debug, Clone, Copy)]
+pub struct PluginParamUpdate {
+    /// Plugin instance ID.
+    pub instance_id: uuid::Uuid,
+    /// Parameter index.
+    pub param_index: u32,
+    /// New parameter value.
+    pub value: f32,
+}
+
+impl PluginParamUpdate {
+    /// Create a new parameter update.
+    pub fn new(instance_id: uuid::Uuid, param_index: u32, value: f32) -> Self {
+        Self {
+            instance_id,
+            param_index,
+            value,
+        }
+    }
+}
+
+impl StereoMeterData {
+    /// Create new stereo meter data.
+    pub fn new(left: MeterData, right: MeterData) -> Self {
+        Self { left, right }
+    }
+
+    /// Check if either channel is clipping.
+    pub fn is_clipping(&self) -> bool {
+        self.left.clipping || self.right.clipping
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meter_data() {
+        let meter = MeterData::new(0.5, 0.3);
+        assert!(!meter.clipping);
+        assert!((meter.peak_db() - (-6.02)).abs() < 0.1);
+
+        let clipping = MeterData::new(1.5, 0.8);
+        assert!(clipping.clipping);
+    }
+}
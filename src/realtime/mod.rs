@@ -18,6 +18,7 @@
 //! All utilities in this module are designed to be called from the audio thread.
 
 pub mod atomic_params;
-pub mod ringbuf;
+pub mod messages;
 
-pub use ringbuf::{PluginParamUpdate, RingBuffer, RingBufferReader, RingBufferWriter};
+pub use messages::PluginParamUpdate;
+pub use sootmix_realtime::{RingBuffer, RingBufferReader, RingBufferWriter};
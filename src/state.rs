@@ -12,6 +12,7 @@ use crate::audio::types::{
 use crate::config::RoutingRulesConfig;
 use crate::plugins::PluginSlotConfig;
 use serde::{Deserialize, Serialize};
+use sootmix_ipc::Balance;
 use std::collections::{HashMap, HashSet};
 use tracing::debug;
 use uuid::Uuid;
@@ -182,8 +183,11 @@ impl MeterDisplayState {
     }
 }
 
-// Re-export ChannelKind from the IPC crate for consistency.
-pub use sootmix_ipc::ChannelKind;
+// Re-export ChannelKind and ChannelLayout from the IPC crate for consistency.
+pub use sootmix_ipc::{
+    AutoLevelInfo, ChannelKind, ChannelLayout, EqBandInfo, EqBandType, LoudnessData, NoiseModel,
+    NoiseSuppressionInfo, SoloMode, SpectrumData, SpectrumTap, TalkInfo, TalkMode,
+};
 
 /// Filter for which channels to display.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -205,10 +209,25 @@ pub struct MixerChannel {
     pub volume_db: f32,
     /// Whether the channel is muted.
     pub muted: bool,
+    /// Pan/balance (-1.0 left to 1.0 right).
+    #[serde(default)]
+    pub pan: f32,
+    /// Surround width (0.0 front speakers only to 1.0 all speakers).
+    #[serde(default = "default_width")]
+    pub width: f32,
+    /// Speaker layout of the channel's sink (output channels, daemon mode).
+    #[serde(default)]
+    pub layout: ChannelLayout,
     /// Whether EQ is enabled for this channel.
     pub eq_enabled: bool,
     /// Name of the EQ preset applied.
     pub eq_preset: String,
+    /// Bands of the channel's EQ, as reported by the daemon.
+    #[serde(skip)]
+    pub eq_bands: Vec<EqBandInfo>,
+    /// Whether the EQ bands were edited after choosing the preset.
+    #[serde(skip)]
+    pub eq_custom: bool,
     /// App identifiers assigned to this channel.
     pub assigned_apps: Vec<String>,
     /// Whether this channel owns its sink (managed) or just controls it (adopted).
@@ -284,6 +303,18 @@ pub struct MixerChannel {
     /// VAD threshold for noise suppression (0-100%). Higher = more aggressive noise gating.
     #[serde(default = "default_vad_threshold")]
     pub vad_threshold: f32,
+    /// Noise suppression model, VAD grace periods and attenuation limit.
+    #[serde(default)]
+    pub noise_suppression: NoiseSuppressionInfo,
+    /// Whether noise suppression currently hears a voice (daemon mode only).
+    #[serde(skip)]
+    pub talking: bool,
+    /// Push-to-talk or push-to-mute mode, release tail and fade.
+    #[serde(default)]
+    pub talk: TalkInfo,
+    /// Whether the mic is live: unmuted and, with a talk mode, keyed open (daemon mode only).
+    #[serde(skip)]
+    pub live: bool,
     /// Hardware microphone gain in dB (-12.0 to +12.0). Controls the physical input device level.
     #[serde(default)]
     pub input_gain_db: f32,
@@ -291,6 +322,22 @@ pub struct MixerChannel {
     /// Routes this channel's audio to the monitor output device.
     #[serde(skip)]
     pub solo: bool,
+    /// Processing latency reported by the daemon, in samples (plugins plus
+    /// alignment delay).
+    #[serde(skip)]
+    pub latency_samples: u32,
+    /// Part of `latency_samples` added to line up with other channels.
+    #[serde(skip)]
+    pub latency_compensation_samples: u32,
+    /// Latest EBU R128 loudness readings from the daemon.
+    #[serde(skip)]
+    pub loudness: Option<LoudnessData>,
+    /// Automatic loudness normalization (daemon mode only).
+    #[serde(default)]
+    pub auto_level: AutoLevelInfo,
+    /// Gain currently applied by auto level, in dB.
+    #[serde(skip)]
+    pub auto_level_gain_db: f32,
 }
 
 fn default_vad_threshold() -> f32 {
@@ -301,6 +348,10 @@ fn default_is_managed() -> bool {
     true
 }
 
+fn default_width() -> f32 {
+    1.0
+}
+
 fn default_sidetone_db() -> f32 {
     -20.0
 }
@@ -318,8 +369,13 @@ impl MixerChannel {
             name,
             volume_db: 0.0,
             muted: false,
+            pan: 0.0,
+            width: 1.0,
+            layout: ChannelLayout::Stereo,
             eq_enabled: false,
             eq_preset: "Flat".to_string(),
+            eq_bands: Vec::new(),
+            eq_custom: false,
             assigned_apps: Vec::new(),
             is_managed: true,
             pw_sink_id: None,
@@ -340,8 +396,17 @@ impl MixerChannel {
             sidetone_volume_db: -20.0,
             noise_suppression_enabled: false,
             vad_threshold: 95.0,
+            noise_suppression: NoiseSuppressionInfo::default(),
+            talking: false,
+            talk: TalkInfo::default(),
+            live: true,
             input_gain_db: 0.0,
             solo: false,
+            latency_samples: 0,
+            latency_compensation_samples: 0,
+            loudness: None,
+            auto_level: AutoLevelInfo::default(),
+            auto_level_gain_db: 0.0,
         }
     }
 
@@ -358,8 +423,13 @@ impl MixerChannel {
             name,
             volume_db: 0.0,
             muted: false,
+            pan: 0.0,
+            width: 1.0,
+            layout: ChannelLayout::Stereo,
             eq_enabled: false,
             eq_preset: "Flat".to_string(),
+            eq_bands: Vec::new(),
+            eq_custom: false,
             assigned_apps: Vec::new(),
             is_managed: true,
             pw_sink_id: None,
@@ -379,8 +449,17 @@ impl MixerChannel {
             sidetone_volume_db: -20.0,
             noise_suppression_enabled: false,
             vad_threshold: 95.0,
+            noise_suppression: NoiseSuppressionInfo::default(),
+            talking: false,
+            talk: TalkInfo::default(),
+            live: true,
             input_gain_db: 0.0,
             solo: false,
+            latency_samples: 0,
+            latency_compensation_samples: 0,
+            loudness: None,
+            auto_level: AutoLevelInfo::default(),
+            auto_level_gain_db: 0.0,
         }
     }
 
@@ -389,6 +468,11 @@ impl MixerChannel {
         self.kind == ChannelKind::Input
     }
 
+    /// Pan and width as applied to the channel's node.
+    pub fn balance(&self) -> Balance {
+        Balance::new(self.pan, self.width)
+    }
+
     /// Convert volume in dB to linear scale (0.0 to ~4.0 for +12dB).
     pub fn volume_linear(&self) -> f32 {
         if self.muted {
@@ -745,6 +829,43 @@ pub struct EditingRule {
     pub target_channel: String,
     /// Priority value.
     pub priority: u32,
+    /// Property conditions being edited.
+    pub conditions: Vec<EditingCondition>,
+    /// How conditions are combined.
+    pub combine: crate::config::ConditionCombine,
+}
+
+/// State for editing one property condition of a routing rule.
+#[derive(Debug, Clone)]
+pub struct EditingCondition {
+    /// Node property key.
+    pub property: String,
+    /// Match type name ("contains", "exact", "regex", "glob").
+    pub match_type_name: String,
+    /// Pattern string.
+    pub pattern: String,
+}
+
+impl Default for EditingCondition {
+    fn default() -> Self {
+        Self {
+            property: "media.role".to_string(),
+            match_type_name: "exact".to_string(),
+            pattern: String::new(),
+        }
+    }
+}
+
+/// Build a `MatchType` from its UI name and pattern.
+fn match_type_from_name(name: &str, pattern: &str) -> crate::config::MatchType {
+    use crate::config::MatchType;
+
+    match name {
+        "exact" => MatchType::Exact(pattern.to_string()),
+        "regex" => MatchType::Regex(pattern.to_string()),
+        "glob" => MatchType::Glob(pattern.to_string()),
+        _ => MatchType::Contains(pattern.to_string()),
+    }
 }
 
 impl Default for EditingRule {
@@ -757,6 +878,8 @@ impl Default for EditingRule {
             pattern: String::new(),
             target_channel: String::new(),
             priority: 100,
+            conditions: Vec::new(),
+            combine: crate::config::ConditionCombine::All,
         }
     }
 }
@@ -772,28 +895,45 @@ impl EditingRule {
             pattern: rule.match_type.pattern().to_string(),
             target_channel: rule.target_channel.clone(),
             priority: rule.priority,
+            conditions: rule
+                .conditions
+                .iter()
+                .map(|c| EditingCondition {
+                    property: c.property.clone(),
+                    match_type_name: c.match_type.type_name().to_string(),
+                    pattern: c.match_type.pattern().to_string(),
+                })
+                .collect(),
+            combine: rule.combine,
         }
     }
 
     /// Convert to a RoutingRule.
+    ///
+    /// Conditions with an empty property key are dropped.
     pub fn to_rule(&self) -> crate::config::RoutingRule {
-        use crate::config::MatchType;
-
-        let match_type = match self.match_type_name.as_str() {
-            "exact" => MatchType::Exact(self.pattern.clone()),
-            "regex" => MatchType::Regex(self.pattern.clone()),
-            "glob" => MatchType::Glob(self.pattern.clone()),
-            _ => MatchType::Contains(self.pattern.clone()),
-        };
+        let conditions = self
+            .conditions
+            .iter()
+            .filter(|c| !c.property.trim().is_empty())
+            .map(|c| {
+                crate::config::PropertyCondition::new(
+                    c.property.trim(),
+                    match_type_from_name(&c.match_type_name, &c.pattern),
+                )
+            })
+            .collect();
 
         crate::config::RoutingRule {
             id: self.id.unwrap_or_else(Uuid::new_v4),
             name: self.name.clone(),
             match_target: self.match_target,
-            match_type,
+            match_type: match_type_from_name(&self.match_type_name, &self.pattern),
             target_channel: self.target_channel.clone(),
             enabled: true,
             priority: self.priority,
+            conditions,
+            combine: self.combine,
         }
     }
 }
@@ -951,6 +1091,15 @@ impl PwGraphState {
     }
 }
 
+/// Spectrum analyzer running on a channel (daemon mode only).
+#[derive(Debug, Clone)]
+pub struct ChannelSpectrum {
+    pub channel_id: Uuid,
+    pub tap: SpectrumTap,
+    /// Latest analysis, once the daemon has sent one.
+    pub data: Option<SpectrumData>,
+}
+
 /// Main application state.
 #[derive(Debug)]
 pub struct AppState {
@@ -976,6 +1125,10 @@ pub struct AppState {
     pub pw_connected: bool,
     /// Currently open EQ panel (channel ID).
     pub eq_panel_channel: Option<Uuid>,
+    /// Band selected in the EQ editor.
+    pub eq_selected_band: Option<usize>,
+    /// EQ preset names offered by the editor.
+    pub eq_presets: Vec<String>,
     /// Settings modal open.
     pub settings_open: bool,
     /// Last error message.
@@ -994,6 +1147,10 @@ pub struct AppState {
     pub startup_complete: bool,
     /// Master VU meter display state.
     pub master_meter_display: MeterDisplayState,
+    /// Latest EBU R128 loudness readings of the master output (daemon mode only).
+    pub master_loudness: Option<LoudnessData>,
+    /// Spectrum analyzer of the selected channel (`None` while off).
+    pub spectrum: Option<ChannelSpectrum>,
     /// Auto-routing rules configuration.
     pub routing_rules: RoutingRulesConfig,
     /// Node IDs that have been auto-routed in this session (to avoid re-routing).
@@ -1031,6 +1188,15 @@ pub struct AppState {
     /// Selected monitor output device name for PFL solo routing.
     /// Audio from soloed channels is sent to this device (e.g. headphones).
     pub monitor_device: Option<String>,
+    /// Monitor bus level in dB (daemon-owned, round-tripped).
+    pub monitor_volume_db: f32,
+    /// Monitor bus mute (daemon-owned, round-tripped).
+    pub monitor_muted: bool,
+    /// How soloed channels are heard (daemon mode only).
+    pub solo_mode: SoloMode,
+
+    /// Ducking relationships from the mixer config (daemon-owned, round-tripped).
+    pub saved_ducking: Vec<toml::Value>,
 
     // ==================== Daemon Settings ====================
     /// Whether the daemon systemd service is enabled (autostart on login).
@@ -1092,6 +1258,8 @@ impl AppState {
             pw_graph: PwGraphState::new(),
             pw_connected: false,
             eq_panel_channel: None,
+            eq_selected_band: None,
+            eq_presets: Vec::new(),
             settings_open: false,
             last_error: None,
             dragging_app: None,
@@ -1101,6 +1269,8 @@ impl AppState {
             pending_reroute: None,
             startup_complete: false,
             master_meter_display: MeterDisplayState::default(),
+            master_loudness: None,
+            spectrum: None,
             routing_rules: RoutingRulesConfig::default(),
             auto_routed_apps: HashSet::new(),
             routing_rules_panel_open: false,
@@ -1116,8 +1286,12 @@ impl AppState {
             selected_channel: None,
             left_sidebar_collapsed: false,
             bottom_panel_expanded: false,
-            bottom_panel_height: 200.0,
+            bottom_panel_height: 340.0,
             monitor_device: None,
+            monitor_volume_db: 0.0,
+            monitor_muted: false,
+            solo_mode: SoloMode::default(),
+            saved_ducking: Vec::new(),
             daemon_autostart: false,
             daemon_action_pending: false,
             downloader_open: false,
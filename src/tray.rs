@@ -19,6 +19,8 @@ pub enum TrayMessage {
     ShowWindow,
     /// Toggle mute all channels.
     ToggleMuteAll,
+    /// Hold or let go of the talk key of every push-to-talk channel.
+    ToggleTalk,
     /// Quit the application.
     Quit,
 }
@@ -29,6 +31,10 @@ struct SootMixTray {
     tx: mpsc::Sender<TrayMessage>,
     /// Whether all channels are currently muted.
     muted: bool,
+    /// Whether the mic is live, if any input channel has a talk mode.
+    mic_live: Option<bool>,
+    /// Whether the talk item is holding the talk key down.
+    talk_held: bool,
 }
 
 impl Tray for SootMixTray {
@@ -48,14 +54,18 @@ impl Tray for SootMixTray {
     fn tool_tip(&self) -> ksni::ToolTip {
         ksni::ToolTip {
             title: "SootMix".to_string(),
-            description: "Audio routing and mixing".to_string(),
+            description: match self.mic_live {
+                Some(true) => "Mic live".to_string(),
+                Some(false) => "Mic muted".to_string(),
+                None => "Audio routing and mixing".to_string(),
+            },
             icon_name: String::new(),
             icon_pixmap: vec![],
         }
     }
 
     fn menu(&self) -> Vec<MenuItem<Self>> {
-        vec![
+        let mut items = vec![
             MenuItem::Standard(StandardItem {
                 label: "Show SootMix".to_string(),
                 activate: Box::new(|tray: &mut Self| {
@@ -77,16 +87,31 @@ impl Tray for SootMixTray {
                 }),
                 ..Default::default()
             }),
-            MenuItem::Separator,
-            MenuItem::Standard(StandardItem {
-                label: "Quit".to_string(),
+        ];
+        if self.mic_live.is_some() {
+            items.push(MenuItem::Standard(StandardItem {
+                label: if self.talk_held {
+                    "Release Talk Key".to_string()
+                } else {
+                    "Hold Talk Key".to_string()
+                },
                 activate: Box::new(|tray: &mut Self| {
-                    debug!("Tray: Quit clicked");
-                    let _ = tray.tx.send(TrayMessage::Quit);
+                    debug!("Tray: Talk toggle clicked");
+                    let _ = tray.tx.send(TrayMessage::ToggleTalk);
                 }),
                 ..Default::default()
+            }));
+        }
+        items.push(MenuItem::Separator);
+        items.push(MenuItem::Standard(StandardItem {
+            label: "Quit".to_string(),
+            activate: Box::new(|tray: &mut Self| {
+                debug!("Tray: Quit clicked");
+                let _ = tray.tx.send(TrayMessage::Quit);
             }),
-        ]
+            ..Default::default()
+        }));
+        items
     }
 
     fn activate(&mut self, _x: i32, _y: i32) {
@@ -115,6 +140,26 @@ impl TrayHandle {
         });
     }
 
+    /// Update whether the mic is live (`None` when no channel has a talk mode).
+    pub fn set_mic_live(&self, mic_live: Option<bool>) {
+        let handle = self.handle.clone();
+        tokio::spawn(async move {
+            handle.update(move |tray| {
+                tray.mic_live = mic_live;
+            }).await;
+        });
+    }
+
+    /// Update whether the talk item shows the talk key as held.
+    pub fn set_talk_held(&self, talk_held: bool) {
+        let handle = self.handle.clone();
+        tokio::spawn(async move {
+            handle.update(move |tray| {
+                tray.talk_held = talk_held;
+            }).await;
+        });
+    }
+
     /// Shut down the tray icon, removing it from the system tray.
     pub fn shutdown(&self) {
         info!("Shutting down system tray");
@@ -138,7 +183,12 @@ impl Drop for TrayHandle {
 pub fn start_tray() -> Option<(mpsc::Receiver<TrayMessage>, TrayHandle)> {
     let (tx, rx) = mpsc::channel();
 
-    let tray = SootMixTray { tx, muted: false };
+    let tray = SootMixTray {
+        tx,
+        muted: false,
+        mic_live: None,
+        talk_held: false,
+    };
 
     // Shutdown flag shared between the handle and the background thread
     let shutdown_flag = Arc::new(AtomicBool::new(false));
@@ -14,8 +14,8 @@
 
 use crate::audio::types::{InputDevice, OutputDevice};
 use crate::message::Message;
-use crate::state::{AppInfo, ChannelKind, MeterDisplayState, MixerChannel};
-use crate::ui::meter::vu_meter;
+use crate::state::{AppInfo, ChannelKind, LoudnessData, MeterDisplayState, MixerChannel};
+use crate::ui::meter::{loudness_readout, vu_meter};
 use crate::ui::plugin_chain::fx_button;
 use crate::ui::theme::{self, *};
 use iced::widget::{
@@ -308,6 +308,42 @@ pub fn channel_strip<'a>(
     let plugin_count = channel.plugin_chain.len();
     let fx_btn = fx_button(id, plugin_count);
 
+    // Processing latency (plugins plus alignment), hidden when there is none
+    let latency_label: Element<'a, Message> = if channel.latency_samples > 0 {
+        let label = text(format!("{} smp", channel.latency_samples))
+            .size(TEXT_CAPTION)
+            .color(TEXT_DIM);
+        let detail = if channel.latency_compensation_samples > 0 {
+            format!(
+                "Latency: {} samples, {} of them to line up with other channels",
+                channel.latency_samples, channel.latency_compensation_samples
+            )
+        } else {
+            format!(
+                "Latency reported by plugins: {} samples",
+                channel.latency_samples
+            )
+        };
+        tooltip(
+            label,
+            container(text(detail).size(TEXT_CAPTION).color(TEXT))
+                .padding([SPACING_XS, SPACING_SM])
+                .style(|_theme: &Theme| container::Style {
+                    background: Some(Background::Color(SOOTMIX_DARK.surface_overlay)),
+                    border: Border::default()
+                        .rounded(RADIUS_SM)
+                        .color(SOOTMIX_DARK.border_default)
+                        .width(1.0),
+                    ..container::Style::default()
+                }),
+            tooltip::Position::Top,
+        )
+        .gap(4)
+        .into()
+    } else {
+        Space::new().width(0).height(0).into()
+    };
+
     // === DEVICE PICKER (Output for output channels, Input for input channels) ===
     let is_input = channel.kind == ChannelKind::Input;
     let max_display_chars = 12;
@@ -601,6 +637,7 @@ pub fn channel_strip<'a>(
         container(arrows_row).center_x(Fill),
         // Controls: EQ + FX
         fx_btn,
+        container(latency_label).center_x(Fill),
         Space::new().height(SPACING),
         // Fader section
         container(slider_meter_row).center_x(Fill),
@@ -661,6 +698,7 @@ pub fn master_strip<'a>(
     selected_output: Option<&'a str>,
     meter_display: &'a MeterDisplayState,
     recording_enabled: bool,
+    loudness: Option<&'a LoudnessData>,
 ) -> Element<'a, Message> {
     // === TITLE ===
     let title = container(
@@ -750,6 +788,14 @@ pub fn master_strip<'a>(
         })
         .on_press(Message::MasterMuteToggled);
 
+    // === LOUDNESS (daemon mode only) ===
+    let loudness_row: Element<'a, Message> = match loudness {
+        Some(loudness) => container(loudness_readout(loudness, None))
+            .center_x(Fill)
+            .into(),
+        None => Space::new().width(0).height(0).into(),
+    };
+
     // === RECORDING TOGGLE ===
     let rec_label = if recording_enabled { "REC" } else { "rec" };
     let recording_button = button(text(rec_label).size(TEXT_SMALL))
@@ -861,6 +907,7 @@ pub fn master_strip<'a>(
         container(slider_meter_row).center_x(Fill),
         Space::new().height(SPACING_SM),
         container(volume_text).center_x(Fill),
+        loudness_row,
         Space::new().height(SPACING_SM),
         container(row![mute_button, Space::new().width(SPACING_SM), recording_button].align_y(Alignment::Center))
             .center_x(Fill),
@@ -0,0 +1,531 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Parametric EQ editor.
+//!
+//! Draws a channel's EQ curve on a log-frequency axis with a handle per
+//! band. Dragging a handle moves the band's frequency (and gain, for bell
+//! and shelf bands); the controls below the graph edit the selected band.
+//! Edits update the channel locally and reach the daemon on release.
+
+use crate::message::Message;
+use crate::state::{EqBandInfo, EqBandType, MixerChannel};
+use crate::ui::theme::*;
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke};
+use iced::widget::{button, column, pick_list, row, slider, text, Space};
+use iced::{
+    mouse, Alignment, Background, Border, Color, Element, Fill, Length, Point, Rectangle, Renderer,
+    Size, Theme,
+};
+use sootmix_ipc::{
+    eq_response_db, EQ_MAX_BANDS, EQ_MAX_GAIN_DB, EQ_MAX_HZ, EQ_MAX_Q, EQ_MIN_HZ, EQ_MIN_Q,
+    EQ_SLOPES,
+};
+use uuid::Uuid;
+
+/// Height of the EQ graph in pixels.
+pub const EQ_GRAPH_HEIGHT: f32 = 100.0;
+
+/// Sample rate the curve is drawn at.
+const CURVE_RATE: f64 = 48_000.0;
+
+/// Points computed along the curve.
+const CURVE_POINTS: usize = 160;
+
+/// Radius of a band handle.
+const HANDLE_RADIUS: f32 = 5.0;
+
+/// How close the cursor must be to a handle to grab it.
+const GRAB_RADIUS: f32 = 10.0;
+
+/// Frequencies marked on the grid, with their labels.
+const GRID_FREQUENCIES: [(f64, &str); 3] = [(100.0, "100"), (1000.0, "1k"), (10_000.0, "10k")];
+
+/// Gains marked on the grid.
+const GRID_GAINS_DB: [f64; 4] = [-12.0, -6.0, 6.0, 12.0];
+
+// ============================================================================
+// GRAPH
+// ============================================================================
+
+/// Canvas program drawing the curve and band handles of one channel.
+#[derive(Debug, Clone)]
+struct EqGraph<'a> {
+    channel_id: Uuid,
+    bands: &'a [EqBandInfo],
+    selected: Option<usize>,
+}
+
+impl EqGraph<'_> {
+    /// Position of a band's handle: at its gain for bell and shelf bands,
+    /// on the curve for the others.
+    fn handle_point(&self, band: &EqBandInfo, size: Size) -> Point {
+        let gain = if band.band_type.has_gain() {
+            band.gain_db
+        } else {
+            eq_response_db(self.bands, band.freq_hz, CURVE_RATE)
+        };
+        Point::new(
+            frequency_x(band.freq_hz, size.width),
+            gain_y(gain, size.height),
+        )
+    }
+
+    /// Band whose handle is under `position`, if any.
+    fn band_at(&self, position: Point, size: Size) -> Option<usize> {
+        self.bands
+            .iter()
+            .enumerate()
+            .map(|(index, band)| (index, self.handle_point(band, size).distance(position)))
+            .filter(|(_, distance)| *distance <= GRAB_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+}
+
+impl canvas::Program<Message> for EqGraph<'_> {
+    /// Band being dragged.
+    type State = Option<usize>;
+
+    fn update(
+        &self,
+        dragging: &mut Self::State,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        match event {
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let position = cursor.position_in(bounds)?;
+                *dragging = self.band_at(position, bounds.size());
+                Some(canvas::Action::publish(Message::EqBandSelected(*dragging)).and_capture())
+            }
+            canvas::Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let index = (*dragging)?;
+                let position = cursor.position_from(bounds.position())?;
+                let size = bounds.size();
+                let mut band = *self.bands.get(index)?;
+                band.freq_hz = x_frequency(position.x, size.width);
+                if band.band_type.has_gain() {
+                    band.gain_db = y_gain(position.y, size.height);
+                }
+                let message = Message::EqBandChanged(self.channel_id, index, band.clamped());
+                Some(canvas::Action::publish(message).and_capture())
+            }
+            canvas::Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                dragging.take()?;
+                Some(canvas::Action::publish(Message::EqBandsReleased(
+                    self.channel_id,
+                )))
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        _dragging: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let size = bounds.size();
+
+        frame.fill(&Path::rectangle(Point::ORIGIN, size), METER_BACKGROUND);
+        draw_grid(&mut frame, size);
+
+        let curve: Vec<Point> = (0..CURVE_POINTS)
+            .map(|i| {
+                let x = size.width * i as f32 / (CURVE_POINTS - 1) as f32;
+                let gain = eq_response_db(self.bands, x_frequency(x, size.width), CURVE_RATE);
+                Point::new(x, gain_y(gain, size.height))
+            })
+            .collect();
+        let middle = size.height * 0.5;
+        let fill = Path::new(|b| {
+            b.move_to(Point::new(0.0, middle));
+            for point in &curve {
+                b.line_to(*point);
+            }
+            b.line_to(Point::new(size.width, middle));
+            b.close();
+        });
+        frame.fill(&fill, ACCENT.scale_alpha(0.15));
+        let line = Path::new(|b| {
+            for (i, point) in curve.iter().enumerate() {
+                if i == 0 {
+                    b.move_to(*point);
+                } else {
+                    b.line_to(*point);
+                }
+            }
+        });
+        frame.stroke(&line, Stroke::default().with_color(ACCENT).with_width(1.5));
+
+        for (index, band) in self.bands.iter().enumerate() {
+            let handle = Path::circle(self.handle_point(band, size), HANDLE_RADIUS);
+            if self.selected == Some(index) {
+                frame.fill(&handle, PRIMARY);
+            } else {
+                frame.fill(&handle, METER_BACKGROUND);
+            }
+            frame.stroke(&handle, Stroke::default().with_color(TEXT).with_width(1.0));
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        dragging: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        if dragging.is_some() {
+            return mouse::Interaction::Grabbing;
+        }
+        match cursor.position_in(bounds) {
+            Some(position) if self.band_at(position, bounds.size()).is_some() => {
+                mouse::Interaction::Grab
+            }
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+/// Position of `freq` along the log-frequency axis, from 0 to 1.
+fn frequency_position(freq: f64) -> f64 {
+    (freq / EQ_MIN_HZ).ln() / (EQ_MAX_HZ / EQ_MIN_HZ).ln()
+}
+
+/// Frequency at `position` along the log-frequency axis.
+fn position_frequency(position: f64) -> f64 {
+    EQ_MIN_HZ * (EQ_MAX_HZ / EQ_MIN_HZ).powf(position.clamp(0.0, 1.0))
+}
+
+fn frequency_x(freq: f64, width: f32) -> f32 {
+    width * frequency_position(freq) as f32
+}
+
+fn x_frequency(x: f32, width: f32) -> f64 {
+    position_frequency(f64::from(x / width.max(1.0)))
+}
+
+fn gain_y(gain_db: f64, height: f32) -> f32 {
+    let t = (gain_db / EQ_MAX_GAIN_DB).clamp(-1.0, 1.0) as f32;
+    height * 0.5 * (1.0 - t)
+}
+
+fn y_gain(y: f32, height: f32) -> f64 {
+    let t = 1.0 - 2.0 * f64::from(y / height.max(1.0));
+    EQ_MAX_GAIN_DB * t
+}
+
+fn draw_grid(frame: &mut Frame, size: Size) {
+    let grid = Stroke::default()
+        .with_color(SOOTMIX_DARK.border_subtle)
+        .with_width(1.0);
+
+    for (freq, label) in GRID_FREQUENCIES {
+        let x = frequency_x(freq, size.width);
+        frame.stroke(
+            &Path::line(Point::new(x, 0.0), Point::new(x, size.height)),
+            grid,
+        );
+        frame.fill_text(canvas::Text {
+            content: label.to_string(),
+            position: Point::new(x + 2.0, size.height - TEXT_CAPTION - 2.0),
+            color: TEXT_DIM,
+            size: TEXT_CAPTION.into(),
+            ..canvas::Text::default()
+        });
+    }
+
+    for gain in GRID_GAINS_DB {
+        let y = gain_y(gain, size.height);
+        frame.stroke(
+            &Path::line(Point::new(0.0, y), Point::new(size.width, y)),
+            grid,
+        );
+    }
+
+    let middle = size.height * 0.5;
+    frame.stroke(
+        &Path::line(Point::new(0.0, middle), Point::new(size.width, middle)),
+        grid.with_color(ACCENT.scale_alpha(0.4)),
+    );
+}
+
+// ============================================================================
+// CONTROLS
+// ============================================================================
+
+/// Band type as listed in the type picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BandTypeOption(EqBandType);
+
+impl std::fmt::Display for BandTypeOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.label())
+    }
+}
+
+const BAND_TYPE_OPTIONS: [BandTypeOption; 7] = [
+    BandTypeOption(EqBandType::Peaking),
+    BandTypeOption(EqBandType::LowShelf),
+    BandTypeOption(EqBandType::HighShelf),
+    BandTypeOption(EqBandType::HighPass),
+    BandTypeOption(EqBandType::LowPass),
+    BandTypeOption(EqBandType::Notch),
+    BandTypeOption(EqBandType::BandPass),
+];
+
+fn format_frequency(freq: f64) -> String {
+    if freq >= 1000.0 {
+        format!("{:.2}k Hz", freq / 1000.0)
+    } else {
+        format!("{:.0} Hz", freq)
+    }
+}
+
+fn picker_style(_theme: &Theme, _status: pick_list::Status) -> pick_list::Style {
+    pick_list::Style {
+        text_color: TEXT,
+        placeholder_color: TEXT_DIM,
+        handle_color: TEXT_DIM,
+        background: Background::Color(SURFACE_LIGHT),
+        border: Border::default()
+            .rounded(RADIUS_SM)
+            .color(SOOTMIX_DARK.border_default)
+            .width(1.0),
+    }
+}
+
+fn band_slider_style(_theme: &Theme, _status: slider::Status) -> slider::Style {
+    slider::Style {
+        rail: slider::Rail {
+            backgrounds: (
+                Background::Color(SLIDER_TRACK),
+                Background::Color(SLIDER_TRACK),
+            ),
+            width: 4.0,
+            border: Border::default().rounded(2.0),
+        },
+        handle: slider::Handle {
+            shape: slider::HandleShape::Rectangle {
+                width: 10,
+                border_radius: RADIUS_SM.into(),
+            },
+            background: Background::Color(TEXT),
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+        },
+    }
+}
+
+fn small_button<'a>(
+    label: impl text::IntoFragment<'a>,
+    active: bool,
+) -> button::Button<'a, Message> {
+    button(
+        text(label)
+            .size(TEXT_CAPTION)
+            .color(if active { TEXT } else { TEXT_DIM }),
+    )
+    .padding([SPACING_XS, SPACING_SM])
+    .style(move |_: &Theme, status| {
+        let is_hovered = matches!(status, button::Status::Hovered);
+        let bg = if active {
+            PRIMARY.scale_alpha(0.7)
+        } else if is_hovered {
+            SURFACE_LIGHT
+        } else {
+            SURFACE
+        };
+        button::Style {
+            background: Some(Background::Color(bg)),
+            text_color: if active { TEXT } else { TEXT_DIM },
+            border: Border::default()
+                .rounded(RADIUS_SM)
+                .color(if active {
+                    PRIMARY
+                } else {
+                    SOOTMIX_DARK.border_subtle
+                })
+                .width(1.0),
+            ..button::Style::default()
+        }
+    })
+}
+
+/// A labelled slider row for one band parameter.
+fn parameter_row<'a>(
+    label: &'a str,
+    value: String,
+    control: impl Into<Element<'a, Message>>,
+) -> Element<'a, Message> {
+    row![
+        text(label)
+            .size(TEXT_CAPTION)
+            .color(TEXT_DIM)
+            .width(Length::Fixed(32.0)),
+        control.into(),
+        text(value)
+            .size(TEXT_CAPTION)
+            .color(TEXT)
+            .width(Length::Fixed(56.0)),
+    ]
+    .spacing(SPACING_XS)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Controls for the selected band.
+fn band_controls(id: Uuid, index: usize, band: EqBandInfo) -> Element<'static, Message> {
+    let type_picker = pick_list(
+        BAND_TYPE_OPTIONS,
+        Some(BandTypeOption(band.band_type)),
+        move |option: BandTypeOption| {
+            Message::EqBandSet(
+                id,
+                index,
+                EqBandInfo {
+                    band_type: option.0,
+                    ..band
+                }
+                .clamped(),
+            )
+        },
+    )
+    .text_size(TEXT_CAPTION)
+    .padding([SPACING_XS, SPACING_SM])
+    .style(picker_style);
+
+    let remove_btn = small_button("Remove", false).on_press(Message::EqBandRemoved(id, index));
+
+    let mut controls = column![
+        row![type_picker, Space::new().width(Fill), remove_btn].align_y(Alignment::Center),
+        parameter_row(
+            "Freq",
+            format_frequency(band.freq_hz),
+            slider(
+                0.0..=1.0,
+                frequency_position(band.freq_hz),
+                move |position| {
+                    let freq_hz = position_frequency(position);
+                    Message::EqBandChanged(id, index, EqBandInfo { freq_hz, ..band })
+                }
+            )
+            .on_release(Message::EqBandsReleased(id))
+            .step(0.001)
+            .style(band_slider_style),
+        ),
+    ]
+    .spacing(SPACING_XS);
+
+    if band.band_type.has_gain() {
+        controls = controls.push(parameter_row(
+            "Gain",
+            format!("{:+.1} dB", band.gain_db),
+            slider(
+                -EQ_MAX_GAIN_DB..=EQ_MAX_GAIN_DB,
+                band.gain_db,
+                move |gain_db| Message::EqBandChanged(id, index, EqBandInfo { gain_db, ..band }),
+            )
+            .on_release(Message::EqBandsReleased(id))
+            .step(0.1)
+            .style(band_slider_style),
+        ));
+    }
+
+    // Steeper filters are fixed Butterworth cascades, so only 12 dB/oct
+    // ones take a Q
+    if !band.band_type.has_slope() || band.slope_db == EQ_SLOPES[0] {
+        controls = controls.push(parameter_row(
+            "Q",
+            format!("{:.2}", band.q),
+            slider(EQ_MIN_Q.ln()..=EQ_MAX_Q.ln(), band.q.ln(), move |log_q| {
+                Message::EqBandChanged(
+                    id,
+                    index,
+                    EqBandInfo {
+                        q: log_q.exp(),
+                        ..band
+                    },
+                )
+            })
+            .on_release(Message::EqBandsReleased(id))
+            .step(0.01)
+            .style(band_slider_style),
+        ));
+    }
+
+    if band.band_type.has_slope() {
+        let slopes = EQ_SLOPES
+            .iter()
+            .fold(row![].spacing(SPACING_XS), |slopes, &slope_db| {
+                slopes.push(
+                    small_button(slope_db.to_string(), band.slope_db == slope_db).on_press(
+                        Message::EqBandSet(id, index, EqBandInfo { slope_db, ..band }),
+                    ),
+                )
+            });
+        controls = controls.push(parameter_row("dB/oct", String::new(), slopes));
+    }
+
+    controls.into()
+}
+
+/// Create the EQ editor for a channel: preset picker, curve and the
+/// controls of the selected band.
+pub fn eq_editor<'a>(
+    channel: &'a MixerChannel,
+    selected: Option<usize>,
+    presets: &'a [String],
+) -> Element<'a, Message> {
+    let id = channel.id;
+    let selected = selected.filter(|&index| index < channel.eq_bands.len());
+
+    let preset_picker = pick_list(
+        presets,
+        (!channel.eq_custom).then(|| channel.eq_preset.clone()),
+        move |name| Message::ChannelEqPresetChanged(id, name),
+    )
+    .placeholder("Custom")
+    .text_size(TEXT_CAPTION)
+    .padding([SPACING_XS, SPACING_SM])
+    .style(picker_style);
+
+    let add_btn = small_button("+ Band", false).on_press_maybe(
+        (channel.eq_bands.len() < EQ_MAX_BANDS).then_some(Message::EqBandAdded(id)),
+    );
+
+    let graph = canvas::Canvas::new(EqGraph {
+        channel_id: id,
+        bands: &channel.eq_bands,
+        selected,
+    })
+    .width(Length::Fill)
+    .height(Length::Fixed(EQ_GRAPH_HEIGHT));
+
+    let controls: Element<'a, Message> = match selected {
+        Some(index) => band_controls(id, index, channel.eq_bands[index]),
+        None => text("Drag a handle, or click one to edit it")
+            .size(TEXT_CAPTION)
+            .color(TEXT_DIM)
+            .into(),
+    };
+
+    column![
+        row![preset_picker, Space::new().width(Fill), add_btn]
+            .spacing(SPACING_XS)
+            .align_y(Alignment::Center),
+        graph,
+        controls,
+    ]
+    .spacing(SPACING_XS)
+    .into()
+}
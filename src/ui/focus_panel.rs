@@ -13,12 +13,21 @@
 use crate::audio::types::OutputDevice;
 use crate::message::Message;
 use crate::state::MixerChannel;
+use crate::ui::meter::{format_lufs, loudness_color};
+use crate::ui::spectrum::spectrum_graph;
 use crate::ui::theme::*;
-use crate::state::ChannelKind;
+use crate::state::{
+    AutoLevelInfo, ChannelKind, ChannelLayout, ChannelSpectrum, NoiseModel, NoiseSuppressionInfo,
+    SpectrumTap, TalkInfo, TalkMode,
+};
 use iced::widget::{
-    button, column, container, row, scrollable, slider, text, Space,
+    button, column, container, mouse_area, pick_list, row, scrollable, slider, text, Space,
 };
 use iced::{Alignment, Background, Border, Color, Element, Fill, Length, Theme};
+use sootmix_ipc::{
+    NOISE_MAX_ATTENUATION_DB, NOISE_MAX_GRACE_MS, NOISE_MAX_RETRO_GRACE_MS, TALK_MAX_FADE_MS,
+    TALK_MAX_RELEASE_MS,
+};
 use uuid::Uuid;
 
 /// Width of the focus panel.
@@ -36,6 +45,8 @@ pub fn focus_panel<'a>(
     channel: &'a MixerChannel,
     available_outputs: &'a [OutputDevice],
     plugin_chain: Vec<FocusPluginInfo>,
+    daemon_connected: bool,
+    spectrum: Option<&'a ChannelSpectrum>,
 ) -> Element<'a, Message> {
     let id = channel.id;
 
@@ -48,6 +59,22 @@ pub fn focus_panel<'a>(
     // === INPUT SOURCES ===
     let inputs = input_sources_section(channel);
 
+    // === BALANCE SECTION ===
+    let balance = balance_section(channel);
+
+    // === LOUDNESS SECTION (daemon mode only) ===
+    let loudness = loudness_section(channel);
+
+    // === AUTO LEVEL SECTION ===
+    let auto_level = auto_level_section(channel);
+
+    // === SPECTRUM SECTION (daemon mode only) ===
+    let spectrum_view: Element<Message> = if daemon_connected {
+        spectrum_section(id, spectrum)
+    } else {
+        Space::new().width(0).height(0).into()
+    };
+
     // === NOISE SUPPRESSION SECTION (input channels only) ===
     let noise_section: Element<Message> = if channel.kind == ChannelKind::Input {
         noise_suppression_section(channel)
@@ -55,6 +82,13 @@ pub fn focus_panel<'a>(
         Space::new().width(0).height(0).into()
     };
 
+    // === PUSH-TO-TALK SECTION (input channels only) ===
+    let talk: Element<Message> = if channel.kind == ChannelKind::Input {
+        talk_section(channel)
+    } else {
+        Space::new().width(0).height(0).into()
+    };
+
     // === PLUGIN CHAIN SECTION ===
     let plugins = plugin_chain_section(id, plugin_chain);
 
@@ -82,8 +116,18 @@ pub fn focus_panel<'a>(
         Space::new().height(SPACING),
         inputs,
         Space::new().height(SPACING),
+        balance,
+        Space::new().height(SPACING),
+        loudness,
+        Space::new().height(SPACING),
+        auto_level,
+        Space::new().height(SPACING),
+        spectrum_view,
+        Space::new().height(SPACING),
         noise_section,
         Space::new().height(SPACING),
+        talk,
+        Space::new().height(SPACING),
         plugins,
         Space::new().height(SPACING),
         output,
@@ -341,6 +385,402 @@ fn eq_section<'a>(channel: &'a MixerChannel) -> Element<'a, Message> {
     .into()
 }
 
+/// EBU R128 loudness readings with a button to restart integration.
+fn loudness_section<'a>(channel: &'a MixerChannel) -> Element<'a, Message> {
+    let Some(loudness) = channel.loudness.as_ref() else {
+        return Space::new().width(0).height(0).into();
+    };
+    let id = channel.id;
+
+    let section_title = text("Loudness")
+        .size(TEXT_SMALL)
+        .color(TEXT_DIM);
+
+    let reset_btn = button(text("Reset").size(TEXT_CAPTION).color(TEXT_DIM))
+        .padding([SPACING_XS, SPACING_SM])
+        .style(|_: &Theme, status| {
+            let is_hovered = matches!(status, button::Status::Hovered);
+            button::Style {
+                background: Some(Background::Color(if is_hovered {
+                    SURFACE_LIGHT
+                } else {
+                    Color::TRANSPARENT
+                })),
+                text_color: TEXT_DIM,
+                border: Border::default().rounded(RADIUS_SM),
+                ..button::Style::default()
+            }
+        })
+        .on_press(Message::ResetLoudness(Some(id)));
+
+    let reading = |label: &'a str, value: String, color: Color| {
+        row![
+            text(label).size(TEXT_SMALL).color(TEXT_DIM),
+            Space::new().width(Fill),
+            text(value).size(TEXT_SMALL).color(color),
+        ]
+        .align_y(Alignment::Center)
+    };
+
+    column![
+        row![section_title, Space::new().width(Fill), reset_btn].align_y(Alignment::Center),
+        Space::new().height(SPACING_SM),
+        reading(
+            "Momentary",
+            format!("{} LUFS", format_lufs(loudness.momentary_lufs)),
+            TEXT,
+        ),
+        reading(
+            "Short-term",
+            format!("{} LUFS", format_lufs(loudness.short_term_lufs)),
+            loudness_color(loudness),
+        ),
+        reading(
+            "Integrated",
+            format!("{} LUFS", format_lufs(loudness.integrated_lufs)),
+            TEXT,
+        ),
+        reading("Range", format!("{:.1} LU", loudness.range_lu), TEXT),
+        reading(
+            "True peak",
+            format!("{:.1} dBTP", loudness.true_peak_dbtp),
+            loudness_color(loudness),
+        ),
+    ]
+    .spacing(SPACING_XS)
+    .into()
+}
+
+/// Auto level toggle, the gain it currently applies, and its settings.
+fn auto_level_section<'a>(channel: &'a MixerChannel) -> Element<'a, Message> {
+    let id = channel.id;
+    let settings = channel.auto_level;
+    let enabled = settings.enabled;
+
+    let section_title = text("Auto Level")
+        .size(TEXT_SMALL)
+        .color(TEXT_DIM);
+
+    let toggle = button(
+        text(if enabled { "ON" } else { "OFF" })
+            .size(TEXT_SMALL)
+            .color(if enabled { TEXT } else { TEXT_DIM }),
+    )
+    .padding([SPACING_XS, SPACING_SM])
+    .style(move |_: &Theme, status| {
+        let is_hovered = matches!(status, button::Status::Hovered);
+        let bg = if enabled {
+            if is_hovered {
+                PRIMARY
+            } else {
+                PRIMARY.scale_alpha(0.7)
+            }
+        } else if is_hovered {
+            SURFACE_LIGHT
+        } else {
+            SURFACE
+        };
+        button::Style {
+            background: Some(Background::Color(bg)),
+            text_color: if enabled { TEXT } else { TEXT_DIM },
+            border: Border::default()
+                .rounded(RADIUS_SM)
+                .color(if enabled { PRIMARY } else { SOOTMIX_DARK.border_subtle })
+                .width(1.0),
+            ..button::Style::default()
+        }
+    })
+    .on_press(Message::ChannelAutoLevelToggled(id));
+
+    if !enabled {
+        return column![
+            row![section_title, Space::new().width(Fill), toggle].align_y(Alignment::Center),
+            Space::new().height(SPACING_XS),
+            text("Evens out loud and quiet sources")
+                .size(TEXT_CAPTION)
+                .color(TEXT_DIM),
+        ]
+        .into();
+    }
+
+    let setting = |label: &'a str,
+                   value: String,
+                   range: std::ops::RangeInclusive<f64>,
+                   current: f64,
+                   step: f64,
+                   apply: fn(&mut AutoLevelInfo, f64)| {
+        column![
+            row![
+                text(label).size(TEXT_SMALL).color(TEXT_DIM),
+                Space::new().width(Fill),
+                text(value).size(TEXT_SMALL).color(TEXT),
+            ]
+            .align_y(Alignment::Center),
+            Space::new().height(SPACING_XS),
+            slider(range, current, move |v| {
+                let mut settings = settings;
+                apply(&mut settings, v);
+                Message::ChannelAutoLevelChanged(id, settings)
+            })
+            .on_release(Message::ChannelAutoLevelReleased(id))
+            .step(step)
+            .width(Length::Fill)
+            .style(balance_slider_style),
+        ]
+    };
+
+    column![
+        row![section_title, Space::new().width(Fill), toggle].align_y(Alignment::Center),
+        Space::new().height(SPACING_SM),
+        row![
+            text("Gain").size(TEXT_SMALL).color(TEXT_DIM),
+            Space::new().width(Fill),
+            text(format!("{:+.1} dB", channel.auto_level_gain_db))
+                .size(TEXT_SMALL)
+                .color(TEXT),
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(SPACING_SM),
+        setting(
+            "Target",
+            format!("{:.0} LUFS", settings.target_lufs),
+            -40.0..=-6.0,
+            settings.target_lufs,
+            1.0,
+            |s, v| s.target_lufs = v,
+        ),
+        Space::new().height(SPACING_SM),
+        setting(
+            "Max boost",
+            format!("{:.0} dB", settings.max_boost_db),
+            0.0..=24.0,
+            settings.max_boost_db,
+            1.0,
+            |s, v| s.max_boost_db = v,
+        ),
+        Space::new().height(SPACING_SM),
+        setting(
+            "Max cut",
+            format!("{:.0} dB", settings.max_cut_db),
+            0.0..=24.0,
+            settings.max_cut_db,
+            1.0,
+            |s, v| s.max_cut_db = v,
+        ),
+        Space::new().height(SPACING_SM),
+        setting(
+            "Speed",
+            format!("{:.1} dB/s", settings.speed_db_per_sec),
+            0.5..=6.0,
+            settings.speed_db_per_sec,
+            0.5,
+            |s, v| s.speed_db_per_sec = v,
+        ),
+    ]
+    .into()
+}
+
+/// Spectrum analyzer with its tap selection: off, before the plugin chain
+/// or after it.
+pub fn spectrum_section<'a>(
+    id: Uuid,
+    spectrum: Option<&'a ChannelSpectrum>,
+) -> Element<'a, Message> {
+    let current = spectrum.map(|s| s.tap);
+
+    let section_title = text("Spectrum")
+        .size(TEXT_SMALL)
+        .color(TEXT_DIM);
+
+    let tap_button = |label: &'a str, tap: Option<SpectrumTap>| {
+        let selected = current == tap;
+        button(
+            text(label)
+                .size(TEXT_CAPTION)
+                .color(if selected { TEXT } else { TEXT_DIM }),
+        )
+        .padding([SPACING_XS, SPACING_SM])
+        .style(move |_: &Theme, status| {
+            let is_hovered = matches!(status, button::Status::Hovered);
+            let bg = if selected {
+                PRIMARY.scale_alpha(0.7)
+            } else if is_hovered {
+                SURFACE_LIGHT
+            } else {
+                SURFACE
+            };
+            button::Style {
+                background: Some(Background::Color(bg)),
+                text_color: if selected { TEXT } else { TEXT_DIM },
+                border: Border::default()
+                    .rounded(RADIUS_SM)
+                    .color(if selected { PRIMARY } else { SOOTMIX_DARK.border_subtle })
+                    .width(1.0),
+                ..button::Style::default()
+            }
+        })
+        .on_press(Message::SpectrumTapSelected(id, tap))
+    };
+
+    let header = row![
+        section_title,
+        Space::new().width(Fill),
+        row![
+            tap_button("Off", None),
+            tap_button("Pre", Some(SpectrumTap::Pre)),
+            tap_button("Post", Some(SpectrumTap::Post)),
+        ]
+        .spacing(SPACING_XS),
+    ]
+    .align_y(Alignment::Center);
+
+    let Some(spectrum) = spectrum else {
+        return column![
+            header,
+            Space::new().height(SPACING_XS),
+            text("Frequency content before or after the plugins")
+                .size(TEXT_CAPTION)
+                .color(TEXT_DIM),
+        ]
+        .into();
+    };
+
+    column![
+        header,
+        Space::new().height(SPACING_SM),
+        spectrum_graph(spectrum.data.as_ref()),
+    ]
+    .into()
+}
+
+/// Pan and width sliders, plus the speaker layout of output channels.
+fn balance_section<'a>(channel: &'a MixerChannel) -> Element<'a, Message> {
+    let id = channel.id;
+    let pan = channel.pan;
+    let width = channel.width;
+
+    let section_title = text("Balance")
+        .size(TEXT_SMALL)
+        .color(TEXT_DIM);
+
+    let pan_value = if pan.abs() < 0.005 {
+        "C".to_string()
+    } else if pan < 0.0 {
+        format!("L{}", (pan.abs() * 100.0).round() as i32)
+    } else {
+        format!("R{}", (pan * 100.0).round() as i32)
+    };
+
+    let pan_slider = slider(-1.0..=1.0, pan, move |v| Message::ChannelPanChanged(id, v))
+        .on_release(Message::ChannelBalanceReleased(id))
+        .step(0.01)
+        .width(Length::Fill)
+        .style(balance_slider_style);
+
+    // Width fades surround speakers, so stereo and mono have nothing to offer
+    let width_rows: Element<'a, Message> = if channel.layout.has_surround() {
+        let width_slider = slider(0.0..=1.0, width, move |v| {
+            Message::ChannelWidthChanged(id, v)
+        })
+        .on_release(Message::ChannelBalanceReleased(id))
+        .step(0.01)
+        .width(Length::Fill)
+        .style(balance_slider_style);
+
+        column![
+            Space::new().height(SPACING_SM),
+            row![
+                text("Width").size(TEXT_SMALL).color(TEXT_DIM),
+                Space::new().width(Fill),
+                text(format!("{}%", (width * 100.0).round() as i32))
+                    .size(TEXT_SMALL)
+                    .color(TEXT),
+            ]
+            .align_y(Alignment::Center),
+            Space::new().height(SPACING_XS),
+            width_slider,
+            Space::new().height(SPACING_XS),
+            text("Width fades the side and rear speakers")
+                .size(TEXT_CAPTION)
+                .color(TEXT_DIM),
+        ]
+        .into()
+    } else {
+        Space::new().width(0).height(0).into()
+    };
+
+    let layout_row: Element<'a, Message> = if channel.kind == ChannelKind::Output {
+        let layout_picker = pick_list(
+            ChannelLayout::ALL,
+            Some(channel.layout),
+            move |layout| Message::ChannelLayoutChanged(id, layout),
+        )
+        .text_size(TEXT_SMALL)
+        .padding([SPACING_XS, SPACING_SM])
+        .style(|_theme: &Theme, _status| pick_list::Style {
+            text_color: TEXT,
+            placeholder_color: TEXT_DIM,
+            handle_color: TEXT_DIM,
+            background: Background::Color(SURFACE_LIGHT),
+            border: Border::default()
+                .rounded(RADIUS_SM)
+                .color(SOOTMIX_DARK.border_default)
+                .width(1.0),
+        });
+
+        column![
+            Space::new().height(SPACING_SM),
+            row![
+                text("Speakers").size(TEXT_SMALL).color(TEXT_DIM),
+                Space::new().width(Fill),
+                layout_picker,
+            ]
+            .align_y(Alignment::Center),
+        ]
+        .into()
+    } else {
+        Space::new().width(0).height(0).into()
+    };
+
+    column![
+        section_title,
+        Space::new().height(SPACING_SM),
+        row![
+            text("Pan").size(TEXT_SMALL).color(TEXT_DIM),
+            Space::new().width(Fill),
+            text(pan_value).size(TEXT_SMALL).color(TEXT),
+        ]
+        .align_y(Alignment::Center),
+        Space::new().height(SPACING_XS),
+        pan_slider,
+        width_rows,
+        layout_row,
+    ]
+    .into()
+}
+
+fn balance_slider_style(_theme: &Theme, _status: slider::Status) -> slider::Style {
+    slider::Style {
+        rail: slider::Rail {
+            backgrounds: (
+                Background::Color(SLIDER_TRACK),
+                Background::Color(SLIDER_TRACK),
+            ),
+            width: 4.0,
+            border: Border::default().rounded(2.0),
+        },
+        handle: slider::Handle {
+            shape: slider::HandleShape::Rectangle {
+                width: 12,
+                border_radius: RADIUS_SM.into(),
+            },
+            background: Background::Color(TEXT),
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+        },
+    }
+}
+
 /// Noise suppression section for input channels.
 fn noise_suppression_section<'a>(channel: &'a MixerChannel) -> Element<'a, Message> {
     let id = channel.id;
@@ -423,6 +863,54 @@ fn noise_suppression_section<'a>(channel: &'a MixerChannel) -> Element<'a, Messa
             .size(TEXT_CAPTION)
             .color(TEXT_DIM);
 
+        let settings = channel.noise_suppression;
+        let model_picker = pick_list(NoiseModel::ALL, Some(settings.model), move |model| {
+            Message::ChannelNoiseModelSelected(id, model)
+        })
+        .text_size(TEXT_SMALL)
+        .padding([SPACING_XS, SPACING_SM])
+        .style(|_theme: &Theme, _status| pick_list::Style {
+            text_color: TEXT,
+            placeholder_color: TEXT_DIM,
+            handle_color: TEXT_DIM,
+            background: Background::Color(SURFACE_LIGHT),
+            border: Border::default()
+                .rounded(RADIUS_SM)
+                .color(SOOTMIX_DARK.border_default)
+                .width(1.0),
+        });
+
+        let setting = |label: &'a str,
+                       value: String,
+                       range: std::ops::RangeInclusive<f64>,
+                       current: f64,
+                       apply: fn(&mut NoiseSuppressionInfo, f64)| {
+            column![
+                row![
+                    text(label).size(TEXT_SMALL).color(TEXT_DIM),
+                    Space::new().width(Fill),
+                    text(value).size(TEXT_SMALL).color(TEXT),
+                ]
+                .align_y(Alignment::Center),
+                Space::new().height(SPACING_XS),
+                slider(range, current, move |v| {
+                    let mut settings = settings;
+                    apply(&mut settings, v);
+                    Message::ChannelNoiseSuppressionChanged(id, settings)
+                })
+                .on_release(Message::ChannelNoiseSuppressionReleased(id))
+                .step(10.0)
+                .width(Length::Fill)
+                .style(balance_slider_style),
+            ]
+        };
+
+        let limit = if settings.attenuation_limit_db >= NOISE_MAX_ATTENUATION_DB {
+            "Off".to_string()
+        } else {
+            format!("{:.0} dB", settings.attenuation_limit_db)
+        };
+
         column![
             Space::new().height(SPACING_SM),
             row![vad_label, Space::new().width(Fill), vad_value,].align_y(Alignment::Center),
@@ -430,6 +918,41 @@ fn noise_suppression_section<'a>(channel: &'a MixerChannel) -> Element<'a, Messa
             vad_slider,
             Space::new().height(SPACING_XS),
             help_text,
+            Space::new().height(SPACING_SM),
+            row![
+                text("Model").size(TEXT_SMALL).color(TEXT_DIM),
+                Space::new().width(Fill),
+                model_picker,
+            ]
+            .align_y(Alignment::Center),
+            Space::new().height(SPACING_SM),
+            setting(
+                "Grace",
+                format!("{:.0} ms", settings.grace_ms),
+                0.0..=NOISE_MAX_GRACE_MS,
+                settings.grace_ms,
+                |s, v| s.grace_ms = v,
+            ),
+            Space::new().height(SPACING_SM),
+            setting(
+                "Retroactive grace",
+                format!("{:.0} ms", settings.retro_grace_ms),
+                0.0..=NOISE_MAX_RETRO_GRACE_MS,
+                settings.retro_grace_ms,
+                |s, v| s.retro_grace_ms = v,
+            ),
+            Space::new().height(SPACING_SM),
+            setting(
+                "Attenuation limit",
+                limit,
+                0.0..=NOISE_MAX_ATTENUATION_DB,
+                settings.attenuation_limit_db,
+                |s, v| s.attenuation_limit_db = v,
+            ),
+            Space::new().height(SPACING_XS),
+            text("Retroactive grace delays the mic by as much")
+                .size(TEXT_CAPTION)
+                .color(TEXT_DIM),
         ]
         .into()
     } else {
@@ -442,13 +965,149 @@ fn noise_suppression_section<'a>(channel: &'a MixerChannel) -> Element<'a, Messa
         .into()
     };
 
+    // Lit while the gate is open, like a talk light
+    let talking = text("Talking")
+        .size(TEXT_CAPTION)
+        .color(if ns_enabled && channel.talking {
+            PRIMARY
+        } else {
+            TEXT_DIM.scale_alpha(0.4)
+        });
+
     column![
-        row![section_title, Space::new().width(Fill), ns_toggle,].align_y(Alignment::Center),
+        row![
+            section_title,
+            Space::new().width(Fill),
+            talking,
+            Space::new().width(SPACING_SM),
+            ns_toggle,
+        ]
+        .align_y(Alignment::Center),
         vad_section,
     ]
     .into()
 }
 
+/// Push-to-talk section for input channels.
+fn talk_section<'a>(channel: &'a MixerChannel) -> Element<'a, Message> {
+    let id = channel.id;
+    let settings = channel.talk;
+
+    let section_title = text("Push to Talk").size(TEXT_SMALL).color(TEXT_DIM);
+
+    let mode_picker = pick_list(TalkMode::ALL, Some(settings.mode), move |mode| {
+        Message::ChannelTalkModeSelected(id, mode)
+    })
+    .text_size(TEXT_SMALL)
+    .padding([SPACING_XS, SPACING_SM])
+    .style(|_theme: &Theme, _status| pick_list::Style {
+        text_color: TEXT,
+        placeholder_color: TEXT_DIM,
+        handle_color: TEXT_DIM,
+        background: Background::Color(SURFACE_LIGHT),
+        border: Border::default()
+            .rounded(RADIUS_SM)
+            .color(SOOTMIX_DARK.border_default)
+            .width(1.0),
+    });
+
+    // Lit while the mic is heard, like an on-air light
+    let live = text("Live").size(TEXT_CAPTION).color(if channel.live {
+        PRIMARY
+    } else {
+        TEXT_DIM.scale_alpha(0.4)
+    });
+
+    let header = row![
+        section_title,
+        Space::new().width(Fill),
+        live,
+        Space::new().width(SPACING_SM),
+        mode_picker,
+    ]
+    .align_y(Alignment::Center);
+
+    if settings.mode == TalkMode::Off {
+        return header.into();
+    }
+
+    let setting = |label: &'a str,
+                   range: std::ops::RangeInclusive<f64>,
+                   current: f64,
+                   step: f64,
+                   apply: fn(&mut TalkInfo, f64)| {
+        column![
+            row![
+                text(label).size(TEXT_SMALL).color(TEXT_DIM),
+                Space::new().width(Fill),
+                text(format!("{:.0} ms", current))
+                    .size(TEXT_SMALL)
+                    .color(TEXT),
+            ]
+            .align_y(Alignment::Center),
+            Space::new().height(SPACING_XS),
+            slider(range, current, move |v| {
+                let mut settings = settings;
+                apply(&mut settings, v);
+                Message::ChannelTalkChanged(id, settings)
+            })
+            .on_release(Message::ChannelTalkReleased(id))
+            .step(step)
+            .width(Length::Fill)
+            .style(balance_slider_style),
+        ]
+    };
+
+    // Held with the mouse like the hotkey: pressed on press, released on release
+    let key_label = if settings.mode == TalkMode::PushToMute {
+        "Hold to Mute"
+    } else {
+        "Hold to Talk"
+    };
+    let key = mouse_area(
+        container(text(key_label).size(TEXT_SMALL).color(TEXT))
+            .center_x(Fill)
+            .padding([SPACING_XS, SPACING_SM])
+            .style(|_| container::Style {
+                background: Some(Background::Color(SURFACE_LIGHT)),
+                border: Border::default()
+                    .rounded(RADIUS_SM)
+                    .color(SOOTMIX_DARK.border_default)
+                    .width(1.0),
+                ..container::Style::default()
+            }),
+    )
+    .on_press(Message::ChannelTalkKey(id, true))
+    .on_release(Message::ChannelTalkKey(id, false));
+
+    column![
+        header,
+        Space::new().height(SPACING_SM),
+        setting(
+            "Release",
+            0.0..=TALK_MAX_RELEASE_MS,
+            settings.release_ms,
+            10.0,
+            |s, v| s.release_ms = v,
+        ),
+        Space::new().height(SPACING_SM),
+        setting(
+            "Fade",
+            0.0..=TALK_MAX_FADE_MS,
+            settings.fade_ms,
+            5.0,
+            |s, v| s.fade_ms = v,
+        ),
+        Space::new().height(SPACING_SM),
+        key,
+        Space::new().height(SPACING_XS),
+        text("Bind the global key in the desktop's shortcut settings")
+            .size(TEXT_CAPTION)
+            .color(TEXT_DIM),
+    ]
+    .into()
+}
+
 /// Plugin chain section with expanded plugin list.
 fn plugin_chain_section<'a>(
     channel_id: Uuid,
@@ -9,14 +9,24 @@
 //! - Four-zone gradient coloring (green/yellow/orange/red)
 //! - Peak hold indicators with color-coded warnings
 //! - Smooth visual transitions
+//!
+//! Also holds the EBU R128 loudness readout, fed by the daemon.
 
 #![allow(dead_code)]
 
 use crate::message::Message;
-use crate::state::MeterDisplayState;
-use crate::ui::theme::{METER_BACKGROUND, METER_GREEN, METER_ORANGE, METER_RED, METER_YELLOW};
+use crate::state::{LoudnessData, MeterDisplayState};
+use crate::ui::theme::{
+    METER_BACKGROUND, METER_GREEN, METER_ORANGE, METER_RED, METER_YELLOW, RADIUS_SM, SOOTMIX_DARK,
+    SPACING_SM, SPACING_XS, SURFACE_LIGHT, TEXT, TEXT_CAPTION, TEXT_DIM,
+};
 use iced::widget::canvas::{self, Frame, Geometry, Path};
-use iced::{mouse, Color, Element, Length, Point, Rectangle, Renderer, Size, Theme};
+use iced::widget::{button, container, text, tooltip};
+use iced::{
+    mouse, Background, Border, Color, Element, Length, Point, Rectangle, Renderer, Size, Theme,
+};
+use sootmix_ipc::LOUDNESS_FLOOR_LUFS;
+use uuid::Uuid;
 
 // ============================================================================
 // METER DIMENSIONS
@@ -329,3 +339,86 @@ pub fn vu_meter_silent<'a>(height: f32) -> Element<'a, Message> {
         .height(Length::Fixed(height))
         .into()
 }
+
+// ============================================================================
+// LOUDNESS
+// ============================================================================
+
+/// Short-term loudness above which the readout turns red (streaming
+/// platforms normalize to around -14 LUFS).
+const LOUDNESS_HOT_LUFS: f64 = -9.0;
+
+/// True peak above which the readout turns red (EBU R128 allows -1 dBTP).
+const TRUE_PEAK_LIMIT_DBTP: f64 = -1.0;
+
+/// Format a loudness reading, showing silence as "-inf".
+pub fn format_lufs(lufs: f64) -> String {
+    if lufs <= LOUDNESS_FLOOR_LUFS {
+        "-inf".to_string()
+    } else {
+        format!("{:.1}", lufs)
+    }
+}
+
+/// Color for a loudness reading: red when too loud or clipping.
+pub fn loudness_color(loudness: &LoudnessData) -> Color {
+    if loudness.true_peak_dbtp > TRUE_PEAK_LIMIT_DBTP
+        || loudness.short_term_lufs > LOUDNESS_HOT_LUFS
+    {
+        METER_RED
+    } else {
+        TEXT
+    }
+}
+
+/// Compact short-term loudness readout for a strip.
+///
+/// Hovering shows all readings; clicking restarts the integrated loudness,
+/// loudness range and true peak of `channel_id` (the master output for
+/// `None`).
+pub fn loudness_readout<'a>(
+    loudness: &LoudnessData,
+    channel_id: Option<Uuid>,
+) -> Element<'a, Message> {
+    let label = button(
+        text(format!("{} LUFS", format_lufs(loudness.short_term_lufs)))
+            .size(TEXT_CAPTION)
+            .color(loudness_color(loudness)),
+    )
+    .padding([0.0, SPACING_XS])
+    .style(|_theme: &Theme, status| button::Style {
+        background: matches!(status, button::Status::Hovered | button::Status::Pressed)
+            .then_some(Background::Color(SURFACE_LIGHT)),
+        text_color: TEXT,
+        border: Border::default().rounded(RADIUS_SM),
+        ..button::Style::default()
+    })
+    .on_press(Message::ResetLoudness(channel_id));
+
+    let detail = format!(
+        "Momentary: {} LUFS\nShort-term: {} LUFS\nIntegrated: {} LUFS\n\
+         Range: {:.1} LU\nTrue peak: {:.1} dBTP\nClick to reset",
+        format_lufs(loudness.momentary_lufs),
+        format_lufs(loudness.short_term_lufs),
+        format_lufs(loudness.integrated_lufs),
+        loudness.range_lu,
+        loudness.true_peak_dbtp
+    );
+
+    tooltip(
+        label,
+        container(text(detail).size(TEXT_CAPTION).color(TEXT))
+            .padding([SPACING_XS, SPACING_SM])
+            .style(|_theme: &Theme| container::Style {
+                background: Some(Background::Color(SOOTMIX_DARK.surface_overlay)),
+                border: Border::default()
+                    .rounded(RADIUS_SM)
+                    .color(SOOTMIX_DARK.border_default)
+                    .width(1.0),
+                ..container::Style::default()
+            }),
+        tooltip::Position::Top,
+    )
+    .gap(4)
+    .into()
+}
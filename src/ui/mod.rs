@@ -6,6 +6,7 @@
 
 pub mod apps_panel;
 pub mod channel_strip;
+pub mod eq_editor;
 pub mod focus_panel;
 pub mod layout_drafts;
 pub mod meter;
@@ -13,6 +14,7 @@ pub mod plugin_chain;
 pub mod plugin_downloader;
 pub mod routing_rules_panel;
 pub mod settings_panel;
+pub mod spectrum;
 pub mod theme;
 
 pub use plugin_downloader::plugin_downloader;
@@ -13,7 +13,9 @@
 use crate::message::Message;
 use crate::plugins::{PluginMetadata, PluginType};
 use crate::ui::theme::*;
-use iced::widget::{button, column, container, row, scrollable, slider, text, text_input, Space};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, slider, text, text_input, Space,
+};
 use iced::{Alignment, Background, Border, Color, Element, Length, Theme};
 use sootmix_plugin_api;
 use uuid::Uuid;
@@ -583,14 +585,35 @@ pub struct PluginEditorParam {
     pub min: f32,
     /// Maximum value (display range).
     pub max: f32,
+    /// Step size (display range, 0.0 for continuous).
+    pub step: f32,
     /// Current value (display range, denormalized).
     pub value: f32,
+    /// Current value as formatted by the plugin.
+    pub display: String,
+    /// Named values; shown as a dropdown for discrete parameters.
+    pub choices: Vec<ParameterChoice>,
     /// Parameter curve for normalization.
     pub curve: sootmix_plugin_api::ParameterCurve,
     /// Text being actively edited (None = show formatted value).
     pub editing_text: Option<String>,
 }
 
+/// A named parameter value offered in the plugin editor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterChoice {
+    /// Normalized value (0.0-1.0).
+    pub value: f32,
+    /// Label reported by the plugin.
+    pub label: String,
+}
+
+impl std::fmt::Display for ParameterChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
 /// Create the plugin editor panel showing plugin parameters.
 pub fn plugin_editor(
     instance_id: Uuid,
@@ -682,10 +705,38 @@ fn parameter_slider(instance_id: Uuid, param: PluginEditorParam) -> Element<'sta
     let param_index = param.index;
     let unit = param.unit.clone();
 
-    // Text shown in the input: editing text if active, otherwise formatted value
-    let input_value = param.editing_text.unwrap_or_else(|| format!("{:.2}", param.value));
-
     let name_text = text(param.name.clone()).size(TEXT_SMALL).color(TEXT);
+
+    // Discrete parameters with named values get a dropdown instead
+    if param.step > 0.0 && !param.choices.is_empty() {
+        let selected = param
+            .choices
+            .iter()
+            .find(|choice| choice.label == param.display)
+            .cloned();
+        let picker = pick_list(param.choices, selected, move |choice: ParameterChoice| {
+            Message::PluginParameterChanged(instance_id, param_index, choice.value)
+        })
+        .text_size(TEXT_CAPTION)
+        .padding([SPACING_XS, SPACING_SM])
+        .style(|_theme: &Theme, _status| pick_list::Style {
+            text_color: TEXT,
+            placeholder_color: TEXT_DIM,
+            handle_color: SOOTMIX_DARK.text_muted,
+            background: Background::Color(SURFACE),
+            border: Border::default()
+                .rounded(RADIUS_SM)
+                .color(SOOTMIX_DARK.border_subtle)
+                .width(1.0),
+        });
+
+        return row![name_text, Space::new().width(Length::Fill), picker]
+            .align_y(Alignment::Center)
+            .into();
+    }
+
+    // Text shown in the input: editing text if active, otherwise the plugin's text
+    let input_value = param.editing_text.unwrap_or(param.display);
     let unit_text = if unit.is_empty() {
         text(String::new()).size(0)
     } else {
@@ -719,7 +770,7 @@ fn parameter_slider(instance_id: Uuid, param: PluginEditorParam) -> Element<'sta
         let normalized = sootmix_plugin_api::normalize(v, p_min, p_max, curve);
         Message::PluginParameterChanged(instance_id, param_index, normalized)
     })
-    .step(0.01)
+    .step(if param.step > 0.0 { param.step } else { 0.01 })
     .width(Length::Fill)
     .style(|_theme: &Theme, _status| slider::Style {
         rail: slider::Rail {
@@ -5,11 +5,12 @@
 //! Routing rules panel UI component.
 //!
 //! Auto-routing rules allow automatic assignment of audio applications
-//! to mixer channels based on pattern matching.
+//! to mixer channels based on pattern matching against the app name, the
+//! binary, or any PipeWire node property.
 
-use crate::config::{MatchTarget, RoutingRulesConfig};
+use crate::config::{ConditionCombine, MatchTarget, PropertyCondition, RoutingRulesConfig};
 use crate::message::Message;
-use crate::state::EditingRule;
+use crate::state::{EditingCondition, EditingRule};
 use crate::ui::theme::*;
 use iced::widget::{
     button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Space,
@@ -143,14 +144,40 @@ fn rule_item<'a>(
         .size(TEXT_SMALL)
         .color(if rule.enabled { TEXT } else { TEXT_DIM });
 
-    let pattern_text = text(format!(
-        "{} {} \"{}\"",
-        rule.match_target.display_name(),
-        rule.match_type.type_name(),
-        truncate(rule.match_type.pattern(), 20)
-    ))
-    .size(TEXT_CAPTION)
-    .color(TEXT_DIM);
+    let mut summary = if rule.match_type.pattern().is_empty() {
+        String::new()
+    } else {
+        format!(
+            "{} {} \"{}\"",
+            rule.match_target.display_name(),
+            rule.match_type.type_name(),
+            truncate(rule.match_type.pattern(), 20)
+        )
+    };
+    if !rule.conditions.is_empty() {
+        let joiner = match rule.combine {
+            ConditionCombine::All => " and ",
+            ConditionCombine::Any => " or ",
+        };
+        let conditions = rule
+            .conditions
+            .iter()
+            .map(|c| {
+                format!(
+                    "{} {} \"{}\"",
+                    c.property,
+                    c.match_type.type_name(),
+                    truncate(c.match_type.pattern(), 20)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(joiner);
+        if !summary.is_empty() {
+            summary.push_str(joiner);
+        }
+        summary.push_str(&conditions);
+    }
+    let pattern_text = text(summary).size(TEXT_CAPTION).color(TEXT_DIM);
 
     let target_text = text(format!("\u{2192} {}", &rule.target_channel))
         .size(TEXT_SMALL)
@@ -375,6 +402,52 @@ fn rule_edit_form<'a>(edit: &'a EditingRule, channel_names: Vec<String>) -> Elem
             selection: PRIMARY,
         });
 
+    let combine_picker = pick_list(
+        ConditionCombine::all().to_vec(),
+        Some(edit.combine),
+        Message::RuleCombineChanged,
+    )
+    .text_size(TEXT_SMALL)
+    .padding([SPACING_XS, SPACING_SM])
+    .style(|_theme: &Theme, _status| pick_list::Style {
+        text_color: TEXT,
+        placeholder_color: TEXT_DIM,
+        handle_color: TEXT_DIM,
+        background: Background::Color(SURFACE_LIGHT),
+        border: Border::default()
+            .rounded(RADIUS_SM)
+            .color(SOOTMIX_DARK.border_default)
+            .width(1.0),
+    });
+
+    let add_condition_button = button(text("+ Property").size(TEXT_CAPTION))
+        .padding([SPACING_XS, SPACING_SM])
+        .style(|_theme: &Theme, status| {
+            let is_hovered = matches!(status, button::Status::Hovered | button::Status::Pressed);
+            button::Style {
+                background: Some(Background::Color(if is_hovered {
+                    SURFACE_LIGHT
+                } else {
+                    SURFACE
+                })),
+                text_color: TEXT,
+                border: Border::default()
+                    .rounded(RADIUS_SM)
+                    .color(SOOTMIX_DARK.border_default)
+                    .width(1.0),
+                ..button::Style::default()
+            }
+        })
+        .on_press(Message::AddRuleCondition);
+
+    let conditions = edit
+        .conditions
+        .iter()
+        .enumerate()
+        .fold(column![].spacing(SPACING_XS), |col, (index, condition)| {
+            col.push(condition_row(index, condition))
+        });
+
     let channel_picker = pick_list(
         channel_names,
         if edit.target_channel.is_empty() {
@@ -469,6 +542,15 @@ fn rule_edit_form<'a>(edit: &'a EditingRule, channel_names: Vec<String>) -> Elem
         .align_y(Alignment::Center)
         .spacing(SPACING_SM),
         Space::new().height(SPACING_SM),
+        row![
+            text("Properties:").size(TEXT_SMALL).color(TEXT_DIM).width(70),
+            combine_picker,
+            add_condition_button,
+        ]
+        .align_y(Alignment::Center)
+        .spacing(SPACING_SM),
+        conditions,
+        Space::new().height(SPACING_SM),
         row![
             text("Route to:").size(TEXT_SMALL).color(TEXT_DIM).width(70),
             channel_picker,
@@ -502,6 +584,116 @@ fn rule_edit_form<'a>(edit: &'a EditingRule, channel_names: Vec<String>) -> Elem
         .into()
 }
 
+/// Create the editor row for one property condition.
+fn condition_row<'a>(index: usize, condition: &'a EditingCondition) -> Element<'a, Message> {
+    let property_picker = pick_list(
+        PropertyCondition::COMMON_PROPERTIES
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>(),
+        None::<String>,
+        move |property| Message::RuleConditionPropertyChanged(index, property),
+    )
+    .placeholder("Common...")
+    .text_size(TEXT_CAPTION)
+    .padding([SPACING_XS, SPACING_SM])
+    .style(|_theme: &Theme, _status| pick_list::Style {
+        text_color: TEXT,
+        placeholder_color: TEXT_DIM,
+        handle_color: TEXT_DIM,
+        background: Background::Color(SURFACE_LIGHT),
+        border: Border::default()
+            .rounded(RADIUS_SM)
+            .color(SOOTMIX_DARK.border_default)
+            .width(1.0),
+    });
+
+    let property_input = text_input("property.key", &condition.property)
+        .on_input(move |property| Message::RuleConditionPropertyChanged(index, property))
+        .padding([SPACING_XS, SPACING_SM])
+        .size(TEXT_SMALL)
+        .width(180)
+        .style(|_theme: &Theme, _status| text_input::Style {
+            background: Background::Color(SURFACE_LIGHT),
+            border: Border::default()
+                .rounded(RADIUS_SM)
+                .color(SOOTMIX_DARK.border_default)
+                .width(1.0),
+            icon: TEXT,
+            placeholder: TEXT_DIM,
+            value: TEXT,
+            selection: PRIMARY,
+        });
+
+    let match_type_picker = pick_list(
+        vec![
+            "contains".to_string(),
+            "exact".to_string(),
+            "regex".to_string(),
+            "glob".to_string(),
+        ],
+        Some(condition.match_type_name.clone()),
+        move |match_type| Message::RuleConditionMatchTypeChanged(index, match_type),
+    )
+    .text_size(TEXT_SMALL)
+    .padding([SPACING_XS, SPACING_SM])
+    .style(|_theme: &Theme, _status| pick_list::Style {
+        text_color: TEXT,
+        placeholder_color: TEXT_DIM,
+        handle_color: TEXT_DIM,
+        background: Background::Color(SURFACE_LIGHT),
+        border: Border::default()
+            .rounded(RADIUS_SM)
+            .color(SOOTMIX_DARK.border_default)
+            .width(1.0),
+    });
+
+    let pattern_input = text_input("Pattern...", &condition.pattern)
+        .on_input(move |pattern| Message::RuleConditionPatternChanged(index, pattern))
+        .padding([SPACING_XS, SPACING_SM])
+        .size(TEXT_SMALL)
+        .style(|_theme: &Theme, _status| text_input::Style {
+            background: Background::Color(SURFACE_LIGHT),
+            border: Border::default()
+                .rounded(RADIUS_SM)
+                .color(SOOTMIX_DARK.border_default)
+                .width(1.0),
+            icon: TEXT,
+            placeholder: TEXT_DIM,
+            value: TEXT,
+            selection: PRIMARY,
+        });
+
+    let remove_button = button(text("\u{00D7}").size(TEXT_CAPTION))
+        .padding([SPACING_XS, SPACING_SM])
+        .style(|_theme: &Theme, status| {
+            let is_hovered = matches!(status, button::Status::Hovered | button::Status::Pressed);
+            button::Style {
+                background: Some(Background::Color(if is_hovered {
+                    MUTED_COLOR
+                } else {
+                    Color { a: 0.15, ..MUTED_COLOR }
+                })),
+                text_color: if is_hovered { TEXT } else { MUTED_COLOR },
+                border: Border::default().rounded(RADIUS_SM),
+                ..button::Style::default()
+            }
+        })
+        .on_press(Message::RemoveRuleCondition(index));
+
+    row![
+        Space::new().width(70),
+        property_picker,
+        property_input,
+        match_type_picker,
+        pattern_input,
+        remove_button,
+    ]
+    .align_y(Alignment::Center)
+    .spacing(SPACING_SM)
+    .into()
+}
+
 // ============================================================================
 // HELPERS
 // ============================================================================
@@ -8,7 +8,7 @@
 
 use crate::audio::types::OutputDevice;
 use crate::message::Message;
-use crate::state::{ReportStatus, UpdateStatus};
+use crate::state::{ReportStatus, SoloMode, UpdateStatus};
 use crate::ui::theme::*;
 use iced::widget::{button, checkbox, column, container, pick_list, row, text, Space};
 use iced::{Alignment, Background, Border, Color, Element, Fill, Length, Theme};
@@ -20,6 +20,7 @@ pub fn settings_panel<'a>(
     daemon_action_pending: bool,
     available_outputs: &[OutputDevice],
     monitor_device: Option<&str>,
+    solo_mode: SoloMode,
     report_status: &ReportStatus,
     update_status: &UpdateStatus,
 ) -> Element<'a, Message> {
@@ -168,7 +169,7 @@ pub fn settings_panel<'a>(
             ..container::Style::default()
         });
 
-    let monitor_label = text("Monitor Output (PFL)")
+    let monitor_label = text("Monitor Output")
         .size(TEXT_BODY)
         .color(TEXT);
 
@@ -208,6 +209,33 @@ pub fn settings_panel<'a>(
             .width(1.0),
     });
 
+    let solo_mode_picker = pick_list(SoloMode::ALL, Some(solo_mode), Message::SoloModeChanged)
+        .text_size(TEXT_SMALL)
+        .padding([SPACING_XS, SPACING_SM])
+        .style(|_theme: &Theme, _status| pick_list::Style {
+            text_color: TEXT,
+            placeholder_color: TEXT_DIM,
+            handle_color: SOOTMIX_DARK.text_muted,
+            background: Background::Color(SURFACE),
+            border: Border::default()
+                .rounded(RADIUS_SM)
+                .color(SOOTMIX_DARK.border_default)
+                .width(1.0),
+        });
+
+    let solo_mode_row = row![
+        column![
+            text("Solo mode").size(TEXT_SMALL).color(TEXT),
+            text("PFL listens on the monitor; in-place cuts the other outputs")
+                .size(TEXT_SMALL)
+                .color(TEXT_DIM),
+        ]
+        .spacing(SPACING_XS),
+        Space::new().width(Fill),
+        solo_mode_picker,
+    ]
+    .align_y(Alignment::Center);
+
     // --- Diagnostics section ---
     let diag_divider = container(Space::new().height(1))
         .width(Length::Fill)
@@ -312,6 +340,8 @@ pub fn settings_panel<'a>(
         Space::new().height(SPACING_SM),
         monitor_picker,
         Space::new().height(SPACING_SM),
+        solo_mode_row,
+        Space::new().height(SPACING_SM),
         diag_divider,
         Space::new().height(SPACING_SM),
         diag_label,
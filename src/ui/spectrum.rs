@@ -0,0 +1,174 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Spectrum analyzer display.
+//!
+//! Draws the band levels the daemon sends for the analyzed channel on a
+//! log-frequency axis, with the held peaks as a line on top and the
+//! channel's EQ curve overlaid on its own ±dB scale.
+
+use crate::message::Message;
+use crate::ui::theme::{
+    ACCENT, METER_BACKGROUND, PRIMARY, SOOTMIX_DARK, TEXT, TEXT_CAPTION, TEXT_DIM,
+};
+use iced::widget::canvas::{self, Frame, Geometry, Path, Stroke};
+use iced::{mouse, Element, Length, Point, Rectangle, Renderer, Size, Theme};
+use sootmix_ipc::{SpectrumData, SPECTRUM_MAX_HZ, SPECTRUM_MIN_HZ};
+
+/// Height of the spectrum display in pixels.
+pub const SPECTRUM_HEIGHT: f32 = 120.0;
+
+/// Level at the bottom of the display.
+const DISPLAY_FLOOR_DB: f32 = -90.0;
+
+/// Level at the top of the display.
+const DISPLAY_CEILING_DB: f32 = 0.0;
+
+/// EQ gain at the top of the display (the bottom is its negative).
+const EQ_RANGE_DB: f32 = 18.0;
+
+/// Frequencies marked on the grid, with their labels.
+const GRID_FREQUENCIES: [(f64, &str); 3] = [(100.0, "100"), (1000.0, "1k"), (10_000.0, "10k")];
+
+/// Levels marked on the grid.
+const GRID_LEVELS_DB: [f32; 2] = [-30.0, -60.0];
+
+/// Canvas program drawing one spectrum.
+#[derive(Debug, Clone)]
+pub struct SpectrumGraph<'a> {
+    data: Option<&'a SpectrumData>,
+}
+
+impl<Message> canvas::Program<Message> for SpectrumGraph<'_> {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let size = bounds.size();
+
+        frame.fill(&Path::rectangle(Point::ORIGIN, size), METER_BACKGROUND);
+        draw_grid(&mut frame, size);
+
+        if let Some(data) = self.data {
+            let level_y = |db: f64| {
+                let t = (db as f32 - DISPLAY_FLOOR_DB) / (DISPLAY_CEILING_DB - DISPLAY_FLOOR_DB);
+                size.height * (1.0 - t.clamp(0.0, 1.0))
+            };
+
+            let levels = band_points(&data.levels_db, size.width, level_y);
+            if let (Some(first), Some(last)) = (levels.first(), levels.last()) {
+                let fill = Path::new(|b| {
+                    b.move_to(Point::new(first.x, size.height));
+                    for point in &levels {
+                        b.line_to(*point);
+                    }
+                    b.line_to(Point::new(last.x, size.height));
+                    b.close();
+                });
+                frame.fill(&fill, PRIMARY.scale_alpha(0.5));
+            }
+
+            let peaks = band_points(&data.peaks_db, size.width, level_y);
+            frame.stroke(
+                &polyline(&peaks),
+                Stroke::default()
+                    .with_color(TEXT.scale_alpha(0.6))
+                    .with_width(1.0),
+            );
+
+            let eq_y = |db: f64| {
+                let t = (db as f32 / EQ_RANGE_DB).clamp(-1.0, 1.0);
+                size.height * 0.5 * (1.0 - t)
+            };
+            let eq = band_points(&data.eq_db, size.width, eq_y);
+            frame.stroke(
+                &polyline(&eq),
+                Stroke::default().with_color(ACCENT).with_width(1.5),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Horizontal position of `freq` on the log-frequency axis.
+fn frequency_x(freq: f64, width: f32) -> f32 {
+    let t = (freq / SPECTRUM_MIN_HZ).ln() / (SPECTRUM_MAX_HZ / SPECTRUM_MIN_HZ).ln();
+    width * t as f32
+}
+
+/// One point per band, spread evenly across `width` (the bands are already
+/// log-spaced).
+fn band_points(values: &[f64], width: f32, y: impl Fn(f64) -> f32) -> Vec<Point> {
+    let last = values.len().saturating_sub(1).max(1) as f32;
+    values
+        .iter()
+        .enumerate()
+        .map(|(band, value)| Point::new(width * band as f32 / last, y(*value)))
+        .collect()
+}
+
+fn polyline(points: &[Point]) -> Path {
+    Path::new(|b| {
+        let mut points = points.iter();
+        if let Some(first) = points.next() {
+            b.move_to(*first);
+        }
+        for point in points {
+            b.line_to(*point);
+        }
+    })
+}
+
+fn draw_grid(frame: &mut Frame, size: Size) {
+    let grid = Stroke::default()
+        .with_color(SOOTMIX_DARK.border_subtle)
+        .with_width(1.0);
+
+    for (freq, label) in GRID_FREQUENCIES {
+        let x = frequency_x(freq, size.width);
+        frame.stroke(
+            &Path::line(Point::new(x, 0.0), Point::new(x, size.height)),
+            grid,
+        );
+        frame.fill_text(canvas::Text {
+            content: label.to_string(),
+            position: Point::new(x + 2.0, size.height - TEXT_CAPTION - 2.0),
+            color: TEXT_DIM,
+            size: TEXT_CAPTION.into(),
+            ..canvas::Text::default()
+        });
+    }
+
+    for db in GRID_LEVELS_DB {
+        let t = (db - DISPLAY_FLOOR_DB) / (DISPLAY_CEILING_DB - DISPLAY_FLOOR_DB);
+        let y = size.height * (1.0 - t);
+        frame.stroke(
+            &Path::line(Point::new(0.0, y), Point::new(size.width, y)),
+            grid,
+        );
+    }
+
+    // 0 dB of the EQ overlay
+    let middle = size.height * 0.5;
+    frame.stroke(
+        &Path::line(Point::new(0.0, middle), Point::new(size.width, middle)),
+        grid.with_color(ACCENT.scale_alpha(0.4)),
+    );
+}
+
+/// Create a spectrum display, empty until the first analysis arrives.
+pub fn spectrum_graph(data: Option<&SpectrumData>) -> Element<'_, Message> {
+    canvas::Canvas::new(SpectrumGraph { data })
+        .width(Length::Fill)
+        .height(Length::Fixed(SPECTRUM_HEIGHT))
+        .into()
+}